@@ -21,6 +21,7 @@ use super::producer_and_consumer_group_benchmark::ProducerAndConsumerGroupBenchm
 use super::producer_benchmark::ProducerBenchmark;
 use super::producing_consumer_benchmark::EndToEndProducingConsumerBenchmark;
 use super::producing_consumer_group_benchmark::EndToEndProducingConsumerGroupBenchmark;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 
 pub type BenchmarkFutures = Result<
     Vec<Pin<Box<dyn Future<Output = Result<BenchmarkIndividualMetrics, IggyError>> + Send>>>,
@@ -105,8 +106,10 @@ pub trait Benchmarkable {
                         CompressionAlgorithm::default(),
                         None,
                         None,
+                        None,
                         IggyExpiry::NeverExpire,
                         max_topic_size,
+                        CleanupPolicy::default(),
                     )
                     .await?;
             }
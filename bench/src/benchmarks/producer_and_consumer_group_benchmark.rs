@@ -6,6 +6,7 @@ use crate::{
     rate_limiter::RateLimiter,
 };
 use async_trait::async_trait;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::{
     client::ConsumerGroupClient, clients::client::IggyClient, error::IggyError,
     messages::poll_messages::PollingKind,
@@ -50,6 +51,7 @@ impl ProducerAndConsumerGroupBenchmark {
                     &topic_id.try_into().unwrap(),
                     &consumer_group_name,
                     Some(consumer_group_id),
+                    PartitionAssignmentStrategy::default(),
                 )
                 .await;
             if cg.is_err() {
@@ -6,6 +6,7 @@ use crate::rate_limiter::RateLimiter;
 use async_trait::async_trait;
 use iggy::client::ConsumerGroupClient;
 use iggy::clients::client::IggyClient;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::error::IggyError;
 use iggy::messages::poll_messages::PollingKind;
 use iggy_bench_report::benchmark_kind::BenchmarkKind;
@@ -49,6 +50,7 @@ impl EndToEndProducingConsumerGroupBenchmark {
                     &topic_id.try_into().unwrap(),
                     &consumer_group_name,
                     Some(consumer_group_id),
+                    PartitionAssignmentStrategy::default(),
                 )
                 .await;
             if cg.is_err() {
@@ -13,6 +13,8 @@ pub fn create_client_factory(args: &IggyBenchArgs) -> Arc<dyn ClientFactory> {
         Transport::Tcp => Arc::new(TcpClientFactory {
             server_addr: args.server_address().to_owned(),
             nodelay: args.nodelay(),
+            zero_copy: false,
+            pipelining: false,
         }),
         Transport::Quic => Arc::new(QuicClientFactory {
             server_addr: args.server_address().to_owned(),
@@ -0,0 +1,75 @@
+use clap::{Args, Subcommand};
+use iggy::archives::restore_archive::ArchiveRange;
+use iggy::identifier::Identifier;
+
+#[derive(Debug, Clone, Subcommand)]
+pub(crate) enum ArchiveAction {
+    /// Restore stream, topic or partition from the archive
+    ///
+    /// Restores the archived segments preceding the messages stored locally.
+    /// Stream or topic which does not exist is recreated from the archived state.
+    /// Stream ID can be specified as a stream name or ID
+    /// Topic ID can be specified as a topic name or ID
+    ///
+    /// Examples
+    ///  iggy archive restore prod
+    ///  iggy archive restore prod sensor
+    ///  iggy archive restore 1 2 3 --from-offset 1000 --to-offset 2000
+    ///  iggy archive restore prod --state-snapshot 1700000000000000_state
+    #[clap(verbatim_doc_comment, visible_alias = "r")]
+    Restore(ArchiveRestoreArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ArchiveRestoreArgs {
+    /// Stream ID to restore
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) stream_id: Identifier,
+    /// Topic ID to restore, all topics of the stream are restored if not specified
+    ///
+    /// Topic ID can be specified as a topic name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) topic_id: Option<Identifier>,
+    /// Partition ID to restore, all partitions of the topic are restored if not specified
+    #[arg(value_parser = clap::value_parser!(u32).range(1..), requires = "topic_id")]
+    pub(crate) partition_id: Option<u32>,
+    /// Restore only segments with messages starting from the offset
+    #[arg(long, conflicts_with_all = ["from_timestamp", "to_timestamp"])]
+    pub(crate) from_offset: Option<u64>,
+    /// Restore only segments with messages up to the offset
+    #[arg(long, conflicts_with_all = ["from_timestamp", "to_timestamp"])]
+    pub(crate) to_offset: Option<u64>,
+    /// Restore only segments with messages starting from the timestamp (in microseconds)
+    #[arg(long)]
+    pub(crate) from_timestamp: Option<u64>,
+    /// Restore only segments with messages up to the timestamp (in microseconds)
+    #[arg(long)]
+    pub(crate) to_timestamp: Option<u64>,
+    /// Name of the archived state snapshot used to recreate stream or topic
+    ///
+    /// Latest archived state is used if not specified
+    #[arg(short, long)]
+    pub(crate) state_snapshot: Option<String>,
+}
+
+impl ArchiveRestoreArgs {
+    pub(crate) fn range(&self) -> ArchiveRange {
+        if self.from_offset.is_some() || self.to_offset.is_some() {
+            return ArchiveRange::Offsets {
+                from: self.from_offset.unwrap_or(0),
+                to: self.to_offset.unwrap_or(u64::MAX),
+            };
+        }
+
+        if self.from_timestamp.is_some() || self.to_timestamp.is_some() {
+            return ArchiveRange::Timestamps {
+                from: self.from_timestamp.unwrap_or(0).into(),
+                to: self.to_timestamp.unwrap_or(u64::MAX).into(),
+            };
+        }
+
+        ArchiveRange::All
+    }
+}
@@ -1,12 +1,15 @@
-use clap::ValueEnum;
+use clap::{Args, ValueEnum};
 use iggy::cli::client::get_clients::GetClientsOutput;
 use iggy::cli::consumer_group::get_consumer_groups::GetConsumerGroupsOutput;
 use iggy::cli::context::get_contexts::GetContextsOutput;
 use iggy::cli::personal_access_tokens::get_personal_access_tokens::GetPersonalAccessTokensOutput;
+use iggy::cli::roles::get_roles::GetRolesOutput;
 use iggy::cli::streams::get_streams::GetStreamsOutput;
 use iggy::cli::system::stats::GetStatsOutput;
 use iggy::cli::topics::get_topics::GetTopicsOutput;
 use iggy::cli::users::get_users::GetUsersOutput;
+use iggy::models::quota::Quota;
+use iggy::utils::byte_size::IggyByteSize;
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub(crate) enum ListMode {
@@ -50,6 +53,15 @@ impl From<ListMode> for GetUsersOutput {
     }
 }
 
+impl From<ListMode> for GetRolesOutput {
+    fn from(mode: ListMode) -> Self {
+        match mode {
+            ListMode::Table => GetRolesOutput::Table,
+            ListMode::List => GetRolesOutput::List,
+        }
+    }
+}
+
 impl From<ListMode> for GetClientsOutput {
     fn from(mode: ListMode) -> Self {
         match mode {
@@ -95,3 +107,44 @@ impl From<ListModeExt> for GetStatsOutput {
         }
     }
 }
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct QuotaArgs {
+    /// Max size of the stored messages in human-readable format like "15GB"
+    ///
+    /// Skipping parameter leaves the size unlimited
+    #[arg(long, verbatim_doc_comment)]
+    pub(crate) max_size: Option<IggyByteSize>,
+    /// Max number of the topics, skipping parameter leaves it unlimited
+    #[arg(long)]
+    pub(crate) max_topics: Option<u32>,
+    /// Max number of the partitions, skipping parameter leaves it unlimited
+    #[arg(long)]
+    pub(crate) max_partitions: Option<u32>,
+    /// Max produce throughput per second in human-readable format like "10MB"
+    ///
+    /// Skipping parameter leaves the produce throughput unlimited
+    #[arg(long, verbatim_doc_comment)]
+    pub(crate) max_produce_rate: Option<IggyByteSize>,
+    /// Max consume throughput per second in human-readable format like "10MB"
+    ///
+    /// Skipping parameter leaves the consume throughput unlimited
+    #[arg(long, verbatim_doc_comment)]
+    pub(crate) max_consume_rate: Option<IggyByteSize>,
+}
+
+impl From<&QuotaArgs> for Quota {
+    fn from(args: &QuotaArgs) -> Self {
+        Quota {
+            max_size: args.max_size.map_or(0, |size| size.as_bytes_u64()),
+            max_topics: args.max_topics.unwrap_or_default(),
+            max_partitions: args.max_partitions.unwrap_or_default(),
+            max_produce_bytes_per_second: args
+                .max_produce_rate
+                .map_or(0, |rate| rate.as_bytes_u64()),
+            max_consume_bytes_per_second: args
+                .max_consume_rate
+                .map_or(0, |rate| rate.as_bytes_u64()),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::args::common::ListMode;
 use clap::{Args, Subcommand};
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::identifier::Identifier;
 
 #[derive(Debug, Clone, Subcommand)]
@@ -82,6 +83,11 @@ pub(crate) struct ConsumerGroupCreateArgs {
     pub(crate) group_id: Option<u32>,
     /// Consumer group name to create
     pub(crate) name: String,
+    /// Partition assignment strategy for the consumer group
+    ///
+    /// One of "round_robin", "range", "sticky" or "cooperative".
+    #[arg(short = 's', long, default_value_t = PartitionAssignmentStrategy::default(), value_parser = clap::value_parser!(PartitionAssignmentStrategy), verbatim_doc_comment)]
+    pub(crate) assignment_strategy: PartitionAssignmentStrategy,
 }
 
 #[derive(Debug, Clone, Args)]
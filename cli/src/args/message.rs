@@ -3,6 +3,7 @@ use clap::{ArgGroup, Args, Subcommand};
 use iggy::error::IggyError;
 use iggy::error::IggyError::InvalidFormat;
 use iggy::identifier::Identifier;
+use iggy::messages::message_filter::MessageFilter;
 use iggy::models::header::{HeaderKey, HeaderValue};
 use std::str::FromStr;
 
@@ -171,6 +172,16 @@ pub(crate) struct PollMessagesArgs {
     #[clap(verbatim_doc_comment)]
     #[clap(short, long, default_value_t = Identifier::default(), value_parser = clap::value_parser!(Identifier))]
     pub(crate) consumer: Identifier,
+    /// Filter on the message headers evaluated by the server
+    ///
+    /// Only the messages matching all the conditions separated by ';' are returned,
+    /// the message count applies to the matching messages.
+    /// Supported conditions are: "exists:key", "eq:key:kind:value",
+    /// "in:key:kind:value1|value2" and "range:key:kind:min..max" (numeric kinds only).
+    /// Example: "eq:tenant:string:acme;range:priority:uint8:1..3"
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, value_parser = clap::value_parser!(MessageFilter))]
+    pub(crate) filter: Option<MessageFilter>,
     /// Include the message headers in the output
     ///
     /// Flag indicates whether to include headers in the output
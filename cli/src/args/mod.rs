@@ -10,6 +10,7 @@ use iggy::cli::context::common::ContextConfig;
 use system::SnapshotArgs;
 
 use crate::args::{
+    archive::ArchiveAction,
     client::ClientAction,
     consumer_group::ConsumerGroupAction,
     consumer_offset::ConsumerOffsetAction,
@@ -17,6 +18,7 @@ use crate::args::{
     message::MessageAction,
     partition::PartitionAction,
     personal_access_token::PersonalAccessTokenAction,
+    role::RoleAction,
     stream::StreamAction,
     system::{PingArgs, StatsArgs},
     topic::TopicAction,
@@ -27,6 +29,7 @@ use crate::args::system::LoginArgs;
 
 use self::user::UserAction;
 
+pub(crate) mod archive;
 pub(crate) mod client;
 pub(crate) mod common;
 pub(crate) mod consumer_group;
@@ -36,6 +39,7 @@ pub(crate) mod message;
 pub(crate) mod partition;
 pub(crate) mod permissions;
 pub(crate) mod personal_access_token;
+pub(crate) mod role;
 pub(crate) mod stream;
 pub(crate) mod system;
 pub(crate) mod topic;
@@ -147,6 +151,9 @@ pub(crate) enum Command {
     /// user operations
     #[command(subcommand, visible_alias = "u")]
     User(UserAction),
+    /// role operations
+    #[command(subcommand, visible_alias = "r")]
+    Role(RoleAction),
     /// client operations
     #[command(subcommand, visible_alias = "c")]
     Client(ClientAction),
@@ -159,6 +166,9 @@ pub(crate) enum Command {
     /// message operations
     #[command(subcommand, visible_alias = "m")]
     Message(MessageAction),
+    /// archive operations
+    #[command(subcommand, visible_alias = "a")]
+    Archive(ArchiveAction),
     /// context operations
     #[command(subcommand, visible_alias = "ctx")]
     Context(ContextAction),
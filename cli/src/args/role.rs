@@ -0,0 +1,203 @@
+use crate::args::common::ListMode;
+use crate::args::permissions::global::GlobalPermissionsArg;
+use crate::args::permissions::stream::StreamPermissionsArg;
+use clap::{Args, Subcommand};
+use iggy::identifier::Identifier;
+
+#[derive(Debug, Clone, Subcommand)]
+pub(crate) enum RoleAction {
+    /// Create role with given name and permissions
+    ///
+    /// Role is a reusable set of permissions, which are granted to all the users
+    /// the role is assigned to, in addition to their own permissions.
+    ///
+    /// Examples
+    ///  iggy role create auditors --global-permissions r_srv,r_usr,r_str,r_top
+    ///  iggy role create producers -s 1:s_msg
+    #[clap(verbatim_doc_comment, visible_alias = "c")]
+    Create(RoleCreateArgs),
+    /// Delete role with given ID
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    ///
+    /// Examples:
+    ///  iggy role delete 1
+    ///  iggy role delete auditors
+    #[clap(verbatim_doc_comment, visible_alias = "d")]
+    Delete(RoleDeleteArgs),
+    /// Get details of a single role with given ID
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    ///
+    /// Examples:
+    ///  iggy role get 1
+    ///  iggy role get auditors
+    #[clap(verbatim_doc_comment, visible_alias = "g")]
+    Get(RoleGetArgs),
+    /// List all roles
+    ///
+    /// Examples:
+    ///  iggy role list
+    ///  iggy role list --list-mode table
+    ///  iggy role list -l table
+    #[clap(verbatim_doc_comment, visible_alias = "l")]
+    List(RoleListArgs),
+    /// Change name for role with given ID
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    ///
+    /// Examples:
+    ///  iggy role name 1 reviewers
+    ///  iggy role name auditors reviewers
+    #[clap(verbatim_doc_comment, visible_alias = "n")]
+    Name(RoleNameArgs),
+    /// Set permissions for role with given ID
+    ///
+    /// The role ID can be specified as either a role name or an ID. Permissions
+    /// are configured based on the options provided with this command. If no
+    /// options are set, the role no longer grants any permissions.
+    /// The permissions are updated for all the users the role is assigned to.
+    ///
+    /// Examples:
+    ///  iggy role permissions 1 -g r_usr
+    ///  iggy role permissions auditors
+    #[clap(verbatim_doc_comment, visible_alias = "p")]
+    Permissions(RolePermissionsArgs),
+    /// Assign role with given ID to user with given ID
+    ///
+    /// The role ID can be specified as either a role name or an ID,
+    /// the user ID can be specified as either a username or an ID
+    ///
+    /// Examples:
+    ///  iggy role assign 1 2
+    ///  iggy role assign auditors testuser
+    #[clap(verbatim_doc_comment, visible_alias = "a")]
+    Assign(RoleUserArgs),
+    /// Unassign role with given ID from user with given ID
+    ///
+    /// The role ID can be specified as either a role name or an ID,
+    /// the user ID can be specified as either a username or an ID
+    ///
+    /// Examples:
+    ///  iggy role unassign 1 2
+    ///  iggy role unassign auditors testuser
+    #[clap(verbatim_doc_comment, visible_alias = "u")]
+    Unassign(RoleUserArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleCreateArgs {
+    /// Role name
+    ///
+    /// Unique name of the role, must be between 1 and 255 characters long.
+    #[clap(verbatim_doc_comment)]
+    pub(crate) name: String,
+    /// Set global permissions for created role
+    ///
+    /// All global permissions by default are set to false and this command line option
+    /// allows to set each permission individually. Permissions are separated
+    /// by comma and each permission is identified by the same name as in the iggy
+    /// SDK in iggy::models::permissions::GlobalPermissions struct. For each permission
+    /// there's long variant (same as in SDK) and short variant.
+    ///
+    /// Available permissions (long and short versions):  manage_servers / m_srv,
+    /// read_servers / r_srv, manage_users / m_usr, read_users / r_usr,
+    /// manage_streams / m_str, read_streams / r_str, manage_topics / m_top,
+    /// read_topics / r_top, poll_messages / p_msg, send_messages / s_msg
+    ///
+    /// Examples:
+    ///  iggy role create consumers --global-permissions p_msg
+    ///  iggy role create auditors -g r_srv,r_usr,r_str,r_top
+    #[clap(short, long, verbatim_doc_comment)]
+    #[arg(value_parser = clap::value_parser!(GlobalPermissionsArg))]
+    pub(crate) global_permissions: Option<GlobalPermissionsArg>,
+    /// Set stream permissions for created role
+    ///
+    /// Stream permissions use the same format as for the users, see the help
+    /// of the user create command for the details.
+    ///
+    /// Permissions format: STREAM_ID[:STREAM_PERMISSIONS][#TOPIC_ID[:TOPIC_PERMISSIONS]]
+    ///
+    /// Examples:
+    ///  iggy role create readers -s 1:read_stream,poll_messages
+    ///  iggy role create senders -s 3#1:s_msg#2:s_msg
+    #[clap(short, long, verbatim_doc_comment)]
+    #[arg(value_parser = clap::value_parser!(StreamPermissionsArg))]
+    pub(crate) stream_permissions: Option<Vec<StreamPermissionsArg>>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleDeleteArgs {
+    /// Role ID to delete
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    pub(crate) role_id: Identifier,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleGetArgs {
+    /// Role ID to get
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    pub(crate) role_id: Identifier,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleListArgs {
+    /// List mode (table or list)
+    #[clap(short, long, value_enum, default_value_t = ListMode::Table)]
+    pub(crate) list_mode: ListMode,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleNameArgs {
+    /// Role ID to update
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    pub(crate) role_id: Identifier,
+    /// New role name
+    ///
+    /// New and unique name of the role, must be between 1 and 255 characters long.
+    #[clap(verbatim_doc_comment)]
+    pub(crate) name: String,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RolePermissionsArgs {
+    /// Role ID to update
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    pub(crate) role_id: Identifier,
+    /// Set global permissions for the role
+    ///
+    /// Global permissions use the same format as for the role create command.
+    ///
+    /// Examples:
+    ///  iggy role permissions auditors -g r_srv,r_usr,r_str,r_top
+    #[clap(short, long, verbatim_doc_comment)]
+    #[arg(value_parser = clap::value_parser!(GlobalPermissionsArg))]
+    pub(crate) global_permissions: Option<GlobalPermissionsArg>,
+    /// Set stream permissions for the role
+    ///
+    /// Stream permissions use the same format as for the role create command.
+    ///
+    /// Permissions format: STREAM_ID[:STREAM_PERMISSIONS][#TOPIC_ID[:TOPIC_PERMISSIONS]]
+    ///
+    /// Examples:
+    ///  iggy role permissions readers -s 1:read_stream,poll_messages
+    #[clap(short, long, verbatim_doc_comment)]
+    #[arg(value_parser = clap::value_parser!(StreamPermissionsArg))]
+    pub(crate) stream_permissions: Option<Vec<StreamPermissionsArg>>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleUserArgs {
+    /// Role ID to assign or unassign
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    pub(crate) role_id: Identifier,
+    /// User ID to assign or unassign the role to or from
+    ///
+    /// The user ID can be specified as either a username or an ID
+    pub(crate) user_id: Identifier,
+}
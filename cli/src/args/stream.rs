@@ -1,4 +1,4 @@
-use crate::args::common::ListMode;
+use crate::args::common::{ListMode, QuotaArgs};
 use clap::{Args, Subcommand};
 use iggy::identifier::Identifier;
 
@@ -58,6 +58,16 @@ pub(crate) enum StreamAction {
     ///  iggy stream purge test
     #[clap(verbatim_doc_comment, visible_alias = "p")]
     Purge(StreamPurgeArgs),
+    /// Set quota for given stream ID
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    /// Limits which are not provided are unlimited, so skipping all of them removes the quota.
+    ///
+    /// Examples:
+    ///  iggy stream quota 1 --max-size 10GB --max-topics 5
+    ///  iggy stream quota test --max-produce-rate 1MB --max-consume-rate 5MB
+    #[clap(verbatim_doc_comment, visible_alias = "q")]
+    Quota(StreamQuotaArgs),
 }
 
 #[derive(Debug, Clone, Args)]
@@ -109,3 +119,13 @@ pub(crate) struct StreamPurgeArgs {
     /// Stream ID can be specified as a stream name or ID
     pub(crate) stream_id: Identifier,
 }
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct StreamQuotaArgs {
+    /// Stream ID to set the quota for
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    pub(crate) stream_id: Identifier,
+    #[command(flatten)]
+    pub(crate) quota: QuotaArgs,
+}
@@ -2,6 +2,7 @@ use crate::args::common::ListMode;
 use clap::{Args, Subcommand};
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::identifier::Identifier;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 
@@ -78,6 +79,17 @@ pub(crate) enum TopicAction {
     ///  iggy topic purge 2 debugs
     #[clap(verbatim_doc_comment, visible_alias = "p")]
     Purge(TopicPurgeArgs),
+    /// Set retention for topic with given ID in given stream ID
+    ///
+    /// Applies to the topics with the "consumed" cleanup policy only
+    /// Stream ID can be specified as a stream name or ID
+    /// Topic ID can be specified as a topic name or ID
+    ///
+    /// Examples
+    ///  iggy topic retention 1 1 --consumer-group orders --consumer-group audit
+    ///  iggy topic retention prod events --max-size 10GB
+    #[clap(verbatim_doc_comment, visible_alias = "r")]
+    Retention(TopicRetentionArgs),
 }
 
 #[derive(Debug, Clone, Args)]
@@ -97,6 +109,11 @@ pub(crate) struct TopicCreateArgs {
     /// Compression algorithm for the topic, set to "none" for no compression
     #[arg(value_parser = clap::value_parser!(CompressionAlgorithm), verbatim_doc_comment)]
     pub(crate) compression_algorithm: CompressionAlgorithm,
+    /// Compression level for the topic, skipping parameter uses the algorithm's default level
+    ///
+    /// Supported only by "gzip" (1-9) and "zstd" (1-22) algorithms.
+    #[arg(short = 'l', long, verbatim_doc_comment)]
+    pub(crate) compression_level: Option<u8>,
     /// Max topic size in human-readable format like "unlimited" or "15GB"
     ///
     /// "server_default" or skipping parameter makes CLI to use server default (from current server config) max topic size
@@ -106,6 +123,12 @@ pub(crate) struct TopicCreateArgs {
     /// Replication factor for the topic
     #[arg(short, long, default_value = "1")]
     pub(crate) replication_factor: u8,
+    /// Cleanup policy for the topic, "delete", "compact" or "consumed"
+    ///
+    /// Compacted topics retain only the latest message for each message key.
+    /// Consumed topics remove the segments only after the consumer groups have read them.
+    #[arg(long, default_value = "delete", value_parser = clap::value_parser!(CleanupPolicy), verbatim_doc_comment)]
+    pub(crate) cleanup_policy: CleanupPolicy,
     /// Message expiry time in human-readable format like "unlimited" or "15days 2min 2s"
     ///
     /// "server_default" or skipping parameter makes CLI to use server default (from current server config) expiry time
@@ -144,6 +167,11 @@ pub(crate) struct TopicUpdateArgs {
     /// Compression algorithm for the topic, set to "none" for no compression
     #[arg(value_parser = clap::value_parser!(CompressionAlgorithm), verbatim_doc_comment)]
     pub(crate) compression_algorithm: CompressionAlgorithm,
+    /// New compression level for the topic, skipping parameter uses the algorithm's default level
+    ///
+    /// Supported only by "gzip" (1-9) and "zstd" (1-22) algorithms.
+    #[arg(short = 'l', long, verbatim_doc_comment)]
+    pub(crate) compression_level: Option<u8>,
     /// New max topic size in human-readable format like "unlimited" or "15GB"
     ///
     /// "server_default" or skipping parameter makes CLI to use server default (from current server config) max topic size
@@ -153,6 +181,12 @@ pub(crate) struct TopicUpdateArgs {
     #[arg(short, long, default_value = "1")]
     /// New replication factor for the topic
     pub(crate) replication_factor: u8,
+    /// New cleanup policy for the topic, "delete", "compact" or "consumed"
+    ///
+    /// Compacted topics retain only the latest message for each message key.
+    /// Consumed topics remove the segments only after the consumer groups have read them.
+    #[arg(long, default_value = "delete", value_parser = clap::value_parser!(CleanupPolicy), verbatim_doc_comment)]
+    pub(crate) cleanup_policy: CleanupPolicy,
     /// New message expiry time in human-readable format like "unlimited" or "15days 2min 2s"
     ///
     /// "server_default" or skipping parameter makes CLI to use server default (from current server config) expiry time
@@ -200,3 +234,28 @@ pub(crate) struct TopicPurgeArgs {
     #[arg(value_parser = clap::value_parser!(Identifier))]
     pub(crate) topic_id: Identifier,
 }
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct TopicRetentionArgs {
+    /// Stream ID to set the topic retention
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) stream_id: Identifier,
+    /// Topic ID to set the retention for
+    ///
+    /// Topic ID can be specified as a topic name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) topic_id: Identifier,
+    /// Name of the consumer group which must consume the segments before they are removed
+    ///
+    /// Can be repeated, skipping parameter takes into account all the consumer groups of the topic
+    #[arg(short, long = "consumer-group", verbatim_doc_comment)]
+    pub(crate) consumer_groups: Vec<String>,
+    /// Hard max topic size in human-readable format like "unlimited" or "15GB"
+    ///
+    /// Above this size the unconsumed segments are removed anyway
+    /// "server_default" or skipping parameter uses server default (from current server config) size
+    #[arg(short, long, default_value = "server_default", verbatim_doc_comment)]
+    pub(crate) max_size: MaxTopicSize,
+}
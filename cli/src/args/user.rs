@@ -1,4 +1,4 @@
-use crate::args::common::ListMode;
+use crate::args::common::{ListMode, QuotaArgs};
 use crate::args::permissions::stream::StreamPermissionsArg;
 use crate::args::permissions::UserStatusArg;
 use clap::{Args, Subcommand};
@@ -82,6 +82,17 @@ pub(crate) enum UserAction {
     ///  iggy user permissions client
     #[clap(verbatim_doc_comment, visible_alias = "p")]
     Permissions(UserPermissionsArgs),
+    /// Set quota for user with given ID
+    ///
+    /// The user ID can be specified as either a username or an ID. The size, topics
+    /// and partitions limits apply to all the streams created by the user.
+    /// Limits which are not provided are unlimited, so skipping all of them removes the quota.
+    ///
+    /// Examples:
+    ///  iggy user quota 2 --max-size 10GB --max-partitions 100
+    ///  iggy user quota client --max-produce-rate 1MB
+    #[clap(verbatim_doc_comment, visible_alias = "q")]
+    Quota(UserQuotaArgs),
 }
 
 #[derive(Debug, Clone, Args)]
@@ -285,3 +296,13 @@ pub(crate) struct UserPermissionsArgs {
     #[arg(value_parser = clap::value_parser!(StreamPermissionsArg))]
     pub(crate) stream_permissions: Option<Vec<StreamPermissionsArg>>,
 }
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct UserQuotaArgs {
+    /// User ID to set the quota for
+    ///
+    /// The user ID can be specified as either a username or an ID
+    pub(crate) user_id: Identifier,
+    #[command(flatten)]
+    pub(crate) quota: QuotaArgs,
+}
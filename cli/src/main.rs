@@ -4,7 +4,7 @@ mod error;
 mod logging;
 
 use crate::args::{
-    client::ClientAction, consumer_group::ConsumerGroupAction,
+    archive::ArchiveAction, client::ClientAction, consumer_group::ConsumerGroupAction,
     consumer_offset::ConsumerOffsetAction, permissions::PermissionsArgs,
     personal_access_token::PersonalAccessTokenAction, stream::StreamAction, topic::TopicAction,
     Command, IggyConsoleArgs,
@@ -15,10 +15,13 @@ use crate::logging::Logging;
 use args::context::ContextAction;
 use args::message::MessageAction;
 use args::partition::PartitionAction;
+use args::role::RoleAction;
 use args::user::UserAction;
 use args::{CliOptions, IggyMergedConsoleArgs};
 use clap::Parser;
+use iggy::archives::restore_archive::RestoreArchive;
 use iggy::args::Args;
+use iggy::cli::archives::restore_archive::RestoreArchiveCmd;
 use iggy::cli::context::common::ContextManager;
 use iggy::cli::context::use_context::UseContextCmd;
 use iggy::cli::system::snapshot::GetSnapshotCmd;
@@ -43,14 +46,21 @@ use iggy::cli::{
         delete_personal_access_tokens::DeletePersonalAccessTokenCmd,
         get_personal_access_tokens::GetPersonalAccessTokensCmd,
     },
+    roles::{
+        assign_role::AssignRoleCmd, create_role::CreateRoleCmd, delete_role::DeleteRoleCmd,
+        get_role::GetRoleCmd, get_roles::GetRolesCmd, unassign_role::UnassignRoleCmd,
+        update_role::UpdateRoleCmd,
+    },
     streams::{
         create_stream::CreateStreamCmd, delete_stream::DeleteStreamCmd, get_stream::GetStreamCmd,
-        get_streams::GetStreamsCmd, purge_stream::PurgeStreamCmd, update_stream::UpdateStreamCmd,
+        get_streams::GetStreamsCmd, purge_stream::PurgeStreamCmd,
+        set_stream_quota::SetStreamQuotaCmd, update_stream::UpdateStreamCmd,
     },
     system::{me::GetMeCmd, ping::PingCmd, stats::GetStatsCmd},
     topics::{
         create_topic::CreateTopicCmd, delete_topic::DeleteTopicCmd, get_topic::GetTopicCmd,
-        get_topics::GetTopicsCmd, purge_topic::PurgeTopicCmd, update_topic::UpdateTopicCmd,
+        get_topics::GetTopicsCmd, purge_topic::PurgeTopicCmd,
+        set_topic_retention::SetTopicRetentionCmd, update_topic::UpdateTopicCmd,
     },
     users::{
         change_password::ChangePasswordCmd,
@@ -58,6 +68,7 @@ use iggy::cli::{
         delete_user::DeleteUserCmd,
         get_user::GetUserCmd,
         get_users::GetUsersCmd,
+        set_user_quota::SetUserQuotaCmd,
         update_permissions::UpdatePermissionsCmd,
         update_user::{UpdateUserCmd, UpdateUserType},
     },
@@ -65,6 +76,8 @@ use iggy::cli::{
 use iggy::cli_command::{CliCommand, PRINT_TARGET};
 use iggy::client_provider::{self, ClientProviderConfig};
 use iggy::clients::client::IggyClient;
+use iggy::models::permissions::Permissions;
+use iggy::topics::consumed_retention::ConsumedRetention;
 use iggy::utils::crypto::{Aes256GcmEncryptor, EncryptorKind};
 use iggy::utils::personal_access_token_expiry::PersonalAccessTokenExpiry;
 use std::sync::Arc;
@@ -98,6 +111,10 @@ fn get_command(
             StreamAction::Get(args) => Box::new(GetStreamCmd::new(args.stream_id.clone())),
             StreamAction::List(args) => Box::new(GetStreamsCmd::new(args.list_mode.into())),
             StreamAction::Purge(args) => Box::new(PurgeStreamCmd::new(args.stream_id.clone())),
+            StreamAction::Quota(args) => Box::new(SetStreamQuotaCmd::new(
+                args.stream_id.clone(),
+                (&args.quota).into(),
+            )),
         },
         Command::Topic(command) => match command {
             TopicAction::Create(args) => Box::new(CreateTopicCmd::new(
@@ -105,10 +122,12 @@ fn get_command(
                 args.topic_id,
                 args.partitions_count,
                 args.compression_algorithm,
+                args.compression_level,
                 args.name.clone(),
                 args.message_expiry.clone().into(),
                 args.max_topic_size,
                 args.replication_factor,
+                args.cleanup_policy,
             )),
             TopicAction::Delete(args) => Box::new(DeleteTopicCmd::new(
                 args.stream_id.clone(),
@@ -118,10 +137,12 @@ fn get_command(
                 args.stream_id.clone(),
                 args.topic_id.clone(),
                 args.compression_algorithm,
+                args.compression_level,
                 args.name.clone(),
                 args.message_expiry.clone().into(),
                 args.max_topic_size,
                 args.replication_factor,
+                args.cleanup_policy,
             )),
             TopicAction::Get(args) => Box::new(GetTopicCmd::new(
                 args.stream_id.clone(),
@@ -135,6 +156,14 @@ fn get_command(
                 args.stream_id.clone(),
                 args.topic_id.clone(),
             )),
+            TopicAction::Retention(args) => Box::new(SetTopicRetentionCmd::new(
+                args.stream_id.clone(),
+                args.topic_id.clone(),
+                ConsumedRetention {
+                    consumer_groups: args.consumer_groups.clone(),
+                    max_size: args.max_size,
+                },
+            )),
         },
         Command::Partition(command) => match command {
             PartitionAction::Create(args) => Box::new(CreatePartitionsCmd::new(
@@ -213,6 +242,50 @@ fn get_command(
                 )
                 .into(),
             )),
+            UserAction::Quota(quota_args) => Box::new(SetUserQuotaCmd::new(
+                quota_args.user_id.clone(),
+                (&quota_args.quota).into(),
+            )),
+        },
+        Command::Role(command) => match command {
+            RoleAction::Create(create_args) => Box::new(CreateRoleCmd::new(
+                create_args.name.clone(),
+                PermissionsArgs::new(
+                    create_args.global_permissions.clone(),
+                    create_args.stream_permissions.clone(),
+                )
+                .into(),
+            )),
+            RoleAction::Delete(delete_args) => {
+                Box::new(DeleteRoleCmd::new(delete_args.role_id.clone()))
+            }
+            RoleAction::Get(get_args) => Box::new(GetRoleCmd::new(get_args.role_id.clone())),
+            RoleAction::List(list_args) => Box::new(GetRolesCmd::new(list_args.list_mode.into())),
+            RoleAction::Name(name_args) => Box::new(UpdateRoleCmd::new(
+                name_args.role_id.clone(),
+                Some(name_args.name.clone()),
+                None,
+            )),
+            RoleAction::Permissions(permissions_args) => {
+                let permissions: Option<Permissions> = PermissionsArgs::new(
+                    permissions_args.global_permissions.clone(),
+                    permissions_args.stream_permissions.clone(),
+                )
+                .into();
+                Box::new(UpdateRoleCmd::new(
+                    permissions_args.role_id.clone(),
+                    None,
+                    Some(permissions.unwrap_or_default()),
+                ))
+            }
+            RoleAction::Assign(assign_args) => Box::new(AssignRoleCmd::new(
+                assign_args.role_id.clone(),
+                assign_args.user_id.clone(),
+            )),
+            RoleAction::Unassign(unassign_args) => Box::new(UnassignRoleCmd::new(
+                unassign_args.role_id.clone(),
+                unassign_args.user_id.clone(),
+            )),
         },
         Command::Client(command) => match command {
             ClientAction::Get(get_args) => Box::new(GetClientCmd::new(get_args.client_id)),
@@ -226,6 +299,7 @@ fn get_command(
                 create_args.topic_id.clone(),
                 create_args.name.clone(),
                 create_args.group_id,
+                create_args.assignment_strategy,
             )),
             ConsumerGroupAction::Delete(delete_args) => Box::new(DeleteConsumerGroupCmd::new(
                 delete_args.stream_id.clone(),
@@ -264,6 +338,7 @@ fn get_command(
                 poll_args.last,
                 poll_args.next,
                 poll_args.consumer.clone(),
+                poll_args.filter.clone().unwrap_or_default(),
                 poll_args.show_headers,
                 poll_args.output_file.clone(),
             )),
@@ -289,6 +364,15 @@ fn get_command(
                 set_args.offset,
             )),
         },
+        Command::Archive(command) => match command {
+            ArchiveAction::Restore(args) => Box::new(RestoreArchiveCmd::new(RestoreArchive {
+                stream_id: args.stream_id.clone(),
+                topic_id: args.topic_id.clone(),
+                partition_id: args.partition_id,
+                range: args.range(),
+                state_snapshot: args.state_snapshot.clone(),
+            })),
+        },
         Command::Context(command) => match command {
             ContextAction::List(list_args) => {
                 Box::new(GetContextsCmd::new(list_args.list_mode.into()))
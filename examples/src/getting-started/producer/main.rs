@@ -3,6 +3,7 @@ use iggy::clients::builder::IggyClientBuilder;
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::users::defaults::*;
 use iggy::utils::duration::IggyDuration;
 use iggy::utils::expiry::IggyExpiry;
@@ -57,9 +58,11 @@ async fn init_system(client: &IggyClient) {
             1,
             CompressionAlgorithm::default(),
             None,
+            None,
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
     {
@@ -57,6 +57,9 @@ pub struct Args {
     #[arg(long, default_value = "http://localhost:3000")]
     pub http_api_url: String,
 
+    #[arg(long)]
+    pub http_api_urls: Vec<String>,
+
     #[arg(long, default_value = "3")]
     pub http_retries: u32,
 
@@ -78,6 +81,9 @@ pub struct Args {
     #[arg(long, default_value = "127.0.0.1:8090")]
     pub tcp_server_address: String,
 
+    #[arg(long)]
+    pub tcp_server_addresses: Vec<String>,
+
     #[arg(long, default_value = "false")]
     pub tcp_tls_enabled: bool,
 
@@ -87,12 +93,21 @@ pub struct Args {
     #[arg(long, default_value = "false")]
     pub tcp_nodelay: bool,
 
+    #[arg(long, default_value = "false")]
+    pub tcp_zero_copy: bool,
+
+    #[arg(long, default_value = "false")]
+    pub tcp_pipelining: bool,
+
     #[arg(long, default_value = "127.0.0.1:0")]
     pub quic_client_address: String,
 
     #[arg(long, default_value = "127.0.0.1:8080")]
     pub quic_server_address: String,
 
+    #[arg(long)]
+    pub quic_server_addresses: Vec<String>,
+
     #[arg(long, default_value = "localhost")]
     pub quic_server_name: String,
 
@@ -170,6 +185,7 @@ impl Default for Args {
             transport: "tcp".to_string(),
             encryption_key: String::new(),
             http_api_url: "http://localhost:3000".to_string(),
+            http_api_urls: Vec::new(),
             http_retries: 3,
             tcp_reconnection_enabled: true,
             tcp_reconnection_max_retries: Some(3),
@@ -177,11 +193,15 @@ impl Default for Args {
             tcp_reconnection_reestablish_after: "5s".to_string(),
             tcp_heartbeat_interval: "5s".to_string(),
             tcp_server_address: "127.0.0.1:8090".to_string(),
+            tcp_server_addresses: Vec::new(),
             tcp_tls_enabled: false,
             tcp_tls_domain: "localhost".to_string(),
             tcp_nodelay: true,
+            tcp_zero_copy: false,
+            tcp_pipelining: false,
             quic_client_address: "127.0.0.1:0".to_string(),
             quic_server_address: "127.0.0.1:8080".to_string(),
+            quic_server_addresses: Vec::new(),
             quic_server_name: "localhost".to_string(),
             quic_reconnection_enabled: true,
             quic_reconnection_max_retries: None,
@@ -207,10 +227,12 @@ impl Args {
             transport: self.transport.clone(),
             encryption_key: self.encryption_key.clone(),
             http_api_url: self.http_api_url.clone(),
+            http_api_urls: self.http_api_urls.clone(),
             http_retries: self.http_retries,
             username: self.username.clone(),
             password: self.password.clone(),
             tcp_server_address: self.tcp_server_address.clone(),
+            tcp_server_addresses: self.tcp_server_addresses.clone(),
             tcp_reconnection_enabled: self.tcp_reconnection_enabled,
             tcp_reconnection_max_retries: self.tcp_reconnection_max_retries,
             tcp_reconnection_interval: self.tcp_reconnection_interval.clone(),
@@ -220,8 +242,11 @@ impl Args {
             tcp_tls_domain: self.tcp_tls_domain.clone(),
             tcp_tls_ca_file: None,
             tcp_nodelay: self.tcp_nodelay,
+            tcp_zero_copy: self.tcp_zero_copy,
+            tcp_pipelining: self.tcp_pipelining,
             quic_client_address: self.quic_client_address.clone(),
             quic_server_address: self.quic_server_address.clone(),
+            quic_server_addresses: self.quic_server_addresses.clone(),
             quic_server_name: self.quic_server_name.clone(),
             quic_reconnection_enabled: self.quic_reconnection_enabled,
             quic_reconnection_max_retries: self.quic_reconnection_max_retries,
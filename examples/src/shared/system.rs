@@ -6,6 +6,7 @@ use iggy::error::IggyError;
 use iggy::identifier::Identifier;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::models::messages::PolledMessage;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use tracing::info;
@@ -81,8 +82,10 @@ pub async fn init_by_producer(args: &Args, client: &dyn Client) -> Result<(), Ig
             CompressionAlgorithm::from_code(args.compression_algorithm)?,
             None,
             None,
+            None,
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await?;
     Ok(())
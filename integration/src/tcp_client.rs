@@ -9,6 +9,8 @@ use std::sync::Arc;
 pub struct TcpClientFactory {
     pub server_addr: String,
     pub nodelay: bool,
+    pub zero_copy: bool,
+    pub pipelining: bool,
 }
 
 #[async_trait]
@@ -17,6 +19,8 @@ impl ClientFactory for TcpClientFactory {
         let config = TcpClientConfig {
             server_address: self.server_addr.clone(),
             nodelay: self.nodelay,
+            zero_copy: self.zero_copy,
+            pipelining: self.pipelining,
             ..TcpClientConfig::default()
         };
         let client = TcpClient::create(Arc::new(config)).unwrap_or_else(|e| {
@@ -201,8 +201,10 @@ impl TestServer {
             #[cfg(unix)]
             unsafe {
                 use libc::kill;
-                use libc::SIGTERM;
+                use libc::{SIGCONT, SIGTERM};
                 kill(child_handle.id() as libc::pid_t, SIGTERM);
+                // The paused server must be resumed to handle the termination signal.
+                kill(child_handle.id() as libc::pid_t, SIGCONT);
             }
 
             #[cfg(not(unix))]
@@ -243,6 +245,21 @@ impl TestServer {
         self.child_handle.as_ref().unwrap().id()
     }
 
+    /// Suspends the server process, e.g. to cut it off from the other nodes of the cluster, until it's resumed.
+    #[cfg(unix)]
+    pub fn pause(&self) {
+        unsafe {
+            libc::kill(self.pid() as libc::pid_t, libc::SIGSTOP);
+        }
+    }
+
+    #[cfg(unix)]
+    pub fn resume(&self) {
+        unsafe {
+            libc::kill(self.pid() as libc::pid_t, libc::SIGCONT);
+        }
+    }
+
     fn cleanup(&self) {
         if !self.cleanup {
             return;
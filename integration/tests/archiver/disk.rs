@@ -2,6 +2,7 @@ use crate::archiver::DiskArchiverSetup;
 use server::streaming::utils::file;
 use server::{archiver::Archiver, server_error::ArchiverError};
 use std::path::Path;
+use tokio::fs::create_dir_all;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 #[tokio::test]
@@ -91,6 +92,94 @@ async fn should_fail_when_file_to_archive_does_not_exist() {
     assert!(matches!(error, ArchiverError::FileToArchiveNotFound { .. }));
 }
 
+#[tokio::test]
+async fn should_fetch_archived_file_to_destination() {
+    let setup = DiskArchiverSetup::init().await;
+    let archiver = setup.archiver();
+    let content = "hello world";
+    let file_to_archive_path = format!("{}/file_to_archive", setup.base_path);
+    create_file(&file_to_archive_path, content).await;
+    let files_to_archive = vec![file_to_archive_path.as_ref()];
+    archiver.archive(&files_to_archive, None).await.unwrap();
+
+    let fetched_file_path = format!("{}/fetched/file", setup.base_path);
+    let result = archiver
+        .fetch(&file_to_archive_path, &fetched_file_path, None)
+        .await;
+    assert!(result.is_ok());
+    assert_archived_file(&file_to_archive_path, &fetched_file_path, content).await;
+}
+
+#[tokio::test]
+async fn should_fail_when_file_to_fetch_is_not_archived() {
+    let setup = DiskArchiverSetup::init().await;
+    let archiver = setup.archiver();
+    let fetched_file_path = format!("{}/fetched/file", setup.base_path);
+    let result = archiver
+        .fetch("invalid_archived_file", &fetched_file_path, None)
+        .await;
+
+    assert!(result.is_err());
+    let error = result.err().unwrap();
+    assert!(matches!(error, ArchiverError::ArchivedFileNotFound { .. }));
+    assert!(!Path::new(&fetched_file_path).exists());
+}
+
+#[tokio::test]
+async fn should_list_archived_files_under_path() {
+    let setup = DiskArchiverSetup::init().await;
+    let archiver = setup.archiver();
+    let content = "hello world";
+    let first_file_path = format!("{}/partition/2.log", setup.base_path);
+    let second_file_path = format!("{}/partition/1.log", setup.base_path);
+    let other_file_path = format!("{}/other/1.log", setup.base_path);
+    create_dir_all(format!("{}/partition", setup.base_path))
+        .await
+        .unwrap();
+    create_dir_all(format!("{}/other", setup.base_path))
+        .await
+        .unwrap();
+    for path in [&first_file_path, &second_file_path, &other_file_path] {
+        create_file(path, content).await;
+    }
+    let files_to_archive = vec![
+        first_file_path.as_ref(),
+        second_file_path.as_ref(),
+        other_file_path.as_ref(),
+    ];
+    archiver.archive(&files_to_archive, None).await.unwrap();
+
+    let partition_path = format!("{}/partition", setup.base_path);
+    let archived_files = archiver.list(&partition_path, None).await.unwrap();
+    assert_eq!(archived_files, vec![second_file_path, first_file_path]);
+}
+
+#[tokio::test]
+async fn should_restore_archived_files_to_original_paths() {
+    let setup = DiskArchiverSetup::init().await;
+    let archiver = setup.archiver();
+    let base_directory = "base";
+    let content = "hello world";
+    let file_to_archive_path = format!("{}/file_to_archive", setup.base_path);
+    create_file(&file_to_archive_path, content).await;
+    let files_to_archive = vec![file_to_archive_path.as_ref()];
+    archiver
+        .archive(&files_to_archive, Some(base_directory.to_string()))
+        .await
+        .unwrap();
+    file::remove(&file_to_archive_path).await.unwrap();
+
+    let result = archiver
+        .restore(&files_to_archive, Some(base_directory.to_string()))
+        .await;
+    assert!(result.is_ok());
+    let archived_file_path = format!(
+        "{}/{base_directory}/{}",
+        setup.archive_path, file_to_archive_path
+    );
+    assert_archived_file(&file_to_archive_path, &archived_file_path, content).await;
+}
+
 async fn create_file(path: &str, content: &str) {
     let mut file = file::overwrite(path).await.unwrap();
     file.write_all(content.as_bytes()).await.unwrap();
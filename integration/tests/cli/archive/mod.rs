@@ -0,0 +1,2 @@
+mod test_archive_help_command;
+mod test_archive_restore_command;
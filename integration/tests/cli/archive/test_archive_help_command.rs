@@ -0,0 +1,27 @@
+use crate::cli::common::{help::TestHelpCmd, IggyCmdTest, USAGE_PREFIX};
+use serial_test::parallel;
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["archive", "help"],
+            format!(
+                r#"archive operations
+
+{USAGE_PREFIX} archive <COMMAND>
+
+Commands:
+  restore  Restore stream, topic or partition from the archive [aliases: r]
+  help     Print this message or the help of the given subcommand(s)
+
+Options:
+  -h, --help  Print help
+"#,
+            ),
+        ))
+        .await;
+}
@@ -0,0 +1,103 @@
+use crate::cli::common::{IggyCmdTest, TestHelpCmd, CLAP_INDENT, USAGE_PREFIX};
+use serial_test::parallel;
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["archive", "restore", "--help"],
+            format!(
+                r#"Restore stream, topic or partition from the archive
+
+Restores the archived segments preceding the messages stored locally.
+Stream or topic which does not exist is recreated from the archived state.
+Stream ID can be specified as a stream name or ID
+Topic ID can be specified as a topic name or ID
+
+Examples
+ iggy archive restore prod
+ iggy archive restore prod sensor
+ iggy archive restore 1 2 3 --from-offset 1000 --to-offset 2000
+ iggy archive restore prod --state-snapshot 1700000000000000_state
+
+{USAGE_PREFIX} archive restore [OPTIONS] <STREAM_ID> [TOPIC_ID] [PARTITION_ID]
+
+Arguments:
+  <STREAM_ID>
+          Stream ID to restore
+{CLAP_INDENT}
+          Stream ID can be specified as a stream name or ID
+
+  [TOPIC_ID]
+          Topic ID to restore, all topics of the stream are restored if not specified
+{CLAP_INDENT}
+          Topic ID can be specified as a topic name or ID
+
+  [PARTITION_ID]
+          Partition ID to restore, all partitions of the topic are restored if not specified
+
+Options:
+      --from-offset <FROM_OFFSET>
+          Restore only segments with messages starting from the offset
+
+      --to-offset <TO_OFFSET>
+          Restore only segments with messages up to the offset
+
+      --from-timestamp <FROM_TIMESTAMP>
+          Restore only segments with messages starting from the timestamp (in microseconds)
+
+      --to-timestamp <TO_TIMESTAMP>
+          Restore only segments with messages up to the timestamp (in microseconds)
+
+  -s, --state-snapshot <STATE_SNAPSHOT>
+          Name of the archived state snapshot used to recreate stream or topic
+{CLAP_INDENT}
+          Latest archived state is used if not specified
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_short_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["archive", "restore", "-h"],
+            format!(
+                r#"Restore stream, topic or partition from the archive
+
+{USAGE_PREFIX} archive restore [OPTIONS] <STREAM_ID> [TOPIC_ID] [PARTITION_ID]
+
+Arguments:
+  <STREAM_ID>     Stream ID to restore
+  [TOPIC_ID]      Topic ID to restore, all topics of the stream are restored if not specified
+  [PARTITION_ID]  Partition ID to restore, all partitions of the topic are restored if not specified
+
+Options:
+      --from-offset <FROM_OFFSET>
+          Restore only segments with messages starting from the offset
+      --to-offset <TO_OFFSET>
+          Restore only segments with messages up to the offset
+      --from-timestamp <FROM_TIMESTAMP>
+          Restore only segments with messages starting from the timestamp (in microseconds)
+      --to-timestamp <TO_TIMESTAMP>
+          Restore only segments with messages up to the timestamp (in microseconds)
+  -s, --state-snapshot <STATE_SNAPSHOT>
+          Name of the archived state snapshot used to recreate stream or topic
+  -h, --help
+          Print help (see more with '--help')
+"#,
+            ),
+        ))
+        .await;
+}
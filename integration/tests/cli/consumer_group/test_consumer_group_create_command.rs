@@ -5,6 +5,8 @@ use crate::cli::common::{
 use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::diff;
@@ -17,6 +19,7 @@ struct TestConsumerGroupCreateCmd {
     topic_name: String,
     group_id: Option<u32>,
     group_name: String,
+    assignment_strategy: Option<PartitionAssignmentStrategy>,
     using_stream_id: TestStreamId,
     using_topic_id: TestTopicId,
 }
@@ -30,6 +33,7 @@ impl TestConsumerGroupCreateCmd {
         topic_name: String,
         group_id: Option<u32>,
         group_name: String,
+        assignment_strategy: Option<PartitionAssignmentStrategy>,
         using_stream_id: TestStreamId,
         using_topic_id: TestTopicId,
     ) -> Self {
@@ -40,6 +44,7 @@ impl TestConsumerGroupCreateCmd {
             topic_name,
             group_id,
             group_name,
+            assignment_strategy,
             using_stream_id,
             using_topic_id,
         }
@@ -61,6 +66,11 @@ impl TestConsumerGroupCreateCmd {
             command.push(format!("{}", group_id));
         }
 
+        if let Some(assignment_strategy) = self.assignment_strategy {
+            command.push("-s".to_string());
+            command.push(assignment_strategy.to_string());
+        }
+
         command.push(self.group_name.clone());
 
         command
@@ -82,9 +92,11 @@ impl IggyCmdTestCase for TestConsumerGroupCreateCmd {
                 1,
                 Default::default(),
                 None,
+                None,
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -136,6 +148,10 @@ impl IggyCmdTestCase for TestConsumerGroupCreateCmd {
         if let Some(group_id) = self.group_id {
             assert_eq!(consumer_group_details.id, group_id);
         }
+        assert_eq!(
+            consumer_group_details.assignment_strategy,
+            self.assignment_strategy.unwrap_or_default()
+        );
 
         let topic = client
             .delete_topic(
@@ -166,6 +182,7 @@ pub async fn should_be_successful() {
             String::from("sync"),
             Some(1),
             String::from("group1"),
+            None,
             TestStreamId::Numeric,
             TestTopicId::Numeric,
         ))
@@ -178,6 +195,7 @@ pub async fn should_be_successful() {
             String::from("topic"),
             Some(3),
             String::from("group3"),
+            Some(PartitionAssignmentStrategy::Range),
             TestStreamId::Named,
             TestTopicId::Numeric,
         ))
@@ -190,6 +208,7 @@ pub async fn should_be_successful() {
             String::from("probe"),
             Some(7),
             String::from("group7"),
+            Some(PartitionAssignmentStrategy::Sticky),
             TestStreamId::Numeric,
             TestTopicId::Named,
         ))
@@ -202,6 +221,7 @@ pub async fn should_be_successful() {
             String::from("test"),
             Some(4),
             String::from("group4"),
+            Some(PartitionAssignmentStrategy::Cooperative),
             TestStreamId::Named,
             TestTopicId::Named,
         ))
@@ -249,6 +269,13 @@ Options:
   -g, --group-id <GROUP_ID>
           Consumer group ID to create
 
+  -s, --assignment-strategy <ASSIGNMENT_STRATEGY>
+          Partition assignment strategy for the consumer group
+{CLAP_INDENT}
+          One of "round_robin", "range", "sticky" or "cooperative".
+{CLAP_INDENT}
+          [default: round_robin]
+
   -h, --help
           Print help (see a summary with '-h')
 "#,
@@ -276,8 +303,12 @@ Arguments:
   <NAME>       Consumer group name to create
 
 Options:
-  -g, --group-id <GROUP_ID>  Consumer group ID to create
-  -h, --help                 Print help (see more with '--help')
+  -g, --group-id <GROUP_ID>
+          Consumer group ID to create
+  -s, --assignment-strategy <ASSIGNMENT_STRATEGY>
+          Partition assignment strategy for the consumer group [default: round_robin]
+  -h, --help
+          Print help (see more with '--help')
 "#,
             ),
         ))
@@ -5,6 +5,8 @@ use crate::cli::common::{
 use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::diff;
@@ -83,9 +85,11 @@ impl IggyCmdTestCase for TestConsumerGroupDeleteCmd {
                 1,
                 Default::default(),
                 None,
+                None,
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -96,6 +100,7 @@ impl IggyCmdTestCase for TestConsumerGroupDeleteCmd {
                 &self.topic_id.try_into().unwrap(),
                 &self.group_name,
                 Some(self.group_id),
+                PartitionAssignmentStrategy::default(),
             )
             .await;
         assert!(consumer_group.is_ok());
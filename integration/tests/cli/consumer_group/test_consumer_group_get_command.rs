@@ -5,6 +5,8 @@ use crate::cli::common::{
 use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, starts_with};
@@ -83,9 +85,11 @@ impl IggyCmdTestCase for TestConsumerGroupGetCmd {
                 0,
                 Default::default(),
                 None,
+                None,
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -96,6 +100,7 @@ impl IggyCmdTestCase for TestConsumerGroupGetCmd {
                 &self.topic_id.try_into().unwrap(),
                 &self.group_name,
                 self.group_id.into(),
+                PartitionAssignmentStrategy::default(),
             )
             .await;
         assert!(consumer_group.is_ok());
@@ -137,7 +142,9 @@ impl IggyCmdTestCase for TestConsumerGroupGetCmd {
             .stdout(contains(format!(
                 "Consumer group name | {}",
                 self.group_name
-            )));
+            )))
+            .stdout(contains("Assignment strategy | round_robin"))
+            .stdout(contains("Generation          | 0"));
     }
 
     async fn verify_server_state(&self, client: &dyn Client) {
@@ -5,6 +5,8 @@ use crate::cli::common::{
 use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, starts_with};
@@ -80,9 +82,11 @@ impl IggyCmdTestCase for TestConsumerGroupListCmd {
                 1,
                 Default::default(),
                 None,
+                None,
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -93,6 +97,7 @@ impl IggyCmdTestCase for TestConsumerGroupListCmd {
                 &self.topic_id.try_into().unwrap(),
                 &self.consumer_group_name,
                 self.consumer_group_id.into(),
+                PartitionAssignmentStrategy::default(),
             )
             .await;
         assert!(consumer_group.is_ok());
@@ -8,6 +8,7 @@ use iggy::client::Client;
 use iggy::consumer::{Consumer, ConsumerKind};
 use iggy::identifier::Identifier;
 use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, starts_with};
@@ -96,9 +97,11 @@ impl IggyCmdTestCase for TestConsumerOffsetGetCmd {
                 1,
                 Default::default(),
                 None,
+                None,
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -131,6 +134,7 @@ impl IggyCmdTestCase for TestConsumerOffsetGetCmd {
                 &self.topic_id.try_into().unwrap(),
                 Some(self.partition_id),
                 self.stored_offset,
+                None,
             )
             .await;
         assert!(offset.is_ok());
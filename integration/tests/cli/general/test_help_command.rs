@@ -24,10 +24,12 @@ Commands:
   snapshot         collect iggy server troubleshooting data
   pat              personal access token operations
   user             user operations [aliases: u]
+  role             role operations [aliases: r]
   client           client operations [aliases: c]
   consumer-group   consumer group operations [aliases: g]
   consumer-offset  consumer offset operations [aliases: o]
   message          message operations [aliases: m]
+  archive          archive operations [aliases: a]
   context          context operations [aliases: ctx]
   login            login to Iggy server [aliases: li]
   logout           logout from Iggy server [aliases: lo]
@@ -59,6 +61,9 @@ Options:
 {CLAP_INDENT}
           [default: http://localhost:3000]
 
+      --http-api-urls <HTTP_API_URLS>
+          The optional API URLs of the multiple servers for the HTTP transport, used instead of the single API URL (repeatable)
+
       --http-retries <HTTP_RETRIES>
           The optional number of retries for the HTTP transport
 {CLAP_INDENT}
@@ -69,6 +74,9 @@ Options:
 {CLAP_INDENT}
           [default: 127.0.0.1:8090]
 
+      --tcp-server-addresses <TCP_SERVER_ADDRESSES>
+          The optional addresses of the multiple servers for the TCP transport, used instead of the single server address (repeatable)
+
       --tcp-reconnection-max-retries <TCP_RECONNECTION_MAX_RETRIES>
           The optional number of max reconnect retries for the TCP transport
 {CLAP_INDENT}
@@ -97,6 +105,9 @@ Options:
 {CLAP_INDENT}
           [default: 127.0.0.1:8080]
 
+      --quic-server-addresses <QUIC_SERVER_ADDRESSES>
+          The optional addresses of the multiple servers for the QUIC transport, used instead of the single server address (repeatable)
+
       --quic-server-name <QUIC_SERVER_NAME>
           The optional server name for the QUIC transport
 {CLAP_INDENT}
@@ -35,10 +35,12 @@ Commands:
   snapshot         collect iggy server troubleshooting data
   pat              personal access token operations
   user             user operations [aliases: u]
+  role             role operations [aliases: r]
   client           client operations [aliases: c]
   consumer-group   consumer group operations [aliases: g]
   consumer-offset  consumer offset operations [aliases: o]
   message          message operations [aliases: m]
+  archive          archive operations [aliases: a]
   context          context operations [aliases: ctx]
   login            login to Iggy server [aliases: li]
   logout           logout from Iggy server [aliases: lo]
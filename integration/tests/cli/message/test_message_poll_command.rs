@@ -9,6 +9,7 @@ use iggy::client::Client;
 use iggy::messages::poll_messages::{PollingKind, PollingStrategy};
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, starts_with};
@@ -120,9 +121,11 @@ impl IggyCmdTestCase for TestMessagePollCmd {
                 self.partitions_count,
                 Default::default(),
                 None,
+                None,
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -401,6 +404,15 @@ Options:
 {CLAP_INDENT}
           [default: 1]
 
+      --filter <FILTER>
+          Filter on the message headers evaluated by the server
+{CLAP_INDENT}
+          Only the messages matching all the conditions separated by ';' are returned,
+          the message count applies to the messages before filtering.
+          Supported conditions are: "exists:key", "eq:key:kind:value",
+          "in:key:kind:value1|value2" and "range:key:kind:min..max" (numeric kinds only).
+          Example: "eq:tenant:string:acme;range:priority:uint8:1..3"
+
   -s, --show-headers
           Include the message headers in the output
 {CLAP_INDENT}
@@ -451,6 +463,7 @@ Options:
   -l, --last                           Polling strategy - start polling from the last message in the partition
   -n, --next                           Polling strategy - start polling from the next message
   -c, --consumer <CONSUMER>            Regular consumer which will poll messages [default: 1]
+      --filter <FILTER>                Filter on the message headers evaluated by the server
   -s, --show-headers                   Include the message headers in the output
       --output-file <OUTPUT_FILE>      Store polled message into file in binary format
   -h, --help                           Print help (see more with '--help')
@@ -7,6 +7,7 @@ use iggy::identifier::Identifier;
 use iggy::messages::poll_messages::{PollingKind, PollingStrategy};
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, is_match, starts_with};
@@ -97,8 +98,10 @@ impl IggyCmdTestCase for TestMessagePollToFileCmd<'_> {
                 Default::default(),
                 None,
                 None,
+                None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -1,3 +1,4 @@
+mod archive;
 mod client;
 mod common;
 mod consumer_group;
@@ -7,6 +8,7 @@ mod general;
 mod message;
 mod partition;
 mod personal_access_token;
+mod role;
 mod stream;
 mod system;
 mod topic;
@@ -0,0 +1 @@
+mod test_role_help_command;
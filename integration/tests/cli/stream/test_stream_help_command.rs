@@ -21,6 +21,7 @@ Commands:
   get     Get details of a single stream with given ID [aliases: g]
   list    List all streams [aliases: l]
   purge   Purge all topics in given stream ID [aliases: p]
+  quota   Set quota for given stream ID [aliases: q]
   help    Print this message or the help of the given subcommand(s)
 
 Options:
@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use iggy::cli::system::stats::GetStatsOutput;
 use iggy::client::Client;
 use iggy::identifier::Identifier;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, starts_with};
@@ -56,9 +57,11 @@ impl IggyCmdTestCase for TestStatsCmd {
                 5,
                 Default::default(),
                 None,
+                None,
                 Some(1),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -4,4 +4,5 @@ mod test_topic_get_command;
 mod test_topic_help_command;
 mod test_topic_list_command;
 mod test_topic_purge_command;
+mod test_topic_retention_command;
 mod test_topic_update_command;
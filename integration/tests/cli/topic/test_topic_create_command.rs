@@ -291,6 +291,11 @@ Options:
   -t, --topic-id <TOPIC_ID>
           Topic ID to create
 
+  -l, --compression-level <COMPRESSION_LEVEL>
+          Compression level for the topic, skipping parameter uses the algorithm's default level
+{CLAP_INDENT}
+          Supported only by "gzip" (1-9) and "zstd" (1-22) algorithms.
+
   -m, --max-topic-size <MAX_TOPIC_SIZE>
           Max topic size in human-readable format like "unlimited" or "15GB"
 {CLAP_INDENT}
@@ -304,6 +309,14 @@ Options:
 {CLAP_INDENT}
           [default: 1]
 
+      --cleanup-policy <CLEANUP_POLICY>
+          Cleanup policy for the topic, "delete", "compact" or "consumed"
+{CLAP_INDENT}
+          Compacted topics retain only the latest message for each message key.
+          Consumed topics remove the segments only after the consumer groups have read them.
+{CLAP_INDENT}
+          [default: delete]
+
   -h, --help
           Print help (see a summary with '-h')
 "#,
@@ -334,10 +347,14 @@ Arguments:
 Options:
   -t, --topic-id <TOPIC_ID>
           Topic ID to create
+  -l, --compression-level <COMPRESSION_LEVEL>
+          Compression level for the topic, skipping parameter uses the algorithm's default level
   -m, --max-topic-size <MAX_TOPIC_SIZE>
           Max topic size in human-readable format like "unlimited" or "15GB" [default: server_default]
   -r, --replication-factor <REPLICATION_FACTOR>
           Replication factor for the topic [default: 1]
+      --cleanup-policy <CLEANUP_POLICY>
+          Cleanup policy for the topic, "delete", "compact" or "consumed" [default: delete]
   -h, --help
           Print help (see more with '--help')
 "#,
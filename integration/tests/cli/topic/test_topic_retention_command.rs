@@ -0,0 +1,270 @@
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, TestStreamId, TestTopicId,
+    CLAP_INDENT, USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use iggy::client::Client;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use predicates::str::diff;
+use serial_test::parallel;
+
+struct TestTopicRetentionCmd {
+    stream_id: u32,
+    stream_name: String,
+    topic_id: u32,
+    topic_name: String,
+    consumer_groups: Vec<String>,
+    max_size: MaxTopicSize,
+    using_stream_id: TestStreamId,
+    using_topic_id: TestTopicId,
+}
+
+impl TestTopicRetentionCmd {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        stream_id: u32,
+        stream_name: String,
+        topic_id: u32,
+        topic_name: String,
+        consumer_groups: Vec<String>,
+        max_size: MaxTopicSize,
+        using_stream_id: TestStreamId,
+        using_topic_id: TestTopicId,
+    ) -> Self {
+        Self {
+            stream_id,
+            stream_name,
+            topic_id,
+            topic_name,
+            consumer_groups,
+            max_size,
+            using_stream_id,
+            using_topic_id,
+        }
+    }
+
+    fn to_args(&self) -> Vec<String> {
+        let mut command = match self.using_stream_id {
+            TestStreamId::Numeric => vec![format!("{}", self.stream_id)],
+            TestStreamId::Named => vec![self.stream_name.clone()],
+        };
+
+        command.push(match self.using_topic_id {
+            TestTopicId::Numeric => format!("{}", self.topic_id),
+            TestTopicId::Named => self.topic_name.clone(),
+        });
+
+        for consumer_group in &self.consumer_groups {
+            command.push("--consumer-group".to_string());
+            command.push(consumer_group.clone());
+        }
+
+        if let MaxTopicSize::Custom(_) = self.max_size {
+            command.push("--max-size".to_string());
+            command.push(self.max_size.to_string());
+        }
+
+        command
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestTopicRetentionCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client
+            .create_stream(&self.stream_name, Some(self.stream_id))
+            .await;
+        assert!(stream.is_ok());
+
+        let topic = client
+            .create_topic(
+                &self.stream_id.try_into().unwrap(),
+                &self.topic_name,
+                1,
+                Default::default(),
+                None,
+                None,
+                Some(self.topic_id),
+                IggyExpiry::NeverExpire,
+                MaxTopicSize::ServerDefault,
+                CleanupPolicy::Consumed,
+            )
+            .await;
+        assert!(topic.is_ok());
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("topic")
+            .arg("retention")
+            .args(self.to_args())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let stream_id = match self.using_stream_id {
+            TestStreamId::Numeric => format!("{}", self.stream_id),
+            TestStreamId::Named => self.stream_name.clone(),
+        };
+
+        let topic_id = match self.using_topic_id {
+            TestTopicId::Numeric => format!("{}", self.topic_id),
+            TestTopicId::Named => self.topic_name.clone(),
+        };
+
+        let retention = format!(
+            "consumer_groups: {:?}, max_size: {}",
+            self.consumer_groups, self.max_size
+        );
+        let message = format!("Executing set retention for topic with ID: {} in stream with ID: {} ({})\nTopic with ID: {} in stream with ID: {} retention set ({})\n",
+                              topic_id, stream_id, retention, topic_id, stream_id, retention);
+
+        command_state.success().stdout(diff(message));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let topic_delete = client
+            .delete_topic(
+                &self.stream_id.try_into().unwrap(),
+                &self.topic_id.try_into().unwrap(),
+            )
+            .await;
+        assert!(topic_delete.is_ok());
+
+        let stream_delete = client
+            .delete_stream(&self.stream_id.try_into().unwrap())
+            .await;
+        assert!(stream_delete.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestTopicRetentionCmd::new(
+            1,
+            String::from("main"),
+            1,
+            String::from("sync"),
+            vec![String::from("orders")],
+            MaxTopicSize::ServerDefault,
+            TestStreamId::Numeric,
+            TestTopicId::Numeric,
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestTopicRetentionCmd::new(
+            2,
+            String::from("testing"),
+            2,
+            String::from("topic"),
+            vec![String::from("orders"), String::from("audit")],
+            MaxTopicSize::from(1_000_000_000),
+            TestStreamId::Named,
+            TestTopicId::Named,
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestTopicRetentionCmd::new(
+            3,
+            String::from("prod"),
+            1,
+            String::from("named"),
+            vec![],
+            MaxTopicSize::from(5_000_000_000),
+            TestStreamId::Named,
+            TestTopicId::Numeric,
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["topic", "retention", "--help"],
+            format!(
+                r#"Set retention for topic with given ID in given stream ID
+
+Applies to the topics with the "consumed" cleanup policy only
+Stream ID can be specified as a stream name or ID
+Topic ID can be specified as a topic name or ID
+
+Examples
+ iggy topic retention 1 1 --consumer-group orders --consumer-group audit
+ iggy topic retention prod events --max-size 10GB
+
+{USAGE_PREFIX} topic retention [OPTIONS] <STREAM_ID> <TOPIC_ID>
+
+Arguments:
+  <STREAM_ID>
+          Stream ID to set the topic retention
+{CLAP_INDENT}
+          Stream ID can be specified as a stream name or ID
+
+  <TOPIC_ID>
+          Topic ID to set the retention for
+{CLAP_INDENT}
+          Topic ID can be specified as a topic name or ID
+
+Options:
+  -c, --consumer-group <CONSUMER_GROUPS>
+          Name of the consumer group which must consume the segments before they are removed
+{CLAP_INDENT}
+          Can be repeated, skipping parameter takes into account all the consumer groups of the topic
+
+  -m, --max-size <MAX_SIZE>
+          Hard max topic size in human-readable format like "unlimited" or "15GB"
+{CLAP_INDENT}
+          Above this size the unconsumed segments are removed anyway
+          "server_default" or skipping parameter uses server default (from current server config) size
+{CLAP_INDENT}
+          [default: server_default]
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_short_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["topic", "retention", "-h"],
+            format!(
+                r#"Set retention for topic with given ID in given stream ID
+
+{USAGE_PREFIX} topic retention [OPTIONS] <STREAM_ID> <TOPIC_ID>
+
+Arguments:
+  <STREAM_ID>  Stream ID to set the topic retention
+  <TOPIC_ID>   Topic ID to set the retention for
+
+Options:
+  -c, --consumer-group <CONSUMER_GROUPS>
+          Name of the consumer group which must consume the segments before they are removed
+  -m, --max-size <MAX_SIZE>
+          Hard max topic size in human-readable format like "unlimited" or "15GB" [default: server_default]
+  -h, --help
+          Print help (see more with '--help')
+"#,
+            ),
+        ))
+        .await;
+}
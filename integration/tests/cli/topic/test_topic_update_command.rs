@@ -7,6 +7,8 @@ use async_trait::async_trait;
 use humantime::Duration as HumanDuration;
 use iggy::client::Client;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::topics::replication_factor::ReplicationFactor;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
@@ -125,10 +127,12 @@ impl IggyCmdTestCase for TestTopicUpdateCmd {
                 &self.topic_name,
                 1,
                 self.compression_algorithm,
-                Some(self.replication_factor),
+                None,
+                Some(ReplicationFactor(self.replication_factor)),
                 Some(self.topic_id),
                 message_expiry,
                 self.max_topic_size,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -395,6 +399,11 @@ Arguments:
           [default: server_default]
 
 Options:
+  -l, --compression-level <COMPRESSION_LEVEL>
+          New compression level for the topic, skipping parameter uses the algorithm's default level
+{CLAP_INDENT}
+          Supported only by "gzip" (1-9) and "zstd" (1-22) algorithms.
+
   -m, --max-topic-size <MAX_TOPIC_SIZE>
           New max topic size in human-readable format like "unlimited" or "15GB"
 {CLAP_INDENT}
@@ -408,6 +417,14 @@ Options:
 {CLAP_INDENT}
           [default: 1]
 
+      --cleanup-policy <CLEANUP_POLICY>
+          New cleanup policy for the topic, "delete", "compact" or "consumed"
+{CLAP_INDENT}
+          Compacted topics retain only the latest message for each message key.
+          Consumed topics remove the segments only after the consumer groups have read them.
+{CLAP_INDENT}
+          [default: delete]
+
   -h, --help
           Print help (see a summary with '-h')
 "#,
@@ -437,10 +454,14 @@ Arguments:
   [MESSAGE_EXPIRY]...      New message expiry time in human-readable format like "unlimited" or "15days 2min 2s" [default: server_default]
 
 Options:
+  -l, --compression-level <COMPRESSION_LEVEL>
+          New compression level for the topic, skipping parameter uses the algorithm's default level
   -m, --max-topic-size <MAX_TOPIC_SIZE>
           New max topic size in human-readable format like "unlimited" or "15GB" [default: server_default]
   -r, --replication-factor <REPLICATION_FACTOR>
           New replication factor for the topic [default: 1]
+      --cleanup-policy <CLEANUP_POLICY>
+          New cleanup policy for the topic, "delete", "compact" or "consumed" [default: delete]
   -h, --help
           Print help (see more with '--help')
 "#,
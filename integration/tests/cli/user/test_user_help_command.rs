@@ -23,6 +23,7 @@ Commands:
   status       Change status for user with given ID [aliases: s]
   password     Change password for user with given ID [aliases: pwd]
   permissions  Set permissions for user with given ID [aliases: p]
+  quota        Set quota for user with given ID [aliases: q]
   help         Print this message or the help of the given subcommand(s)
 
 Options:
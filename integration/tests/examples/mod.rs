@@ -9,6 +9,7 @@ use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::tcp::client::TcpClient;
 use iggy::tcp::config::TcpClientConfig;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::users::defaults::*;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
@@ -118,8 +119,10 @@ impl<'a> IggyExampleTest<'a> {
                     CompressionAlgorithm::default(),
                     None,
                     None,
+                    None,
                     IggyExpiry::NeverExpire,
                     MaxTopicSize::ServerDefault,
+                    CleanupPolicy::default(),
                 )
                 .await
                 .unwrap();
@@ -1,8 +1,19 @@
+use crate::server::scenarios::client_certificate_scenario::{
+    self, TestCertificates, TlsClientFactory,
+};
+use crate::server::scenarios::token_login_scenario::{self, TestIdentityProvider};
 use crate::server::scenarios::{
-    create_message_payload, stream_size_validation_scenario, system_scenario, user_scenario,
+    archive_restore_scenario, create_message_payload, dead_letter_scenario,
+    message_filter_scenario, role_scenario, stream_size_validation_scenario, system_scenario,
+    user_scenario,
+};
+use iggy::http::config::HttpClientConfigBuilder;
+use integration::{
+    http_client::HttpClientFactory,
+    test_server::{IpAddrKind, TestServer},
 };
-use integration::{http_client::HttpClientFactory, test_server::TestServer};
 use serial_test::parallel;
+use std::sync::Arc;
 
 #[tokio::test]
 #[parallel]
@@ -53,3 +64,95 @@ async fn user_scenario_should_be_valid() {
     let client_factory = HttpClientFactory { server_addr };
     user_scenario::run(&client_factory).await;
 }
+
+#[tokio::test]
+#[parallel]
+async fn role_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = HttpClientFactory { server_addr };
+    role_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn dead_letter_scenario_should_be_valid() {
+    let mut test_server = dead_letter_scenario::create_test_server();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = HttpClientFactory { server_addr };
+    dead_letter_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn message_filter_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = HttpClientFactory { server_addr };
+    message_filter_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn archive_restore_scenario_should_be_valid() {
+    let mut test_server = archive_restore_scenario::create_test_server();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = HttpClientFactory { server_addr };
+    archive_restore_scenario::run(&client_factory, test_server.get_local_data_path()).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn client_certificate_scenario_should_be_valid() {
+    let certificates = TestCertificates::generate();
+    let mut test_server = TestServer::new(
+        Some(certificates.server_envs(&[
+            ("IGGY_HTTP_TLS_ENABLED", "true"),
+            ("IGGY_HTTP_TLS_CERT_FILE", &certificates.server_cert_file()),
+            ("IGGY_HTTP_TLS_KEY_FILE", &certificates.server_key_file()),
+            ("IGGY_HTTP_TLS_CLIENT_AUTH", "true"),
+        ])),
+        true,
+        None,
+        IpAddrKind::V4,
+    );
+    test_server.start();
+    let api_url = format!("https://{}", test_server.get_http_api_addr().unwrap());
+    let client_factory = TlsClientFactory::Http(Arc::new(
+        HttpClientConfigBuilder::new()
+            .with_api_url(api_url.clone())
+            .with_tls_ca_file(certificates.ca_file())
+            .build(),
+    ));
+    let certificate_client_factory = TlsClientFactory::Http(Arc::new(
+        HttpClientConfigBuilder::new()
+            .with_api_url(api_url)
+            .with_tls_ca_file(certificates.ca_file())
+            .with_tls_client_certificate(
+                certificates.client_cert_file(),
+                certificates.client_key_file(),
+            )
+            .build(),
+    ));
+    client_certificate_scenario::run(&client_factory, &certificate_client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn token_login_scenario_should_be_valid() {
+    let identity_provider = TestIdentityProvider::generate();
+    let mut test_server = TestServer::new(
+        Some(identity_provider.server_envs()),
+        true,
+        None,
+        IpAddrKind::V4,
+    );
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = HttpClientFactory { server_addr };
+    token_login_scenario::run(&client_factory, &identity_provider).await;
+}
@@ -1,10 +1,25 @@
+use crate::server::scenarios::client_certificate_scenario::{
+    self, TestCertificates, TlsClientFactory,
+};
+use crate::server::scenarios::token_login_scenario::{self, TestIdentityProvider};
 use crate::server::scenarios::{
     consumer_group_join_scenario, consumer_group_with_multiple_clients_polling_messages_scenario,
     consumer_group_with_single_client_polling_messages_scenario, create_message_payload,
-    message_headers_scenario, stream_size_validation_scenario, system_scenario, user_scenario,
+    failover_scenario, message_headers_scenario, pipelining_scenario, role_scenario,
+    stream_size_validation_scenario, system_scenario, user_scenario,
+};
+use iggy::client::{AutoLogin, Client, Credentials};
+use iggy::quic::client::QuicClient;
+use iggy::quic::config::{QuicClientConfig, QuicClientConfigBuilder, QuicClientReconnectionConfig};
+use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
+use iggy::utils::duration::IggyDuration;
+use integration::{
+    quic_client::QuicClientFactory,
+    test_server::{IpAddrKind, TestServer},
 };
-use integration::{quic_client::QuicClientFactory, test_server::TestServer};
 use serial_test::parallel;
+use std::str::FromStr;
+use std::sync::Arc;
 
 #[tokio::test]
 #[parallel]
@@ -26,6 +41,16 @@ async fn user_scenario_should_be_valid() {
     user_scenario::run(&client_factory).await;
 }
 
+#[tokio::test]
+#[parallel]
+async fn role_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory { server_addr };
+    role_scenario::run(&client_factory).await;
+}
+
 #[tokio::test]
 #[parallel]
 async fn message_headers_scenario_should_be_valid() {
@@ -85,3 +110,102 @@ async fn stream_size_validation_scenario_should_be_valid() {
     let client_factory = QuicClientFactory { server_addr };
     stream_size_validation_scenario::run(&client_factory).await;
 }
+
+#[tokio::test]
+#[parallel]
+async fn pipelining_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory { server_addr };
+    pipelining_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn client_certificate_scenario_should_be_valid() {
+    let certificates = TestCertificates::generate();
+    let mut test_server = TestServer::new(
+        Some(certificates.server_envs(&[("IGGY_QUIC_CERTIFICATE_CLIENT_AUTH", "true")])),
+        true,
+        None,
+        IpAddrKind::V4,
+    );
+    test_server.start();
+    let server_address = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = TlsClientFactory::Quic(Arc::new(
+        QuicClientConfigBuilder::new()
+            .with_server_address(server_address.clone())
+            .build(),
+    ));
+    let certificate_client_factory = TlsClientFactory::Quic(Arc::new(
+        QuicClientConfigBuilder::new()
+            .with_server_address(server_address)
+            .with_tls_client_certificate(
+                certificates.client_cert_file(),
+                certificates.client_key_file(),
+            )
+            .build(),
+    ));
+    client_certificate_scenario::run(&client_factory, &certificate_client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn failover_scenario_should_be_valid() {
+    let mut primary_server = TestServer::default();
+    let mut standby_server = TestServer::default();
+    primary_server.start();
+    standby_server.start();
+    let primary_client_factory = QuicClientFactory {
+        server_addr: primary_server.get_quic_udp_addr().unwrap(),
+    };
+    let standby_client_factory = QuicClientFactory {
+        server_addr: standby_server.get_quic_udp_addr().unwrap(),
+    };
+    // The short idle timeout lets the client notice the stopped server, as QUIC runs over the connectionless UDP.
+    let failover_client = QuicClient::create(Arc::new(QuicClientConfig {
+        server_addresses: vec![
+            primary_client_factory.server_addr.clone(),
+            standby_client_factory.server_addr.clone(),
+        ],
+        auto_login: AutoLogin::Enabled(Credentials::UsernamePassword(
+            DEFAULT_ROOT_USERNAME.to_owned(),
+            DEFAULT_ROOT_PASSWORD.to_owned(),
+        )),
+        reconnection: QuicClientReconnectionConfig {
+            enabled: true,
+            max_retries: Some(5),
+            interval: IggyDuration::from_str("100ms").unwrap(),
+            reestablish_after: IggyDuration::from_str("100ms").unwrap(),
+        },
+        keep_alive_interval: 100,
+        max_idle_timeout: 1000,
+        ..QuicClientConfig::default()
+    }))
+    .unwrap();
+    Client::connect(&failover_client).await.unwrap();
+    failover_scenario::run(
+        &mut primary_server,
+        &primary_client_factory,
+        &standby_client_factory,
+        Box::new(failover_client),
+    )
+    .await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn token_login_scenario_should_be_valid() {
+    let identity_provider = TestIdentityProvider::generate();
+    let mut test_server = TestServer::new(
+        Some(identity_provider.server_envs()),
+        true,
+        None,
+        IpAddrKind::V4,
+    );
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory { server_addr };
+    token_login_scenario::run(&client_factory, &identity_provider).await;
+}
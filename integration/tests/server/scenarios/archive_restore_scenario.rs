@@ -0,0 +1,323 @@
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::archives::restore_archive::ArchiveRange;
+use iggy::client::{ArchiveClient, MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::messages::PolledMessage;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::timestamp::IggyTimestamp;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{
+    assert_clean_system, login_root, ClientFactory, IpAddrKind, TestServer, SYSTEM_PATH_ENV_VAR,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const BATCHES_COUNT: u32 = 3;
+const MESSAGES_PER_BATCH: u32 = 5;
+const MESSAGES_COUNT: u32 = BATCHES_COUNT * MESSAGES_PER_BATCH;
+const ARCHIVING_ATTEMPTS: u32 = 300;
+
+pub fn create_test_server() -> TestServer {
+    let system_path = TestServer::get_random_path();
+    let extra_envs = HashMap::from([
+        (SYSTEM_PATH_ENV_VAR.to_string(), system_path.clone()),
+        // Each batch of messages fills up the segment, so it's closed right away and can be archived.
+        ("IGGY_SYSTEM_SEGMENT_SIZE".to_string(), "1 B".to_string()),
+        (
+            "IGGY_DATA_MAINTENANCE_ARCHIVER_ENABLED".to_string(),
+            "true".to_string(),
+        ),
+        (
+            "IGGY_DATA_MAINTENANCE_ARCHIVER_KIND".to_string(),
+            "disk".to_string(),
+        ),
+        (
+            "IGGY_DATA_MAINTENANCE_ARCHIVER_DISK_PATH".to_string(),
+            get_archive_path(&system_path),
+        ),
+        (
+            "IGGY_DATA_MAINTENANCE_MESSAGES_ARCHIVER_ENABLED".to_string(),
+            "true".to_string(),
+        ),
+        (
+            "IGGY_DATA_MAINTENANCE_MESSAGES_INTERVAL".to_string(),
+            "1 s".to_string(),
+        ),
+        (
+            "IGGY_DATA_MAINTENANCE_STATE_ARCHIVER_ENABLED".to_string(),
+            "true".to_string(),
+        ),
+        (
+            "IGGY_DATA_MAINTENANCE_STATE_OVERWRITE".to_string(),
+            "false".to_string(),
+        ),
+        (
+            "IGGY_DATA_MAINTENANCE_STATE_INTERVAL".to_string(),
+            "1 s".to_string(),
+        ),
+    ]);
+    TestServer::new(Some(extra_envs), true, None, IpAddrKind::V4)
+}
+
+pub async fn run(client_factory: &dyn ClientFactory, system_path: &str) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Send the batches of messages, each of them ends up in a separate (closed) segment
+    for batch in 0..BATCHES_COUNT {
+        let mut messages = (0..MESSAGES_PER_BATCH)
+            .map(|index| {
+                let offset = batch * MESSAGES_PER_BATCH + index;
+                let payload = create_payload(offset as u64);
+                Message {
+                    id: (offset + 1) as u128,
+                    length: payload.len() as u32,
+                    payload,
+                    headers: None,
+                }
+            })
+            .collect::<Vec<_>>();
+        client
+            .send_messages(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                &Partitioning::partition_id(PARTITION_ID),
+                &mut messages,
+            )
+            .await
+            .unwrap();
+    }
+
+    // 2. Wait for the segments and the state snapshot (containing the stream and the topic) to be archived
+    let archived_at = IggyTimestamp::now().as_micros();
+    let state_snapshot = wait_for_archive(system_path, archived_at).await;
+
+    // 3. Restoring the archive of the existing stream should not restore anything, as all the segments are present locally
+    let restored_archive = client
+        .restore_archive(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            None,
+            None,
+            ArchiveRange::All,
+            Some(&state_snapshot),
+        )
+        .await
+        .unwrap();
+    assert_eq!(restored_archive.created_streams_count, 0);
+    assert_eq!(restored_archive.created_topics_count, 0);
+    assert_eq!(restored_archive.segments_count, 0);
+
+    // 4. Delete the stream and restore it from the archive
+    client
+        .delete_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap();
+    let restored_archive = client
+        .restore_archive(
+            &Identifier::named(STREAM_NAME).unwrap(),
+            None,
+            None,
+            ArchiveRange::All,
+            Some(&state_snapshot),
+        )
+        .await
+        .unwrap();
+    assert_eq!(restored_archive.created_streams_count, 1);
+    assert_eq!(restored_archive.created_topics_count, 1);
+    assert_eq!(restored_archive.segments_count, BATCHES_COUNT);
+    assert_eq!(restored_archive.messages_count, MESSAGES_COUNT as u64);
+
+    let stream = client
+        .get_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap()
+        .expect("Stream should be restored");
+    assert_eq!(stream.name, STREAM_NAME);
+    let topic = client
+        .get_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+        )
+        .await
+        .unwrap()
+        .expect("Topic should be restored");
+    assert_eq!(topic.name, TOPIC_NAME);
+    assert_eq!(topic.partitions_count, PARTITIONS_COUNT);
+
+    let polled_messages = poll_messages(&client, 0).await;
+    assert_eq!(polled_messages.len() as u32, MESSAGES_COUNT);
+    assert_messages(&polled_messages, 0);
+
+    // 5. The new messages are appended after the restored ones
+    let payload = create_payload(MESSAGES_COUNT as u64);
+    let mut messages = vec![Message {
+        id: (MESSAGES_COUNT + 1) as u128,
+        length: payload.len() as u32,
+        payload,
+        headers: None,
+    }];
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+    let polled_messages = poll_messages(&client, 0).await;
+    assert_eq!(polled_messages.len() as u32, MESSAGES_COUNT + 1);
+    assert_messages(&polled_messages, 0);
+
+    // 6. Delete the topic and restore only the segment containing the given range of offsets
+    client
+        .delete_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+        )
+        .await
+        .unwrap();
+    let from_offset = MESSAGES_PER_BATCH as u64;
+    let restored_archive = client
+        .restore_archive(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            Some(&Identifier::numeric(TOPIC_ID).unwrap()),
+            Some(PARTITION_ID),
+            ArchiveRange::Offsets {
+                from: from_offset + 1,
+                to: from_offset + 2,
+            },
+            Some(&state_snapshot),
+        )
+        .await
+        .unwrap();
+    assert_eq!(restored_archive.created_streams_count, 0);
+    assert_eq!(restored_archive.created_topics_count, 1);
+    assert_eq!(restored_archive.segments_count, 1);
+    assert_eq!(restored_archive.messages_count, MESSAGES_PER_BATCH as u64);
+
+    let polled_messages = poll_messages(&client, from_offset).await;
+    assert_eq!(polled_messages.len() as u32, MESSAGES_PER_BATCH);
+    assert_messages(&polled_messages, from_offset);
+
+    // 7. Restoring the stream which has never existed should fail
+    assert!(client
+        .restore_archive(
+            &Identifier::numeric(STREAM_ID + 1).unwrap(),
+            None,
+            None,
+            ArchiveRange::All,
+            Some(&state_snapshot),
+        )
+        .await
+        .is_err());
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+/// Waits until all the segments are archived and returns the name of the state snapshot archived afterwards.
+async fn wait_for_archive(system_path: &str, archived_at: u64) -> String {
+    let archive_path = get_archive_path(system_path);
+    let partition_path = format!(
+        "{archive_path}/{system_path}/streams/{STREAM_ID}/topics/{TOPIC_ID}/partitions/{PARTITION_ID}"
+    );
+    for _ in 0..ARCHIVING_ATTEMPTS {
+        let archived_segments_count = fs::read_dir(&partition_path)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "log"))
+                    .count()
+            })
+            .unwrap_or_default();
+        let state_snapshot = fs::read_dir(&archive_path)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .filter_map(|name| {
+                        let micros = name.strip_suffix("_state")?.parse::<u64>().ok()?;
+                        (micros > archived_at).then_some((micros, name))
+                    })
+                    .max()
+            })
+            .ok()
+            .flatten();
+        if archived_segments_count as u32 >= BATCHES_COUNT {
+            if let Some((_, state_snapshot)) = state_snapshot {
+                return state_snapshot;
+            }
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Segments and state were not archived in time");
+}
+
+async fn poll_messages(client: &IggyClient, offset: u64) -> Vec<PolledMessage> {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(offset),
+            MESSAGES_COUNT * 2,
+            false,
+        )
+        .await
+        .unwrap()
+        .messages
+}
+
+fn assert_messages(messages: &[PolledMessage], start_offset: u64) {
+    for (index, message) in messages.iter().enumerate() {
+        let offset = start_offset + index as u64;
+        assert_eq!(message.offset, offset);
+        assert_eq!(message.id, (offset + 1) as u128);
+        assert_eq!(message.payload, create_payload(offset));
+    }
+}
+
+fn create_payload(offset: u64) -> Bytes {
+    Bytes::from(format!("message {offset}"))
+}
+
+fn get_archive_path(system_path: &str) -> String {
+    format!("{system_path}/archive")
+}
@@ -0,0 +1,162 @@
+use crate::server::scenarios::{create_client, STREAM_ID, STREAM_NAME, USERNAME_1};
+use async_trait::async_trait;
+use iggy::client::{Client, StreamClient};
+use iggy::error::IggyError;
+use iggy::http::client::HttpClient;
+use iggy::http::config::HttpClientConfig;
+use iggy::identifier::Identifier;
+use iggy::quic::client::QuicClient;
+use iggy::quic::config::QuicClientConfig;
+use integration::test_server::{create_user, delete_user, login_root, ClientFactory};
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair, SanType,
+};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+const CLIENT_COMMON_NAME: &str = "iggy-test-client";
+
+pub async fn run(
+    client_factory: &dyn ClientFactory,
+    certificate_client_factory: &dyn ClientFactory,
+) {
+    let root_client = create_client(client_factory).await;
+    login_root(&root_client).await;
+    create_user(&root_client, USERNAME_1).await;
+
+    // 1. The client presenting the certificate mapped to the user is authenticated without logging in
+    let certificate_client = create_client(certificate_client_factory).await;
+    certificate_client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    let streams = certificate_client.get_streams().await.unwrap();
+    assert_eq!(streams.len(), 1);
+    assert_eq!(streams[0].name, STREAM_NAME);
+
+    // 2. The client without the certificate stays unauthenticated
+    let client = create_client(client_factory).await;
+    let error = client.get_streams().await.unwrap_err();
+    assert_eq!(error.as_code(), IggyError::Unauthenticated.as_code());
+
+    root_client
+        .delete_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap();
+    delete_user(&root_client, USERNAME_1).await;
+}
+
+/// The CA, the server certificate and the client certificate, stored as the PEM files in the temporary directory.
+pub struct TestCertificates {
+    directory: TempDir,
+}
+
+impl TestCertificates {
+    pub fn generate() -> Self {
+        let directory = TempDir::new().unwrap();
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::default();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "iggy-test-ca");
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let mut server_params = CertificateParams::new(vec!["localhost".to_owned()]).unwrap();
+        server_params
+            .subject_alt_names
+            .push(SanType::IpAddress(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        server_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+
+        let mut client_params = CertificateParams::default();
+        client_params
+            .distinguished_name
+            .push(DnType::CommonName, CLIENT_COMMON_NAME);
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+
+        let certificates = Self { directory };
+        std::fs::write(certificates.path("ca_cert.pem"), ca.pem()).unwrap();
+        certificates.write_signed("server", server_params, &ca, &ca_key);
+        certificates.write_signed("client", client_params, &ca, &ca_key);
+        certificates
+    }
+
+    /// Returns the server config, which maps the client certificate to the user.
+    pub fn server_envs(&self, envs: &[(&str, &str)]) -> HashMap<String, String> {
+        let mut server_envs = HashMap::from([
+            ("IGGY_CLIENT_AUTH_CA_FILE".to_owned(), self.ca_file()),
+            (
+                "IGGY_CLIENT_AUTH_USERS".to_owned(),
+                format!("[cn:{CLIENT_COMMON_NAME}={USERNAME_1}]"),
+            ),
+        ]);
+        for (key, value) in envs {
+            server_envs.insert(key.to_string(), value.to_string());
+        }
+        server_envs
+    }
+
+    pub fn ca_file(&self) -> String {
+        self.path("ca_cert.pem")
+    }
+
+    pub fn server_cert_file(&self) -> String {
+        self.path("server_cert.pem")
+    }
+
+    pub fn server_key_file(&self) -> String {
+        self.path("server_key.pem")
+    }
+
+    pub fn client_cert_file(&self) -> String {
+        self.path("client_cert.pem")
+    }
+
+    pub fn client_key_file(&self) -> String {
+        self.path("client_key.pem")
+    }
+
+    fn write_signed(
+        &self,
+        name: &str,
+        params: CertificateParams,
+        ca: &Certificate,
+        ca_key: &KeyPair,
+    ) {
+        let key = KeyPair::generate().unwrap();
+        let certificate = params.signed_by(&key, ca, ca_key).unwrap();
+        std::fs::write(self.path(&format!("{name}_cert.pem")), certificate.pem()).unwrap();
+        std::fs::write(self.path(&format!("{name}_key.pem")), key.serialize_pem()).unwrap();
+    }
+
+    fn path(&self, file: &str) -> String {
+        self.directory
+            .path()
+            .join(file)
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+/// Creates the QUIC or HTTP clients using the provided TLS config.
+pub enum TlsClientFactory {
+    Quic(Arc<QuicClientConfig>),
+    Http(Arc<HttpClientConfig>),
+}
+
+#[async_trait]
+impl ClientFactory for TlsClientFactory {
+    async fn create_client(&self) -> Box<dyn Client> {
+        match self {
+            TlsClientFactory::Quic(config) => {
+                let client = QuicClient::create(config.clone()).unwrap();
+                Client::connect(&client).await.unwrap();
+                Box::new(client)
+            }
+            TlsClientFactory::Http(config) => Box::new(HttpClient::create(config.clone()).unwrap()),
+        }
+    }
+}
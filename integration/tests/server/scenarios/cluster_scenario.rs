@@ -0,0 +1,314 @@
+use crate::server::scenarios::{
+    create_client, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::messages::PolledMessage;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::topics::replication_factor::ReplicationFactor;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::tcp_client::TcpClientFactory;
+use integration::test_server::{login_root, IpAddrKind, TestServer};
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::time::Duration;
+
+const NODES_COUNT: u32 = 3;
+const MESSAGES_COUNT: u32 = 10;
+const RETRIES: u32 = 100;
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+const UNCOMMITTED_STREAM_ID: u32 = 10;
+const UNCOMMITTED_STREAM_NAME: &str = "uncommitted-stream";
+const COMMITTED_STREAM_ID: u32 = 20;
+const COMMITTED_STREAM_NAME: &str = "committed-stream";
+
+pub fn create_test_servers() -> Vec<TestServer> {
+    let addresses = (0..NODES_COUNT)
+        .map(|_| {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().to_string()
+        })
+        .collect::<Vec<_>>();
+
+    (1..=NODES_COUNT)
+        .map(|id| {
+            let peers = (1..=NODES_COUNT)
+                .filter(|peer_id| *peer_id != id)
+                .map(|peer_id| format!("{peer_id}@{}", addresses[peer_id as usize - 1]))
+                .collect::<Vec<_>>()
+                .join(",");
+            let extra_envs = HashMap::from([
+                ("IGGY_CLUSTER_ENABLED".to_string(), "true".to_string()),
+                ("IGGY_CLUSTER_NODE_ID".to_string(), id.to_string()),
+                (
+                    "IGGY_CLUSTER_NODE_ADDRESS".to_string(),
+                    addresses[id as usize - 1].clone(),
+                ),
+                ("IGGY_CLUSTER_PEERS".to_string(), format!("[{peers}]")),
+                (
+                    "IGGY_CLUSTER_HEARTBEAT_INTERVAL".to_string(),
+                    "100 ms".to_string(),
+                ),
+                (
+                    "IGGY_CLUSTER_ELECTION_TIMEOUT".to_string(),
+                    "1 s".to_string(),
+                ),
+                (
+                    "IGGY_CLUSTER_REPLICATION_TIMEOUT".to_string(),
+                    "2 s".to_string(),
+                ),
+            ]);
+            TestServer::new(Some(extra_envs), true, None, IpAddrKind::V4)
+        })
+        .collect()
+}
+
+pub async fn run(servers: &mut [TestServer]) {
+    let clients = create_clients(servers).await;
+
+    // 1. Wait for the leader to be elected and create the stream on it
+    let leader = create_stream_on_leader(&clients, STREAM_NAME, STREAM_ID).await;
+
+    // 2. Topic with the replication factor greater than the number of nodes cannot be created
+    let result = create_topic(&clients[leader], NODES_COUNT as u8 + 1).await;
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::InvalidReplicationFactor.as_code()
+    );
+
+    // 3. Create the topic replicated to all the nodes and send the messages to the leader
+    create_topic(&clients[leader], NODES_COUNT as u8)
+        .await
+        .unwrap();
+    let mut messages = (0..MESSAGES_COUNT)
+        .map(|offset| {
+            let payload = Bytes::from(format!("message {offset}"));
+            Message {
+                id: (offset + 1) as u128,
+                length: payload.len() as u32,
+                payload,
+                headers: None,
+            }
+        })
+        .collect::<Vec<_>>();
+    clients[leader]
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+
+    // 4. The followers should contain the same messages at the same offsets and timestamps, and reject the writes
+    let leader_messages = poll_replicated_messages(&clients[leader]).await;
+    for (index, client) in clients.iter().enumerate() {
+        if index == leader {
+            continue;
+        }
+
+        let polled_messages = poll_replicated_messages(client).await;
+        for (offset, message) in polled_messages.iter().enumerate() {
+            assert_eq!(message.offset, offset as u64);
+            assert_eq!(message.id, (offset + 1) as u128);
+            assert_eq!(message.payload, Bytes::from(format!("message {offset}")));
+            assert_eq!(message.timestamp, leader_messages[offset].timestamp);
+        }
+
+        let result = client.create_stream("follower-stream", None).await;
+        assert_eq!(
+            result.unwrap_err().as_code(),
+            IggyError::NotLeader(0).as_code()
+        );
+    }
+
+    // 5. After stopping the leader, the new one should be elected among the remaining nodes
+    servers[leader].stop();
+    let remaining_clients = clients
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| *index != leader)
+        .map(|(_, client)| client)
+        .collect::<Vec<_>>();
+    let new_leader = create_stream_on_leader(&remaining_clients, STREAM_NAME, STREAM_ID).await;
+    let polled_messages = poll_replicated_messages(&remaining_clients[new_leader]).await;
+    assert_eq!(polled_messages.len() as u32, MESSAGES_COUNT);
+}
+
+/// The leader cut off from the followers applies the commands which can't be committed,
+/// their effects must be discarded once it rejoins the cluster and the new leader's entries replace them.
+#[cfg(unix)]
+pub async fn run_leader_rejoin(servers: &mut [TestServer]) {
+    let clients = create_clients(servers).await;
+
+    // 1. Wait for the leader to be elected and create the stream on it
+    let leader = create_stream_on_leader(&clients, STREAM_NAME, STREAM_ID).await;
+
+    // 2. Cut off the leader, the stream created on it is applied, but it cannot be committed
+    for (index, server) in servers.iter().enumerate() {
+        if index != leader {
+            server.pause();
+        }
+    }
+    let result = clients[leader]
+        .create_stream(UNCOMMITTED_STREAM_NAME, Some(UNCOMMITTED_STREAM_ID))
+        .await;
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::StateEntryNotCommitted(0).as_code()
+    );
+    let stream = clients[leader]
+        .get_stream(&Identifier::numeric(UNCOMMITTED_STREAM_ID).unwrap())
+        .await
+        .unwrap();
+    assert!(stream.is_some());
+
+    // 3. The new leader should be elected among the remaining nodes, which create the other stream
+    servers[leader].pause();
+    for (index, server) in servers.iter().enumerate() {
+        if index != leader {
+            server.resume();
+        }
+    }
+    let mut remaining_clients = Vec::with_capacity(servers.len() - 1);
+    for (index, server) in servers.iter().enumerate() {
+        if index != leader {
+            remaining_clients.push(create_server_client(server).await);
+        }
+    }
+    let new_leader = create_stream_on_leader(
+        &remaining_clients,
+        COMMITTED_STREAM_NAME,
+        COMMITTED_STREAM_ID,
+    )
+    .await;
+
+    // 4. Once the previous leader rejoins the cluster, its state should match the new leader's one.
+    // The uncommitted stream is usually discarded, unless its entry, buffered by the sockets of the paused followers,
+    // has still reached them and been committed by the new leader.
+    let uncommitted_stream_id = Identifier::numeric(UNCOMMITTED_STREAM_ID).unwrap();
+    let is_uncommitted_stream_kept = remaining_clients[new_leader]
+        .get_stream(&uncommitted_stream_id)
+        .await
+        .unwrap()
+        .is_some();
+    servers[leader].resume();
+    let mut has_rejoined = false;
+    for _ in 0..RETRIES {
+        let committed_stream = clients[leader]
+            .get_stream(&Identifier::numeric(COMMITTED_STREAM_ID).unwrap())
+            .await;
+        let uncommitted_stream = clients[leader].get_stream(&uncommitted_stream_id).await;
+        if matches!(committed_stream, Ok(Some(_)))
+            && matches!(uncommitted_stream, Ok(stream) if stream.is_some() == is_uncommitted_stream_kept)
+        {
+            has_rejoined = true;
+            break;
+        }
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+    assert!(
+        has_rejoined,
+        "The previous leader has not rejoined the cluster."
+    );
+
+    let result = clients[leader].create_stream("follower-stream", None).await;
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::NotLeader(0).as_code()
+    );
+}
+
+async fn create_clients(servers: &[TestServer]) -> Vec<IggyClient> {
+    let mut clients = Vec::with_capacity(servers.len());
+    for server in servers.iter() {
+        clients.push(create_server_client(server).await);
+    }
+    clients
+}
+
+async fn create_server_client(server: &TestServer) -> IggyClient {
+    let client_factory = TcpClientFactory {
+        server_addr: server.get_raw_tcp_addr().unwrap(),
+        ..Default::default()
+    };
+    let client = create_client(&client_factory).await;
+    login_root(&client).await;
+    client
+}
+
+async fn create_stream_on_leader(clients: &[IggyClient], name: &str, stream_id: u32) -> usize {
+    for _ in 0..RETRIES {
+        for (index, client) in clients.iter().enumerate() {
+            match client.create_stream(name, Some(stream_id)).await {
+                Ok(_) => return index,
+                Err(error)
+                    if error.as_code() == IggyError::NotLeader(0).as_code()
+                        || error.as_code() == IggyError::LeaderNotElected.as_code() => {}
+                // The stream could have been already created by the previous leader.
+                Err(error)
+                    if error.as_code() == IggyError::StreamIdAlreadyExists(0).as_code()
+                        || error.as_code()
+                            == IggyError::StreamNameAlreadyExists(String::new()).as_code() =>
+                {
+                    return index
+                }
+                Err(error) => panic!("Unexpected error while creating the stream: {error}"),
+            }
+        }
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+
+    panic!("The leader has not been elected.");
+}
+
+async fn create_topic(client: &IggyClient, replication_factor: u8) -> Result<(), IggyError> {
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            Some(ReplicationFactor(replication_factor)),
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .map(|_| ())
+}
+
+async fn poll_replicated_messages(client: &IggyClient) -> Vec<PolledMessage> {
+    for _ in 0..RETRIES {
+        if let Ok(polled_messages) = client
+            .poll_messages(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                Some(PARTITION_ID),
+                &Consumer::default(),
+                &PollingStrategy::offset(0),
+                MESSAGES_COUNT,
+                false,
+            )
+            .await
+        {
+            if polled_messages.messages.len() as u32 == MESSAGES_COUNT {
+                return polled_messages.messages;
+            }
+        }
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+
+    panic!("The messages have not been replicated.");
+}
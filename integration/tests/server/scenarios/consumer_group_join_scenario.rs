@@ -6,9 +6,11 @@ use crate::server::scenarios::{
 use iggy::client::{ConsumerGroupClient, StreamClient, SystemClient, TopicClient};
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::identifier::Identifier;
 use iggy::models::client_info::ClientInfoDetails;
 use iggy::models::consumer_group::ConsumerGroupDetails;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use integration::test_server::{
@@ -38,9 +40,11 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             PARTITIONS_COUNT,
             CompressionAlgorithm::default(),
             None,
+            None,
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -52,6 +56,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             CONSUMER_GROUP_NAME,
             Some(CONSUMER_GROUP_ID),
+            PartitionAssignmentStrategy::default(),
         )
         .await
         .unwrap();
@@ -7,10 +7,12 @@ use iggy::client::{ConsumerGroupClient, MessageClient, StreamClient, SystemClien
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::Consumer;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::identifier::Identifier;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::consumer_group::ConsumerGroupDetails;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use integration::test_server::{
@@ -54,9 +56,11 @@ async fn init_system(
             PARTITIONS_COUNT,
             CompressionAlgorithm::default(),
             None,
+            None,
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -68,6 +72,7 @@ async fn init_system(
             &Identifier::numeric(TOPIC_ID).unwrap(),
             CONSUMER_GROUP_NAME,
             Some(CONSUMER_GROUP_ID),
+            PartitionAssignmentStrategy::default(),
         )
         .await
         .unwrap();
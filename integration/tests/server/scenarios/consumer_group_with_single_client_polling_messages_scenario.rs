@@ -7,9 +7,11 @@ use iggy::client::{ConsumerGroupClient, MessageClient, StreamClient, SystemClien
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::Consumer;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::identifier::Identifier;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use integration::test_server::{assert_clean_system, login_root, ClientFactory};
@@ -45,9 +47,11 @@ async fn init_system(client: &IggyClient) {
             PARTITIONS_COUNT,
             CompressionAlgorithm::default(),
             None,
+            None,
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -59,6 +63,7 @@ async fn init_system(client: &IggyClient) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             CONSUMER_GROUP_NAME,
             Some(CONSUMER_GROUP_ID),
+            PartitionAssignmentStrategy::default(),
         )
         .await
         .unwrap();
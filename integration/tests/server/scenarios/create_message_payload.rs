@@ -5,6 +5,7 @@ use iggy::consumer::Consumer;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use integration::test_server::{assert_clean_system, login_root, ClientFactory};
@@ -112,8 +113,10 @@ async fn init_system(client: &IggyClient) {
             Default::default(),
             None,
             None,
+            None,
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
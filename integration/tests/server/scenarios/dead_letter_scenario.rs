@@ -0,0 +1,257 @@
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::nack_message::{
+    DEAD_LETTER_OFFSET_HEADER, DEAD_LETTER_PARTITION_ID_HEADER, DEAD_LETTER_REASON_HEADER,
+    DEAD_LETTER_STREAM_ID_HEADER, DEAD_LETTER_TOPIC_ID_HEADER,
+};
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::header::HeaderKey;
+use iggy::models::messages::{MessageState, PolledMessage};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{
+    assert_clean_system, login_root, ClientFactory, IpAddrKind, TestServer,
+};
+use std::collections::HashMap;
+
+const DEAD_LETTER_STREAM_NAME: &str = "dead_letter";
+const DEAD_LETTER_TOPIC_NAME: &str = "messages";
+const MAX_DELIVERY_ATTEMPTS: u32 = 3;
+const DEAD_LETTER_STREAM_ID: u32 = 100;
+const MESSAGES_COUNT: u32 = 10;
+const POISONED_OFFSET: u64 = 5;
+const REASON: &str = "invalid payload";
+
+pub fn create_test_server() -> TestServer {
+    let extra_envs = HashMap::from([
+        (
+            "IGGY_SYSTEM_DEAD_LETTER_ENABLED".to_string(),
+            "true".to_string(),
+        ),
+        (
+            "IGGY_SYSTEM_DEAD_LETTER_MAX_DELIVERY_ATTEMPTS".to_string(),
+            MAX_DELIVERY_ATTEMPTS.to_string(),
+        ),
+        (
+            "IGGY_SYSTEM_DEAD_LETTER_STREAM".to_string(),
+            DEAD_LETTER_STREAM_NAME.to_string(),
+        ),
+        (
+            "IGGY_SYSTEM_DEAD_LETTER_TOPIC".to_string(),
+            DEAD_LETTER_TOPIC_NAME.to_string(),
+        ),
+    ]);
+    TestServer::new(Some(extra_envs), true, None, IpAddrKind::V4)
+}
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Send messages to the source topic
+    let mut messages = (0..MESSAGES_COUNT)
+        .map(|offset| {
+            let payload = Bytes::from(format!("message {}", offset));
+            Message {
+                id: (offset + 1) as u128,
+                length: payload.len() as u32,
+                payload,
+                headers: None,
+            }
+        })
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+
+    // 2. Nack the message until the max delivery attempts are reached
+    let consumer = Consumer::default();
+    for attempt in 1..=MAX_DELIVERY_ATTEMPTS {
+        client
+            .nack_message(
+                &consumer,
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                PARTITION_ID,
+                POISONED_OFFSET,
+                REASON,
+            )
+            .await
+            .unwrap();
+
+        let dead_letter_messages = poll_dead_letter_messages(&client).await;
+        if attempt < MAX_DELIVERY_ATTEMPTS {
+            assert!(dead_letter_messages.is_empty());
+        } else {
+            assert_eq!(dead_letter_messages.len(), 1);
+        }
+    }
+
+    // 3. Nack the message past the max delivery attempts and validate it's moved only once
+    client
+        .nack_message(
+            &consumer,
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            POISONED_OFFSET,
+            REASON,
+        )
+        .await
+        .unwrap();
+
+    // 4. Validate the message moved to the dead letter topic
+    let dead_letter_messages = poll_dead_letter_messages(&client).await;
+    assert_eq!(dead_letter_messages.len(), 1);
+    let dead_letter_message = &dead_letter_messages[0];
+    assert_eq!(dead_letter_message.id, (POISONED_OFFSET + 1) as u128);
+    assert_eq!(
+        dead_letter_message.payload,
+        Bytes::from(format!("message {}", POISONED_OFFSET))
+    );
+    let headers = dead_letter_message.headers.as_ref().unwrap();
+    let get_header = |key: &str| headers.get(&HeaderKey::new(key).unwrap()).unwrap();
+    assert_eq!(
+        get_header(DEAD_LETTER_STREAM_ID_HEADER)
+            .as_uint32()
+            .unwrap(),
+        STREAM_ID
+    );
+    assert_eq!(
+        get_header(DEAD_LETTER_TOPIC_ID_HEADER).as_uint32().unwrap(),
+        TOPIC_ID
+    );
+    assert_eq!(
+        get_header(DEAD_LETTER_PARTITION_ID_HEADER)
+            .as_uint32()
+            .unwrap(),
+        PARTITION_ID
+    );
+    assert_eq!(
+        get_header(DEAD_LETTER_OFFSET_HEADER).as_uint64().unwrap(),
+        POISONED_OFFSET
+    );
+    assert_eq!(
+        get_header(DEAD_LETTER_REASON_HEADER).as_str().unwrap(),
+        REASON
+    );
+
+    // 5. Poll the source topic and validate that only the nacked message is poisoned
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &consumer,
+            &PollingStrategy::offset(0),
+            MESSAGES_COUNT,
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(polled_messages.messages.len() as u32, MESSAGES_COUNT);
+    for message in polled_messages.messages {
+        if message.offset == POISONED_OFFSET {
+            assert_eq!(message.state, MessageState::Poisoned);
+        } else {
+            assert_eq!(message.state, MessageState::Available);
+        }
+    }
+
+    // 6. Nacking the message with the invalid offset should fail
+    assert!(client
+        .nack_message(
+            &consumer,
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            MESSAGES_COUNT as u64,
+            REASON,
+        )
+        .await
+        .is_err());
+
+    client
+        .delete_stream(&Identifier::numeric(DEAD_LETTER_STREAM_ID).unwrap())
+        .await
+        .unwrap();
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn poll_dead_letter_messages(client: &IggyClient) -> Vec<PolledMessage> {
+    client
+        .poll_messages(
+            &Identifier::named(DEAD_LETTER_STREAM_NAME).unwrap(),
+            &Identifier::named(DEAD_LETTER_TOPIC_NAME).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            MESSAGES_COUNT,
+            false,
+        )
+        .await
+        .unwrap()
+        .messages
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the source stream and topic
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+    // 2. Create the dead letter stream and topic with a single partition
+    client
+        .create_stream(DEAD_LETTER_STREAM_NAME, Some(DEAD_LETTER_STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(DEAD_LETTER_STREAM_ID).unwrap(),
+            DEAD_LETTER_TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            None,
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
@@ -0,0 +1,112 @@
+use crate::server::scenarios::{
+    create_client, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{Client, MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{login_root, ClientFactory, TestServer};
+
+const MESSAGES_COUNT: u32 = 10;
+const SEND_RETRIES: u32 = 10;
+
+pub async fn run(
+    primary_server: &mut TestServer,
+    primary_client_factory: &dyn ClientFactory,
+    standby_client_factory: &dyn ClientFactory,
+    failover_client: Box<dyn Client>,
+) {
+    let primary_client = create_client(primary_client_factory).await;
+    let standby_client = create_client(standby_client_factory).await;
+    for client in [&primary_client, &standby_client] {
+        login_root(client).await;
+        init_system(client).await;
+    }
+
+    // 1. The client configured with both servers is connected to the primary one
+    let client = IggyClient::create(failover_client, None, None);
+    send_messages(&client, "before failover").await;
+    assert_eq!(poll_messages(&primary_client).await, MESSAGES_COUNT);
+
+    // 2. Once the primary server is gone, the client reconnects (and logs in again) to the standby one
+    primary_server.stop();
+    let mut retries = 0;
+    while let Err(error) = try_send_messages(&client, "after failover").await {
+        retries += 1;
+        assert!(
+            retries < SEND_RETRIES,
+            "Failed to send messages after failover: {error}"
+        );
+    }
+    assert_eq!(poll_messages(&standby_client).await, MESSAGES_COUNT);
+
+    standby_client
+        .delete_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap();
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn send_messages(client: &IggyClient, payload: &str) {
+    try_send_messages(client, payload).await.unwrap();
+}
+
+async fn try_send_messages(client: &IggyClient, payload: &str) -> Result<(), IggyError> {
+    let mut messages = (0..MESSAGES_COUNT)
+        .map(|_| Message::new(None, Bytes::from(payload.to_owned()), None))
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+}
+
+async fn poll_messages(client: &IggyClient) -> u32 {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            MESSAGES_COUNT * 2,
+            false,
+        )
+        .await
+        .unwrap()
+        .messages
+        .len() as u32
+}
@@ -0,0 +1,264 @@
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::send_messages::{
+    Message, Partitioning, PRODUCER_EPOCH_HEADER, PRODUCER_ID_HEADER, PRODUCER_SEQUENCE_HEADER,
+};
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::models::messages::PolledMessage;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+use std::collections::HashMap;
+
+const PRODUCER_ID: u64 = 123;
+const MESSAGES_COUNT: u64 = 10;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Send the messages using the idempotent producer
+    let mut producer = client
+        .producer(STREAM_NAME, TOPIC_NAME)
+        .unwrap()
+        .partitioning(Partitioning::partition_id(PARTITION_ID))
+        .idempotence(Some(PRODUCER_ID))
+        .without_send_interval()
+        .do_not_create_stream_if_not_exists()
+        .do_not_create_topic_if_not_exists()
+        .build();
+    producer.init().await.unwrap();
+    assert_eq!(producer.producer_id(), Some(PRODUCER_ID));
+    let messages = (1..=MESSAGES_COUNT)
+        .map(|sequence| Message::new(None, create_payload(sequence), None))
+        .collect::<Vec<_>>();
+    producer.send(messages).await.unwrap();
+
+    let polled_messages = poll_messages(&client).await;
+    assert_eq!(polled_messages.len() as u64, MESSAGES_COUNT);
+    for (index, message) in polled_messages.iter().enumerate() {
+        let sequence = index as u64 + 1;
+        assert_eq!(message.payload, create_payload(sequence));
+        let headers = message.headers.as_ref().unwrap();
+        assert_eq!(
+            headers
+                .get(&HeaderKey::new(PRODUCER_SEQUENCE_HEADER).unwrap())
+                .unwrap()
+                .as_uint64()
+                .unwrap(),
+            sequence
+        );
+    }
+
+    // 2. Resend the already appended sequences (e.g. retry after a timeout) along with the new ones
+    let epoch = polled_messages[0]
+        .headers
+        .as_ref()
+        .unwrap()
+        .get(&HeaderKey::new(PRODUCER_EPOCH_HEADER).unwrap())
+        .unwrap()
+        .as_uint64()
+        .unwrap();
+    assert_eq!(epoch, 1);
+    let create_messages = |epoch: u64, sequences: std::ops::RangeInclusive<u64>| {
+        sequences
+            .map(|sequence| {
+                let headers = HashMap::from([
+                    (
+                        HeaderKey::new(PRODUCER_ID_HEADER).unwrap(),
+                        HeaderValue::from_uint64(PRODUCER_ID).unwrap(),
+                    ),
+                    (
+                        HeaderKey::new(PRODUCER_EPOCH_HEADER).unwrap(),
+                        HeaderValue::from_uint64(epoch).unwrap(),
+                    ),
+                    (
+                        HeaderKey::new(PRODUCER_SEQUENCE_HEADER).unwrap(),
+                        HeaderValue::from_uint64(sequence).unwrap(),
+                    ),
+                ]);
+                Message::new(None, create_payload(sequence), Some(headers))
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut messages = create_messages(epoch, MESSAGES_COUNT - 4..=MESSAGES_COUNT + 2);
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+
+    // 3. Only the new sequences should be appended
+    let polled_messages = poll_messages(&client).await;
+    assert_eq!(polled_messages.len() as u64, MESSAGES_COUNT + 2);
+    for (index, message) in polled_messages.iter().enumerate() {
+        assert_eq!(message.offset, index as u64);
+        assert_eq!(message.payload, create_payload(index as u64 + 1));
+    }
+
+    // 4. The restarted producer starts the new epoch, so its sequences aren't discarded as the duplicates
+    let mut producer = client
+        .producer(STREAM_NAME, TOPIC_NAME)
+        .unwrap()
+        .partitioning(Partitioning::partition_id(PARTITION_ID))
+        .idempotence(Some(PRODUCER_ID))
+        .without_send_interval()
+        .do_not_create_stream_if_not_exists()
+        .do_not_create_topic_if_not_exists()
+        .build();
+    producer.init().await.unwrap();
+    producer
+        .send(vec![Message::new(
+            None,
+            create_payload(MESSAGES_COUNT + 3),
+            None,
+        )])
+        .await
+        .unwrap();
+    let polled_messages = poll_messages(&client).await;
+    assert_eq!(polled_messages.len() as u64, MESSAGES_COUNT + 3);
+    let new_epoch = polled_messages[MESSAGES_COUNT as usize + 2]
+        .headers
+        .as_ref()
+        .unwrap()
+        .get(&HeaderKey::new(PRODUCER_EPOCH_HEADER).unwrap())
+        .unwrap()
+        .as_uint64()
+        .unwrap();
+    assert_eq!(new_epoch, epoch + 1);
+
+    // 5. The messages of the previous epoch are rejected
+    let mut messages = create_messages(epoch, MESSAGES_COUNT + 3..=MESSAGES_COUNT + 3);
+    let result = client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await;
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::ProducerFenced(0, 0, 0).as_code()
+    );
+
+    // 6. The sequences leaving a gap after the last one (e.g. the lost batch) are rejected
+    let mut messages = create_messages(new_epoch, 3..=3);
+    let result = client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await;
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::OutOfOrderSequence(0, 0, 0).as_code()
+    );
+
+    // 7. Idempotent producer resolves the balanced partitioning itself, so the sequences are assigned per partition
+    let mut producer = client
+        .producer(STREAM_NAME, TOPIC_NAME)
+        .unwrap()
+        .partitioning(Partitioning::balanced())
+        .idempotence(None)
+        .without_send_interval()
+        .do_not_create_stream_if_not_exists()
+        .do_not_create_topic_if_not_exists()
+        .build();
+    producer.init().await.unwrap();
+    let producer_id = producer.producer_id().unwrap();
+    for sequence in 1..=2 * PARTITIONS_COUNT as u64 {
+        producer
+            .send(vec![Message::new(None, create_payload(sequence), None)])
+            .await
+            .unwrap();
+    }
+    for partition_id in 1..=PARTITIONS_COUNT {
+        let sequences = poll_partition_messages(&client, partition_id)
+            .await
+            .iter()
+            .filter_map(|message| {
+                let headers = message.headers.as_ref()?;
+                let message_producer_id = headers
+                    .get(&HeaderKey::new(PRODUCER_ID_HEADER).unwrap())?
+                    .as_uint64()
+                    .unwrap();
+                (message_producer_id == producer_id).then(|| {
+                    headers
+                        .get(&HeaderKey::new(PRODUCER_SEQUENCE_HEADER).unwrap())
+                        .unwrap()
+                        .as_uint64()
+                        .unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(sequences, vec![1, 2]);
+    }
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+fn create_payload(sequence: u64) -> Bytes {
+    Bytes::from(format!("message {sequence}"))
+}
+
+async fn poll_messages(client: &IggyClient) -> Vec<PolledMessage> {
+    poll_partition_messages(client, PARTITION_ID).await
+}
+
+async fn poll_partition_messages(client: &IggyClient, partition_id: u32) -> Vec<PolledMessage> {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(partition_id),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            100,
+            false,
+        )
+        .await
+        .unwrap()
+        .messages
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
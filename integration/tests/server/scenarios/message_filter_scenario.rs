@@ -0,0 +1,172 @@
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{ConsumerOffsetClient, MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::message_filter::{HeaderCondition, MessageFilter};
+use iggy::messages::poll_messages::{IsolationLevel, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::models::messages::PolledMessage;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const MESSAGES_COUNT: u32 = 20;
+const TENANTS: [&str; 4] = ["acme", "globex", "initech", "umbrella"];
+const CONSUMER_ID: u32 = 1;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Send the messages for the different tenants, every other message has the priority header
+    let mut messages = (0..MESSAGES_COUNT)
+        .map(|offset| {
+            let mut headers = HashMap::from([(
+                HeaderKey::new("tenant").unwrap(),
+                HeaderValue::from_str(TENANTS[offset as usize % TENANTS.len()]).unwrap(),
+            )]);
+            if offset % 2 == 0 {
+                headers.insert(
+                    HeaderKey::new("priority").unwrap(),
+                    HeaderValue::from_uint32(offset).unwrap(),
+                );
+            }
+            Message::new(
+                None,
+                Bytes::from(format!("message {offset}")),
+                Some(headers),
+            )
+        })
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+
+    // 2. The empty filter should return all the messages
+    let polled_messages = poll_messages(&client, "", MESSAGES_COUNT, false).await;
+    assert_eq!(polled_messages.len() as u32, MESSAGES_COUNT);
+
+    // 3. Only the messages of a single tenant should be returned
+    let polled_messages =
+        poll_messages(&client, "eq:tenant:string:acme", MESSAGES_COUNT, false).await;
+    assert_offsets(&polled_messages, &[0, 4, 8, 12, 16]);
+
+    // 4. The conditions should be combined, including the set of values and the numeric range
+    let polled_messages = poll_messages(
+        &client,
+        "in:tenant:string:acme|initech;range:priority:uint32:4..12",
+        MESSAGES_COUNT,
+        false,
+    )
+    .await;
+    assert_offsets(&polled_messages, &[4, 6, 8, 10, 12]);
+
+    let filter = MessageFilter::new(vec![
+        HeaderCondition::Exists(HeaderKey::new("priority").unwrap()),
+        HeaderCondition::Range {
+            key: HeaderKey::new("priority").unwrap(),
+            min: Some(HeaderValue::from_uint32(15).unwrap()),
+            max: None,
+        },
+    ]);
+    let polled_messages = poll_messages(&client, &filter.to_string(), MESSAGES_COUNT, false).await;
+    assert_offsets(&polled_messages, &[16, 18]);
+
+    // 5. The count applies to the matching messages, and the auto commit should store the offset of the last matching message
+    let polled_messages = poll_messages(&client, "eq:tenant:string:umbrella", 3, true).await;
+    assert_offsets(&polled_messages, &[3, 7, 11]);
+    let consumer_offset = client
+        .get_consumer_offset(
+            &consumer(),
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(consumer_offset.stored_offset, 11);
+
+    // 6. The filter not matching any message should return no messages
+    let polled_messages = poll_messages(&client, "exists:missing", MESSAGES_COUNT, false).await;
+    assert!(polled_messages.is_empty());
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn poll_messages(
+    client: &IggyClient,
+    filter: &str,
+    count: u32,
+    auto_commit: bool,
+) -> Vec<PolledMessage> {
+    client
+        .poll_messages_with_filter(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &consumer(),
+            &PollingStrategy::offset(0),
+            count,
+            auto_commit,
+            IsolationLevel::ReadUncommitted,
+            &MessageFilter::from_str(filter).unwrap(),
+        )
+        .await
+        .unwrap()
+        .messages
+}
+
+fn assert_offsets(messages: &[PolledMessage], offsets: &[u64]) {
+    assert_eq!(
+        messages
+            .iter()
+            .map(|message| message.offset)
+            .collect::<Vec<_>>(),
+        offsets
+    );
+}
+
+fn consumer() -> Consumer {
+    Consumer::new(Identifier::numeric(CONSUMER_ID).unwrap())
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
@@ -5,15 +5,27 @@ use iggy::identifier::Identifier;
 use iggy::models::consumer_group::ConsumerGroupDetails;
 use integration::test_server::{delete_user, ClientFactory};
 
+pub mod archive_restore_scenario;
+pub mod client_certificate_scenario;
+pub mod cluster_scenario;
 pub mod consumer_group_join_scenario;
 pub mod consumer_group_with_multiple_clients_polling_messages_scenario;
 pub mod consumer_group_with_single_client_polling_messages_scenario;
 pub mod create_message_payload;
+pub mod dead_letter_scenario;
+pub mod failover_scenario;
+pub mod idempotent_producer_scenario;
+pub mod message_filter_scenario;
 pub mod message_headers_scenario;
 pub mod message_size_scenario;
+pub mod pipelining_scenario;
+pub mod role_scenario;
 pub mod stream_size_validation_scenario;
 pub mod system_scenario;
+pub mod token_login_scenario;
+pub mod transaction_scenario;
 pub mod user_scenario;
+pub mod zero_copy_scenario;
 
 const STREAM_ID: u32 = 1;
 const TOPIC_ID: u32 = 1;
@@ -0,0 +1,189 @@
+use crate::server::scenarios::{
+    cleanup, create_client, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME,
+};
+use bytes::Bytes;
+use futures::future::join_all;
+use iggy::client::{MessageClient, StreamClient, SystemClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+const PARTITIONS_COUNT: u32 = 10;
+const MESSAGES_PER_PARTITION: u32 = 20;
+const ORDERED_SENDS_COUNT: u32 = 20;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Send the messages to all the partitions concurrently over the same connection
+    join_all((1..=PARTITIONS_COUNT).map(|partition_id| send_messages(&client, partition_id))).await;
+
+    // 2. Poll the messages from all the partitions concurrently, mixed with the other commands,
+    // to ensure that every response is matched with its request
+    let polls =
+        join_all((1..=PARTITIONS_COUNT).map(|partition_id| poll_messages(&client, partition_id)));
+    let pings = join_all((0..PARTITIONS_COUNT).map(|_| client.ping()));
+    let (polls, pings) = tokio::join!(polls, pings);
+    for ping in pings {
+        ping.unwrap();
+    }
+    for (partition_id, messages) in (1..=PARTITIONS_COUNT).zip(polls) {
+        assert_eq!(messages.len() as u32, MESSAGES_PER_PARTITION);
+        for (offset, payload) in messages.iter().enumerate() {
+            assert_eq!(payload, &create_payload(partition_id, offset as u32));
+        }
+    }
+
+    // 3. Errors of the concurrent requests are returned to the matching callers only
+    let invalid_partition_id = PARTITIONS_COUNT + 1;
+    let invalid_poll = async {
+        client
+            .poll_messages(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                Some(invalid_partition_id),
+                &Consumer::default(),
+                &PollingStrategy::offset(0),
+                MESSAGES_PER_PARTITION,
+                false,
+            )
+            .await
+    };
+    let (invalid_poll, valid_poll) = tokio::join!(invalid_poll, poll_messages(&client, 1));
+    assert!(invalid_poll.is_err());
+    assert_eq!(valid_poll.len() as u32, MESSAGES_PER_PARTITION);
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+/// The sends pipelined over the same connection to the same partition must be appended in the order they were sent.
+pub async fn run_ordered_sends(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Pipeline the single message sends to the same partition
+    let partition_id = 1;
+    let client = &client;
+    join_all((0..ORDERED_SENDS_COUNT).map(|offset| async move {
+        let payload = create_payload(partition_id, offset);
+        let mut messages = vec![Message {
+            id: 0,
+            length: payload.len() as u32,
+            payload,
+            headers: None,
+        }];
+        client
+            .send_messages(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                &Partitioning::partition_id(partition_id),
+                &mut messages,
+            )
+            .await
+            .unwrap();
+    }))
+    .await;
+
+    // 2. The offsets of the messages should follow the order of the sends
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(partition_id),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            ORDERED_SENDS_COUNT,
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(polled_messages.messages.len() as u32, ORDERED_SENDS_COUNT);
+    for (offset, message) in polled_messages.messages.iter().enumerate() {
+        assert_eq!(message.offset, offset as u64);
+        assert_eq!(message.payload, create_payload(partition_id, offset as u32));
+    }
+
+    cleanup(client, false).await;
+    assert_clean_system(client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+fn create_payload(partition_id: u32, offset: u32) -> Bytes {
+    Bytes::from(format!("partition {partition_id}, message {offset}"))
+}
+
+async fn send_messages(client: &IggyClient, partition_id: u32) {
+    let mut messages = (0..MESSAGES_PER_PARTITION)
+        .map(|offset| {
+            let payload = create_payload(partition_id, offset);
+            Message {
+                id: 0,
+                length: payload.len() as u32,
+                payload,
+                headers: None,
+            }
+        })
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(partition_id),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn poll_messages(client: &IggyClient, partition_id: u32) -> Vec<Bytes> {
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(partition_id),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            MESSAGES_PER_PARTITION,
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(polled_messages.partition_id, partition_id);
+    polled_messages
+        .messages
+        .into_iter()
+        .map(|message| message.payload)
+        .collect()
+}
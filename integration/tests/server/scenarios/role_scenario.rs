@@ -0,0 +1,82 @@
+use crate::server::scenarios::{create_client, USERNAME_1};
+use iggy::client::{RoleClient, StreamClient, UserClient};
+use iggy::identifier::Identifier;
+use iggy::models::permissions::{GlobalPermissions, Permissions};
+use iggy::models::user_status::UserStatus;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+const ROLE_NAME: &str = "readers";
+const PASSWORD: &str = "secret";
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let root_client = create_client(client_factory).await;
+    login_root(&root_client).await;
+    let user_id = root_client
+        .create_user(USERNAME_1, PASSWORD, UserStatus::Active, None)
+        .await
+        .unwrap()
+        .id;
+    let user_client = create_client(client_factory).await;
+    user_client.login_user(USERNAME_1, PASSWORD).await.unwrap();
+    let role_id = Identifier::named(ROLE_NAME).unwrap();
+    let user = Identifier::named(USERNAME_1).unwrap();
+
+    // 1. The user without permissions cannot read the streams
+    assert!(user_client.get_streams().await.is_err());
+
+    // 2. The created role is not assigned to any user
+    let role = root_client
+        .create_role(ROLE_NAME, Some(get_permissions(true, false)))
+        .await
+        .unwrap();
+    assert_eq!(role.name, ROLE_NAME);
+    assert!(role.users.is_empty());
+    assert!(root_client.create_role(ROLE_NAME, None).await.is_err());
+
+    // 3. The assigned role grants its permissions to the user
+    root_client.assign_role(&role_id, &user).await.unwrap();
+    assert!(root_client.assign_role(&role_id, &user).await.is_err());
+    user_client.get_streams().await.unwrap();
+    assert!(user_client.get_roles().await.is_err());
+    let role = root_client.get_role(&role_id).await.unwrap().unwrap();
+    assert_eq!(role.users, vec![user_id]);
+    let roles = root_client.get_roles().await.unwrap();
+    assert_eq!(roles.len(), 1);
+    assert_eq!(roles[0].users_count, 1);
+
+    // 4. The updated role permissions are applied to all its users
+    root_client
+        .update_role(&role_id, None, Some(get_permissions(false, true)))
+        .await
+        .unwrap();
+    assert!(user_client.get_streams().await.is_err());
+    let roles = user_client.get_roles().await.unwrap();
+    assert_eq!(roles.len(), 1);
+
+    // 5. The unassigned role no longer grants its permissions
+    root_client.unassign_role(&role_id, &user).await.unwrap();
+    assert!(root_client.unassign_role(&role_id, &user).await.is_err());
+    assert!(user_client.get_roles().await.is_err());
+
+    // 6. The deleted role no longer grants its permissions
+    root_client.assign_role(&role_id, &user).await.unwrap();
+    user_client.get_roles().await.unwrap();
+    root_client.delete_role(&role_id).await.unwrap();
+    assert!(user_client.get_roles().await.is_err());
+    assert!(root_client.get_role(&role_id).await.unwrap().is_none());
+    assert!(root_client.get_roles().await.unwrap().is_empty());
+
+    root_client.delete_user(&user).await.unwrap();
+    assert_clean_system(&root_client).await;
+}
+
+fn get_permissions(read_streams: bool, read_users: bool) -> Permissions {
+    Permissions {
+        global: GlobalPermissions {
+            read_streams,
+            read_users,
+            ..Default::default()
+        },
+        streams: None,
+    }
+}
@@ -12,12 +12,16 @@ use iggy::client::{
 };
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::compression::compression_level::CompressionLevel;
 use iggy::consumer::Consumer;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::messages::PolledMessage;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::topics::replication_factor::ReplicationFactor;
 use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
@@ -107,9 +111,11 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             PARTITIONS_COUNT,
             Default::default(),
             None,
+            None,
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -197,9 +203,11 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             PARTITIONS_COUNT,
             Default::default(),
             None,
+            None,
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await;
     assert!(create_topic_result.is_err());
@@ -212,9 +220,11 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             PARTITIONS_COUNT,
             Default::default(),
             None,
+            None,
             Some(TOPIC_ID + 1),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await;
     assert!(create_topic_result.is_err());
@@ -334,6 +344,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             Some(PARTITION_ID),
             stored_offset,
+            None,
         )
         .await
         .unwrap();
@@ -384,6 +395,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             Some(PARTITION_ID),
             stored_offset,
+            None,
         )
         .await
         .unwrap();
@@ -442,12 +454,17 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             CONSUMER_GROUP_NAME,
             Some(CONSUMER_GROUP_ID),
+            PartitionAssignmentStrategy::Sticky,
         )
         .await
         .unwrap();
 
     assert_eq!(consumer_group.id, CONSUMER_GROUP_ID);
     assert_eq!(consumer_group.name, CONSUMER_GROUP_NAME);
+    assert_eq!(
+        consumer_group.assignment_strategy,
+        PartitionAssignmentStrategy::Sticky
+    );
 
     // 31. Get the consumer groups and validate that there is one group
     let consumer_groups = client
@@ -463,6 +480,10 @@ pub async fn run(client_factory: &dyn ClientFactory) {
     assert_eq!(consumer_group.id, CONSUMER_GROUP_ID);
     assert_eq!(consumer_group.partitions_count, PARTITIONS_COUNT);
     assert_eq!(consumer_group.members_count, 0);
+    assert_eq!(
+        consumer_group.assignment_strategy,
+        PartitionAssignmentStrategy::Sticky
+    );
 
     // 32. Get the consumer group details
     let consumer_group = client
@@ -490,15 +511,34 @@ pub async fn run(client_factory: &dyn ClientFactory) {
         .await;
 
     match result {
-        Ok(_) => {
+        Ok(membership) => {
             let consumer_group = get_consumer_group(&client).await;
             assert_eq!(consumer_group.id, CONSUMER_GROUP_ID);
             assert_eq!(consumer_group.partitions_count, PARTITIONS_COUNT);
             assert_eq!(consumer_group.name, CONSUMER_GROUP_NAME);
             assert_eq!(consumer_group.members_count, 1);
             assert_eq!(consumer_group.members.len(), 1);
+            assert_eq!(consumer_group.generation, membership.generation);
             let member = &consumer_group.members[0];
             assert_eq!(member.partitions_count, PARTITIONS_COUNT);
+            assert_eq!(member.id, membership.member_id);
+            assert_eq!(membership.partitions.len() as u32, PARTITIONS_COUNT);
+
+            let group_consumer = Consumer::group(Identifier::numeric(CONSUMER_GROUP_ID).unwrap());
+            let stale_offset = client
+                .store_consumer_offset(
+                    &group_consumer,
+                    &Identifier::numeric(STREAM_ID).unwrap(),
+                    &Identifier::numeric(TOPIC_ID).unwrap(),
+                    Some(PARTITION_ID),
+                    0,
+                    Some(membership.generation + 1),
+                )
+                .await;
+            assert_eq!(
+                stale_offset.unwrap_err().as_code(),
+                IggyError::StaleConsumerGroupGeneration(0, 0, 0).as_code()
+            );
 
             let me = client.get_me().await.unwrap();
             assert!(me.client_id > 0);
@@ -596,6 +636,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
     let message_expiry_duration = updated_message_expiry.into();
     let updated_max_topic_size = MaxTopicSize::Custom(IggyByteSize::from_str("2 GB").unwrap());
     let updated_replication_factor = 5;
+    let updated_compression_level = 6;
 
     client
         .update_topic(
@@ -603,9 +644,11 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             &updated_topic_name,
             CompressionAlgorithm::Gzip,
-            Some(updated_replication_factor),
+            Some(CompressionLevel(updated_compression_level)),
+            Some(ReplicationFactor(updated_replication_factor)),
             IggyExpiry::ExpireDuration(message_expiry_duration),
             updated_max_topic_size,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -628,6 +671,10 @@ pub async fn run(client_factory: &dyn ClientFactory) {
         updated_topic.compression_algorithm,
         CompressionAlgorithm::Gzip
     );
+    assert_eq!(
+        updated_topic.compression_level,
+        Some(updated_compression_level)
+    );
     assert_eq!(updated_topic.max_topic_size, updated_max_topic_size);
     assert_eq!(updated_topic.replication_factor, updated_replication_factor);
 
@@ -745,8 +792,10 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             CompressionAlgorithm::default(),
             None,
             None,
+            None,
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
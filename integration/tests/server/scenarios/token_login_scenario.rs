@@ -0,0 +1,207 @@
+use crate::server::scenarios::{create_client, USERNAME_1, USERNAME_2, USERNAME_3};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use iggy::client::{RoleClient, StreamClient, UserClient};
+use iggy::identifier::Identifier;
+use iggy::models::permissions::{GlobalPermissions, Permissions};
+use iggy::models::user_status::UserStatus;
+use iggy::users::defaults::DEFAULT_ROOT_USERNAME;
+use iggy::utils::timestamp::IggyTimestamp;
+use integration::test_server::{create_user, delete_user, login_root, ClientFactory};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tempfile::TempDir;
+
+const ISSUER: &str = "https://sso.iggy.local";
+const AUDIENCE: &str = "iggy-test";
+const KEY_ID: &str = "test-key";
+const GROUP: &str = "iggy-analytics";
+const ROLE_GROUP: &str = "iggy-readers";
+const ROLE_NAME: &str = "readers";
+const PASSWORD: &str = "secret";
+
+pub async fn run(client_factory: &dyn ClientFactory, identity_provider: &TestIdentityProvider) {
+    let root_client = create_client(client_factory).await;
+    login_root(&root_client).await;
+    create_user(&root_client, USERNAME_1).await;
+    create_user(&root_client, USERNAME_2).await;
+    let user_1 = get_user_id(&root_client, USERNAME_1).await;
+    let user_2 = get_user_id(&root_client, USERNAME_2).await;
+
+    // 1. The token of the subject mapped to the existing user logs in this user
+    let client = create_client(client_factory).await;
+    let identity = client
+        .login_with_token(&identity_provider.create_token(USERNAME_1, &[GROUP]))
+        .await
+        .unwrap();
+    assert_eq!(identity.user_id, user_1);
+    client.get_streams().await.unwrap();
+
+    // 2. The token of the subject without the user logs in the user mapped to its group
+    let client = create_client(client_factory).await;
+    let identity = client
+        .login_with_token(&identity_provider.create_token("jane.doe", &["other", GROUP]))
+        .await
+        .unwrap();
+    assert_eq!(identity.user_id, user_2);
+    client.get_streams().await.unwrap();
+
+    // 3. The token of the subject without the mapped user and group is rejected
+    let client = create_client(client_factory).await;
+    assert!(client
+        .login_with_token(&identity_provider.create_token("jane.doe", &["other"]))
+        .await
+        .is_err());
+
+    // 4. The token with the username claim of the existing user, which isn't mapped to its subject, is rejected
+    assert!(client
+        .login_with_token(&identity_provider.create_token(DEFAULT_ROOT_USERNAME, &[]))
+        .await
+        .is_err());
+
+    // 5. The token signed by the unknown key is rejected
+    let other_identity_provider = TestIdentityProvider::generate();
+    assert!(client
+        .login_with_token(&other_identity_provider.create_token(USERNAME_1, &[]))
+        .await
+        .is_err());
+
+    // 6. The group of the token grants its role to the user without the permissions, until it logs in without the group
+    root_client
+        .create_user(USERNAME_3, PASSWORD, UserStatus::Active, None)
+        .await
+        .unwrap();
+    let role_id = Identifier::named(ROLE_NAME).unwrap();
+    root_client
+        .create_role(
+            ROLE_NAME,
+            Some(Permissions {
+                global: GlobalPermissions {
+                    read_streams: true,
+                    ..Default::default()
+                },
+                streams: None,
+            }),
+        )
+        .await
+        .unwrap();
+    let client = create_client(client_factory).await;
+    client
+        .login_with_token(&identity_provider.create_token(USERNAME_3, &[]))
+        .await
+        .unwrap();
+    assert!(client.get_streams().await.is_err());
+
+    let client = create_client(client_factory).await;
+    client
+        .login_with_token(&identity_provider.create_token(USERNAME_3, &[ROLE_GROUP]))
+        .await
+        .unwrap();
+    client.get_streams().await.unwrap();
+    let role = root_client.get_role(&role_id).await.unwrap().unwrap();
+    assert!(role.users.is_empty());
+
+    let other_client = create_client(client_factory).await;
+    other_client
+        .login_with_token(&identity_provider.create_token(USERNAME_3, &[]))
+        .await
+        .unwrap();
+    assert!(other_client.get_streams().await.is_err());
+    assert!(client.get_streams().await.is_err());
+
+    root_client.delete_role(&role_id).await.unwrap();
+    delete_user(&root_client, USERNAME_1).await;
+    delete_user(&root_client, USERNAME_2).await;
+    delete_user(&root_client, USERNAME_3).await;
+}
+
+async fn get_user_id(client: &impl UserClient, username: &str) -> u32 {
+    client
+        .get_user(&Identifier::named(username).unwrap())
+        .await
+        .unwrap()
+        .expect("User not found")
+        .id
+}
+
+/// The external identity provider, signing the tokens with the key published in the JWKS file.
+pub struct TestIdentityProvider {
+    directory: TempDir,
+    encoding_key: EncodingKey,
+}
+
+impl TestIdentityProvider {
+    pub fn generate() -> Self {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let key_pair =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+                .unwrap();
+        let public_key = key_pair.public_key().as_ref();
+        let jwks = json!({
+            "keys": [{
+                "kty": "EC",
+                "crv": "P-256",
+                "use": "sig",
+                "alg": "ES256",
+                "kid": KEY_ID,
+                "x": URL_SAFE_NO_PAD.encode(&public_key[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&public_key[33..65]),
+            }]
+        });
+
+        let identity_provider = Self {
+            directory: TempDir::new().unwrap(),
+            encoding_key: EncodingKey::from_ec_der(pkcs8.as_ref()),
+        };
+        std::fs::write(identity_provider.jwks_file(), jwks.to_string()).unwrap();
+        identity_provider
+    }
+
+    /// Returns the server config, which trusts the tokens of this identity provider.
+    pub fn server_envs(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("IGGY_OIDC_ENABLED".to_owned(), "true".to_owned()),
+            ("IGGY_OIDC_ISSUER".to_owned(), ISSUER.to_owned()),
+            ("IGGY_OIDC_AUDIENCE".to_owned(), AUDIENCE.to_owned()),
+            ("IGGY_OIDC_JWKS".to_owned(), self.jwks_file()),
+            (
+                "IGGY_OIDC_SUBJECTS".to_owned(),
+                format!("[subject-{USERNAME_1}={USERNAME_1},subject-{USERNAME_3}={USERNAME_3}]"),
+            ),
+            (
+                "IGGY_OIDC_GROUPS".to_owned(),
+                format!("[{GROUP}={USERNAME_2}]"),
+            ),
+            (
+                "IGGY_OIDC_GROUP_ROLES".to_owned(),
+                format!("[{ROLE_GROUP}={ROLE_NAME}]"),
+            ),
+        ])
+    }
+
+    pub fn create_token(&self, username: &str, groups: &[&str]) -> String {
+        let claims: Value = json!({
+            "iss": ISSUER,
+            "aud": AUDIENCE,
+            "sub": format!("subject-{username}"),
+            "exp": IggyTimestamp::now().to_secs() + 60,
+            "preferred_username": username,
+            "groups": groups,
+        });
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(KEY_ID.to_owned());
+        encode(&header, &claims, &self.encoding_key).unwrap()
+    }
+
+    fn jwks_file(&self) -> String {
+        self.directory
+            .path()
+            .join("jwks.json")
+            .to_string_lossy()
+            .to_string()
+    }
+}
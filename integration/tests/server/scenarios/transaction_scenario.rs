@@ -0,0 +1,174 @@
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{
+    ConsumerOffsetClient, MessageClient, StreamClient, TopicClient, TransactionClient,
+};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{IsolationLevel, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::messages::PolledMessage;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::transactions::commit_transaction::TransactionOffset;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+const MESSAGES_COUNT: u64 = 5;
+const CONSUMER_ID: u32 = 1;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    let mut producer = client
+        .producer(STREAM_NAME, TOPIC_NAME)
+        .unwrap()
+        .partitioning(Partitioning::partition_id(PARTITION_ID))
+        .without_send_interval()
+        .do_not_create_stream_if_not_exists()
+        .do_not_create_topic_if_not_exists()
+        .build();
+    producer.init().await.unwrap();
+
+    // 1. Messages sent within the open transaction should be visible only to the `read uncommitted` consumers
+    let transaction_id = producer.begin_transaction().await.unwrap();
+    assert_eq!(producer.transaction_id(), Some(transaction_id));
+    assert_eq!(
+        producer.begin_transaction().await.unwrap_err().as_code(),
+        IggyError::TransactionAlreadyInProgress.as_code()
+    );
+    producer.send(create_messages(0)).await.unwrap();
+
+    let polled_messages = poll_messages(&client, IsolationLevel::ReadUncommitted).await;
+    assert_eq!(polled_messages.len() as u64, MESSAGES_COUNT);
+    let polled_messages = poll_messages(&client, IsolationLevel::ReadCommitted).await;
+    assert!(polled_messages.is_empty());
+
+    // 2. Once committed, the messages and the consumer offset stored along with the transaction should be visible
+    let offset = TransactionOffset {
+        consumer: consumer(),
+        stream_id: Identifier::numeric(STREAM_ID).unwrap(),
+        topic_id: Identifier::numeric(TOPIC_ID).unwrap(),
+        partition_id: Some(PARTITION_ID),
+        offset: MESSAGES_COUNT - 1,
+        generation: None,
+    };
+    producer.commit_transaction(&[offset]).await.unwrap();
+    assert_eq!(producer.transaction_id(), None);
+
+    let polled_messages = poll_messages(&client, IsolationLevel::ReadCommitted).await;
+    assert_eq!(polled_messages.len() as u64, MESSAGES_COUNT);
+    let consumer_offset = client
+        .get_consumer_offset(
+            &consumer(),
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(consumer_offset.stored_offset, MESSAGES_COUNT - 1);
+
+    // 3. Messages of the aborted transaction should never be visible to the `read committed` consumers
+    producer.begin_transaction().await.unwrap();
+    producer
+        .send(create_messages(MESSAGES_COUNT))
+        .await
+        .unwrap();
+    producer.abort_transaction().await.unwrap();
+    assert_eq!(
+        producer.abort_transaction().await.unwrap_err().as_code(),
+        IggyError::NoTransactionInProgress.as_code()
+    );
+    producer
+        .send(create_messages(2 * MESSAGES_COUNT))
+        .await
+        .unwrap();
+
+    let polled_messages = poll_messages(&client, IsolationLevel::ReadUncommitted).await;
+    assert_eq!(polled_messages.len() as u64, 3 * MESSAGES_COUNT);
+    let polled_messages = poll_messages(&client, IsolationLevel::ReadCommitted).await;
+    assert_eq!(polled_messages.len() as u64, 2 * MESSAGES_COUNT);
+    for (index, message) in polled_messages.iter().enumerate() {
+        let index = index as u64;
+        let sequence = if index < MESSAGES_COUNT {
+            index
+        } else {
+            index + MESSAGES_COUNT
+        };
+        assert_eq!(message.offset, sequence);
+        assert_eq!(message.payload, create_payload(sequence));
+    }
+
+    // 4. Unknown transaction cannot be committed
+    let result = client.commit_transaction(transaction_id, &[]).await;
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::TransactionNotFound(transaction_id).as_code()
+    );
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+fn consumer() -> Consumer {
+    Consumer::new(Identifier::numeric(CONSUMER_ID).unwrap())
+}
+
+fn create_payload(sequence: u64) -> Bytes {
+    Bytes::from(format!("message {sequence}"))
+}
+
+fn create_messages(start: u64) -> Vec<Message> {
+    (start..start + MESSAGES_COUNT)
+        .map(|sequence| Message::new(None, create_payload(sequence), None))
+        .collect()
+}
+
+async fn poll_messages(client: &IggyClient, isolation_level: IsolationLevel) -> Vec<PolledMessage> {
+    client
+        .poll_messages_with_isolation_level(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            100,
+            false,
+            isolation_level,
+        )
+        .await
+        .unwrap()
+        .messages
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
@@ -0,0 +1,231 @@
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{ConsumerOffsetClient, MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::models::messages::PolledMessages;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{
+    assert_clean_system, login_root, ClientFactory, IpAddrKind, TestServer,
+};
+use std::collections::HashMap;
+
+const COMPRESSED_TOPIC_ID: u32 = 2;
+const COMPRESSED_TOPIC_NAME: &str = "test-compressed-topic";
+const BATCHES_COUNT: u32 = 3;
+const MESSAGES_PER_BATCH: u32 = 10;
+const UNSAVED_MESSAGES_COUNT: u32 = 5;
+
+pub fn create_test_server() -> TestServer {
+    let extra_envs = HashMap::from([
+        ("IGGY_TCP_ZERO_COPY".to_string(), "true".to_string()),
+        (
+            "IGGY_SYSTEM_COMPRESSION_ALLOW_OVERRIDE".to_string(),
+            "true".to_string(),
+        ),
+    ]);
+    TestServer::new(Some(extra_envs), true, None, IpAddrKind::V4)
+}
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    for topic_id in [TOPIC_ID, COMPRESSED_TOPIC_ID] {
+        let topic = Identifier::numeric(topic_id).unwrap();
+
+        // 1. Send the batches of messages and flush them to the segment log file
+        for batch in 0..BATCHES_COUNT {
+            let mut messages = (0..MESSAGES_PER_BATCH)
+                .map(|index| create_message(batch * MESSAGES_PER_BATCH + index))
+                .collect::<Vec<_>>();
+            send_messages(&client, &topic, &mut messages).await;
+        }
+        client
+            .flush_unsaved_buffer(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &topic,
+                PARTITION_ID,
+                false,
+            )
+            .await
+            .unwrap();
+
+        // 2. Poll all the persisted messages, which are sent as the batches
+        let saved_messages_count = BATCHES_COUNT * MESSAGES_PER_BATCH;
+        let polled_messages = poll_messages(
+            &client,
+            &topic,
+            &PollingStrategy::offset(0),
+            saved_messages_count,
+            false,
+        )
+        .await;
+        assert_messages(&polled_messages, 0, saved_messages_count);
+
+        // 3. Poll the messages in the middle of the batches, to ensure the messages outside the range are skipped
+        let polled_messages = poll_messages(
+            &client,
+            &topic,
+            &PollingStrategy::offset(5),
+            MESSAGES_PER_BATCH,
+            false,
+        )
+        .await;
+        assert_messages(&polled_messages, 5, MESSAGES_PER_BATCH);
+
+        // 4. Poll the next messages with the auto commit enabled, to ensure the consumer offset is stored
+        let polled_messages = poll_messages(
+            &client,
+            &topic,
+            &PollingStrategy::next(),
+            MESSAGES_PER_BATCH,
+            true,
+        )
+        .await;
+        assert_messages(&polled_messages, 0, MESSAGES_PER_BATCH);
+        let consumer_offset = client
+            .get_consumer_offset(
+                &Consumer::default(),
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &topic,
+                Some(PARTITION_ID),
+            )
+            .await
+            .unwrap()
+            .expect("Consumer offset should be stored");
+        assert_eq!(consumer_offset.stored_offset, MESSAGES_PER_BATCH as u64 - 1);
+
+        // 5. Send the messages which aren't flushed, and poll them as the regular messages
+        let mut messages = (0..UNSAVED_MESSAGES_COUNT)
+            .map(|index| create_message(saved_messages_count + index))
+            .collect::<Vec<_>>();
+        send_messages(&client, &topic, &mut messages).await;
+        let polled_messages = poll_messages(
+            &client,
+            &topic,
+            &PollingStrategy::offset(0),
+            saved_messages_count + UNSAVED_MESSAGES_COUNT,
+            false,
+        )
+        .await;
+        assert_messages(
+            &polled_messages,
+            0,
+            saved_messages_count + UNSAVED_MESSAGES_COUNT,
+        );
+    }
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .await
+        .unwrap();
+    for (topic_id, topic_name, compression_algorithm) in [
+        (TOPIC_ID, TOPIC_NAME, CompressionAlgorithm::None),
+        (
+            COMPRESSED_TOPIC_ID,
+            COMPRESSED_TOPIC_NAME,
+            CompressionAlgorithm::Gzip,
+        ),
+    ] {
+        client
+            .create_topic(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                topic_name,
+                1,
+                compression_algorithm,
+                None,
+                None,
+                Some(topic_id),
+                IggyExpiry::NeverExpire,
+                MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
+            )
+            .await
+            .unwrap();
+    }
+}
+
+fn create_message(offset: u32) -> Message {
+    let payload = Bytes::from(format!("message {}", offset));
+    // Every other message has the headers, to validate both layouts within the same batch.
+    let headers = offset.is_multiple_of(2).then(|| {
+        HashMap::from([(
+            HeaderKey::new("offset").unwrap(),
+            HeaderValue::from_uint32(offset).unwrap(),
+        )])
+    });
+    Message {
+        id: (offset + 1) as u128,
+        length: payload.len() as u32,
+        payload,
+        headers,
+    }
+}
+
+async fn send_messages(client: &IggyClient, topic_id: &Identifier, messages: &mut [Message]) {
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            topic_id,
+            &Partitioning::partition_id(PARTITION_ID),
+            messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn poll_messages(
+    client: &IggyClient,
+    topic_id: &Identifier,
+    strategy: &PollingStrategy,
+    count: u32,
+    auto_commit: bool,
+) -> PolledMessages {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            topic_id,
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            strategy,
+            count,
+            auto_commit,
+        )
+        .await
+        .unwrap()
+}
+
+fn assert_messages(polled_messages: &PolledMessages, start_offset: u32, count: u32) {
+    assert_eq!(polled_messages.partition_id, PARTITION_ID);
+    assert_eq!(polled_messages.messages.len() as u32, count);
+    for (index, message) in polled_messages.messages.iter().enumerate() {
+        let offset = start_offset + index as u32;
+        assert_eq!(message.offset, offset as u64);
+        assert_eq!(message.id, (offset + 1) as u128);
+        assert_eq!(message.payload, Bytes::from(format!("message {}", offset)));
+        match &message.headers {
+            Some(headers) => {
+                assert_eq!(offset % 2, 0);
+                let header = headers.get(&HeaderKey::new("offset").unwrap()).unwrap();
+                assert_eq!(header.as_uint32().unwrap(), offset);
+            }
+            None => assert_eq!(offset % 2, 1),
+        }
+    }
+}
@@ -1,11 +1,25 @@
+use crate::server::scenarios::token_login_scenario::{self, TestIdentityProvider};
 use crate::server::scenarios::{
-    consumer_group_join_scenario, consumer_group_with_multiple_clients_polling_messages_scenario,
+    archive_restore_scenario, cluster_scenario, consumer_group_join_scenario,
+    consumer_group_with_multiple_clients_polling_messages_scenario,
     consumer_group_with_single_client_polling_messages_scenario, create_message_payload,
-    message_headers_scenario, message_size_scenario, stream_size_validation_scenario,
-    system_scenario, user_scenario,
+    dead_letter_scenario, failover_scenario, idempotent_producer_scenario, message_filter_scenario,
+    message_headers_scenario, message_size_scenario, pipelining_scenario, role_scenario,
+    stream_size_validation_scenario, system_scenario, transaction_scenario, user_scenario,
+    zero_copy_scenario,
+};
+use iggy::client::{AutoLogin, Client, Credentials};
+use iggy::tcp::client::TcpClient;
+use iggy::tcp::config::{TcpClientConfig, TcpClientReconnectionConfig};
+use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
+use iggy::utils::duration::IggyDuration;
+use integration::{
+    tcp_client::TcpClientFactory,
+    test_server::{IpAddrKind, TestServer},
 };
-use integration::{tcp_client::TcpClientFactory, test_server::TestServer};
 use serial_test::parallel;
+use std::str::FromStr;
+use std::sync::Arc;
 
 #[tokio::test]
 #[parallel]
@@ -33,6 +47,19 @@ async fn user_scenario_should_be_valid() {
     user_scenario::run(&client_factory).await;
 }
 
+#[tokio::test]
+#[parallel]
+async fn role_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    role_scenario::run(&client_factory).await;
+}
+
 #[tokio::test]
 #[parallel]
 async fn message_headers_scenario_should_be_valid() {
@@ -123,3 +150,207 @@ async fn message_size_scenario_should_be_valid() {
     };
     message_size_scenario::run(&client_factory).await;
 }
+
+#[tokio::test]
+#[parallel]
+async fn dead_letter_scenario_should_be_valid() {
+    let mut test_server = dead_letter_scenario::create_test_server();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    dead_letter_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn cluster_scenario_should_be_valid() {
+    let mut test_servers = cluster_scenario::create_test_servers();
+    for test_server in test_servers.iter_mut() {
+        test_server.start();
+    }
+    cluster_scenario::run(&mut test_servers).await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+#[parallel]
+async fn cluster_leader_rejoin_scenario_should_be_valid() {
+    let mut test_servers = cluster_scenario::create_test_servers();
+    for test_server in test_servers.iter_mut() {
+        test_server.start();
+    }
+    cluster_scenario::run_leader_rejoin(&mut test_servers).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn idempotent_producer_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    idempotent_producer_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn transaction_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    transaction_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn message_filter_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    message_filter_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn archive_restore_scenario_should_be_valid() {
+    let mut test_server = archive_restore_scenario::create_test_server();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    archive_restore_scenario::run(&client_factory, test_server.get_local_data_path()).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn zero_copy_scenario_should_be_valid() {
+    let mut test_server = zero_copy_scenario::create_test_server();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        zero_copy: true,
+        ..Default::default()
+    };
+    zero_copy_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn pipelining_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        pipelining: true,
+        ..Default::default()
+    };
+    pipelining_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn pipelining_ordered_sends_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        pipelining: true,
+        ..Default::default()
+    };
+    pipelining_scenario::run_ordered_sends(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn system_scenario_with_pipelining_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        pipelining: true,
+        ..Default::default()
+    };
+    system_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn failover_scenario_should_be_valid() {
+    let mut primary_server = TestServer::default();
+    let mut standby_server = TestServer::default();
+    primary_server.start();
+    standby_server.start();
+    let primary_client_factory = TcpClientFactory {
+        server_addr: primary_server.get_raw_tcp_addr().unwrap(),
+        ..Default::default()
+    };
+    let standby_client_factory = TcpClientFactory {
+        server_addr: standby_server.get_raw_tcp_addr().unwrap(),
+        ..Default::default()
+    };
+    let failover_client = TcpClient::create(Arc::new(TcpClientConfig {
+        server_addresses: vec![
+            primary_client_factory.server_addr.clone(),
+            standby_client_factory.server_addr.clone(),
+        ],
+        auto_login: AutoLogin::Enabled(Credentials::UsernamePassword(
+            DEFAULT_ROOT_USERNAME.to_owned(),
+            DEFAULT_ROOT_PASSWORD.to_owned(),
+        )),
+        reconnection: TcpClientReconnectionConfig {
+            enabled: true,
+            max_retries: Some(5),
+            interval: IggyDuration::from_str("100ms").unwrap(),
+            reestablish_after: IggyDuration::from_str("100ms").unwrap(),
+        },
+        ..TcpClientConfig::default()
+    }))
+    .unwrap();
+    Client::connect(&failover_client).await.unwrap();
+    failover_scenario::run(
+        &mut primary_server,
+        &primary_client_factory,
+        &standby_client_factory,
+        Box::new(failover_client),
+    )
+    .await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn token_login_scenario_should_be_valid() {
+    let identity_provider = TestIdentityProvider::generate();
+    let mut test_server = TestServer::new(
+        Some(identity_provider.server_envs()),
+        true,
+        None,
+        IpAddrKind::V4,
+    );
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    token_login_scenario::run(&client_factory, &identity_provider).await;
+}
@@ -3,8 +3,11 @@ use bytes::Bytes;
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::streams::create_stream::CreateStream;
 use iggy::users::create_user::CreateUser;
+use iggy::utils::timestamp::IggyTimestamp;
 use server::state::command::EntryCommand;
 use server::state::entry::StateEntry;
+use server::state::snapshot::{StateSnapshot, StateSnapshotHeader};
+use server::state::system::SystemState;
 use server::state::State;
 
 #[tokio::test]
@@ -121,6 +124,54 @@ async fn should_apply_multiple_entries() {
     );
 }
 
+#[tokio::test]
+async fn should_truncate_log_given_snapshot_and_load_only_remaining_entries() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    for stream_id in 1..=3 {
+        let create_stream = EntryCommand::CreateStream(CreateStream {
+            stream_id: None,
+            name: format!("test-{stream_id}"),
+        });
+        state.apply(user_id, create_stream).await.unwrap();
+    }
+
+    let mut entries = state.load_entries().await.unwrap();
+    entries.truncate(2);
+    let snapshot_state = SystemState::init(entries).await.unwrap();
+    let header = StateSnapshotHeader {
+        index: 1,
+        term: 0,
+        leader_id: 0,
+        version: setup.version(),
+        created_at: IggyTimestamp::now(),
+    };
+    state
+        .snapshot(StateSnapshot::new(header, snapshot_state))
+        .await
+        .unwrap();
+
+    let entries = state.load_entries().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].index, 2);
+
+    let entries = state.init().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(state.current_index(), 2);
+    assert_eq!(state.next_index(), 3);
+
+    let snapshot = state.load_snapshot().await.unwrap().unwrap();
+    assert_eq!(snapshot.header.index, header.index);
+    let system_state = SystemState::init_from_snapshot(Some(snapshot.state), entries)
+        .await
+        .unwrap();
+    assert_eq!(system_state.streams.len(), 3);
+    assert_eq!(system_state.current_stream_id, 3);
+}
+
 fn assert_entry(entry: StateEntry, index: u64, version: u32, user_id: u32, command: Bytes) {
     assert_eq!(entry.index, index);
     assert_eq!(entry.term, 0);
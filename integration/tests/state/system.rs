@@ -1,6 +1,12 @@
 use crate::state::StateSetup;
+use iggy::identifier::Identifier;
+use iggy::models::permissions::{GlobalPermissions, Permissions};
 use iggy::partitions::create_partitions::CreatePartitions;
 use iggy::personal_access_tokens::create_personal_access_token::CreatePersonalAccessToken;
+use iggy::roles::assign_role::AssignRole;
+use iggy::roles::create_role::CreateRole;
+use iggy::roles::delete_role::DeleteRole;
+use iggy::roles::update_role::UpdateRole;
 use iggy::streams::create_stream::CreateStream;
 use iggy::streams::delete_stream::DeleteStream;
 use iggy::topics::create_topic::CreateTopic;
@@ -46,10 +52,12 @@ async fn should_be_initialized_based_on_state_entries() {
         topic_id: Some(1),
         partitions_count: 1,
         compression_algorithm: Default::default(),
+        compression_level: None,
         message_expiry: Default::default(),
         max_topic_size: Default::default(),
         name: "topic1".to_string(),
         replication_factor: None,
+        cleanup_policy: Default::default(),
     };
 
     let create_topic1_clone = CreateTopic {
@@ -57,10 +65,12 @@ async fn should_be_initialized_based_on_state_entries() {
         topic_id: Some(1),
         partitions_count: 1,
         compression_algorithm: Default::default(),
+        compression_level: None,
         message_expiry: Default::default(),
         max_topic_size: Default::default(),
         name: "topic1".to_string(),
         replication_factor: None,
+        cleanup_policy: Default::default(),
     };
 
     let create_stream2 = CreateStream {
@@ -73,10 +83,12 @@ async fn should_be_initialized_based_on_state_entries() {
         topic_id: Some(2),
         partitions_count: 1,
         compression_algorithm: Default::default(),
+        compression_level: None,
         message_expiry: Default::default(),
         max_topic_size: Default::default(),
         name: "topic2".to_string(),
         replication_factor: None,
+        cleanup_policy: Default::default(),
     };
 
     let create_partitions = CreatePartitions {
@@ -174,3 +186,61 @@ async fn should_be_initialized_based_on_state_entries() {
     assert_eq!(topic.name, create_topic1_clone.name);
     assert_eq!(topic.partitions.len(), 3);
 }
+
+#[tokio::test]
+async fn roles_should_be_initialized_based_on_state_entries() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    let permissions = Permissions {
+        global: GlobalPermissions {
+            read_users: true,
+            ..Default::default()
+        },
+        streams: None,
+    };
+    let commands = vec![
+        EntryCommand::CreateUser(CreateUser {
+            username: "user".to_string(),
+            password: "secret".to_string(),
+            status: Default::default(),
+            permissions: None,
+        }),
+        EntryCommand::CreateRole(CreateRole {
+            name: "auditors".to_string(),
+            permissions: None,
+        }),
+        EntryCommand::CreateRole(CreateRole {
+            name: "producers".to_string(),
+            permissions: None,
+        }),
+        EntryCommand::UpdateRole(UpdateRole {
+            role_id: Identifier::named("auditors").unwrap(),
+            name: Some("reviewers".to_string()),
+            permissions: Some(permissions.clone()),
+        }),
+        EntryCommand::AssignRole(AssignRole {
+            role_id: Identifier::named("reviewers").unwrap(),
+            user_id: Identifier::named("user").unwrap(),
+        }),
+        EntryCommand::DeleteRole(DeleteRole {
+            role_id: Identifier::numeric(2).unwrap(),
+        }),
+    ];
+    for command in commands {
+        state.apply(user_id, command).await.unwrap();
+    }
+
+    let entries = state.load_entries().await.unwrap();
+    let mut system = SystemState::init(entries).await.unwrap();
+
+    assert_eq!(system.current_role_id, 2);
+    assert_eq!(system.roles.len(), 1);
+    let role = system.roles.remove(&1).unwrap();
+    assert_eq!(role.name, "reviewers");
+    assert_eq!(role.permissions, Some(permissions));
+    assert_eq!(role.users.len(), 1);
+    assert!(role.users.contains(&1));
+}
@@ -1,6 +1,7 @@
 use crate::streaming::common::test_setup::TestSetup;
 use bytes::BytesMut;
 use iggy::bytes_serializable::BytesSerializable;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::messages::send_messages::Message;
 use iggy::models::header::{HeaderKey, HeaderValue};
 use iggy::utils::byte_size::IggyByteSize;
@@ -109,6 +110,8 @@ async fn test_get_messages_by_timestamp(
         config.clone(),
         setup.storage.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
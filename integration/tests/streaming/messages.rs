@@ -1,6 +1,7 @@
 use crate::streaming::common::test_setup::TestSetup;
 use bytes::Bytes;
 use iggy::bytes_serializable::BytesSerializable;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::messages::send_messages::Message;
 use iggy::models::header::{HeaderKey, HeaderValue};
 use iggy::utils::byte_size::IggyByteSize;
@@ -40,6 +41,8 @@ async fn should_persist_messages_and_then_load_them_by_timestamp() {
         config.clone(),
         setup.storage.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -202,6 +205,8 @@ async fn should_persist_messages_and_then_load_them_from_disk() {
         config.clone(),
         setup.storage.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -273,6 +278,8 @@ async fn should_persist_messages_and_then_load_them_from_disk() {
         config.clone(),
         setup.storage.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -1,5 +1,6 @@
 use crate::streaming::common::test_setup::TestSetup;
 use crate::streaming::create_messages;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
@@ -29,6 +30,8 @@ async fn should_persist_partition_with_segment() {
             setup.config.clone(),
             setup.storage.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -61,6 +64,8 @@ async fn should_load_existing_partition_from_disk() {
             setup.config.clone(),
             setup.storage.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -81,6 +86,8 @@ async fn should_load_existing_partition_from_disk() {
             setup.config.clone(),
             setup.storage.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -136,6 +143,8 @@ async fn should_delete_existing_partition_from_disk() {
             setup.config.clone(),
             setup.storage.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -170,6 +179,8 @@ async fn should_purge_existing_partition_on_disk() {
             setup.config.clone(),
             setup.storage.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -1,6 +1,7 @@
 use crate::streaming::common::test_setup::TestSetup;
 use bytes::Bytes;
 use iggy::bytes_serializable::BytesSerializable;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::confirmation::Confirmation;
 use iggy::models::messages::{MessageState, PolledMessage};
 use iggy::utils::byte_size::IggyByteSize;
@@ -31,6 +32,8 @@ async fn should_persist_segment() {
             start_offset,
             setup.config.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -68,6 +71,8 @@ async fn should_load_existing_segment_from_disk() {
             start_offset,
             setup.config.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -94,6 +99,8 @@ async fn should_load_existing_segment_from_disk() {
             start_offset,
             setup.config.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -130,6 +137,8 @@ async fn should_persist_and_load_segment_with_messages() {
         start_offset,
         setup.config.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -180,6 +189,8 @@ async fn should_persist_and_load_segment_with_messages() {
         start_offset,
         setup.config.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -195,6 +206,112 @@ async fn should_persist_and_load_segment_with_messages() {
     assert_eq!(messages.len(), messages_count as usize);
 }
 
+#[tokio::test]
+async fn should_persist_and_load_segment_with_compressed_messages() {
+    let setup = TestSetup::init().await;
+    let compression = [
+        (CompressionAlgorithm::Gzip, None),
+        (CompressionAlgorithm::Gzip, Some(9)),
+        (CompressionAlgorithm::Lz4, None),
+        (CompressionAlgorithm::Zstd, None),
+        (CompressionAlgorithm::Zstd, Some(19)),
+        (CompressionAlgorithm::Snappy, None),
+    ];
+    for (partition_id, (algorithm, level)) in (1..).zip(compression) {
+        assert_persisted_and_loaded_compressed_segment(&setup, partition_id, algorithm, level)
+            .await;
+    }
+}
+
+async fn assert_persisted_and_loaded_compressed_segment(
+    setup: &TestSetup,
+    partition_id: u32,
+    algorithm: CompressionAlgorithm,
+    level: Option<u8>,
+) {
+    let stream_id = 1;
+    let topic_id = 2;
+    let start_offset = 0;
+    let mut segment = Segment::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        start_offset,
+        setup.config.clone(),
+        IggyExpiry::NeverExpire,
+        algorithm,
+        level,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    );
+
+    setup
+        .create_partition_directory(stream_id, topic_id, partition_id)
+        .await;
+    segment.persist().await.unwrap();
+    let messages_count = 10;
+    let payload = "{\"name\": \"iggy\", \"kind\": \"message\"}".repeat(10);
+    let mut messages = Vec::new();
+    let mut batch_size = IggyByteSize::default();
+    for i in 0..messages_count {
+        let message = create_message(i, &payload, IggyTimestamp::now());
+
+        let retained_message = Arc::new(RetainedMessage {
+            id: message.id,
+            offset: message.offset,
+            timestamp: message.timestamp,
+            checksum: message.checksum,
+            message_state: message.state,
+            headers: message.headers.map(|headers| headers.to_bytes()),
+            payload: message.payload.clone(),
+        });
+        batch_size += retained_message.get_size_bytes();
+        messages.push(retained_message);
+    }
+
+    segment
+        .append_batch(batch_size, messages_count as u32, &messages)
+        .await
+        .unwrap();
+    segment.persist_messages(None).await.unwrap();
+    let log_size = fs::metadata(&segment.log_path).await.unwrap().len();
+    assert!(log_size < batch_size.as_bytes_u64());
+
+    // Compressed batches are decompressed transparently, regardless of the current algorithm.
+    let mut loaded_segment = Segment::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        start_offset,
+        setup.config.clone(),
+        IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        None,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    );
+    loaded_segment.load_from_disk().await.unwrap();
+    let loaded_messages = loaded_segment
+        .get_messages_by_offset(0, messages_count as u32)
+        .await
+        .unwrap();
+    assert_eq!(loaded_messages.len(), messages_count as usize);
+    for (index, message) in loaded_messages.iter().enumerate() {
+        assert_eq!(message.offset, index as u64);
+        assert_eq!(message.payload, messages[index].payload);
+        assert_eq!(message.checksum, messages[index].checksum);
+    }
+    loaded_segment.load_message_checksums().await.unwrap();
+}
+
 #[tokio::test]
 async fn should_persist_and_load_segment_with_messages_with_nowait_confirmation() {
     let setup = TestSetup::init_with_config(SystemConfig {
@@ -216,6 +333,8 @@ async fn should_persist_and_load_segment_with_messages_with_nowait_confirmation(
         start_offset,
         setup.config.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -270,6 +389,8 @@ async fn should_persist_and_load_segment_with_messages_with_nowait_confirmation(
         start_offset,
         setup.config.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -301,6 +422,8 @@ async fn given_all_expired_messages_segment_should_be_expired() {
         start_offset,
         setup.config.clone(),
         message_expiry,
+        CompressionAlgorithm::None,
+        None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -368,6 +491,8 @@ async fn given_at_least_one_not_expired_message_segment_should_not_be_expired()
         start_offset,
         setup.config.clone(),
         message_expiry,
+        CompressionAlgorithm::None,
+        None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -4,6 +4,7 @@ use ahash::AHashMap;
 use iggy::identifier::Identifier;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::Partitioning;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
@@ -62,6 +63,8 @@ async fn should_load_existing_stream_from_disk() {
             created_at: IggyTimestamp::now(),
             topics: AHashMap::new(),
             current_topic_id: 0,
+            owner_id: 0,
+            quota: None,
         };
         loaded_stream.load(state).await.unwrap();
 
@@ -118,8 +121,10 @@ async fn should_purge_existing_stream_on_disk() {
                 1,
                 IggyExpiry::NeverExpire,
                 Default::default(),
+                None,
                 MaxTopicSize::ServerDefault,
                 1,
+                CleanupPolicy::default(),
             )
             .await
             .unwrap();
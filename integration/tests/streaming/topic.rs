@@ -4,6 +4,7 @@ use ahash::AHashMap;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::Partitioning;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
@@ -38,8 +39,10 @@ async fn should_persist_topics_with_partitions_directories_and_info_file() {
             Arc::new(AtomicU32::new(0)),
             IggyExpiry::NeverExpire,
             CompressionAlgorithm::default(),
+            None,
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -76,8 +79,10 @@ async fn should_load_existing_topic_from_disk() {
             Arc::new(AtomicU32::new(0)),
             IggyExpiry::NeverExpire,
             CompressionAlgorithm::default(),
+            None,
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -113,9 +118,12 @@ async fn should_load_existing_topic_from_disk() {
             },
             consumer_groups: Default::default(),
             compression_algorithm: Default::default(),
+            compression_level: None,
             message_expiry: IggyExpiry::NeverExpire,
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: Some(1),
+            cleanup_policy: Default::default(),
+            consumed_retention: Default::default(),
             created_at: Default::default(),
             current_consumer_group_id: 0,
         };
@@ -154,8 +162,10 @@ async fn should_delete_existing_topic_from_disk() {
             Arc::new(AtomicU32::new(0)),
             IggyExpiry::NeverExpire,
             CompressionAlgorithm::default(),
+            None,
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -194,8 +204,10 @@ async fn should_purge_existing_topic_on_disk() {
             Arc::new(AtomicU32::new(0)),
             IggyExpiry::NeverExpire,
             CompressionAlgorithm::default(),
+            None,
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -3,6 +3,7 @@ use bytes::Bytes;
 use iggy::locking::IggySharedMutFn;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
@@ -241,8 +242,10 @@ async fn init_topic(setup: &TestSetup, partitions_count: u32) -> Topic {
         Arc::new(AtomicU32::new(0)),
         IggyExpiry::NeverExpire,
         Default::default(),
+        None,
         MaxTopicSize::ServerDefault,
         1,
+        CleanupPolicy::default(),
     )
     .await
     .unwrap();
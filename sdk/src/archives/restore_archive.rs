@@ -0,0 +1,341 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, RESTORE_ARCHIVE_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::utils::sizeable::Sizeable;
+use crate::utils::timestamp::IggyTimestamp;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+const MAX_STATE_SNAPSHOT_LENGTH: usize = 255;
+
+/// `RestoreArchive` command is used to rehydrate the stream, topic or partition from the archived segments.
+/// If the stream or the topic no longer exists, it's recreated (with the same ID and settings) from the archived state.
+/// It has additional payload:
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `topic_id` - unique topic ID (numeric or name), all the topics of the stream are restored if not specified.
+/// - `partition_id` - partition ID, all the partitions of the topic are restored if not specified.
+/// - `range` - the range of the archived segments to restore, either all, by offsets or by timestamps.
+/// - `state_snapshot` - the name of the archived state snapshot (e.g. `1700000000000000_state`), the latest (overwritten) archived state is used if not specified.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RestoreArchive {
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name), all the topics of the stream are restored if not specified.
+    #[serde(skip)]
+    pub topic_id: Option<Identifier>,
+    /// Partition ID, all the partitions of the topic are restored if not specified.
+    #[serde(default)]
+    pub partition_id: Option<u32>,
+    /// The range of the archived segments to restore.
+    #[serde(default)]
+    pub range: ArchiveRange,
+    /// The name of the archived state snapshot, the latest archived state is used if not specified.
+    #[serde(default)]
+    pub state_snapshot: Option<String>,
+}
+
+/// `ArchiveRange` represents the range of the archived segments to restore.
+/// The segment is restored if any of its messages is within the range (both bounds are inclusive).
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ArchiveRange {
+    /// All the archived segments.
+    #[default]
+    All,
+    /// The segments containing the messages with offsets within the range.
+    Offsets { from: u64, to: u64 },
+    /// The segments containing the messages with timestamps (in microseconds) within the range.
+    Timestamps {
+        from: IggyTimestamp,
+        to: IggyTimestamp,
+    },
+}
+
+impl ArchiveRange {
+    pub fn as_code(&self) -> u8 {
+        match self {
+            ArchiveRange::All => 0,
+            ArchiveRange::Offsets { .. } => 1,
+            ArchiveRange::Timestamps { .. } => 2,
+        }
+    }
+
+    pub fn from_code(code: u8, from: u64, to: u64) -> Result<Self, IggyError> {
+        match code {
+            0 => Ok(ArchiveRange::All),
+            1 => Ok(ArchiveRange::Offsets { from, to }),
+            2 => Ok(ArchiveRange::Timestamps {
+                from: from.into(),
+                to: to.into(),
+            }),
+            _ => Err(IggyError::InvalidArchiveRange),
+        }
+    }
+
+    fn bounds(&self) -> (u64, u64) {
+        match self {
+            ArchiveRange::All => (0, 0),
+            ArchiveRange::Offsets { from, to } => (*from, *to),
+            ArchiveRange::Timestamps { from, to } => (from.as_micros(), to.as_micros()),
+        }
+    }
+}
+
+impl Display for ArchiveRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArchiveRange::All => write!(f, "all"),
+            ArchiveRange::Offsets { from, to } => write!(f, "offsets:{from}..{to}"),
+            ArchiveRange::Timestamps { from, to } => {
+                write!(f, "timestamps:{}..{}", from.as_micros(), to.as_micros())
+            }
+        }
+    }
+}
+
+impl Command for RestoreArchive {
+    fn code(&self) -> u32 {
+        RESTORE_ARCHIVE_CODE
+    }
+}
+
+impl Validatable<IggyError> for RestoreArchive {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.topic_id.is_none() && self.partition_id.is_some() {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        if self.partition_id == Some(0) {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let (from, to) = self.range.bounds();
+        if from > to {
+            return Err(IggyError::InvalidArchiveRange);
+        }
+
+        if let Some(state_snapshot) = &self.state_snapshot {
+            if state_snapshot.is_empty()
+                || state_snapshot.len() > MAX_STATE_SNAPSHOT_LENGTH
+                || state_snapshot.contains(['/', '\\'])
+                || state_snapshot.contains("..")
+            {
+                return Err(IggyError::InvalidStateSnapshot(state_snapshot.to_owned()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for RestoreArchive {
+    fn to_bytes(&self) -> Bytes {
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.as_ref().map(|topic_id| topic_id.to_bytes());
+        let state_snapshot = self.state_snapshot.as_deref().unwrap_or_default();
+        let mut bytes = BytesMut::with_capacity(
+            stream_id_bytes.len()
+                + 1
+                + topic_id_bytes.as_ref().map_or(0, |bytes| bytes.len())
+                + 4
+                + 17
+                + 1
+                + state_snapshot.len(),
+        );
+        bytes.put_slice(&stream_id_bytes);
+        if let Some(topic_id_bytes) = topic_id_bytes {
+            bytes.put_u8(1);
+            bytes.put_slice(&topic_id_bytes);
+        } else {
+            bytes.put_u8(0);
+        }
+        bytes.put_u32_le(self.partition_id.unwrap_or(0));
+        let (from, to) = self.range.bounds();
+        bytes.put_u8(self.range.as_code());
+        bytes.put_u64_le(from);
+        bytes.put_u64_le(to);
+        bytes.put_u8(state_snapshot.len() as u8);
+        bytes.put_slice(state_snapshot.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<RestoreArchive, IggyError> {
+        if bytes.len() < 26 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone())?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let has_topic_id = *bytes.get(position).ok_or(IggyError::InvalidCommand)?;
+        position += 1;
+        let topic_id = match has_topic_id {
+            0 => None,
+            1 => {
+                let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+                position += topic_id.get_size_bytes().as_bytes_usize();
+                Some(topic_id)
+            }
+            _ => return Err(IggyError::InvalidCommand),
+        };
+        if bytes.len() < position + 22 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let partition_id = u32::from_le_bytes(
+            bytes[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let partition_id = if partition_id == 0 {
+            None
+        } else {
+            Some(partition_id)
+        };
+        position += 4;
+        let range_code = bytes[position];
+        let from = u64::from_le_bytes(
+            bytes[position + 1..position + 9]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let to = u64::from_le_bytes(
+            bytes[position + 9..position + 17]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let range = ArchiveRange::from_code(range_code, from, to)?;
+        position += 17;
+        let state_snapshot_length = bytes[position] as usize;
+        position += 1;
+        if bytes.len() != position + state_snapshot_length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let state_snapshot = match state_snapshot_length {
+            0 => None,
+            _ => Some(
+                String::from_utf8(bytes[position..].to_vec())
+                    .map_err(|_| IggyError::InvalidUtf8)?,
+            ),
+        };
+        let command = RestoreArchive {
+            stream_id,
+            topic_id,
+            partition_id,
+            range,
+            state_snapshot,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for RestoreArchive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}",
+            self.stream_id,
+            self.topic_id
+                .as_ref()
+                .map_or("all".to_string(), |topic_id| topic_id.to_string()),
+            self.partition_id.unwrap_or(0),
+            self.range,
+            self.state_snapshot.as_deref().unwrap_or_default()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_and_deserialized_with_all_fields() {
+        let command = RestoreArchive {
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Some(Identifier::named("orders").unwrap()),
+            partition_id: Some(3),
+            range: ArchiveRange::Timestamps {
+                from: 100.into(),
+                to: 200.into(),
+            },
+            state_snapshot: Some("1700000000000000_state".to_string()),
+        };
+
+        let bytes = command.to_bytes();
+        let deserialized_command = RestoreArchive::from_bytes(bytes).unwrap();
+        assert_eq!(deserialized_command, command);
+    }
+
+    #[test]
+    fn should_be_serialized_and_deserialized_without_optional_fields() {
+        let command = RestoreArchive {
+            stream_id: Identifier::named("stream").unwrap(),
+            ..Default::default()
+        };
+
+        let bytes = command.to_bytes();
+        let deserialized_command = RestoreArchive::from_bytes(bytes).unwrap();
+        assert_eq!(deserialized_command, command);
+        assert!(deserialized_command.topic_id.is_none());
+        assert!(deserialized_command.partition_id.is_none());
+        assert_eq!(deserialized_command.range, ArchiveRange::All);
+        assert!(deserialized_command.state_snapshot.is_none());
+    }
+
+    #[test]
+    fn should_not_be_deserialized_given_invalid_range_code() {
+        let command = RestoreArchive {
+            stream_id: Identifier::numeric(1).unwrap(),
+            range: ArchiveRange::Offsets { from: 1, to: 2 },
+            ..Default::default()
+        };
+        let mut bytes = BytesMut::from(&command.to_bytes()[..]);
+        let range_code_position = command.stream_id.get_size_bytes().as_bytes_usize() + 1 + 4;
+        bytes[range_code_position] = 3;
+
+        let result = RestoreArchive::from_bytes(bytes.freeze());
+        assert!(matches!(result, Err(IggyError::InvalidArchiveRange)));
+    }
+
+    #[test]
+    fn should_not_be_valid_given_reversed_range() {
+        let command = RestoreArchive {
+            stream_id: Identifier::numeric(1).unwrap(),
+            range: ArchiveRange::Offsets { from: 10, to: 5 },
+            ..Default::default()
+        };
+        assert!(matches!(
+            command.validate(),
+            Err(IggyError::InvalidArchiveRange)
+        ));
+    }
+
+    #[test]
+    fn should_not_be_valid_given_partition_without_topic() {
+        let command = RestoreArchive {
+            stream_id: Identifier::numeric(1).unwrap(),
+            partition_id: Some(1),
+            ..Default::default()
+        };
+        assert!(command.validate().is_err());
+    }
+
+    #[test]
+    fn should_not_be_valid_given_state_snapshot_outside_of_archive() {
+        let command = RestoreArchive {
+            stream_id: Identifier::numeric(1).unwrap(),
+            state_snapshot: Some("../state".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            command.validate(),
+            Err(IggyError::InvalidStateSnapshot(_))
+        ));
+    }
+}
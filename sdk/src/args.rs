@@ -45,6 +45,11 @@ pub struct ArgsOptional {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub http_api_url: Option<String>,
 
+    /// The optional API URLs of the multiple servers for the HTTP transport, used instead of the single API URL (repeatable)
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_api_urls: Option<Vec<String>>,
+
     /// The optional number of retries for the HTTP transport
     ///
     /// [default: 3]
@@ -59,6 +64,11 @@ pub struct ArgsOptional {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tcp_server_address: Option<String>,
 
+    /// The optional addresses of the multiple servers for the TCP transport, used instead of the single server address (repeatable)
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp_server_addresses: Option<Vec<String>>,
+
     /// The optional number of max reconnect retries for the TCP transport
     ///
     /// [default: 3]
@@ -99,6 +109,11 @@ pub struct ArgsOptional {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quic_server_address: Option<String>,
 
+    /// The optional addresses of the multiple servers for the QUIC transport, used instead of the single server address (repeatable)
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quic_server_addresses: Option<Vec<String>>,
+
     /// The optional server name for the QUIC transport
     ///
     /// [default: localhost]
@@ -194,6 +209,9 @@ pub struct Args {
     /// The optional API URL for the HTTP transport
     pub http_api_url: String,
 
+    /// The optional API URLs of the multiple servers for the HTTP transport
+    pub http_api_urls: Vec<String>,
+
     /// The optional number of retries for the HTTP transport
     pub http_retries: u32,
 
@@ -206,6 +224,9 @@ pub struct Args {
     /// The optional client address for the TCP transport
     pub tcp_server_address: String,
 
+    /// The optional addresses of the multiple servers for the TCP transport
+    pub tcp_server_addresses: Vec<String>,
+
     /// The optional number of maximum reconnect retries for the TCP transport
     pub tcp_reconnection_enabled: bool,
 
@@ -233,12 +254,21 @@ pub struct Args {
     /// Disable nodelay for the TCP transport
     pub tcp_nodelay: bool,
 
+    /// Whether to request the polled messages as the batches stored on the server for the TCP transport
+    pub tcp_zero_copy: bool,
+
+    /// Whether to negotiate the pipelined protocol for the TCP transport
+    pub tcp_pipelining: bool,
+
     /// The optional client address for the QUIC transport
     pub quic_client_address: String,
 
     /// The optional server address for the QUIC transport
     pub quic_server_address: String,
 
+    /// The optional addresses of the multiple servers for the QUIC transport
+    pub quic_server_addresses: Vec<String>,
+
     /// The optional server name for the QUIC transport
     pub quic_server_name: String,
 
@@ -311,10 +341,12 @@ impl Default for Args {
             transport: "tcp".to_string(),
             encryption_key: "".to_string(),
             http_api_url: "http://localhost:3000".to_string(),
+            http_api_urls: Vec::new(),
             http_retries: 3,
             username: DEFAULT_ROOT_USERNAME.to_string(),
             password: DEFAULT_ROOT_PASSWORD.to_string(),
             tcp_server_address: "127.0.0.1:8090".to_string(),
+            tcp_server_addresses: Vec::new(),
             tcp_reconnection_enabled: true,
             tcp_reconnection_max_retries: None,
             tcp_reconnection_interval: "1s".to_string(),
@@ -324,8 +356,11 @@ impl Default for Args {
             tcp_tls_domain: "localhost".to_string(),
             tcp_tls_ca_file: None,
             tcp_nodelay: false,
+            tcp_zero_copy: false,
+            tcp_pipelining: false,
             quic_client_address: "127.0.0.1:0".to_string(),
             quic_server_address: "127.0.0.1:8080".to_string(),
+            quic_server_addresses: Vec::new(),
             quic_server_name: "localhost".to_string(),
             quic_reconnection_enabled: true,
             quic_reconnection_max_retries: None,
@@ -365,12 +400,18 @@ impl From<Vec<ArgsOptional>> for Args {
             if let Some(http_api_url) = optional_args.http_api_url {
                 args.http_api_url = http_api_url;
             }
+            if let Some(http_api_urls) = optional_args.http_api_urls {
+                args.http_api_urls = http_api_urls;
+            }
             if let Some(http_retries) = optional_args.http_retries {
                 args.http_retries = http_retries;
             }
             if let Some(tcp_server_address) = optional_args.tcp_server_address {
                 args.tcp_server_address = tcp_server_address;
             }
+            if let Some(tcp_server_addresses) = optional_args.tcp_server_addresses {
+                args.tcp_server_addresses = tcp_server_addresses;
+            }
             if let Some(tcp_reconnection_retries) = optional_args.tcp_reconnection_max_retries {
                 args.tcp_reconnection_max_retries = Some(tcp_reconnection_retries);
             }
@@ -389,6 +430,9 @@ impl From<Vec<ArgsOptional>> for Args {
             if let Some(quic_server_address) = optional_args.quic_server_address {
                 args.quic_server_address = quic_server_address;
             }
+            if let Some(quic_server_addresses) = optional_args.quic_server_addresses {
+                args.quic_server_addresses = quic_server_addresses;
+            }
             if let Some(quic_server_name) = optional_args.quic_server_name {
                 args.quic_server_name = quic_server_name;
             }
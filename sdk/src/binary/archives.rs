@@ -0,0 +1,31 @@
+use crate::archives::restore_archive::{ArchiveRange, RestoreArchive};
+use crate::binary::binary_client::BinaryClient;
+use crate::binary::{fail_if_not_authenticated, mapper};
+use crate::client::ArchiveClient;
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::models::archive::RestoredArchive;
+
+#[async_trait::async_trait]
+impl<B: BinaryClient> ArchiveClient for B {
+    async fn restore_archive(
+        &self,
+        stream_id: &Identifier,
+        topic_id: Option<&Identifier>,
+        partition_id: Option<u32>,
+        range: ArchiveRange,
+        state_snapshot: Option<&str>,
+    ) -> Result<RestoredArchive, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_with_response(&RestoreArchive {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.cloned(),
+                partition_id,
+                range,
+                state_snapshot: state_snapshot.map(|state_snapshot| state_snapshot.to_owned()),
+            })
+            .await?;
+        mapper::map_restored_archive(response)
+    }
+}
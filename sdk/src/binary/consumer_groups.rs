@@ -7,9 +7,10 @@ use crate::consumer_groups::get_consumer_group::GetConsumerGroup;
 use crate::consumer_groups::get_consumer_groups::GetConsumerGroups;
 use crate::consumer_groups::join_consumer_group::JoinConsumerGroup;
 use crate::consumer_groups::leave_consumer_group::LeaveConsumerGroup;
+use crate::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
-use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails};
+use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails, ConsumerGroupMembership};
 
 #[async_trait::async_trait]
 impl<B: BinaryClient> ConsumerGroupClient for B {
@@ -55,6 +56,7 @@ impl<B: BinaryClient> ConsumerGroupClient for B {
         topic_id: &Identifier,
         name: &str,
         group_id: Option<u32>,
+        assignment_strategy: PartitionAssignmentStrategy,
     ) -> Result<ConsumerGroupDetails, IggyError> {
         fail_if_not_authenticated(self).await?;
         let response = self
@@ -63,6 +65,7 @@ impl<B: BinaryClient> ConsumerGroupClient for B {
                 topic_id: topic_id.clone(),
                 name: name.to_string(),
                 group_id,
+                assignment_strategy,
             })
             .await?;
         mapper::map_consumer_group(response)
@@ -89,15 +92,16 @@ impl<B: BinaryClient> ConsumerGroupClient for B {
         stream_id: &Identifier,
         topic_id: &Identifier,
         group_id: &Identifier,
-    ) -> Result<(), IggyError> {
+    ) -> Result<ConsumerGroupMembership, IggyError> {
         fail_if_not_authenticated(self).await?;
-        self.send_with_response(&JoinConsumerGroup {
-            stream_id: stream_id.clone(),
-            topic_id: topic_id.clone(),
-            group_id: group_id.clone(),
-        })
-        .await?;
-        Ok(())
+        let response = self
+            .send_with_response(&JoinConsumerGroup {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                group_id: group_id.clone(),
+            })
+            .await?;
+        mapper::map_consumer_group_membership(response)
     }
 
     async fn leave_consumer_group(
@@ -18,6 +18,7 @@ impl<B: BinaryClient> ConsumerOffsetClient for B {
         topic_id: &Identifier,
         partition_id: Option<u32>,
         offset: u64,
+        generation: Option<u32>,
     ) -> Result<(), IggyError> {
         fail_if_not_authenticated(self).await?;
         self.send_with_response(&StoreConsumerOffset {
@@ -26,6 +27,7 @@ impl<B: BinaryClient> ConsumerOffsetClient for B {
             topic_id: topic_id.clone(),
             partition_id,
             offset,
+            generation,
         })
         .await?;
         Ok(())
@@ -1,19 +1,26 @@
 use crate::bytes_serializable::BytesSerializable;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use crate::error::IggyError;
+use crate::messages::poll_messages::PollingFormat;
+use crate::models::archive::RestoredArchive;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails, ConsumerGroupInfo};
-use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails, ConsumerGroupMember};
+use crate::models::consumer_group::{
+    ConsumerGroup, ConsumerGroupDetails, ConsumerGroupMember, ConsumerGroupMembership,
+};
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::messages::{MessageState, PolledMessage, PolledMessages};
 use crate::models::partition::Partition;
 use crate::models::permissions::Permissions;
 use crate::models::personal_access_token::{PersonalAccessTokenInfo, RawPersonalAccessToken};
+use crate::models::role::{RoleInfo, RoleInfoDetails};
 use crate::models::stats::{CacheMetrics, CacheMetricsKey, Stats};
 use crate::models::stream::{Stream, StreamDetails};
 use crate::models::topic::{Topic, TopicDetails};
 use crate::models::user_info::{UserInfo, UserInfoDetails};
 use crate::models::user_status::UserStatus;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::utils::byte_size::IggyByteSize;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::topic_size::MaxTopicSize;
@@ -26,6 +33,7 @@ const EMPTY_TOPICS: Vec<Topic> = vec![];
 const EMPTY_STREAMS: Vec<Stream> = vec![];
 const EMPTY_CLIENTS: Vec<ClientInfo> = vec![];
 const EMPTY_USERS: Vec<UserInfo> = vec![];
+const EMPTY_ROLES: Vec<RoleInfo> = vec![];
 const EMPTY_PERSONAL_ACCESS_TOKENS: Vec<PersonalAccessTokenInfo> = vec![];
 const EMPTY_CONSUMER_GROUPS: Vec<ConsumerGroup> = vec![];
 
@@ -428,6 +436,63 @@ pub fn map_users(payload: Bytes) -> Result<Vec<UserInfo>, IggyError> {
     Ok(users)
 }
 
+pub fn map_role(payload: Bytes) -> Result<RoleInfoDetails, IggyError> {
+    let (role, mut position) = map_to_role_info(payload.clone(), 0)?;
+    let has_permissions = payload[position];
+    position += 1;
+    let permissions = if has_permissions == 1 {
+        let permissions_length = u32::from_le_bytes(
+            payload[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        position += 4;
+        let permissions = payload.slice(position..position + permissions_length);
+        position += permissions_length;
+        Some(Permissions::from_bytes(permissions)?)
+    } else {
+        None
+    };
+
+    let mut users = Vec::with_capacity(role.users_count as usize);
+    for _ in 0..role.users_count {
+        let user_id = u32::from_le_bytes(
+            payload[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        users.push(user_id);
+        position += 4;
+    }
+    users.sort();
+
+    let role = RoleInfoDetails {
+        id: role.id,
+        created_at: role.created_at,
+        name: role.name,
+        permissions,
+        users,
+    };
+    Ok(role)
+}
+
+pub fn map_roles(payload: Bytes) -> Result<Vec<RoleInfo>, IggyError> {
+    if payload.is_empty() {
+        return Ok(EMPTY_ROLES);
+    }
+
+    let mut roles = Vec::new();
+    let length = payload.len();
+    let mut position = 0;
+    while position < length {
+        let (role, read_bytes) = map_to_role_info(payload.clone(), position)?;
+        roles.push(role);
+        position += read_bytes;
+    }
+    roles.sort_by_key(|role| role.id);
+    Ok(roles)
+}
+
 pub fn map_personal_access_tokens(
     payload: Bytes,
 ) -> Result<Vec<PersonalAccessTokenInfo>, IggyError> {
@@ -533,6 +598,7 @@ pub fn map_polled_messages(payload: Bytes) -> Result<PolledMessages, IggyError>
             messages: EMPTY_MESSAGES,
             partition_id: 0,
             current_offset: 0,
+            generation: None,
         });
     }
 
@@ -547,15 +613,14 @@ pub fn map_polled_messages(payload: Bytes) -> Result<PolledMessages, IggyError>
             .try_into()
             .map_err(|_| IggyError::InvalidNumberEncoding)?,
     );
-    // Currently ignored
-    let _messages_count = u32::from_le_bytes(
+    let messages_count = u32::from_le_bytes(
         payload[12..16]
             .try_into()
             .map_err(|_| IggyError::InvalidNumberEncoding)?,
     );
     let mut position = 16;
-    let mut messages = Vec::new();
-    while position < length {
+    let mut messages = Vec::with_capacity(messages_count as usize);
+    while position < length && messages.len() < messages_count as usize {
         let offset = u64::from_le_bytes(
             payload[position..position + 8]
                 .try_into()
@@ -619,14 +684,207 @@ pub fn map_polled_messages(payload: Bytes) -> Result<PolledMessages, IggyError>
         }
     }
 
+    // The generation of the consumer group is appended after the messages, so it's missing in the responses of the older servers.
+    let generation = match payload.get(position..position + 4) {
+        Some(generation) => u32::from_le_bytes(
+            generation
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ),
+        None => 0,
+    };
+    let generation = if generation == 0 {
+        None
+    } else {
+        Some(generation)
+    };
     messages.sort_by(|x, y| x.offset.cmp(&y.offset));
     Ok(PolledMessages {
         partition_id,
         current_offset,
         messages,
+        generation,
+    })
+}
+
+/// Maps the response of the poll messages command sent with the given format.
+/// The response to the non-default format starts with the code of the format chosen by the server.
+pub fn map_polled_messages_with_format(
+    payload: Bytes,
+    format: PollingFormat,
+) -> Result<PolledMessages, IggyError> {
+    if format == PollingFormat::Messages || payload.is_empty() {
+        return map_polled_messages(payload);
+    }
+
+    match PollingFormat::from_code(payload[0])? {
+        PollingFormat::Messages => map_polled_messages(payload.slice(1..)),
+        PollingFormat::Batches => map_polled_batches(payload.slice(1..)),
+    }
+}
+
+// The batches are sent as stored in the segment log files, so they might contain the messages
+// outside the polled range, which are skipped, and the payload of the batch might be compressed.
+fn map_polled_batches(payload: Bytes) -> Result<PolledMessages, IggyError> {
+    if payload.len() < 32 {
+        return Err(IggyError::InvalidCommand);
+    }
+
+    let partition_id = u32::from_le_bytes(
+        payload[..4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let current_offset = u64::from_le_bytes(
+        payload[4..12]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let generation = u32::from_le_bytes(
+        payload[12..16]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let generation = if generation == 0 {
+        None
+    } else {
+        Some(generation)
+    };
+    let first_offset = u64::from_le_bytes(
+        payload[16..24]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let last_offset = u64::from_le_bytes(
+        payload[24..32]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+
+    let length = payload.len();
+    let mut position = 32;
+    let mut messages = Vec::new();
+    while position + 24 <= length {
+        let batch_length = u32::from_le_bytes(
+            payload[position + 8..position + 12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        let batch_range = position + 24..position + 24 + batch_length;
+        if batch_range.end > length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        position = batch_range.end;
+        let batch = decompress_batch(payload.slice(batch_range))?;
+        map_batch_messages(&batch, first_offset, last_offset, &mut messages)?;
+    }
+
+    Ok(PolledMessages {
+        partition_id,
+        current_offset,
+        messages,
+        generation,
     })
 }
 
+fn decompress_batch(batch: Bytes) -> Result<Bytes, IggyError> {
+    // The compressed batch starts with the zeroed marker, the algorithm code and the decompressed length.
+    if batch.len() < 9 || batch[0..4] != [0; 4] {
+        return Ok(batch);
+    }
+
+    let algorithm = CompressionAlgorithm::from_code(batch[4])?;
+    let length = u32::from_le_bytes(
+        batch[5..9]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    ) as usize;
+    let decompressed = algorithm.decompress(&batch[9..], length)?;
+    if decompressed.len() != length {
+        return Err(IggyError::CannotDecompressData);
+    }
+
+    Ok(Bytes::from(decompressed))
+}
+
+fn map_batch_messages(
+    batch: &Bytes,
+    first_offset: u64,
+    last_offset: u64,
+    messages: &mut Vec<PolledMessage>,
+) -> Result<(), IggyError> {
+    let length = batch.len();
+    let mut position = 0;
+    while position + 4 <= length {
+        let message_length = u32::from_le_bytes(
+            batch[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        let start = position + 4;
+        let end = start + message_length;
+        if message_length < 41 || end > length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        position = end;
+        let offset = u64::from_le_bytes(
+            batch[start..start + 8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        if offset < first_offset || offset > last_offset {
+            continue;
+        }
+
+        let state = MessageState::from_code(batch[start + 8])?;
+        let timestamp = u64::from_le_bytes(
+            batch[start + 9..start + 17]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let id = u128::from_le_bytes(
+            batch[start + 17..start + 33]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let checksum = u32::from_le_bytes(
+            batch[start + 33..start + 37]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let headers_length = u32::from_le_bytes(
+            batch[start + 37..start + 41]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        let payload_start = start + 41 + headers_length;
+        if payload_start > end {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let headers = if headers_length > 0 {
+            Some(HashMap::from_bytes(batch.slice(start + 41..payload_start))?)
+        } else {
+            None
+        };
+        let payload = batch.slice(payload_start..end);
+        messages.push(PolledMessage {
+            offset,
+            timestamp,
+            state,
+            checksum,
+            id,
+            headers,
+            length: IggyByteSize::from(payload.len() as u64),
+            payload,
+        });
+    }
+
+    Ok(())
+}
+
 pub fn map_streams(payload: Bytes) -> Result<Vec<Stream>, IggyError> {
     if payload.is_empty() {
         return Ok(EMPTY_STREAMS);
@@ -749,8 +1007,10 @@ pub fn map_topic(payload: Bytes) -> Result<TopicDetails, IggyError> {
         messages_count: topic.messages_count,
         message_expiry: topic.message_expiry,
         compression_algorithm: topic.compression_algorithm,
+        compression_level: topic.compression_level,
         max_topic_size: topic.max_topic_size,
         replication_factor: topic.replication_factor,
+        cleanup_policy: topic.cleanup_policy,
         #[allow(clippy::cast_possible_truncation)]
         partitions_count: partitions.len() as u32,
         partitions,
@@ -805,7 +1065,12 @@ fn map_to_topic(payload: Bytes, position: usize) -> Result<(Topic, usize), IggyE
     let name = from_utf8(&payload[position + 51..position + 51 + name_length as usize])
         .map_err(|_| IggyError::InvalidUtf8)?
         .to_string();
-    let read_bytes = 4 + 8 + 4 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + name_length as usize;
+    let compression_level = match payload[position + 51 + name_length as usize] {
+        0 => None,
+        level => Some(level),
+    };
+    let cleanup_policy = CleanupPolicy::from_code(payload[position + 52 + name_length as usize])?;
+    let read_bytes = 4 + 8 + 4 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + name_length as usize + 1 + 1;
     Ok((
         Topic {
             id,
@@ -816,8 +1081,10 @@ fn map_to_topic(payload: Bytes, position: usize) -> Result<(Topic, usize), IggyE
             messages_count,
             message_expiry,
             compression_algorithm,
+            compression_level,
             max_topic_size,
             replication_factor,
+            cleanup_policy,
         },
         read_bytes,
     ))
@@ -902,11 +1169,45 @@ pub fn map_consumer_group(payload: Bytes) -> Result<ConsumerGroupDetails, IggyEr
         name: consumer_group.name,
         partitions_count: consumer_group.partitions_count,
         members_count: consumer_group.members_count,
+        assignment_strategy: consumer_group.assignment_strategy,
+        generation: consumer_group.generation,
         members,
     };
     Ok(consumer_group_details)
 }
 
+pub fn map_consumer_group_membership(payload: Bytes) -> Result<ConsumerGroupMembership, IggyError> {
+    let member_id = u32::from_le_bytes(
+        payload[..4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let generation = u32::from_le_bytes(
+        payload[4..8]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let partitions_count = u32::from_le_bytes(
+        payload[8..12]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let mut partitions = Vec::with_capacity(partitions_count as usize);
+    for i in 0..partitions_count as usize {
+        let partition_id = u32::from_le_bytes(
+            payload[12 + i * 4..12 + (i + 1) * 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        partitions.push(partition_id);
+    }
+    Ok(ConsumerGroupMembership {
+        member_id,
+        generation,
+        partitions,
+    })
+}
+
 fn map_to_consumer_group(
     payload: Bytes,
     position: usize,
@@ -930,12 +1231,21 @@ fn map_to_consumer_group(
     let name = from_utf8(&payload[position + 13..position + 13 + name_length as usize])
         .map_err(|_| IggyError::InvalidUtf8)?
         .to_string();
-    let read_bytes = 13 + name_length as usize;
+    let position = position + 13 + name_length as usize;
+    let assignment_strategy = PartitionAssignmentStrategy::from_code(payload[position])?;
+    let generation = u32::from_le_bytes(
+        payload[position + 1..position + 5]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let read_bytes = 18 + name_length as usize;
     Ok((
         ConsumerGroup {
             id,
             partitions_count,
             members_count,
+            assignment_strategy,
+            generation,
             name,
         },
         read_bytes,
@@ -1064,6 +1374,40 @@ fn map_to_user_info(payload: Bytes, position: usize) -> Result<(UserInfo, usize)
     ))
 }
 
+fn map_to_role_info(payload: Bytes, position: usize) -> Result<(RoleInfo, usize), IggyError> {
+    let id = u32::from_le_bytes(
+        payload[position..position + 4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let created_at = u64::from_le_bytes(
+        payload[position + 4..position + 12]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let created_at = created_at.into();
+    let users_count = u32::from_le_bytes(
+        payload[position + 12..position + 16]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let name_length = payload[position + 16];
+    let name = from_utf8(&payload[position + 17..position + 17 + name_length as usize])
+        .map_err(|_| IggyError::InvalidUtf8)?
+        .to_string();
+    let read_bytes = 4 + 8 + 4 + 1 + name_length as usize;
+
+    Ok((
+        RoleInfo {
+            id,
+            created_at,
+            name,
+            users_count,
+        },
+        read_bytes,
+    ))
+}
+
 fn map_to_pat_info(
     payload: Bytes,
     position: usize,
@@ -1085,3 +1429,58 @@ fn map_to_pat_info(
     let read_bytes = 1 + name_length as usize + 8;
     Ok((PersonalAccessTokenInfo { name, expiry_at }, read_bytes))
 }
+
+pub fn map_transaction_id(payload: Bytes) -> Result<u64, IggyError> {
+    let transaction_id = u64::from_le_bytes(
+        payload
+            .get(..8)
+            .ok_or(IggyError::InvalidNumberEncoding)?
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    Ok(transaction_id)
+}
+
+pub fn map_producer_epoch(payload: Bytes) -> Result<u64, IggyError> {
+    let epoch = u64::from_le_bytes(
+        payload
+            .get(..8)
+            .ok_or(IggyError::InvalidNumberEncoding)?
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    Ok(epoch)
+}
+
+pub fn map_restored_archive(payload: Bytes) -> Result<RestoredArchive, IggyError> {
+    if payload.len() != 20 {
+        return Err(IggyError::InvalidNumberEncoding);
+    }
+
+    let created_streams_count = u32::from_le_bytes(
+        payload[0..4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let created_topics_count = u32::from_le_bytes(
+        payload[4..8]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let segments_count = u32::from_le_bytes(
+        payload[8..12]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let messages_count = u64::from_le_bytes(
+        payload[12..20]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    Ok(RestoredArchive {
+        created_streams_count,
+        created_topics_count,
+        segments_count,
+        messages_count,
+    })
+}
@@ -6,7 +6,10 @@ use crate::consumer::Consumer;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::messages::flush_unsaved_buffer::FlushUnsavedBuffer;
-use crate::messages::poll_messages::PollingStrategy;
+use crate::messages::init_producer::InitProducer;
+use crate::messages::message_filter::MessageFilter;
+use crate::messages::nack_message::NackMessage;
+use crate::messages::poll_messages::{IsolationLevel, PollingStrategy};
 use crate::messages::send_messages::{Message, Partitioning};
 use crate::messages::{poll_messages, send_messages};
 use crate::models::messages::PolledMessages;
@@ -22,8 +25,59 @@ impl<B: BinaryClient> MessageClient for B {
         strategy: &PollingStrategy,
         count: u32,
         auto_commit: bool,
+    ) -> Result<PolledMessages, IggyError> {
+        self.poll_messages_with_isolation_level(
+            stream_id,
+            topic_id,
+            partition_id,
+            consumer,
+            strategy,
+            count,
+            auto_commit,
+            IsolationLevel::ReadUncommitted,
+        )
+        .await
+    }
+
+    async fn poll_messages_with_isolation_level(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+    ) -> Result<PolledMessages, IggyError> {
+        self.poll_messages_with_filter(
+            stream_id,
+            topic_id,
+            partition_id,
+            consumer,
+            strategy,
+            count,
+            auto_commit,
+            isolation_level,
+            &MessageFilter::default(),
+        )
+        .await
+    }
+
+    async fn poll_messages_with_filter(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+        filter: &MessageFilter,
     ) -> Result<PolledMessages, IggyError> {
         fail_if_not_authenticated(self).await?;
+        let format = self.get_polling_format();
         let response = self
             .send_raw_with_response(
                 POLL_MESSAGES_CODE,
@@ -35,10 +89,13 @@ impl<B: BinaryClient> MessageClient for B {
                     strategy,
                     count,
                     auto_commit,
+                    isolation_level,
+                    filter,
+                    format,
                 ),
             )
             .await?;
-        mapper::map_polled_messages(response)
+        mapper::map_polled_messages_with_format(response, format)
     }
 
     async fn send_messages(
@@ -74,4 +131,43 @@ impl<B: BinaryClient> MessageClient for B {
         .await?;
         Ok(())
     }
+
+    async fn nack_message(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+        reason: &str,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&NackMessage {
+            consumer: consumer.clone(),
+            stream_id: stream_id.clone(),
+            topic_id: topic_id.clone(),
+            partition_id,
+            offset,
+            reason: reason.to_string(),
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn init_producer(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        producer_id: u64,
+    ) -> Result<u64, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_with_response(&InitProducer {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                producer_id,
+            })
+            .await?;
+        mapper::map_producer_epoch(response)
+    }
 }
@@ -1,11 +1,14 @@
 use crate::command::Command;
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
+use crate::messages::poll_messages::PollingFormat;
+use crate::system::get_me::GetMe;
 use crate::utils::duration::IggyDuration;
 use async_trait::async_trait;
 use bytes::Bytes;
 use derive_more::Display;
 
+pub mod archives;
 #[allow(deprecated)]
 pub mod binary_client;
 #[allow(deprecated)]
@@ -20,12 +23,16 @@ pub mod partitions;
 #[allow(deprecated)]
 pub mod personal_access_tokens;
 #[allow(deprecated)]
+pub mod roles;
+#[allow(deprecated)]
 pub mod streams;
 #[allow(deprecated)]
 pub mod system;
 #[allow(deprecated)]
 pub mod topics;
 #[allow(deprecated)]
+pub mod transactions;
+#[allow(deprecated)]
 pub mod users;
 
 /// The state of the client.
@@ -62,6 +69,10 @@ pub trait BinaryTransport {
     async fn send_with_response<T: Command>(&self, command: &T) -> Result<Bytes, IggyError>;
     async fn send_raw_with_response(&self, code: u32, payload: Bytes) -> Result<Bytes, IggyError>;
     fn get_heartbeat_interval(&self) -> IggyDuration;
+    /// Gets the format of the polled messages requested from the server.
+    fn get_polling_format(&self) -> PollingFormat {
+        PollingFormat::Messages
+    }
 }
 
 async fn fail_if_not_authenticated<T: BinaryTransport>(transport: &T) -> Result<(), IggyError> {
@@ -74,3 +85,23 @@ async fn fail_if_not_authenticated<T: BinaryTransport>(transport: &T) -> Result<
         ClientState::Authenticated => Ok(()),
     }
 }
+
+/// Checks whether the server has authenticated the connection as the user mapped to the client certificate,
+/// so the client can send the commands without logging in.
+pub(crate) async fn authenticate_with_client_certificate<T: BinaryTransport>(
+    transport: &T,
+) -> bool {
+    let Ok(response) = transport.send_with_response(&GetMe {}).await else {
+        return false;
+    };
+    let Ok(client) = mapper::map_client(response) else {
+        return false;
+    };
+    if client.user_id.is_none() {
+        return false;
+    }
+
+    transport.set_state(ClientState::Authenticated).await;
+    transport.publish_event(DiagnosticEvent::SignedIn).await;
+    true
+}
@@ -1,6 +1,7 @@
 use crate::binary::binary_client::BinaryClient;
 use crate::binary::{fail_if_not_authenticated, mapper, ClientState};
 use crate::client::PersonalAccessTokenClient;
+use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::personal_access_token::{PersonalAccessTokenInfo, RawPersonalAccessToken};
@@ -52,6 +53,7 @@ impl<B: BinaryClient> PersonalAccessTokenClient for B {
             })
             .await?;
         self.set_state(ClientState::Authenticated).await;
+        self.publish_event(DiagnosticEvent::SignedIn).await;
         mapper::map_identity_info(response)
     }
 }
@@ -0,0 +1,105 @@
+use crate::binary::binary_client::BinaryClient;
+use crate::binary::{fail_if_not_authenticated, mapper};
+use crate::client::RoleClient;
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::models::permissions::Permissions;
+use crate::models::role::{RoleInfo, RoleInfoDetails};
+use crate::roles::assign_role::AssignRole;
+use crate::roles::create_role::CreateRole;
+use crate::roles::delete_role::DeleteRole;
+use crate::roles::get_role::GetRole;
+use crate::roles::get_roles::GetRoles;
+use crate::roles::unassign_role::UnassignRole;
+use crate::roles::update_role::UpdateRole;
+
+#[async_trait::async_trait]
+impl<B: BinaryClient> RoleClient for B {
+    async fn get_role(&self, role_id: &Identifier) -> Result<Option<RoleInfoDetails>, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_with_response(&GetRole {
+                role_id: role_id.clone(),
+            })
+            .await?;
+        if response.is_empty() {
+            return Ok(None);
+        }
+
+        mapper::map_role(response).map(Some)
+    }
+
+    async fn get_roles(&self) -> Result<Vec<RoleInfo>, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self.send_with_response(&GetRoles {}).await?;
+        mapper::map_roles(response)
+    }
+
+    async fn create_role(
+        &self,
+        name: &str,
+        permissions: Option<Permissions>,
+    ) -> Result<RoleInfoDetails, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_with_response(&CreateRole {
+                name: name.to_string(),
+                permissions,
+            })
+            .await?;
+        mapper::map_role(response)
+    }
+
+    async fn delete_role(&self, role_id: &Identifier) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&DeleteRole {
+            role_id: role_id.clone(),
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn update_role(
+        &self,
+        role_id: &Identifier,
+        name: Option<&str>,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&UpdateRole {
+            role_id: role_id.clone(),
+            name: name.map(|s| s.to_string()),
+            permissions,
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn assign_role(
+        &self,
+        role_id: &Identifier,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&AssignRole {
+            role_id: role_id.clone(),
+            user_id: user_id.clone(),
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn unassign_role(
+        &self,
+        role_id: &Identifier,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&UnassignRole {
+            role_id: role_id.clone(),
+            user_id: user_id.clone(),
+        })
+        .await?;
+        Ok(())
+    }
+}
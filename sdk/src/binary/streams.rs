@@ -3,12 +3,14 @@ use crate::binary::{fail_if_not_authenticated, mapper};
 use crate::client::StreamClient;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::models::quota::Quota;
 use crate::models::stream::{Stream, StreamDetails};
 use crate::streams::create_stream::CreateStream;
 use crate::streams::delete_stream::DeleteStream;
 use crate::streams::get_stream::GetStream;
 use crate::streams::get_streams::GetStreams;
 use crate::streams::purge_stream::PurgeStream;
+use crate::streams::set_stream_quota::SetStreamQuota;
 use crate::streams::update_stream::UpdateStream;
 
 #[async_trait::async_trait]
@@ -75,4 +77,18 @@ impl<B: BinaryClient> StreamClient for B {
         .await?;
         Ok(())
     }
+
+    async fn set_stream_quota(
+        &self,
+        stream_id: &Identifier,
+        quota: Quota,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&SetStreamQuota {
+            stream_id: stream_id.clone(),
+            quota,
+        })
+        .await?;
+        Ok(())
+    }
 }
@@ -2,14 +2,19 @@ use crate::binary::binary_client::BinaryClient;
 use crate::binary::{fail_if_not_authenticated, mapper};
 use crate::client::TopicClient;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::compression::compression_level::CompressionLevel;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::models::topic::{Topic, TopicDetails};
+use crate::topics::cleanup_policy::CleanupPolicy;
+use crate::topics::consumed_retention::ConsumedRetention;
 use crate::topics::create_topic::CreateTopic;
 use crate::topics::delete_topic::DeleteTopic;
 use crate::topics::get_topic::GetTopic;
 use crate::topics::get_topics::GetTopics;
 use crate::topics::purge_topic::PurgeTopic;
+use crate::topics::replication_factor::ReplicationFactor;
+use crate::topics::set_topic_retention::SetTopicRetention;
 use crate::topics::update_topic::UpdateTopic;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::topic_size::MaxTopicSize;
@@ -51,10 +56,12 @@ impl<B: BinaryClient> TopicClient for B {
         name: &str,
         partitions_count: u32,
         compression_algorithm: CompressionAlgorithm,
-        replication_factor: Option<u8>,
+        compression_level: Option<CompressionLevel>,
+        replication_factor: Option<ReplicationFactor>,
         topic_id: Option<u32>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<TopicDetails, IggyError> {
         fail_if_not_authenticated(self).await?;
         let response = self
@@ -63,10 +70,12 @@ impl<B: BinaryClient> TopicClient for B {
                 name: name.to_string(),
                 partitions_count,
                 compression_algorithm,
-                replication_factor,
+                compression_level: compression_level.map(u8::from),
+                replication_factor: replication_factor.map(u8::from),
                 topic_id,
                 message_expiry,
                 max_topic_size,
+                cleanup_policy,
             })
             .await?;
         mapper::map_topic(response)
@@ -78,9 +87,11 @@ impl<B: BinaryClient> TopicClient for B {
         topic_id: &Identifier,
         name: &str,
         compression_algorithm: CompressionAlgorithm,
-        replication_factor: Option<u8>,
+        compression_level: Option<CompressionLevel>,
+        replication_factor: Option<ReplicationFactor>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<(), IggyError> {
         fail_if_not_authenticated(self).await?;
         self.send_with_response(&UpdateTopic {
@@ -88,9 +99,11 @@ impl<B: BinaryClient> TopicClient for B {
             topic_id: topic_id.clone(),
             name: name.to_string(),
             compression_algorithm,
-            replication_factor,
+            compression_level: compression_level.map(u8::from),
+            replication_factor: replication_factor.map(u8::from),
             message_expiry,
             max_topic_size,
+            cleanup_policy,
         })
         .await?;
         Ok(())
@@ -123,4 +136,20 @@ impl<B: BinaryClient> TopicClient for B {
         .await?;
         Ok(())
     }
+
+    async fn set_topic_retention(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        retention: ConsumedRetention,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&SetTopicRetention {
+            stream_id: stream_id.clone(),
+            topic_id: topic_id.clone(),
+            retention,
+        })
+        .await?;
+        Ok(())
+    }
 }
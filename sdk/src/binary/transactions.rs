@@ -0,0 +1,37 @@
+use crate::binary::binary_client::BinaryClient;
+use crate::binary::{fail_if_not_authenticated, mapper};
+use crate::client::TransactionClient;
+use crate::error::IggyError;
+use crate::transactions::abort_transaction::AbortTransaction;
+use crate::transactions::begin_transaction::BeginTransaction;
+use crate::transactions::commit_transaction::{CommitTransaction, TransactionOffset};
+
+#[async_trait::async_trait]
+impl<B: BinaryClient> TransactionClient for B {
+    async fn begin_transaction(&self) -> Result<u64, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self.send_with_response(&BeginTransaction {}).await?;
+        mapper::map_transaction_id(response)
+    }
+
+    async fn commit_transaction(
+        &self,
+        transaction_id: u64,
+        offsets: &[TransactionOffset],
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&CommitTransaction {
+            transaction_id,
+            offsets: offsets.to_vec(),
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn abort_transaction(&self, transaction_id: u64) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&AbortTransaction { transaction_id })
+            .await?;
+        Ok(())
+    }
+}
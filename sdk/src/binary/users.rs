@@ -6,6 +6,7 @@ use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::permissions::Permissions;
+use crate::models::quota::Quota;
 use crate::models::user_info::{UserInfo, UserInfoDetails};
 use crate::models::user_status::UserStatus;
 use crate::users::change_password::ChangePassword;
@@ -14,7 +15,9 @@ use crate::users::delete_user::DeleteUser;
 use crate::users::get_user::GetUser;
 use crate::users::get_users::GetUsers;
 use crate::users::login_user::LoginUser;
+use crate::users::login_with_token::LoginWithToken;
 use crate::users::logout_user::LogoutUser;
+use crate::users::set_user_quota::SetUserQuota;
 use crate::users::update_permissions::UpdatePermissions;
 use crate::users::update_user::UpdateUser;
 
@@ -98,6 +101,16 @@ impl<B: BinaryClient> UserClient for B {
         Ok(())
     }
 
+    async fn set_user_quota(&self, user_id: &Identifier, quota: Quota) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&SetUserQuota {
+            user_id: user_id.clone(),
+            quota,
+        })
+        .await?;
+        Ok(())
+    }
+
     async fn change_password(
         &self,
         user_id: &Identifier,
@@ -128,6 +141,17 @@ impl<B: BinaryClient> UserClient for B {
         mapper::map_identity_info(response)
     }
 
+    async fn login_with_token(&self, token: &str) -> Result<IdentityInfo, IggyError> {
+        let response = self
+            .send_with_response(&LoginWithToken {
+                token: token.to_string(),
+            })
+            .await?;
+        self.set_state(ClientState::Authenticated).await;
+        self.publish_event(DiagnosticEvent::SignedIn).await;
+        mapper::map_identity_info(response)
+    }
+
     async fn logout_user(&self) -> Result<(), IggyError> {
         fail_if_not_authenticated(self).await?;
         self.send_with_response(&LogoutUser {}).await?;
@@ -0,0 +1,62 @@
+use crate::archives::restore_archive::RestoreArchive;
+use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::client::Client;
+use anyhow::Context;
+use async_trait::async_trait;
+use tracing::{event, Level};
+
+pub struct RestoreArchiveCmd {
+    restore_archive: RestoreArchive,
+}
+
+impl RestoreArchiveCmd {
+    pub fn new(restore_archive: RestoreArchive) -> Self {
+        Self { restore_archive }
+    }
+
+    fn describe(&self) -> String {
+        let mut description = format!("stream with ID: {}", self.restore_archive.stream_id);
+        if let Some(topic_id) = &self.restore_archive.topic_id {
+            description = format!("topic with ID: {topic_id} and {description}");
+        }
+        if let Some(partition_id) = self.restore_archive.partition_id {
+            description = format!("partition with ID: {partition_id} for {description}");
+        }
+        description
+    }
+}
+
+#[async_trait]
+impl CliCommand for RestoreArchiveCmd {
+    fn explain(&self) -> String {
+        format!(
+            "restore {} from archive (range: {})",
+            self.describe(),
+            self.restore_archive.range
+        )
+    }
+
+    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+        let restored_archive = client
+            .restore_archive(
+                &self.restore_archive.stream_id,
+                self.restore_archive.topic_id.as_ref(),
+                self.restore_archive.partition_id,
+                self.restore_archive.range,
+                self.restore_archive.state_snapshot.as_deref(),
+            )
+            .await
+            .with_context(|| format!("Problem restoring {} from archive", self.describe()))?;
+
+        event!(target: PRINT_TARGET, Level::INFO,
+            "Restored {} from archive: {} segments with {} messages, recreated {} streams and {} topics",
+            self.describe(),
+            restored_archive.segments_count,
+            restored_archive.messages_count,
+            restored_archive.created_streams_count,
+            restored_archive.created_topics_count,
+        );
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::cli_command::{CliCommand, PRINT_TARGET};
 use crate::client::Client;
 use crate::consumer_groups::create_consumer_group::CreateConsumerGroup;
+use crate::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use crate::identifier::Identifier;
 use anyhow::Context;
 use async_trait::async_trait;
@@ -16,6 +17,7 @@ impl CreateConsumerGroupCmd {
         topic_id: Identifier,
         name: String,
         group_id: Option<u32>,
+        assignment_strategy: PartitionAssignmentStrategy,
     ) -> Self {
         Self {
             create_consumer_group: CreateConsumerGroup {
@@ -23,6 +25,7 @@ impl CreateConsumerGroupCmd {
                 topic_id,
                 name,
                 group_id,
+                assignment_strategy,
             },
         }
     }
@@ -49,7 +52,7 @@ impl CliCommand for CreateConsumerGroupCmd {
 
     async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
         client
-            .create_consumer_group(&self.create_consumer_group.stream_id, &self.create_consumer_group.topic_id, &self.create_consumer_group.name, self.create_consumer_group.group_id)
+            .create_consumer_group(&self.create_consumer_group.stream_id, &self.create_consumer_group.topic_id, &self.create_consumer_group.name, self.create_consumer_group.group_id, self.create_consumer_group.assignment_strategy)
             .await
             .with_context(|| {
                 format!(
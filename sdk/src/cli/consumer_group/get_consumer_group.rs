@@ -67,6 +67,14 @@ impl CliCommand for GetConsumerGroupCmd {
             "Members count",
             format!("{}", consumer_group.members_count).as_str(),
         ]);
+        table.add_row(vec![
+            "Assignment strategy",
+            format!("{}", consumer_group.assignment_strategy).as_str(),
+        ]);
+        table.add_row(vec![
+            "Generation",
+            format!("{}", consumer_group.generation).as_str(),
+        ]);
 
         if consumer_group.members_count > 0 {
             let mut members_table = Table::new();
@@ -29,6 +29,7 @@ impl SetConsumerOffsetCmd {
                 topic_id,
                 partition_id: Some(partition_id),
                 offset,
+                generation: None,
             },
         }
     }
@@ -49,7 +50,7 @@ impl CliCommand for SetConsumerOffsetCmd {
 
     async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
         client
-            .store_consumer_offset(&self.set_consumer_offset.consumer, &self.set_consumer_offset.stream_id, &self.set_consumer_offset.topic_id, self.set_consumer_offset.partition_id, self.set_consumer_offset.offset)
+            .store_consumer_offset(&self.set_consumer_offset.consumer, &self.set_consumer_offset.stream_id, &self.set_consumer_offset.topic_id, self.set_consumer_offset.partition_id, self.set_consumer_offset.offset, self.set_consumer_offset.generation)
             .await
             .with_context(|| {
                 format!(
@@ -3,7 +3,10 @@ use crate::cli_command::{CliCommand, PRINT_TARGET};
 use crate::client::Client;
 use crate::consumer::Consumer;
 use crate::identifier::Identifier;
-use crate::messages::poll_messages::{PollMessages, PollingStrategy};
+use crate::messages::message_filter::MessageFilter;
+use crate::messages::poll_messages::{
+    IsolationLevel, PollMessages, PollingFormat, PollingStrategy,
+};
 use crate::messages::send_messages::Message;
 use crate::models::header::{HeaderKey, HeaderKind};
 use crate::models::messages::PolledMessages;
@@ -37,6 +40,7 @@ impl PollMessagesCmd {
         last: bool,
         next: bool,
         consumer: Identifier,
+        filter: MessageFilter,
         show_headers: bool,
         output_file: Option<String>,
     ) -> Self {
@@ -56,6 +60,9 @@ impl PollMessagesCmd {
                 strategy,
                 count: message_count,
                 auto_commit,
+                isolation_level: IsolationLevel::default(),
+                filter,
+                format: PollingFormat::default(),
             },
             show_headers,
             output_file,
@@ -151,7 +158,7 @@ impl CliCommand for PollMessagesCmd {
     async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
         let start = std::time::Instant::now();
         let messages = client
-            .poll_messages(
+            .poll_messages_with_filter(
                 &self.poll_messages.stream_id,
                 &self.poll_messages.topic_id,
                 self.poll_messages.partition_id,
@@ -159,6 +166,8 @@ impl CliCommand for PollMessagesCmd {
                 &self.poll_messages.strategy,
                 self.poll_messages.count,
                 self.poll_messages.auto_commit,
+                self.poll_messages.isolation_level,
+                &self.poll_messages.filter,
             )
             .await
             .with_context(|| {
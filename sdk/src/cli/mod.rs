@@ -1,3 +1,4 @@
+pub mod archives;
 pub mod client;
 pub mod consumer_group;
 pub mod consumer_offset;
@@ -5,6 +6,7 @@ pub mod context;
 pub mod message;
 pub mod partitions;
 pub mod personal_access_tokens;
+pub mod roles;
 pub mod streams;
 pub mod system;
 pub mod topics;
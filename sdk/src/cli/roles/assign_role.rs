@@ -0,0 +1,48 @@
+use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::client::Client;
+use crate::identifier::Identifier;
+use crate::roles::assign_role::AssignRole;
+use anyhow::Context;
+use async_trait::async_trait;
+use tracing::{event, Level};
+
+pub struct AssignRoleCmd {
+    assign_role: AssignRole,
+}
+
+impl AssignRoleCmd {
+    pub fn new(role_id: Identifier, user_id: Identifier) -> Self {
+        Self {
+            assign_role: AssignRole { role_id, user_id },
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for AssignRoleCmd {
+    fn explain(&self) -> String {
+        format!(
+            "assign role with ID: {} to user with ID: {}",
+            self.assign_role.role_id, self.assign_role.user_id
+        )
+    }
+
+    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+        client
+            .assign_role(&self.assign_role.role_id, &self.assign_role.user_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem assigning role with ID: {} to user with ID: {}",
+                    self.assign_role.role_id, self.assign_role.user_id
+                )
+            })?;
+
+        event!(target: PRINT_TARGET, Level::INFO,
+            "Role with ID: {} assigned to user with ID: {}",
+            self.assign_role.role_id, self.assign_role.user_id
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::client::Client;
+use crate::roles::get_roles::GetRoles;
+use anyhow::Context;
+use async_trait::async_trait;
+use comfy_table::Table;
+use tracing::{event, Level};
+
+pub enum GetRolesOutput {
+    Table,
+    List,
+}
+
+pub struct GetRolesCmd {
+    _get_roles: GetRoles,
+    output: GetRolesOutput,
+}
+
+impl GetRolesCmd {
+    pub fn new(output: GetRolesOutput) -> Self {
+        GetRolesCmd {
+            _get_roles: GetRoles {},
+            output,
+        }
+    }
+}
+
+impl Default for GetRolesCmd {
+    fn default() -> Self {
+        GetRolesCmd {
+            _get_roles: GetRoles {},
+            output: GetRolesOutput::Table,
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for GetRolesCmd {
+    fn explain(&self) -> String {
+        let mode = match self.output {
+            GetRolesOutput::Table => "table",
+            GetRolesOutput::List => "list",
+        };
+        format!("list roles in {mode} mode")
+    }
+
+    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+        let roles = client
+            .get_roles()
+            .await
+            .with_context(|| String::from("Problem getting list of roles"))?;
+
+        if roles.is_empty() {
+            event!(target: PRINT_TARGET, Level::INFO, "No roles found!");
+            return Ok(());
+        }
+
+        match self.output {
+            GetRolesOutput::Table => {
+                let mut table = Table::new();
+
+                table.set_header(vec!["ID", "Created", "Name", "Users"]);
+
+                roles.iter().for_each(|role| {
+                    table.add_row(vec![
+                        format!("{}", role.id),
+                        role.created_at.to_local_string("%Y-%m-%d %H:%M:%S"),
+                        role.name.clone(),
+                        format!("{}", role.users_count),
+                    ]);
+                });
+
+                event!(target: PRINT_TARGET, Level::INFO, "{table}");
+            }
+            GetRolesOutput::List => {
+                roles.iter().for_each(|role| {
+                    event!(target: PRINT_TARGET, Level::INFO,
+                        "{}|{}|{}|{}",
+                        role.id,
+                        role.created_at.to_local_string("%Y-%m-%d %H:%M:%S"),
+                        role.name,
+                        role.users_count,
+                    );
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod assign_role;
+pub mod create_role;
+pub mod delete_role;
+pub mod get_role;
+pub mod get_roles;
+pub mod unassign_role;
+pub mod update_role;
@@ -0,0 +1,48 @@
+use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::client::Client;
+use crate::identifier::Identifier;
+use crate::roles::unassign_role::UnassignRole;
+use anyhow::Context;
+use async_trait::async_trait;
+use tracing::{event, Level};
+
+pub struct UnassignRoleCmd {
+    unassign_role: UnassignRole,
+}
+
+impl UnassignRoleCmd {
+    pub fn new(role_id: Identifier, user_id: Identifier) -> Self {
+        Self {
+            unassign_role: UnassignRole { role_id, user_id },
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for UnassignRoleCmd {
+    fn explain(&self) -> String {
+        format!(
+            "unassign role with ID: {} from user with ID: {}",
+            self.unassign_role.role_id, self.unassign_role.user_id
+        )
+    }
+
+    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+        client
+            .unassign_role(&self.unassign_role.role_id, &self.unassign_role.user_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem unassigning role with ID: {} from user with ID: {}",
+                    self.unassign_role.role_id, self.unassign_role.user_id
+                )
+            })?;
+
+        event!(target: PRINT_TARGET, Level::INFO,
+            "Role with ID: {} unassigned from user with ID: {}",
+            self.unassign_role.role_id, self.unassign_role.user_id
+        );
+
+        Ok(())
+    }
+}
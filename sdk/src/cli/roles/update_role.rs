@@ -0,0 +1,55 @@
+use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::client::Client;
+use crate::identifier::Identifier;
+use crate::models::permissions::Permissions;
+use crate::roles::update_role::UpdateRole;
+use anyhow::Context;
+use async_trait::async_trait;
+use tracing::{event, Level};
+
+pub struct UpdateRoleCmd {
+    update_role: UpdateRole,
+}
+
+impl UpdateRoleCmd {
+    pub fn new(
+        role_id: Identifier,
+        name: Option<String>,
+        permissions: Option<Permissions>,
+    ) -> Self {
+        Self {
+            update_role: UpdateRole {
+                role_id,
+                name,
+                permissions,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for UpdateRoleCmd {
+    fn explain(&self) -> String {
+        format!("update role with ID: {}", self.update_role.role_id)
+    }
+
+    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+        client
+            .update_role(
+                &self.update_role.role_id,
+                self.update_role.name.as_deref(),
+                self.update_role.permissions.clone(),
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem updating role with ID: {}",
+                    self.update_role.role_id
+                )
+            })?;
+
+        event!(target: PRINT_TARGET, Level::INFO, "Role with ID: {} updated", self.update_role.role_id);
+
+        Ok(())
+    }
+}
@@ -3,4 +3,5 @@ pub mod delete_stream;
 pub mod get_stream;
 pub mod get_streams;
 pub mod purge_stream;
+pub mod set_stream_quota;
 pub mod update_stream;
@@ -0,0 +1,52 @@
+use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::client::Client;
+use crate::identifier::Identifier;
+use crate::models::quota::Quota;
+use crate::streams::set_stream_quota::SetStreamQuota;
+use anyhow::Context;
+use async_trait::async_trait;
+use tracing::{event, Level};
+
+pub struct SetStreamQuotaCmd {
+    set_stream_quota: SetStreamQuota,
+}
+
+impl SetStreamQuotaCmd {
+    pub fn new(stream_id: Identifier, quota: Quota) -> Self {
+        SetStreamQuotaCmd {
+            set_stream_quota: SetStreamQuota { stream_id, quota },
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for SetStreamQuotaCmd {
+    fn explain(&self) -> String {
+        format!(
+            "set quota for stream with ID: {} ({})",
+            self.set_stream_quota.stream_id, self.set_stream_quota.quota
+        )
+    }
+
+    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+        client
+            .set_stream_quota(
+                &self.set_stream_quota.stream_id,
+                self.set_stream_quota.quota,
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem setting quota for stream with ID: {}",
+                    self.set_stream_quota.stream_id
+                )
+            })?;
+
+        event!(target: PRINT_TARGET, Level::INFO,
+            "Stream with ID: {} quota set ({})",
+            self.set_stream_quota.stream_id, self.set_stream_quota.quota
+        );
+
+        Ok(())
+    }
+}
@@ -1,8 +1,11 @@
 use crate::cli_command::{CliCommand, PRINT_TARGET};
 use crate::client::Client;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::compression::compression_level::CompressionLevel;
 use crate::identifier::Identifier;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::topics::create_topic::CreateTopic;
+use crate::topics::replication_factor::ReplicationFactor;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::topic_size::MaxTopicSize;
 use anyhow::Context;
@@ -24,10 +27,12 @@ impl CreateTopicCmd {
         topic_id: Option<u32>,
         partitions_count: u32,
         compression_algorithm: CompressionAlgorithm,
+        compression_level: Option<u8>,
         name: String,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
+        cleanup_policy: CleanupPolicy,
     ) -> Self {
         Self {
             create_topic: CreateTopic {
@@ -35,10 +40,12 @@ impl CreateTopicCmd {
                 topic_id,
                 partitions_count,
                 compression_algorithm,
+                compression_level,
                 name,
                 message_expiry,
                 max_topic_size,
                 replication_factor: Some(replication_factor),
+                cleanup_policy,
             },
             message_expiry,
             max_topic_size,
@@ -62,7 +69,7 @@ impl CliCommand for CreateTopicCmd {
 
     async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
         client
-            .create_topic(&self.create_topic.stream_id, &self.create_topic.name, self.create_topic.partitions_count, self.create_topic.compression_algorithm, self.create_topic.replication_factor, self.create_topic.topic_id, self.create_topic.message_expiry, self.create_topic.max_topic_size)
+            .create_topic(&self.create_topic.stream_id, &self.create_topic.name, self.create_topic.partitions_count, self.create_topic.compression_algorithm, self.create_topic.compression_level.map(CompressionLevel), self.create_topic.replication_factor.map(ReplicationFactor), self.create_topic.topic_id, self.create_topic.message_expiry, self.create_topic.max_topic_size, self.create_topic.cleanup_policy)
             .await
             .with_context(|| {
                 format!(
@@ -63,6 +63,13 @@ impl CliCommand for GetTopicCmd {
             "Compression",
             topic.compression_algorithm.to_string().as_str(),
         ]);
+        table.add_row(vec![
+            "Compression level",
+            topic
+                .compression_level
+                .map_or("default".to_string(), |level| level.to_string())
+                .as_str(),
+        ]);
         table.add_row(vec![
             "Message expiry",
             match topic.message_expiry {
@@ -76,6 +83,10 @@ impl CliCommand for GetTopicCmd {
             "Max topic size",
             format!("{}", topic.max_topic_size).as_str(),
         ]);
+        table.add_row(vec![
+            "Cleanup policy",
+            topic.cleanup_policy.to_string().as_str(),
+        ]);
         table.add_row(vec![
             "Topic message count",
             format!("{}", topic.messages_count).as_str(),
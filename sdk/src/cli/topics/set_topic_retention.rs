@@ -0,0 +1,61 @@
+use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::client::Client;
+use crate::identifier::Identifier;
+use crate::topics::consumed_retention::ConsumedRetention;
+use crate::topics::set_topic_retention::SetTopicRetention;
+use anyhow::Context;
+use async_trait::async_trait;
+use tracing::{event, Level};
+
+pub struct SetTopicRetentionCmd {
+    set_topic_retention: SetTopicRetention,
+}
+
+impl SetTopicRetentionCmd {
+    pub fn new(stream_id: Identifier, topic_id: Identifier, retention: ConsumedRetention) -> Self {
+        SetTopicRetentionCmd {
+            set_topic_retention: SetTopicRetention {
+                stream_id,
+                topic_id,
+                retention,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for SetTopicRetentionCmd {
+    fn explain(&self) -> String {
+        format!(
+            "set retention for topic with ID: {} in stream with ID: {} ({})",
+            self.set_topic_retention.topic_id,
+            self.set_topic_retention.stream_id,
+            self.set_topic_retention.retention
+        )
+    }
+
+    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+        client
+            .set_topic_retention(
+                &self.set_topic_retention.stream_id,
+                &self.set_topic_retention.topic_id,
+                self.set_topic_retention.retention.clone(),
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem setting retention for topic with ID: {} in stream with ID: {}",
+                    self.set_topic_retention.topic_id, self.set_topic_retention.stream_id
+                )
+            })?;
+
+        event!(target: PRINT_TARGET, Level::INFO,
+            "Topic with ID: {} in stream with ID: {} retention set ({})",
+            self.set_topic_retention.topic_id,
+            self.set_topic_retention.stream_id,
+            self.set_topic_retention.retention
+        );
+
+        Ok(())
+    }
+}
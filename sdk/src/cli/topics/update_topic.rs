@@ -1,7 +1,10 @@
 use crate::cli_command::{CliCommand, PRINT_TARGET};
 use crate::client::Client;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::compression::compression_level::CompressionLevel;
 use crate::identifier::Identifier;
+use crate::topics::cleanup_policy::CleanupPolicy;
+use crate::topics::replication_factor::ReplicationFactor;
 use crate::topics::update_topic::UpdateTopic;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::topic_size::MaxTopicSize;
@@ -18,14 +21,17 @@ pub struct UpdateTopicCmd {
 }
 
 impl UpdateTopicCmd {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         stream_id: Identifier,
         topic_id: Identifier,
         compression_algorithm: CompressionAlgorithm,
+        compression_level: Option<u8>,
         name: String,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
+        cleanup_policy: CleanupPolicy,
     ) -> Self {
         Self {
             update_topic: UpdateTopic {
@@ -33,9 +39,11 @@ impl UpdateTopicCmd {
                 topic_id,
                 name,
                 compression_algorithm,
+                compression_level,
                 message_expiry,
                 max_topic_size,
                 replication_factor: Some(replication_factor),
+                cleanup_policy,
             },
             message_expiry,
             max_topic_size,
@@ -52,7 +60,7 @@ impl CliCommand for UpdateTopicCmd {
 
     async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
         client
-            .update_topic(&self.update_topic.stream_id, &self.update_topic.topic_id, &self.update_topic.name, self.update_topic.compression_algorithm, self.replication_factor.into(), self.message_expiry, self.max_topic_size)
+            .update_topic(&self.update_topic.stream_id, &self.update_topic.topic_id, &self.update_topic.name, self.update_topic.compression_algorithm, self.update_topic.compression_level.map(CompressionLevel), Some(ReplicationFactor(self.replication_factor)), self.message_expiry, self.max_topic_size, self.update_topic.cleanup_policy)
             .await
             .with_context(|| {
                 format!(
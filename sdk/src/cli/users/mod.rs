@@ -3,5 +3,6 @@ pub mod create_user;
 pub mod delete_user;
 pub mod get_user;
 pub mod get_users;
+pub mod set_user_quota;
 pub mod update_permissions;
 pub mod update_user;
@@ -0,0 +1,49 @@
+use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::client::Client;
+use crate::identifier::Identifier;
+use crate::models::quota::Quota;
+use crate::users::set_user_quota::SetUserQuota;
+use anyhow::Context;
+use async_trait::async_trait;
+use tracing::{event, Level};
+
+pub struct SetUserQuotaCmd {
+    set_user_quota: SetUserQuota,
+}
+
+impl SetUserQuotaCmd {
+    pub fn new(user_id: Identifier, quota: Quota) -> Self {
+        SetUserQuotaCmd {
+            set_user_quota: SetUserQuota { user_id, quota },
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for SetUserQuotaCmd {
+    fn explain(&self) -> String {
+        format!(
+            "set quota for user with ID: {} ({})",
+            self.set_user_quota.user_id, self.set_user_quota.quota
+        )
+    }
+
+    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+        client
+            .set_user_quota(&self.set_user_quota.user_id, self.set_user_quota.quota)
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem setting quota for user with ID: {}",
+                    self.set_user_quota.user_id
+                )
+            })?;
+
+        event!(target: PRINT_TARGET, Level::INFO,
+            "User with ID: {} quota set ({})",
+            self.set_user_quota.user_id, self.set_user_quota.quota
+        );
+
+        Ok(())
+    }
+}
@@ -1,17 +1,24 @@
+use crate::archives::restore_archive::ArchiveRange;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::compression::compression_level::CompressionLevel;
 use crate::consumer::Consumer;
+use crate::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
-use crate::messages::poll_messages::PollingStrategy;
+use crate::messages::message_filter::MessageFilter;
+use crate::messages::poll_messages::{IsolationLevel, PollingStrategy};
 use crate::messages::send_messages::{Message, Partitioning};
+use crate::models::archive::RestoredArchive;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails};
-use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails};
+use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails, ConsumerGroupMembership};
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::messages::PolledMessages;
 use crate::models::permissions::Permissions;
 use crate::models::personal_access_token::{PersonalAccessTokenInfo, RawPersonalAccessToken};
+use crate::models::quota::Quota;
+use crate::models::role::{RoleInfo, RoleInfoDetails};
 use crate::models::snapshot::Snapshot;
 use crate::models::stats::Stats;
 use crate::models::stream::{Stream, StreamDetails};
@@ -20,9 +27,14 @@ use crate::models::user_info::{UserInfo, UserInfoDetails};
 use crate::models::user_status::UserStatus;
 use crate::snapshot::{SnapshotCompression, SystemSnapshotType};
 use crate::tcp::config::{TcpClientConfig, TcpClientReconnectionConfig};
+use crate::topics::cleanup_policy::CleanupPolicy;
+use crate::topics::consumed_retention::ConsumedRetention;
+use crate::topics::replication_factor::ReplicationFactor;
+use crate::transactions::commit_transaction::TransactionOffset;
 use crate::utils::duration::IggyDuration;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::personal_access_token_expiry::PersonalAccessTokenExpiry;
+use crate::utils::server_address::ServerAddressSelection;
 use crate::utils::topic_size::MaxTopicSize;
 use async_broadcast::Receiver;
 use async_trait::async_trait;
@@ -34,6 +46,7 @@ const CONNECTION_STRING_PREFIX: &str = "iggy://";
 #[derive(Debug)]
 pub(crate) struct ConnectionString {
     server_address: String,
+    server_addresses: Vec<String>,
     auto_login: AutoLogin,
     options: ConnectionStringOptions,
 }
@@ -57,6 +70,7 @@ pub enum Credentials {
 pub trait Client:
     SystemClient
     + UserClient
+    + RoleClient
     + PersonalAccessTokenClient
     + StreamClient
     + TopicClient
@@ -64,6 +78,8 @@ pub trait Client:
     + MessageClient
     + ConsumerOffsetClient
     + ConsumerGroupClient
+    + TransactionClient
+    + ArchiveClient
     + Sync
     + Send
     + Debug
@@ -156,6 +172,10 @@ pub trait UserClient {
         user_id: &Identifier,
         permissions: Option<Permissions>,
     ) -> Result<(), IggyError>;
+    /// Set the quota of a user by unique ID or username, the quota with all the limits set to 0 removes the existing quota.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn set_user_quota(&self, user_id: &Identifier, quota: Quota) -> Result<(), IggyError>;
     /// Change the password of a user by unique ID or username.
     ///
     /// Authentication is required, and the permission to manage the users, unless the provided user ID is the same as the authenticated user.
@@ -167,10 +187,65 @@ pub trait UserClient {
     ) -> Result<(), IggyError>;
     /// Login a user by username and password.
     async fn login_user(&self, username: &str, password: &str) -> Result<IdentityInfo, IggyError>;
+    /// Login a user with the JWT issued by the external identity provider (OpenID Connect).
+    ///
+    /// The server must be configured with the issuer of the token, and its claims must be mapped to the existing user.
+    async fn login_with_token(&self, token: &str) -> Result<IdentityInfo, IggyError>;
     /// Logout the currently authenticated user.
     async fn logout_user(&self) -> Result<(), IggyError>;
 }
 
+/// This trait defines the methods to interact with the role module.
+/// The role grants its permissions to all the users it's assigned to, in addition to their own permissions.
+#[async_trait]
+pub trait RoleClient {
+    /// Get the info about a specific role by unique ID or name.
+    ///
+    /// Authentication is required, and the permission to read the users.
+    async fn get_role(&self, role_id: &Identifier) -> Result<Option<RoleInfoDetails>, IggyError>;
+    /// Get the info about all the roles.
+    ///
+    /// Authentication is required, and the permission to read the users.
+    async fn get_roles(&self) -> Result<Vec<RoleInfo>, IggyError>;
+    /// Create a new role.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn create_role(
+        &self,
+        name: &str,
+        permissions: Option<Permissions>,
+    ) -> Result<RoleInfoDetails, IggyError>;
+    /// Delete a role by unique ID or name, which also unassigns it from all its users.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn delete_role(&self, role_id: &Identifier) -> Result<(), IggyError>;
+    /// Update the name and (or) permissions of a role by unique ID or name.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn update_role(
+        &self,
+        role_id: &Identifier,
+        name: Option<&str>,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError>;
+    /// Assign a role by unique ID or name to a user by unique ID or username.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn assign_role(
+        &self,
+        role_id: &Identifier,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError>;
+    /// Unassign a role by unique ID or name from a user by unique ID or username.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn unassign_role(
+        &self,
+        role_id: &Identifier,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError>;
+}
+
 /// This trait defines the methods to interact with the personal access token module.
 #[async_trait]
 pub trait PersonalAccessTokenClient {
@@ -222,6 +297,11 @@ pub trait StreamClient {
     ///
     /// Authentication is required, and the permission to manage the streams.
     async fn purge_stream(&self, stream_id: &Identifier) -> Result<(), IggyError>;
+    /// Set the quota of a stream by unique ID or name, the quota with all the limits set to 0 removes the existing quota.
+    ///
+    /// Authentication is required, and the global permission to manage the streams.
+    async fn set_stream_quota(&self, stream_id: &Identifier, quota: Quota)
+        -> Result<(), IggyError>;
 }
 
 /// This trait defines the methods to interact with the topic module.
@@ -250,10 +330,12 @@ pub trait TopicClient {
         name: &str,
         partitions_count: u32,
         compression_algorithm: CompressionAlgorithm,
-        replication_factor: Option<u8>,
+        compression_level: Option<CompressionLevel>,
+        replication_factor: Option<ReplicationFactor>,
         topic_id: Option<u32>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<TopicDetails, IggyError>;
     /// Update a topic by unique ID or name.
     ///
@@ -264,9 +346,11 @@ pub trait TopicClient {
         topic_id: &Identifier,
         name: &str,
         compression_algorithm: CompressionAlgorithm,
-        replication_factor: Option<u8>,
+        compression_level: Option<CompressionLevel>,
+        replication_factor: Option<ReplicationFactor>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<(), IggyError>;
     /// Delete a topic by unique ID or name.
     ///
@@ -284,6 +368,16 @@ pub trait TopicClient {
         stream_id: &Identifier,
         topic_id: &Identifier,
     ) -> Result<(), IggyError>;
+    /// Set the consumed retention of a topic by unique ID or name, which applies to the topics with the consumed cleanup policy.
+    /// The empty list of the consumer groups means all the consumer groups of the topic.
+    ///
+    /// Authentication is required, and the permission to manage the topics.
+    async fn set_topic_retention(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        retention: ConsumedRetention,
+    ) -> Result<(), IggyError>;
 }
 
 /// This trait defines the methods to interact with the partition module.
@@ -330,6 +424,40 @@ pub trait MessageClient {
         count: u32,
         auto_commit: bool,
     ) -> Result<PolledMessages, IggyError>;
+    /// Poll given amount of messages using the specified consumer, strategy and isolation level from the specified stream and topic by unique IDs or names.
+    /// With the `read committed` isolation level, the messages from the open and aborted transactions are not returned.
+    ///
+    /// Authentication is required, and the permission to poll the messages.
+    #[allow(clippy::too_many_arguments)]
+    async fn poll_messages_with_isolation_level(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+    ) -> Result<PolledMessages, IggyError>;
+    /// Poll given amount of messages using the specified consumer, strategy and isolation level from the specified stream and topic by unique IDs or names,
+    /// returning only the messages with the headers matching the filter. The filter is evaluated by the server, and the count
+    /// applies to the matching messages, so fewer messages than requested are returned only if the partition end is reached.
+    ///
+    /// Authentication is required, and the permission to poll the messages.
+    #[allow(clippy::too_many_arguments)]
+    async fn poll_messages_with_filter(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+        filter: &MessageFilter,
+    ) -> Result<PolledMessages, IggyError>;
     /// Send messages using specified partitioning strategy to the given stream and topic by unique IDs or names.
     ///
     /// Authentication is required, and the permission to send the messages.
@@ -349,12 +477,36 @@ pub trait MessageClient {
         partition_id: u32,
         fsync: bool,
     ) -> Result<(), IggyError>;
+    /// Negatively acknowledge the message at the given offset, which couldn't be processed by the consumer or consumer group.
+    /// Once the configured number of delivery attempts is reached, the message is moved to the dead letter topic (if enabled on the server).
+    ///
+    /// Authentication is required, and the permission to poll the messages.
+    async fn nack_message(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+        reason: &str,
+    ) -> Result<(), IggyError>;
+    /// Start the new epoch of the idempotent producer for the given stream and topic by unique IDs or names.
+    /// The server allocates the epoch higher than the previous ones of the producer, so the older instances get fenced.
+    ///
+    /// Authentication is required, and the permission to send the messages.
+    async fn init_producer(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        producer_id: u64,
+    ) -> Result<u64, IggyError>;
 }
 
 /// This trait defines the methods to interact with the consumer offset module.
 #[async_trait]
 pub trait ConsumerOffsetClient {
     /// Store the consumer offset for a specific consumer or consumer group for the given stream and topic by unique IDs or names.
+    /// If the consumer group generation is provided, the offset is rejected when the consumer group has been rebalanced since then.
     ///
     /// Authentication is required, and the permission to poll the messages.
     async fn store_consumer_offset(
@@ -364,6 +516,7 @@ pub trait ConsumerOffsetClient {
         topic_id: &Identifier,
         partition_id: Option<u32>,
         offset: u64,
+        generation: Option<u32>,
     ) -> Result<(), IggyError>;
     /// Get the consumer offset for a specific consumer or consumer group for the given stream and topic by unique IDs or names.
     ///
@@ -416,6 +569,7 @@ pub trait ConsumerGroupClient {
         topic_id: &Identifier,
         name: &str,
         group_id: Option<u32>,
+        assignment_strategy: PartitionAssignmentStrategy,
     ) -> Result<ConsumerGroupDetails, IggyError>;
     /// Delete a consumer group by unique ID or name for the given stream and topic by unique IDs or names.
     ///
@@ -427,6 +581,7 @@ pub trait ConsumerGroupClient {
         group_id: &Identifier,
     ) -> Result<(), IggyError>;
     /// Join a consumer group by unique ID or name for the given stream and topic by unique IDs or names.
+    /// Returns the member ID, the current generation of the consumer group and the partitions assigned to the member.
     ///
     /// Authentication is required, and the permission to read the streams or topics.
     async fn join_consumer_group(
@@ -434,7 +589,7 @@ pub trait ConsumerGroupClient {
         stream_id: &Identifier,
         topic_id: &Identifier,
         group_id: &Identifier,
-    ) -> Result<(), IggyError>;
+    ) -> Result<ConsumerGroupMembership, IggyError>;
     /// Leave a consumer group by unique ID or name for the given stream and topic by unique IDs or names.
     ///
     /// Authentication is required, and the permission to read the streams or topics.
@@ -446,6 +601,47 @@ pub trait ConsumerGroupClient {
     ) -> Result<(), IggyError>;
 }
 
+/// This trait defines the methods to interact with the transaction module.
+#[async_trait]
+pub trait TransactionClient {
+    /// Begin a new transaction and return its ID, which has to be set in the `iggy-transaction-id` header of the sent messages.
+    /// The transaction is aborted if the client disconnects before committing it.
+    ///
+    /// Authentication is required.
+    async fn begin_transaction(&self) -> Result<u64, IggyError>;
+    /// Commit the transaction by unique ID, which makes its messages visible to the `read committed` consumers,
+    /// and store the provided consumer offsets.
+    ///
+    /// Authentication is required, and the permission to poll the messages if any offsets are provided.
+    async fn commit_transaction(
+        &self,
+        transaction_id: u64,
+        offsets: &[TransactionOffset],
+    ) -> Result<(), IggyError>;
+    /// Abort the transaction by unique ID, its messages will never be visible to the `read committed` consumers.
+    ///
+    /// Authentication is required.
+    async fn abort_transaction(&self, transaction_id: u64) -> Result<(), IggyError>;
+}
+
+/// This trait defines the methods to interact with the archive module.
+#[async_trait]
+pub trait ArchiveClient {
+    /// Restore the stream, topic (if specified) or partition (if specified) from the archived segments within the provided range.
+    /// The stream or topic which no longer exists is recreated from the archived state snapshot (or the latest archived state if not specified).
+    /// Only the segments preceding the messages stored locally are restored.
+    ///
+    /// Authentication is required, and the permission to manage the streams.
+    async fn restore_archive(
+        &self,
+        stream_id: &Identifier,
+        topic_id: Option<&Identifier>,
+        partition_id: Option<u32>,
+        range: ArchiveRange,
+        state_snapshot: Option<&str>,
+    ) -> Result<RestoredArchive, IggyError>;
+}
+
 impl FromStr for ConnectionString {
     type Err = IggyError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -486,22 +682,24 @@ impl ConnectionString {
             return Err(IggyError::InvalidConnectionString);
         }
 
-        let server_address = server_and_options[0];
-        if server_address.is_empty() {
+        if server_and_options[0].is_empty() {
             return Err(IggyError::InvalidConnectionString);
         }
 
-        if !server_address.contains(':') {
-            return Err(IggyError::InvalidConnectionString);
-        }
+        // The multiple servers can be provided as the comma-separated addresses.
+        let server_addresses = server_and_options[0].split(',').collect::<Vec<&str>>();
+        for address in &server_addresses {
+            let Some((_, port)) = address.rsplit_once(':') else {
+                return Err(IggyError::InvalidConnectionString);
+            };
 
-        let port = server_address.split(':').collect::<Vec<&str>>()[1];
-        if port.is_empty() {
-            return Err(IggyError::InvalidConnectionString);
-        }
+            if port.is_empty() {
+                return Err(IggyError::InvalidConnectionString);
+            }
 
-        if port.parse::<u16>().is_err() {
-            return Err(IggyError::InvalidConnectionString);
+            if port.parse::<u16>().is_err() {
+                return Err(IggyError::InvalidConnectionString);
+            }
         }
 
         let connection_string_options;
@@ -512,7 +710,12 @@ impl ConnectionString {
         }
 
         Ok(ConnectionString {
-            server_address: server_address.to_owned(),
+            server_address: server_addresses[0].to_owned(),
+            server_addresses: if server_addresses.len() > 1 {
+                server_addresses.into_iter().map(str::to_owned).collect()
+            } else {
+                Vec::new()
+            },
             auto_login: AutoLogin::Enabled(Credentials::UsernamePassword(
                 username.to_owned(),
                 password.to_owned(),
@@ -526,11 +729,16 @@ impl ConnectionString {
         let mut tls_enabled = false;
         let mut tls_domain = "localhost".to_string();
         let mut tls_ca_file = None;
+        let mut tls_cert_file = None;
+        let mut tls_key_file = None;
         let mut reconnection_retries = "unlimited".to_owned();
         let mut reconnection_interval = "1s".to_owned();
         let mut reestablish_after = "5s".to_owned();
         let mut heartbeat_interval = "5s".to_owned();
         let mut nodelay = false;
+        let mut zero_copy = false;
+        let mut pipelining = false;
+        let mut address_selection = ServerAddressSelection::default();
 
         for option in options {
             let option_parts = option.split('=').collect::<Vec<&str>>();
@@ -547,6 +755,12 @@ impl ConnectionString {
                 "tls_ca_file" => {
                     tls_ca_file = Some(option_parts[1].to_string());
                 }
+                "tls_cert_file" => {
+                    tls_cert_file = Some(option_parts[1].to_string());
+                }
+                "tls_key_file" => {
+                    tls_key_file = Some(option_parts[1].to_string());
+                }
                 "reconnection_retries" => {
                     reconnection_retries = option_parts[1].to_string();
                 }
@@ -562,6 +776,16 @@ impl ConnectionString {
                 "nodelay" => {
                     nodelay = option_parts[1] == "true";
                 }
+                "zero_copy" => {
+                    zero_copy = option_parts[1] == "true";
+                }
+                "pipelining" => {
+                    pipelining = option_parts[1] == "true";
+                }
+                "address_selection" => {
+                    address_selection = ServerAddressSelection::from_str(option_parts[1])
+                        .map_err(|_| IggyError::InvalidConnectionString)?;
+                }
                 _ => {
                     return Err(IggyError::InvalidConnectionString);
                 }
@@ -571,6 +795,8 @@ impl ConnectionString {
             tls_enabled,
             tls_domain,
             tls_ca_file,
+            tls_cert_file,
+            tls_key_file,
             heartbeat_interval: IggyDuration::from_str(heartbeat_interval.as_str())
                 .map_err(|_| IggyError::InvalidConnectionString)?,
             reconnection: TcpClientReconnectionConfig {
@@ -589,6 +815,9 @@ impl ConnectionString {
                     .map_err(|_| IggyError::InvalidConnectionString)?,
             },
             nodelay,
+            zero_copy,
+            pipelining,
+            address_selection,
         })
     }
 }
@@ -598,9 +827,14 @@ struct ConnectionStringOptions {
     tls_enabled: bool,
     tls_domain: String,
     tls_ca_file: Option<String>,
+    tls_cert_file: Option<String>,
+    tls_key_file: Option<String>,
     reconnection: TcpClientReconnectionConfig,
     heartbeat_interval: IggyDuration,
     nodelay: bool,
+    zero_copy: bool,
+    pipelining: bool,
+    address_selection: ServerAddressSelection,
 }
 
 impl Default for ConnectionStringOptions {
@@ -609,9 +843,14 @@ impl Default for ConnectionStringOptions {
             tls_enabled: false,
             tls_domain: "".to_string(),
             tls_ca_file: None,
+            tls_cert_file: None,
+            tls_key_file: None,
             reconnection: Default::default(),
             heartbeat_interval: IggyDuration::from_str("5s").unwrap(),
             nodelay: false,
+            zero_copy: false,
+            pipelining: false,
+            address_selection: ServerAddressSelection::default(),
         }
     }
 }
@@ -620,13 +859,19 @@ impl From<ConnectionString> for TcpClientConfig {
     fn from(connection_string: ConnectionString) -> Self {
         TcpClientConfig {
             server_address: connection_string.server_address,
+            server_addresses: connection_string.server_addresses,
+            address_selection: connection_string.options.address_selection,
             auto_login: connection_string.auto_login,
             tls_enabled: connection_string.options.tls_enabled,
             tls_domain: connection_string.options.tls_domain,
             tls_ca_file: connection_string.options.tls_ca_file,
+            tls_cert_file: connection_string.options.tls_cert_file,
+            tls_key_file: connection_string.options.tls_key_file,
             reconnection: connection_string.options.reconnection,
             heartbeat_interval: connection_string.options.heartbeat_interval,
             nodelay: connection_string.options.nodelay,
+            zero_copy: connection_string.options.zero_copy,
+            pipelining: connection_string.options.pipelining,
         }
     }
 }
@@ -685,6 +930,8 @@ mod tests {
         assert!(!connection_string.options.tls_enabled);
         assert!(connection_string.options.tls_domain.is_empty());
         assert!(connection_string.options.tls_ca_file.is_none());
+        assert!(connection_string.options.tls_cert_file.is_none());
+        assert!(connection_string.options.tls_key_file.is_none());
         assert!(connection_string.options.reconnection.enabled);
         assert!(connection_string.options.reconnection.max_retries.is_none());
         assert_eq!(
@@ -692,6 +939,8 @@ mod tests {
             IggyDuration::from_str("1s").unwrap()
         );
         assert!(!connection_string.options.nodelay);
+        assert!(!connection_string.options.zero_copy);
+        assert!(!connection_string.options.pipelining);
     }
 
     #[test]
@@ -702,12 +951,16 @@ mod tests {
         let tls = true;
         let tls_domain = "test.com";
         let tls_ca_file = "ca.pem";
+        let tls_cert_file = "client.pem";
+        let tls_key_file = "client_key.pem";
         let reconnection_retries = 5;
         let reconnection_interval = "5s";
         let reestablish_after = "10s";
         let heartbeat_interval = "3s";
         let nodelay = true;
-        let value = format!("{CONNECTION_STRING_PREFIX}{username}:{password}@{server_address}?tls={tls}&tls_domain={tls_domain}&tls_ca_file={tls_ca_file}&reconnection_retries={reconnection_retries}&reconnection_interval={reconnection_interval}&reestablish_after={reestablish_after}&heartbeat_interval={heartbeat_interval}&nodelay={nodelay}");
+        let zero_copy = true;
+        let pipelining = true;
+        let value = format!("{CONNECTION_STRING_PREFIX}{username}:{password}@{server_address}?tls={tls}&tls_domain={tls_domain}&tls_ca_file={tls_ca_file}&tls_cert_file={tls_cert_file}&tls_key_file={tls_key_file}&reconnection_retries={reconnection_retries}&reconnection_interval={reconnection_interval}&reestablish_after={reestablish_after}&heartbeat_interval={heartbeat_interval}&nodelay={nodelay}&zero_copy={zero_copy}&pipelining={pipelining}");
         let connection_string = ConnectionString::new(&value);
         assert!(connection_string.is_ok());
         let connection_string = connection_string.unwrap();
@@ -726,6 +979,14 @@ mod tests {
             connection_string.options.tls_ca_file,
             Some(tls_ca_file.to_owned())
         );
+        assert_eq!(
+            connection_string.options.tls_cert_file,
+            Some(tls_cert_file.to_owned())
+        );
+        assert_eq!(
+            connection_string.options.tls_key_file,
+            Some(tls_key_file.to_owned())
+        );
         assert!(connection_string.options.reconnection.enabled);
         assert_eq!(
             connection_string.options.reconnection.max_retries,
@@ -744,5 +1005,30 @@ mod tests {
             IggyDuration::from_str(heartbeat_interval).unwrap()
         );
         assert_eq!(connection_string.options.nodelay, nodelay);
+        assert_eq!(connection_string.options.zero_copy, zero_copy);
+        assert_eq!(connection_string.options.pipelining, pipelining);
+    }
+
+    #[test]
+    fn connection_string_with_multiple_server_addresses_should_be_parsed_correctly() {
+        let value = format!(
+            "{CONNECTION_STRING_PREFIX}user1:secret@primary:8090,standby:8090?address_selection=round_robin"
+        );
+        let connection_string = ConnectionString::new(&value).unwrap();
+        assert_eq!(connection_string.server_address, "primary:8090");
+        assert_eq!(
+            connection_string.server_addresses,
+            vec!["primary:8090", "standby:8090"]
+        );
+        assert_eq!(
+            connection_string.options.address_selection,
+            ServerAddressSelection::RoundRobin
+        );
+    }
+
+    #[test]
+    fn connection_string_with_server_address_without_port_should_fail() {
+        let value = format!("{CONNECTION_STRING_PREFIX}user1:secret@primary:8090,standby");
+        assert!(ConnectionString::new(&value).is_err());
     }
 }
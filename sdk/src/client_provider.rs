@@ -9,6 +9,7 @@ use crate::quic::config::{QuicClientConfig, QuicClientReconnectionConfig};
 use crate::tcp::client::TcpClient;
 use crate::tcp::config::{TcpClientConfig, TcpClientReconnectionConfig};
 use crate::utils::duration::IggyDuration;
+use crate::utils::server_address::ServerAddressSelection;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -69,6 +70,8 @@ impl ClientProviderConfig {
                 config.quic = Some(Arc::new(QuicClientConfig {
                     client_address: args.quic_client_address,
                     server_address: args.quic_server_address,
+                    server_addresses: args.quic_server_addresses,
+                    address_selection: ServerAddressSelection::default(),
                     server_name: args.quic_server_name,
                     heartbeat_interval: IggyDuration::from_str(&args.quic_heartbeat_interval)
                         .unwrap(),
@@ -98,21 +101,34 @@ impl ClientProviderConfig {
                     keep_alive_interval: args.quic_keep_alive_interval,
                     max_idle_timeout: args.quic_max_idle_timeout,
                     validate_certificate: args.quic_validate_certificate,
+                    tls_cert_file: None,
+                    tls_key_file: None,
                 }));
             }
             HTTP_TRANSPORT => {
                 config.http = Some(Arc::new(HttpClientConfig {
                     api_url: args.http_api_url,
+                    api_urls: args.http_api_urls,
+                    address_selection: ServerAddressSelection::default(),
                     retries: args.http_retries,
+                    tls_ca_file: None,
+                    tls_cert_file: None,
+                    tls_key_file: None,
                 }));
             }
             TCP_TRANSPORT => {
                 config.tcp = Some(Arc::new(TcpClientConfig {
                     server_address: args.tcp_server_address,
+                    server_addresses: args.tcp_server_addresses,
+                    address_selection: ServerAddressSelection::default(),
                     tls_enabled: args.tcp_tls_enabled,
                     tls_domain: args.tcp_tls_domain,
                     tls_ca_file: args.tcp_tls_ca_file,
+                    tls_cert_file: None,
+                    tls_key_file: None,
                     nodelay: args.tcp_nodelay,
+                    zero_copy: args.tcp_zero_copy,
+                    pipelining: args.tcp_pipelining,
                     heartbeat_interval: IggyDuration::from_str(&args.tcp_heartbeat_interval)
                         .unwrap(),
                     reconnection: TcpClientReconnectionConfig {
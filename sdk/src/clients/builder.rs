@@ -10,6 +10,7 @@ use crate::tcp::client::TcpClient;
 use crate::tcp::config::TcpClientConfigBuilder;
 use crate::utils::crypto::EncryptorKind;
 use crate::utils::duration::IggyDuration;
+use crate::utils::server_address::ServerAddressSelection;
 use std::sync::Arc;
 use tracing::error;
 
@@ -111,6 +112,18 @@ impl TcpClientBuilder {
         self
     }
 
+    /// Sets the addresses of the multiple servers, used instead of the single server address.
+    pub fn with_server_addresses(mut self, server_addresses: Vec<String>) -> Self {
+        self.config = self.config.with_server_addresses(server_addresses);
+        self
+    }
+
+    /// Sets the policy of selecting the server, when the multiple server addresses are configured.
+    pub fn with_address_selection(mut self, address_selection: ServerAddressSelection) -> Self {
+        self.config = self.config.with_address_selection(address_selection);
+        self
+    }
+
     /// Sets the auto sign in during connection.
     pub fn with_auto_sign_in(mut self, auto_sign_in: AutoLogin) -> Self {
         self.config = self.config.with_auto_sign_in(auto_sign_in);
@@ -151,12 +164,30 @@ impl TcpClientBuilder {
         self
     }
 
+    /// Sets the paths to the client certificate and its private key for TLS.
+    pub fn with_tls_client_certificate(mut self, cert_file: String, key_file: String) -> Self {
+        self.config = self.config.with_tls_client_certificate(cert_file, key_file);
+        self
+    }
+
     /// Sets the nodelay option for the TCP socket.
     pub fn with_no_delay(mut self) -> Self {
         self.config = self.config.with_no_delay();
         self
     }
 
+    /// Sets whether to request the polled messages as the batches stored on the server.
+    pub fn with_zero_copy(mut self, zero_copy: bool) -> Self {
+        self.config = self.config.with_zero_copy(zero_copy);
+        self
+    }
+
+    /// Sets whether to negotiate the pipelined protocol with the server.
+    pub fn with_pipelining(mut self, pipelining: bool) -> Self {
+        self.config = self.config.with_pipelining(pipelining);
+        self
+    }
+
     /// Builds the parent `IggyClient` with TCP configuration.
     pub fn build(self) -> Result<IggyClient, IggyError> {
         let client = TcpClient::create(Arc::new(self.config.build()))?;
@@ -178,6 +209,18 @@ impl QuicClientBuilder {
         self
     }
 
+    /// Sets the addresses of the multiple servers, used instead of the single server address.
+    pub fn with_server_addresses(mut self, server_addresses: Vec<String>) -> Self {
+        self.config = self.config.with_server_addresses(server_addresses);
+        self
+    }
+
+    /// Sets the policy of selecting the server, when the multiple server addresses are configured.
+    pub fn with_address_selection(mut self, address_selection: ServerAddressSelection) -> Self {
+        self.config = self.config.with_address_selection(address_selection);
+        self
+    }
+
     /// Sets the auto sign in during connection.
     pub fn with_auto_sign_in(mut self, auto_sign_in: AutoLogin) -> Self {
         self.config = self.config.with_auto_sign_in(auto_sign_in);
@@ -206,6 +249,12 @@ impl QuicClientBuilder {
         self
     }
 
+    /// Sets the paths to the client certificate and its private key.
+    pub fn with_tls_client_certificate(mut self, cert_file: String, key_file: String) -> Self {
+        self.config = self.config.with_tls_client_certificate(cert_file, key_file);
+        self
+    }
+
     /// Builds the parent `IggyClient` with QUIC configuration.
     pub fn build(self) -> Result<IggyClient, IggyError> {
         let client = QuicClient::create(Arc::new(self.config.build()))?;
@@ -227,12 +276,36 @@ impl HttpClientBuilder {
         self
     }
 
+    /// Sets the URLs of the multiple servers, used instead of the single API URL.
+    pub fn with_api_urls(mut self, api_urls: Vec<String>) -> Self {
+        self.config = self.config.with_api_urls(api_urls);
+        self
+    }
+
+    /// Sets the policy of selecting the server, when the multiple server addresses are configured.
+    pub fn with_address_selection(mut self, address_selection: ServerAddressSelection) -> Self {
+        self.config = self.config.with_address_selection(address_selection);
+        self
+    }
+
     /// Sets the number of retries for the HTTP client.
     pub fn with_retries(mut self, retries: u32) -> Self {
         self.config = self.config.with_retries(retries);
         self
     }
 
+    /// Sets the path to the CA file for TLS.
+    pub fn with_tls_ca_file(mut self, tls_ca_file: String) -> Self {
+        self.config = self.config.with_tls_ca_file(tls_ca_file);
+        self
+    }
+
+    /// Sets the paths to the client certificate and its private key for TLS.
+    pub fn with_tls_client_certificate(mut self, cert_file: String, key_file: String) -> Self {
+        self.config = self.config.with_tls_client_certificate(cert_file, key_file);
+        self
+    }
+
     /// Builds the parent `IggyClient` with HTTP configuration.
     pub fn build(self) -> Result<IggyClient, IggyError> {
         let client = HttpClient::create(Arc::new(self.config.build()))?;
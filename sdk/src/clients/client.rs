@@ -1,26 +1,34 @@
+use crate::archives::restore_archive::ArchiveRange;
 use crate::client::{
-    Client, ConsumerGroupClient, ConsumerOffsetClient, MessageClient, PartitionClient,
-    PersonalAccessTokenClient, StreamClient, SystemClient, TopicClient, UserClient,
+    ArchiveClient, Client, ConsumerGroupClient, ConsumerOffsetClient, MessageClient,
+    PartitionClient, PersonalAccessTokenClient, RoleClient, StreamClient, SystemClient,
+    TopicClient, TransactionClient, UserClient,
 };
 use crate::clients::builder::IggyClientBuilder;
 use crate::clients::consumer::IggyConsumerBuilder;
 use crate::clients::producer::IggyProducerBuilder;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::compression::compression_level::CompressionLevel;
 use crate::consumer::Consumer;
+use crate::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::locking::IggySharedMut;
 use crate::locking::IggySharedMutFn;
-use crate::messages::poll_messages::PollingStrategy;
+use crate::messages::message_filter::MessageFilter;
+use crate::messages::poll_messages::{IsolationLevel, PollingStrategy};
 use crate::messages::send_messages::{Message, Partitioning};
+use crate::models::archive::RestoredArchive;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails};
-use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails};
+use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails, ConsumerGroupMembership};
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::messages::PolledMessages;
 use crate::models::permissions::Permissions;
 use crate::models::personal_access_token::{PersonalAccessTokenInfo, RawPersonalAccessToken};
+use crate::models::quota::Quota;
+use crate::models::role::{RoleInfo, RoleInfoDetails};
 use crate::models::snapshot::Snapshot;
 use crate::models::stats::Stats;
 use crate::models::stream::{Stream, StreamDetails};
@@ -30,6 +38,10 @@ use crate::models::user_status::UserStatus;
 use crate::partitioner::Partitioner;
 use crate::snapshot::{SnapshotCompression, SystemSnapshotType};
 use crate::tcp::client::TcpClient;
+use crate::topics::cleanup_policy::CleanupPolicy;
+use crate::topics::consumed_retention::ConsumedRetention;
+use crate::topics::replication_factor::ReplicationFactor;
+use crate::transactions::commit_transaction::TransactionOffset;
 use crate::utils::byte_size::IggyByteSize;
 use crate::utils::crypto::EncryptorKind;
 use crate::utils::duration::IggyDuration;
@@ -266,6 +278,14 @@ impl UserClient for IggyClient {
             .await
     }
 
+    async fn set_user_quota(&self, user_id: &Identifier, quota: Quota) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .set_user_quota(user_id, quota)
+            .await
+    }
+
     async fn change_password(
         &self,
         user_id: &Identifier,
@@ -287,11 +307,75 @@ impl UserClient for IggyClient {
             .await
     }
 
+    async fn login_with_token(&self, token: &str) -> Result<IdentityInfo, IggyError> {
+        self.client.read().await.login_with_token(token).await
+    }
+
     async fn logout_user(&self) -> Result<(), IggyError> {
         self.client.read().await.logout_user().await
     }
 }
 
+#[async_trait]
+impl RoleClient for IggyClient {
+    async fn get_role(&self, role_id: &Identifier) -> Result<Option<RoleInfoDetails>, IggyError> {
+        self.client.read().await.get_role(role_id).await
+    }
+
+    async fn get_roles(&self) -> Result<Vec<RoleInfo>, IggyError> {
+        self.client.read().await.get_roles().await
+    }
+
+    async fn create_role(
+        &self,
+        name: &str,
+        permissions: Option<Permissions>,
+    ) -> Result<RoleInfoDetails, IggyError> {
+        self.client
+            .read()
+            .await
+            .create_role(name, permissions)
+            .await
+    }
+
+    async fn delete_role(&self, role_id: &Identifier) -> Result<(), IggyError> {
+        self.client.read().await.delete_role(role_id).await
+    }
+
+    async fn update_role(
+        &self,
+        role_id: &Identifier,
+        name: Option<&str>,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .update_role(role_id, name, permissions)
+            .await
+    }
+
+    async fn assign_role(
+        &self,
+        role_id: &Identifier,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        self.client.read().await.assign_role(role_id, user_id).await
+    }
+
+    async fn unassign_role(
+        &self,
+        role_id: &Identifier,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .unassign_role(role_id, user_id)
+            .await
+    }
+}
+
 #[async_trait]
 impl PersonalAccessTokenClient for IggyClient {
     async fn get_personal_access_tokens(&self) -> Result<Vec<PersonalAccessTokenInfo>, IggyError> {
@@ -406,6 +490,18 @@ impl StreamClient for IggyClient {
     async fn purge_stream(&self, stream_id: &Identifier) -> Result<(), IggyError> {
         self.client.read().await.purge_stream(stream_id).await
     }
+
+    async fn set_stream_quota(
+        &self,
+        stream_id: &Identifier,
+        quota: Quota,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .set_stream_quota(stream_id, quota)
+            .await
+    }
 }
 
 #[async_trait]
@@ -432,10 +528,12 @@ impl TopicClient for IggyClient {
         name: &str,
         partitions_count: u32,
         compression_algorithm: CompressionAlgorithm,
-        replication_factor: Option<u8>,
+        compression_level: Option<CompressionLevel>,
+        replication_factor: Option<ReplicationFactor>,
         topic_id: Option<u32>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<TopicDetails, IggyError> {
         self.client
             .read()
@@ -445,10 +543,12 @@ impl TopicClient for IggyClient {
                 name,
                 partitions_count,
                 compression_algorithm,
+                compression_level,
                 replication_factor,
                 topic_id,
                 message_expiry,
                 max_topic_size,
+                cleanup_policy,
             )
             .await
     }
@@ -459,9 +559,11 @@ impl TopicClient for IggyClient {
         topic_id: &Identifier,
         name: &str,
         compression_algorithm: CompressionAlgorithm,
-        replication_factor: Option<u8>,
+        compression_level: Option<CompressionLevel>,
+        replication_factor: Option<ReplicationFactor>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<(), IggyError> {
         self.client
             .read()
@@ -471,9 +573,11 @@ impl TopicClient for IggyClient {
                 topic_id,
                 name,
                 compression_algorithm,
+                compression_level,
                 replication_factor,
                 message_expiry,
                 max_topic_size,
+                cleanup_policy,
             )
             .await
     }
@@ -501,6 +605,19 @@ impl TopicClient for IggyClient {
             .purge_topic(stream_id, topic_id)
             .await
     }
+
+    async fn set_topic_retention(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        retention: ConsumedRetention,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .set_topic_retention(stream_id, topic_id, retention)
+            .await
+    }
 }
 
 #[async_trait]
@@ -543,6 +660,56 @@ impl MessageClient for IggyClient {
         strategy: &PollingStrategy,
         count: u32,
         auto_commit: bool,
+    ) -> Result<PolledMessages, IggyError> {
+        self.poll_messages_with_isolation_level(
+            stream_id,
+            topic_id,
+            partition_id,
+            consumer,
+            strategy,
+            count,
+            auto_commit,
+            IsolationLevel::ReadUncommitted,
+        )
+        .await
+    }
+
+    async fn poll_messages_with_isolation_level(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+    ) -> Result<PolledMessages, IggyError> {
+        self.poll_messages_with_filter(
+            stream_id,
+            topic_id,
+            partition_id,
+            consumer,
+            strategy,
+            count,
+            auto_commit,
+            isolation_level,
+            &MessageFilter::default(),
+        )
+        .await
+    }
+
+    async fn poll_messages_with_filter(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+        filter: &MessageFilter,
     ) -> Result<PolledMessages, IggyError> {
         if count == 0 {
             return Err(IggyError::InvalidMessagesCount);
@@ -552,7 +719,7 @@ impl MessageClient for IggyClient {
             .client
             .read()
             .await
-            .poll_messages(
+            .poll_messages_with_filter(
                 stream_id,
                 topic_id,
                 partition_id,
@@ -560,6 +727,8 @@ impl MessageClient for IggyClient {
                 strategy,
                 count,
                 auto_commit,
+                isolation_level,
+                filter,
             )
             .await?;
 
@@ -612,6 +781,35 @@ impl MessageClient for IggyClient {
             .flush_unsaved_buffer(stream_id, topic_id, partition_id, fsync)
             .await
     }
+
+    async fn nack_message(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+        reason: &str,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .nack_message(consumer, stream_id, topic_id, partition_id, offset, reason)
+            .await
+    }
+
+    async fn init_producer(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        producer_id: u64,
+    ) -> Result<u64, IggyError> {
+        self.client
+            .read()
+            .await
+            .init_producer(stream_id, topic_id, producer_id)
+            .await
+    }
 }
 
 #[async_trait]
@@ -623,11 +821,19 @@ impl ConsumerOffsetClient for IggyClient {
         topic_id: &Identifier,
         partition_id: Option<u32>,
         offset: u64,
+        generation: Option<u32>,
     ) -> Result<(), IggyError> {
         self.client
             .read()
             .await
-            .store_consumer_offset(consumer, stream_id, topic_id, partition_id, offset)
+            .store_consumer_offset(
+                consumer,
+                stream_id,
+                topic_id,
+                partition_id,
+                offset,
+                generation,
+            )
             .await
     }
 
@@ -693,11 +899,12 @@ impl ConsumerGroupClient for IggyClient {
         topic_id: &Identifier,
         name: &str,
         group_id: Option<u32>,
+        assignment_strategy: PartitionAssignmentStrategy,
     ) -> Result<ConsumerGroupDetails, IggyError> {
         self.client
             .read()
             .await
-            .create_consumer_group(stream_id, topic_id, name, group_id)
+            .create_consumer_group(stream_id, topic_id, name, group_id, assignment_strategy)
             .await
     }
 
@@ -719,7 +926,7 @@ impl ConsumerGroupClient for IggyClient {
         stream_id: &Identifier,
         topic_id: &Identifier,
         group_id: &Identifier,
-    ) -> Result<(), IggyError> {
+    ) -> Result<ConsumerGroupMembership, IggyError> {
         self.client
             .read()
             .await
@@ -741,6 +948,51 @@ impl ConsumerGroupClient for IggyClient {
     }
 }
 
+#[async_trait]
+impl TransactionClient for IggyClient {
+    async fn begin_transaction(&self) -> Result<u64, IggyError> {
+        self.client.read().await.begin_transaction().await
+    }
+
+    async fn commit_transaction(
+        &self,
+        transaction_id: u64,
+        offsets: &[TransactionOffset],
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .commit_transaction(transaction_id, offsets)
+            .await
+    }
+
+    async fn abort_transaction(&self, transaction_id: u64) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .abort_transaction(transaction_id)
+            .await
+    }
+}
+
+#[async_trait]
+impl ArchiveClient for IggyClient {
+    async fn restore_archive(
+        &self,
+        stream_id: &Identifier,
+        topic_id: Option<&Identifier>,
+        partition_id: Option<u32>,
+        range: ArchiveRange,
+        state_snapshot: Option<&str>,
+    ) -> Result<RestoredArchive, IggyError> {
+        self.client
+            .read()
+            .await
+            .restore_archive(stream_id, topic_id, partition_id, range, state_snapshot)
+            .await
+    }
+}
+
 #[async_trait]
 impl AsyncDrop for IggyClient {
     async fn async_drop(&mut self) {
@@ -1,10 +1,15 @@
 use crate::client::Client;
+use crate::compression::compression_algorithm::{CompressionAlgorithm, COMPRESSION_HEADER_KEY};
 use crate::consumer::{Consumer, ConsumerKind};
+use crate::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
 use crate::identifier::{IdKind, Identifier};
 use crate::locking::{IggySharedMut, IggySharedMutFn};
 use crate::messages::poll_messages::{PollingKind, PollingStrategy};
+use crate::messages::MAX_PAYLOAD_SIZE;
+use crate::models::consumer_group::ConsumerGroupMembership;
+use crate::models::header::HeaderKey;
 use crate::models::messages::{PolledMessage, PolledMessages};
 use crate::utils::byte_size::IggyByteSize;
 use crate::utils::crypto::EncryptorKind;
@@ -15,10 +20,12 @@ use dashmap::DashMap;
 use futures::Stream;
 use futures_util::{FutureExt, StreamExt};
 use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
 use std::future::Future;
 use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 use std::time::Duration;
 use tokio::time;
@@ -77,6 +84,101 @@ pub enum AutoCommitAfter {
     ConsumingEveryNthMessage(u32),
 }
 
+/// The callback invoked with the IDs of the partitions assigned to or revoked from the consumer group member.
+#[derive(Clone)]
+pub struct PartitionsCallback(Arc<PartitionsCallbackFn>);
+
+type PartitionsCallbackFn = dyn Fn(&[u32]) + Send + Sync;
+
+impl PartitionsCallback {
+    pub fn new(callback: impl Fn(&[u32]) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+}
+
+impl Debug for PartitionsCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PartitionsCallback")
+    }
+}
+
+/// The partitions assigned to the consumer group member along with the generation of the consumer group they belong to.
+#[derive(Debug, Default)]
+struct ConsumerGroupAssignment {
+    member_id: AtomicU32,
+    generation: AtomicU32,
+    partitions: Mutex<Vec<u32>>,
+    on_partitions_assigned: Option<PartitionsCallback>,
+    on_partitions_revoked: Option<PartitionsCallback>,
+}
+
+impl ConsumerGroupAssignment {
+    fn generation(&self) -> Option<u32> {
+        match self.generation.load(ORDERING) {
+            0 => None,
+            generation => Some(generation),
+        }
+    }
+
+    fn partitions(&self) -> Vec<u32> {
+        self.partitions
+            .lock()
+            .map(|partitions| partitions.clone())
+            .unwrap_or_default()
+    }
+
+    fn join(&self, membership: ConsumerGroupMembership) -> Vec<u32> {
+        self.member_id.store(membership.member_id, ORDERING);
+        self.update(membership.generation, membership.partitions)
+    }
+
+    fn leave(&self) -> Vec<u32> {
+        self.member_id.store(0, ORDERING);
+        self.update(0, Vec::new())
+    }
+
+    /// Invokes the callbacks for the revoked partitions first and then for the newly assigned ones, returns the revoked partitions.
+    fn update(&self, generation: u32, partitions: Vec<u32>) -> Vec<u32> {
+        let Ok(mut current_partitions) = self.partitions.lock() else {
+            return Vec::new();
+        };
+
+        let revoked_partitions = current_partitions
+            .iter()
+            .filter(|partition_id| !partitions.contains(partition_id))
+            .copied()
+            .collect::<Vec<_>>();
+        let assigned_partitions = partitions
+            .iter()
+            .filter(|partition_id| !current_partitions.contains(partition_id))
+            .copied()
+            .collect::<Vec<_>>();
+
+        if !revoked_partitions.is_empty() {
+            info!(
+                "Partitions: {revoked_partitions:?} have been revoked, generation: {generation}."
+            );
+            if let Some(callback) = &self.on_partitions_revoked {
+                (callback.0)(&revoked_partitions);
+            }
+        }
+
+        *current_partitions = partitions;
+        self.generation.store(generation, ORDERING);
+
+        if !assigned_partitions.is_empty() {
+            info!(
+                "Partitions: {assigned_partitions:?} have been assigned, generation: {generation}."
+            );
+            if let Some(callback) = &self.on_partitions_assigned {
+                (callback.0)(&assigned_partitions);
+            }
+        }
+
+        revoked_partitions
+    }
+}
+
 unsafe impl Send for IggyConsumer {}
 unsafe impl Sync for IggyConsumer {}
 
@@ -114,6 +216,7 @@ pub struct IggyConsumer {
     init_retries: Option<u32>,
     init_retry_interval: IggyDuration,
     allow_replay: bool,
+    assignment: Arc<ConsumerGroupAssignment>,
 }
 
 impl IggyConsumer {
@@ -136,6 +239,8 @@ impl IggyConsumer {
         init_retries: Option<u32>,
         init_retry_interval: IggyDuration,
         allow_replay: bool,
+        on_partitions_assigned: Option<PartitionsCallback>,
+        on_partitions_revoked: Option<PartitionsCallback>,
     ) -> Self {
         let (store_offset_sender, _) = flume::unbounded();
         Self {
@@ -190,6 +295,11 @@ impl IggyConsumer {
             init_retries,
             init_retry_interval,
             allow_replay,
+            assignment: Arc::new(ConsumerGroupAssignment {
+                on_partitions_assigned,
+                on_partitions_revoked,
+                ..Default::default()
+            }),
         }
     }
 
@@ -217,6 +327,16 @@ impl IggyConsumer {
         self.current_partition_id.load(ORDERING)
     }
 
+    /// Returns the generation of the consumer group known to the consumer, if it's the consumer group member.
+    pub fn generation(&self) -> Option<u32> {
+        self.assignment.generation()
+    }
+
+    /// Returns the partitions assigned to the consumer, if it's the consumer group member.
+    pub fn assigned_partitions(&self) -> Vec<u32> {
+        self.assignment.partitions()
+    }
+
     /// Stores the consumer offset on the server either for the current partition or the provided partition ID.
     pub async fn store_offset(
         &self,
@@ -235,6 +355,7 @@ impl IggyConsumer {
             &self.topic_id,
             partition_id,
             offset,
+            self.assignment.generation(),
             &self.last_stored_offsets,
             self.allow_replay,
         )
@@ -343,6 +464,7 @@ impl IggyConsumer {
         let stream_id = self.stream_id.clone();
         let topic_id = self.topic_id.clone();
         let last_stored_offsets = self.last_stored_offsets.clone();
+        let assignment = self.assignment.clone();
         let (store_offset_sender, store_offset_receiver) = flume::unbounded();
         self.store_offset_sender = store_offset_sender;
 
@@ -356,6 +478,7 @@ impl IggyConsumer {
                     &topic_id,
                     partition_id,
                     offset,
+                    assignment.generation(),
                     &last_stored_offsets,
                     false,
                 )
@@ -379,6 +502,7 @@ impl IggyConsumer {
         topic_id: &Identifier,
         partition_id: u32,
         offset: u64,
+        generation: Option<u32>,
         last_stored_offsets: &DashMap<u32, AtomicU64>,
         allow_replay: bool,
     ) -> Result<(), IggyError> {
@@ -398,7 +522,14 @@ impl IggyConsumer {
 
         let client = client.read().await;
         if let Err(error) = client
-            .store_consumer_offset(consumer, stream_id, topic_id, Some(partition_id), offset)
+            .store_consumer_offset(
+                consumer,
+                stream_id,
+                topic_id,
+                Some(partition_id),
+                offset,
+                generation,
+            )
             .await
         {
             error!("Failed to store offset: {offset} for consumer: {consumer}, partition ID: {partition_id}, topic: {topic_id}, stream: {stream_id}. {error}");
@@ -420,6 +551,7 @@ impl IggyConsumer {
         let topic_id = self.topic_id.clone();
         let last_consumed_offsets = self.last_consumed_offsets.clone();
         let last_stored_offsets = self.last_stored_offsets.clone();
+        let assignment = self.assignment.clone();
         tokio::spawn(async move {
             loop {
                 sleep(interval.get_duration()).await;
@@ -433,6 +565,7 @@ impl IggyConsumer {
                         &topic_id,
                         partition_id,
                         consumed_offset,
+                        assignment.generation(),
                         &last_stored_offsets,
                         false,
                     )
@@ -466,6 +599,7 @@ impl IggyConsumer {
             self.consumer.clone(),
             &self.consumer_name,
             self.joined_consumer_group.clone(),
+            &self.assignment,
         )
         .await
     }
@@ -488,6 +622,9 @@ impl IggyConsumer {
         let consumer_name = self.consumer_name.clone();
         let can_poll = self.can_poll.clone();
         let joined_consumer_group = self.joined_consumer_group.clone();
+        let assignment = self.assignment.clone();
+        let last_consumed_offsets = self.last_consumed_offsets.clone();
+        let last_stored_offsets = self.last_stored_offsets.clone();
         let mut reconnected = false;
         let mut disconnected = false;
 
@@ -515,6 +652,11 @@ impl IggyConsumer {
                         reconnected = false;
                         joined_consumer_group.store(false, ORDERING);
                         can_poll.store(false, ORDERING);
+                        // The server removes the member from the consumer group once the client is disconnected.
+                        for partition_id in assignment.leave() {
+                            last_consumed_offsets.remove(&partition_id);
+                            last_stored_offsets.remove(&partition_id);
+                        }
                         warn!("Disconnected from the server");
                     }
                     DiagnosticEvent::SignedIn => {
@@ -548,6 +690,7 @@ impl IggyConsumer {
                             consumer.clone(),
                             &consumer_name,
                             joined_consumer_group.clone(),
+                            &assignment,
                         )
                         .await
                         {
@@ -584,7 +727,9 @@ impl IggyConsumer {
         let retry_interval = self.reconnection_retry_interval;
         let last_stored_offset = self.last_stored_offsets.clone();
         let last_consumed_offset = self.last_consumed_offsets.clone();
+        let current_offsets = self.current_offsets.clone();
         let allow_replay = self.allow_replay;
+        let assignment = self.assignment.clone();
 
         async move {
             if interval > 0 {
@@ -613,6 +758,24 @@ impl IggyConsumer {
                 .await;
 
             if let Ok(mut polled_messages) = polled_messages {
+                if polled_messages.generation.is_some()
+                    && polled_messages.generation != assignment.generation()
+                {
+                    let revoked_partitions = Self::refresh_assignment(
+                        &client,
+                        &stream_id,
+                        &topic_id,
+                        &consumer,
+                        &assignment,
+                    )
+                    .await;
+                    for partition_id in revoked_partitions {
+                        last_consumed_offset.remove(&partition_id);
+                        last_stored_offset.remove(&partition_id);
+                        current_offsets.remove(&partition_id);
+                    }
+                }
+
                 if polled_messages.messages.is_empty() {
                     return Ok(polled_messages);
                 }
@@ -638,6 +801,7 @@ impl IggyConsumer {
                             messages: EMPTY_MESSAGES,
                             current_offset: polled_messages.current_offset,
                             partition_id,
+                            generation: polled_messages.generation,
                         });
                     }
                 }
@@ -679,6 +843,7 @@ impl IggyConsumer {
                                 &topic_id,
                                 Some(partition_id),
                                 consumed_offset,
+                                assignment.generation(),
                             )
                             .await?;
                         if let Some(stored_offset_entry) = last_stored_offset.get(&partition_id) {
@@ -693,6 +858,7 @@ impl IggyConsumer {
                         messages: EMPTY_MESSAGES,
                         current_offset: polled_messages.current_offset,
                         partition_id,
+                        generation: polled_messages.generation,
                     });
                 }
 
@@ -729,6 +895,7 @@ impl IggyConsumer {
         sleep(Duration::from_micros(remaining)).await;
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn initialize_consumer_group(
         client: IggySharedMut<Box<dyn Client>>,
         create_consumer_group_if_not_exists: bool,
@@ -737,6 +904,7 @@ impl IggyConsumer {
         consumer: Arc<Consumer>,
         consumer_name: &str,
         joined_consumer_group: Arc<AtomicBool>,
+        assignment: &ConsumerGroupAssignment,
     ) -> Result<(), IggyError> {
         if joined_consumer_group.load(ORDERING) {
             return Ok(());
@@ -765,24 +933,99 @@ impl IggyConsumer {
 
             info!("Creating consumer group: {consumer_group_id} for topic: {topic_id}, stream: {stream_id}");
             client
-                .create_consumer_group(&stream_id, &topic_id, &name, id)
+                .create_consumer_group(
+                    &stream_id,
+                    &topic_id,
+                    &name,
+                    id,
+                    PartitionAssignmentStrategy::default(),
+                )
                 .await?;
         }
 
         info!("Joining consumer group: {consumer_group_id} for topic: {topic_id}, stream: {stream_id}",);
-        if let Err(error) = client
+        let membership = match client
             .join_consumer_group(&stream_id, &topic_id, &consumer_group_id)
             .await
         {
-            joined_consumer_group.store(false, ORDERING);
-            error!("Failed to join consumer group: {consumer_group_id} for topic: {topic_id}, stream: {stream_id}: {error}");
-            return Err(error);
-        }
+            Ok(membership) => membership,
+            Err(error) => {
+                joined_consumer_group.store(false, ORDERING);
+                error!("Failed to join consumer group: {consumer_group_id} for topic: {topic_id}, stream: {stream_id}: {error}");
+                return Err(error);
+            }
+        };
 
         joined_consumer_group.store(true, ORDERING);
         info!(
-            "Joined consumer group: {consumer_group_id} for topic: {topic_id}, stream: {stream_id}"
+            "Joined consumer group: {consumer_group_id} for topic: {topic_id}, stream: {stream_id}, member ID: {}, generation: {}",
+            membership.member_id, membership.generation
         );
+        assignment.join(membership);
+        Ok(())
+    }
+
+    /// Fetches the partitions currently assigned to the consumer group member, once the consumer group has been rebalanced.
+    /// Returns the revoked partitions.
+    async fn refresh_assignment(
+        client: &IggySharedMut<Box<dyn Client>>,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        consumer: &Consumer,
+        assignment: &ConsumerGroupAssignment,
+    ) -> Vec<u32> {
+        let member_id = assignment.member_id.load(ORDERING);
+        if member_id == 0 {
+            return Vec::new();
+        }
+
+        let consumer_group = match client
+            .read()
+            .await
+            .get_consumer_group(stream_id, topic_id, &consumer.id)
+            .await
+        {
+            Ok(Some(consumer_group)) => consumer_group,
+            Ok(None) => {
+                warn!(
+                    "Consumer group: {} for topic: {topic_id}, stream: {stream_id} was not found.",
+                    consumer.id
+                );
+                return Vec::new();
+            }
+            Err(error) => {
+                error!("Failed to refresh the partitions assignment for consumer group: {} for topic: {topic_id}, stream: {stream_id}. {error}", consumer.id);
+                return Vec::new();
+            }
+        };
+
+        let partitions = consumer_group
+            .members
+            .into_iter()
+            .find(|member| member.id == member_id)
+            .map(|member| member.partitions)
+            .unwrap_or_default();
+        assignment.update(consumer_group.generation, partitions)
+    }
+    /// Decompresses the message payload if it was compressed by the producer on the client side.
+    /// The decompressed payload cannot exceed the maximum payload size of the message.
+    fn decompress_message(message: &mut PolledMessage) -> Result<(), IggyError> {
+        let Some(headers) = message.headers.as_mut() else {
+            return Ok(());
+        };
+
+        let Some(algorithm) = headers.remove(&HeaderKey::new(COMPRESSION_HEADER_KEY)?) else {
+            return Ok(());
+        };
+
+        let algorithm = CompressionAlgorithm::from_str(algorithm.as_str()?)
+            .map_err(|_| IggyError::CannotDecompressData)?;
+        if headers.is_empty() {
+            message.headers = None;
+        }
+        message.payload =
+            Bytes::from(algorithm.decompress(&message.payload, MAX_PAYLOAD_SIZE as usize)?);
+        message.length = IggyByteSize::from(message.payload.len() as u64);
         Ok(())
     }
 }
@@ -880,6 +1123,14 @@ impl Stream for IggyConsumer {
                             }
                         }
 
+                        for message in &mut polled_messages.messages {
+                            if let Err(error) = Self::decompress_message(message) {
+                                self.poll_future = None;
+                                error!("Failed to decompress the message payload at offset: {}, partition ID: {}", message.offset, partition_id);
+                                return Poll::Ready(Some(Err(error)));
+                            }
+                        }
+
                         if let Some(current_offset_entry) = self.current_offsets.get(&partition_id)
                         {
                             current_offset_entry.store(polled_messages.current_offset, ORDERING);
@@ -954,6 +1205,8 @@ pub struct IggyConsumerBuilder {
     init_retries: Option<u32>,
     init_retry_interval: IggyDuration,
     allow_replay: bool,
+    on_partitions_assigned: Option<PartitionsCallback>,
+    on_partitions_revoked: Option<PartitionsCallback>,
 }
 
 impl IggyConsumerBuilder {
@@ -989,6 +1242,8 @@ impl IggyConsumerBuilder {
             init_retries: None,
             init_retry_interval: IggyDuration::ONE_SECOND,
             allow_replay: false,
+            on_partitions_assigned: None,
+            on_partitions_revoked: None,
         }
     }
 
@@ -1126,6 +1381,24 @@ impl IggyConsumerBuilder {
         }
     }
 
+    /// Sets the callback invoked with the partitions assigned to the consumer group member, after joining or rebalancing the consumer group.
+    pub fn on_partitions_assigned(self, callback: impl Fn(&[u32]) + Send + Sync + 'static) -> Self {
+        Self {
+            on_partitions_assigned: Some(PartitionsCallback::new(callback)),
+            ..self
+        }
+    }
+
+    /// Sets the callback invoked with the partitions revoked from the consumer group member, before consuming the messages from the new assignment.
+    /// Might be used to flush the state kept per partition. Using the cooperative assignment strategy, the revoked partitions
+    /// are handed over to the other members only after the callback has been invoked (or the revocation has timed out).
+    pub fn on_partitions_revoked(self, callback: impl Fn(&[u32]) + Send + Sync + 'static) -> Self {
+        Self {
+            on_partitions_revoked: Some(PartitionsCallback::new(callback)),
+            ..self
+        }
+    }
+
     /// Builds the consumer.
     ///
     /// Note: After building the consumer, `init()` must be invoked before producing messages.
@@ -1148,6 +1421,8 @@ impl IggyConsumerBuilder {
             self.init_retries,
             self.init_retry_interval,
             self.allow_replay,
+            self.on_partitions_assigned,
+            self.on_partitions_revoked,
         )
     }
 }
@@ -1,23 +1,35 @@
 use crate::client::Client;
-use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::compression::compression_algorithm::{CompressionAlgorithm, COMPRESSION_HEADER_KEY};
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
 use crate::identifier::{IdKind, Identifier};
 use crate::locking::{IggySharedMut, IggySharedMutFn};
-use crate::messages::send_messages::{Message, Partitioning};
-use crate::partitioner::Partitioner;
+use crate::messages::send_messages::{
+    Message, Partitioning, PartitioningKind, PRODUCER_EPOCH_HEADER, PRODUCER_ID_HEADER,
+    PRODUCER_SEQUENCE_HEADER, TRANSACTION_ID_HEADER,
+};
+use crate::models::header::{HeaderKey, HeaderValue};
+use crate::partitioner::{calculate_partition_id_by_messages_key, Partitioner};
+use crate::topics::cleanup_policy::CleanupPolicy;
+use crate::topics::replication_factor::ReplicationFactor;
+use crate::transactions::commit_transaction::TransactionOffset;
 use crate::utils::crypto::EncryptorKind;
 use crate::utils::duration::IggyDuration;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::timestamp::IggyTimestamp;
 use crate::utils::topic_size::MaxTopicSize;
 use bytes::Bytes;
+use dashmap::DashMap;
 use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{Mutex, OwnedMutexGuard};
 use tokio::time::{sleep, Interval};
 use tracing::{error, info, trace, warn};
+use uuid::Uuid;
 
 const ORDERING: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqCst;
 const MAX_BATCH_SIZE: usize = 1000000;
@@ -36,6 +48,8 @@ pub struct IggyProducer {
     batch_size: Option<usize>,
     partitioning: Option<Arc<Partitioning>>,
     encryptor: Option<Arc<EncryptorKind>>,
+    compression_algorithm: CompressionAlgorithm,
+    compression_level: Option<u8>,
     partitioner: Option<Arc<dyn Partitioner>>,
     send_interval_micros: u64,
     create_stream_if_not_exists: bool,
@@ -49,6 +63,14 @@ pub struct IggyProducer {
     last_sent_at: Arc<AtomicU64>,
     send_retries_count: Option<u32>,
     send_retries_interval: Option<IggyDuration>,
+    producer_id: Option<u64>,
+    producer_epochs: DashMap<(Identifier, Identifier), u64>,
+    producer_epoch_lock: Mutex<()>,
+    sequences: DashMap<(Identifier, Identifier, u32), u64>,
+    sequence_locks: DashMap<(Identifier, Identifier, u32), Arc<Mutex<()>>>,
+    partitions_counts: DashMap<(Identifier, Identifier), u32>,
+    next_partition_index: AtomicU64,
+    transaction_id: AtomicU64,
 }
 
 impl IggyProducer {
@@ -62,6 +84,8 @@ impl IggyProducer {
         batch_size: Option<usize>,
         partitioning: Option<Partitioning>,
         encryptor: Option<Arc<EncryptorKind>>,
+        compression_algorithm: CompressionAlgorithm,
+        compression_level: Option<u8>,
         partitioner: Option<Arc<dyn Partitioner>>,
         interval: Option<IggyDuration>,
         create_stream_if_not_exists: bool,
@@ -72,6 +96,7 @@ impl IggyProducer {
         topic_max_size: MaxTopicSize,
         send_retries_count: Option<u32>,
         send_retries_interval: Option<IggyDuration>,
+        producer_id: Option<u64>,
     ) -> Self {
         Self {
             initialized: false,
@@ -84,6 +109,8 @@ impl IggyProducer {
             batch_size,
             partitioning: partitioning.map(Arc::new),
             encryptor,
+            compression_algorithm,
+            compression_level,
             partitioner,
             send_interval_micros: interval.map_or(0, |i| i.as_micros()),
             create_stream_if_not_exists,
@@ -97,6 +124,14 @@ impl IggyProducer {
             last_sent_at: Arc::new(AtomicU64::new(0)),
             send_retries_count,
             send_retries_interval,
+            producer_id,
+            producer_epochs: DashMap::new(),
+            producer_epoch_lock: Mutex::new(()),
+            sequences: DashMap::new(),
+            sequence_locks: DashMap::new(),
+            partitions_counts: DashMap::new(),
+            next_partition_index: AtomicU64::new(0),
+            transaction_id: AtomicU64::new(0),
         }
     }
 
@@ -108,6 +143,66 @@ impl IggyProducer {
         &self.topic_id
     }
 
+    /// Returns the ID of the idempotent producer, if the idempotence is enabled.
+    pub fn producer_id(&self) -> Option<u64> {
+        self.producer_id
+    }
+
+    /// Returns the ID of the transaction in progress, if any.
+    pub fn transaction_id(&self) -> Option<u64> {
+        match self.transaction_id.load(ORDERING) {
+            0 => None,
+            transaction_id => Some(transaction_id),
+        }
+    }
+
+    /// Begins a new transaction, all the messages sent until it's committed or aborted belong to it,
+    /// and will be visible to the consumers polling with the `read committed` isolation level only once committed.
+    pub async fn begin_transaction(&self) -> Result<u64, IggyError> {
+        if let Some(transaction_id) = self.transaction_id() {
+            error!("Transaction with ID: {transaction_id} is already in progress.");
+            return Err(IggyError::TransactionAlreadyInProgress);
+        }
+
+        let transaction_id = self.client.read().await.begin_transaction().await?;
+        self.transaction_id.store(transaction_id, ORDERING);
+        info!("Transaction with ID: {transaction_id} has begun.");
+        Ok(transaction_id)
+    }
+
+    /// Commits the transaction in progress, and atomically stores the provided consumer offsets (e.g. of the messages
+    /// consumed from the other topic and transformed into the sent ones).
+    pub async fn commit_transaction(&self, offsets: &[TransactionOffset]) -> Result<(), IggyError> {
+        let Some(transaction_id) = self.transaction_id() else {
+            return Err(IggyError::NoTransactionInProgress);
+        };
+
+        self.client
+            .read()
+            .await
+            .commit_transaction(transaction_id, offsets)
+            .await?;
+        self.transaction_id.store(0, ORDERING);
+        info!("Transaction with ID: {transaction_id} has been committed.");
+        Ok(())
+    }
+
+    /// Aborts the transaction in progress, its messages will never be visible to the `read committed` consumers.
+    pub async fn abort_transaction(&self) -> Result<(), IggyError> {
+        let Some(transaction_id) = self.transaction_id() else {
+            return Err(IggyError::NoTransactionInProgress);
+        };
+
+        self.client
+            .read()
+            .await
+            .abort_transaction(transaction_id)
+            .await?;
+        self.transaction_id.store(0, ORDERING);
+        info!("Transaction with ID: {transaction_id} has been aborted.");
+        Ok(())
+    }
+
     /// Initializes the producer by subscribing to diagnostic events, creating the stream and topic if they do not exist etc.
     ///
     /// Note: This method must be invoked before producing messages.
@@ -116,6 +211,8 @@ impl IggyProducer {
             return Ok(());
         }
 
+        self.compression_algorithm
+            .validate_level(self.compression_level)?;
         let stream_id = self.stream_id.clone();
         let topic_id = self.topic_id.clone();
         info!("Initializing producer for stream: {stream_id} and topic: {topic_id}...");
@@ -162,14 +259,18 @@ impl IggyProducer {
                     &self.topic_name,
                     self.topic_partitions_count,
                     CompressionAlgorithm::None,
-                    self.topic_replication_factor,
+                    None,
+                    self.topic_replication_factor.map(ReplicationFactor),
                     id,
                     self.topic_message_expiry,
                     self.topic_max_size,
+                    CleanupPolicy::default(),
                 )
                 .await?;
         }
 
+        drop(client);
+        self.get_producer_epoch(&stream_id, &topic_id).await?;
         self.initialized = true;
         info!("Producer has been initialized for stream: {stream_id} and topic: {topic_id}.");
         Ok(())
@@ -291,8 +392,13 @@ impl IggyProducer {
         mut messages: Vec<Message>,
         partitioning: Option<Arc<Partitioning>>,
     ) -> Result<(), IggyError> {
+        self.compress_messages(&mut messages)?;
         self.encrypt_messages(&mut messages)?;
         let partitioning = self.get_partitioning(&stream, &topic, &messages, partitioning)?;
+        let (partitioning, _sequence_lock) = self
+            .assign_sequences(&stream, &topic, partitioning, &mut messages)
+            .await?;
+        self.assign_transaction(&mut messages)?;
         let batch_size = self.batch_size.unwrap_or(MAX_BATCH_SIZE);
         let batches = messages.chunks_mut(batch_size);
         let mut current_batch = 1;
@@ -313,7 +419,8 @@ impl IggyProducer {
             self.last_sent_at
                 .store(IggyTimestamp::now().into(), ORDERING);
             self.try_send_messages(&self.stream_id, &self.topic_id, &partitioning, batch)
-                .await?;
+                .await
+                .inspect_err(|_| self.start_new_epoch())?;
             trace!("Sent {messages_count} messages ({current_batch}/{batches_count} batch(es)).");
             current_batch += 1;
         }
@@ -328,14 +435,20 @@ impl IggyProducer {
         partitioning: Option<Arc<Partitioning>>,
     ) -> Result<(), IggyError> {
         trace!("No batch size specified, sending messages immediately.");
+        self.compress_messages(&mut messages)?;
         self.encrypt_messages(&mut messages)?;
         let partitioning = self.get_partitioning(stream, topic, &messages, partitioning)?;
+        let (partitioning, _sequence_lock) = self
+            .assign_sequences(stream, topic, partitioning, &mut messages)
+            .await?;
+        self.assign_transaction(&mut messages)?;
         let batch_size = self.batch_size.unwrap_or(MAX_BATCH_SIZE);
         if messages.len() <= batch_size {
             self.last_sent_at
                 .store(IggyTimestamp::now().into(), ORDERING);
             self.try_send_messages(stream, topic, &partitioning, &mut messages)
-                .await?;
+                .await
+                .inspect_err(|_| self.start_new_epoch())?;
             return Ok(());
         }
 
@@ -343,7 +456,8 @@ impl IggyProducer {
             self.last_sent_at
                 .store(IggyTimestamp::now().into(), ORDERING);
             self.try_send_messages(stream, topic, &partitioning, batch)
-                .await?;
+                .await
+                .inspect_err(|_| self.start_new_epoch())?;
         }
         Ok(())
     }
@@ -365,6 +479,27 @@ impl IggyProducer {
         sleep(Duration::from_micros(remaining)).await;
     }
 
+    fn compress_messages(&self, messages: &mut [Message]) -> Result<(), IggyError> {
+        if self.compression_algorithm == CompressionAlgorithm::None {
+            return Ok(());
+        }
+
+        let header_key = HeaderKey::new(COMPRESSION_HEADER_KEY)?;
+        let header_value = HeaderValue::from_str(&self.compression_algorithm.to_string())?;
+        for message in messages {
+            message.payload = Bytes::from(
+                self.compression_algorithm
+                    .compress(&message.payload, self.compression_level)?,
+            );
+            message.length = message.payload.len() as u32;
+            message
+                .headers
+                .get_or_insert_with(HashMap::new)
+                .insert(header_key.clone(), header_value.clone());
+        }
+        Ok(())
+    }
+
     fn encrypt_messages(&self, messages: &mut [Message]) -> Result<(), IggyError> {
         if let Some(encryptor) = &self.encryptor {
             for message in messages {
@@ -375,6 +510,180 @@ impl IggyProducer {
         Ok(())
     }
 
+    /// Sets the producer ID, its epoch and the next sequence numbers of the target partition in the messages' headers,
+    /// so the server can discard the messages which have been already appended, e.g. when retrying after a timeout.
+    /// The epoch is allocated by the server for each topic, so the restarted producer doesn't reuse the sequences.
+    /// Returns the partitioning resolved to the partition ID and the lock of the partition's sequences, which must be held
+    /// until the messages are sent, as the server rejects the sequences arriving out of order (e.g. from the concurrent sends).
+    async fn assign_sequences(
+        &self,
+        stream: &Identifier,
+        topic: &Identifier,
+        partitioning: Arc<Partitioning>,
+        messages: &mut [Message],
+    ) -> Result<(Arc<Partitioning>, Option<OwnedMutexGuard<()>>), IggyError> {
+        let Some(producer_id) = self.producer_id else {
+            return Ok((partitioning, None));
+        };
+
+        let partition_id = self
+            .resolve_partition_id(stream, topic, &partitioning)
+            .await?;
+        let key = (stream.clone(), topic.clone(), partition_id);
+        let sequence_lock = self
+            .sequence_locks
+            .entry(key.clone())
+            .or_default()
+            .clone()
+            .lock_owned()
+            .await;
+        let epoch = self.get_producer_epoch(stream, topic).await?;
+        let producer_id_key = HeaderKey::new(PRODUCER_ID_HEADER)?;
+        let producer_id_value = HeaderValue::from_uint64(producer_id)?;
+        let epoch_key = HeaderKey::new(PRODUCER_EPOCH_HEADER)?;
+        let epoch_value = HeaderValue::from_uint64(epoch)?;
+        let sequence_key = HeaderKey::new(PRODUCER_SEQUENCE_HEADER)?;
+        let mut sequence = self.sequences.entry(key).or_insert(0);
+        for message in messages {
+            *sequence += 1;
+            let headers = message.headers.get_or_insert_with(HashMap::new);
+            headers.insert(producer_id_key.clone(), producer_id_value.clone());
+            headers.insert(epoch_key.clone(), epoch_value.clone());
+            headers.insert(sequence_key.clone(), HeaderValue::from_uint64(*sequence)?);
+        }
+        Ok((
+            Arc::new(Partitioning::partition_id(partition_id)),
+            Some(sequence_lock),
+        ))
+    }
+
+    /// Resolves the partition the messages of the idempotent producer are sent to, as the sequences are assigned per partition.
+    /// The balanced partitioning is resolved in the round-robin manner, and the messages key is hashed the same way as by the server.
+    async fn resolve_partition_id(
+        &self,
+        stream: &Identifier,
+        topic: &Identifier,
+        partitioning: &Partitioning,
+    ) -> Result<u32, IggyError> {
+        match partitioning.kind {
+            PartitioningKind::PartitionId => Ok(u32::from_le_bytes(
+                partitioning.value[..4]
+                    .try_into()
+                    .map_err(|_| IggyError::InvalidNumberEncoding)?,
+            )),
+            PartitioningKind::Balanced => {
+                let partitions_count = self.get_partitions_count(stream, topic).await?;
+                let index = self.next_partition_index.fetch_add(1, ORDERING);
+                Ok((index % partitions_count as u64) as u32 + 1)
+            }
+            PartitioningKind::MessagesKey => {
+                let partitions_count = self.get_partitions_count(stream, topic).await?;
+                Ok(calculate_partition_id_by_messages_key(
+                    &partitioning.value[..partitioning.length as usize],
+                    partitions_count,
+                ))
+            }
+        }
+    }
+
+    /// Returns the number of the topic partitions, fetching it from the server if it's not known yet.
+    async fn get_partitions_count(
+        &self,
+        stream: &Identifier,
+        topic: &Identifier,
+    ) -> Result<u32, IggyError> {
+        let key = (stream.clone(), topic.clone());
+        if let Some(partitions_count) = self.partitions_counts.get(&key) {
+            return Ok(*partitions_count);
+        }
+
+        let Some(topic_details) = self.client.read().await.get_topic(stream, topic).await? else {
+            error!("Topic: {topic} for stream: {stream} was not found.");
+            return Err(IggyError::TopicNameNotFound(
+                topic.to_string(),
+                stream.to_string(),
+            ));
+        };
+        if topic_details.partitions_count == 0 {
+            error!("Topic: {topic} for stream: {stream} has no partitions.");
+            return Err(IggyError::NoPartitions(
+                topic_details.id,
+                stream.get_u32_value().unwrap_or_default(),
+            ));
+        }
+
+        self.partitions_counts
+            .insert(key, topic_details.partitions_count);
+        Ok(topic_details.partitions_count)
+    }
+
+    /// Returns the epoch of the idempotent producer for the topic, requesting the new one from the server if there's none yet.
+    async fn get_producer_epoch(
+        &self,
+        stream: &Identifier,
+        topic: &Identifier,
+    ) -> Result<u64, IggyError> {
+        let Some(producer_id) = self.producer_id else {
+            return Ok(0);
+        };
+
+        let key = (stream.clone(), topic.clone());
+        if let Some(epoch) = self.producer_epochs.get(&key) {
+            return Ok(*epoch);
+        }
+
+        let _lock = self.producer_epoch_lock.lock().await;
+        if let Some(epoch) = self.producer_epochs.get(&key) {
+            return Ok(*epoch);
+        }
+
+        let epoch = self
+            .client
+            .read()
+            .await
+            .init_producer(stream, topic, producer_id)
+            .await?;
+        self.producer_epochs.insert(key, epoch);
+        info!("Idempotent producer with ID: {producer_id} has started the epoch: {epoch} for stream: {stream} and topic: {topic}.");
+        Ok(epoch)
+    }
+
+    /// Discards the epochs of the idempotent producer and starts the sequences over, once the messages couldn't be sent
+    /// (even after the retries). It's unknown whether they were appended, so the next sequences can neither reuse
+    /// the failed ones (the new messages would be discarded), nor skip them (leaving the gap).
+    /// The new epochs are requested from the server before sending the next messages, and the number of the partitions
+    /// is fetched again, in case they have changed.
+    fn start_new_epoch(&self) {
+        if self.producer_id.is_none() {
+            return;
+        }
+
+        self.producer_epochs.clear();
+        self.sequences.clear();
+        self.partitions_counts.clear();
+        warn!(
+            "Idempotent producer with ID: {:?} will start the new epoch after the failed send.",
+            self.producer_id
+        );
+    }
+
+    /// Sets the ID of the transaction in progress in the messages' headers.
+    fn assign_transaction(&self, messages: &mut [Message]) -> Result<(), IggyError> {
+        let Some(transaction_id) = self.transaction_id() else {
+            return Ok(());
+        };
+
+        let transaction_id_key = HeaderKey::new(TRANSACTION_ID_HEADER)?;
+        let transaction_id_value = HeaderValue::from_uint64(transaction_id)?;
+        for message in messages {
+            message
+                .headers
+                .get_or_insert_with(HashMap::new)
+                .insert(transaction_id_key.clone(), transaction_id_value.clone());
+        }
+        Ok(())
+    }
+
     async fn try_send_messages(
         &self,
         stream: &Identifier,
@@ -526,6 +835,8 @@ pub struct IggyProducerBuilder {
     batch_size: Option<usize>,
     partitioning: Option<Partitioning>,
     encryptor: Option<Arc<EncryptorKind>>,
+    compression_algorithm: CompressionAlgorithm,
+    compression_level: Option<u8>,
     partitioner: Option<Arc<dyn Partitioner>>,
     send_interval: Option<IggyDuration>,
     create_stream_if_not_exists: bool,
@@ -536,6 +847,7 @@ pub struct IggyProducerBuilder {
     send_retries_interval: Option<IggyDuration>,
     topic_message_expiry: IggyExpiry,
     topic_max_size: MaxTopicSize,
+    producer_id: Option<u64>,
 }
 
 impl IggyProducerBuilder {
@@ -558,6 +870,8 @@ impl IggyProducerBuilder {
             batch_size: Some(1000),
             partitioning: None,
             encryptor,
+            compression_algorithm: CompressionAlgorithm::None,
+            compression_level: None,
             partitioner,
             send_interval: Some(IggyDuration::from(1000)),
             create_stream_if_not_exists: true,
@@ -568,6 +882,7 @@ impl IggyProducerBuilder {
             topic_max_size: MaxTopicSize::ServerDefault,
             send_retries_count: Some(3),
             send_retries_interval: Some(IggyDuration::ONE_SECOND),
+            producer_id: None,
         }
     }
 
@@ -633,6 +948,25 @@ impl IggyProducerBuilder {
         }
     }
 
+    /// Sets the compression algorithm and the optional level (the algorithm's default if `None`) for compressing
+    /// the messages' payloads on the client side, the consumer decompresses them transparently.
+    pub fn compression(self, algorithm: CompressionAlgorithm, level: Option<u8>) -> Self {
+        Self {
+            compression_algorithm: algorithm,
+            compression_level: level,
+            ..self
+        }
+    }
+
+    /// Clears the client side compression of the messages' payloads.
+    pub fn without_compression(self) -> Self {
+        Self {
+            compression_algorithm: CompressionAlgorithm::None,
+            compression_level: None,
+            ..self
+        }
+    }
+
     /// Sets the partitioning strategy for messages.
     pub fn partitioning(self, partitioning: Partitioning) -> Self {
         Self {
@@ -718,6 +1052,25 @@ impl IggyProducerBuilder {
         }
     }
 
+    /// Enables the idempotent producer with the given ID (randomly generated if `None`), which must be unique
+    /// for each producer instance. The messages get the per-partition sequence numbers and the duplicates
+    /// (e.g. sent again after a timeout) are discarded by the server. The balanced and messages key partitionings
+    /// are resolved to the partition ID by the producer, and the messages sent to the same partition are sent one batch at a time.
+    pub fn idempotence(self, producer_id: Option<u64>) -> Self {
+        Self {
+            producer_id: Some(producer_id.unwrap_or_else(|| Uuid::now_v7().as_u64_pair().1)),
+            ..self
+        }
+    }
+
+    /// Disables the idempotent producer.
+    pub fn without_idempotence(self) -> Self {
+        Self {
+            producer_id: None,
+            ..self
+        }
+    }
+
     /// Builds the producer.
     ///
     /// Note: After building the producer, `init()` must be invoked before producing messages.
@@ -731,6 +1084,8 @@ impl IggyProducerBuilder {
             self.batch_size,
             self.partitioning,
             self.encryptor,
+            self.compression_algorithm,
+            self.compression_level,
             self.partitioner,
             self.send_interval,
             self.create_stream_if_not_exists,
@@ -741,6 +1096,7 @@ impl IggyProducerBuilder {
             self.topic_max_size,
             self.send_retries_count,
             self.send_retries_interval,
+            self.producer_id,
         )
     }
 }
@@ -9,6 +9,8 @@ pub trait Command: BytesSerializable + Validatable<IggyError> + Send + Sync + Di
 
 pub const PING: &str = "ping";
 pub const PING_CODE: u32 = 1;
+pub const SET_PROTOCOL_VERSION: &str = "protocol.version";
+pub const SET_PROTOCOL_VERSION_CODE: u32 = 2;
 pub const GET_STATS: &str = "stats";
 pub const GET_STATS_CODE: u32 = 10;
 pub const GET_SNAPSHOT_FILE: &str = "snapshot";
@@ -37,6 +39,24 @@ pub const LOGIN_USER: &str = "user.login";
 pub const LOGIN_USER_CODE: u32 = 38;
 pub const LOGOUT_USER: &str = "user.logout";
 pub const LOGOUT_USER_CODE: u32 = 39;
+pub const SET_USER_QUOTA: &str = "user.quota";
+pub const SET_USER_QUOTA_CODE: u32 = 45;
+pub const LOGIN_WITH_TOKEN: &str = "user.login_with_token";
+pub const LOGIN_WITH_TOKEN_CODE: u32 = 46;
+pub const GET_ROLE: &str = "role.get";
+pub const GET_ROLE_CODE: u32 = 50;
+pub const GET_ROLES: &str = "role.list";
+pub const GET_ROLES_CODE: u32 = 51;
+pub const CREATE_ROLE: &str = "role.create";
+pub const CREATE_ROLE_CODE: u32 = 52;
+pub const DELETE_ROLE: &str = "role.delete";
+pub const DELETE_ROLE_CODE: u32 = 53;
+pub const UPDATE_ROLE: &str = "role.update";
+pub const UPDATE_ROLE_CODE: u32 = 54;
+pub const ASSIGN_ROLE: &str = "role.assign";
+pub const ASSIGN_ROLE_CODE: u32 = 55;
+pub const UNASSIGN_ROLE: &str = "role.unassign";
+pub const UNASSIGN_ROLE_CODE: u32 = 56;
 pub const GET_PERSONAL_ACCESS_TOKENS: &str = "personal_access_token.list";
 pub const GET_PERSONAL_ACCESS_TOKENS_CODE: u32 = 41;
 pub const CREATE_PERSONAL_ACCESS_TOKEN: &str = "personal_access_token.create";
@@ -51,6 +71,10 @@ pub const SEND_MESSAGES: &str = "message.send";
 pub const SEND_MESSAGES_CODE: u32 = 101;
 pub const FLUSH_UNSAVED_BUFFER: &str = "message.flush_unsaved_buffer";
 pub const FLUSH_UNSAVED_BUFFER_CODE: u32 = 102;
+pub const NACK_MESSAGE: &str = "message.nack";
+pub const NACK_MESSAGE_CODE: u32 = 103;
+pub const INIT_PRODUCER: &str = "message.init_producer";
+pub const INIT_PRODUCER_CODE: u32 = 104;
 pub const GET_CONSUMER_OFFSET: &str = "consumer_offset.get";
 pub const GET_CONSUMER_OFFSET_CODE: u32 = 120;
 pub const STORE_CONSUMER_OFFSET: &str = "consumer_offset.store";
@@ -69,6 +93,8 @@ pub const UPDATE_STREAM: &str = "stream.update";
 pub const UPDATE_STREAM_CODE: u32 = 204;
 pub const PURGE_STREAM: &str = "stream.purge";
 pub const PURGE_STREAM_CODE: u32 = 205;
+pub const SET_STREAM_QUOTA: &str = "stream.quota";
+pub const SET_STREAM_QUOTA_CODE: u32 = 206;
 pub const GET_TOPIC: &str = "topic.get";
 pub const GET_TOPIC_CODE: u32 = 300;
 pub const GET_TOPICS: &str = "topic.list";
@@ -81,6 +107,8 @@ pub const UPDATE_TOPIC: &str = "topic.update";
 pub const UPDATE_TOPIC_CODE: u32 = 304;
 pub const PURGE_TOPIC: &str = "topic.purge";
 pub const PURGE_TOPIC_CODE: u32 = 305;
+pub const SET_TOPIC_RETENTION: &str = "topic.retention";
+pub const SET_TOPIC_RETENTION_CODE: u32 = 306;
 pub const CREATE_PARTITIONS: &str = "partition.create";
 pub const CREATE_PARTITIONS_CODE: u32 = 402;
 pub const DELETE_PARTITIONS: &str = "partition.delete";
@@ -97,10 +125,19 @@ pub const JOIN_CONSUMER_GROUP: &str = "consumer_group.join";
 pub const JOIN_CONSUMER_GROUP_CODE: u32 = 604;
 pub const LEAVE_CONSUMER_GROUP: &str = "consumer_group.leave";
 pub const LEAVE_CONSUMER_GROUP_CODE: u32 = 605;
+pub const BEGIN_TRANSACTION: &str = "transaction.begin";
+pub const BEGIN_TRANSACTION_CODE: u32 = 700;
+pub const COMMIT_TRANSACTION: &str = "transaction.commit";
+pub const COMMIT_TRANSACTION_CODE: u32 = 701;
+pub const ABORT_TRANSACTION: &str = "transaction.abort";
+pub const ABORT_TRANSACTION_CODE: u32 = 702;
+pub const RESTORE_ARCHIVE: &str = "archive.restore";
+pub const RESTORE_ARCHIVE_CODE: u32 = 800;
 
 pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
     match code {
         PING_CODE => Ok(PING),
+        SET_PROTOCOL_VERSION_CODE => Ok(SET_PROTOCOL_VERSION),
         GET_STATS_CODE => Ok(GET_STATS),
         GET_ME_CODE => Ok(GET_ME),
         GET_CLIENT_CODE => Ok(GET_CLIENT),
@@ -114,6 +151,15 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         CHANGE_PASSWORD_CODE => Ok(CHANGE_PASSWORD),
         LOGIN_USER_CODE => Ok(LOGIN_USER),
         LOGOUT_USER_CODE => Ok(LOGOUT_USER),
+        SET_USER_QUOTA_CODE => Ok(SET_USER_QUOTA),
+        LOGIN_WITH_TOKEN_CODE => Ok(LOGIN_WITH_TOKEN),
+        GET_ROLE_CODE => Ok(GET_ROLE),
+        GET_ROLES_CODE => Ok(GET_ROLES),
+        CREATE_ROLE_CODE => Ok(CREATE_ROLE),
+        DELETE_ROLE_CODE => Ok(DELETE_ROLE),
+        UPDATE_ROLE_CODE => Ok(UPDATE_ROLE),
+        ASSIGN_ROLE_CODE => Ok(ASSIGN_ROLE),
+        UNASSIGN_ROLE_CODE => Ok(UNASSIGN_ROLE),
         GET_PERSONAL_ACCESS_TOKENS_CODE => Ok(GET_PERSONAL_ACCESS_TOKENS),
         CREATE_PERSONAL_ACCESS_TOKEN_CODE => Ok(CREATE_PERSONAL_ACCESS_TOKEN),
         DELETE_PERSONAL_ACCESS_TOKEN_CODE => Ok(DELETE_PERSONAL_ACCESS_TOKEN),
@@ -121,6 +167,8 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         SEND_MESSAGES_CODE => Ok(SEND_MESSAGES),
         POLL_MESSAGES_CODE => Ok(POLL_MESSAGES),
         FLUSH_UNSAVED_BUFFER_CODE => Ok(FLUSH_UNSAVED_BUFFER),
+        NACK_MESSAGE_CODE => Ok(NACK_MESSAGE),
+        INIT_PRODUCER_CODE => Ok(INIT_PRODUCER),
         STORE_CONSUMER_OFFSET_CODE => Ok(STORE_CONSUMER_OFFSET),
         GET_CONSUMER_OFFSET_CODE => Ok(GET_CONSUMER_OFFSET),
         GET_STREAM_CODE => Ok(GET_STREAM),
@@ -129,12 +177,14 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         DELETE_STREAM_CODE => Ok(DELETE_STREAM),
         UPDATE_STREAM_CODE => Ok(UPDATE_STREAM),
         PURGE_STREAM_CODE => Ok(PURGE_STREAM),
+        SET_STREAM_QUOTA_CODE => Ok(SET_STREAM_QUOTA),
         GET_TOPIC_CODE => Ok(GET_TOPIC),
         GET_TOPICS_CODE => Ok(GET_TOPICS),
         CREATE_TOPIC_CODE => Ok(CREATE_TOPIC),
         DELETE_TOPIC_CODE => Ok(DELETE_TOPIC),
         UPDATE_TOPIC_CODE => Ok(UPDATE_TOPIC),
         PURGE_TOPIC_CODE => Ok(PURGE_TOPIC),
+        SET_TOPIC_RETENTION_CODE => Ok(SET_TOPIC_RETENTION),
         CREATE_PARTITIONS_CODE => Ok(CREATE_PARTITIONS),
         DELETE_PARTITIONS_CODE => Ok(DELETE_PARTITIONS),
         GET_CONSUMER_GROUP_CODE => Ok(GET_CONSUMER_GROUP),
@@ -143,6 +193,10 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         DELETE_CONSUMER_GROUP_CODE => Ok(DELETE_CONSUMER_GROUP),
         JOIN_CONSUMER_GROUP_CODE => Ok(JOIN_CONSUMER_GROUP),
         LEAVE_CONSUMER_GROUP_CODE => Ok(LEAVE_CONSUMER_GROUP),
+        BEGIN_TRANSACTION_CODE => Ok(BEGIN_TRANSACTION),
+        COMMIT_TRANSACTION_CODE => Ok(COMMIT_TRANSACTION),
+        ABORT_TRANSACTION_CODE => Ok(ABORT_TRANSACTION),
+        RESTORE_ARCHIVE_CODE => Ok(RESTORE_ARCHIVE),
         GET_SNAPSHOT_FILE_CODE => Ok(GET_SNAPSHOT_FILE),
         _ => Err(IggyError::InvalidCommand),
     }
@@ -1,16 +1,27 @@
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use lz4_flex::frame::{FrameDecoder, FrameEncoder};
 use serde::{
     de::{self, Deserializer, Visitor},
     Deserialize, Serialize, Serializer,
 };
 use std::{
     fmt::{Display, Formatter},
+    io::{Read, Write},
     str::FromStr,
 };
 
 use crate::error::IggyError;
 
-// for now only those, in the future will add snappy, lz4, zstd (same as in confluent kafka) in addition to that
-// we should consider brotli as well.
+/// The message header set by the producer when the payload was compressed on the client side,
+/// its value is the name of the used compression algorithm.
+pub const COMPRESSION_HEADER_KEY: &str = "iggy-compression";
+
+const MIN_GZIP_LEVEL: u8 = 1;
+const MAX_GZIP_LEVEL: u8 = 9;
+const MIN_ZSTD_LEVEL: u8 = 1;
+const MAX_ZSTD_LEVEL: u8 = 22;
+
+// Same set as in confluent kafka, we should consider brotli as well.
 /// Supported compression algorithms
 #[derive(Debug, Default, PartialEq, Clone, Copy)]
 pub enum CompressionAlgorithm {
@@ -19,6 +30,12 @@ pub enum CompressionAlgorithm {
     None,
     // Gzip compression algorithm
     Gzip,
+    // LZ4 compression algorithm (frame format)
+    Lz4,
+    // Zstandard compression algorithm
+    Zstd,
+    // Snappy compression algorithm (frame format)
+    Snappy,
 }
 
 impl FromStr for CompressionAlgorithm {
@@ -27,6 +44,9 @@ impl FromStr for CompressionAlgorithm {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "gzip" => Ok(CompressionAlgorithm::Gzip),
+            "lz4" => Ok(CompressionAlgorithm::Lz4),
+            "zstd" => Ok(CompressionAlgorithm::Zstd),
+            "snappy" => Ok(CompressionAlgorithm::Snappy),
             "none" => Ok(CompressionAlgorithm::None),
             _ => Err(format!("Unknown compression type: {}", s)),
         }
@@ -38,6 +58,9 @@ impl CompressionAlgorithm {
         match self {
             CompressionAlgorithm::None => 1,
             CompressionAlgorithm::Gzip => 2,
+            CompressionAlgorithm::Lz4 => 3,
+            CompressionAlgorithm::Zstd => 4,
+            CompressionAlgorithm::Snappy => 5,
         }
     }
 
@@ -45,9 +68,113 @@ impl CompressionAlgorithm {
         match code {
             1 => Ok(CompressionAlgorithm::None),
             2 => Ok(CompressionAlgorithm::Gzip),
+            3 => Ok(CompressionAlgorithm::Lz4),
+            4 => Ok(CompressionAlgorithm::Zstd),
+            5 => Ok(CompressionAlgorithm::Snappy),
             _ => Err(IggyError::InvalidCommand),
         }
     }
+
+    /// Validates the compression level for the algorithm, `None` means the algorithm's default level.
+    /// Gzip supports levels 1-9, Zstd supports levels 1-22, the other algorithms do not support levels.
+    pub fn validate_level(&self, level: Option<u8>) -> Result<(), IggyError> {
+        let Some(level) = level else {
+            return Ok(());
+        };
+
+        let is_valid = match self {
+            CompressionAlgorithm::Gzip => (MIN_GZIP_LEVEL..=MAX_GZIP_LEVEL).contains(&level),
+            CompressionAlgorithm::Zstd => (MIN_ZSTD_LEVEL..=MAX_ZSTD_LEVEL).contains(&level),
+            CompressionAlgorithm::None
+            | CompressionAlgorithm::Lz4
+            | CompressionAlgorithm::Snappy => false,
+        };
+
+        if !is_valid {
+            return Err(IggyError::InvalidCompressionLevel(level, self.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Compresses the provided data using the optional level (the algorithm's default if `None`),
+    /// for `None` algorithm the data is returned as is.
+    pub fn compress(&self, data: &[u8], level: Option<u8>) -> Result<Vec<u8>, IggyError> {
+        self.validate_level(level)?;
+        match self {
+            CompressionAlgorithm::None => Ok(data.to_vec()),
+            CompressionAlgorithm::Gzip => {
+                let compression = level.map_or(Compression::default(), |level| {
+                    Compression::new(level as u32)
+                });
+                let mut encoder = GzEncoder::new(Vec::with_capacity(data.len()), compression);
+                encoder
+                    .write_all(data)
+                    .map_err(|_| IggyError::CannotCompressData)?;
+                encoder.finish().map_err(|_| IggyError::CannotCompressData)
+            }
+            CompressionAlgorithm::Lz4 => {
+                let mut encoder = FrameEncoder::new(Vec::with_capacity(data.len()));
+                encoder
+                    .write_all(data)
+                    .map_err(|_| IggyError::CannotCompressData)?;
+                encoder.finish().map_err(|_| IggyError::CannotCompressData)
+            }
+            CompressionAlgorithm::Zstd => {
+                let level = level.map_or(zstd::DEFAULT_COMPRESSION_LEVEL, |level| level as i32);
+                zstd::bulk::compress(data, level).map_err(|_| IggyError::CannotCompressData)
+            }
+            CompressionAlgorithm::Snappy => {
+                let mut encoder = snap::write::FrameEncoder::new(Vec::with_capacity(data.len()));
+                encoder
+                    .write_all(data)
+                    .map_err(|_| IggyError::CannotCompressData)?;
+                encoder
+                    .into_inner()
+                    .map_err(|_| IggyError::CannotCompressData)
+            }
+        }
+    }
+
+    /// Decompresses the provided data, failing once the decompressed data exceeds `max_size` bytes,
+    /// so the data crafted to decompress to the huge size (e.g. the decompression bomb) is rejected early.
+    pub fn decompress(&self, data: &[u8], max_size: usize) -> Result<Vec<u8>, IggyError> {
+        match self {
+            CompressionAlgorithm::None => {
+                if data.len() > max_size {
+                    return Err(IggyError::DecompressedDataTooLarge(max_size));
+                }
+                Ok(data.to_vec())
+            }
+            CompressionAlgorithm::Gzip => read_decompressed(GzDecoder::new(data), data, max_size),
+            CompressionAlgorithm::Lz4 => read_decompressed(FrameDecoder::new(data), data, max_size),
+            CompressionAlgorithm::Zstd => {
+                let decoder = zstd::stream::Decoder::new(data)
+                    .map_err(|_| IggyError::CannotDecompressData)?;
+                read_decompressed(decoder, data, max_size)
+            }
+            CompressionAlgorithm::Snappy => {
+                read_decompressed(snap::read::FrameDecoder::new(data), data, max_size)
+            }
+        }
+    }
+}
+
+/// Reads at most one byte more than `max_size` from the decoder, to detect the data exceeding the limit.
+fn read_decompressed(
+    decoder: impl Read,
+    data: &[u8],
+    max_size: usize,
+) -> Result<Vec<u8>, IggyError> {
+    let mut decompressed = Vec::with_capacity(data.len().min(max_size));
+    decoder
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|_| IggyError::CannotDecompressData)?;
+    if decompressed.len() > max_size {
+        return Err(IggyError::DecompressedDataTooLarge(max_size));
+    }
+    Ok(decompressed)
 }
 
 impl Display for CompressionAlgorithm {
@@ -55,6 +182,9 @@ impl Display for CompressionAlgorithm {
         match self {
             CompressionAlgorithm::None => write!(f, "none"),
             CompressionAlgorithm::Gzip => write!(f, "gzip"),
+            CompressionAlgorithm::Lz4 => write!(f, "lz4"),
+            CompressionAlgorithm::Zstd => write!(f, "zstd"),
+            CompressionAlgorithm::Snappy => write!(f, "snappy"),
         }
     }
 }
@@ -67,6 +197,9 @@ impl Serialize for CompressionAlgorithm {
         match self {
             CompressionAlgorithm::None => serializer.serialize_str("none"),
             CompressionAlgorithm::Gzip => serializer.serialize_str("gzip"),
+            CompressionAlgorithm::Lz4 => serializer.serialize_str("lz4"),
+            CompressionAlgorithm::Zstd => serializer.serialize_str("zstd"),
+            CompressionAlgorithm::Snappy => serializer.serialize_str("snappy"),
         }
     }
 }
@@ -76,6 +209,9 @@ impl From<CompressionAlgorithm> for String {
         match value {
             CompressionAlgorithm::None => "none".to_string(),
             CompressionAlgorithm::Gzip => "gzip".to_string(),
+            CompressionAlgorithm::Lz4 => "lz4".to_string(),
+            CompressionAlgorithm::Zstd => "zstd".to_string(),
+            CompressionAlgorithm::Snappy => "snappy".to_string(),
         }
     }
 }
@@ -126,6 +262,18 @@ mod tests {
         let gzip_alg = CompressionAlgorithm::from_str("Gzip");
         assert!(gzip_alg.is_ok());
         assert_eq!(gzip_alg.unwrap(), CompressionAlgorithm::Gzip);
+
+        let lz4_alg = CompressionAlgorithm::from_str("LZ4");
+        assert!(lz4_alg.is_ok());
+        assert_eq!(lz4_alg.unwrap(), CompressionAlgorithm::Lz4);
+
+        let zstd_alg = CompressionAlgorithm::from_str("zstd");
+        assert!(zstd_alg.is_ok());
+        assert_eq!(zstd_alg.unwrap(), CompressionAlgorithm::Zstd);
+
+        let snappy_alg = CompressionAlgorithm::from_str("Snappy");
+        assert!(snappy_alg.is_ok());
+        assert_eq!(snappy_alg.unwrap(), CompressionAlgorithm::Snappy);
     }
 
     #[test]
@@ -148,6 +296,15 @@ mod tests {
         let gzip_string: String = gzip.into();
 
         assert_eq!(gzip_string, "gzip".to_string());
+
+        let lz4_string: String = CompressionAlgorithm::Lz4.into();
+        assert_eq!(lz4_string, "lz4".to_string());
+
+        let zstd_string: String = CompressionAlgorithm::Zstd.into();
+        assert_eq!(zstd_string, "zstd".to_string());
+
+        let snappy_string: String = CompressionAlgorithm::Snappy.into();
+        assert_eq!(snappy_string, "snappy".to_string());
     }
     #[test]
     fn test_as_code() {
@@ -158,6 +315,10 @@ mod tests {
         let gzip = CompressionAlgorithm::Gzip;
         let gzip_code = gzip.as_code();
         assert_eq!(gzip_code, 2);
+
+        assert_eq!(CompressionAlgorithm::Lz4.as_code(), 3);
+        assert_eq!(CompressionAlgorithm::Zstd.as_code(), 4);
+        assert_eq!(CompressionAlgorithm::Snappy.as_code(), 5);
     }
     #[test]
     fn test_from_code() {
@@ -168,12 +329,105 @@ mod tests {
         let gzip = CompressionAlgorithm::from_code(2);
         assert!(gzip.is_ok());
         assert_eq!(gzip.unwrap(), CompressionAlgorithm::Gzip);
+
+        assert_eq!(
+            CompressionAlgorithm::from_code(3).unwrap(),
+            CompressionAlgorithm::Lz4
+        );
+        assert_eq!(
+            CompressionAlgorithm::from_code(4).unwrap(),
+            CompressionAlgorithm::Zstd
+        );
+        assert_eq!(
+            CompressionAlgorithm::from_code(5).unwrap(),
+            CompressionAlgorithm::Snappy
+        );
+    }
+    #[test]
+    fn test_compress_and_decompress() {
+        let data = "{\"key\": \"value\"}".repeat(100).into_bytes();
+        for algorithm in [
+            CompressionAlgorithm::None,
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Lz4,
+            CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Snappy,
+        ] {
+            let compressed = algorithm.compress(&data, None).unwrap();
+            if algorithm != CompressionAlgorithm::None {
+                assert!(compressed.len() < data.len());
+            }
+            let decompressed = algorithm.decompress(&compressed, data.len()).unwrap();
+            assert_eq!(decompressed, data);
+        }
+    }
+    #[test]
+    fn test_compress_and_decompress_with_level() {
+        let data = "{\"key\": \"value\"}".repeat(100).into_bytes();
+        for (algorithm, level) in [
+            (CompressionAlgorithm::Gzip, 1),
+            (CompressionAlgorithm::Gzip, 9),
+            (CompressionAlgorithm::Zstd, 1),
+            (CompressionAlgorithm::Zstd, 22),
+        ] {
+            let compressed = algorithm.compress(&data, Some(level)).unwrap();
+            let decompressed = algorithm.decompress(&compressed, data.len()).unwrap();
+            assert_eq!(decompressed, data);
+        }
+    }
+    #[test]
+    fn test_validate_invalid_level() {
+        assert!(CompressionAlgorithm::Gzip.validate_level(Some(0)).is_err());
+        assert!(CompressionAlgorithm::Gzip.validate_level(Some(10)).is_err());
+        assert!(CompressionAlgorithm::Zstd.validate_level(Some(0)).is_err());
+        assert!(CompressionAlgorithm::Zstd.validate_level(Some(23)).is_err());
+        assert!(CompressionAlgorithm::Lz4.validate_level(Some(1)).is_err());
+        assert!(CompressionAlgorithm::Snappy
+            .validate_level(Some(1))
+            .is_err());
+        assert!(CompressionAlgorithm::None.validate_level(Some(1)).is_err());
+        assert!(CompressionAlgorithm::Snappy.validate_level(None).is_ok());
+    }
+    #[test]
+    fn test_decompress_invalid_input() {
+        for algorithm in [
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Lz4,
+            CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Snappy,
+        ] {
+            let result = algorithm.decompress(b"invalid", 0);
+            assert!(result.is_err());
+        }
+    }
+    #[test]
+    fn test_decompress_exceeding_max_size() {
+        let data = vec![0u8; 10_000];
+        for algorithm in [
+            CompressionAlgorithm::None,
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Lz4,
+            CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Snappy,
+        ] {
+            let compressed = algorithm.compress(&data, None).unwrap();
+            let result = algorithm.decompress(&compressed, data.len() - 1);
+            assert!(matches!(
+                result,
+                Err(IggyError::DecompressedDataTooLarge(max_size)) if max_size == data.len() - 1
+            ));
+            let decompressed = algorithm.decompress(&compressed, data.len()).unwrap();
+            assert_eq!(decompressed, data);
+        }
     }
     #[test]
     fn test_from_code_invalid_input() {
         let invalid_compression_kind = CompressionAlgorithm::from_code(0);
         assert!(invalid_compression_kind.is_err());
 
+        let invalid_compression_kind = CompressionAlgorithm::from_code(6);
+        assert!(invalid_compression_kind.is_err());
+
         let invalid_compression_kind = CompressionAlgorithm::from_code(69);
         assert!(invalid_compression_kind.is_err());
 
@@ -0,0 +1,24 @@
+use std::fmt::{Display, Formatter};
+
+/// `CompressionLevel` is the level of the compression algorithm used for the topic,
+/// its valid range depends on the algorithm (e.g. 1-9 for gzip and 1-22 for zstd).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompressionLevel(pub u8);
+
+impl From<u8> for CompressionLevel {
+    fn from(value: u8) -> Self {
+        CompressionLevel(value)
+    }
+}
+
+impl From<CompressionLevel> for u8 {
+    fn from(value: CompressionLevel) -> Self {
+        value.0
+    }
+}
+
+impl Display for CompressionLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::bytes_serializable::BytesSerializable;
 use crate::command::{Command, CREATE_CONSUMER_GROUP_CODE};
+use crate::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use crate::consumer_groups::MAX_NAME_LENGTH;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
@@ -16,6 +17,7 @@ use std::str::from_utf8;
 /// - `topic_id` - unique topic ID (numeric or name).
 /// - `group_id` - unique consumer group ID.
 /// - `name` - unique consumer group name, max length is 255 characters.
+/// - `assignment_strategy` - partition assignment strategy, appended after the name for backwards compatibility.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct CreateConsumerGroup {
     /// Unique stream ID (numeric or name).
@@ -28,6 +30,9 @@ pub struct CreateConsumerGroup {
     pub group_id: Option<u32>,
     /// Unique consumer group name, max length is 255 characters.
     pub name: String,
+    /// Strategy used to assign the partitions to the members of the consumer group.
+    #[serde(default)]
+    pub assignment_strategy: PartitionAssignmentStrategy,
 }
 
 impl Command for CreateConsumerGroup {
@@ -43,6 +48,7 @@ impl Default for CreateConsumerGroup {
             topic_id: Identifier::default(),
             group_id: None,
             name: "consumer_group_1".to_string(),
+            assignment_strategy: PartitionAssignmentStrategy::default(),
         }
     }
 }
@@ -68,7 +74,7 @@ impl BytesSerializable for CreateConsumerGroup {
         let stream_id_bytes = self.stream_id.to_bytes();
         let topic_id_bytes = self.topic_id.to_bytes();
         let mut bytes = BytesMut::with_capacity(
-            4 + stream_id_bytes.len() + topic_id_bytes.len() + self.name.len(),
+            6 + stream_id_bytes.len() + topic_id_bytes.len() + self.name.len(),
         );
         bytes.put_slice(&stream_id_bytes);
         bytes.put_slice(&topic_id_bytes);
@@ -76,6 +82,7 @@ impl BytesSerializable for CreateConsumerGroup {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(self.name.len() as u8);
         bytes.put_slice(self.name.as_bytes());
+        bytes.put_u8(self.assignment_strategy.as_code());
         bytes.freeze()
     }
 
@@ -99,11 +106,16 @@ impl BytesSerializable for CreateConsumerGroup {
         let name = from_utf8(&bytes[position + 5..position + 5 + name_length as usize])
             .map_err(|_| IggyError::InvalidUtf8)?
             .to_string();
+        let assignment_strategy = match bytes.get(position + 5 + name_length as usize) {
+            None | Some(0) => PartitionAssignmentStrategy::default(),
+            Some(code) => PartitionAssignmentStrategy::from_code(*code)?,
+        };
         let command = CreateConsumerGroup {
             stream_id,
             topic_id,
             group_id,
             name,
+            assignment_strategy,
         };
         Ok(command)
     }
@@ -113,11 +125,12 @@ impl Display for CreateConsumerGroup {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}",
             self.stream_id,
             self.topic_id,
             self.group_id.unwrap_or(0),
-            self.name
+            self.name,
+            self.assignment_strategy
         )
     }
 }
@@ -133,6 +146,7 @@ mod tests {
             topic_id: Identifier::numeric(2).unwrap(),
             group_id: Some(3),
             name: "test".to_string(),
+            assignment_strategy: PartitionAssignmentStrategy::Sticky,
         };
 
         let bytes = command.to_bytes();
@@ -145,11 +159,16 @@ mod tests {
 
         let name_length = bytes[position + 4];
         let name = from_utf8(&bytes[position + 5..position + 5 + name_length as usize]).unwrap();
+        let assignment_strategy =
+            PartitionAssignmentStrategy::from_code(bytes[position + 5 + name_length as usize])
+                .unwrap();
         assert!(!bytes.is_empty());
         assert_eq!(stream_id, command.stream_id);
         assert_eq!(topic_id, command.topic_id);
         assert_eq!(group_id, command.group_id.unwrap());
         assert_eq!(name, command.name);
+        assert_eq!(assignment_strategy, command.assignment_strategy);
+        assert_eq!(CreateConsumerGroup::from_bytes(bytes).unwrap(), command);
     }
 
     #[test]
@@ -176,5 +195,9 @@ mod tests {
         assert_eq!(command.topic_id, topic_id);
         assert_eq!(command.group_id.unwrap(), group_id);
         assert_eq!(command.name, name);
+        assert_eq!(
+            command.assignment_strategy,
+            PartitionAssignmentStrategy::RoundRobin
+        );
     }
 }
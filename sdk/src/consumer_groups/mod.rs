@@ -4,5 +4,6 @@ pub mod get_consumer_group;
 pub mod get_consumer_groups;
 pub mod join_consumer_group;
 pub mod leave_consumer_group;
+pub mod partition_assignment_strategy;
 
 const MAX_NAME_LENGTH: usize = 255;
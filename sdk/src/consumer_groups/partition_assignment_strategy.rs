@@ -0,0 +1,107 @@
+use crate::error::IggyError;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// `PartitionAssignmentStrategy` defines how the partitions of the topic are distributed
+/// across the members of the consumer group whenever a member joins or leaves the group,
+/// or the partitions count changes.
+#[derive(
+    Clone, Copy, Debug, Default, Display, Serialize, Deserialize, EnumString, PartialEq, Eq, Hash,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PartitionAssignmentStrategy {
+    /// The partitions are reassigned from scratch, one by one to the next member (sorted by ID).
+    #[default]
+    RoundRobin,
+    /// The partitions are reassigned from scratch as contiguous ranges, one range per member (sorted by ID).
+    Range,
+    /// The members keep as many of their current partitions as possible, only the partitions
+    /// of the members that left or exceeding the fair share are moved.
+    Sticky,
+    /// Same target assignment as `Sticky`, but a partition moved between two existing members is first
+    /// revoked from its current owner and handed over to the new one only after the current owner
+    /// has acknowledged the revocation (by polling again) or has left the group.
+    Cooperative,
+}
+
+impl PartitionAssignmentStrategy {
+    /// Returns the code of the strategy.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            PartitionAssignmentStrategy::RoundRobin => 1,
+            PartitionAssignmentStrategy::Range => 2,
+            PartitionAssignmentStrategy::Sticky => 3,
+            PartitionAssignmentStrategy::Cooperative => 4,
+        }
+    }
+
+    /// Returns the strategy from the code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(PartitionAssignmentStrategy::RoundRobin),
+            2 => Ok(PartitionAssignmentStrategy::Range),
+            3 => Ok(PartitionAssignmentStrategy::Sticky),
+            4 => Ok(PartitionAssignmentStrategy::Cooperative),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(
+            PartitionAssignmentStrategy::RoundRobin.to_string(),
+            "round_robin"
+        );
+        assert_eq!(PartitionAssignmentStrategy::Range.to_string(), "range");
+        assert_eq!(PartitionAssignmentStrategy::Sticky.to_string(), "sticky");
+        assert_eq!(
+            PartitionAssignmentStrategy::Cooperative.to_string(),
+            "cooperative"
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            PartitionAssignmentStrategy::from_str("round_robin").unwrap(),
+            PartitionAssignmentStrategy::RoundRobin
+        );
+        assert_eq!(
+            PartitionAssignmentStrategy::from_str("cooperative").unwrap(),
+            PartitionAssignmentStrategy::Cooperative
+        );
+        assert!(PartitionAssignmentStrategy::from_str("invalid").is_err());
+    }
+
+    #[test]
+    fn test_code() {
+        for strategy in [
+            PartitionAssignmentStrategy::RoundRobin,
+            PartitionAssignmentStrategy::Range,
+            PartitionAssignmentStrategy::Sticky,
+            PartitionAssignmentStrategy::Cooperative,
+        ] {
+            assert_eq!(
+                PartitionAssignmentStrategy::from_code(strategy.as_code()).unwrap(),
+                strategy
+            );
+        }
+        assert!(PartitionAssignmentStrategy::from_code(0).is_err());
+        assert!(PartitionAssignmentStrategy::from_code(5).is_err());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(
+            PartitionAssignmentStrategy::default(),
+            PartitionAssignmentStrategy::RoundRobin
+        );
+    }
+}
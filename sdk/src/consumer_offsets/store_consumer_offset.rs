@@ -16,6 +16,7 @@ use std::fmt::Display;
 /// - `topic_id` - unique topic ID (numeric or name).
 /// - `partition_id` - partition ID on which the offset is stored. Has to be specified for the regular consumer. For consumer group it is ignored (use `None`).
 /// - `offset` - offset to store.
+/// - `generation` - optional consumer group generation, the offset is rejected if the group has been rebalanced since then.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct StoreConsumerOffset {
     /// The consumer that is storing the offset, either the regular consumer or the consumer group.
@@ -31,6 +32,10 @@ pub struct StoreConsumerOffset {
     pub partition_id: Option<u32>,
     /// Offset to store.
     pub offset: u64,
+    /// Consumer group generation known to the member, if the consumer group has been rebalanced since then, the offset is rejected.
+    /// Applicable only for the consumer group, `None` skips the validation.
+    #[serde(default)]
+    pub generation: Option<u32>,
 }
 
 impl Default for StoreConsumerOffset {
@@ -41,6 +46,7 @@ impl Default for StoreConsumerOffset {
             topic_id: Identifier::default(),
             partition_id: Some(1),
             offset: 0,
+            generation: None,
         }
     }
 }
@@ -63,7 +69,7 @@ impl BytesSerializable for StoreConsumerOffset {
         let stream_id_bytes = self.stream_id.to_bytes();
         let topic_id_bytes = self.topic_id.to_bytes();
         let mut bytes = BytesMut::with_capacity(
-            12 + consumer_bytes.len() + stream_id_bytes.len() + topic_id_bytes.len(),
+            16 + consumer_bytes.len() + stream_id_bytes.len() + topic_id_bytes.len(),
         );
         bytes.put_slice(&consumer_bytes);
         bytes.put_slice(&stream_id_bytes);
@@ -74,6 +80,9 @@ impl BytesSerializable for StoreConsumerOffset {
             bytes.put_u32_le(0);
         }
         bytes.put_u64_le(self.offset);
+        if let Some(generation) = self.generation {
+            bytes.put_u32_le(generation);
+        }
         bytes.freeze()
     }
 
@@ -109,12 +118,28 @@ impl BytesSerializable for StoreConsumerOffset {
                 .try_into()
                 .map_err(|_| IggyError::InvalidNumberEncoding)?,
         );
+        let generation = match bytes.get(position + 12..position + 16) {
+            Some(generation) => {
+                let generation = u32::from_le_bytes(
+                    generation
+                        .try_into()
+                        .map_err(|_| IggyError::InvalidNumberEncoding)?,
+                );
+                if generation == 0 {
+                    None
+                } else {
+                    Some(generation)
+                }
+            }
+            None => None,
+        };
         let command = StoreConsumerOffset {
             consumer,
             stream_id,
             topic_id,
             partition_id,
             offset,
+            generation,
         };
         Ok(command)
     }
@@ -124,12 +149,13 @@ impl Display for StoreConsumerOffset {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}|{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}|{}",
             self.consumer,
             self.stream_id,
             self.topic_id,
             self.partition_id.unwrap_or(0),
-            self.offset
+            self.offset,
+            self.generation.unwrap_or(0)
         )
     }
 }
@@ -146,6 +172,7 @@ mod tests {
             topic_id: Identifier::numeric(3).unwrap(),
             partition_id: Some(4),
             offset: 5,
+            generation: Some(6),
         };
 
         let bytes = command.to_bytes();
@@ -163,6 +190,8 @@ mod tests {
         position += topic_id.get_size_bytes().as_bytes_usize();
         let partition_id = u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
         let offset = u64::from_le_bytes(bytes[position + 4..position + 12].try_into().unwrap());
+        let generation =
+            u32::from_le_bytes(bytes[position + 12..position + 16].try_into().unwrap());
 
         assert!(!bytes.is_empty());
         assert_eq!(consumer, command.consumer);
@@ -170,6 +199,8 @@ mod tests {
         assert_eq!(topic_id, command.topic_id);
         assert_eq!(Some(partition_id), command.partition_id);
         assert_eq!(offset, command.offset);
+        assert_eq!(Some(generation), command.generation);
+        assert_eq!(StoreConsumerOffset::from_bytes(bytes).unwrap(), command);
     }
 
     #[test]
@@ -201,5 +232,6 @@ mod tests {
         assert_eq!(command.topic_id, topic_id);
         assert_eq!(command.partition_id, Some(partition_id));
         assert_eq!(command.offset, offset);
+        assert_eq!(command.generation, None);
     }
 }
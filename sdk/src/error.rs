@@ -44,6 +44,10 @@ pub enum IggyError {
     StateFileCorrupted = 15,
     #[error("Invalid state entry checksum: {0}, expected: {1}, for index: {2}")]
     InvalidStateEntryChecksum(u32, u32, u64) = 16,
+    #[error("Invalid state entry index: {0}, expected: {1}")]
+    InvalidStateEntryIndex(u64, u64) = 17,
+    #[error("Invalid state snapshot checksum: {0}, expected: {1}, for index: {2}")]
+    InvalidStateSnapshotChecksum(u32, u32, u64) = 18,
     #[error("Cannot open database, Path: {0}")]
     CannotOpenDatabase(String) = 19,
     #[error("Resource with key: {0} was not found.")]
@@ -90,6 +94,24 @@ pub enum IggyError {
     PersonalAccessTokenExpired(String, u32) = 54,
     #[error("Users limit reached.")]
     UsersLimitReached = 55,
+    #[error(
+        "Size quota: {1} bytes of the streams created by user with ID: {0} has been exceeded."
+    )]
+    UserSizeQuotaExceeded(u32, u64) = 56,
+    #[error("Topics quota: {1} of the streams created by user with ID: {0} has been exceeded.")]
+    UserTopicsQuotaExceeded(u32, u32) = 57,
+    #[error(
+        "Partitions quota: {1} of the streams created by user with ID: {0} has been exceeded."
+    )]
+    UserPartitionsQuotaExceeded(u32, u32) = 58,
+    #[error(
+        "Produce throughput quota: {1} bytes per second of user with ID: {0} has been exceeded."
+    )]
+    UserProduceThroughputQuotaExceeded(u32, u64) = 59,
+    #[error(
+        "Consume throughput quota: {1} bytes per second of user with ID: {0} has been exceeded."
+    )]
+    UserConsumeThroughputQuotaExceeded(u32, u64) = 60,
     #[error("Not connected")]
     NotConnected = 61,
     #[error("Client shutdown")]
@@ -102,6 +124,10 @@ pub enum IggyError {
     InvalidTlsCertificate = 66,
     #[error("Failed to add certificate")]
     FailedToAddCertificate = 67,
+    #[error("Invalid TLS private key")]
+    InvalidTlsPrivateKey = 68,
+    #[error("Invalid client certificate user: {0}")]
+    InvalidClientCertificateUser(String) = 69,
     #[error("Invalid encryption key")]
     InvalidEncryptionKey = 70,
     #[error("Cannot encrypt data")]
@@ -120,6 +146,8 @@ pub enum IggyError {
     AccessTokenMissing = 77,
     #[error("Invalid access token")]
     InvalidAccessToken = 78,
+    #[error("Cannot load JSON Web Key Set from: {0}")]
+    CannotLoadJwks(String) = 79,
     #[error("Invalid size bytes")]
     InvalidSizeBytes = 80,
     #[error("Invalid UTF-8")]
@@ -130,6 +158,14 @@ pub enum IggyError {
     InvalidBooleanValue,
     #[error("Invalid number value")]
     InvalidNumberValue,
+    #[error("Cannot compress data")]
+    CannotCompressData = 85,
+    #[error("Cannot decompress data")]
+    CannotDecompressData = 86,
+    #[error("Invalid compression level: {0} for algorithm: {1}")]
+    InvalidCompressionLevel(u8, String) = 87,
+    #[error("Decompressed data exceeds the maximum size: {0} bytes")]
+    DecompressedDataTooLarge(usize) = 88,
     #[error("Client with ID: {0} was not found.")]
     ClientNotFound(u32) = 100,
     #[error("Invalid client ID")]
@@ -192,6 +228,20 @@ pub enum IggyError {
     MissingPartitions(u32, u32) = 1018,
     #[error("Max topic size cannot be lower than segment size. Max topic size: {0} < segment size: {1}.")]
     InvalidTopicSize(MaxTopicSize, IggyByteSize) = 1019,
+    #[error("Size quota: {1} bytes of stream with ID: {0} has been exceeded.")]
+    StreamSizeQuotaExceeded(u32, u64) = 1020,
+    #[error("Topics quota: {1} of stream with ID: {0} has been exceeded.")]
+    StreamTopicsQuotaExceeded(u32, u32) = 1021,
+    #[error("Partitions quota: {1} of stream with ID: {0} has been exceeded.")]
+    StreamPartitionsQuotaExceeded(u32, u32) = 1022,
+    #[error(
+        "Produce throughput quota: {1} bytes per second of stream with ID: {0} has been exceeded."
+    )]
+    StreamProduceThroughputQuotaExceeded(u32, u64) = 1023,
+    #[error(
+        "Consume throughput quota: {1} bytes per second of stream with ID: {0} has been exceeded."
+    )]
+    StreamConsumeThroughputQuotaExceeded(u32, u64) = 1024,
     #[error("Cannot create topics directory for stream with ID: {0}, Path: {1}")]
     CannotCreateTopicsDirectory(u32, String) = 2000,
     #[error(
@@ -328,6 +378,16 @@ pub enum IggyError {
     InvalidKeyValueLength = 4028,
     #[error("Command length error: {0}")]
     CommandLengthError(String) = 4029,
+    #[error("Invalid nack reason")]
+    InvalidNackReason = 4030,
+    #[error("Idempotent producer requires the messages to be sent to the specific partition ID")]
+    InvalidProducerPartitioning = 4031,
+    #[error("Invalid message filter: {0}")]
+    InvalidMessageFilter(String) = 4032,
+    #[error("Producer with ID: {0} and epoch: {1} has been fenced by the newer epoch: {2}")]
+    ProducerFenced(u64, u64, u64) = 4033,
+    #[error("Producer with ID: {0} sent the sequence: {1} out of order, expected: {2}")]
+    OutOfOrderSequence(u64, u64, u64) = 4034,
     #[error("Cannot sed messages due to client disconnection")]
     CannotSendMessagesDueToClientDisconnection = 4050,
     #[error("Invalid offset: {0}")]
@@ -350,6 +410,8 @@ pub enum IggyError {
     CannotCreateConsumerGroupInfo(u32, u32, u32) = 5007,
     #[error("Failed to delete consumer group info file for ID: {0} for topic with ID: {1} for stream with ID: {2}.")]
     CannotDeleteConsumerGroupInfo(u32, u32, u32) = 5008,
+    #[error("Generation: {0} is stale for consumer group with ID: {1}, current generation: {2}.")]
+    StaleConsumerGroupGeneration(u32, u32, u32) = 5009,
     #[error("Base offset is missing")]
     MissingBaseOffsetRetainedMessageBatch = 6000,
     #[error("Last offset delta is missing")]
@@ -400,6 +462,66 @@ pub enum IggyError {
     CannotReadIndexPosition = 10011,
     #[error("Cannot read index timestamp")]
     CannotReadIndexTimestamp = 10012,
+    #[error("io_uring is not available")]
+    IoUringUnavailable = 10013,
+    #[error("Node is not a leader of the cluster, current leader ID: {0}")]
+    NotLeader(u32) = 11000,
+    #[error("Leader of the cluster has not been elected yet")]
+    LeaderNotElected = 11001,
+    #[error(
+        "Not enough replicas: {0} have acknowledged the messages, required replication factor: {1}"
+    )]
+    NotEnoughReplicas(u32, u8) = 11002,
+    #[error("Cluster node with ID: {0} was not found")]
+    ClusterNodeNotFound(u32) = 11003,
+    #[error("Invalid cluster node: {0}")]
+    InvalidClusterNode(String) = 11004,
+    #[error("Invalid cluster secret")]
+    InvalidClusterSecret = 11005,
+    #[error("Stale cluster term: {0}, current term: {1}")]
+    StaleClusterTerm(u64, u64) = 11006,
+    #[error(
+        "State entry with index: {0} has not been committed by the majority of the cluster nodes"
+    )]
+    StateEntryNotCommitted(u64) = 11007,
+    #[error("Invalid cluster metadata")]
+    InvalidClusterMetadata = 11008,
+    #[error("Applied state entry with index: {0} conflicts with the leader's log, the node must be restarted")]
+    ClusterStateDiverged(u64) = 11009,
+    #[error("Transaction with ID: {0} was not found")]
+    TransactionNotFound(u64) = 12000,
+    #[error("Messages in a batch must belong to the same transaction")]
+    InvalidTransactionMessages = 12001,
+    #[error("Transaction is already in progress")]
+    TransactionAlreadyInProgress = 12002,
+    #[error("No transaction is in progress")]
+    NoTransactionInProgress = 12003,
+    #[error("Transactions are not supported in the cluster mode")]
+    TransactionsNotSupported = 12004,
+    #[error("Archiver is disabled")]
+    ArchiverDisabled = 13000,
+    #[error("Invalid archive range")]
+    InvalidArchiveRange = 13001,
+    #[error("Invalid state snapshot: {0}")]
+    InvalidStateSnapshot(String) = 13002,
+    #[error("Archived state was not found")]
+    ArchivedStateNotFound = 13003,
+    #[error("Stream with ID: {0} was not found in the archived state")]
+    ArchivedStreamNotFound(String) = 13004,
+    #[error("Topic with ID: {0} was not found in the archived state of stream with ID: {1}")]
+    ArchivedTopicNotFound(String, String) = 13005,
+    #[error("Cannot restore the archived file: {0}")]
+    CannotRestoreArchivedFile(String) = 13006,
+    #[error("Invalid role name")]
+    InvalidRoleName = 14000,
+    #[error("Role: {0} already exists")]
+    RoleAlreadyExists(String) = 14001,
+    #[error("Roles limit reached.")]
+    RolesLimitReached = 14002,
+    #[error("Role: {0} is already assigned to user with ID: {1}")]
+    RoleAlreadyAssigned(String, u32) = 14003,
+    #[error("Role: {0} is not assigned to user with ID: {1}")]
+    RoleNotAssigned(String, u32) = 14004,
 }
 
 impl IggyError {
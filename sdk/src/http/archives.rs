@@ -0,0 +1,48 @@
+use crate::archives::restore_archive::{ArchiveRange, RestoreArchive};
+use crate::client::ArchiveClient;
+use crate::error::IggyError;
+use crate::http::client::HttpClient;
+use crate::http::HttpTransport;
+use crate::identifier::Identifier;
+use crate::models::archive::RestoredArchive;
+use async_trait::async_trait;
+
+#[async_trait]
+impl ArchiveClient for HttpClient {
+    async fn restore_archive(
+        &self,
+        stream_id: &Identifier,
+        topic_id: Option<&Identifier>,
+        partition_id: Option<u32>,
+        range: ArchiveRange,
+        state_snapshot: Option<&str>,
+    ) -> Result<RestoredArchive, IggyError> {
+        let response = self
+            .post(
+                &get_path(&stream_id.as_cow_str(), topic_id),
+                &RestoreArchive {
+                    stream_id: stream_id.clone(),
+                    topic_id: topic_id.cloned(),
+                    partition_id,
+                    range,
+                    state_snapshot: state_snapshot.map(|state_snapshot| state_snapshot.to_owned()),
+                },
+            )
+            .await?;
+        let restored_archive = response
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        Ok(restored_archive)
+    }
+}
+
+fn get_path(stream_id: &str, topic_id: Option<&Identifier>) -> String {
+    match topic_id {
+        Some(topic_id) => format!(
+            "streams/{stream_id}/topics/{}/archive/restore",
+            topic_id.as_cow_str()
+        ),
+        None => format!("streams/{stream_id}/archive/restore"),
+    }
+}
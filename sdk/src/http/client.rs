@@ -6,15 +6,17 @@ use crate::http::HttpTransport;
 use crate::locking::{IggySharedMut, IggySharedMutFn};
 use crate::models::identity_info::IdentityInfo;
 use crate::utils::duration::IggyDuration;
+use crate::utils::server_address::ServerAddresses;
 use async_broadcast::{broadcast, Receiver, Sender};
 use async_trait::async_trait;
-use reqwest::{Response, StatusCode, Url};
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
+use reqwest::{Certificate, Identity, Response, StatusCode, Url};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, RequestBuilder};
 use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
 use serde::Serialize;
 use std::ops::Deref;
 use std::str::FromStr;
 use std::sync::Arc;
+use tracing::{error, warn};
 
 const PUBLIC_PATHS: &[&str] = &[
     "/",
@@ -22,6 +24,7 @@ const PUBLIC_PATHS: &[&str] = &[
     "/ping",
     "/stats",
     "/users/login",
+    "/users/login/token",
     "/users/refresh-token",
     "/personal-access-tokens/login",
 ];
@@ -30,11 +33,14 @@ const PUBLIC_PATHS: &[&str] = &[
 /// It requires a valid API URL.
 #[derive(Debug)]
 pub struct HttpClient {
-    /// The URL of the Iggy API.
+    /// The URL of the Iggy API (the first one, if the multiple URLs are configured).
     pub api_url: Url,
+    api_urls: ServerAddresses<Url>,
     pub(crate) heartbeat_interval: IggyDuration,
     client: ClientWithMiddleware,
     access_token: IggySharedMut<String>,
+    /// Whether the client certificate is presented, so the server might authenticate the requests without the access token.
+    client_certificate: bool,
     events: (Sender<DiagnosticEvent>, Receiver<DiagnosticEvent>),
 }
 
@@ -70,24 +76,15 @@ impl Default for HttpClient {
 impl HttpTransport for HttpClient {
     /// Get full URL for the provided path.
     fn get_url(&self, path: &str) -> Result<Url, IggyError> {
-        self.api_url
+        self.api_urls
+            .current()
             .join(path)
             .map_err(|_| IggyError::CannotParseUrl)
     }
 
     /// Invoke HTTP GET request to the Iggy API.
     async fn get(&self, path: &str) -> Result<Response, IggyError> {
-        let url = self.get_url(path)?;
-        self.fail_if_not_authenticated(path).await?;
-        let token = self.access_token.read().await;
-        let response = self
-            .client
-            .get(url)
-            .bearer_auth(token.deref())
-            .send()
-            .await
-            .map_err(|_| IggyError::InvalidHttpRequest)?;
-        Self::handle_response(response).await
+        self.send(path, |url| self.client.get(url)).await
     }
 
     /// Invoke HTTP GET request to the Iggy API with query parameters.
@@ -96,18 +93,8 @@ impl HttpTransport for HttpClient {
         path: &str,
         query: &T,
     ) -> Result<Response, IggyError> {
-        let url = self.get_url(path)?;
-        self.fail_if_not_authenticated(path).await?;
-        let token = self.access_token.read().await;
-        let response = self
-            .client
-            .get(url)
-            .bearer_auth(token.deref())
-            .query(query)
-            .send()
+        self.send(path, |url| self.client.get(url).query(query))
             .await
-            .map_err(|_| IggyError::InvalidHttpRequest)?;
-        Self::handle_response(response).await
     }
 
     /// Invoke HTTP POST request to the Iggy API.
@@ -116,18 +103,8 @@ impl HttpTransport for HttpClient {
         path: &str,
         payload: &T,
     ) -> Result<Response, IggyError> {
-        let url = self.get_url(path)?;
-        self.fail_if_not_authenticated(path).await?;
-        let token = self.access_token.read().await;
-        let response = self
-            .client
-            .post(url)
-            .bearer_auth(token.deref())
-            .json(payload)
-            .send()
+        self.send(path, |url| self.client.post(url).json(payload))
             .await
-            .map_err(|_| IggyError::InvalidHttpRequest)?;
-        Self::handle_response(response).await
     }
 
     /// Invoke HTTP PUT request to the Iggy API.
@@ -136,33 +113,13 @@ impl HttpTransport for HttpClient {
         path: &str,
         payload: &T,
     ) -> Result<Response, IggyError> {
-        let url = self.get_url(path)?;
-        self.fail_if_not_authenticated(path).await?;
-        let token = self.access_token.read().await;
-        let response = self
-            .client
-            .put(url)
-            .bearer_auth(token.deref())
-            .json(payload)
-            .send()
+        self.send(path, |url| self.client.put(url).json(payload))
             .await
-            .map_err(|_| IggyError::InvalidHttpRequest)?;
-        Self::handle_response(response).await
     }
 
     /// Invoke HTTP DELETE request to the Iggy API.
     async fn delete(&self, path: &str) -> Result<Response, IggyError> {
-        let url = self.get_url(path)?;
-        self.fail_if_not_authenticated(path).await?;
-        let token = self.access_token.read().await;
-        let response = self
-            .client
-            .delete(url)
-            .bearer_auth(token.deref())
-            .send()
-            .await
-            .map_err(|_| IggyError::InvalidHttpRequest)?;
-        Self::handle_response(response).await
+        self.send(path, |url| self.client.delete(url)).await
     }
 
     /// Invoke HTTP DELETE request to the Iggy API with query parameters.
@@ -171,18 +128,8 @@ impl HttpTransport for HttpClient {
         path: &str,
         query: &T,
     ) -> Result<Response, IggyError> {
-        let url = self.get_url(path)?;
-        self.fail_if_not_authenticated(path).await?;
-        let token = self.access_token.read().await;
-        let response = self
-            .client
-            .delete(url)
-            .bearer_auth(token.deref())
-            .query(query)
-            .send()
+        self.send(path, |url| self.client.delete(url).query(query))
             .await
-            .map_err(|_| IggyError::InvalidHttpRequest)?;
-        Self::handle_response(response).await
     }
 
     /// Returns true if the client is authenticated.
@@ -248,25 +195,91 @@ impl HttpClient {
 
     /// Create a new HTTP client for interacting with the Iggy API using the provided configuration.
     pub fn create(config: Arc<HttpClientConfig>) -> Result<Self, IggyError> {
-        let api_url = Url::parse(&config.api_url);
-        if api_url.is_err() {
-            return Err(IggyError::CannotParseUrl);
-        }
-        let api_url = api_url.unwrap();
+        let api_urls = ServerAddresses::parse(
+            &config.api_url,
+            &config.api_urls,
+            config.address_selection,
+            |url| Url::parse(url).map_err(|_| IggyError::CannotParseUrl),
+        )?;
+        let api_url = api_urls.current().clone();
         let retry_policy = ExponentialBackoff::builder().build_with_max_retries(config.retries);
-        let client = ClientBuilder::new(reqwest::Client::new())
+        let client = ClientBuilder::new(Self::create_reqwest_client(&config)?)
             .with(RetryTransientMiddleware::new_with_policy(retry_policy))
             .build();
 
         Ok(Self {
             api_url,
+            api_urls,
             client,
             heartbeat_interval: IggyDuration::from_str("5s").unwrap(),
             access_token: IggySharedMut::new("".to_string()),
+            client_certificate: config.tls_cert_file.is_some() && config.tls_key_file.is_some(),
             events: broadcast(1000),
         })
     }
 
+    fn create_reqwest_client(config: &HttpClientConfig) -> Result<reqwest::Client, IggyError> {
+        let mut builder = reqwest::Client::builder().use_rustls_tls();
+        if let Some(tls_ca_file) = &config.tls_ca_file {
+            let certificate = std::fs::read(tls_ca_file).map_err(|error| {
+                error!("Failed to read the CA file: {tls_ca_file}. {error}");
+                IggyError::InvalidTlsCertificatePath
+            })?;
+            let certificate = Certificate::from_pem(&certificate).map_err(|error| {
+                error!("Failed to read the CA certificate: {tls_ca_file}. {error}");
+                IggyError::InvalidTlsCertificate
+            })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        if let (Some(cert_file), Some(key_file)) = (&config.tls_cert_file, &config.tls_key_file) {
+            let mut pem = std::fs::read(cert_file).map_err(|error| {
+                error!("Failed to read the client certificate file: {cert_file}. {error}");
+                IggyError::InvalidTlsCertificatePath
+            })?;
+            let key = std::fs::read(key_file).map_err(|error| {
+                error!("Failed to read the client private key file: {key_file}. {error}");
+                IggyError::InvalidTlsPrivateKey
+            })?;
+            pem.push(b'\n');
+            pem.extend(key);
+            let identity = Identity::from_pem(&pem).map_err(|error| {
+                error!("Failed to use the client certificate: {cert_file}. {error}");
+                IggyError::InvalidTlsCertificate
+            })?;
+            builder = builder.identity(identity);
+        }
+
+        builder.build().map_err(|error| {
+            error!("Failed to create HTTP client: {error}");
+            IggyError::InvalidConfiguration
+        })
+    }
+
+    /// Sends the request to the current server, and fails over to the next servers if it can't be reached.
+    async fn send(
+        &self,
+        path: &str,
+        request: impl Fn(Url) -> RequestBuilder + Send + Sync,
+    ) -> Result<Response, IggyError> {
+        self.fail_if_not_authenticated(path).await?;
+        let token = self.access_token.read().await;
+        for (index, api_url) in self.api_urls.candidates() {
+            let url = api_url.join(path).map_err(|_| IggyError::CannotParseUrl)?;
+            match request(url).bearer_auth(token.deref()).send().await {
+                Ok(response) => {
+                    self.api_urls.set_current(index);
+                    return Self::handle_response(response).await;
+                }
+                Err(error) if error.is_connect() => {
+                    warn!("Failed to connect to the server: {api_url}. {error}");
+                }
+                Err(_) => return Err(IggyError::InvalidHttpRequest),
+            }
+        }
+        Err(IggyError::InvalidHttpRequest)
+    }
+
     async fn handle_response(response: Response) -> Result<Response, IggyError> {
         let status = response.status();
         match status.is_success() {
@@ -284,7 +297,7 @@ impl HttpClient {
     }
 
     async fn fail_if_not_authenticated(&self, path: &str) -> Result<(), IggyError> {
-        if PUBLIC_PATHS.contains(&path) {
+        if PUBLIC_PATHS.contains(&path) || self.client_certificate {
             return Ok(());
         }
         if !self.is_authenticated().await {
@@ -1,17 +1,34 @@
+use crate::utils::server_address::ServerAddressSelection;
+
 /// Configuration for the HTTP client.
 #[derive(Debug, Clone)]
 pub struct HttpClientConfig {
     /// The URL of the Iggy API.
     pub api_url: String,
+    /// The URLs of the multiple servers, e.g. the primary and the standby one, used instead of `api_url` when not empty.
+    pub api_urls: Vec<String>,
+    /// The policy of selecting the server to send the requests to, when the multiple URLs are configured.
+    pub address_selection: ServerAddressSelection,
     /// The number of retries to perform on transient errors.
     pub retries: u32,
+    /// The path to the CA file for TLS, in addition to the default root certificates.
+    pub tls_ca_file: Option<String>,
+    /// The path to the certificate file (PEM) presented to the server, which verifies the client certificates.
+    pub tls_cert_file: Option<String>,
+    /// The path to the private key file (PEM) of the client certificate.
+    pub tls_key_file: Option<String>,
 }
 
 impl Default for HttpClientConfig {
     fn default() -> HttpClientConfig {
         HttpClientConfig {
             api_url: "http://127.0.0.1:3000".to_string(),
+            api_urls: Vec::new(),
+            address_selection: ServerAddressSelection::default(),
             retries: 3,
+            tls_ca_file: None,
+            tls_cert_file: None,
+            tls_key_file: None,
         }
     }
 }
@@ -19,7 +36,11 @@ impl Default for HttpClientConfig {
 /// The builder for the `HttpClientConfig` configuration.
 /// Allows configuring the HTTP client with custom settings or using defaults:
 /// - `api_url`: Default is "http://127.0.0.1:3000"
+/// - `api_urls`: Default is empty.
+/// - `address_selection`: Default is ServerAddressSelection::Ordered.
 /// - `retries`: Default is 3.
+/// - `tls_ca_file`: Default is None.
+/// - `tls_cert_file` and `tls_key_file`: Default is None.
 #[derive(Debug, Default)]
 pub struct HttpClientConfigBuilder {
     config: HttpClientConfig,
@@ -37,12 +58,38 @@ impl HttpClientConfigBuilder {
         self
     }
 
+    /// Sets the URLs of the multiple servers, used instead of the single API URL.
+    pub fn with_api_urls(mut self, urls: Vec<String>) -> Self {
+        self.config.api_urls = urls;
+        self
+    }
+
+    /// Sets the policy of selecting the server to send the requests to, when the multiple URLs are configured.
+    pub fn with_address_selection(mut self, address_selection: ServerAddressSelection) -> Self {
+        self.config.address_selection = address_selection;
+        self
+    }
+
     /// Sets the number of retries for the HTTP client.
     pub fn with_retries(mut self, retries: u32) -> Self {
         self.config.retries = retries;
         self
     }
 
+    /// Sets the path to the CA file for TLS.
+    pub fn with_tls_ca_file(mut self, tls_ca_file: String) -> Self {
+        self.config.tls_ca_file = Some(tls_ca_file);
+        self
+    }
+
+    /// Sets the paths to the client certificate and its private key for TLS,
+    /// which are presented to the server requiring the client certificates.
+    pub fn with_tls_client_certificate(mut self, cert_file: String, key_file: String) -> Self {
+        self.config.tls_cert_file = Some(cert_file);
+        self.config.tls_key_file = Some(key_file);
+        self
+    }
+
     /// Builds the `HttpClientConfig` instance.
     pub fn build(self) -> HttpClientConfig {
         self.config
@@ -1,10 +1,11 @@
 use crate::client::ConsumerGroupClient;
 use crate::consumer_groups::create_consumer_group::CreateConsumerGroup;
+use crate::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use crate::error::IggyError;
 use crate::http::client::HttpClient;
 use crate::http::HttpTransport;
 use crate::identifier::Identifier;
-use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails};
+use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails, ConsumerGroupMembership};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -58,6 +59,7 @@ impl ConsumerGroupClient for HttpClient {
         topic_id: &Identifier,
         name: &str,
         group_id: Option<u32>,
+        assignment_strategy: PartitionAssignmentStrategy,
     ) -> Result<ConsumerGroupDetails, IggyError> {
         let response = self
             .post(
@@ -67,6 +69,7 @@ impl ConsumerGroupClient for HttpClient {
                     topic_id: topic_id.clone(),
                     name: name.to_string(),
                     group_id,
+                    assignment_strategy,
                 },
             )
             .await?;
@@ -97,7 +100,7 @@ impl ConsumerGroupClient for HttpClient {
         _: &Identifier,
         _: &Identifier,
         _: &Identifier,
-    ) -> Result<(), IggyError> {
+    ) -> Result<ConsumerGroupMembership, IggyError> {
         Err(IggyError::FeatureUnavailable)
     }
 
@@ -18,6 +18,7 @@ impl ConsumerOffsetClient for HttpClient {
         topic_id: &Identifier,
         partition_id: Option<u32>,
         offset: u64,
+        generation: Option<u32>,
     ) -> Result<(), IggyError> {
         self.put(
             &get_path(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
@@ -27,6 +28,7 @@ impl ConsumerOffsetClient for HttpClient {
                 topic_id: topic_id.clone(),
                 partition_id,
                 offset,
+                generation,
             },
         )
         .await?;
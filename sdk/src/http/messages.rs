@@ -5,7 +5,12 @@ use crate::http::client::HttpClient;
 use crate::http::HttpTransport;
 use crate::identifier::Identifier;
 use crate::messages::flush_unsaved_buffer::FlushUnsavedBuffer;
-use crate::messages::poll_messages::{PollMessages, PollingStrategy};
+use crate::messages::init_producer::InitProducer;
+use crate::messages::message_filter::MessageFilter;
+use crate::messages::nack_message::NackMessage;
+use crate::messages::poll_messages::{
+    IsolationLevel, PollMessages, PollingFormat, PollingStrategy,
+};
 use crate::messages::send_messages::{Message, Partitioning, SendMessages};
 use crate::models::messages::PolledMessages;
 use async_trait::async_trait;
@@ -21,6 +26,56 @@ impl MessageClient for HttpClient {
         strategy: &PollingStrategy,
         count: u32,
         auto_commit: bool,
+    ) -> Result<PolledMessages, IggyError> {
+        self.poll_messages_with_isolation_level(
+            stream_id,
+            topic_id,
+            partition_id,
+            consumer,
+            strategy,
+            count,
+            auto_commit,
+            IsolationLevel::ReadUncommitted,
+        )
+        .await
+    }
+
+    async fn poll_messages_with_isolation_level(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+    ) -> Result<PolledMessages, IggyError> {
+        self.poll_messages_with_filter(
+            stream_id,
+            topic_id,
+            partition_id,
+            consumer,
+            strategy,
+            count,
+            auto_commit,
+            isolation_level,
+            &MessageFilter::default(),
+        )
+        .await
+    }
+
+    async fn poll_messages_with_filter(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+        filter: &MessageFilter,
     ) -> Result<PolledMessages, IggyError> {
         let response = self
             .get_with_query(
@@ -33,6 +88,9 @@ impl MessageClient for HttpClient {
                     strategy: *strategy,
                     count,
                     auto_commit,
+                    isolation_level,
+                    filter: filter.clone(),
+                    format: PollingFormat::Messages,
                 },
             )
             .await?;
@@ -88,6 +146,53 @@ impl MessageClient for HttpClient {
             .await?;
         Ok(())
     }
+
+    async fn nack_message(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+        reason: &str,
+    ) -> Result<(), IggyError> {
+        self.post(
+            &get_path_nack_message(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
+            &NackMessage {
+                consumer: consumer.clone(),
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                partition_id,
+                offset,
+                reason: reason.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn init_producer(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        producer_id: u64,
+    ) -> Result<u64, IggyError> {
+        let response = self
+            .post(
+                &get_path_init_producer(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
+                &InitProducer {
+                    stream_id: stream_id.clone(),
+                    topic_id: topic_id.clone(),
+                    producer_id,
+                },
+            )
+            .await?;
+        let epoch = response
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        Ok(epoch)
+    }
 }
 
 fn get_path(stream_id: &str, topic_id: &str) -> String {
@@ -102,3 +207,11 @@ fn get_path_flush_unsaved_buffer(
 ) -> String {
     format!("streams/{stream_id}/topics/{topic_id}/messages/flush/{partition_id}/fsync={fsync}")
 }
+
+fn get_path_nack_message(stream_id: &str, topic_id: &str) -> String {
+    format!("streams/{stream_id}/topics/{topic_id}/messages/nack")
+}
+
+fn get_path_init_producer(stream_id: &str, topic_id: &str) -> String {
+    format!("streams/{stream_id}/topics/{topic_id}/messages/producers")
+}
@@ -5,6 +5,7 @@ use reqwest::{Response, Url};
 use serde::Serialize;
 
 #[allow(deprecated)]
+pub mod archives;
 pub mod client;
 pub mod config;
 pub mod consumer_groups;
@@ -12,9 +13,11 @@ pub mod consumer_offsets;
 pub mod messages;
 pub mod partitions;
 pub mod personal_access_tokens;
+pub mod roles;
 pub mod streams;
 pub mod system;
 pub mod topics;
+pub mod transactions;
 pub mod users;
 
 #[async_trait]
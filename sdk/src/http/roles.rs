@@ -0,0 +1,120 @@
+use crate::client::RoleClient;
+use crate::error::IggyError;
+use crate::http::client::HttpClient;
+use crate::http::HttpTransport;
+use crate::identifier::Identifier;
+use crate::models::permissions::Permissions;
+use crate::models::role::{RoleInfo, RoleInfoDetails};
+use crate::roles::assign_role::AssignRole;
+use crate::roles::create_role::CreateRole;
+use crate::roles::update_role::UpdateRole;
+use async_trait::async_trait;
+
+const PATH: &str = "/roles";
+
+#[async_trait]
+impl RoleClient for HttpClient {
+    async fn get_role(&self, role_id: &Identifier) -> Result<Option<RoleInfoDetails>, IggyError> {
+        let response = self.get(&format!("{PATH}/{}", role_id)).await;
+        if let Err(error) = response {
+            if matches!(error, IggyError::ResourceNotFound(_)) {
+                return Ok(None);
+            }
+
+            return Err(error);
+        }
+
+        let role = response?
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        Ok(Some(role))
+    }
+
+    async fn get_roles(&self) -> Result<Vec<RoleInfo>, IggyError> {
+        let response = self.get(PATH).await?;
+        let roles = response
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        Ok(roles)
+    }
+
+    async fn create_role(
+        &self,
+        name: &str,
+        permissions: Option<Permissions>,
+    ) -> Result<RoleInfoDetails, IggyError> {
+        let response = self
+            .post(
+                PATH,
+                &CreateRole {
+                    name: name.to_string(),
+                    permissions,
+                },
+            )
+            .await?;
+        let role = response
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        Ok(role)
+    }
+
+    async fn delete_role(&self, role_id: &Identifier) -> Result<(), IggyError> {
+        self.delete(&format!("{PATH}/{}", &role_id.as_cow_str()))
+            .await?;
+        Ok(())
+    }
+
+    async fn update_role(
+        &self,
+        role_id: &Identifier,
+        name: Option<&str>,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError> {
+        self.put(
+            &format!("{PATH}/{}", &role_id.as_cow_str()),
+            &UpdateRole {
+                role_id: role_id.clone(),
+                name: name.map(|s| s.to_string()),
+                permissions,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn assign_role(
+        &self,
+        role_id: &Identifier,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        self.post(
+            &get_user_path(role_id, user_id),
+            &AssignRole {
+                role_id: role_id.clone(),
+                user_id: user_id.clone(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn unassign_role(
+        &self,
+        role_id: &Identifier,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        self.delete(&get_user_path(role_id, user_id)).await?;
+        Ok(())
+    }
+}
+
+fn get_user_path(role_id: &Identifier, user_id: &Identifier) -> String {
+    format!(
+        "{PATH}/{}/users/{}",
+        role_id.as_cow_str(),
+        user_id.as_cow_str()
+    )
+}
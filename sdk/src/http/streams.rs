@@ -3,8 +3,10 @@ use crate::error::IggyError;
 use crate::http::client::HttpClient;
 use crate::http::HttpTransport;
 use crate::identifier::Identifier;
+use crate::models::quota::Quota;
 use crate::models::stream::{Stream, StreamDetails};
 use crate::streams::create_stream::CreateStream;
+use crate::streams::set_stream_quota::SetStreamQuota;
 use crate::streams::update_stream::UpdateStream;
 use async_trait::async_trait;
 
@@ -85,6 +87,22 @@ impl StreamClient for HttpClient {
         .await?;
         Ok(())
     }
+
+    async fn set_stream_quota(
+        &self,
+        stream_id: &Identifier,
+        quota: Quota,
+    ) -> Result<(), IggyError> {
+        self.put(
+            &format!("{}/quota", get_details_path(&stream_id.as_cow_str())),
+            &SetStreamQuota {
+                stream_id: stream_id.clone(),
+                quota,
+            },
+        )
+        .await?;
+        Ok(())
+    }
 }
 
 fn get_details_path(stream_id: &str) -> String {
@@ -1,11 +1,16 @@
 use crate::client::TopicClient;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::compression::compression_level::CompressionLevel;
 use crate::error::IggyError;
 use crate::http::client::HttpClient;
 use crate::http::HttpTransport;
 use crate::identifier::Identifier;
 use crate::models::topic::{Topic, TopicDetails};
+use crate::topics::cleanup_policy::CleanupPolicy;
+use crate::topics::consumed_retention::ConsumedRetention;
 use crate::topics::create_topic::CreateTopic;
+use crate::topics::replication_factor::ReplicationFactor;
+use crate::topics::set_topic_retention::SetTopicRetention;
 use crate::topics::update_topic::UpdateTopic;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::topic_size::MaxTopicSize;
@@ -54,10 +59,12 @@ impl TopicClient for HttpClient {
         name: &str,
         partitions_count: u32,
         compression_algorithm: CompressionAlgorithm,
-        replication_factor: Option<u8>,
+        compression_level: Option<CompressionLevel>,
+        replication_factor: Option<ReplicationFactor>,
         topic_id: Option<u32>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<TopicDetails, IggyError> {
         let response = self
             .post(
@@ -67,10 +74,12 @@ impl TopicClient for HttpClient {
                     name: name.to_string(),
                     partitions_count,
                     compression_algorithm,
-                    replication_factor,
+                    compression_level: compression_level.map(u8::from),
+                    replication_factor: replication_factor.map(u8::from),
                     topic_id,
                     message_expiry,
                     max_topic_size,
+                    cleanup_policy,
                 },
             )
             .await?;
@@ -87,9 +96,11 @@ impl TopicClient for HttpClient {
         topic_id: &Identifier,
         name: &str,
         compression_algorithm: CompressionAlgorithm,
-        replication_factor: Option<u8>,
+        compression_level: Option<CompressionLevel>,
+        replication_factor: Option<ReplicationFactor>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<(), IggyError> {
         self.put(
             &get_details_path(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
@@ -98,9 +109,11 @@ impl TopicClient for HttpClient {
                 topic_id: topic_id.clone(),
                 name: name.to_string(),
                 compression_algorithm,
-                replication_factor,
+                compression_level: compression_level.map(u8::from),
+                replication_factor: replication_factor.map(u8::from),
                 message_expiry,
                 max_topic_size,
+                cleanup_policy,
             },
         )
         .await?;
@@ -132,6 +145,27 @@ impl TopicClient for HttpClient {
         .await?;
         Ok(())
     }
+
+    async fn set_topic_retention(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        retention: ConsumedRetention,
+    ) -> Result<(), IggyError> {
+        self.put(
+            &format!(
+                "{}/retention",
+                get_details_path(&stream_id.as_cow_str(), &topic_id.as_cow_str())
+            ),
+            &SetTopicRetention {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                retention,
+            },
+        )
+        .await?;
+        Ok(())
+    }
 }
 
 fn get_path(stream_id: &str) -> String {
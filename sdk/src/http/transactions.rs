@@ -0,0 +1,42 @@
+use crate::client::TransactionClient;
+use crate::error::IggyError;
+use crate::http::client::HttpClient;
+use crate::http::HttpTransport;
+use crate::transactions::begin_transaction::BeginTransaction;
+use crate::transactions::commit_transaction::{CommitTransaction, TransactionOffset};
+use async_trait::async_trait;
+
+const PATH: &str = "/transactions";
+
+#[async_trait]
+impl TransactionClient for HttpClient {
+    async fn begin_transaction(&self) -> Result<u64, IggyError> {
+        let response = self.post(PATH, &BeginTransaction {}).await?;
+        let transaction_id = response
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        Ok(transaction_id)
+    }
+
+    async fn commit_transaction(
+        &self,
+        transaction_id: u64,
+        offsets: &[TransactionOffset],
+    ) -> Result<(), IggyError> {
+        self.post(
+            &format!("{PATH}/{transaction_id}/commit"),
+            &CommitTransaction {
+                transaction_id,
+                offsets: offsets.to_vec(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn abort_transaction(&self, transaction_id: u64) -> Result<(), IggyError> {
+        self.delete(&format!("{PATH}/{transaction_id}")).await?;
+        Ok(())
+    }
+}
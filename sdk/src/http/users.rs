@@ -5,11 +5,14 @@ use crate::http::HttpTransport;
 use crate::identifier::Identifier;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::permissions::Permissions;
+use crate::models::quota::Quota;
 use crate::models::user_info::{UserInfo, UserInfoDetails};
 use crate::models::user_status::UserStatus;
 use crate::users::change_password::ChangePassword;
 use crate::users::create_user::CreateUser;
 use crate::users::login_user::LoginUser;
+use crate::users::login_with_token::LoginWithToken;
+use crate::users::set_user_quota::SetUserQuota;
 use crate::users::update_permissions::UpdatePermissions;
 use crate::users::update_user::UpdateUser;
 use async_trait::async_trait;
@@ -109,6 +112,18 @@ impl UserClient for HttpClient {
         Ok(())
     }
 
+    async fn set_user_quota(&self, user_id: &Identifier, quota: Quota) -> Result<(), IggyError> {
+        self.put(
+            &format!("{PATH}/{}/quota", &user_id.as_cow_str()),
+            &SetUserQuota {
+                user_id: user_id.clone(),
+                quota,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
     async fn change_password(
         &self,
         user_id: &Identifier,
@@ -147,6 +162,23 @@ impl UserClient for HttpClient {
         Ok(identity_info)
     }
 
+    async fn login_with_token(&self, token: &str) -> Result<IdentityInfo, IggyError> {
+        let response = self
+            .post(
+                &format!("{PATH}/login/token"),
+                &LoginWithToken {
+                    token: token.to_string(),
+                },
+            )
+            .await?;
+        let identity_info = response
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        self.set_token_from_identity(&identity_info).await?;
+        Ok(identity_info)
+    }
+
     async fn logout_user(&self) -> Result<(), IggyError> {
         self.delete(&format!("{PATH}/logout")).await?;
         self.set_access_token(None).await;
@@ -1,3 +1,4 @@
+pub mod archives;
 pub mod args;
 pub mod binary;
 pub mod bytes_serializable;
@@ -29,12 +30,14 @@ pub mod partitioner;
 pub mod partitions;
 pub mod personal_access_tokens;
 pub mod quic;
+pub mod roles;
 pub mod snapshot;
 pub mod stream_builder;
 pub mod streams;
 pub mod system;
 pub mod tcp;
 pub mod topics;
+pub mod transactions;
 pub mod users;
 pub mod utils;
 pub mod validatable;
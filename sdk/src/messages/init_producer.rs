@@ -0,0 +1,136 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, INIT_PRODUCER_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `InitProducer` command is used to start the new epoch of the idempotent producer for the topic.
+/// The server allocates the epoch higher than any epoch of the producer stored for the topic partitions,
+/// which fences the previous instances of the producer, and the sequences of the new epoch start over.
+/// It has additional payload:
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `topic_id` - unique topic ID (numeric or name).
+/// - `producer_id` - unique ID of the idempotent producer.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct InitProducer {
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    #[serde(skip)]
+    pub topic_id: Identifier,
+    /// Unique ID of the idempotent producer.
+    pub producer_id: u64,
+}
+
+impl Command for InitProducer {
+    fn code(&self) -> u32 {
+        INIT_PRODUCER_CODE
+    }
+}
+
+impl Validatable<IggyError> for InitProducer {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for InitProducer {
+    fn to_bytes(&self) -> Bytes {
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(8 + stream_id_bytes.len() + topic_id_bytes.len());
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u64_le(self.producer_id);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<InitProducer, IggyError> {
+        if bytes.len() < 14 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone())?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        let producer_id = u64::from_le_bytes(
+            bytes
+                .get(position..position + 8)
+                .ok_or(IggyError::InvalidCommand)?
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let command = InitProducer {
+            stream_id,
+            topic_id,
+            producer_id,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for InitProducer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}",
+            self.stream_id, self.topic_id, self.producer_id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = InitProducer {
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::named("test").unwrap(),
+            producer_id: 3,
+        };
+
+        let bytes = command.to_bytes();
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        let producer_id = u64::from_le_bytes(bytes[position..position + 8].try_into().unwrap());
+
+        assert!(!bytes.is_empty());
+        assert_eq!(stream_id, command.stream_id);
+        assert_eq!(topic_id, command.topic_id);
+        assert_eq!(producer_id, command.producer_id);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let stream_id = Identifier::numeric(1).unwrap();
+        let topic_id = Identifier::numeric(2).unwrap();
+        let producer_id = 3u64;
+
+        let stream_id_bytes = stream_id.to_bytes();
+        let topic_id_bytes = topic_id.to_bytes();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u64_le(producer_id);
+
+        let command = InitProducer::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.stream_id, stream_id);
+        assert_eq!(command.topic_id, topic_id);
+        assert_eq!(command.producer_id, producer_id);
+    }
+}
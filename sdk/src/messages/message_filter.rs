@@ -0,0 +1,542 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::error::IggyError;
+use crate::models::header::{HeaderKey, HeaderKind, HeaderValue};
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+const CONDITIONS_SEPARATOR: char = ';';
+const VALUES_SEPARATOR: char = '|';
+const RANGE_SEPARATOR: &str = "..";
+const MAX_CONDITIONS: usize = 255;
+
+/// `MessageFilter` is an optional predicate on the message headers, evaluated by the server while polling the messages,
+/// so that only the matching messages are sent to the consumer. All the conditions must be met for the message to match,
+/// and the empty filter matches all the messages.
+///
+/// The filter can be also represented as an expression with the conditions separated by `;`:
+/// - `exists:key` - the header is present.
+/// - `eq:key:kind:value` - the header is equal to the value of the specified kind, e.g. `eq:tenant:string:acme`.
+/// - `in:key:kind:value1|value2` - the header is equal to one of the values, e.g. `in:priority:uint8:1|2`.
+/// - `range:key:kind:min..max` - the numeric header is within the inclusive range (either bound can be omitted), e.g. `range:amount:float64:10..`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MessageFilter {
+    /// Conditions which all must be met for the message to match the filter.
+    pub conditions: Vec<HeaderCondition>,
+}
+
+/// `HeaderCondition` is a single condition of the `MessageFilter` on the header with the specified key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeaderCondition {
+    /// The header is present, regardless of its value.
+    Exists(HeaderKey),
+    /// The header is equal to the value (including its kind).
+    Equals(HeaderKey, HeaderValue),
+    /// The header is equal to one of the values.
+    In(HeaderKey, Vec<HeaderValue>),
+    /// The header is within the inclusive range, available only for the numeric header kinds.
+    Range {
+        key: HeaderKey,
+        min: Option<HeaderValue>,
+        max: Option<HeaderValue>,
+    },
+}
+
+impl MessageFilter {
+    /// Creates a new filter matching the messages which meet all the conditions.
+    pub fn new(conditions: Vec<HeaderCondition>) -> Self {
+        Self { conditions }
+    }
+
+    /// Returns `true` if the filter has no conditions and matches all the messages.
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    /// Returns `true` if the message with the specified headers matches the filter.
+    pub fn matches(&self, headers: Option<&HashMap<HeaderKey, HeaderValue>>) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(headers))
+    }
+}
+
+impl HeaderCondition {
+    /// Returns `true` if the message with the specified headers meets the condition.
+    pub fn matches(&self, headers: Option<&HashMap<HeaderKey, HeaderValue>>) -> bool {
+        let Some(header) = headers.and_then(|headers| headers.get(self.key())) else {
+            return false;
+        };
+
+        match self {
+            HeaderCondition::Exists(_) => true,
+            HeaderCondition::Equals(_, value) => header == value,
+            HeaderCondition::In(_, values) => values.contains(header),
+            HeaderCondition::Range { min, max, .. } => {
+                let above_min = min.as_ref().is_none_or(|min| {
+                    matches!(
+                        compare_numeric(header, min),
+                        Some(Ordering::Greater | Ordering::Equal)
+                    )
+                });
+                let below_max = max.as_ref().is_none_or(|max| {
+                    matches!(
+                        compare_numeric(header, max),
+                        Some(Ordering::Less | Ordering::Equal)
+                    )
+                });
+                above_min && below_max
+            }
+        }
+    }
+
+    /// Returns the key of the header which the condition applies to.
+    pub fn key(&self) -> &HeaderKey {
+        match self {
+            HeaderCondition::Exists(key)
+            | HeaderCondition::Equals(key, _)
+            | HeaderCondition::In(key, _)
+            | HeaderCondition::Range { key, .. } => key,
+        }
+    }
+
+    fn as_code(&self) -> u8 {
+        match self {
+            HeaderCondition::Exists(_) => 1,
+            HeaderCondition::Equals(_, _) => 2,
+            HeaderCondition::In(_, _) => 3,
+            HeaderCondition::Range { .. } => 4,
+        }
+    }
+}
+
+impl Validatable<IggyError> for MessageFilter {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.conditions.len() > MAX_CONDITIONS {
+            return Err(IggyError::InvalidMessageFilter(format!(
+                "too many conditions, max is {MAX_CONDITIONS}"
+            )));
+        }
+
+        for condition in &self.conditions {
+            match condition {
+                HeaderCondition::Exists(_) | HeaderCondition::Equals(_, _) => {}
+                HeaderCondition::In(key, values) => {
+                    if values.is_empty() || values.len() > MAX_CONDITIONS {
+                        return Err(IggyError::InvalidMessageFilter(format!(
+                            "invalid number of values for header: {key}"
+                        )));
+                    }
+                }
+                HeaderCondition::Range { key, min, max } => {
+                    if min.is_none() && max.is_none() {
+                        return Err(IggyError::InvalidMessageFilter(format!(
+                            "missing range bounds for header: {key}"
+                        )));
+                    }
+
+                    let kinds = min.iter().chain(max.iter()).map(|value| value.kind);
+                    for kind in kinds {
+                        if !is_numeric(kind) {
+                            return Err(IggyError::InvalidMessageFilter(format!(
+                                "range for header: {key} requires the numeric kind, found: {kind}"
+                            )));
+                        }
+                    }
+
+                    if let (Some(min), Some(max)) = (min, max) {
+                        if min.kind != max.kind {
+                            return Err(IggyError::InvalidMessageFilter(format!(
+                                "range bounds for header: {key} have different kinds"
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for MessageFilter {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::new();
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u8(self.conditions.len() as u8);
+        for condition in &self.conditions {
+            bytes.put_u8(condition.as_code());
+            let key = condition.key().as_str();
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.put_u8(key.len() as u8);
+            bytes.put_slice(key.as_bytes());
+            match condition {
+                HeaderCondition::Exists(_) => {}
+                HeaderCondition::Equals(_, value) => put_value(&mut bytes, value),
+                HeaderCondition::In(_, values) => {
+                    #[allow(clippy::cast_possible_truncation)]
+                    bytes.put_u8(values.len() as u8);
+                    for value in values {
+                        put_value(&mut bytes, value);
+                    }
+                }
+                HeaderCondition::Range { min, max, .. } => {
+                    let mut bounds = 0;
+                    if min.is_some() {
+                        bounds |= 1;
+                    }
+                    if max.is_some() {
+                        bounds |= 2;
+                    }
+                    bytes.put_u8(bounds);
+                    for value in min.iter().chain(max.iter()) {
+                        put_value(&mut bytes, value);
+                    }
+                }
+            }
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        let mut position = 0;
+        let filter = MessageFilter::read_from(&bytes, &mut position)?;
+        if position != bytes.len() {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(filter)
+    }
+}
+
+impl MessageFilter {
+    /// Reads the filter starting at the given position and advances it past the filter,
+    /// which allows the filter to be followed by the other fields of the command.
+    pub(crate) fn read_from(bytes: &Bytes, position: &mut usize) -> Result<Self, IggyError> {
+        let conditions_count = read_u8(bytes, position)? as usize;
+        let mut conditions = Vec::with_capacity(conditions_count);
+        for _ in 0..conditions_count {
+            let code = read_u8(bytes, position)?;
+            let key_length = read_u8(bytes, position)? as usize;
+            let key = read_slice(bytes, position, key_length)?;
+            let key = std::str::from_utf8(&key).map_err(|_| IggyError::InvalidHeaderKey)?;
+            let key = HeaderKey::new(key)?;
+            let condition = match code {
+                1 => HeaderCondition::Exists(key),
+                2 => HeaderCondition::Equals(key, read_value(bytes, position)?),
+                3 => {
+                    let values_count = read_u8(bytes, position)?;
+                    let mut values = Vec::with_capacity(values_count as usize);
+                    for _ in 0..values_count {
+                        values.push(read_value(bytes, position)?);
+                    }
+                    HeaderCondition::In(key, values)
+                }
+                4 => {
+                    let bounds = read_u8(bytes, position)?;
+                    let min = if bounds & 1 != 0 {
+                        Some(read_value(bytes, position)?)
+                    } else {
+                        None
+                    };
+                    let max = if bounds & 2 != 0 {
+                        Some(read_value(bytes, position)?)
+                    } else {
+                        None
+                    };
+                    HeaderCondition::Range { key, min, max }
+                }
+                _ => return Err(IggyError::InvalidCommand),
+            };
+            conditions.push(condition);
+        }
+
+        Ok(MessageFilter { conditions })
+    }
+}
+
+impl FromStr for MessageFilter {
+    type Err = IggyError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut conditions = Vec::new();
+        for condition in input
+            .split(CONDITIONS_SEPARATOR)
+            .map(str::trim)
+            .filter(|condition| !condition.is_empty())
+        {
+            conditions.push(HeaderCondition::from_str(condition)?);
+        }
+
+        let filter = MessageFilter { conditions };
+        filter.validate()?;
+        Ok(filter)
+    }
+}
+
+impl FromStr for HeaderCondition {
+    type Err = IggyError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid_condition =
+            || IggyError::InvalidMessageFilter(format!("invalid condition: {input}"));
+        let parts = input.splitn(4, ':').collect::<Vec<_>>();
+        let key = HeaderKey::new(parts.get(1).ok_or_else(invalid_condition)?)?;
+        if parts[0] == "exists" {
+            if parts.len() != 2 {
+                return Err(invalid_condition());
+            }
+            return Ok(HeaderCondition::Exists(key));
+        }
+
+        if parts.len() != 4 {
+            return Err(invalid_condition());
+        }
+
+        let kind = HeaderKind::from_str(parts[2])?;
+        let value = parts[3];
+        match parts[0] {
+            "eq" => Ok(HeaderCondition::Equals(
+                key,
+                HeaderValue::from_kind_and_value_str(kind, value)?,
+            )),
+            "in" => Ok(HeaderCondition::In(
+                key,
+                value
+                    .split(VALUES_SEPARATOR)
+                    .map(|value| HeaderValue::from_kind_and_value_str(kind, value))
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            "range" => {
+                let (min, max) = value
+                    .split_once(RANGE_SEPARATOR)
+                    .ok_or_else(invalid_condition)?;
+                let parse_bound = |bound: &str| {
+                    if bound.is_empty() {
+                        return Ok(None);
+                    }
+                    HeaderValue::from_kind_and_value_str(kind, bound).map(Some)
+                };
+                Ok(HeaderCondition::Range {
+                    key,
+                    min: parse_bound(min)?,
+                    max: parse_bound(max)?,
+                })
+            }
+            _ => Err(invalid_condition()),
+        }
+    }
+}
+
+impl Display for MessageFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, condition) in self.conditions.iter().enumerate() {
+            if index > 0 {
+                write!(f, "{CONDITIONS_SEPARATOR}")?;
+            }
+            write!(f, "{condition}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for HeaderCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeaderCondition::Exists(key) => write!(f, "exists:{key}"),
+            HeaderCondition::Equals(key, value) => {
+                write!(f, "eq:{key}:{}:{}", value.kind, value_to_string(value))
+            }
+            HeaderCondition::In(key, values) => {
+                let kind = values
+                    .first()
+                    .map_or(HeaderKind::String, |value| value.kind);
+                let values = values
+                    .iter()
+                    .map(value_to_string)
+                    .collect::<Vec<_>>()
+                    .join(&VALUES_SEPARATOR.to_string());
+                write!(f, "in:{key}:{kind}:{values}")
+            }
+            HeaderCondition::Range { key, min, max } => {
+                let kind = min
+                    .as_ref()
+                    .or(max.as_ref())
+                    .map_or(HeaderKind::Int64, |value| value.kind);
+                write!(
+                    f,
+                    "range:{key}:{kind}:{}{RANGE_SEPARATOR}{}",
+                    min.as_ref().map(value_to_string).unwrap_or_default(),
+                    max.as_ref().map(value_to_string).unwrap_or_default()
+                )
+            }
+        }
+    }
+}
+
+fn value_to_string(value: &HeaderValue) -> String {
+    match value.kind {
+        HeaderKind::Raw => String::from_utf8_lossy(&value.value).to_string(),
+        _ => value.value_only_to_string(),
+    }
+}
+
+fn is_numeric(kind: HeaderKind) -> bool {
+    !matches!(
+        kind,
+        HeaderKind::Raw | HeaderKind::String | HeaderKind::Bool
+    )
+}
+
+fn compare_numeric(value: &HeaderValue, bound: &HeaderValue) -> Option<Ordering> {
+    if value.kind != bound.kind {
+        return None;
+    }
+
+    match value.kind {
+        HeaderKind::Int8 => Some(value.as_int8().ok()?.cmp(&bound.as_int8().ok()?)),
+        HeaderKind::Int16 => Some(value.as_int16().ok()?.cmp(&bound.as_int16().ok()?)),
+        HeaderKind::Int32 => Some(value.as_int32().ok()?.cmp(&bound.as_int32().ok()?)),
+        HeaderKind::Int64 => Some(value.as_int64().ok()?.cmp(&bound.as_int64().ok()?)),
+        HeaderKind::Int128 => Some(value.as_int128().ok()?.cmp(&bound.as_int128().ok()?)),
+        HeaderKind::Uint8 => Some(value.as_uint8().ok()?.cmp(&bound.as_uint8().ok()?)),
+        HeaderKind::Uint16 => Some(value.as_uint16().ok()?.cmp(&bound.as_uint16().ok()?)),
+        HeaderKind::Uint32 => Some(value.as_uint32().ok()?.cmp(&bound.as_uint32().ok()?)),
+        HeaderKind::Uint64 => Some(value.as_uint64().ok()?.cmp(&bound.as_uint64().ok()?)),
+        HeaderKind::Uint128 => Some(value.as_uint128().ok()?.cmp(&bound.as_uint128().ok()?)),
+        HeaderKind::Float32 => value
+            .as_float32()
+            .ok()?
+            .partial_cmp(&bound.as_float32().ok()?),
+        HeaderKind::Float64 => value
+            .as_float64()
+            .ok()?
+            .partial_cmp(&bound.as_float64().ok()?),
+        HeaderKind::Raw | HeaderKind::String | HeaderKind::Bool => None,
+    }
+}
+
+fn put_value(bytes: &mut BytesMut, value: &HeaderValue) {
+    bytes.put_u8(value.kind.as_code());
+    #[allow(clippy::cast_possible_truncation)]
+    bytes.put_u8(value.value.len() as u8);
+    bytes.put_slice(&value.value);
+}
+
+fn read_u8(bytes: &Bytes, position: &mut usize) -> Result<u8, IggyError> {
+    let value = *bytes.get(*position).ok_or(IggyError::InvalidCommand)?;
+    *position += 1;
+    Ok(value)
+}
+
+fn read_slice(bytes: &Bytes, position: &mut usize, length: usize) -> Result<Bytes, IggyError> {
+    if *position + length > bytes.len() {
+        return Err(IggyError::InvalidCommand);
+    }
+
+    let slice = bytes.slice(*position..*position + length);
+    *position += length;
+    Ok(slice)
+}
+
+fn read_value(bytes: &Bytes, position: &mut usize) -> Result<HeaderValue, IggyError> {
+    let kind = HeaderKind::from_code(read_u8(bytes, position)?)?;
+    let length = read_u8(bytes, position)? as usize;
+    if length == 0 {
+        return Err(IggyError::InvalidHeaderValue);
+    }
+
+    let value = read_slice(bytes, position, length)?;
+    Ok(HeaderValue { kind, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(entries: &[(&str, HeaderValue)]) -> HashMap<HeaderKey, HeaderValue> {
+        entries
+            .iter()
+            .map(|(key, value)| (HeaderKey::new(key).unwrap(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn filter_should_match_headers_meeting_all_conditions() {
+        let filter = MessageFilter::from_str(
+            "eq:tenant:string:acme;in:priority:uint8:1|2;range:amount:float64:10..100;exists:trace",
+        )
+        .unwrap();
+        let mut headers = headers(&[
+            ("tenant", HeaderValue::from_str("acme").unwrap()),
+            ("priority", HeaderValue::from_uint8(2).unwrap()),
+            ("amount", HeaderValue::from_float64(10.0).unwrap()),
+            ("trace", HeaderValue::from_bool(true).unwrap()),
+        ]);
+        assert!(filter.matches(Some(&headers)));
+
+        headers.insert(
+            HeaderKey::new("amount").unwrap(),
+            HeaderValue::from_float64(100.5).unwrap(),
+        );
+        assert!(!filter.matches(Some(&headers)));
+        assert!(!filter.matches(None));
+        assert!(MessageFilter::default().matches(None));
+    }
+
+    #[test]
+    fn range_should_not_match_header_of_different_kind() {
+        let filter = MessageFilter::from_str("range:amount:int64:..5").unwrap();
+        assert!(filter.matches(Some(&headers(&[(
+            "amount",
+            HeaderValue::from_int64(-3).unwrap()
+        )]))));
+        assert!(!filter.matches(Some(&headers(&[(
+            "amount",
+            HeaderValue::from_int32(-3).unwrap()
+        )]))));
+    }
+
+    #[test]
+    fn invalid_expressions_should_be_rejected() {
+        assert!(MessageFilter::from_str("eq:tenant").is_err());
+        assert!(MessageFilter::from_str("exists:tenant:string").is_err());
+        assert!(MessageFilter::from_str("range:tenant:string:a..b").is_err());
+        assert!(MessageFilter::from_str("range:amount:int32:..").is_err());
+        assert!(MessageFilter::from_str("like:tenant:string:acme").is_err());
+    }
+
+    #[test]
+    fn filter_should_be_serialized_as_bytes_and_string() {
+        let filter = MessageFilter::new(vec![
+            HeaderCondition::Exists(HeaderKey::new("trace").unwrap()),
+            HeaderCondition::Equals(
+                HeaderKey::new("tenant").unwrap(),
+                HeaderValue::from_str("acme").unwrap(),
+            ),
+            HeaderCondition::In(
+                HeaderKey::new("priority").unwrap(),
+                vec![
+                    HeaderValue::from_uint8(1).unwrap(),
+                    HeaderValue::from_uint8(2).unwrap(),
+                ],
+            ),
+            HeaderCondition::Range {
+                key: HeaderKey::new("amount").unwrap(),
+                min: None,
+                max: Some(HeaderValue::from_float64(99.5).unwrap()),
+            },
+        ]);
+
+        let deserialized = MessageFilter::from_bytes(filter.to_bytes()).unwrap();
+        assert_eq!(deserialized, filter);
+
+        let expression = filter.to_string();
+        assert_eq!(
+            expression,
+            "exists:trace;eq:tenant:string:acme;in:priority:uint8:1|2;range:amount:float64:..99.5"
+        );
+        assert_eq!(MessageFilter::from_str(&expression).unwrap(), filter);
+    }
+}
@@ -1,4 +1,7 @@
 pub mod flush_unsaved_buffer;
+pub mod init_producer;
+pub mod message_filter;
+pub mod nack_message;
 pub mod poll_messages;
 pub mod send_messages;
 
@@ -0,0 +1,253 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, NACK_MESSAGE_CODE};
+use crate::consumer::{Consumer, ConsumerKind};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::from_utf8;
+
+/// Header containing the ID of the stream from which the message has been moved to the dead letter topic.
+pub const DEAD_LETTER_STREAM_ID_HEADER: &str = "iggy-dead-letter-stream-id";
+/// Header containing the ID of the topic from which the message has been moved to the dead letter topic.
+pub const DEAD_LETTER_TOPIC_ID_HEADER: &str = "iggy-dead-letter-topic-id";
+/// Header containing the ID of the partition from which the message has been moved to the dead letter topic.
+pub const DEAD_LETTER_PARTITION_ID_HEADER: &str = "iggy-dead-letter-partition-id";
+/// Header containing the original offset of the message moved to the dead letter topic.
+pub const DEAD_LETTER_OFFSET_HEADER: &str = "iggy-dead-letter-offset";
+/// Header containing the reason of the last failed delivery attempt of the message moved to the dead letter topic.
+pub const DEAD_LETTER_REASON_HEADER: &str = "iggy-dead-letter-reason";
+
+const MAX_REASON_LENGTH: usize = 255;
+
+/// `NackMessage` command is used to negatively acknowledge the message, which couldn't be processed by the consumer.
+/// Each nack is recorded as a failed delivery attempt for the consumer (or consumer group), once the configured
+/// number of attempts is reached, the message is marked as poisoned and moved to the dead letter topic (if enabled).
+/// It has additional payload:
+/// - `consumer` - the consumer that failed to process the message, either the regular consumer or the consumer group.
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `topic_id` - unique topic ID (numeric or name).
+/// - `partition_id` - partition ID containing the message.
+/// - `offset` - offset of the message.
+/// - `reason` - optional reason of the failure, max length is 255 characters.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct NackMessage {
+    /// The consumer that failed to process the message, either the regular consumer or the consumer group.
+    #[serde(flatten)]
+    pub consumer: Consumer,
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    #[serde(skip)]
+    pub topic_id: Identifier,
+    /// Partition ID containing the message.
+    pub partition_id: u32,
+    /// Offset of the message.
+    pub offset: u64,
+    /// Optional reason of the failure, max length is 255 characters.
+    #[serde(default)]
+    pub reason: String,
+}
+
+impl Default for NackMessage {
+    fn default() -> Self {
+        NackMessage {
+            consumer: Consumer::default(),
+            stream_id: Identifier::default(),
+            topic_id: Identifier::default(),
+            partition_id: 1,
+            offset: 0,
+            reason: String::new(),
+        }
+    }
+}
+
+impl Command for NackMessage {
+    fn code(&self) -> u32 {
+        NACK_MESSAGE_CODE
+    }
+}
+
+impl Validatable<IggyError> for NackMessage {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.reason.len() > MAX_REASON_LENGTH {
+            return Err(IggyError::InvalidNackReason);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for NackMessage {
+    fn to_bytes(&self) -> Bytes {
+        let consumer_bytes = self.consumer.to_bytes();
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(
+            13 + consumer_bytes.len()
+                + stream_id_bytes.len()
+                + topic_id_bytes.len()
+                + self.reason.len(),
+        );
+        bytes.put_slice(&consumer_bytes);
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(self.partition_id);
+        bytes.put_u64_le(self.offset);
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u8(self.reason.len() as u8);
+        bytes.put_slice(self.reason.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<NackMessage, IggyError> {
+        if bytes.len() < 24 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let consumer_kind = ConsumerKind::from_code(bytes[0])?;
+        let consumer_id = Identifier::from_bytes(bytes.slice(1..))?;
+        position += 1 + consumer_id.get_size_bytes().as_bytes_usize();
+        let consumer = Consumer {
+            kind: consumer_kind,
+            id: consumer_id,
+        };
+        let stream_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        if bytes.len() < position + 13 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let partition_id = u32::from_le_bytes(
+            bytes[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let offset = u64::from_le_bytes(
+            bytes[position + 4..position + 12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let reason_length = bytes[position + 12] as usize;
+        let reason = bytes
+            .get(position + 13..position + 13 + reason_length)
+            .ok_or(IggyError::InvalidCommand)?;
+        let reason = from_utf8(reason)
+            .map_err(|_| IggyError::InvalidUtf8)?
+            .to_string();
+        let command = NackMessage {
+            consumer,
+            stream_id,
+            topic_id,
+            partition_id,
+            offset,
+            reason,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for NackMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}",
+            self.consumer,
+            self.stream_id,
+            self.topic_id,
+            self.partition_id,
+            self.offset,
+            self.reason
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = NackMessage {
+            consumer: Consumer::group(Identifier::numeric(1).unwrap()),
+            stream_id: Identifier::numeric(2).unwrap(),
+            topic_id: Identifier::named("test").unwrap(),
+            partition_id: 4,
+            offset: 5,
+            reason: "invalid payload".to_string(),
+        };
+
+        let bytes = command.to_bytes();
+        let mut position = 0;
+        let consumer_kind = ConsumerKind::from_code(bytes[0]).unwrap();
+        let consumer_id = Identifier::from_bytes(bytes.slice(1..)).unwrap();
+        position += 1 + consumer_id.get_size_bytes().as_bytes_usize();
+        let stream_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        let partition_id = u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
+        let offset = u64::from_le_bytes(bytes[position + 4..position + 12].try_into().unwrap());
+        let reason_length = bytes[position + 12] as usize;
+        let reason = from_utf8(&bytes[position + 13..position + 13 + reason_length]).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(consumer_kind, command.consumer.kind);
+        assert_eq!(consumer_id, command.consumer.id);
+        assert_eq!(stream_id, command.stream_id);
+        assert_eq!(topic_id, command.topic_id);
+        assert_eq!(partition_id, command.partition_id);
+        assert_eq!(offset, command.offset);
+        assert_eq!(reason, command.reason);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let consumer = Consumer::new(Identifier::numeric(1).unwrap());
+        let stream_id = Identifier::numeric(2).unwrap();
+        let topic_id = Identifier::numeric(3).unwrap();
+        let partition_id = 4u32;
+        let offset = 5u64;
+        let reason = "timeout";
+
+        let consumer_bytes = consumer.to_bytes();
+        let stream_id_bytes = stream_id.to_bytes();
+        let topic_id_bytes = topic_id.to_bytes();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&consumer_bytes);
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(partition_id);
+        bytes.put_u64_le(offset);
+        bytes.put_u8(reason.len() as u8);
+        bytes.put_slice(reason.as_bytes());
+
+        let command = NackMessage::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.consumer, consumer);
+        assert_eq!(command.stream_id, stream_id);
+        assert_eq!(command.topic_id, topic_id);
+        assert_eq!(command.partition_id, partition_id);
+        assert_eq!(command.offset, offset);
+        assert_eq!(command.reason, reason);
+    }
+
+    #[test]
+    fn should_not_be_valid_given_too_long_reason() {
+        let command = NackMessage {
+            reason: "a".repeat(MAX_REASON_LENGTH + 1),
+            ..Default::default()
+        };
+
+        assert!(command.validate().is_err());
+    }
+}
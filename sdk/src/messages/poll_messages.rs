@@ -3,6 +3,7 @@ use crate::command::{Command, POLL_MESSAGES_CODE};
 use crate::consumer::{Consumer, ConsumerKind};
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::messages::message_filter::MessageFilter;
 use crate::utils::sizeable::Sizeable;
 use crate::utils::timestamp::IggyTimestamp;
 use crate::validatable::Validatable;
@@ -21,6 +22,10 @@ use std::str::FromStr;
 /// - `strategy` - polling strategy which specifies from where to start polling messages.
 /// - `count` - number of messages to poll.
 /// - `auto_commit` - whether to commit offset on the server automatically after polling the messages.
+/// - `isolation_level` - whether to return the messages appended within the transactions which haven't been committed yet.
+/// - `filter` - optional filter on the message headers, only the matching messages are returned.
+/// - `format` - format of the polled messages in the response, which is negotiated by the binary clients.
+#[serde_as]
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct PollMessages {
     /// Consumer which will poll messages. Either regular consumer or consumer group.
@@ -44,6 +49,49 @@ pub struct PollMessages {
     #[serde(default)]
     /// Whether to commit offset on the server automatically after polling the messages.
     pub auto_commit: bool,
+    #[serde(default)]
+    /// Whether to return the messages appended within the transactions which haven't been committed yet.
+    pub isolation_level: IsolationLevel,
+    /// Optional filter on the message headers, only the matching messages are returned.
+    /// The count applies to the matching messages, the server keeps reading until enough of them are found.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(default, skip_serializing_if = "MessageFilter::is_empty")]
+    pub filter: MessageFilter,
+    /// Format of the polled messages in the response, the batches format is supported only by the TCP transport.
+    #[serde(skip)]
+    pub format: PollingFormat,
+}
+
+/// `IsolationLevel` specifies which messages appended within the transactions are returned to the consumer.
+/// It has the following kinds:
+/// - `ReadUncommitted` - all the messages are returned, including the ones from the open and aborted transactions.
+/// - `ReadCommitted` - only the messages outside of transactions and from the committed transactions are returned,
+///   the polling stops at the first message of the oldest open transaction.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Default, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum IsolationLevel {
+    #[default]
+    /// Return all the messages, including the ones from the open and aborted transactions.
+    ReadUncommitted,
+    /// Return only the messages outside of transactions and from the committed transactions.
+    ReadCommitted,
+}
+
+/// `PollingFormat` specifies how the polled messages are laid out in the binary response.
+/// It has the following kinds:
+/// - `Messages` - the messages are serialized one by one, which is the default format.
+/// - `Batches` - the messages are returned as the batches stored in the segment log files, which allows the server
+///   to send them directly from the files to the socket. The server responds with the format byte followed by
+///   either the regular messages or the batches, as it might not be able to send the batches, e.g. when the server-side
+///   encryption is enabled, or the messages haven't been persisted yet.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Default, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum PollingFormat {
+    #[default]
+    /// Return the messages serialized one by one.
+    Messages,
+    /// Return the batches stored in the segment log files, if possible.
+    Batches,
 }
 
 /// `PollingStrategy` specifies from where to start polling messages.
@@ -93,6 +141,9 @@ impl Default for PollMessages {
             strategy: default_strategy(),
             count: default_count(),
             auto_commit: false,
+            isolation_level: IsolationLevel::default(),
+            filter: MessageFilter::default(),
+            format: PollingFormat::default(),
         }
     }
 }
@@ -134,7 +185,7 @@ fn default_count() -> u32 {
 
 impl Validatable<IggyError> for PollMessages {
     fn validate(&self) -> Result<(), IggyError> {
-        Ok(())
+        self.filter.validate()
     }
 }
 
@@ -212,6 +263,62 @@ impl PollingKind {
     }
 }
 
+impl IsolationLevel {
+    /// Returns code of the isolation level.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            IsolationLevel::ReadUncommitted => 0,
+            IsolationLevel::ReadCommitted => 1,
+        }
+    }
+
+    /// Returns isolation level from the specified code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            0 => Ok(IsolationLevel::ReadUncommitted),
+            1 => Ok(IsolationLevel::ReadCommitted),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl PollingFormat {
+    /// Returns code of the polling format.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            PollingFormat::Messages => 1,
+            PollingFormat::Batches => 2,
+        }
+    }
+
+    /// Returns polling format from the specified code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(PollingFormat::Messages),
+            2 => Ok(PollingFormat::Batches),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl Display for PollingFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PollingFormat::Messages => write!(f, "messages"),
+            PollingFormat::Batches => write!(f, "batches"),
+        }
+    }
+}
+
+impl Display for IsolationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IsolationLevel::ReadUncommitted => write!(f, "read_uncommitted"),
+            IsolationLevel::ReadCommitted => write!(f, "read_committed"),
+        }
+    }
+}
+
 impl FromStr for PollingKind {
     type Err = IggyError;
     fn from_str(input: &str) -> Result<Self, Self::Err> {
@@ -248,6 +355,9 @@ impl BytesSerializable for PollMessages {
             &self.strategy,
             self.count,
             self.auto_commit,
+            self.isolation_level,
+            &self.filter,
+            self.format,
         )
     }
 
@@ -295,6 +405,23 @@ impl BytesSerializable for PollMessages {
         );
         let auto_commit = bytes[position + 12];
         let auto_commit = matches!(auto_commit, 1);
+        let isolation_level = match bytes.get(position + 13) {
+            Some(code) => IsolationLevel::from_code(*code)?,
+            None => IsolationLevel::default(),
+        };
+        position += 14;
+        let filter = if bytes.len() > position {
+            MessageFilter::read_from(&bytes, &mut position)?
+        } else {
+            MessageFilter::default()
+        };
+        let format = match bytes.get(position) {
+            Some(code) => PollingFormat::from_code(*code)?,
+            None => PollingFormat::default(),
+        };
+        if bytes.len() > position + 1 {
+            return Err(IggyError::InvalidCommand);
+        }
         let command = PollMessages {
             consumer,
             stream_id,
@@ -303,12 +430,16 @@ impl BytesSerializable for PollMessages {
             strategy,
             count,
             auto_commit,
+            isolation_level,
+            filter,
+            format,
         };
         Ok(command)
     }
 }
 
 // This method is used by the new version of `IggyClient` to serialize `PollMessages` without cloning the args.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn as_bytes(
     stream_id: &Identifier,
     topic_id: &Identifier,
@@ -317,13 +448,16 @@ pub(crate) fn as_bytes(
     strategy: &PollingStrategy,
     count: u32,
     auto_commit: bool,
+    isolation_level: IsolationLevel,
+    filter: &MessageFilter,
+    format: PollingFormat,
 ) -> Bytes {
     let consumer_bytes = consumer.to_bytes();
     let stream_id_bytes = stream_id.to_bytes();
     let topic_id_bytes = topic_id.to_bytes();
     let strategy_bytes = strategy.to_bytes();
     let mut bytes = BytesMut::with_capacity(
-        9 + consumer_bytes.len()
+        10 + consumer_bytes.len()
             + stream_id_bytes.len()
             + topic_id_bytes.len()
             + strategy_bytes.len(),
@@ -343,6 +477,17 @@ pub(crate) fn as_bytes(
     } else {
         bytes.put_u8(0);
     }
+    // The isolation level, filter and format are optional to remain compatible with the servers which don't support them.
+    let has_format = format != PollingFormat::Messages;
+    if isolation_level != IsolationLevel::ReadUncommitted || !filter.is_empty() || has_format {
+        bytes.put_u8(isolation_level.as_code());
+    }
+    if !filter.is_empty() || has_format {
+        bytes.put_slice(&filter.to_bytes());
+    }
+    if has_format {
+        bytes.put_u8(format.as_code());
+    }
 
     bytes.freeze()
 }
@@ -351,14 +496,16 @@ impl Display for PollMessages {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}|{}|{}|{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}",
             self.consumer,
             self.stream_id,
             self.topic_id,
             self.partition_id.unwrap_or(0),
             self.strategy,
             self.count,
-            auto_commit_to_string(self.auto_commit)
+            auto_commit_to_string(self.auto_commit),
+            self.isolation_level,
+            self.filter
         )
     }
 }
@@ -415,6 +562,9 @@ mod tests {
             strategy: PollingStrategy::offset(2),
             count: 3,
             auto_commit: true,
+            isolation_level: IsolationLevel::ReadCommitted,
+            filter: MessageFilter::default(),
+            format: PollingFormat::Messages,
         };
 
         let bytes = command.to_bytes();
@@ -441,6 +591,7 @@ mod tests {
         let count = u32::from_le_bytes(bytes[position + 8..position + 12].try_into().unwrap());
         let auto_commit = bytes[position + 12];
         let auto_commit = matches!(auto_commit, 1);
+        let isolation_level = IsolationLevel::from_code(bytes[position + 13]).unwrap();
 
         assert!(!bytes.is_empty());
         assert_eq!(consumer, command.consumer);
@@ -450,6 +601,7 @@ mod tests {
         assert_eq!(strategy, command.strategy);
         assert_eq!(count, command.count);
         assert_eq!(auto_commit, command.auto_commit);
+        assert_eq!(isolation_level, command.isolation_level);
     }
 
     #[test]
@@ -493,5 +645,58 @@ mod tests {
         assert_eq!(command.strategy, strategy);
         assert_eq!(command.count, count);
         assert_eq!(command.auto_commit, auto_commit);
+        assert_eq!(command.isolation_level, IsolationLevel::ReadUncommitted);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes_with_isolation_level() {
+        let command = PollMessages {
+            isolation_level: IsolationLevel::ReadCommitted,
+            ..PollMessages::default()
+        };
+
+        let command = PollMessages::from_bytes(command.to_bytes());
+        assert!(command.is_ok());
+        assert_eq!(
+            command.unwrap().isolation_level,
+            IsolationLevel::ReadCommitted
+        );
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes_with_filter() {
+        let command = PollMessages {
+            filter: MessageFilter::from_str("eq:tenant:string:acme;exists:trace").unwrap(),
+            ..PollMessages::default()
+        };
+
+        let deserialized = PollMessages::from_bytes(command.to_bytes()).unwrap();
+        assert_eq!(
+            deserialized.isolation_level,
+            IsolationLevel::ReadUncommitted
+        );
+        assert_eq!(deserialized.filter, command.filter);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes_with_batches_format() {
+        let command = PollMessages {
+            format: PollingFormat::Batches,
+            ..PollMessages::default()
+        };
+
+        let deserialized = PollMessages::from_bytes(command.to_bytes()).unwrap();
+        assert_eq!(deserialized.format, PollingFormat::Batches);
+        assert!(deserialized.filter.is_empty());
+
+        let command = PollMessages {
+            filter: MessageFilter::from_str("exists:trace").unwrap(),
+            format: PollingFormat::Batches,
+            ..PollMessages::default()
+        };
+
+        let deserialized = PollMessages::from_bytes(command.to_bytes()).unwrap();
+        assert_eq!(deserialized.format, PollingFormat::Batches);
+        assert_eq!(deserialized.filter, command.filter);
     }
 }
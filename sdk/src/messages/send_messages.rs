@@ -20,6 +20,20 @@ use uuid::Uuid;
 
 const EMPTY_KEY_VALUE: Vec<u8> = vec![];
 
+/// Header containing the ID of the idempotent producer which sent the message.
+pub const PRODUCER_ID_HEADER: &str = "iggy-producer-id";
+/// Header containing the sequence number of the message assigned by the idempotent producer, monotonically increasing per partition.
+pub const PRODUCER_SEQUENCE_HEADER: &str = "iggy-producer-sequence";
+/// Header containing the epoch of the idempotent producer, which is bumped on restart and after a failed send,
+/// so the sequence numbers start over and the messages of the previous epoch are rejected.
+pub const PRODUCER_EPOCH_HEADER: &str = "iggy-producer-epoch";
+/// Header containing the ID of the transaction which the message belongs to, set by the transactional producer.
+pub const TRANSACTION_ID_HEADER: &str = "iggy-transaction-id";
+/// Header containing the key of the message, only the latest message for each key is retained in the compacted topics.
+pub const MESSAGE_KEY_HEADER: &str = "iggy-message-key";
+/// Header marking the message as a tombstone (boolean), which removes the key from the compacted topics.
+pub const TOMBSTONE_HEADER: &str = "iggy-tombstone";
+
 /// `SendMessages` command is used to send messages to a topic in a stream.
 /// It has additional payload:
 /// - `stream_id` - unique stream ID (numeric or name).
@@ -291,6 +305,65 @@ impl Message {
     }
 }
 
+/// The sequence number of the message sent by the idempotent producer within its epoch.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ProducerSequence {
+    pub producer_id: u64,
+    pub epoch: u64,
+    pub sequence: u64,
+}
+
+/// Returns the producer ID, the epoch (0 if missing) and the sequence number from the headers set by the idempotent producer, if present.
+pub fn get_producer_sequence(
+    headers: &HashMap<HeaderKey, HeaderValue>,
+) -> Result<Option<ProducerSequence>, IggyError> {
+    let Some(producer_id) = headers.get(&HeaderKey::new(PRODUCER_ID_HEADER)?) else {
+        return Ok(None);
+    };
+    let Some(sequence) = headers.get(&HeaderKey::new(PRODUCER_SEQUENCE_HEADER)?) else {
+        return Ok(None);
+    };
+    let epoch = match headers.get(&HeaderKey::new(PRODUCER_EPOCH_HEADER)?) {
+        Some(epoch) => epoch.as_uint64()?,
+        None => 0,
+    };
+
+    Ok(Some(ProducerSequence {
+        producer_id: producer_id.as_uint64()?,
+        epoch,
+        sequence: sequence.as_uint64()?,
+    }))
+}
+
+/// Returns the ID of the transaction from the headers set by the transactional producer, if present.
+pub fn get_transaction_id(
+    headers: &HashMap<HeaderKey, HeaderValue>,
+) -> Result<Option<u64>, IggyError> {
+    let Some(transaction_id) = headers.get(&HeaderKey::new(TRANSACTION_ID_HEADER)?) else {
+        return Ok(None);
+    };
+
+    Ok(Some(transaction_id.as_uint64()?))
+}
+
+/// Returns the raw value of the message key header used by the compacted topics, if present.
+pub fn get_message_key(
+    headers: &HashMap<HeaderKey, HeaderValue>,
+) -> Result<Option<Bytes>, IggyError> {
+    Ok(headers
+        .get(&HeaderKey::new(MESSAGE_KEY_HEADER)?)
+        .map(|key| key.value.clone()))
+}
+
+/// Returns `true` if the message is marked as a tombstone.
+pub fn is_tombstone(headers: &HashMap<HeaderKey, HeaderValue>) -> Result<bool, IggyError> {
+    let Some(tombstone) = headers.get(&HeaderKey::new(TOMBSTONE_HEADER)?) else {
+        return Ok(false);
+    };
+
+    tombstone.as_bool()
+}
+
 impl Sizeable for Message {
     fn get_size_bytes(&self) -> IggyByteSize {
         // ID + Length + Payload + Headers
@@ -565,6 +638,83 @@ impl Display for PartitioningKind {
 mod tests {
     use super::*;
 
+    #[test]
+    fn producer_sequence_should_be_read_from_headers() {
+        let mut headers = HashMap::new();
+        assert!(get_producer_sequence(&headers).unwrap().is_none());
+
+        headers.insert(
+            HeaderKey::new(PRODUCER_ID_HEADER).unwrap(),
+            HeaderValue::from_uint64(7).unwrap(),
+        );
+        assert!(get_producer_sequence(&headers).unwrap().is_none());
+
+        headers.insert(
+            HeaderKey::new(PRODUCER_SEQUENCE_HEADER).unwrap(),
+            HeaderValue::from_uint64(42).unwrap(),
+        );
+        assert_eq!(
+            get_producer_sequence(&headers).unwrap(),
+            Some(ProducerSequence {
+                producer_id: 7,
+                epoch: 0,
+                sequence: 42
+            })
+        );
+
+        headers.insert(
+            HeaderKey::new(PRODUCER_EPOCH_HEADER).unwrap(),
+            HeaderValue::from_uint64(3).unwrap(),
+        );
+        assert_eq!(
+            get_producer_sequence(&headers).unwrap(),
+            Some(ProducerSequence {
+                producer_id: 7,
+                epoch: 3,
+                sequence: 42
+            })
+        );
+
+        headers.insert(
+            HeaderKey::new(PRODUCER_SEQUENCE_HEADER).unwrap(),
+            HeaderValue::from_str("42").unwrap(),
+        );
+        assert!(get_producer_sequence(&headers).is_err());
+    }
+
+    #[test]
+    fn transaction_id_should_be_read_from_headers() {
+        let mut headers = HashMap::new();
+        assert!(get_transaction_id(&headers).unwrap().is_none());
+
+        headers.insert(
+            HeaderKey::new(TRANSACTION_ID_HEADER).unwrap(),
+            HeaderValue::from_uint64(3).unwrap(),
+        );
+        assert_eq!(get_transaction_id(&headers).unwrap(), Some(3));
+    }
+
+    #[test]
+    fn message_key_and_tombstone_should_be_read_from_headers() {
+        let mut headers = HashMap::new();
+        assert!(get_message_key(&headers).unwrap().is_none());
+        assert!(!is_tombstone(&headers).unwrap());
+
+        headers.insert(
+            HeaderKey::new(MESSAGE_KEY_HEADER).unwrap(),
+            HeaderValue::from_str("key-1").unwrap(),
+        );
+        headers.insert(
+            HeaderKey::new(TOMBSTONE_HEADER).unwrap(),
+            HeaderValue::from_bool(true).unwrap(),
+        );
+        assert_eq!(
+            get_message_key(&headers).unwrap(),
+            Some(Bytes::from("key-1"))
+        );
+        assert!(is_tombstone(&headers).unwrap());
+    }
+
     #[test]
     fn should_be_serialized_as_bytes() {
         let message_1 = Message::from_str("hello 1").unwrap();
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// `RestoredArchive` represents the result of restoring the stream, topic or partition from the archive.
+/// It consists of the following fields:
+/// - `created_streams_count`: the number of streams recreated from the archived state.
+/// - `created_topics_count`: the number of topics recreated from the archived state.
+/// - `segments_count`: the number of segments restored from the archive.
+/// - `messages_count`: the number of messages in the restored segments.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub struct RestoredArchive {
+    /// The number of streams recreated from the archived state.
+    pub created_streams_count: u32,
+    /// The number of topics recreated from the archived state.
+    pub created_topics_count: u32,
+    /// The number of segments restored from the archive.
+    pub segments_count: u32,
+    /// The number of messages in the restored segments.
+    pub messages_count: u64,
+}
@@ -1,3 +1,4 @@
+use crate::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use serde::{Deserialize, Serialize};
 
 /// `ConsumerGroup` represents the information about a consumer group.
@@ -6,6 +7,8 @@ use serde::{Deserialize, Serialize};
 /// - `name`: the name of the consumer group.
 /// - `partitions_count`: the number of partitions the consumer group is consuming.
 /// - `members_count`: the number of members in the consumer group.
+/// - `assignment_strategy`: the strategy used to assign the partitions to the members.
+/// - `generation`: the generation of the consumer group, incremented on each rebalance.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConsumerGroup {
     /// The unique identifier (numeric) of the consumer group.
@@ -16,6 +19,12 @@ pub struct ConsumerGroup {
     pub partitions_count: u32,
     /// The number of members in the consumer group.
     pub members_count: u32,
+    /// The strategy used to assign the partitions to the members of the consumer group.
+    #[serde(default)]
+    pub assignment_strategy: PartitionAssignmentStrategy,
+    /// The generation of the consumer group, incremented each time the partitions are reassigned.
+    #[serde(default)]
+    pub generation: u32,
 }
 
 /// `ConsumerGroupDetails` represents the detailed information about a consumer group.
@@ -24,6 +33,8 @@ pub struct ConsumerGroup {
 /// - `name`: the name of the consumer group.
 /// - `partitions_count`: the number of partitions the consumer group is consuming.
 /// - `members_count`: the number of members in the consumer group.
+/// - `assignment_strategy`: the strategy used to assign the partitions to the members.
+/// - `generation`: the generation of the consumer group, incremented on each rebalance.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConsumerGroupDetails {
     /// The unique identifier (numeric) of the consumer group.
@@ -34,6 +45,12 @@ pub struct ConsumerGroupDetails {
     pub partitions_count: u32,
     /// The number of members in the consumer group.
     pub members_count: u32,
+    /// The strategy used to assign the partitions to the members of the consumer group.
+    #[serde(default)]
+    pub assignment_strategy: PartitionAssignmentStrategy,
+    /// The generation of the consumer group, incremented each time the partitions are reassigned.
+    #[serde(default)]
+    pub generation: u32,
     /// The collection of members in the consumer group.
     pub members: Vec<ConsumerGroupMember>,
 }
@@ -52,3 +69,18 @@ pub struct ConsumerGroupMember {
     /// The collection of partitions the consumer group member is consuming.
     pub partitions: Vec<u32>,
 }
+
+/// `ConsumerGroupMembership` represents the state of the member right after joining the consumer group.
+/// It consists of the following fields:
+/// - `member_id`: the unique identifier (numeric) of the consumer group member.
+/// - `generation`: the generation of the consumer group after the member has joined.
+/// - `partitions`: the collection of partitions assigned to the member.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsumerGroupMembership {
+    /// The unique identifier (numeric) of the consumer group member.
+    pub member_id: u32,
+    /// The generation of the consumer group after the member has joined.
+    pub generation: u32,
+    /// The collection of partitions assigned to the member.
+    pub partitions: Vec<u32>,
+}
@@ -20,6 +20,7 @@ pub const POLLED_MESSAGE_METADATA: u32 = 8 + 1 + 8 + 4;
 /// - `partition_id`: the identifier of the partition.
 /// - `current_offset`: the current offset of the partition.
 /// - `messages`: the collection of messages.
+/// - `generation`: the generation of the consumer group, if polled by the consumer group member.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PolledMessages {
     /// The identifier of the partition. If it's '0', then there's no partition assigned to the consumer group member.
@@ -28,6 +29,9 @@ pub struct PolledMessages {
     pub current_offset: u64,
     /// The collection of messages.
     pub messages: Vec<PolledMessage>,
+    /// The generation of the consumer group, available only when polled by the consumer group member.
+    #[serde(default)]
+    pub generation: Option<u32>,
 }
 
 /// The single message that is polled from the partition.
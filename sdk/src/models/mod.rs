@@ -1,3 +1,4 @@
+pub mod archive;
 pub mod client_info;
 pub mod consumer_group;
 pub mod consumer_offset_info;
@@ -7,6 +8,8 @@ pub mod messages;
 pub mod partition;
 pub mod permissions;
 pub mod personal_access_token;
+pub mod quota;
+pub mod role;
 pub mod snapshot;
 pub mod stats;
 pub mod stream;
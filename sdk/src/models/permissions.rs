@@ -37,11 +37,18 @@ pub struct GlobalPermissions {
     /// - delete_user
     /// - update_permissions
     /// - change_password
+    /// - create_role
+    /// - update_role
+    /// - delete_role
+    /// - assign_role
+    /// - unassign_role
     pub manage_users: bool,
 
     /// `read_users` permission allows to invoke the following methods:
     /// - get_user
     /// - get_users
+    /// - get_role
+    /// - get_roles
     pub read_users: bool,
 
     /// `manage_streams` permission allows to manage the streams and includes all the permissions of `read_streams`.
@@ -159,6 +166,58 @@ impl Permissions {
             streams: None,
         }
     }
+
+    /// Merges the other permissions into these ones, so that each permission granted by either of them is granted.
+    pub fn merge(&mut self, other: &Permissions) {
+        self.global.merge(&other.global);
+        if let Some(other_streams) = &other.streams {
+            let streams = self.streams.get_or_insert_with(AHashMap::new);
+            for (stream_id, other_stream) in other_streams {
+                streams.entry(*stream_id).or_default().merge(other_stream);
+            }
+        }
+    }
+}
+
+impl GlobalPermissions {
+    fn merge(&mut self, other: &GlobalPermissions) {
+        self.manage_servers |= other.manage_servers;
+        self.read_servers |= other.read_servers;
+        self.manage_users |= other.manage_users;
+        self.read_users |= other.read_users;
+        self.manage_streams |= other.manage_streams;
+        self.read_streams |= other.read_streams;
+        self.manage_topics |= other.manage_topics;
+        self.read_topics |= other.read_topics;
+        self.poll_messages |= other.poll_messages;
+        self.send_messages |= other.send_messages;
+    }
+}
+
+impl StreamPermissions {
+    fn merge(&mut self, other: &StreamPermissions) {
+        self.manage_stream |= other.manage_stream;
+        self.read_stream |= other.read_stream;
+        self.manage_topics |= other.manage_topics;
+        self.read_topics |= other.read_topics;
+        self.poll_messages |= other.poll_messages;
+        self.send_messages |= other.send_messages;
+        if let Some(other_topics) = &other.topics {
+            let topics = self.topics.get_or_insert_with(AHashMap::new);
+            for (topic_id, other_topic) in other_topics {
+                topics.entry(*topic_id).or_default().merge(other_topic);
+            }
+        }
+    }
+}
+
+impl TopicPermissions {
+    fn merge(&mut self, other: &TopicPermissions) {
+        self.manage_topic |= other.manage_topic;
+        self.read_topic |= other.read_topic;
+        self.poll_messages |= other.poll_messages;
+        self.send_messages |= other.send_messages;
+    }
 }
 
 impl Display for Permissions {
@@ -414,4 +473,72 @@ mod tests {
 
         assert_eq!(permissions, deserialized_permissions);
     }
+
+    #[test]
+    fn merged_permissions_should_grant_permissions_of_both() {
+        let mut permissions = Permissions {
+            global: GlobalPermissions {
+                read_streams: true,
+                ..Default::default()
+            },
+            streams: Some(AHashMap::from([(
+                1,
+                StreamPermissions {
+                    poll_messages: true,
+                    topics: Some(AHashMap::from([(
+                        1,
+                        TopicPermissions {
+                            read_topic: true,
+                            ..Default::default()
+                        },
+                    )])),
+                    ..Default::default()
+                },
+            )])),
+        };
+        let other = Permissions {
+            global: GlobalPermissions {
+                read_users: true,
+                ..Default::default()
+            },
+            streams: Some(AHashMap::from([
+                (
+                    1,
+                    StreamPermissions {
+                        send_messages: true,
+                        topics: Some(AHashMap::from([(
+                            1,
+                            TopicPermissions {
+                                manage_topic: true,
+                                ..Default::default()
+                            },
+                        )])),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    2,
+                    StreamPermissions {
+                        read_stream: true,
+                        ..Default::default()
+                    },
+                ),
+            ])),
+        };
+
+        permissions.merge(&other);
+
+        assert!(permissions.global.read_streams);
+        assert!(permissions.global.read_users);
+        assert!(!permissions.global.manage_users);
+        let streams = permissions.streams.unwrap();
+        let stream = streams.get(&1).unwrap();
+        assert!(stream.poll_messages);
+        assert!(stream.send_messages);
+        assert!(!stream.manage_stream);
+        let topic = stream.topics.as_ref().unwrap().get(&1).unwrap();
+        assert!(topic.read_topic);
+        assert!(topic.manage_topic);
+        assert!(streams.get(&2).unwrap().read_stream);
+    }
 }
@@ -0,0 +1,110 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::error::IggyError;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+const QUOTA_SIZE: usize = 8 + 4 + 4 + 8 + 8;
+
+/// `Quota` limits the resources which can be used by a stream or a user.
+/// The user quota is applied to the sum of the resources of all the streams created by the user,
+/// and to the messages produced and consumed by the user, regardless of the stream.
+/// Each limit set to 0 means that the resource is unlimited.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Quota {
+    /// Maximum size of the stored messages in bytes.
+    #[serde(default)]
+    pub max_size: u64,
+    /// Maximum number of the topics.
+    #[serde(default)]
+    pub max_topics: u32,
+    /// Maximum number of the partitions, across all the topics.
+    #[serde(default)]
+    pub max_partitions: u32,
+    /// Maximum number of the bytes which can be appended per second.
+    #[serde(default)]
+    pub max_produce_bytes_per_second: u64,
+    /// Maximum number of the bytes which can be polled per second.
+    #[serde(default)]
+    pub max_consume_bytes_per_second: u64,
+}
+
+impl Quota {
+    /// Returns true if none of the resources is limited.
+    pub fn is_unlimited(&self) -> bool {
+        *self == Quota::default()
+    }
+}
+
+impl BytesSerializable for Quota {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(QUOTA_SIZE);
+        bytes.put_u64_le(self.max_size);
+        bytes.put_u32_le(self.max_topics);
+        bytes.put_u32_le(self.max_partitions);
+        bytes.put_u64_le(self.max_produce_bytes_per_second);
+        bytes.put_u64_le(self.max_consume_bytes_per_second);
+        bytes.freeze()
+    }
+
+    fn from_bytes(mut bytes: Bytes) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        if bytes.len() != QUOTA_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(Quota {
+            max_size: bytes.get_u64_le(),
+            max_topics: bytes.get_u32_le(),
+            max_partitions: bytes.get_u32_le(),
+            max_produce_bytes_per_second: bytes.get_u64_le(),
+            max_consume_bytes_per_second: bytes.get_u64_le(),
+        })
+    }
+}
+
+impl Display for Quota {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "max_size: {}, max_topics: {}, max_partitions: {}, max_produce_bytes_per_second: {}, max_consume_bytes_per_second: {}",
+            self.max_size,
+            self.max_topics,
+            self.max_partitions,
+            self.max_produce_bytes_per_second,
+            self.max_consume_bytes_per_second
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_and_deserialized() {
+        let quota = Quota {
+            max_size: 1_000_000,
+            max_topics: 10,
+            max_partitions: 100,
+            max_produce_bytes_per_second: 10_000,
+            max_consume_bytes_per_second: 20_000,
+        };
+
+        let bytes = quota.to_bytes();
+        assert_eq!(bytes.len(), QUOTA_SIZE);
+        assert_eq!(Quota::from_bytes(bytes).unwrap(), quota);
+    }
+
+    #[test]
+    fn should_be_unlimited_by_default() {
+        assert!(Quota::default().is_unlimited());
+        assert!(!Quota {
+            max_topics: 1,
+            ..Default::default()
+        }
+        .is_unlimited());
+    }
+}
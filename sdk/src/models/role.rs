@@ -0,0 +1,46 @@
+use crate::models::permissions::Permissions;
+use crate::models::user_info::UserId;
+use crate::utils::timestamp::IggyTimestamp;
+use serde::{Deserialize, Serialize};
+
+/// `RoleId` represents the unique identifier (numeric) of the role.
+pub type RoleId = u32;
+
+/// `RoleInfo` represents the basic information about the role.
+/// It consists of the following fields:
+/// - `id`: the unique identifier (numeric) of the role.
+/// - `created_at`: the timestamp when the role was created.
+/// - `name`: the unique name of the role.
+/// - `users_count`: the number of users the role is assigned to.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoleInfo {
+    /// The unique identifier (numeric) of the role.
+    pub id: RoleId,
+    /// The timestamp when the role was created.
+    pub created_at: IggyTimestamp,
+    /// The unique name of the role.
+    pub name: String,
+    /// The number of users the role is assigned to.
+    pub users_count: u32,
+}
+
+/// `RoleInfoDetails` represents the detailed information about the role.
+/// It consists of the following fields:
+/// - `id`: the unique identifier (numeric) of the role.
+/// - `created_at`: the timestamp when the role was created.
+/// - `name`: the unique name of the role.
+/// - `permissions`: the optional permissions granted by the role.
+/// - `users`: the IDs of the users the role is assigned to.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoleInfoDetails {
+    /// The unique identifier (numeric) of the role.
+    pub id: RoleId,
+    /// The timestamp when the role was created.
+    pub created_at: IggyTimestamp,
+    /// The unique name of the role.
+    pub name: String,
+    /// The optional permissions granted by the role.
+    pub permissions: Option<Permissions>,
+    /// The IDs of the users the role is assigned to.
+    pub users: Vec<UserId>,
+}
@@ -1,5 +1,6 @@
 use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::models::partition::Partition;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::utils::byte_size::IggyByteSize;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::timestamp::IggyTimestamp;
@@ -15,6 +16,7 @@ use serde::{Deserialize, Serialize};
 /// - `message_expiry`: the expiry of the messages in the topic.
 /// - `max_topic_size`: the maximum size of the topic.
 /// - `replication_factor`: replication factor for the topic.
+/// - `cleanup_policy`: the cleanup policy of the topic, either delete or compact.
 /// - `messages_count`: the total number of messages in the topic.
 /// - `partitions_count`: the total number of partitions in the topic.
 #[derive(Debug, Serialize, Deserialize)]
@@ -31,11 +33,16 @@ pub struct Topic {
     pub message_expiry: IggyExpiry,
     /// Compression algorithm for the topic.
     pub compression_algorithm: CompressionAlgorithm,
+    /// Compression level for the topic, if `None` then the algorithm's default level is used.
+    pub compression_level: Option<u8>,
     /// The optional maximum size of the topic.
     /// Can't be lower than segment size in the config.
     pub max_topic_size: MaxTopicSize,
     /// Replication factor for the topic.
     pub replication_factor: u8,
+    /// Cleanup policy for the topic, compacted topics retain only the latest message per key.
+    #[serde(default)]
+    pub cleanup_policy: CleanupPolicy,
     /// The total number of messages in the topic.
     pub messages_count: u64,
     /// The total number of partitions in the topic.
@@ -51,6 +58,7 @@ pub struct Topic {
 /// - `message_expiry`: the expiry of the messages in the topic.
 /// - `max_topic_size`: the maximum size of the topic.
 /// - `replication_factor`: replication factor for the topic.
+/// - `cleanup_policy`: the cleanup policy of the topic, either delete or compact.
 /// - `messages_count`: the total number of messages in the topic.
 /// - `partitions_count`: the total number of partitions in the topic.
 /// - `partitions`: the collection of partitions in the topic.
@@ -68,11 +76,16 @@ pub struct TopicDetails {
     pub message_expiry: IggyExpiry,
     /// Compression algorithm for the topic.
     pub compression_algorithm: CompressionAlgorithm,
+    /// Compression level for the topic, if `None` then the algorithm's default level is used.
+    pub compression_level: Option<u8>,
     /// The optional maximum size of the topic.
     /// Can't be lower than segment size in the config.
     pub max_topic_size: MaxTopicSize,
     /// Replication factor for the topic.
     pub replication_factor: u8,
+    /// Cleanup policy for the topic, compacted topics retain only the latest message per key.
+    #[serde(default)]
+    pub cleanup_policy: CleanupPolicy,
     /// The total number of messages in the topic.
     pub messages_count: u64,
     /// The total number of partitions in the topic.
@@ -2,6 +2,7 @@ use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::messages::send_messages::Message;
 use std::fmt::Debug;
+use twox_hash::XxHash32;
 
 /// The trait represent the logic responsible for calculating the partition ID and is used by the `IggyClient`.
 /// This might be especially useful when the partition ID is not constant and might be calculated based on the stream ID, topic ID and other parameters.
@@ -13,3 +14,14 @@ pub trait Partitioner: Send + Sync + Debug {
         messages: &[Message],
     ) -> Result<u32, IggyError>;
 }
+
+/// Calculates the partition ID (starting from 1) for the messages key, the same way as the server does
+/// for the messages sent with the messages key partitioning.
+pub fn calculate_partition_id_by_messages_key(messages_key: &[u8], partitions_count: u32) -> u32 {
+    let messages_key_hash = XxHash32::oneshot(0, messages_key);
+    let partition_id = messages_key_hash % partitions_count;
+    if partition_id == 0 {
+        return partitions_count;
+    }
+    partition_id
+}
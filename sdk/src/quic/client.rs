@@ -1,19 +1,23 @@
 use crate::binary::binary_client::BinaryClient;
-use crate::binary::{BinaryTransport, ClientState};
+use crate::binary::{self, BinaryTransport, ClientState};
 use crate::client::{AutoLogin, Client, Credentials, PersonalAccessTokenClient, UserClient};
 use crate::command::Command;
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
 use crate::quic::config::QuicClientConfig;
 use crate::utils::duration::IggyDuration;
+use crate::utils::server_address::ServerAddresses;
 use crate::utils::timestamp::IggyTimestamp;
+use crate::utils::tls;
 use async_broadcast::{broadcast, Receiver, Sender};
 use async_trait::async_trait;
 use bytes::Bytes;
 use quinn::crypto::rustls::QuicClientConfig as QuinnQuicClientConfig;
-use quinn::{ClientConfig, Connection, Endpoint, IdleTimeout, RecvStream, VarInt};
+use quinn::{
+    ClientConfig, Connection, Endpoint, IdleTimeout, ReadError, ReadToEndError, RecvStream, VarInt,
+    WriteError,
+};
 use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
-use rustls::crypto::CryptoProvider;
 use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
 use rustls::{DigitallySignedStruct, Error, SignatureScheme};
 use std::net::SocketAddr;
@@ -33,7 +37,7 @@ pub struct QuicClient {
     pub(crate) endpoint: Endpoint,
     pub(crate) connection: Mutex<Option<Connection>>,
     pub(crate) config: Arc<QuicClientConfig>,
-    pub(crate) server_address: SocketAddr,
+    server_addresses: ServerAddresses<SocketAddr>,
     pub(crate) state: Mutex<ClientState>,
     events: (Sender<DiagnosticEvent>, Receiver<DiagnosticEvent>),
     connected_at: Mutex<Option<IggyTimestamp>>,
@@ -104,7 +108,8 @@ impl BinaryTransport for QuicClient {
         self.disconnect().await?;
         info!(
             "Reconnecting to the server: {}, by client: {}",
-            self.config.server_address, self.config.client_address
+            self.server_addresses.current(),
+            self.config.client_address
         );
         self.connect().await?;
         self.send_raw(code, payload).await
@@ -144,14 +149,18 @@ impl QuicClient {
 
     /// Create a new QUIC client for the provided configuration.
     pub fn create(config: Arc<QuicClientConfig>) -> Result<Self, IggyError> {
-        let server_address = config
-            .server_address
-            .parse::<SocketAddr>()
-            .map_err(|error| {
-                error!("Invalid server address: {error}");
-                IggyError::InvalidServerAddress
-            })?;
-        let client_address = if server_address.is_ipv6()
+        let server_addresses = ServerAddresses::parse(
+            &config.server_address,
+            &config.server_addresses,
+            config.address_selection,
+            |address| {
+                address.parse::<SocketAddr>().map_err(|error| {
+                    error!("Invalid server address: {address}. {error}");
+                    IggyError::InvalidServerAddress
+                })
+            },
+        )?;
+        let client_address = if server_addresses.current().is_ipv6()
             && config.client_address == QuicClientConfig::default().client_address
         {
             "[::1]:0"
@@ -177,7 +186,7 @@ impl QuicClient {
         Ok(Self {
             config,
             endpoint,
-            server_address,
+            server_addresses,
             connection: Mutex::new(None),
             state: Mutex::new(ClientState::Disconnected),
             events: broadcast(1000),
@@ -191,7 +200,10 @@ impl QuicClient {
             .await
             .map_err(|error| {
                 error!("Failed to read response data: {error}");
-                IggyError::QuicError
+                match error {
+                    ReadToEndError::Read(ReadError::ConnectionLost(_)) => IggyError::Disconnected,
+                    _ => IggyError::QuicError,
+                }
             })?;
         if buffer.is_empty() {
             return Err(IggyError::EmptyResponse);
@@ -261,61 +273,66 @@ impl QuicClient {
         }
 
         let mut retry_count = 0;
-        let connection;
-        let remote_address;
-        loop {
-            info!(
-                "{NAME} client is connecting to server: {}...",
-                self.config.server_address
-            );
-            let connection_result = self
-                .endpoint
-                .connect(self.server_address, &self.config.server_name)
-                .unwrap()
-                .await;
-
-            if connection_result.is_err() {
-                error!(
-                    "Failed to connect to server: {}",
-                    self.config.server_address
-                );
-                if !self.config.reconnection.enabled {
-                    warn!("Automatic reconnection is disabled.");
-                    return Err(IggyError::CannotEstablishConnection);
+        let connection = loop {
+            let mut connection = None;
+            for (index, server_address) in self.server_addresses.candidates() {
+                info!("{NAME} client is connecting to server: {server_address}...");
+                let connecting = match self
+                    .endpoint
+                    .connect(*server_address, &self.config.server_name)
+                {
+                    Ok(connecting) => connecting,
+                    Err(error) => {
+                        error!("Failed to connect to server: {server_address}. {error}");
+                        continue;
+                    }
+                };
+                match connecting.await {
+                    Ok(established) => {
+                        self.server_addresses.set_current(index);
+                        connection = Some(established);
+                        break;
+                    }
+                    Err(error) => {
+                        error!("Failed to connect to server: {server_address}. {error}");
+                    }
                 }
+            }
 
-                let unlimited_retries = self.config.reconnection.max_retries.is_none();
-                let max_retries = self.config.reconnection.max_retries.unwrap_or_default();
-                let max_retries_str =
-                    if let Some(max_retries) = self.config.reconnection.max_retries {
-                        max_retries.to_string()
-                    } else {
-                        "unlimited".to_string()
-                    };
-
-                let interval_str = self.config.reconnection.interval.as_human_time_string();
-                if unlimited_retries || retry_count < max_retries {
-                    retry_count += 1;
-                    info!(
-                        "Retrying to connect to server ({retry_count}/{max_retries_str}): {} in: {interval_str}",
-                        self.config.server_address,
-                    );
-                    sleep(self.config.reconnection.interval.get_duration()).await;
-                    continue;
-                }
+            if let Some(connection) = connection {
+                break connection;
+            }
 
+            if !self.config.reconnection.enabled {
+                warn!("Automatic reconnection is disabled.");
                 self.set_state(ClientState::Disconnected).await;
-                self.publish_event(DiagnosticEvent::Disconnected).await;
                 return Err(IggyError::CannotEstablishConnection);
             }
 
-            connection = connection_result.map_err(|error| {
-                error!("Failed to establish QUIC connection: {error}");
-                IggyError::CannotEstablishConnection
-            })?;
-            remote_address = connection.remote_address();
-            break;
-        }
+            let unlimited_retries = self.config.reconnection.max_retries.is_none();
+            let max_retries = self.config.reconnection.max_retries.unwrap_or_default();
+            let max_retries_str = if let Some(max_retries) = self.config.reconnection.max_retries {
+                max_retries.to_string()
+            } else {
+                "unlimited".to_string()
+            };
+
+            let interval_str = self.config.reconnection.interval.as_human_time_string();
+            if unlimited_retries || retry_count < max_retries {
+                retry_count += 1;
+                info!(
+                    "Retrying to connect to server ({retry_count}/{max_retries_str}): {} in: {interval_str}",
+                    self.server_addresses.current(),
+                );
+                sleep(self.config.reconnection.interval.get_duration()).await;
+                continue;
+            }
+
+            self.set_state(ClientState::Disconnected).await;
+            self.publish_event(DiagnosticEvent::Disconnected).await;
+            return Err(IggyError::CannotEstablishConnection);
+        };
+        let remote_address = connection.remote_address();
 
         let now = IggyTimestamp::now();
         info!("{NAME} client has connected to server: {remote_address} at {now}",);
@@ -327,6 +344,14 @@ impl QuicClient {
         match &self.config.auto_login {
             AutoLogin::Disabled => {
                 info!("Automatic sign-in is disabled.");
+                if self.config.tls_cert_file.is_some()
+                    && binary::authenticate_with_client_certificate(self).await
+                {
+                    info!(
+                        "{NAME} client: {} has been authenticated with the client certificate.",
+                        self.config.client_address
+                    );
+                }
                 Ok(())
             }
             AutoLogin::Enabled(credentials) => {
@@ -338,13 +363,11 @@ impl QuicClient {
                 match credentials {
                     Credentials::UsernamePassword(username, password) => {
                         self.login_user(username, password).await?;
-                        self.publish_event(DiagnosticEvent::SignedIn).await;
                         info!("{NAME} client: {} has signed in with the user credentials, username: {username}", self.config.client_address);
                         Ok(())
                     }
                     Credentials::PersonalAccessToken(token) => {
                         self.login_with_personal_access_token(token).await?;
-                        self.publish_event(DiagnosticEvent::SignedIn).await;
                         info!(
                             "{NAME} client: {} has signed in with a personal access token.",
                             self.config.client_address
@@ -418,27 +441,30 @@ impl QuicClient {
             _ => {}
         }
 
-        let connection = self.connection.lock().await;
-        if let Some(connection) = connection.as_ref() {
+        // The connection is cloned, so the lock isn't held for the whole round trip, as every request
+        // uses its own bidirectional stream, which allows multiple requests in flight over the same connection.
+        let connection = self.connection.lock().await.clone();
+        if let Some(connection) = connection {
             let payload_length = payload.len() + REQUEST_INITIAL_BYTES_LENGTH;
+            // The lost connection is reported as disconnected, so the client can reconnect (to the next server).
             let (mut send, mut recv) = connection.open_bi().await.map_err(|error| {
                 error!("Failed to open a bidirectional stream: {error}");
-                IggyError::QuicError
+                IggyError::Disconnected
             })?;
             trace!("Sending a QUIC request with code: {code}");
             send.write_all(&(payload_length as u32).to_le_bytes())
                 .await
                 .map_err(|error| {
                     error!("Failed to write payload length: {error}");
-                    IggyError::QuicError
+                    map_write_error(error)
                 })?;
             send.write_all(&code.to_le_bytes()).await.map_err(|error| {
                 error!("Failed to write payload code: {error}");
-                IggyError::QuicError
+                map_write_error(error)
             })?;
             send.write_all(&payload).await.map_err(|error| {
                 error!("Failed to write payload: {error}");
-                IggyError::QuicError
+                map_write_error(error)
             })?;
             send.finish().map_err(|error| {
                 error!("Failed to finish sending data: {error}");
@@ -453,6 +479,13 @@ impl QuicClient {
     }
 }
 
+fn map_write_error(error: WriteError) -> IggyError {
+    match error {
+        WriteError::ConnectionLost(_) => IggyError::Disconnected,
+        _ => IggyError::QuicError,
+    }
+}
+
 fn configure(config: &QuicClientConfig) -> Result<ClientConfig, IggyError> {
     let max_concurrent_bidi_streams = VarInt::try_from(config.max_concurrent_bidi_streams);
     if max_concurrent_bidi_streams.is_err() {
@@ -488,20 +521,34 @@ fn configure(config: &QuicClientConfig) -> Result<ClientConfig, IggyError> {
         transport.max_idle_timeout(Some(max_idle_timeout.unwrap()));
     }
 
-    if CryptoProvider::get_default().is_none() {
-        if let Err(e) = rustls::crypto::ring::default_provider().install_default() {
-            warn!("Failed to install rustls crypto provider. Error: {:?}. This may be normal if another thread installed it first.", e);
-        }
-    }
-    let mut client_config = match config.validate_certificate {
-        true => ClientConfig::with_platform_verifier(),
-        false => {
-            match QuinnQuicClientConfig::try_from(
-                rustls::ClientConfig::builder()
-                    .dangerous()
-                    .with_custom_certificate_verifier(SkipServerVerification::new())
-                    .with_no_client_auth(),
-            ) {
+    tls::install_crypto_provider();
+    let mut client_config = match (
+        config.validate_certificate,
+        &config.tls_cert_file,
+        &config.tls_key_file,
+    ) {
+        (true, None, _) | (true, _, None) => ClientConfig::with_platform_verifier(),
+        (validate_certificate, cert_file, key_file) => {
+            let verifier: Arc<dyn ServerCertVerifier> = match validate_certificate {
+                true => Arc::new(rustls_platform_verifier::Verifier::new()),
+                false => SkipServerVerification::new(),
+            };
+            let builder = rustls::ClientConfig::builder()
+                .dangerous()
+                .with_custom_certificate_verifier(verifier);
+            let crypto = match (cert_file, key_file) {
+                (Some(cert_file), Some(key_file)) => {
+                    let (certificates, key) = tls::load_client_certificate(cert_file, key_file)?;
+                    builder
+                        .with_client_auth_cert(certificates, key)
+                        .map_err(|error| {
+                            error!("Failed to use the client certificate: {cert_file}. {error}");
+                            IggyError::InvalidTlsCertificate
+                        })?
+                }
+                _ => builder.with_no_client_auth(),
+            };
+            match QuinnQuicClientConfig::try_from(crypto) {
                 Ok(config) => ClientConfig::new(Arc::new(config)),
                 Err(error) => {
                     error!("Failed to create QUIC client configuration: {error}");
@@ -1,5 +1,6 @@
 use crate::client::AutoLogin;
 use crate::utils::duration::IggyDuration;
+use crate::utils::server_address::ServerAddressSelection;
 use std::str::FromStr;
 
 /// Configuration for the QUIC client.
@@ -9,6 +10,10 @@ pub struct QuicClientConfig {
     pub client_address: String,
     /// The address of the QUIC server to connect to.
     pub server_address: String,
+    /// The addresses of the multiple servers, e.g. the primary and the standby one, used instead of `server_address` when not empty.
+    pub server_addresses: Vec<String>,
+    /// The policy of selecting the server to connect to, when the multiple server addresses are configured.
+    pub address_selection: ServerAddressSelection,
     /// The server name to use.
     pub server_name: String,
     /// Whether to automatically login user after establishing connection.
@@ -33,6 +38,10 @@ pub struct QuicClientConfig {
     pub max_idle_timeout: u64,
     /// Whether to validate the server certificate.
     pub validate_certificate: bool,
+    /// The path to the certificate file (PEM) presented to the server, which verifies the client certificates.
+    pub tls_cert_file: Option<String>,
+    /// The path to the private key file (PEM) of the client certificate.
+    pub tls_key_file: Option<String>,
     /// Interval of heartbeats sent by the client
     pub heartbeat_interval: IggyDuration,
 }
@@ -61,6 +70,8 @@ impl Default for QuicClientConfig {
         QuicClientConfig {
             client_address: "127.0.0.1:0".to_string(),
             server_address: "127.0.0.1:8080".to_string(),
+            server_addresses: Vec::new(),
+            address_selection: ServerAddressSelection::default(),
             server_name: "localhost".to_string(),
             auto_login: AutoLogin::Disabled,
             heartbeat_interval: IggyDuration::from_str("5s").unwrap(),
@@ -74,6 +85,8 @@ impl Default for QuicClientConfig {
             keep_alive_interval: 5000,
             max_idle_timeout: 10000,
             validate_certificate: false,
+            tls_cert_file: None,
+            tls_key_file: None,
         }
     }
 }
@@ -83,6 +96,8 @@ impl Default for QuicClientConfig {
 /// Allows configuring the QUIC client with custom settings or using defaults:
 /// - `client_address`: Default is "127.0.0.1:0" (binds to any available port).
 /// - `server_address`: Default is "127.0.0.1:8080".
+/// - `server_addresses`: Default is empty.
+/// - `address_selection`: Default is ServerAddressSelection::Ordered.
 /// - `server_name`: Default is "localhost".
 /// - `auto_login`: Default is AutoLogin::Disabled.
 /// - `reconnection`: Default is enabled unlimited retries and 1 second interval.
@@ -95,6 +110,7 @@ impl Default for QuicClientConfig {
 /// - `keep_alive_interval`: Default is 5000 milliseconds.
 /// - `max_idle_timeout`: Default is 10,000 milliseconds.
 /// - `validate_certificate`: Default is false (certificate validation is disabled).
+/// - `tls_cert_file` and `tls_key_file`: Default is None.
 #[derive(Debug, Default)]
 pub struct QuicClientConfigBuilder {
     config: QuicClientConfig,
@@ -118,6 +134,18 @@ impl QuicClientConfigBuilder {
         self
     }
 
+    /// Sets the addresses of the multiple servers, used instead of the single server address.
+    pub fn with_server_addresses(mut self, server_addresses: Vec<String>) -> Self {
+        self.config.server_addresses = server_addresses;
+        self
+    }
+
+    /// Sets the policy of selecting the server to connect to, when the multiple server addresses are configured.
+    pub fn with_address_selection(mut self, address_selection: ServerAddressSelection) -> Self {
+        self.config.address_selection = address_selection;
+        self
+    }
+
     /// Sets the auto sign in during connection.
     pub fn with_auto_sign_in(mut self, auto_sign_in: AutoLogin) -> Self {
         self.config.auto_login = auto_sign_in;
@@ -201,6 +229,14 @@ impl QuicClientConfigBuilder {
         self
     }
 
+    /// Sets the paths to the client certificate and its private key,
+    /// which are presented to the server requiring the client certificates.
+    pub fn with_tls_client_certificate(mut self, cert_file: String, key_file: String) -> Self {
+        self.config.tls_cert_file = Some(cert_file);
+        self.config.tls_key_file = Some(key_file);
+        self
+    }
+
     /// Sets the heartbeat interval. Defaults to 5000ms.
     pub fn with_heartbeat_interval(mut self, interval: IggyDuration) -> Self {
         self.config.heartbeat_interval = interval;
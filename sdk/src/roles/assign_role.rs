@@ -0,0 +1,102 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, ASSIGN_ROLE_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `AssignRole` command is used to assign a role to a user, who is then granted the role's permissions in addition to its own.
+/// It has additional payload:
+/// - `role_id` - unique role ID (numeric or name).
+/// - `user_id` - unique user ID (numeric or name).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct AssignRole {
+    /// Unique role ID (numeric or name).
+    #[serde(skip)]
+    pub role_id: Identifier,
+    /// Unique user ID (numeric or name).
+    #[serde(skip)]
+    pub user_id: Identifier,
+}
+
+impl Command for AssignRole {
+    fn code(&self) -> u32 {
+        ASSIGN_ROLE_CODE
+    }
+}
+
+impl Validatable<IggyError> for AssignRole {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for AssignRole {
+    fn to_bytes(&self) -> Bytes {
+        let role_id_bytes = self.role_id.to_bytes();
+        let user_id_bytes = self.user_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(role_id_bytes.len() + user_id_bytes.len());
+        bytes.put_slice(&role_id_bytes);
+        bytes.put_slice(&user_id_bytes);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<AssignRole, IggyError> {
+        if bytes.len() < 6 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let role_id = Identifier::from_bytes(bytes.clone())?;
+        let position = role_id.get_size_bytes().as_bytes_usize();
+        let user_id = Identifier::from_bytes(bytes.slice(position..))?;
+        let command = AssignRole { role_id, user_id };
+        Ok(command)
+    }
+}
+
+impl Display for AssignRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.role_id, self.user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = AssignRole {
+            role_id: Identifier::named("auditors").unwrap(),
+            user_id: Identifier::numeric(2).unwrap(),
+        };
+
+        let bytes = command.to_bytes();
+        let role_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        let position = role_id.get_size_bytes().as_bytes_usize();
+        let user_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(role_id, command.role_id);
+        assert_eq!(user_id, command.user_id);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let role_id = Identifier::named("auditors").unwrap();
+        let user_id = Identifier::numeric(2).unwrap();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&role_id.to_bytes());
+        bytes.put_slice(&user_id.to_bytes());
+
+        let command = AssignRole::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.role_id, role_id);
+        assert_eq!(command.user_id, user_id);
+    }
+}
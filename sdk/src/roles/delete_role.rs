@@ -0,0 +1,81 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, DELETE_ROLE_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::validatable::Validatable;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `DeleteRole` command is used to delete a role by unique ID, which also unassigns it from all its users.
+/// It has additional payload:
+/// - `role_id` - unique role ID (numeric or name).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct DeleteRole {
+    /// Unique role ID (numeric or name).
+    #[serde(skip)]
+    pub role_id: Identifier,
+}
+
+impl Command for DeleteRole {
+    fn code(&self) -> u32 {
+        DELETE_ROLE_CODE
+    }
+}
+
+impl Validatable<IggyError> for DeleteRole {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for DeleteRole {
+    fn to_bytes(&self) -> Bytes {
+        self.role_id.to_bytes()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<DeleteRole, IggyError> {
+        if bytes.len() < 3 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let role_id = Identifier::from_bytes(bytes)?;
+        let command = DeleteRole { role_id };
+        Ok(command)
+    }
+}
+
+impl Display for DeleteRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.role_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = DeleteRole {
+            role_id: Identifier::numeric(1).unwrap(),
+        };
+
+        let bytes = command.to_bytes();
+        let role_id = Identifier::from_bytes(bytes.clone()).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(role_id, command.role_id);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let role_id = Identifier::numeric(1).unwrap();
+        let bytes = role_id.to_bytes();
+        let command = DeleteRole::from_bytes(bytes);
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.role_id, role_id);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, GET_ROLES_CODE};
+use crate::error::IggyError;
+use crate::validatable::Validatable;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `GetRoles` command is used to retrieve the information about all roles.
+/// It has no additional payload.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GetRoles {}
+
+impl Command for GetRoles {
+    fn code(&self) -> u32 {
+        GET_ROLES_CODE
+    }
+}
+
+impl Validatable<IggyError> for GetRoles {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for GetRoles {
+    fn to_bytes(&self) -> Bytes {
+        Bytes::new()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<GetRoles, IggyError> {
+        if !bytes.is_empty() {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(GetRoles {})
+    }
+}
+
+impl Display for GetRoles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_empty_bytes() {
+        let command = GetRoles {};
+        let bytes = command.to_bytes();
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn should_be_deserialized_from_empty_bytes() {
+        let command = GetRoles::from_bytes(Bytes::new());
+        assert!(command.is_ok());
+    }
+
+    #[test]
+    fn should_not_be_deserialized_from_empty_bytes() {
+        let command = GetRoles::from_bytes(Bytes::from_static(&[0]));
+        assert!(command.is_err());
+    }
+}
@@ -0,0 +1,9 @@
+pub mod assign_role;
+pub mod create_role;
+pub mod delete_role;
+pub mod get_role;
+pub mod get_roles;
+pub mod unassign_role;
+pub mod update_role;
+
+const MAX_NAME_LENGTH: usize = 255;
@@ -0,0 +1,226 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, UPDATE_ROLE_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::models::permissions::Permissions;
+use crate::roles::MAX_NAME_LENGTH;
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::from_utf8;
+
+/// `UpdateRole` command is used to update a role's name and permissions, which are applied to all the users the role is assigned to.
+/// It has additional payload:
+/// - `role_id` - unique role ID (numeric or name).
+/// - `name` - new name (optional), if provided, must be between 1 and 255 characters long.
+/// - `permissions` - new permissions (optional), if not provided, the existing role's permissions are kept.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct UpdateRole {
+    /// Unique role ID (numeric or name).
+    #[serde(skip)]
+    pub role_id: Identifier,
+    /// New name (optional), if provided, must be between 1 and 255 characters long.
+    pub name: Option<String>,
+    /// New permissions (optional), if not provided, the existing role's permissions are kept.
+    pub permissions: Option<Permissions>,
+}
+
+impl Command for UpdateRole {
+    fn code(&self) -> u32 {
+        UPDATE_ROLE_CODE
+    }
+}
+
+impl Validatable<IggyError> for UpdateRole {
+    fn validate(&self) -> Result<(), IggyError> {
+        if let Some(name) = &self.name {
+            if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+                return Err(IggyError::InvalidRoleName);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for UpdateRole {
+    fn to_bytes(&self) -> Bytes {
+        let role_id_bytes = self.role_id.to_bytes();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&role_id_bytes);
+        if let Some(name) = &self.name {
+            bytes.put_u8(1);
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.put_u8(name.len() as u8);
+            bytes.put_slice(name.as_bytes());
+        } else {
+            bytes.put_u8(0);
+        }
+        if let Some(permissions) = &self.permissions {
+            bytes.put_u8(1);
+            let permissions = permissions.to_bytes();
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.put_u32_le(permissions.len() as u32);
+            bytes.put_slice(&permissions);
+        } else {
+            bytes.put_u8(0);
+        }
+
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<UpdateRole, IggyError> {
+        if bytes.len() < 5 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let role_id = Identifier::from_bytes(bytes.clone())?;
+        let mut position = role_id.get_size_bytes().as_bytes_usize();
+        if bytes.len() < position + 2 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let has_name = bytes[position];
+        if has_name > 1 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        position += 1;
+        let name = if has_name == 1 {
+            let name_length = bytes[position] as usize;
+            position += 1;
+            if bytes.len() < position + name_length + 1 {
+                return Err(IggyError::InvalidCommand);
+            }
+
+            let name = from_utf8(&bytes[position..position + name_length])
+                .map_err(|_| IggyError::InvalidUtf8)?
+                .to_string();
+            position += name_length;
+            Some(name)
+        } else {
+            None
+        };
+
+        let has_permissions = bytes[position];
+        if has_permissions > 1 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        position += 1;
+        let permissions = if has_permissions == 1 {
+            if bytes.len() < position + 4 {
+                return Err(IggyError::InvalidCommand);
+            }
+
+            let permissions_length = u32::from_le_bytes(
+                bytes[position..position + 4]
+                    .try_into()
+                    .map_err(|_| IggyError::InvalidNumberEncoding)?,
+            ) as usize;
+            position += 4;
+            if bytes.len() != position + permissions_length {
+                return Err(IggyError::InvalidCommand);
+            }
+
+            Some(Permissions::from_bytes(
+                bytes.slice(position..position + permissions_length),
+            )?)
+        } else {
+            None
+        };
+
+        let command = UpdateRole {
+            role_id,
+            name,
+            permissions,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for UpdateRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.name.as_deref().unwrap_or("");
+        let permissions = if let Some(permissions) = &self.permissions {
+            permissions.to_string()
+        } else {
+            "no_permissions".to_string()
+        };
+        write!(f, "{}|{name}|{permissions}", self.role_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::permissions::GlobalPermissions;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = UpdateRole {
+            role_id: Identifier::numeric(1).unwrap(),
+            name: Some("auditors".to_string()),
+            permissions: Some(get_permissions()),
+        };
+
+        let bytes = command.to_bytes();
+        let role_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        let mut position = role_id.get_size_bytes().as_bytes_usize();
+        let has_name = bytes[position];
+        position += 1;
+        let name_length = bytes[position];
+        position += 1;
+        let name = from_utf8(&bytes[position..position + name_length as usize]).unwrap();
+        position += name_length as usize;
+        let has_permissions = bytes[position];
+        position += 1;
+        let permissions_length =
+            u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
+        position += 4;
+        let permissions =
+            Permissions::from_bytes(bytes.slice(position..position + permissions_length as usize))
+                .unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(role_id, command.role_id);
+        assert_eq!(has_name, 1);
+        assert_eq!(name, command.name.unwrap());
+        assert_eq!(has_permissions, 1);
+        assert_eq!(permissions, command.permissions.unwrap());
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let role_id = Identifier::named("auditors").unwrap();
+        let permissions = get_permissions();
+        let permissions_bytes = permissions.to_bytes();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&role_id.to_bytes());
+        bytes.put_u8(0);
+        bytes.put_u8(1);
+        bytes.put_u32_le(permissions_bytes.len() as u32);
+        bytes.put_slice(&permissions_bytes);
+
+        let command = UpdateRole::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.role_id, role_id);
+        assert!(command.name.is_none());
+        assert_eq!(command.permissions.unwrap(), permissions);
+    }
+
+    fn get_permissions() -> Permissions {
+        Permissions {
+            global: GlobalPermissions {
+                read_streams: true,
+                poll_messages: true,
+                ..Default::default()
+            },
+            streams: None,
+        }
+    }
+}
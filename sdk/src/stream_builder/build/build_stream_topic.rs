@@ -4,6 +4,8 @@ use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::error::IggyError;
 use crate::identifier::{IdKind, Identifier};
 use crate::stream_builder::IggyConsumerConfig;
+use crate::topics::cleanup_policy::CleanupPolicy;
+use crate::topics::replication_factor::ReplicationFactor;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::topic_size::MaxTopicSize;
 use tracing::{trace, warn};
@@ -65,7 +67,7 @@ pub(crate) async fn build_iggy_stream_topic_if_not_exists(
         let stream_id = config.stream_id();
         let stream_name = config.stream_name();
         let topic_partitions_count = config.partitions_count();
-        let topic_replication_factor = config.replication_factor();
+        let topic_replication_factor = config.replication_factor().map(ReplicationFactor);
 
         let (name, id) = extract_name_id_from_identifier(topic_id, topic_name)?;
         trace!("Create topic: {name} for stream: {}", stream_name);
@@ -75,10 +77,12 @@ pub(crate) async fn build_iggy_stream_topic_if_not_exists(
                 topic_name,
                 topic_partitions_count,
                 CompressionAlgorithm::None,
+                None,
                 topic_replication_factor,
                 id,
                 IggyExpiry::ServerDefault,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
     }
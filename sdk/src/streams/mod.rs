@@ -3,6 +3,7 @@ pub mod delete_stream;
 pub mod get_stream;
 pub mod get_streams;
 pub mod purge_stream;
+pub mod set_stream_quota;
 pub mod update_stream;
 
 const MAX_NAME_LENGTH: usize = 255;
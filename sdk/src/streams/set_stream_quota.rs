@@ -0,0 +1,113 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, SET_STREAM_QUOTA_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::models::quota::Quota;
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `SetStreamQuota` command is used to set the quota of an existing stream.
+/// It has additional payload:
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `quota` - the limits of the stored messages size, topics and partitions count, and the produce and consume throughput.
+///   The quota with all the limits set to 0 removes the existing quota.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SetStreamQuota {
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// New quota of the stream, each limit set to 0 means unlimited.
+    #[serde(flatten)]
+    pub quota: Quota,
+}
+
+impl Command for SetStreamQuota {
+    fn code(&self) -> u32 {
+        SET_STREAM_QUOTA_CODE
+    }
+}
+
+impl Validatable<IggyError> for SetStreamQuota {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for SetStreamQuota {
+    fn to_bytes(&self) -> Bytes {
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let quota_bytes = self.quota.to_bytes();
+        let mut bytes = BytesMut::with_capacity(stream_id_bytes.len() + quota_bytes.len());
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&quota_bytes);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<SetStreamQuota, IggyError> {
+        if bytes.len() < 4 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let stream_id = Identifier::from_bytes(bytes.clone())?;
+        let position = stream_id.get_size_bytes().as_bytes_usize();
+        let quota = Quota::from_bytes(bytes.slice(position..))?;
+        Ok(SetStreamQuota { stream_id, quota })
+    }
+}
+
+impl Display for SetStreamQuota {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.stream_id, self.quota)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = SetStreamQuota {
+            stream_id: Identifier::numeric(1).unwrap(),
+            quota: get_quota(),
+        };
+
+        let bytes = command.to_bytes();
+        let stream_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        let position = stream_id.get_size_bytes().as_bytes_usize();
+        let quota = Quota::from_bytes(bytes.slice(position..)).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(stream_id, command.stream_id);
+        assert_eq!(quota, command.quota);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let stream_id = Identifier::named("test").unwrap();
+        let quota = get_quota();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&stream_id.to_bytes());
+        bytes.put_slice(&quota.to_bytes());
+
+        let command = SetStreamQuota::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.stream_id, stream_id);
+        assert_eq!(command.quota, quota);
+    }
+
+    fn get_quota() -> Quota {
+        Quota {
+            max_size: 1_000_000,
+            max_topics: 5,
+            max_partitions: 50,
+            max_produce_bytes_per_second: 1000,
+            max_consume_bytes_per_second: 2000,
+        }
+    }
+}
@@ -4,3 +4,4 @@ pub mod get_me;
 pub mod get_snapshot;
 pub mod get_stats;
 pub mod ping;
+pub mod set_protocol_version;
@@ -0,0 +1,127 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, SET_PROTOCOL_VERSION_CODE};
+use crate::error::IggyError;
+use crate::validatable::Validatable;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `SetProtocolVersion` command is used to negotiate the version of the binary protocol used by the TCP connection.
+/// The response is sent using the current version, and all the subsequent frames use the requested version.
+/// It has additional payload:
+/// - `version` - the version of the protocol.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SetProtocolVersion {
+    /// The version of the protocol.
+    pub version: ProtocolVersion,
+}
+
+/// `ProtocolVersion` specifies the framing of the binary protocol.
+/// It has the following kinds:
+/// - `V1` - the request is `[length][code][payload]` and the response is `[status][length][payload]`,
+///   so the connection can have a single request in flight, which is the default version.
+/// - `V2` - every request and response frame is prefixed with the `u32` request ID, so the connection can have
+///   multiple requests in flight, and the server can respond to them in any order.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Default, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolVersion {
+    #[default]
+    /// A single request in flight per connection.
+    V1,
+    /// Multiple requests in flight per connection, correlated by the request ID.
+    V2,
+}
+
+impl ProtocolVersion {
+    /// Returns code of the protocol version.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            ProtocolVersion::V1 => 1,
+            ProtocolVersion::V2 => 2,
+        }
+    }
+
+    /// Returns protocol version from the specified code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(ProtocolVersion::V1),
+            2 => Ok(ProtocolVersion::V2),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtocolVersion::V1 => write!(f, "v1"),
+            ProtocolVersion::V2 => write!(f, "v2"),
+        }
+    }
+}
+
+impl Command for SetProtocolVersion {
+    fn code(&self) -> u32 {
+        SET_PROTOCOL_VERSION_CODE
+    }
+}
+
+impl Validatable<IggyError> for SetProtocolVersion {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for SetProtocolVersion {
+    fn to_bytes(&self) -> Bytes {
+        Bytes::copy_from_slice(&[self.version.as_code()])
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<SetProtocolVersion, IggyError> {
+        if bytes.len() != 1 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let version = ProtocolVersion::from_code(bytes[0])?;
+        let command = SetProtocolVersion { version };
+        Ok(command)
+    }
+}
+
+impl Display for SetProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = SetProtocolVersion {
+            version: ProtocolVersion::V2,
+        };
+
+        let bytes = command.to_bytes();
+
+        assert_eq!(bytes.len(), 1);
+        assert_eq!(bytes[0], ProtocolVersion::V2.as_code());
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let command = SetProtocolVersion::from_bytes(Bytes::from_static(&[2]));
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.version, ProtocolVersion::V2);
+    }
+
+    #[test]
+    fn should_not_be_deserialized_from_unknown_version() {
+        let command = SetProtocolVersion::from_bytes(Bytes::from_static(&[3]));
+        assert!(command.is_err());
+    }
+}
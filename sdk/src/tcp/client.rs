@@ -1,14 +1,20 @@
 use crate::binary::binary_client::BinaryClient;
-use crate::binary::{BinaryTransport, ClientState};
+use crate::binary::{self, BinaryTransport, ClientState};
+use crate::bytes_serializable::BytesSerializable;
 use crate::client::{
     AutoLogin, Client, ConnectionString, Credentials, PersonalAccessTokenClient, UserClient,
 };
 use crate::command::Command;
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::{IggyError, IggyErrorDiscriminants};
+use crate::messages::poll_messages::PollingFormat;
+use crate::system::set_protocol_version::{ProtocolVersion, SetProtocolVersion};
 use crate::tcp::config::TcpClientConfig;
+use crate::tcp::pipeline::{PipelineReader, PipelineWriter, TcpPipeline};
 use crate::utils::duration::IggyDuration;
+use crate::utils::server_address::ServerAddresses;
 use crate::utils::timestamp::IggyTimestamp;
+use crate::utils::tls;
 use async_broadcast::{broadcast, Receiver, Sender};
 use async_trait::async_trait;
 use bytes::{BufMut, Bytes, BytesMut};
@@ -34,7 +40,9 @@ const NAME: &str = "Iggy";
 #[derive(Debug)]
 pub struct TcpClient {
     pub(crate) stream: Mutex<Option<ConnectionStreamKind>>,
+    pipeline: Mutex<Option<Arc<TcpPipeline>>>,
     pub(crate) config: Arc<TcpClientConfig>,
+    server_addresses: ServerAddresses<String>,
     pub(crate) state: Mutex<ClientState>,
     client_address: Mutex<Option<SocketAddr>>,
     events: (Sender<DiagnosticEvent>, Receiver<DiagnosticEvent>),
@@ -83,6 +91,16 @@ impl ConnectionStreamKind {
             Self::TcpTls(c) => c.shutdown().await,
         }
     }
+
+    fn into_split(self) -> (PipelineReader, PipelineWriter) {
+        match self {
+            Self::Tcp(c) => (Box::new(c.reader), Box::new(c.writer)),
+            Self::TcpTls(c) => {
+                let (reader, writer) = tokio::io::split(c.stream);
+                (Box::new(reader), Box::new(writer))
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -272,7 +290,7 @@ impl BinaryTransport for TcpClient {
             let client_address = self.get_client_address_value().await;
             info!(
                 "Reconnecting to the server: {} by client: {client_address}...",
-                self.config.server_address
+                self.server_addresses.current()
             );
         }
 
@@ -289,6 +307,14 @@ impl BinaryTransport for TcpClient {
     fn get_heartbeat_interval(&self) -> IggyDuration {
         self.config.heartbeat_interval
     }
+
+    fn get_polling_format(&self) -> PollingFormat {
+        if self.config.zero_copy {
+            PollingFormat::Batches
+        } else {
+            PollingFormat::Messages
+        }
+    }
 }
 
 impl BinaryClient for TcpClient {}
@@ -333,10 +359,18 @@ impl TcpClient {
 
     /// Create a new TCP client based on the provided configuration.
     pub fn create(config: Arc<TcpClientConfig>) -> Result<Self, IggyError> {
+        let server_addresses = ServerAddresses::parse(
+            &config.server_address,
+            &config.server_addresses,
+            config.address_selection,
+            |address| Ok(address.to_owned()),
+        )?;
         Ok(Self {
+            server_addresses,
             config,
             client_address: Mutex::new(None),
             stream: Mutex::new(None),
+            pipeline: Mutex::new(None),
             state: Mutex::new(ClientState::Disconnected),
             events: broadcast(1000),
             connected_at: Mutex::new(None),
@@ -350,30 +384,7 @@ impl TcpClient {
         stream: &mut ConnectionStreamKind,
     ) -> Result<Bytes, IggyError> {
         if status != 0 {
-            // TEMP: See https://github.com/iggy-rs/iggy/pull/604 for context.
-            if status == IggyErrorDiscriminants::TopicIdAlreadyExists as u32
-                || status == IggyErrorDiscriminants::TopicNameAlreadyExists as u32
-                || status == IggyErrorDiscriminants::StreamIdAlreadyExists as u32
-                || status == IggyErrorDiscriminants::StreamNameAlreadyExists as u32
-                || status == IggyErrorDiscriminants::UserAlreadyExists as u32
-                || status == IggyErrorDiscriminants::PersonalAccessTokenAlreadyExists as u32
-                || status == IggyErrorDiscriminants::ConsumerGroupIdAlreadyExists as u32
-                || status == IggyErrorDiscriminants::ConsumerGroupNameAlreadyExists as u32
-            {
-                tracing::debug!(
-                    "Received a server resource already exists response: {} ({})",
-                    status,
-                    IggyError::from_code_as_string(status)
-                )
-            } else {
-                error!(
-                    "Received an invalid response with status: {} ({}).",
-                    status,
-                    IggyError::from_code_as_string(status),
-                );
-            }
-
-            return Err(IggyError::from_code(status));
+            return Err(map_error_status(status));
         }
 
         trace!("Status: OK. Response length: {}", length);
@@ -421,135 +432,86 @@ impl TcpClient {
             }
         }
 
-        let tls_enabled = self.config.tls_enabled;
         let mut retry_count = 0;
-        let connection_stream: ConnectionStreamKind;
-        let remote_address;
-        let client_address;
-        loop {
-            info!(
-                "{NAME} client is connecting to server: {}...",
-                self.config.server_address
-            );
-
-            let connection = TcpStream::connect(&self.config.server_address).await;
-            if connection.is_err() {
-                error!(
-                    "Failed to connect to server: {}",
-                    self.config.server_address
-                );
-                if !self.config.reconnection.enabled {
-                    warn!("Automatic reconnection is disabled.");
-                    return Err(IggyError::CannotEstablishConnection);
-                }
-
-                let unlimited_retries = self.config.reconnection.max_retries.is_none();
-                let max_retries = self.config.reconnection.max_retries.unwrap_or_default();
-                let max_retries_str =
-                    if let Some(max_retries) = self.config.reconnection.max_retries {
-                        max_retries.to_string()
-                    } else {
-                        "unlimited".to_string()
-                    };
-
-                let interval_str = self.config.reconnection.interval.as_human_time_string();
-                if unlimited_retries || retry_count < max_retries {
-                    retry_count += 1;
-                    info!(
-                        "Retrying to connect to server ({retry_count}/{max_retries_str}): {} in: {interval_str}",
-                        self.config.server_address,
-                    );
-                    sleep(self.config.reconnection.interval.get_duration()).await;
-                    continue;
+        let (connection_stream, client_address, remote_address) = loop {
+            let mut connection = None;
+            for (index, server_address) in self.server_addresses.candidates() {
+                info!("{NAME} client is connecting to server: {server_address}...");
+                match self.connect_to_server(server_address).await {
+                    Ok(established) => {
+                        self.server_addresses.set_current(index);
+                        connection = Some(established);
+                        break;
+                    }
+                    Err(IggyError::CannotEstablishConnection) => {
+                        error!("Failed to connect to server: {server_address}");
+                    }
+                    Err(error) => {
+                        self.set_state(ClientState::Disconnected).await;
+                        return Err(error);
+                    }
                 }
-
-                self.set_state(ClientState::Disconnected).await;
-                self.publish_event(DiagnosticEvent::Disconnected).await;
-                return Err(IggyError::CannotEstablishConnection);
             }
 
-            let stream = connection.map_err(|error| {
-                error!("Failed to establish TCP connection to the server: {error}",);
-                IggyError::CannotEstablishConnection
-            })?;
-            client_address = stream.local_addr().map_err(|error| {
-                error!("Failed to get the local address of the client: {error}",);
-                IggyError::CannotEstablishConnection
-            })?;
-            remote_address = stream.peer_addr().map_err(|error| {
-                error!("Failed to get the remote address of the server: {error}",);
-                IggyError::CannotEstablishConnection
-            })?;
-            self.client_address.lock().await.replace(client_address);
-
-            if let Err(e) = stream.set_nodelay(self.config.nodelay) {
-                error!("Failed to set the nodelay option on the client: {e}, continuing...",);
+            if let Some(connection) = connection {
+                break connection;
             }
 
-            if !tls_enabled {
-                connection_stream =
-                    ConnectionStreamKind::Tcp(TcpConnectionStream::new(client_address, stream));
-                break;
+            if !self.config.reconnection.enabled {
+                warn!("Automatic reconnection is disabled.");
+                self.set_state(ClientState::Disconnected).await;
+                return Err(IggyError::CannotEstablishConnection);
             }
 
-            let mut root_cert_store = rustls::RootCertStore::empty();
-            if let Some(certificate_path) = &self.config.tls_ca_file {
-                for cert in CertificateDer::pem_file_iter(certificate_path).map_err(|error| {
-                    error!("Failed to read the CA file: {certificate_path}. {error}",);
-                    IggyError::InvalidTlsCertificatePath
-                })? {
-                    let certificate = cert.map_err(|error| {
-                        error!(
-                            "Failed to read a certificate from the CA file: {certificate_path}. {error}",
-                        );
-                        IggyError::InvalidTlsCertificate
-                    })?;
-                    root_cert_store.add(certificate).map_err(|error| {
-                        error!(
-                            "Failed to add a certificate to the root certificate store. {error}",
-                        );
-                        IggyError::InvalidTlsCertificate
-                    })?;
-                }
+            let unlimited_retries = self.config.reconnection.max_retries.is_none();
+            let max_retries = self.config.reconnection.max_retries.unwrap_or_default();
+            let max_retries_str = if let Some(max_retries) = self.config.reconnection.max_retries {
+                max_retries.to_string()
             } else {
-                root_cert_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+                "unlimited".to_string()
+            };
+
+            let interval_str = self.config.reconnection.interval.as_human_time_string();
+            if unlimited_retries || retry_count < max_retries {
+                retry_count += 1;
+                info!(
+                    "Retrying to connect to server ({retry_count}/{max_retries_str}): {} in: {interval_str}",
+                    self.server_addresses.current(),
+                );
+                sleep(self.config.reconnection.interval.get_duration()).await;
+                continue;
             }
 
-            let config = rustls::ClientConfig::builder()
-                .with_root_certificates(root_cert_store)
-                .with_no_client_auth();
-            let connector = TlsConnector::from(Arc::new(config));
-            let stream = TcpStream::connect(client_address).await.map_err(|error| {
-                error!("Failed to establish TCP connection to the server: {error}",);
-                IggyError::CannotEstablishConnection
-            })?;
-            let tls_domain = self.config.tls_domain.to_owned();
-            let domain = ServerName::try_from(tls_domain).map_err(|error| {
-                error!("Failed to create a server name from the domain. {error}",);
-                IggyError::InvalidTlsDomain
-            })?;
-            let stream = connector.connect(domain, stream).await.map_err(|error| {
-                error!("Failed to establish a TLS connection to the server: {error}",);
-                IggyError::CannotEstablishConnection
-            })?;
-            connection_stream = ConnectionStreamKind::TcpTls(TcpTlsConnectionStream::new(
-                client_address,
-                TlsStream::Client(stream),
-            ));
-            break;
-        }
+            self.set_state(ClientState::Disconnected).await;
+            self.publish_event(DiagnosticEvent::Disconnected).await;
+            return Err(IggyError::CannotEstablishConnection);
+        };
+        self.client_address.lock().await.replace(client_address);
 
         let now = IggyTimestamp::now();
         info!(
             "{NAME} client: {client_address} has connected to server: {remote_address} at: {now}",
         );
-        self.stream.lock().await.replace(connection_stream);
+        let mut connection_stream = connection_stream;
+        if self.config.pipelining && self.negotiate_pipelining(&mut connection_stream).await {
+            let (reader, writer) = connection_stream.into_split();
+            let pipeline = TcpPipeline::start(client_address, reader, writer);
+            self.pipeline.lock().await.replace(Arc::new(pipeline));
+        } else {
+            self.stream.lock().await.replace(connection_stream);
+        }
         self.set_state(ClientState::Connected).await;
         self.connected_at.lock().await.replace(now);
         self.publish_event(DiagnosticEvent::Connected).await;
         match &self.config.auto_login {
             AutoLogin::Disabled => {
                 info!("Automatic sign-in is disabled.");
+                if self.config.tls_enabled
+                    && self.config.tls_cert_file.is_some()
+                    && binary::authenticate_with_client_certificate(self).await
+                {
+                    info!("{NAME} client: {client_address} has been authenticated with the client certificate.");
+                }
                 Ok(())
             }
             AutoLogin::Enabled(credentials) => {
@@ -571,6 +533,91 @@ impl TcpClient {
         }
     }
 
+    /// Establishes the TCP (and optionally TLS) connection to the provided server.
+    /// Returns `CannotEstablishConnection` if the server can't be reached, so the next server can be tried.
+    async fn connect_to_server(
+        &self,
+        server_address: &str,
+    ) -> Result<(ConnectionStreamKind, SocketAddr, SocketAddr), IggyError> {
+        let stream = TcpStream::connect(server_address).await.map_err(|error| {
+            error!("Failed to establish TCP connection to the server: {error}",);
+            IggyError::CannotEstablishConnection
+        })?;
+        let client_address = stream.local_addr().map_err(|error| {
+            error!("Failed to get the local address of the client: {error}",);
+            IggyError::CannotEstablishConnection
+        })?;
+        let remote_address = stream.peer_addr().map_err(|error| {
+            error!("Failed to get the remote address of the server: {error}",);
+            IggyError::CannotEstablishConnection
+        })?;
+
+        if let Err(e) = stream.set_nodelay(self.config.nodelay) {
+            error!("Failed to set the nodelay option on the client: {e}, continuing...",);
+        }
+
+        if !self.config.tls_enabled {
+            let connection_stream =
+                ConnectionStreamKind::Tcp(TcpConnectionStream::new(client_address, stream));
+            return Ok((connection_stream, client_address, remote_address));
+        }
+
+        let connector = self.create_tls_connector()?;
+        let tls_domain = self.config.tls_domain.to_owned();
+        let domain = ServerName::try_from(tls_domain).map_err(|error| {
+            error!("Failed to create a server name from the domain. {error}",);
+            IggyError::InvalidTlsDomain
+        })?;
+        let stream = connector.connect(domain, stream).await.map_err(|error| {
+            error!("Failed to establish a TLS connection to the server: {error}",);
+            IggyError::CannotEstablishConnection
+        })?;
+        let connection_stream = ConnectionStreamKind::TcpTls(TcpTlsConnectionStream::new(
+            client_address,
+            TlsStream::Client(stream),
+        ));
+        Ok((connection_stream, client_address, remote_address))
+    }
+
+    fn create_tls_connector(&self) -> Result<TlsConnector, IggyError> {
+        let mut root_cert_store = rustls::RootCertStore::empty();
+        if let Some(certificate_path) = &self.config.tls_ca_file {
+            for cert in CertificateDer::pem_file_iter(certificate_path).map_err(|error| {
+                error!("Failed to read the CA file: {certificate_path}. {error}",);
+                IggyError::InvalidTlsCertificatePath
+            })? {
+                let certificate = cert.map_err(|error| {
+                    error!(
+                        "Failed to read a certificate from the CA file: {certificate_path}. {error}",
+                    );
+                    IggyError::InvalidTlsCertificate
+                })?;
+                root_cert_store.add(certificate).map_err(|error| {
+                    error!("Failed to add a certificate to the root certificate store. {error}",);
+                    IggyError::InvalidTlsCertificate
+                })?;
+            }
+        } else {
+            root_cert_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        }
+
+        tls::install_crypto_provider();
+        let config = rustls::ClientConfig::builder().with_root_certificates(root_cert_store);
+        let config = match (&self.config.tls_cert_file, &self.config.tls_key_file) {
+            (Some(cert_file), Some(key_file)) => {
+                let (certificates, key) = tls::load_client_certificate(cert_file, key_file)?;
+                config
+                    .with_client_auth_cert(certificates, key)
+                    .map_err(|error| {
+                        error!("Failed to use the client certificate: {cert_file}. {error}");
+                        IggyError::InvalidTlsCertificate
+                    })?
+            }
+            _ => config.with_no_client_auth(),
+        };
+        Ok(TlsConnector::from(Arc::new(config)))
+    }
+
     async fn disconnect(&self) -> Result<(), IggyError> {
         if self.get_state().await == ClientState::Disconnected {
             return Ok(());
@@ -580,6 +627,9 @@ impl TcpClient {
         info!("{NAME} client: {client_address} is disconnecting from server...");
         self.set_state(ClientState::Disconnected).await;
         self.stream.lock().await.take();
+        if let Some(pipeline) = self.pipeline.lock().await.take() {
+            pipeline.close();
+        }
         self.publish_event(DiagnosticEvent::Disconnected).await;
         let now = IggyTimestamp::now();
         info!("{NAME} client: {client_address} has disconnected from server at: {now}.");
@@ -597,6 +647,10 @@ impl TcpClient {
         if let Some(mut stream) = stream {
             stream.shutdown().await?;
         }
+        let pipeline = self.pipeline.lock().await.take();
+        if let Some(pipeline) = pipeline {
+            pipeline.shutdown().await?;
+        }
         self.set_state(ClientState::Shutdown).await;
         self.publish_event(DiagnosticEvent::Shutdown).await;
         info!("{NAME} TCP client: {client_address} has been shutdown.");
@@ -620,46 +674,81 @@ impl TcpClient {
             _ => {}
         }
 
+        let pipeline = self.pipeline.lock().await.clone();
+        if let Some(pipeline) = pipeline {
+            return pipeline.send(code, payload).await;
+        }
+
         let mut stream = self.stream.lock().await;
         if let Some(stream) = stream.as_mut() {
-            let payload_length = payload.len() + REQUEST_INITIAL_BYTES_LENGTH;
-            trace!("Sending a TCP request with code: {code}");
-            stream.write(&(payload_length as u32).to_le_bytes()).await?;
-            stream.write(&code.to_le_bytes()).await?;
-            stream.write(&payload).await?;
-            stream.flush().await?;
-            trace!("Sent a TCP request with code: {code}, waiting for a response...");
-
-            let mut response_buffer = [0u8; RESPONSE_INITIAL_BYTES_LENGTH];
-            let read_bytes = stream.read(&mut response_buffer).await.map_err(|error| {
-                error!(
-                    "Failed to read response for TCP request with code: {code}: {error}",
-                    code = code,
-                    error = error
-                );
-                IggyError::Disconnected
-            })?;
+            return self.send_on_stream(stream, code, payload).await;
+        }
 
-            if read_bytes != RESPONSE_INITIAL_BYTES_LENGTH {
-                error!("Received an invalid or empty response.");
-                return Err(IggyError::EmptyResponse);
-            }
+        error!("Cannot send data. Client is not connected.");
+        Err(IggyError::NotConnected)
+    }
 
-            let status = u32::from_le_bytes(
-                response_buffer[..4]
-                    .try_into()
-                    .map_err(|_| IggyError::InvalidNumberEncoding)?,
-            );
-            let length = u32::from_le_bytes(
-                response_buffer[4..]
-                    .try_into()
-                    .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    async fn send_on_stream(
+        &self,
+        stream: &mut ConnectionStreamKind,
+        code: u32,
+        payload: Bytes,
+    ) -> Result<Bytes, IggyError> {
+        let payload_length = payload.len() + REQUEST_INITIAL_BYTES_LENGTH;
+        trace!("Sending a TCP request with code: {code}");
+        stream.write(&(payload_length as u32).to_le_bytes()).await?;
+        stream.write(&code.to_le_bytes()).await?;
+        stream.write(&payload).await?;
+        stream.flush().await?;
+        trace!("Sent a TCP request with code: {code}, waiting for a response...");
+
+        let mut response_buffer = [0u8; RESPONSE_INITIAL_BYTES_LENGTH];
+        let read_bytes = stream.read(&mut response_buffer).await.map_err(|error| {
+            error!(
+                "Failed to read response for TCP request with code: {code}: {error}",
+                code = code,
+                error = error
             );
-            return self.handle_response(status, length, stream).await;
+            IggyError::Disconnected
+        })?;
+
+        if read_bytes != RESPONSE_INITIAL_BYTES_LENGTH {
+            error!("Received an invalid or empty response.");
+            return Err(IggyError::EmptyResponse);
         }
 
-        error!("Cannot send data. Client is not connected.");
-        Err(IggyError::NotConnected)
+        let status = u32::from_le_bytes(
+            response_buffer[..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let length = u32::from_le_bytes(
+            response_buffer[4..]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        self.handle_response(status, length, stream).await
+    }
+
+    /// Negotiates the pipelined protocol, and returns whether the server has accepted it.
+    /// The servers which don't support it reject the command, so the connection keeps using a single request in flight.
+    async fn negotiate_pipelining(&self, stream: &mut ConnectionStreamKind) -> bool {
+        let command = SetProtocolVersion {
+            version: ProtocolVersion::V2,
+        };
+        match self
+            .send_on_stream(stream, command.code(), command.to_bytes())
+            .await
+        {
+            Ok(_) => {
+                info!("{NAME} client has switched to the pipelined protocol.");
+                true
+            }
+            Err(error) => {
+                warn!("{NAME} client couldn't switch to the pipelined protocol, a single request will be in flight: {error}");
+                false
+            }
+        }
     }
 
     async fn get_client_address_value(&self) -> String {
@@ -671,3 +760,30 @@ impl TcpClient {
         }
     }
 }
+
+pub(crate) fn map_error_status(status: u32) -> IggyError {
+    // TEMP: See https://github.com/iggy-rs/iggy/pull/604 for context.
+    if status == IggyErrorDiscriminants::TopicIdAlreadyExists as u32
+        || status == IggyErrorDiscriminants::TopicNameAlreadyExists as u32
+        || status == IggyErrorDiscriminants::StreamIdAlreadyExists as u32
+        || status == IggyErrorDiscriminants::StreamNameAlreadyExists as u32
+        || status == IggyErrorDiscriminants::UserAlreadyExists as u32
+        || status == IggyErrorDiscriminants::PersonalAccessTokenAlreadyExists as u32
+        || status == IggyErrorDiscriminants::ConsumerGroupIdAlreadyExists as u32
+        || status == IggyErrorDiscriminants::ConsumerGroupNameAlreadyExists as u32
+    {
+        tracing::debug!(
+            "Received a server resource already exists response: {} ({})",
+            status,
+            IggyError::from_code_as_string(status)
+        )
+    } else {
+        error!(
+            "Received an invalid response with status: {} ({}).",
+            status,
+            IggyError::from_code_as_string(status),
+        );
+    }
+
+    IggyError::from_code(status)
+}
@@ -1,5 +1,6 @@
 use crate::client::AutoLogin;
 use crate::utils::duration::IggyDuration;
+use crate::utils::server_address::ServerAddressSelection;
 use std::str::FromStr;
 
 /// Configuration for the TCP client.
@@ -7,12 +8,20 @@ use std::str::FromStr;
 pub struct TcpClientConfig {
     /// The address of the Iggy server.
     pub server_address: String,
+    /// The addresses of the multiple servers, e.g. the primary and the standby one, used instead of `server_address` when not empty.
+    pub server_addresses: Vec<String>,
+    /// The policy of selecting the server to connect to, when the multiple server addresses are configured.
+    pub address_selection: ServerAddressSelection,
     /// Whether to use TLS when connecting to the server.
     pub tls_enabled: bool,
     /// The domain to use for TLS when connecting to the server.
     pub tls_domain: String,
     /// The path to the CA file for TLS.
     pub tls_ca_file: Option<String>,
+    /// The path to the certificate file (PEM) presented to the server, which verifies the client certificates.
+    pub tls_cert_file: Option<String>,
+    /// The path to the private key file (PEM) of the client certificate.
+    pub tls_key_file: Option<String>,
     /// Whether to automatically login user after establishing connection.
     pub auto_login: AutoLogin,
     /// Whether to automatically reconnect when disconnected.
@@ -21,6 +30,10 @@ pub struct TcpClientConfig {
     pub heartbeat_interval: IggyDuration,
     /// Disable Nagle algorithm for the TCP socket.
     pub nodelay: bool,
+    /// Whether to request the polled messages as the batches stored on the server, which can be sent without copying them.
+    pub zero_copy: bool,
+    /// Whether to negotiate the pipelined protocol, which allows multiple requests to be in flight over the same connection.
+    pub pipelining: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -35,13 +48,19 @@ impl Default for TcpClientConfig {
     fn default() -> TcpClientConfig {
         TcpClientConfig {
             server_address: "127.0.0.1:8090".to_string(),
+            server_addresses: Vec::new(),
+            address_selection: ServerAddressSelection::default(),
             tls_enabled: false,
             tls_domain: "localhost".to_string(),
             tls_ca_file: None,
+            tls_cert_file: None,
+            tls_key_file: None,
             heartbeat_interval: IggyDuration::from_str("5s").unwrap(),
             auto_login: AutoLogin::Disabled,
             reconnection: TcpClientReconnectionConfig::default(),
             nodelay: false,
+            zero_copy: false,
+            pipelining: false,
         }
     }
 }
@@ -60,11 +79,14 @@ impl Default for TcpClientReconnectionConfig {
 /// Builder for the TCP client configuration.
 /// Allows configuring the TCP client with custom settings or using defaults:
 /// - `server_address`: Default is "127.0.0.1:8090"
+/// - `server_addresses`: Default is empty.
+/// - `address_selection`: Default is ServerAddressSelection::Ordered.
 /// - `auto_login`: Default is AutoLogin::Disabled.
 /// - `reconnection`: Default is enabled unlimited retries and 1 second interval.
 /// - `tls_enabled`: Default is false.
 /// - `tls_domain`: Default is "localhost".
 /// - `tls_ca_file`: Default is None.
+/// - `tls_cert_file` and `tls_key_file`: Default is None.
 #[derive(Debug, Default)]
 pub struct TcpClientConfigBuilder {
     config: TcpClientConfig,
@@ -81,6 +103,18 @@ impl TcpClientConfigBuilder {
         self
     }
 
+    /// Sets the addresses of the multiple servers, used instead of the single server address.
+    pub fn with_server_addresses(mut self, server_addresses: Vec<String>) -> Self {
+        self.config.server_addresses = server_addresses;
+        self
+    }
+
+    /// Sets the policy of selecting the server to connect to, when the multiple server addresses are configured.
+    pub fn with_address_selection(mut self, address_selection: ServerAddressSelection) -> Self {
+        self.config.address_selection = address_selection;
+        self
+    }
+
     /// Sets the auto sign in during connection.
     pub fn with_auto_sign_in(mut self, auto_sign_in: AutoLogin) -> Self {
         self.config.auto_login = auto_sign_in;
@@ -122,12 +156,32 @@ impl TcpClientConfigBuilder {
         self
     }
 
+    /// Sets the paths to the client certificate and its private key for TLS,
+    /// which are presented to the server requiring the client certificates.
+    pub fn with_tls_client_certificate(mut self, cert_file: String, key_file: String) -> Self {
+        self.config.tls_cert_file = Some(cert_file);
+        self.config.tls_key_file = Some(key_file);
+        self
+    }
+
     /// Sets the nodelay option for the TCP socket.
     pub fn with_no_delay(mut self) -> Self {
         self.config.nodelay = true;
         self
     }
 
+    /// Sets whether to request the polled messages as the batches stored on the server.
+    pub fn with_zero_copy(mut self, zero_copy: bool) -> Self {
+        self.config.zero_copy = zero_copy;
+        self
+    }
+
+    /// Sets whether to negotiate the pipelined protocol with the server.
+    pub fn with_pipelining(mut self, pipelining: bool) -> Self {
+        self.config.pipelining = pipelining;
+        self
+    }
+
     /// Builds the TCP client configuration.
     pub fn build(self) -> TcpClientConfig {
         self.config
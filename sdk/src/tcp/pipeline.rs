@@ -0,0 +1,298 @@
+use crate::error::IggyError;
+use crate::tcp::client::map_error_status;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{error, trace, warn};
+
+const RESPONSE_INITIAL_BYTES_LENGTH: usize = 12;
+
+pub(crate) type PipelineReader = Box<dyn AsyncRead + Send + Unpin>;
+pub(crate) type PipelineWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
+#[derive(Default)]
+struct PendingRequests {
+    responses: HashMap<u32, oneshot::Sender<Result<Bytes, IggyError>>>,
+    closed: bool,
+}
+
+enum WriterCommand {
+    Write(Bytes, oneshot::Sender<Result<(), IggyError>>),
+    Shutdown(oneshot::Sender<Result<(), IggyError>>),
+}
+
+/// Removes the pending request once the caller stops waiting for the response, including when its future is dropped.
+struct PendingRequestGuard<'a> {
+    pending: &'a std::sync::Mutex<PendingRequests>,
+    request_id: u32,
+}
+
+impl Drop for PendingRequestGuard<'_> {
+    fn drop(&mut self) {
+        self.pending
+            .lock()
+            .unwrap()
+            .responses
+            .remove(&self.request_id);
+    }
+}
+
+/// The TCP connection using the pipelined protocol, in which every request and response frame is prefixed
+/// with the request ID, so multiple requests can be in flight, and their responses can arrive in any order.
+/// The requests are written by the background writer task, so each frame is written as a whole even if the caller
+/// gives up on it, while the responses are read by the background reader task, which completes the pending request
+/// with the matching ID.
+pub(crate) struct TcpPipeline {
+    client_address: SocketAddr,
+    writer: mpsc::UnboundedSender<WriterCommand>,
+    pending: Arc<std::sync::Mutex<PendingRequests>>,
+    next_request_id: AtomicU32,
+    reader: JoinHandle<()>,
+}
+
+impl Debug for TcpPipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpPipeline")
+            .field("client_address", &self.client_address)
+            .field("next_request_id", &self.next_request_id)
+            .finish()
+    }
+}
+
+impl TcpPipeline {
+    pub fn start(
+        client_address: SocketAddr,
+        reader: PipelineReader,
+        writer: PipelineWriter,
+    ) -> Self {
+        let pending = Arc::new(std::sync::Mutex::new(PendingRequests::default()));
+        let reader = tokio::spawn(read_responses(client_address, reader, pending.clone()));
+        let (writer_sender, writer_receiver) = mpsc::unbounded_channel();
+        tokio::spawn(write_requests(
+            client_address,
+            writer,
+            writer_receiver,
+            pending.clone(),
+        ));
+        Self {
+            client_address,
+            writer: writer_sender,
+            pending,
+            next_request_id: AtomicU32::new(1),
+            reader,
+        }
+    }
+
+    pub async fn send(&self, code: u32, payload: Bytes) -> Result<Bytes, IggyError> {
+        let request_id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let (response_sender, response_receiver) = oneshot::channel();
+        {
+            let mut pending = self.pending.lock().unwrap();
+            if pending.closed {
+                return Err(IggyError::Disconnected);
+            }
+            pending.responses.insert(request_id, response_sender);
+        }
+        let _guard = PendingRequestGuard {
+            pending: &self.pending,
+            request_id,
+        };
+
+        let mut frame = BytesMut::with_capacity(12 + payload.len());
+        frame.put_u32_le(request_id);
+        frame.put_u32_le(payload.len() as u32 + 4);
+        frame.put_u32_le(code);
+        frame.put_slice(&payload);
+        trace!("Sending a pipelined TCP request: {request_id} with code: {code}");
+        let (write_sender, write_receiver) = oneshot::channel();
+        self.writer
+            .send(WriterCommand::Write(frame.freeze(), write_sender))
+            .map_err(|_| IggyError::Disconnected)?;
+        write_receiver
+            .await
+            .map_err(|_| IggyError::Disconnected)??;
+
+        trace!("Sent a pipelined TCP request: {request_id} with code: {code}, waiting for a response...");
+        response_receiver
+            .await
+            .map_err(|_| IggyError::Disconnected)?
+    }
+
+    /// Stops reading the responses and fails all the pending requests.
+    pub fn close(&self) {
+        self.reader.abort();
+        fail_pending_requests(&self.pending);
+    }
+
+    pub async fn shutdown(&self) -> Result<(), IggyError> {
+        self.close();
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel();
+        if self
+            .writer
+            .send(WriterCommand::Shutdown(shutdown_sender))
+            .is_err()
+        {
+            // The writer task has already stopped after the failed write.
+            return Ok(());
+        }
+
+        shutdown_receiver.await.unwrap_or(Ok(()))
+    }
+}
+
+impl Drop for TcpPipeline {
+    fn drop(&mut self) {
+        // Dropping the sender stops the writer task once the queued frames are written.
+        self.reader.abort();
+    }
+}
+
+async fn write_requests(
+    client_address: SocketAddr,
+    mut writer: PipelineWriter,
+    mut commands: mpsc::UnboundedReceiver<WriterCommand>,
+    pending: Arc<std::sync::Mutex<PendingRequests>>,
+) {
+    while let Some(command) = commands.recv().await {
+        match command {
+            WriterCommand::Write(frame, result_sender) => {
+                let result = write_frame(client_address, &mut writer, &frame).await;
+                let failed = result.is_err();
+                let _ = result_sender.send(result);
+                if failed {
+                    // The connection might contain a partially written frame, so it cannot be used anymore.
+                    fail_pending_requests(&pending);
+                    return;
+                }
+            }
+            WriterCommand::Shutdown(result_sender) => {
+                let result = writer.shutdown().await.map_err(|error| {
+                    error!(
+                        "Failed to shutdown the TCP connection by client: {client_address} to the TCP connection: {error}"
+                    );
+                    IggyError::TcpError
+                });
+                let _ = result_sender.send(result);
+                return;
+            }
+        }
+    }
+}
+
+async fn write_frame(
+    client_address: SocketAddr,
+    writer: &mut PipelineWriter,
+    frame: &[u8],
+) -> Result<(), IggyError> {
+    writer.write_all(frame).await.map_err(|error| {
+        error!("Failed to write data by client: {client_address} to the TCP connection: {error}");
+        IggyError::TcpError
+    })?;
+    writer.flush().await.map_err(|error| {
+        error!("Failed to flush data by client: {client_address} to the TCP connection: {error}");
+        IggyError::TcpError
+    })
+}
+
+fn fail_pending_requests(pending: &std::sync::Mutex<PendingRequests>) {
+    let mut pending = pending.lock().unwrap();
+    pending.closed = true;
+    // Dropping the senders completes the pending requests with the disconnected error.
+    pending.responses.clear();
+}
+
+async fn read_responses(
+    client_address: SocketAddr,
+    mut reader: PipelineReader,
+    pending: Arc<std::sync::Mutex<PendingRequests>>,
+) {
+    if let Err(error) = try_read_responses(&mut reader, &pending).await {
+        error!("Failed to read the pipelined responses by client: {client_address}: {error}");
+    }
+    fail_pending_requests(&pending);
+}
+
+async fn try_read_responses(
+    reader: &mut PipelineReader,
+    pending: &std::sync::Mutex<PendingRequests>,
+) -> Result<(), IggyError> {
+    let mut response_buffer = [0u8; RESPONSE_INITIAL_BYTES_LENGTH];
+    loop {
+        reader
+            .read_exact(&mut response_buffer)
+            .await
+            .map_err(|_| IggyError::Disconnected)?;
+        let request_id = u32::from_le_bytes(
+            response_buffer[..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let status = u32::from_le_bytes(
+            response_buffer[4..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let length = u32::from_le_bytes(
+            response_buffer[8..]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let mut payload = BytesMut::with_capacity(length as usize);
+        payload.put_bytes(0, length as usize);
+        reader
+            .read_exact(&mut payload)
+            .await
+            .map_err(|_| IggyError::Disconnected)?;
+
+        trace!(
+            "Received a pipelined TCP response: {request_id}, status: {status}, length: {length}"
+        );
+        let response = if status == 0 {
+            Ok(payload.freeze())
+        } else {
+            Err(map_error_status(status))
+        };
+        match pending.lock().unwrap().responses.remove(&request_id) {
+            // The request might have been cancelled by the caller in the meantime.
+            Some(response_sender) => {
+                let _ = response_sender.send(response);
+            }
+            None => warn!("Received a pipelined TCP response for an unknown request: {request_id}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn cancelled_request_should_be_removed_and_its_frame_fully_written() {
+        let (client_reader, _server_writer) = tokio::io::duplex(64);
+        let (client_writer, mut server_reader) = tokio::io::duplex(1024);
+        let pipeline = TcpPipeline::start(
+            "127.0.0.1:0".parse().unwrap(),
+            Box::new(client_reader),
+            Box::new(client_writer),
+        );
+
+        let payload = Bytes::from(vec![1u8; 100]);
+        let result =
+            tokio::time::timeout(Duration::from_millis(50), pipeline.send(1, payload)).await;
+        assert!(result.is_err());
+        assert!(pipeline.pending.lock().unwrap().responses.is_empty());
+
+        let mut frame = [0u8; 112];
+        server_reader.read_exact(&mut frame).await.unwrap();
+        assert_eq!(u32::from_le_bytes(frame[..4].try_into().unwrap()), 1);
+        assert_eq!(u32::from_le_bytes(frame[4..8].try_into().unwrap()), 104);
+        assert_eq!(&frame[12..], &[1u8; 100]);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::error::IggyError;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// `CleanupPolicy` defines how the messages are removed from the topic, apart from the message expiry and max topic size.
+#[derive(
+    Clone, Copy, Debug, Default, Display, Serialize, Deserialize, EnumString, PartialEq, Eq, Hash,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupPolicy {
+    /// The messages are removed only when the segments are deleted (expired or exceeding the max topic size).
+    #[default]
+    Delete,
+    /// The closed segments are compacted in the background, only the latest message for each key
+    /// (set in the `iggy-message-key` header) is retained, and the tombstones are eventually removed.
+    Compact,
+    /// The segments are removed (expired or exceeding the max topic size) only after all the consumer groups
+    /// (or the ones selected for the topic) have stored the offsets past their end offsets.
+    /// The unconsumed segments are removed anyway when the topic exceeds the hard max size set in the server config.
+    Consumed,
+}
+
+impl CleanupPolicy {
+    /// Returns the code of the cleanup policy.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            CleanupPolicy::Delete => 1,
+            CleanupPolicy::Compact => 2,
+            CleanupPolicy::Consumed => 3,
+        }
+    }
+
+    /// Returns the cleanup policy from the code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(CleanupPolicy::Delete),
+            2 => Ok(CleanupPolicy::Compact),
+            3 => Ok(CleanupPolicy::Consumed),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(CleanupPolicy::Delete.to_string(), "delete");
+        assert_eq!(CleanupPolicy::Compact.to_string(), "compact");
+        assert_eq!(CleanupPolicy::Consumed.to_string(), "consumed");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            CleanupPolicy::from_str("compact").unwrap(),
+            CleanupPolicy::Compact
+        );
+        assert!(CleanupPolicy::from_str("invalid").is_err());
+    }
+
+    #[test]
+    fn test_code() {
+        for policy in [
+            CleanupPolicy::Delete,
+            CleanupPolicy::Compact,
+            CleanupPolicy::Consumed,
+        ] {
+            assert_eq!(CleanupPolicy::from_code(policy.as_code()).unwrap(), policy);
+        }
+        assert!(CleanupPolicy::from_code(0).is_err());
+        assert!(CleanupPolicy::from_code(4).is_err());
+    }
+}
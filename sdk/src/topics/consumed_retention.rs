@@ -0,0 +1,120 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::error::IggyError;
+use crate::utils::topic_size::MaxTopicSize;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::from_utf8;
+
+/// `ConsumedRetention` configures the removal of the segments of the topic with the consumed cleanup policy.
+/// The segments are removed only after the selected consumer groups have stored the offsets past their end offsets,
+/// unless the topic exceeds the hard max size, in which case the oldest segments are removed anyway.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ConsumedRetention {
+    /// Names of the consumer groups which must consume the segments, empty means all the consumer groups of the topic.
+    #[serde(default)]
+    pub consumer_groups: Vec<String>,
+    /// Hard max size of the topic, above which the unconsumed segments are removed, the server default is used if not set.
+    #[serde(default)]
+    pub max_size: MaxTopicSize,
+}
+
+impl BytesSerializable for ConsumedRetention {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(
+            12 + self
+                .consumer_groups
+                .iter()
+                .map(|name| 1 + name.len())
+                .sum::<usize>(),
+        );
+        bytes.put_u64_le(self.max_size.into());
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u32_le(self.consumer_groups.len() as u32);
+        for name in &self.consumer_groups {
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.put_u8(name.len() as u8);
+            bytes.put_slice(name.as_bytes());
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        if bytes.len() < 12 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let max_size = u64::from_le_bytes(
+            bytes[..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let count = u32::from_le_bytes(
+            bytes[8..12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let mut position = 12;
+        let mut consumer_groups = Vec::new();
+        for _ in 0..count {
+            let name_length = *bytes.get(position).ok_or(IggyError::InvalidCommand)? as usize;
+            let name = bytes
+                .get(position + 1..position + 1 + name_length)
+                .ok_or(IggyError::InvalidCommand)?;
+            let name = from_utf8(name)
+                .map_err(|_| IggyError::InvalidUtf8)?
+                .to_string();
+            consumer_groups.push(name);
+            position += 1 + name_length;
+        }
+
+        if position != bytes.len() {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(ConsumedRetention {
+            consumer_groups,
+            max_size: max_size.into(),
+        })
+    }
+}
+
+impl Display for ConsumedRetention {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "consumer_groups: {:?}, max_size: {}",
+            self.consumer_groups, self.max_size
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_and_deserialized() {
+        let retention = ConsumedRetention {
+            consumer_groups: vec!["group-1".to_string(), "group-2".to_string()],
+            max_size: MaxTopicSize::from(1_000_000),
+        };
+
+        let bytes = retention.to_bytes();
+        assert_eq!(ConsumedRetention::from_bytes(bytes).unwrap(), retention);
+    }
+
+    #[test]
+    fn should_not_be_deserialized_given_truncated_consumer_group_name() {
+        let retention = ConsumedRetention {
+            consumer_groups: vec!["group-1".to_string()],
+            max_size: MaxTopicSize::Unlimited,
+        };
+
+        let bytes = retention.to_bytes();
+        assert!(ConsumedRetention::from_bytes(bytes.slice(..bytes.len() - 1)).is_err());
+    }
+}
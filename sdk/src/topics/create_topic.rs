@@ -3,6 +3,7 @@ use crate::command::{Command, CREATE_TOPIC_CODE};
 use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::topics::{MAX_NAME_LENGTH, MAX_PARTITIONS_COUNT};
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::sizeable::Sizeable;
@@ -18,11 +19,14 @@ use std::str::from_utf8;
 /// - `stream_id` - unique stream ID (numeric or name).
 /// - `topic_id` - unique topic ID (numeric).
 /// - `partitions_count` - number of partitions in the topic, max value is 1000.
+/// - `compression_algorithm` - compression algorithm for the topic.
 /// - `message_expiry` - message expiry, if `NeverExpire` then messages will never expire.
 /// - `max_topic_size` - maximum size of the topic, if `Unlimited` then topic size is unlimited.
 ///                      Can't be lower than segment size in the config.
 /// - `replication_factor` - replication factor for the topic.
 /// - `name` - unique topic name, max length is 255 characters.
+/// - `compression_level` - optional compression level, appended after the name for backwards compatibility.
+/// - `cleanup_policy` - cleanup policy for the topic, appended after the compression level for backwards compatibility.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct CreateTopic {
     /// Unique stream ID (numeric or name).
@@ -34,6 +38,9 @@ pub struct CreateTopic {
     pub partitions_count: u32,
     /// Compression algorithm for the topic.
     pub compression_algorithm: CompressionAlgorithm,
+    /// Compression level for the topic, if `None` then the algorithm's default level is used.
+    #[serde(default)]
+    pub compression_level: Option<u8>,
     /// Message expiry, if `NeverExpire` then messages will never expire.
     pub message_expiry: IggyExpiry,
     /// Max topic size, if `Unlimited` then topic size is unlimited.
@@ -41,6 +48,9 @@ pub struct CreateTopic {
     pub max_topic_size: MaxTopicSize,
     /// Replication factor for the topic.
     pub replication_factor: Option<u8>,
+    /// Cleanup policy for the topic, if `Compact` then only the latest message for each key is retained.
+    #[serde(default)]
+    pub cleanup_policy: CleanupPolicy,
     /// Unique topic name, max length is 255 characters.
     pub name: String,
 }
@@ -58,9 +68,11 @@ impl Default for CreateTopic {
             topic_id: Some(1),
             partitions_count: 1,
             compression_algorithm: CompressionAlgorithm::None,
+            compression_level: None,
             message_expiry: IggyExpiry::NeverExpire,
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: None,
+            cleanup_policy: CleanupPolicy::default(),
             name: "topic".to_string(),
         }
     }
@@ -88,6 +100,9 @@ impl Validatable<IggyError> for CreateTopic {
             }
         }
 
+        self.compression_algorithm
+            .validate_level(self.compression_level)?;
+
         Ok(())
     }
 }
@@ -95,7 +110,7 @@ impl Validatable<IggyError> for CreateTopic {
 impl BytesSerializable for CreateTopic {
     fn to_bytes(&self) -> Bytes {
         let stream_id_bytes = self.stream_id.to_bytes();
-        let mut bytes = BytesMut::with_capacity(23 + stream_id_bytes.len() + self.name.len());
+        let mut bytes = BytesMut::with_capacity(25 + stream_id_bytes.len() + self.name.len());
         bytes.put_slice(&stream_id_bytes);
         bytes.put_u32_le(self.topic_id.unwrap_or(0));
         bytes.put_u32_le(self.partitions_count);
@@ -109,6 +124,8 @@ impl BytesSerializable for CreateTopic {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(self.name.len() as u8);
         bytes.put_slice(self.name.as_bytes());
+        bytes.put_u8(self.compression_level.unwrap_or(0));
+        bytes.put_u8(self.cleanup_policy.as_code());
         bytes.freeze()
     }
 
@@ -154,14 +171,24 @@ impl BytesSerializable for CreateTopic {
         if name.len() != name_length as usize {
             return Err(IggyError::InvalidCommand);
         }
+        let compression_level = match bytes.get(position + 27 + name_length as usize) {
+            None | Some(0) => None,
+            Some(level) => Some(*level),
+        };
+        let cleanup_policy = match bytes.get(position + 28 + name_length as usize) {
+            None | Some(0) => CleanupPolicy::default(),
+            Some(code) => CleanupPolicy::from_code(*code)?,
+        };
         let command = CreateTopic {
             stream_id,
             topic_id,
             partitions_count,
             compression_algorithm,
+            compression_level,
             message_expiry,
             max_topic_size,
             replication_factor,
+            cleanup_policy,
             name,
         };
         Ok(command)
@@ -196,9 +223,11 @@ mod tests {
             topic_id: Some(2),
             partitions_count: 3,
             message_expiry: IggyExpiry::NeverExpire,
-            compression_algorithm: CompressionAlgorithm::None,
+            compression_algorithm: CompressionAlgorithm::Gzip,
+            compression_level: Some(9),
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: Some(1),
+            cleanup_policy: CleanupPolicy::Compact,
             name: "test".to_string(),
         };
         let bytes = command.to_bytes();
@@ -220,6 +249,9 @@ mod tests {
         let name = from_utf8(&bytes[position + 27..(position + 27 + name_length as usize)])
             .unwrap()
             .to_string();
+        let compression_level = bytes[position + 27 + name_length as usize];
+        let cleanup_policy =
+            CleanupPolicy::from_code(bytes[position + 28 + name_length as usize]).unwrap();
 
         assert!(!bytes.is_empty());
         assert_eq!(stream_id, command.stream_id);
@@ -231,6 +263,9 @@ mod tests {
         assert_eq!(replication_factor, command.replication_factor.unwrap());
         assert_eq!(name.len() as u8, command.name.len() as u8);
         assert_eq!(name, command.name);
+        assert_eq!(compression_level, command.compression_level.unwrap());
+        assert_eq!(cleanup_policy, command.cleanup_policy);
+        assert_eq!(CreateTopic::from_bytes(bytes).unwrap(), command);
     }
 
     #[test]
@@ -269,5 +304,7 @@ mod tests {
         assert_eq!(command.max_topic_size, max_topic_size);
         assert_eq!(command.replication_factor.unwrap(), replication_factor);
         assert_eq!(command.partitions_count, partitions_count);
+        assert_eq!(command.compression_level, None);
+        assert_eq!(command.cleanup_policy, CleanupPolicy::Delete);
     }
 }
@@ -1,8 +1,12 @@
+pub mod cleanup_policy;
+pub mod consumed_retention;
 pub mod create_topic;
 pub mod delete_topic;
 pub mod get_topic;
 pub mod get_topics;
 pub mod purge_topic;
+pub mod replication_factor;
+pub mod set_topic_retention;
 pub mod update_topic;
 
 const MAX_NAME_LENGTH: usize = 255;
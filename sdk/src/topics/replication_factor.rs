@@ -0,0 +1,24 @@
+use std::fmt::{Display, Formatter};
+
+/// `ReplicationFactor` is the number of the cluster nodes storing the copies of the topic partitions,
+/// including the leader, it cannot exceed the number of the nodes in the cluster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReplicationFactor(pub u8);
+
+impl From<u8> for ReplicationFactor {
+    fn from(value: u8) -> Self {
+        ReplicationFactor(value)
+    }
+}
+
+impl From<ReplicationFactor> for u8 {
+    fn from(value: ReplicationFactor) -> Self {
+        value.0
+    }
+}
+
+impl Display for ReplicationFactor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, SET_TOPIC_RETENTION_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::topics::consumed_retention::ConsumedRetention;
+use crate::topics::MAX_NAME_LENGTH;
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `SetTopicRetention` command is used to set the consumed retention of an existing topic.
+/// It has additional payload:
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `topic_id` - unique topic ID (numeric or name).
+/// - `retention` - the consumer groups which must consume the segments before they are removed (all if empty),
+///   and the hard max size of the topic, above which the unconsumed segments are removed anyway.
+///   It only applies to the topics with the consumed cleanup policy.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SetTopicRetention {
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    #[serde(skip)]
+    pub topic_id: Identifier,
+    /// New consumed retention of the topic.
+    #[serde(flatten)]
+    pub retention: ConsumedRetention,
+}
+
+impl Command for SetTopicRetention {
+    fn code(&self) -> u32 {
+        SET_TOPIC_RETENTION_CODE
+    }
+}
+
+impl Validatable<IggyError> for SetTopicRetention {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self
+            .retention
+            .consumer_groups
+            .iter()
+            .any(|name| name.is_empty() || name.len() > MAX_NAME_LENGTH)
+        {
+            return Err(IggyError::InvalidConsumerGroupName);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for SetTopicRetention {
+    fn to_bytes(&self) -> Bytes {
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.to_bytes();
+        let retention_bytes = self.retention.to_bytes();
+        let mut bytes = BytesMut::with_capacity(
+            stream_id_bytes.len() + topic_id_bytes.len() + retention_bytes.len(),
+        );
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_slice(&retention_bytes);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<SetTopicRetention, IggyError> {
+        if bytes.len() < 18 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone())?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        let retention = ConsumedRetention::from_bytes(bytes.slice(position..))?;
+        Ok(SetTopicRetention {
+            stream_id,
+            topic_id,
+            retention,
+        })
+    }
+}
+
+impl Display for SetTopicRetention {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}|{}", self.stream_id, self.topic_id, self.retention)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::topic_size::MaxTopicSize;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = SetTopicRetention {
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::named("test").unwrap(),
+            retention: get_retention(),
+        };
+
+        let bytes = command.to_bytes();
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        let retention = ConsumedRetention::from_bytes(bytes.slice(position..)).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(stream_id, command.stream_id);
+        assert_eq!(topic_id, command.topic_id);
+        assert_eq!(retention, command.retention);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let stream_id = Identifier::named("stream").unwrap();
+        let topic_id = Identifier::numeric(2).unwrap();
+        let retention = get_retention();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&stream_id.to_bytes());
+        bytes.put_slice(&topic_id.to_bytes());
+        bytes.put_slice(&retention.to_bytes());
+
+        let command = SetTopicRetention::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.stream_id, stream_id);
+        assert_eq!(command.topic_id, topic_id);
+        assert_eq!(command.retention, retention);
+    }
+
+    #[test]
+    fn should_not_be_valid_given_empty_consumer_group_name() {
+        let command = SetTopicRetention {
+            retention: ConsumedRetention {
+                consumer_groups: vec![String::new()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert!(command.validate().is_err());
+    }
+
+    fn get_retention() -> ConsumedRetention {
+        ConsumedRetention {
+            consumer_groups: vec!["group-1".to_string()],
+            max_size: MaxTopicSize::from(1_000_000),
+        }
+    }
+}
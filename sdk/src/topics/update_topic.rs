@@ -3,6 +3,7 @@ use crate::command::{Command, UPDATE_TOPIC_CODE};
 use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::topics::MAX_NAME_LENGTH;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::sizeable::Sizeable;
@@ -17,11 +18,14 @@ use std::str::from_utf8;
 /// It has additional payload:
 /// - `stream_id` - unique stream ID (numeric or name).
 /// - `topic_id` - unique topic ID (numeric or name).
+/// - `compression_algorithm` - compression algorithm for the topic.
 /// - `message_expiry` - message expiry, if `NeverExpire` then messages will never expire.
 /// - `max_topic_size` - maximum size of the topic in bytes, if `Unlimited` then topic size is unlimited.
 ///                      Can't be lower than segment size in the config.
 /// - `replication_factor` - replication factor for the topic.
 /// - `name` - unique topic name, max length is 255 characters.
+/// - `compression_level` - optional compression level, appended after the name for backwards compatibility.
+/// - `cleanup_policy` - cleanup policy for the topic, appended after the compression level for backwards compatibility.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct UpdateTopic {
     /// Unique stream ID (numeric or name).
@@ -32,6 +36,9 @@ pub struct UpdateTopic {
     pub topic_id: Identifier,
     /// Compression algorithm for the topic.
     pub compression_algorithm: CompressionAlgorithm,
+    /// Compression level for the topic, if `None` then the algorithm's default level is used.
+    #[serde(default)]
+    pub compression_level: Option<u8>,
     /// Message expiry, if `NeverExpire` then messages will never expire.
     pub message_expiry: IggyExpiry,
     /// Max topic size, if `Unlimited` then topic size is unlimited.
@@ -39,6 +46,9 @@ pub struct UpdateTopic {
     pub max_topic_size: MaxTopicSize,
     /// Replication factor for the topic.
     pub replication_factor: Option<u8>,
+    /// Cleanup policy for the topic, if `Compact` then only the latest message for each key is retained.
+    #[serde(default)]
+    pub cleanup_policy: CleanupPolicy,
     /// Unique topic name, max length is 255 characters.
     pub name: String,
 }
@@ -55,9 +65,11 @@ impl Default for UpdateTopic {
             stream_id: Identifier::default(),
             topic_id: Identifier::default(),
             compression_algorithm: Default::default(),
+            compression_level: None,
             message_expiry: IggyExpiry::NeverExpire,
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: None,
+            cleanup_policy: CleanupPolicy::default(),
             name: "topic".to_string(),
         }
     }
@@ -75,6 +87,9 @@ impl Validatable<IggyError> for UpdateTopic {
             }
         }
 
+        self.compression_algorithm
+            .validate_level(self.compression_level)?;
+
         Ok(())
     }
 }
@@ -84,7 +99,7 @@ impl BytesSerializable for UpdateTopic {
         let stream_id_bytes = self.stream_id.to_bytes();
         let topic_id_bytes = self.topic_id.to_bytes();
         let mut bytes = BytesMut::with_capacity(
-            19 + stream_id_bytes.len() + topic_id_bytes.len() + self.name.len(),
+            21 + stream_id_bytes.len() + topic_id_bytes.len() + self.name.len(),
         );
         bytes.put_slice(&stream_id_bytes.clone());
         bytes.put_slice(&topic_id_bytes.clone());
@@ -98,6 +113,8 @@ impl BytesSerializable for UpdateTopic {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(self.name.len() as u8);
         bytes.put_slice(self.name.as_bytes());
+        bytes.put_u8(self.compression_level.unwrap_or(0));
+        bytes.put_u8(self.cleanup_policy.as_code());
         bytes.freeze()
     }
 
@@ -135,13 +152,23 @@ impl BytesSerializable for UpdateTopic {
         if name.len() != name_length as usize {
             return Err(IggyError::InvalidCommand);
         }
+        let compression_level = match bytes.get(position + 18 + name_length as usize) {
+            None | Some(0) => None,
+            Some(level) => Some(*level),
+        };
+        let cleanup_policy = match bytes.get(position + 19 + name_length as usize) {
+            None | Some(0) => CleanupPolicy::default(),
+            Some(code) => CleanupPolicy::from_code(*code)?,
+        };
         let command = UpdateTopic {
             stream_id,
             topic_id,
             compression_algorithm,
+            compression_level,
             message_expiry,
             max_topic_size,
             replication_factor,
+            cleanup_policy,
             name,
         };
         Ok(command)
@@ -174,10 +201,12 @@ mod tests {
         let command = UpdateTopic {
             stream_id: Identifier::numeric(1).unwrap(),
             topic_id: Identifier::numeric(2).unwrap(),
-            compression_algorithm: CompressionAlgorithm::None,
+            compression_algorithm: CompressionAlgorithm::Zstd,
+            compression_level: Some(3),
             message_expiry: IggyExpiry::NeverExpire,
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: Some(1),
+            cleanup_policy: CleanupPolicy::Compact,
             name: "test".to_string(),
         };
 
@@ -199,6 +228,9 @@ mod tests {
         let name = from_utf8(&bytes[position + 18..position + 18 + name_length as usize])
             .unwrap()
             .to_string();
+        let compression_level = bytes[position + 18 + name_length as usize];
+        let cleanup_policy =
+            CleanupPolicy::from_code(bytes[position + 19 + name_length as usize]).unwrap();
 
         assert!(!bytes.is_empty());
         assert_eq!(stream_id, command.stream_id);
@@ -209,6 +241,9 @@ mod tests {
         assert_eq!(replication_factor, command.replication_factor.unwrap());
         assert_eq!(name.len() as u8, command.name.len() as u8);
         assert_eq!(name, command.name);
+        assert_eq!(compression_level, command.compression_level.unwrap());
+        assert_eq!(cleanup_policy, command.cleanup_policy);
+        assert_eq!(UpdateTopic::from_bytes(bytes).unwrap(), command);
     }
 
     #[test]
@@ -247,5 +282,7 @@ mod tests {
         assert_eq!(command.max_topic_size, max_topic_size);
         assert_eq!(command.replication_factor, Some(replication_factor));
         assert_eq!(command.name, name);
+        assert_eq!(command.compression_level, None);
+        assert_eq!(command.cleanup_policy, CleanupPolicy::Delete);
     }
 }
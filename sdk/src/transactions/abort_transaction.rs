@@ -0,0 +1,89 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, ABORT_TRANSACTION_CODE};
+use crate::error::IggyError;
+use crate::validatable::Validatable;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `AbortTransaction` command is used to abort the transaction, the messages appended within it will never be visible
+/// to the consumers polling with the `read committed` isolation level.
+/// It has additional payload:
+/// - `transaction_id` - unique transaction ID.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct AbortTransaction {
+    /// Unique transaction ID.
+    #[serde(skip)]
+    pub transaction_id: u64,
+}
+
+impl Command for AbortTransaction {
+    fn code(&self) -> u32 {
+        ABORT_TRANSACTION_CODE
+    }
+}
+
+impl Validatable<IggyError> for AbortTransaction {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for AbortTransaction {
+    fn to_bytes(&self) -> Bytes {
+        Bytes::copy_from_slice(&self.transaction_id.to_le_bytes())
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<AbortTransaction, IggyError> {
+        if bytes.len() != 8 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let transaction_id = u64::from_le_bytes(
+            bytes
+                .as_ref()
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let command = AbortTransaction { transaction_id };
+        Ok(command)
+    }
+}
+
+impl Display for AbortTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.transaction_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = AbortTransaction { transaction_id: 1 };
+
+        let bytes = command.to_bytes();
+        let transaction_id = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+
+        assert_eq!(bytes.len(), 8);
+        assert_eq!(transaction_id, command.transaction_id);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let transaction_id = 1u64;
+        let bytes = Bytes::copy_from_slice(&transaction_id.to_le_bytes());
+
+        let command = AbortTransaction::from_bytes(bytes);
+        assert!(command.is_ok());
+        assert_eq!(command.unwrap().transaction_id, transaction_id);
+    }
+
+    #[test]
+    fn should_not_be_deserialized_from_invalid_bytes() {
+        let command = AbortTransaction::from_bytes(Bytes::from_static(&[0, 1, 2]));
+        assert!(command.is_err());
+    }
+}
@@ -0,0 +1,71 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, BEGIN_TRANSACTION_CODE};
+use crate::error::IggyError;
+use crate::validatable::Validatable;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `BeginTransaction` command is used to begin a new transaction, which allows to atomically append the messages
+/// to the multiple partitions and topics, and to store the consumer offsets once the transaction is committed.
+/// The ID of the transaction is returned and has to be set in the `iggy-transaction-id` header of the sent messages.
+/// It has no additional payload.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct BeginTransaction {}
+
+impl Command for BeginTransaction {
+    fn code(&self) -> u32 {
+        BEGIN_TRANSACTION_CODE
+    }
+}
+
+impl Validatable<IggyError> for BeginTransaction {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for BeginTransaction {
+    fn to_bytes(&self) -> Bytes {
+        Bytes::new()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<BeginTransaction, IggyError> {
+        if !bytes.is_empty() {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let command = BeginTransaction {};
+        Ok(command)
+    }
+}
+
+impl Display for BeginTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_empty_bytes() {
+        let command = BeginTransaction {};
+        let bytes = command.to_bytes();
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn should_be_deserialized_from_empty_bytes() {
+        let command = BeginTransaction::from_bytes(Bytes::new());
+        assert!(command.is_ok());
+    }
+
+    #[test]
+    fn should_not_be_deserialized_from_non_empty_bytes() {
+        let command = BeginTransaction::from_bytes(Bytes::from_static(&[0]));
+        assert!(command.is_err());
+    }
+}
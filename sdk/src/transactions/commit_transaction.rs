@@ -0,0 +1,249 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, COMMIT_TRANSACTION_CODE};
+use crate::consumer::{Consumer, ConsumerKind};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::utils::byte_size::IggyByteSize;
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `CommitTransaction` command is used to commit the transaction, which makes the messages appended within it
+/// visible to the consumers polling with the `read committed` isolation level, and stores the provided consumer offsets.
+/// It has additional payload:
+/// - `transaction_id` - unique transaction ID.
+/// - `offsets` - collection of the consumer offsets to be stored along with the transaction (e.g. consume-transform-produce).
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CommitTransaction {
+    /// Unique transaction ID.
+    #[serde(skip)]
+    pub transaction_id: u64,
+    /// Collection of the consumer offsets to be stored along with the transaction.
+    #[serde(default)]
+    pub offsets: Vec<TransactionOffset>,
+}
+
+/// `TransactionOffset` represents the consumer offset stored when the transaction is committed.
+/// It consists of the following fields:
+/// - `consumer` - the consumer that is storing the offset, either the regular consumer or the consumer group.
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `topic_id` - unique topic ID (numeric or name).
+/// - `partition_id` - partition ID on which the offset is stored. Has to be specified for the regular consumer. For consumer group it is ignored (use `None`).
+/// - `offset` - offset to store.
+/// - `generation` - optional consumer group generation, the offset is rejected if the group has been rebalanced since then.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct TransactionOffset {
+    /// The consumer that is storing the offset, either the regular consumer or the consumer group.
+    #[serde(flatten)]
+    pub consumer: Consumer,
+    /// Unique stream ID (numeric or name).
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    pub topic_id: Identifier,
+    /// Partition ID on which the offset is stored. Has to be specified for the regular consumer. For consumer group it is ignored (use `None`).
+    pub partition_id: Option<u32>,
+    /// Offset to store.
+    pub offset: u64,
+    /// Consumer group generation known to the member, `None` skips the validation.
+    #[serde(default)]
+    pub generation: Option<u32>,
+}
+
+impl Command for CommitTransaction {
+    fn code(&self) -> u32 {
+        COMMIT_TRANSACTION_CODE
+    }
+}
+
+impl Validatable<IggyError> for CommitTransaction {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for CommitTransaction {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(8);
+        bytes.put_u64_le(self.transaction_id);
+        for offset in &self.offsets {
+            bytes.put_slice(&offset.to_bytes());
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<CommitTransaction, IggyError> {
+        if bytes.len() < 8 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let transaction_id = u64::from_le_bytes(
+            bytes[..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let mut position = 8;
+        let mut offsets = Vec::new();
+        while position < bytes.len() {
+            let offset = TransactionOffset::from_bytes(bytes.slice(position..))?;
+            position += offset.get_size_bytes().as_bytes_usize();
+            offsets.push(offset);
+        }
+
+        let command = CommitTransaction {
+            transaction_id,
+            offsets,
+        };
+        Ok(command)
+    }
+}
+
+impl Sizeable for TransactionOffset {
+    fn get_size_bytes(&self) -> IggyByteSize {
+        // Consumer kind + consumer ID + stream ID + topic ID + partition ID + offset + generation
+        (1 + self.consumer.id.get_size_bytes().as_bytes_u64()
+            + self.stream_id.get_size_bytes().as_bytes_u64()
+            + self.topic_id.get_size_bytes().as_bytes_u64()
+            + 16)
+            .into()
+    }
+}
+
+impl BytesSerializable for TransactionOffset {
+    fn to_bytes(&self) -> Bytes {
+        let consumer_bytes = self.consumer.to_bytes();
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(
+            16 + consumer_bytes.len() + stream_id_bytes.len() + topic_id_bytes.len(),
+        );
+        bytes.put_slice(&consumer_bytes);
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(self.partition_id.unwrap_or(0));
+        bytes.put_u64_le(self.offset);
+        bytes.put_u32_le(self.generation.unwrap_or(0));
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<TransactionOffset, IggyError> {
+        if bytes.len() < 25 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let consumer_kind = ConsumerKind::from_code(bytes[0])?;
+        let consumer_id = Identifier::from_bytes(bytes.slice(1..))?;
+        position += 1 + consumer_id.get_size_bytes().as_bytes_usize();
+        let consumer = Consumer {
+            kind: consumer_kind,
+            id: consumer_id,
+        };
+        let stream_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        if bytes.len() < position + 16 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let partition_id = u32::from_le_bytes(
+            bytes[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let offset = u64::from_le_bytes(
+            bytes[position + 4..position + 12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let generation = u32::from_le_bytes(
+            bytes[position + 12..position + 16]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        Ok(TransactionOffset {
+            consumer,
+            stream_id,
+            topic_id,
+            partition_id: if partition_id == 0 {
+                None
+            } else {
+                Some(partition_id)
+            },
+            offset,
+            generation: if generation == 0 {
+                None
+            } else {
+                Some(generation)
+            },
+        })
+    }
+}
+
+impl Display for CommitTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.transaction_id, self.offsets.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_and_deserialized_from_bytes() {
+        let command = CommitTransaction {
+            transaction_id: 1,
+            offsets: vec![
+                TransactionOffset {
+                    consumer: Consumer::new(Identifier::numeric(1).unwrap()),
+                    stream_id: Identifier::numeric(2).unwrap(),
+                    topic_id: Identifier::named("test").unwrap(),
+                    partition_id: Some(3),
+                    offset: 4,
+                    generation: None,
+                },
+                TransactionOffset {
+                    consumer: Consumer::group(Identifier::named("group").unwrap()),
+                    stream_id: Identifier::named("stream").unwrap(),
+                    topic_id: Identifier::numeric(5).unwrap(),
+                    partition_id: None,
+                    offset: 6,
+                    generation: Some(7),
+                },
+            ],
+        };
+
+        let bytes = command.to_bytes();
+        let transaction_id = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let deserialized_command = CommitTransaction::from_bytes(bytes).unwrap();
+
+        assert_eq!(transaction_id, command.transaction_id);
+        assert_eq!(deserialized_command, command);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes_without_offsets() {
+        let transaction_id = 1u64;
+        let bytes = Bytes::copy_from_slice(&transaction_id.to_le_bytes());
+
+        let command = CommitTransaction::from_bytes(bytes);
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.transaction_id, transaction_id);
+        assert!(command.offsets.is_empty());
+    }
+
+    #[test]
+    fn should_not_be_deserialized_from_truncated_offset() {
+        let mut bytes = BytesMut::new();
+        bytes.put_u64_le(1);
+        bytes.put_slice(&[1, 1, 4, 1, 0, 0, 0]);
+
+        let command = CommitTransaction::from_bytes(bytes.freeze());
+        assert!(command.is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, LOGIN_WITH_TOKEN_CODE};
+use crate::error::IggyError;
+use crate::users::defaults::*;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::from_utf8;
+
+/// `LoginWithToken` command is used to login the user with a JWT issued by the external identity provider (OpenID Connect), instead of the username and password.
+/// The token is verified by the server using the keys (JWKS) of the configured issuer, and its claims are mapped to the user.
+/// It has additional payload:
+/// - `token` - JWT issued by the external identity provider
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LoginWithToken {
+    /// JWT issued by the external identity provider
+    pub token: String,
+}
+
+impl Command for LoginWithToken {
+    fn code(&self) -> u32 {
+        LOGIN_WITH_TOKEN_CODE
+    }
+}
+
+impl Default for LoginWithToken {
+    fn default() -> Self {
+        LoginWithToken {
+            token: "token".to_string(),
+        }
+    }
+}
+
+impl Validatable<IggyError> for LoginWithToken {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.token.is_empty() || self.token.len() > MAX_IDENTITY_TOKEN_LENGTH {
+            return Err(IggyError::InvalidAccessToken);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for LoginWithToken {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(4 + self.token.len());
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u32_le(self.token.len() as u32);
+        bytes.put_slice(self.token.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<LoginWithToken, IggyError> {
+        if bytes.len() < 5 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let token_length = u32::from_le_bytes(
+            bytes[0..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        if bytes.len() != 4 + token_length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let token = from_utf8(&bytes[4..4 + token_length])
+            .map_err(|_| IggyError::InvalidUtf8)?
+            .to_string();
+        let command = LoginWithToken { token };
+        Ok(command)
+    }
+}
+
+impl Display for LoginWithToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "******")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = LoginWithToken {
+            token: "header.payload.signature".to_string(),
+        };
+
+        let bytes = command.to_bytes();
+        let token_length = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        let token = from_utf8(&bytes[4..4 + token_length]).unwrap();
+        assert!(!bytes.is_empty());
+        assert_eq!(token, command.token);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let token = "header.payload.signature";
+        let mut bytes = BytesMut::new();
+        bytes.put_u32_le(token.len() as u32);
+        bytes.put_slice(token.as_bytes());
+
+        let command = LoginWithToken::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.token, token);
+    }
+
+    #[test]
+    fn token_longer_than_limit_should_be_invalid() {
+        let command = LoginWithToken {
+            token: "a".repeat(MAX_IDENTITY_TOKEN_LENGTH + 1),
+        };
+
+        assert!(command.validate().is_err());
+    }
+}
@@ -5,6 +5,8 @@ pub mod delete_user;
 pub mod get_user;
 pub mod get_users;
 pub mod login_user;
+pub mod login_with_token;
 pub mod logout_user;
+pub mod set_user_quota;
 pub mod update_permissions;
 pub mod update_user;
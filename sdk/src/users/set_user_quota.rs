@@ -0,0 +1,113 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, SET_USER_QUOTA_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::models::quota::Quota;
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `SetUserQuota` command is used to set the quota of an existing user.
+/// It has additional payload:
+/// - `user_id` - unique user ID (numeric or name).
+/// - `quota` - the limits of the stored messages size, topics and partitions count (of all the streams created by the user),
+///   and the produce and consume throughput of the user. The quota with all the limits set to 0 removes the existing quota.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SetUserQuota {
+    /// Unique user ID (numeric or name).
+    #[serde(skip)]
+    pub user_id: Identifier,
+    /// New quota of the user, each limit set to 0 means unlimited.
+    #[serde(flatten)]
+    pub quota: Quota,
+}
+
+impl Command for SetUserQuota {
+    fn code(&self) -> u32 {
+        SET_USER_QUOTA_CODE
+    }
+}
+
+impl Validatable<IggyError> for SetUserQuota {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for SetUserQuota {
+    fn to_bytes(&self) -> Bytes {
+        let user_id_bytes = self.user_id.to_bytes();
+        let quota_bytes = self.quota.to_bytes();
+        let mut bytes = BytesMut::with_capacity(user_id_bytes.len() + quota_bytes.len());
+        bytes.put_slice(&user_id_bytes);
+        bytes.put_slice(&quota_bytes);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<SetUserQuota, IggyError> {
+        if bytes.len() < 4 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let user_id = Identifier::from_bytes(bytes.clone())?;
+        let position = user_id.get_size_bytes().as_bytes_usize();
+        let quota = Quota::from_bytes(bytes.slice(position..))?;
+        Ok(SetUserQuota { user_id, quota })
+    }
+}
+
+impl Display for SetUserQuota {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.user_id, self.quota)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = SetUserQuota {
+            user_id: Identifier::numeric(1).unwrap(),
+            quota: get_quota(),
+        };
+
+        let bytes = command.to_bytes();
+        let user_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        let position = user_id.get_size_bytes().as_bytes_usize();
+        let quota = Quota::from_bytes(bytes.slice(position..)).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(user_id, command.user_id);
+        assert_eq!(quota, command.quota);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let user_id = Identifier::named("user").unwrap();
+        let quota = get_quota();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&user_id.to_bytes());
+        bytes.put_slice(&quota.to_bytes());
+
+        let command = SetUserQuota::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.user_id, user_id);
+        assert_eq!(command.quota, quota);
+    }
+
+    fn get_quota() -> Quota {
+        Quota {
+            max_size: 1_000_000,
+            max_topics: 5,
+            max_partitions: 50,
+            max_produce_bytes_per_second: 1000,
+            max_consume_bytes_per_second: 2000,
+        }
+    }
+}
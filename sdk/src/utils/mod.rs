@@ -4,7 +4,9 @@ pub mod crypto;
 pub mod duration;
 pub mod expiry;
 pub mod personal_access_token_expiry;
+pub mod server_address;
 pub mod sizeable;
 pub mod text;
 pub mod timestamp;
+pub(crate) mod tls;
 pub mod topic_size;
@@ -0,0 +1,218 @@
+use crate::error::IggyError;
+use rand::seq::SliceRandom;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The policy of selecting the server to connect to, when the multiple server addresses are configured.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ServerAddressSelection {
+    /// Stays connected to the same server until it fails, and then moves to the next one in the configured order,
+    /// e.g. from the primary to the standby server.
+    #[default]
+    Ordered,
+    /// Tries the servers in a random order.
+    Random,
+    /// Starts from the server following the one used most recently, which spreads the connections across all the servers.
+    RoundRobin,
+}
+
+impl FromStr for ServerAddressSelection {
+    type Err = IggyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ordered" => Ok(ServerAddressSelection::Ordered),
+            "random" => Ok(ServerAddressSelection::Random),
+            "round_robin" => Ok(ServerAddressSelection::RoundRobin),
+            _ => Err(IggyError::InvalidConfiguration),
+        }
+    }
+}
+
+impl Display for ServerAddressSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerAddressSelection::Ordered => write!(f, "ordered"),
+            ServerAddressSelection::Random => write!(f, "random"),
+            ServerAddressSelection::RoundRobin => write!(f, "round_robin"),
+        }
+    }
+}
+
+/// The addresses of all the servers, which the client can connect to.
+#[derive(Debug)]
+pub(crate) struct ServerAddresses<T> {
+    addresses: Vec<T>,
+    selection: ServerAddressSelection,
+    current: AtomicUsize,
+}
+
+impl<T> ServerAddresses<T> {
+    /// Parses the addresses of the multiple servers using the provided parser,
+    /// or the single server address, if no multiple addresses are configured.
+    pub fn parse(
+        server_address: &str,
+        server_addresses: &[String],
+        selection: ServerAddressSelection,
+        parser: impl Fn(&str) -> Result<T, IggyError>,
+    ) -> Result<Self, IggyError> {
+        let addresses = if server_addresses.is_empty() {
+            vec![parser(server_address)?]
+        } else {
+            server_addresses
+                .iter()
+                .map(|address| parser(address))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        Ok(Self {
+            addresses,
+            selection,
+            current: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns the address of the server used most recently.
+    pub fn current(&self) -> &T {
+        &self.addresses[self.current.load(Ordering::Acquire)]
+    }
+
+    /// Returns all the addresses (with their indexes) in the order in which they should be tried by the next connection.
+    pub fn candidates(&self) -> Vec<(usize, &T)> {
+        let count = self.addresses.len();
+        let current = self.current.load(Ordering::Acquire);
+        let mut indexes = match self.selection {
+            ServerAddressSelection::Ordered => (0..count)
+                .map(|offset| (current + offset) % count)
+                .collect::<Vec<_>>(),
+            ServerAddressSelection::RoundRobin => (1..=count)
+                .map(|offset| (current + offset) % count)
+                .collect::<Vec<_>>(),
+            ServerAddressSelection::Random => (0..count).collect::<Vec<_>>(),
+        };
+        if self.selection == ServerAddressSelection::Random {
+            indexes.shuffle(&mut rand::rng());
+        }
+
+        indexes
+            .into_iter()
+            .map(|index| (index, &self.addresses[index]))
+            .collect()
+    }
+
+    /// Marks the server as the one used most recently.
+    pub fn set_current(&self, index: usize) {
+        self.current.store(index, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_addresses(selection: ServerAddressSelection) -> ServerAddresses<String> {
+        let server_addresses = ["server1:8090", "server2:8090", "server3:8090"]
+            .map(str::to_owned)
+            .to_vec();
+        ServerAddresses::parse("server0:8090", &server_addresses, selection, |address| {
+            Ok(address.to_owned())
+        })
+        .unwrap()
+    }
+
+    fn get_candidates(addresses: &ServerAddresses<String>) -> Vec<&str> {
+        addresses
+            .candidates()
+            .into_iter()
+            .map(|(_, address)| address.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn ordered_selection_should_stay_on_current_server_and_fail_over_to_next_ones() {
+        let addresses = create_addresses(ServerAddressSelection::Ordered);
+        assert_eq!(
+            get_candidates(&addresses),
+            vec!["server1:8090", "server2:8090", "server3:8090"]
+        );
+
+        addresses.set_current(1);
+        assert_eq!(addresses.current(), "server2:8090");
+        assert_eq!(
+            get_candidates(&addresses),
+            vec!["server2:8090", "server3:8090", "server1:8090"]
+        );
+    }
+
+    #[test]
+    fn round_robin_selection_should_start_from_server_following_current_one() {
+        let addresses = create_addresses(ServerAddressSelection::RoundRobin);
+        assert_eq!(
+            get_candidates(&addresses),
+            vec!["server2:8090", "server3:8090", "server1:8090"]
+        );
+
+        addresses.set_current(2);
+        assert_eq!(
+            get_candidates(&addresses),
+            vec!["server1:8090", "server2:8090", "server3:8090"]
+        );
+    }
+
+    #[test]
+    fn random_selection_should_return_all_servers() {
+        let addresses = create_addresses(ServerAddressSelection::Random);
+        let mut candidates = get_candidates(&addresses);
+        candidates.sort();
+        assert_eq!(
+            candidates,
+            vec!["server1:8090", "server2:8090", "server3:8090"]
+        );
+    }
+
+    #[test]
+    fn single_server_address_should_be_used_when_no_multiple_addresses_are_configured() {
+        let addresses = ServerAddresses::parse(
+            "server1:8090,server2:8090",
+            &[],
+            ServerAddressSelection::Ordered,
+            |address| Ok(address.to_owned()),
+        )
+        .unwrap();
+        assert_eq!(
+            get_candidates(&addresses),
+            vec!["server1:8090,server2:8090"]
+        );
+    }
+
+    #[test]
+    fn invalid_server_address_should_fail() {
+        let server_addresses = vec!["server1:8090".to_owned(), "server2".to_owned()];
+        let addresses = ServerAddresses::parse(
+            "server0:8090",
+            &server_addresses,
+            ServerAddressSelection::Ordered,
+            |address| {
+                address
+                    .contains(':')
+                    .then(|| address.to_owned())
+                    .ok_or(IggyError::InvalidServerAddress)
+            },
+        );
+        assert!(addresses.is_err());
+    }
+
+    #[test]
+    fn selection_should_be_parsed_and_displayed() {
+        for selection in ["ordered", "random", "round_robin"] {
+            assert_eq!(
+                ServerAddressSelection::from_str(selection)
+                    .unwrap()
+                    .to_string(),
+                selection
+            );
+        }
+        assert!(ServerAddressSelection::from_str("sticky").is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use crate::error::IggyError;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tracing::{error, warn};
+
+/// Installs the default crypto provider, unless any provider has been installed already.
+pub(crate) fn install_crypto_provider() {
+    if CryptoProvider::get_default().is_none() {
+        if let Err(e) = rustls::crypto::ring::default_provider().install_default() {
+            warn!("Failed to install rustls crypto provider. Error: {:?}. This may be normal if another thread installed it first.", e);
+        }
+    }
+}
+
+/// Loads the certificate chain and the private key (both in the PEM format) presented by the client to the server.
+pub(crate) fn load_client_certificate(
+    cert_file: &str,
+    key_file: &str,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), IggyError> {
+    let certificates = CertificateDer::pem_file_iter(cert_file)
+        .map_err(|error| {
+            error!("Failed to read the client certificate file: {cert_file}. {error}");
+            IggyError::InvalidTlsCertificatePath
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| {
+            error!("Failed to read a certificate from the client certificate file: {cert_file}. {error}");
+            IggyError::InvalidTlsCertificate
+        })?;
+    if certificates.is_empty() {
+        error!("Client certificate file: {cert_file} doesn't contain any certificate.");
+        return Err(IggyError::InvalidTlsCertificate);
+    }
+
+    let key = PrivateKeyDer::from_pem_file(key_file).map_err(|error| {
+        error!("Failed to read the client private key file: {key_file}. {error}");
+        IggyError::InvalidTlsPrivateKey
+    })?;
+    Ok((certificates, key))
+}
@@ -2,7 +2,7 @@ use crate::archiver::{Archiver, COMPONENT};
 use crate::configs::server::DiskArchiverConfig;
 use crate::server_error::ArchiverError;
 use error_set::ErrContext;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 use tracing::{debug, info};
 
@@ -15,6 +15,15 @@ impl DiskArchiver {
     pub fn new(config: DiskArchiverConfig) -> Self {
         DiskArchiver { config }
     }
+
+    // The absolute paths of the archived files are stored relative to the archive directory as well.
+    fn get_archive_path(&self, file: &str, base_directory: Option<&str>) -> PathBuf {
+        let file = Path::new(file);
+        let file = file.strip_prefix("/").unwrap_or(file);
+        Path::new(&self.config.path)
+            .join(base_directory.unwrap_or_default())
+            .join(file)
+    }
 }
 
 impl Archiver for DiskArchiver {
@@ -39,8 +48,7 @@ impl Archiver for DiskArchiver {
         base_directory: Option<String>,
     ) -> Result<bool, ArchiverError> {
         debug!("Checking if file: {file} is archived on disk.");
-        let base_directory = base_directory.as_deref().unwrap_or_default();
-        let path = Path::new(&self.config.path).join(base_directory).join(file);
+        let path = self.get_archive_path(file, base_directory.as_deref());
         let is_archived = path.exists();
         debug!("File: {file} is archived: {is_archived}");
         Ok(is_archived)
@@ -61,8 +69,7 @@ impl Archiver for DiskArchiver {
                 });
             }
 
-            let base_directory = base_directory.as_deref().unwrap_or_default();
-            let destination = Path::new(&self.config.path).join(base_directory).join(file);
+            let destination = self.get_archive_path(file, base_directory.as_deref());
             let destination_path = destination.to_str().unwrap_or_default().to_owned();
             fs::create_dir_all(destination.parent().unwrap())
                 .await
@@ -77,4 +84,90 @@ impl Archiver for DiskArchiver {
 
         Ok(())
     }
+    async fn fetch(
+        &self,
+        file: &str,
+        destination: &str,
+        base_directory: Option<String>,
+    ) -> Result<(), ArchiverError> {
+        debug!("Fetching file: {file} from disk to: {destination}");
+        let source = self.get_archive_path(file, base_directory.as_deref());
+        if !source.exists() {
+            return Err(ArchiverError::ArchivedFileNotFound {
+                file_path: file.to_string(),
+            });
+        }
+
+        let destination_path = Path::new(destination);
+        if let Some(parent) = destination_path.parent() {
+            fs::create_dir_all(parent).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to create directory for fetched file: {destination}")
+            })?;
+        }
+        fs::copy(&source, destination_path).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to copy archived file: {file} to destination: {destination}")
+        })?;
+        debug!("Fetched file: {file} to: {destination}");
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        path: &str,
+        base_directory: Option<String>,
+    ) -> Result<Vec<String>, ArchiverError> {
+        debug!("Listing archived files on disk under path: {path}");
+        let base_path =
+            Path::new(&self.config.path).join(base_directory.as_deref().unwrap_or_default());
+        let root = self.get_archive_path(path, base_directory.as_deref());
+        let mut files = Vec::new();
+        let mut directories = vec![root];
+        while let Some(directory) = directories.pop() {
+            if directory.is_file() {
+                files.push(directory);
+                continue;
+            }
+
+            let Ok(mut entries) = fs::read_dir(&directory).await else {
+                continue;
+            };
+
+            while let Some(entry) = entries.next_entry().await.with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read archive directory: {}",
+                    directory.display()
+                )
+            })? {
+                directories.push(entry.path());
+            }
+        }
+
+        let prefix = if path.starts_with('/') { "/" } else { "" };
+        let mut files = files
+            .into_iter()
+            .filter_map(|file| {
+                file.strip_prefix(&base_path)
+                    .ok()
+                    .map(|file| format!("{prefix}{}", file.to_string_lossy()))
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        debug!(
+            "Found {} archived files on disk under path: {path}",
+            files.len()
+        );
+        Ok(files)
+    }
+
+    async fn restore(
+        &self,
+        files: &[&str],
+        base_directory: Option<String>,
+    ) -> Result<(), ArchiverError> {
+        debug!("Restoring files from disk: {:?}", files);
+        for file in files {
+            self.fetch(file, file, base_directory.clone()).await?;
+        }
+        Ok(())
+    }
 }
@@ -47,6 +47,24 @@ pub trait Archiver: Send {
         files: &[&str],
         base_directory: Option<String>,
     ) -> impl Future<Output = Result<(), ArchiverError>> + Send;
+    fn fetch(
+        &self,
+        file: &str,
+        destination: &str,
+        base_directory: Option<String>,
+    ) -> impl Future<Output = Result<(), ArchiverError>> + Send;
+    /// Returns the (sorted) original paths of the archived files located under the given path.
+    fn list(
+        &self,
+        path: &str,
+        base_directory: Option<String>,
+    ) -> impl Future<Output = Result<Vec<String>, ArchiverError>> + Send;
+    /// Restores the archived files to their original paths.
+    fn restore(
+        &self,
+        files: &[&str],
+        base_directory: Option<String>,
+    ) -> impl Future<Output = Result<(), ArchiverError>> + Send;
 }
 
 #[derive(Debug)]
@@ -93,4 +111,38 @@ impl ArchiverKind {
             Self::S3(d) => d.archive(files, base_directory).await,
         }
     }
+
+    pub async fn fetch(
+        &self,
+        file: &str,
+        destination: &str,
+        base_directory: Option<String>,
+    ) -> Result<(), ArchiverError> {
+        match self {
+            Self::Disk(d) => d.fetch(file, destination, base_directory).await,
+            Self::S3(d) => d.fetch(file, destination, base_directory).await,
+        }
+    }
+
+    pub async fn list(
+        &self,
+        path: &str,
+        base_directory: Option<String>,
+    ) -> Result<Vec<String>, ArchiverError> {
+        match self {
+            Self::Disk(d) => d.list(path, base_directory).await,
+            Self::S3(d) => d.list(path, base_directory).await,
+        }
+    }
+
+    pub async fn restore(
+        &self,
+        files: &[&str],
+        base_directory: Option<String>,
+    ) -> Result<(), ArchiverError> {
+        match self {
+            Self::Disk(d) => d.restore(files, base_directory).await,
+            Self::S3(d) => d.restore(files, base_directory).await,
+        }
+    }
 }
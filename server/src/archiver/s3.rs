@@ -176,4 +176,110 @@ impl Archiver for S3Archiver {
         }
         Ok(())
     }
+    async fn fetch(
+        &self,
+        file: &str,
+        destination: &str,
+        base_directory: Option<String>,
+    ) -> Result<(), ArchiverError> {
+        debug!("Fetching file: {file} from S3 to: {destination}");
+        let base_directory = base_directory.as_deref().unwrap_or_default();
+        let source = Path::new(&base_directory).join(file);
+        let source_path = source.to_str().unwrap_or_default().to_owned();
+        let destination_path = Path::new(destination);
+        if let Some(parent) = destination_path.parent() {
+            fs::create_dir_all(parent).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to create directory for fetched file: {destination}")
+            })?;
+        }
+
+        let mut output = fs::File::create(destination_path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to create fetched file: {destination}"
+                )
+            })?;
+        let response = self
+            .bucket
+            .get_object_to_writer(source_path, &mut output)
+            .await;
+        let status = match response {
+            Ok(status) => status,
+            Err(error) => {
+                error!("Cannot fetch file: {file} from S3: {error}");
+                0
+            }
+        };
+        if status == 200 {
+            debug!("Fetched file: {file} from S3 to: {destination}");
+            return Ok(());
+        }
+
+        fs::remove_file(destination_path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to remove fetched file: {destination} after S3 failure")
+            })?;
+        if status == 404 {
+            return Err(ArchiverError::ArchivedFileNotFound {
+                file_path: file.to_string(),
+            });
+        }
+
+        error!("Cannot fetch file: {file} from S3, received an invalid status code: {status}.");
+        Err(ArchiverError::CannotFetchFile {
+            file_path: file.to_string(),
+        })
+    }
+
+    async fn list(
+        &self,
+        path: &str,
+        base_directory: Option<String>,
+    ) -> Result<Vec<String>, ArchiverError> {
+        debug!("Listing archived files on S3 under path: {path}");
+        let base_directory = base_directory.as_deref().unwrap_or_default();
+        let prefix = Path::new(&base_directory).join(path);
+        let prefix = prefix.to_str().unwrap_or_default().to_owned();
+        let response = self.bucket.list(prefix, None).await;
+        let results = match response {
+            Ok(results) => results,
+            Err(error) => {
+                error!("Cannot list archived files under path: {path} on S3: {error}");
+                return Err(ArchiverError::CannotListFiles {
+                    path: path.to_string(),
+                });
+            }
+        };
+
+        let mut files = results
+            .into_iter()
+            .flat_map(|result| result.contents)
+            .map(|object| {
+                Path::new(&object.key)
+                    .strip_prefix(base_directory)
+                    .map(|file| file.to_string_lossy().to_string())
+                    .unwrap_or(object.key)
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        debug!(
+            "Found {} archived files on S3 under path: {path}",
+            files.len()
+        );
+        Ok(files)
+    }
+
+    async fn restore(
+        &self,
+        files: &[&str],
+        base_directory: Option<String>,
+    ) -> Result<(), ArchiverError> {
+        debug!("Restoring files from S3: {:?}", files);
+        for file in files {
+            self.fetch(file, file, base_directory.clone()).await?;
+        }
+        Ok(())
+    }
 }
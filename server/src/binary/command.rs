@@ -1,3 +1,4 @@
+use crate::binary::handlers::archives::restore_archive_handler;
 use crate::binary::handlers::consumer_groups::{
     create_consumer_group_handler, delete_consumer_group_handler, get_consumer_group_handler,
     get_consumer_groups_handler, join_consumer_group_handler, leave_consumer_group_handler,
@@ -9,13 +10,20 @@ use crate::binary::handlers::personal_access_tokens::{
     create_personal_access_token_handler, delete_personal_access_token_handler,
     get_personal_access_tokens_handler, login_with_personal_access_token_handler,
 };
+use crate::binary::handlers::roles::{
+    assign_role_handler, create_role_handler, delete_role_handler, get_role_handler,
+    get_roles_handler, unassign_role_handler, update_role_handler,
+};
 use crate::binary::handlers::streams::*;
 use crate::binary::handlers::system::*;
 use crate::binary::handlers::topics::*;
+use crate::binary::handlers::transactions::{
+    abort_transaction_handler, begin_transaction_handler, commit_transaction_handler,
+};
 use crate::binary::handlers::users::{
     change_password_handler, create_user_handler, delete_user_handler, get_user_handler,
-    get_users_handler, login_user_handler, logout_user_handler, update_permissions_handler,
-    update_user_handler,
+    get_users_handler, login_user_handler, login_with_token_handler, logout_user_handler,
+    set_user_quota_handler, update_permissions_handler, update_user_handler,
 };
 use crate::binary::sender::SenderKind;
 use crate::binary::COMPONENT;
@@ -70,10 +78,16 @@ async fn try_handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("Handling command '{command}', session: {session}...");
+    if command.requires_leader() {
+        system.read().await.ensure_leader()?;
+    }
+
     match command {
         ServerCommand::Ping(command) => {
             ping_handler::handle(command, sender, session, system).await
         }
+        // The protocol version is negotiated by the TCP connection handler, as it changes the framing of the connection.
+        ServerCommand::SetProtocolVersion(_) => Err(IggyError::FeatureUnavailable),
         ServerCommand::GetStats(command) => {
             get_stats_handler::handle(command, sender, session, system).await
         }
@@ -104,15 +118,42 @@ async fn try_handle(
         ServerCommand::UpdatePermissions(command) => {
             update_permissions_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::SetUserQuota(command) => {
+            set_user_quota_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::ChangePassword(command) => {
             change_password_handler::handle(command, sender, session, system).await
         }
         ServerCommand::LoginUser(command) => {
             login_user_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::LoginWithToken(command) => {
+            login_with_token_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::LogoutUser(command) => {
             logout_user_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::GetRole(command) => {
+            get_role_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::GetRoles(command) => {
+            get_roles_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::CreateRole(command) => {
+            create_role_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::DeleteRole(command) => {
+            delete_role_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::UpdateRole(command) => {
+            update_role_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::AssignRole(command) => {
+            assign_role_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::UnassignRole(command) => {
+            unassign_role_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::GetPersonalAccessTokens(command) => {
             get_personal_access_tokens_handler::handle(command, sender, session, system).await
         }
@@ -158,6 +199,9 @@ async fn try_handle(
         ServerCommand::PurgeStream(command) => {
             purge_stream_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::SetStreamQuota(command) => {
+            set_stream_quota_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::GetTopic(command) => {
             get_topic_handler::handle(command, sender, session, system).await
         }
@@ -176,6 +220,9 @@ async fn try_handle(
         ServerCommand::PurgeTopic(command) => {
             purge_topic_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::SetTopicRetention(command) => {
+            set_topic_retention_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::CreatePartitions(command) => {
             create_partitions_handler::handle(command, sender, session, system).await
         }
@@ -200,9 +247,27 @@ async fn try_handle(
         ServerCommand::LeaveConsumerGroup(command) => {
             leave_consumer_group_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::BeginTransaction(command) => {
+            begin_transaction_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::CommitTransaction(command) => {
+            commit_transaction_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::AbortTransaction(command) => {
+            abort_transaction_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::RestoreArchive(command) => {
+            restore_archive_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::FlushUnsavedBuffer(command) => {
             flush_unsaved_buffer_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::NackMessage(command) => {
+            nack_message_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::InitProducer(command) => {
+            init_producer_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::GetSnapshotFile(command) => {
             get_snapshot::handle(command, sender, session, system).await
         }
@@ -0,0 +1,3 @@
+pub mod restore_archive_handler;
+
+pub const COMPONENT: &str = "ARCHIVE_HANDLER";
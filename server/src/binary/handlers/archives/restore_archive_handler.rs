@@ -0,0 +1,76 @@
+use crate::binary::mapper;
+use crate::binary::{handlers::archives::COMPONENT, sender::SenderKind};
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::archives::restore_archive::RestoreArchive;
+use iggy::error::IggyError;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_restore_archive", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id, iggy_stream_id = command.stream_id.as_string()))]
+pub async fn handle(
+    command: RestoreArchive,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let mut archive_restore = system
+        .read()
+        .await
+        .prepare_archive_restore(session, &command)
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to prepare archive restore for stream_id: {}, session: {session}",
+                command.stream_id
+            )
+        })?;
+    archive_restore
+        .fetch_archived_state(command.state_snapshot.as_deref())
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to fetch archived state for stream_id: {}, session: {session}",
+                command.stream_id
+            )
+        })?;
+
+    let mut system = system.write().await;
+    let entries = system
+        .recreate_archived_resources(session, &command, &mut archive_restore)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to recreate archived resources for stream_id: {}, session: {session}",
+                command.stream_id
+            )
+        })?;
+    let system = system.downgrade();
+    for entry in entries {
+        system
+            .state
+            .apply(session.get_user_id(), entry)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to apply recreated archived resources for stream_id: {}, session: {session}",
+                    command.stream_id
+                )
+            })?;
+    }
+    drop(system);
+
+    let restored_archive = archive_restore
+        .restore_partitions(command.range)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to restore archive for stream_id: {}, session: {session}",
+                command.stream_id
+            )
+        })?;
+    let response = mapper::map_restored_archive(&restored_archive);
+    sender.send_ok_response(&response).await?;
+    Ok(())
+}
@@ -26,6 +26,7 @@ pub async fn handle(
                 &command.topic_id,
                 command.group_id,
                 &command.name,
+                command.assignment_strategy,
             )
             .await
             .with_error_context(|error| {
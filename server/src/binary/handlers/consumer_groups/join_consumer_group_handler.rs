@@ -1,3 +1,4 @@
+use crate::binary::mapper;
 use crate::binary::{handlers::consumer_groups::COMPONENT, sender::SenderKind};
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
@@ -16,7 +17,7 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
-    system
+    let membership = system
         .join_consumer_group(
             session,
             &command.stream_id,
@@ -30,6 +31,7 @@ pub async fn handle(
                 command.stream_id, command.topic_id, command.group_id, session
             )
         })?;
-    sender.send_empty_ok_response().await?;
+    let membership = mapper::map_consumer_group_membership(&membership);
+    sender.send_ok_response(&membership).await?;
     Ok(())
 }
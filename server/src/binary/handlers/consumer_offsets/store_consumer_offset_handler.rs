@@ -24,6 +24,7 @@ pub async fn handle(
             &command.topic_id,
             command.partition_id,
             command.offset,
+            command.generation,
         )
         .await
         .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to store consumer offset for stream_id: {}, topic_id: {}, partition_id: {:?}, offset: {}, session: {}",
@@ -0,0 +1,35 @@
+use crate::binary::{handlers::messages::COMPONENT, sender::SenderKind};
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::messages::init_producer::InitProducer;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_init_producer", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id, iggy_stream_id = command.stream_id.as_string(), iggy_topic_id = command.topic_id.as_string(), iggy_producer_id = command.producer_id))]
+pub async fn handle(
+    command: InitProducer,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    let epoch = system
+        .init_producer(
+            session,
+            &command.stream_id,
+            &command.topic_id,
+            command.producer_id,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to init producer with ID: {} for stream_id: {}, topic_id: {}, session: {}",
+                command.producer_id, command.stream_id, command.topic_id, session
+            )
+        })?;
+    sender.send_ok_response(&epoch.to_le_bytes()).await?;
+    Ok(())
+}
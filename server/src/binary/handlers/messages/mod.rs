@@ -1,4 +1,6 @@
 pub mod flush_unsaved_buffer_handler;
+pub mod init_producer_handler;
+pub mod nack_message_handler;
 pub mod poll_messages_handler;
 pub mod send_messages_handler;
 
@@ -0,0 +1,38 @@
+use crate::binary::{handlers::messages::COMPONENT, sender::SenderKind};
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::messages::nack_message::NackMessage;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_nack_message", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id, iggy_stream_id = command.stream_id.as_string(), iggy_topic_id = command.topic_id.as_string(), iggy_partition_id = command.partition_id, iggy_offset = command.offset))]
+pub async fn handle(
+    command: NackMessage,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    system
+        .nack_message(
+            session,
+            &command.consumer,
+            &command.stream_id,
+            &command.topic_id,
+            command.partition_id,
+            command.offset,
+            &command.reason,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to nack message for stream_id: {}, topic_id: {}, partition_id: {}, offset: {}, session: {}",
+                command.stream_id, command.topic_id, command.partition_id, command.offset, session
+            )
+        })?;
+    sender.send_empty_ok_response().await?;
+    Ok(())
+}
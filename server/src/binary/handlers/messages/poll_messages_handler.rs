@@ -1,13 +1,14 @@
 use crate::binary::handlers::messages::COMPONENT;
 use crate::binary::mapper;
-use crate::binary::sender::SenderKind;
+use crate::binary::sender::{Sender, SenderKind};
+use crate::streaming::models::messages::PolledMessagesKind;
 use crate::streaming::session::Session;
 use crate::streaming::systems::messages::PollingArgs;
 use crate::streaming::systems::system::SharedSystem;
 use anyhow::Result;
 use error_set::ErrContext;
 use iggy::error::IggyError;
-use iggy::messages::poll_messages::PollMessages;
+use iggy::messages::poll_messages::{PollMessages, PollingFormat};
 use tracing::debug;
 
 pub async fn handle(
@@ -17,6 +18,48 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
+    let args = PollingArgs::new(
+        command.strategy,
+        command.count,
+        command.auto_commit,
+        command.isolation_level,
+        command.filter,
+    );
+    if command.format == PollingFormat::Batches {
+        if let Some(tcp_sender) = sender.as_zero_copy_sender() {
+            let system = system.read().await;
+            let polled = system
+                .poll_messages_or_batches(
+                    session,
+                    &command.consumer,
+                    &command.stream_id,
+                    &command.topic_id,
+                    command.partition_id,
+                    args,
+                )
+                .await
+                .with_error_context(|error| format!(
+                    "{COMPONENT} (error: {error}) - failed to poll messages or batches for consumer: {}, stream_id: {}, topic_id: {}, partition_id: {:?}, session: {}.",
+                    command.consumer, command.stream_id, command.topic_id, command.partition_id, session
+                ))?;
+            drop(system);
+            match polled {
+                PolledMessagesKind::Batches(batches) => {
+                    let header = mapper::map_polled_batches_header(&batches);
+                    tcp_sender
+                        .send_ok_response_with_regions(&header, &batches.regions)
+                        .await?;
+                }
+                PolledMessagesKind::Messages(messages) => {
+                    let messages =
+                        mapper::map_polled_messages_with_format(&messages, command.format);
+                    tcp_sender.send_ok_response(&messages).await?;
+                }
+            }
+            return Ok(());
+        }
+    }
+
     let system = system.read().await;
     let messages = system
         .poll_messages(
@@ -25,14 +68,15 @@ pub async fn handle(
             &command.stream_id,
             &command.topic_id,
             command.partition_id,
-            PollingArgs::new(command.strategy, command.count, command.auto_commit),
+            args,
         )
         .await
         .with_error_context(|error| format!(
             "{COMPONENT} (error: {error}) - failed to poll messages for consumer: {}, stream_id: {}, topic_id: {}, partition_id: {:?}, session: {}.",
             command.consumer, command.stream_id, command.topic_id, command.partition_id, session
         ))?;
-    let messages = mapper::map_polled_messages(&messages);
+    drop(system);
+    let messages = mapper::map_polled_messages_with_format(&messages, command.format);
     sender.send_ok_response(&messages).await?;
     Ok(())
 }
@@ -15,13 +15,14 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    let system = system.read().await;
     let stream_id = command.stream_id.clone();
     let topic_id = command.topic_id.clone();
     let partitioning = command.partitioning.clone();
     let messages = command.messages;
     // TODO(haze): Add confirmation level after testing is complete
-    system
+    let replication = system
+        .read()
+        .await
         .append_messages(session, stream_id, topic_id, partitioning, messages, None)
         .await
         .with_error_context(|error| {
@@ -30,6 +31,15 @@ pub async fn handle(
                 command.stream_id, command.topic_id, command.partitioning, session
             )
         })?;
+    // The replicas are awaited without holding the system lock.
+    if let Some(replication) = replication {
+        replication.wait().await.with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to replicate messages for stream_id: {}, topic_id: {}, session: {}",
+                command.stream_id, command.topic_id, session
+            )
+        })?;
+    }
     sender.send_empty_ok_response().await?;
     Ok(())
 }
@@ -0,0 +1,46 @@
+use crate::binary::mapper;
+use crate::binary::{handlers::roles::COMPONENT, sender::SenderKind};
+use crate::state::command::EntryCommand;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::roles::create_role::CreateRole;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_create_role", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id))]
+pub async fn handle(
+    command: CreateRole,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+
+    let mut system = system.write().await;
+    let role = system
+        .create_role(session, &command.name, command.permissions.clone())
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to create role with name: {}, session: {session}",
+                command.name
+            )
+        })?;
+    let response = mapper::map_role(role);
+
+    let system = system.downgrade();
+    let name = command.name.clone();
+    system
+        .state
+        .apply(session.get_user_id(), EntryCommand::CreateRole(command))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply create role with name: {name}, session: {session}"
+            )
+        })?;
+    sender.send_ok_response(&response).await?;
+    Ok(())
+}
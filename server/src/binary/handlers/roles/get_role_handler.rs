@@ -0,0 +1,29 @@
+use crate::binary::mapper;
+use crate::binary::sender::SenderKind;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use iggy::error::IggyError;
+use iggy::roles::get_role::GetRole;
+use tracing::debug;
+
+pub async fn handle(
+    command: GetRole,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    let Ok(role) = system.find_role(session, &command.role_id) else {
+        sender.send_empty_ok_response().await?;
+        return Ok(());
+    };
+    let Some(role) = role else {
+        sender.send_empty_ok_response().await?;
+        return Ok(());
+    };
+
+    let bytes = mapper::map_role(role);
+    sender.send_ok_response(&bytes).await?;
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::binary::handlers::roles::COMPONENT;
+use crate::binary::mapper;
+use crate::binary::sender::SenderKind;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::roles::get_roles::GetRoles;
+use tracing::debug;
+
+pub async fn handle(
+    command: GetRoles,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    let roles = system
+        .get_roles(session)
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get roles, session: {session}")
+        })?;
+    let roles = mapper::map_roles(&roles);
+    sender.send_ok_response(&roles).await?;
+    Ok(())
+}
@@ -0,0 +1,9 @@
+pub mod assign_role_handler;
+pub mod create_role_handler;
+pub mod delete_role_handler;
+pub mod get_role_handler;
+pub mod get_roles_handler;
+pub mod unassign_role_handler;
+pub mod update_role_handler;
+
+pub const COMPONENT: &str = "ROLE_HANDLER";
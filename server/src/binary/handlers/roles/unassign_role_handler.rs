@@ -0,0 +1,45 @@
+use crate::binary::{handlers::roles::COMPONENT, sender::SenderKind};
+use crate::state::command::EntryCommand;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::roles::unassign_role::UnassignRole;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_unassign_role", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id))]
+pub async fn handle(
+    command: UnassignRole,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+
+    let mut system = system.write().await;
+    system
+        .unassign_role(session, &command.role_id, &command.user_id)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to unassign role with ID: {} for user with ID: {}, session: {session}",
+                command.role_id, command.user_id
+            )
+        })?;
+
+    let system = system.downgrade();
+    let role_id = command.role_id.clone();
+    let user_id = command.user_id.clone();
+    system
+        .state
+        .apply(session.get_user_id(), EntryCommand::UnassignRole(command))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply unassign role with ID: {role_id} for user with ID: {user_id}, session: {session}"
+            )
+        })?;
+    sender.send_empty_ok_response().await?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::binary::{handlers::roles::COMPONENT, sender::SenderKind};
+use crate::state::command::EntryCommand;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::roles::update_role::UpdateRole;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_update_role", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id))]
+pub async fn handle(
+    command: UpdateRole,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+
+    let mut system = system.write().await;
+    system
+        .update_role(
+            session,
+            &command.role_id,
+            command.name.clone(),
+            command.permissions.clone(),
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to update role with ID: {}, session: {session}",
+                command.role_id
+            )
+        })?;
+
+    let system = system.downgrade();
+    let role_id = command.role_id.clone();
+    system
+        .state
+        .apply(session.get_user_id(), EntryCommand::UpdateRole(command))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply update role with ID: {role_id}, session: {session}"
+            )
+        })?;
+    sender.send_empty_ok_response().await?;
+    Ok(())
+}
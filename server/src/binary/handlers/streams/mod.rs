@@ -3,6 +3,7 @@ pub mod delete_stream_handler;
 pub mod get_stream_handler;
 pub mod get_streams_handler;
 pub mod purge_stream_handler;
+pub mod set_stream_quota_handler;
 pub mod update_stream_handler;
 
 pub const COMPONENT: &str = "STREAM_HANDLER";
@@ -29,8 +29,10 @@ pub async fn handle(
                 command.partitions_count,
                 command.message_expiry,
                 command.compression_algorithm,
+                command.compression_level,
                 command.max_topic_size,
                 command.replication_factor,
+                command.cleanup_policy,
             )
             .await
             .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to create topic for stream_id: {stream_id}, topic_id: {:?}",
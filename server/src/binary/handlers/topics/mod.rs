@@ -3,6 +3,7 @@ pub mod delete_topic_handler;
 pub mod get_topic_handler;
 pub mod get_topics_handler;
 pub mod purge_topic_handler;
+pub mod set_topic_retention_handler;
 pub mod update_topic_handler;
 
 pub const COMPONENT: &str = "TOPIC_HANDLER";
@@ -27,8 +27,10 @@ pub async fn handle(
                 &command.name,
                 command.message_expiry,
                 command.compression_algorithm,
+                command.compression_level,
                 command.max_topic_size,
                 command.replication_factor,
+                command.cleanup_policy,
             )
             .await
             .with_error_context(|error| format!(
@@ -0,0 +1,30 @@
+use crate::binary::{handlers::transactions::COMPONENT, sender::SenderKind};
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::transactions::abort_transaction::AbortTransaction;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_abort_transaction", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id, iggy_transaction_id = command.transaction_id))]
+pub async fn handle(
+    command: AbortTransaction,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    system
+        .abort_transaction(session, command.transaction_id)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to abort transaction with ID: {}, session: {session}",
+                command.transaction_id
+            )
+        })?;
+    sender.send_empty_ok_response().await?;
+    Ok(())
+}
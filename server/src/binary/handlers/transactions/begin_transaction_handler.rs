@@ -0,0 +1,31 @@
+use crate::binary::{handlers::transactions::COMPONENT, sender::SenderKind};
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::transactions::begin_transaction::BeginTransaction;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_begin_transaction", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id))]
+pub async fn handle(
+    command: BeginTransaction,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    let transaction_id = system
+        .begin_transaction(session)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to begin transaction, session: {session}"
+            )
+        })?;
+    sender
+        .send_ok_response(&transaction_id.to_le_bytes())
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::binary::{handlers::transactions::COMPONENT, sender::SenderKind};
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::transactions::commit_transaction::CommitTransaction;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_commit_transaction", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id, iggy_transaction_id = command.transaction_id))]
+pub async fn handle(
+    command: CommitTransaction,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    system
+        .commit_transaction(session, command.transaction_id, command.offsets)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to commit transaction with ID: {}, session: {session}",
+                command.transaction_id
+            )
+        })?;
+    sender.send_empty_ok_response().await?;
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod abort_transaction_handler;
+pub mod begin_transaction_handler;
+pub mod commit_transaction_handler;
+
+pub const COMPONENT: &str = "TRANSACTION_HANDLER";
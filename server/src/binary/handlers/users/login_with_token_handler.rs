@@ -0,0 +1,36 @@
+use crate::binary::mapper;
+use crate::binary::{handlers::users::COMPONENT, sender::SenderKind};
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::users::login_with_token::LoginWithToken;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_login_with_token", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id))]
+pub async fn handle(
+    command: LoginWithToken,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let authenticator = system.read().await.get_oidc_authenticator()?;
+    let identity = authenticator
+        .verify(&command.token)
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to verify token, session: {session}")
+        })?;
+    let mut system = system.write().await;
+    let user = system
+        .login_with_oidc_identity(&authenticator, &identity, Some(session))
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to login with token, session: {session}")
+        })?;
+    let identity_info = mapper::map_identity_info(user.id);
+    sender.send_ok_response(&identity_info).await?;
+    Ok(())
+}
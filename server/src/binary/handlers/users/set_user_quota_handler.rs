@@ -0,0 +1,35 @@
+use crate::binary::{handlers::users::COMPONENT, sender::SenderKind};
+use crate::state::command::EntryCommand;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::users::set_user_quota::SetUserQuota;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_set_user_quota", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id))]
+pub async fn handle(
+    command: SetUserQuota,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+
+    let mut system = system.write().await;
+    system
+            .set_user_quota(session, &command.user_id, command.quota)
+            .await
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to set quota for user_id: {}, session: {session}",
+                command.user_id
+            ))?;
+
+    let system = system.downgrade();
+    system
+        .state
+        .apply(session.get_user_id(), EntryCommand::SetUserQuota(command))
+        .await?;
+    sender.send_empty_ok_response().await?;
+    Ok(())
+}
@@ -1,13 +1,18 @@
 use crate::streaming::clients::client_manager::{Client, Transport};
+use crate::streaming::models::messages::PolledBatches;
 use crate::streaming::partitions::partition::Partition;
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::topics::consumer_group::ConsumerGroup;
 use crate::streaming::topics::topic::Topic;
+use crate::streaming::users::role::Role;
 use crate::streaming::users::user::User;
 use bytes::{BufMut, Bytes, BytesMut};
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::messages::poll_messages::PollingFormat;
+use iggy::models::archive::RestoredArchive;
+use iggy::models::consumer_group::ConsumerGroupMembership;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
 use iggy::models::messages::PolledMessages;
 use iggy::models::stats::Stats;
@@ -72,6 +77,26 @@ pub fn map_consumer_offset(offset: &ConsumerOffsetInfo) -> Bytes {
     bytes.freeze()
 }
 
+pub fn map_restored_archive(restored_archive: &RestoredArchive) -> Bytes {
+    let mut bytes = BytesMut::with_capacity(20);
+    bytes.put_u32_le(restored_archive.created_streams_count);
+    bytes.put_u32_le(restored_archive.created_topics_count);
+    bytes.put_u32_le(restored_archive.segments_count);
+    bytes.put_u64_le(restored_archive.messages_count);
+    bytes.freeze()
+}
+
+pub fn map_consumer_group_membership(membership: &ConsumerGroupMembership) -> Bytes {
+    let mut bytes = BytesMut::with_capacity(12 + 4 * membership.partitions.len());
+    bytes.put_u32_le(membership.member_id);
+    bytes.put_u32_le(membership.generation);
+    bytes.put_u32_le(membership.partitions.len() as u32);
+    for partition in &membership.partitions {
+        bytes.put_u32_le(*partition);
+    }
+    bytes.freeze()
+}
+
 pub fn map_client(client: &Client) -> Bytes {
     let mut bytes = BytesMut::new();
     extend_client(client, &mut bytes);
@@ -115,6 +140,32 @@ pub fn map_users(users: &[&User]) -> Bytes {
     bytes.freeze()
 }
 
+pub fn map_role(role: &Role) -> Bytes {
+    let mut bytes = BytesMut::new();
+    extend_role(role, &mut bytes);
+    if let Some(permissions) = &role.permissions {
+        bytes.put_u8(1);
+        let permissions = permissions.to_bytes();
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u32_le(permissions.len() as u32);
+        bytes.put_slice(&permissions);
+    } else {
+        bytes.put_u8(0);
+    }
+    for user_id in &role.users {
+        bytes.put_u32_le(*user_id);
+    }
+    bytes.freeze()
+}
+
+pub fn map_roles(roles: &[&Role]) -> Bytes {
+    let mut bytes = BytesMut::new();
+    for role in roles {
+        extend_role(role, &mut bytes);
+    }
+    bytes.freeze()
+}
+
 pub fn map_identity_info(user_id: UserId) -> Bytes {
     let mut bytes = BytesMut::with_capacity(4);
     bytes.put_u32_le(user_id);
@@ -136,6 +187,35 @@ pub fn map_personal_access_tokens(personal_access_tokens: &[&PersonalAccessToken
     bytes.freeze()
 }
 
+/// Maps the polled messages requested with the given format, the response to the non-default format
+/// starts with the code of the format, as the server might respond with the regular messages instead of the batches.
+pub fn map_polled_messages_with_format(
+    polled_messages: &PolledMessages,
+    format: PollingFormat,
+) -> Bytes {
+    let messages = map_polled_messages(polled_messages);
+    if format == PollingFormat::Messages {
+        return messages;
+    }
+
+    let mut bytes = BytesMut::with_capacity(1 + messages.len());
+    bytes.put_u8(PollingFormat::Messages.as_code());
+    bytes.put_slice(&messages);
+    bytes.freeze()
+}
+
+/// Maps the header of the polled batches, which are sent afterwards directly from the segment log files.
+pub fn map_polled_batches_header(polled_batches: &PolledBatches) -> Bytes {
+    let mut bytes = BytesMut::with_capacity(33);
+    bytes.put_u8(PollingFormat::Batches.as_code());
+    bytes.put_u32_le(polled_batches.partition_id);
+    bytes.put_u64_le(polled_batches.current_offset);
+    bytes.put_u32_le(polled_batches.generation.unwrap_or(0));
+    bytes.put_u64_le(polled_batches.first_offset);
+    bytes.put_u64_le(polled_batches.last_offset);
+    bytes.freeze()
+}
+
 pub fn map_polled_messages(polled_messages: &PolledMessages) -> Bytes {
     let messages_count = polled_messages.messages.len() as u32;
     let messages_size = polled_messages
@@ -151,6 +231,7 @@ pub fn map_polled_messages(polled_messages: &PolledMessages) -> Bytes {
     for message in polled_messages.messages.iter() {
         message.extend(&mut bytes);
     }
+    bytes.put_u32_le(polled_messages.generation.unwrap_or(0));
 
     bytes.freeze()
 }
@@ -237,6 +318,8 @@ fn extend_topic(topic: &Topic, bytes: &mut BytesMut) {
     bytes.put_u64_le(topic.get_messages_count());
     bytes.put_u8(topic.name.len() as u8);
     bytes.put_slice(topic.name.as_bytes());
+    bytes.put_u8(topic.compression_level.unwrap_or(0));
+    bytes.put_u8(topic.cleanup_policy.as_code());
 }
 
 fn extend_partition(partition: &Partition, bytes: &mut BytesMut) {
@@ -254,6 +337,8 @@ fn extend_consumer_group(consumer_group: &ConsumerGroup, bytes: &mut BytesMut) {
     bytes.put_u32_le(consumer_group.get_members().len() as u32);
     bytes.put_u8(consumer_group.name.len() as u8);
     bytes.put_slice(consumer_group.name.as_bytes());
+    bytes.put_u8(consumer_group.assignment_strategy.as_code());
+    bytes.put_u32_le(consumer_group.get_generation());
 }
 
 fn extend_client(client: &Client, bytes: &mut BytesMut) {
@@ -278,6 +363,14 @@ fn extend_user(user: &User, bytes: &mut BytesMut) {
     bytes.put_slice(user.username.as_bytes());
 }
 
+fn extend_role(role: &Role, bytes: &mut BytesMut) {
+    bytes.put_u32_le(role.id);
+    bytes.put_u64_le(role.created_at.into());
+    bytes.put_u32_le(role.users.len() as u32);
+    bytes.put_u8(role.name.len() as u8);
+    bytes.put_slice(role.name.as_bytes());
+}
+
 fn extend_pat(personal_access_token: &PersonalAccessToken, bytes: &mut BytesMut) {
     bytes.put_u8(personal_access_token.name.len() as u8);
     bytes.put_slice(personal_access_token.name.as_bytes());
@@ -1,12 +1,14 @@
 use std::future::Future;
 
+use crate::tcp::tcp_pipelined_sender::{PipelinedResponse, TcpPipelinedSender};
 use crate::tcp::tcp_sender::TcpSender;
 use crate::tcp::tcp_tls_sender::TcpTlsSender;
 use crate::{quic::quic_sender::QuicSender, server_error::ServerError};
 use iggy::error::IggyError;
 use quinn::{RecvStream, SendStream};
 use tokio::net::TcpStream;
-use tokio_native_tls::TlsStream;
+use tokio::sync::mpsc;
+use tokio_rustls::server::TlsStream;
 
 macro_rules! forward_async_methods {
     (
@@ -21,6 +23,7 @@ macro_rules! forward_async_methods {
                 match self {
                     Self::Tcp(d) => d.$method_name($( $arg ),*).await,
                     Self::TcpTls(s) => s.$method_name($( $arg ),*).await,
+                    Self::TcpPipelined(s) => s.$method_name($( $arg ),*).await,
                     Self::Quic(s) => s.$method_name($( $arg ),*).await,
                 }
             }
@@ -45,18 +48,29 @@ pub trait Sender {
 pub enum SenderKind {
     Tcp(TcpSender),
     TcpTls(TcpTlsSender),
+    TcpPipelined(TcpPipelinedSender),
     Quic(QuicSender),
 }
 
 impl SenderKind {
-    pub fn get_tcp_sender(stream: TcpStream) -> Self {
-        Self::Tcp(TcpSender { stream })
+    pub fn get_tcp_sender(stream: TcpStream, zero_copy: bool) -> Self {
+        Self::Tcp(TcpSender { stream, zero_copy })
     }
 
     pub fn get_tcp_tls_sender(stream: TlsStream<TcpStream>) -> Self {
         Self::TcpTls(TcpTlsSender { stream })
     }
 
+    pub fn get_tcp_pipelined_sender(
+        request_id: u32,
+        responses: mpsc::Sender<PipelinedResponse>,
+    ) -> Self {
+        Self::TcpPipelined(TcpPipelinedSender {
+            request_id,
+            responses,
+        })
+    }
+
     pub fn get_quic_sender(send_stream: SendStream, recv_stream: RecvStream) -> Self {
         Self::Quic(QuicSender {
             send: send_stream,
@@ -64,6 +78,14 @@ impl SenderKind {
         })
     }
 
+    /// Returns the TCP sender, if it can send the polled batches directly from the segment log files.
+    pub fn as_zero_copy_sender(&mut self) -> Option<&mut TcpSender> {
+        match self {
+            Self::Tcp(sender) if sender.zero_copy => Some(sender),
+            _ => None,
+        }
+    }
+
     forward_async_methods! {
         async fn read(&mut self, buffer: &mut [u8]) -> Result<usize, IggyError>;
         async fn send_empty_ok_response(&mut self) -> Result<(), IggyError>;
@@ -0,0 +1,89 @@
+use crate::channels::server_command::ServerCommand;
+use crate::configs::server::TransactionConfig;
+use crate::streaming::systems::system::SharedSystem;
+use flume::Sender;
+use iggy::utils::duration::IggyDuration;
+use tokio::time;
+use tracing::{error, info, instrument};
+
+pub struct ExpiredTransactionsCleaner {
+    timeout: IggyDuration,
+    interval: IggyDuration,
+    sender: Sender<AbortExpiredTransactionsCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct AbortExpiredTransactionsCommand {
+    timeout: IggyDuration,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct AbortExpiredTransactionsExecutor;
+
+impl ExpiredTransactionsCleaner {
+    pub fn new(
+        config: &TransactionConfig,
+        sender: Sender<AbortExpiredTransactionsCommand>,
+    ) -> Self {
+        Self {
+            timeout: config.timeout,
+            interval: config.cleaner_interval,
+            sender,
+        }
+    }
+
+    pub fn start(&self) {
+        let timeout = self.timeout;
+        let interval = self.interval;
+        let sender = self.sender.clone();
+        info!("Transactions open for longer than: {timeout} will be aborted every: {interval}.");
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            loop {
+                interval_timer.tick().await;
+                sender
+                    .send(AbortExpiredTransactionsCommand { timeout })
+                    .unwrap_or_else(|error| {
+                        error!(
+                            "Failed to send AbortExpiredTransactionsCommand. Error: {}",
+                            error
+                        );
+                    });
+            }
+        });
+    }
+}
+
+impl ServerCommand<AbortExpiredTransactionsCommand> for AbortExpiredTransactionsExecutor {
+    #[instrument(skip_all, name = "trace_abort_expired_transactions")]
+    async fn execute(&mut self, system: &SharedSystem, command: AbortExpiredTransactionsCommand) {
+        let system = system.read().await;
+        system.abort_expired_transactions(command.timeout).await;
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &crate::configs::server::ServerConfig,
+        sender: Sender<AbortExpiredTransactionsCommand>,
+    ) {
+        let expired_transactions_cleaner =
+            ExpiredTransactionsCleaner::new(&config.transaction, sender);
+        expired_transactions_cleaner.start();
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        _config: &crate::configs::server::ServerConfig,
+        receiver: flume::Receiver<AbortExpiredTransactionsCommand>,
+    ) {
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            info!("Expired transactions cleaner receiver stopped.");
+        });
+    }
+}
@@ -0,0 +1,142 @@
+use crate::channels::server_command::ServerCommand;
+use crate::configs::server::CompactionMaintenanceConfig;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::systems::system::SharedSystem;
+use flume::Sender;
+use iggy::locking::IggySharedMutFn;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::timestamp::IggyTimestamp;
+use tokio::time;
+use tracing::{error, info, instrument, trace};
+
+pub struct MessagesCompactor {
+    enabled: bool,
+    interval: IggyDuration,
+    tombstones_retention: IggyDuration,
+    sender: Sender<CompactMessagesCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct CompactMessagesCommand {
+    tombstones_retention: IggyDuration,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct CompactMessagesExecutor;
+
+impl MessagesCompactor {
+    pub fn new(
+        config: &CompactionMaintenanceConfig,
+        sender: Sender<CompactMessagesCommand>,
+    ) -> Self {
+        Self {
+            enabled: config.enabled,
+            interval: config.interval,
+            tombstones_retention: config.tombstones_retention,
+            sender,
+        }
+    }
+
+    pub fn start(&self) {
+        if !self.enabled {
+            info!("Messages compactor is disabled.");
+            return;
+        }
+
+        let interval = self.interval;
+        let tombstones_retention = self.tombstones_retention;
+        let sender = self.sender.clone();
+        info!("Messages compactor is enabled, compacted topics will be compacted every: {interval}, tombstones retention: {tombstones_retention}.");
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            loop {
+                interval_timer.tick().await;
+                sender
+                    .send(CompactMessagesCommand {
+                        tombstones_retention,
+                    })
+                    .unwrap_or_else(|error| {
+                        error!("Failed to send CompactMessagesCommand. Error: {}", error);
+                    });
+            }
+        });
+    }
+}
+
+impl ServerCommand<CompactMessagesCommand> for CompactMessagesExecutor {
+    #[instrument(skip_all, name = "trace_compact_messages")]
+    async fn execute(&mut self, system: &SharedSystem, command: CompactMessagesCommand) {
+        let now = IggyTimestamp::now();
+        let topics = {
+            let system = system.read().await;
+            system
+                .get_streams()
+                .into_iter()
+                .flat_map(|stream| stream.get_topics())
+                .filter(|topic| topic.cleanup_policy == CleanupPolicy::Compact)
+                .map(|topic| (topic.stream_id, topic.topic_id, topic.get_partitions()))
+                .collect::<Vec<_>>()
+        };
+
+        for (stream_id, topic_id, partitions) in topics {
+            let mut segments_count = 0;
+            let mut messages_count = 0;
+            for partition in partitions {
+                match Partition::compact(&partition, command.tombstones_retention, now).await {
+                    Ok(compacted_segments) => {
+                        segments_count += compacted_segments.segments_count;
+                        messages_count += compacted_segments.messages_count;
+                    }
+                    Err(error) => {
+                        error!(
+                            "Failed to compact partition with ID: {} for stream ID: {stream_id}, topic ID: {topic_id}. Error: {error}",
+                            partition.read().await.partition_id
+                        );
+                    }
+                }
+            }
+
+            if segments_count == 0 {
+                trace!(
+                    "No segments were compacted for stream ID: {stream_id}, topic ID: {topic_id}"
+                );
+                continue;
+            }
+
+            info!(
+                "Compacted {segments_count} segments and removed {messages_count} messages for stream ID: {stream_id}, topic ID: {topic_id}"
+            );
+        }
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &crate::configs::server::ServerConfig,
+        sender: Sender<CompactMessagesCommand>,
+    ) {
+        let messages_compactor =
+            MessagesCompactor::new(&config.data_maintenance.compaction, sender);
+        messages_compactor.start();
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        config: &crate::configs::server::ServerConfig,
+        receiver: flume::Receiver<CompactMessagesCommand>,
+    ) {
+        if !config.data_maintenance.compaction.enabled {
+            return;
+        }
+
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            info!("Messages compactor receiver stopped.");
+        });
+    }
+}
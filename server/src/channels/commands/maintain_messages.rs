@@ -2,7 +2,9 @@ use crate::archiver::ArchiverKind;
 use crate::channels::server_command::ServerCommand;
 use crate::configs::server::MessagesMaintenanceConfig;
 use crate::map_toggle_str;
+use crate::streaming::diagnostics::metrics::Metrics;
 use crate::streaming::systems::system::SharedSystem;
+use crate::streaming::topics::retention::BlockedSegments;
 use crate::streaming::topics::topic::Topic;
 use error_set::ErrContext;
 use flume::Sender;
@@ -12,7 +14,7 @@ use iggy::utils::duration::IggyDuration;
 use iggy::utils::timestamp::IggyTimestamp;
 use std::sync::Arc;
 use tokio::time;
-use tracing::{debug, error, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 pub struct MessagesMaintainer {
     cleaner_enabled: bool,
@@ -79,6 +81,7 @@ impl ServerCommand<MaintainMessagesCommand> for MaintainMessagesExecutor {
     #[instrument(skip_all, name = "trace_maintain_messages")]
     async fn execute(&mut self, system: &SharedSystem, command: MaintainMessagesCommand) {
         let system = system.read().await;
+        system.metrics.clear_retention_blocked_segments();
         let streams = system.get_streams();
         for stream in streams {
             let topics = stream.get_topics();
@@ -88,11 +91,13 @@ impl ServerCommand<MaintainMessagesCommand> for MaintainMessagesExecutor {
                 } else {
                     None
                 };
+                let mut blocked_segments = BlockedSegments::default();
                 let expired_segments = handle_expired_segments(
                     topic,
                     archiver.clone(),
                     system.config.segment.archive_expired,
                     command.clean_messages,
+                    &mut blocked_segments,
                 )
                 .await;
                 if expired_segments.is_err() {
@@ -100,6 +105,7 @@ impl ServerCommand<MaintainMessagesCommand> for MaintainMessagesExecutor {
                         "Failed to get expired segments for stream ID: {}, topic ID: {}",
                         topic.stream_id, topic.topic_id
                     );
+                    report_blocked_segments(&system.metrics, topic, &blocked_segments);
                     continue;
                 }
 
@@ -107,8 +113,10 @@ impl ServerCommand<MaintainMessagesCommand> for MaintainMessagesExecutor {
                     topic,
                     archiver.clone(),
                     system.config.topic.delete_oldest_segments,
+                    &mut blocked_segments,
                 )
                 .await;
+                report_blocked_segments(&system.metrics, topic, &blocked_segments);
                 if oldest_segments.is_err() {
                     error!(
                         "Failed to get oldest segments for stream ID: {}, topic ID: {}",
@@ -199,8 +207,14 @@ async fn handle_expired_segments(
     archiver: Option<Arc<ArchiverKind>>,
     archive: bool,
     clean: bool,
+    blocked_segments: &mut BlockedSegments,
 ) -> Result<HandledSegments, IggyError> {
-    let expired_segments = get_expired_segments(topic, IggyTimestamp::now()).await;
+    let mut expired_segments = get_expired_segments(topic, IggyTimestamp::now()).await;
+    if topic.retains_unconsumed_segments() {
+        expired_segments =
+            retain_consumed_segments(topic, expired_segments, blocked_segments).await;
+    }
+
     if expired_segments.is_empty() {
         return Ok(HandledSegments::none());
     }
@@ -270,6 +284,7 @@ async fn handle_oldest_segments(
     topic: &Topic,
     archiver: Option<Arc<ArchiverKind>>,
     delete_oldest_segments: bool,
+    blocked_segments: &mut BlockedSegments,
 ) -> Result<HandledSegments, IggyError> {
     if let Some(archiver) = archiver {
         let mut segments_to_archive = Vec::new();
@@ -329,23 +344,24 @@ async fn handle_oldest_segments(
             })?;
     }
 
-    if topic.is_unlimited() {
+    if topic.exceeds_retention_max_size() {
+        warn!(
+            "Topic exceeds the consumed retention max size, oldest segments will be deleted regardless of the consumer groups for stream ID: {}, topic ID: {}",
+            topic.stream_id, topic.topic_id
+        );
+    } else if topic.is_unlimited() {
         debug!(
             "Topic is unlimited, oldest segments will not be deleted for stream ID: {}, topic ID: {}",
             topic.stream_id, topic.topic_id
         );
         return Ok(HandledSegments::none());
-    }
-
-    if !delete_oldest_segments {
+    } else if !delete_oldest_segments {
         debug!(
             "Delete oldest segments is disabled, oldest segments will not be deleted for stream ID: {}, topic ID: {}",
             topic.stream_id, topic.topic_id
         );
         return Ok(HandledSegments::none());
-    }
-
-    if !topic.is_almost_full() {
+    } else if !topic.is_almost_full() {
         debug!(
             "Topic is not almost full, oldest segments will not be deleted for stream ID: {}, topic ID: {}",
             topic.stream_id, topic.topic_id
@@ -353,7 +369,11 @@ async fn handle_oldest_segments(
         return Ok(HandledSegments::none());
     }
 
-    let oldest_segments = get_oldest_segments(topic).await;
+    let mut oldest_segments = get_oldest_segments(topic).await;
+    if topic.retains_unconsumed_segments() {
+        oldest_segments = retain_consumed_segments(topic, oldest_segments, blocked_segments).await;
+    }
+
     if oldest_segments.is_empty() {
         return Ok(HandledSegments::none());
     }
@@ -395,6 +415,50 @@ async fn get_oldest_segments(topic: &Topic) -> Vec<SegmentsToHandle> {
     oldest_segments
 }
 
+async fn retain_consumed_segments(
+    topic: &Topic,
+    segments: Vec<SegmentsToHandle>,
+    blocked_segments: &mut BlockedSegments,
+) -> Vec<SegmentsToHandle> {
+    let mut consumed_segments = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let start_offsets = topic
+            .retain_consumed_segments(
+                segment.partition_id,
+                segment.start_offsets,
+                blocked_segments,
+            )
+            .await;
+        if !start_offsets.is_empty() {
+            consumed_segments.push(SegmentsToHandle {
+                partition_id: segment.partition_id,
+                start_offsets,
+            });
+        }
+    }
+
+    consumed_segments
+}
+
+fn report_blocked_segments(metrics: &Metrics, topic: &Topic, blocked_segments: &BlockedSegments) {
+    if blocked_segments.is_empty() {
+        return;
+    }
+
+    for (consumer_group, count) in blocked_segments.counts() {
+        info!(
+            "Consumer group: {consumer_group} blocks the deletion of {count} segments for stream ID: {}, topic ID: {}",
+            topic.stream_id, topic.topic_id
+        );
+        metrics.set_retention_blocked_segments(
+            topic.stream_id,
+            topic.topic_id,
+            consumer_group,
+            count,
+        );
+    }
+}
+
 #[derive()]
 struct SegmentsToHandle {
     partition_id: u32,
@@ -436,7 +500,7 @@ async fn archive_segments(
     for segment_to_archive in segments_to_archive {
         match topic.get_partition(segment_to_archive.partition_id) {
             Ok(partition) => {
-                let partition = partition.read().await;
+                let mut partition = partition.write().await;
                 for start_offset in &segment_to_archive.start_offsets {
                     let segment = partition.get_segment(*start_offset);
                     if segment.is_none() {
@@ -448,6 +512,7 @@ async fn archive_segments(
                     }
 
                     let segment = segment.unwrap();
+                    let end_offset = segment.end_offset;
                     let files = [segment.index_path.as_ref(), segment.log_path.as_ref()];
                     if let Err(error) = archiver.archive(&files, None).await {
                         error!(
@@ -456,6 +521,15 @@ async fn archive_segments(
                         );
                         continue;
                     }
+                    partition
+                        .add_archived_segment(*start_offset, end_offset)
+                        .await
+                        .with_error_context(|error| {
+                            format!(
+                                "CHANNEL_COMMAND - failed to register archived segment with start offset: {start_offset} for stream ID: {}, topic ID: {}, partition ID: {}. {error}",
+                                topic.stream_id, topic.topic_id, partition.partition_id
+                            )
+                        })?;
                     info!(
                         "Archived Segment with start offset: {}, for stream ID: {}, topic ID: {}, partition ID: {}",
                         start_offset, topic.stream_id, topic.topic_id, partition.partition_id
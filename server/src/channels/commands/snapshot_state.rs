@@ -0,0 +1,112 @@
+use crate::channels::server_command::ServerCommand;
+use crate::configs::server::SnapshotMaintenanceConfig;
+use crate::streaming::systems::system::SharedSystem;
+use flume::Sender;
+use iggy::utils::duration::IggyDuration;
+use tokio::time;
+use tracing::{error, info, instrument, warn};
+
+pub struct StateSnapshotter {
+    enabled: bool,
+    interval: IggyDuration,
+    min_entries: u64,
+    sender: Sender<SnapshotStateCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SnapshotStateCommand {
+    min_entries: u64,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SnapshotStateExecutor;
+
+impl StateSnapshotter {
+    pub fn new(config: &SnapshotMaintenanceConfig, sender: Sender<SnapshotStateCommand>) -> Self {
+        Self {
+            enabled: config.enabled,
+            interval: config.interval,
+            min_entries: config.min_entries,
+            sender,
+        }
+    }
+
+    pub fn start(&self) {
+        if !self.enabled {
+            info!("State snapshotter is disabled.");
+            return;
+        }
+
+        let interval = self.interval;
+        let min_entries = self.min_entries;
+        let sender = self.sender.clone();
+        info!("State snapshotter is enabled, state will be checked every: {interval} and snapshotted after {min_entries} new entries.");
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            loop {
+                interval_timer.tick().await;
+                sender
+                    .send(SnapshotStateCommand { min_entries })
+                    .unwrap_or_else(|error| {
+                        error!("Failed to send SnapshotStateCommand. Error: {}", error);
+                    });
+            }
+        });
+    }
+}
+
+impl ServerCommand<SnapshotStateCommand> for SnapshotStateExecutor {
+    #[instrument(skip_all, name = "trace_snapshot_state")]
+    async fn execute(&mut self, system: &SharedSystem, command: SnapshotStateCommand) {
+        // Holding the write lock ensures that no state entries are appended while the snapshot is being created.
+        let system = system.write().await;
+        if system.cluster.is_some() {
+            warn!("Cluster mode is enabled, state will not be snapshotted.");
+            return;
+        }
+
+        match system.create_state_snapshot(command.min_entries).await {
+            Ok(Some(header)) => info!("State snapshotted successfully: {header}."),
+            Ok(None) => {}
+            Err(error) => error!("Failed to snapshot state. Error: {}", error),
+        }
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &crate::configs::server::ServerConfig,
+        sender: Sender<SnapshotStateCommand>,
+    ) {
+        if !config.data_maintenance.snapshot.enabled {
+            return;
+        }
+
+        if config.cluster.enabled {
+            info!("State snapshotter is disabled in the cluster mode.");
+            return;
+        }
+
+        let state_snapshotter = StateSnapshotter::new(&config.data_maintenance.snapshot, sender);
+        state_snapshotter.start();
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        config: &crate::configs::server::ServerConfig,
+        receiver: flume::Receiver<SnapshotStateCommand>,
+    ) {
+        if !config.data_maintenance.snapshot.enabled || config.cluster.enabled {
+            return;
+        }
+
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            info!("State snapshotter receiver stopped.");
+        });
+    }
+}
@@ -0,0 +1,234 @@
+use crate::cluster::COMPONENT;
+use crate::state::command::EntryCommand;
+use crate::state::entry::StateEntry;
+use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::System;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::users::defaults::DEFAULT_ROOT_USER_ID;
+use std::net::{Ipv4Addr, SocketAddr};
+use tracing::debug;
+
+/// Applies the state entry replicated from the leader to the system.
+/// The commands are executed on behalf of the root user, as they have been already authorized by the leader,
+/// except the personal access tokens, which belong to the user who issued the command.
+pub async fn apply_entry(system: &mut System, entry: &StateEntry) -> Result<(), IggyError> {
+    let command = entry.command().with_error_context(|error| {
+        format!(
+            "{COMPONENT} (error: {error}) - failed to parse the command of the state entry with index: {}",
+            entry.index
+        )
+    })?;
+    debug!("Applying replicated state entry: {entry}, command: {command}");
+    let address = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+    let session = Session::stateless(DEFAULT_ROOT_USER_ID, address);
+    match command {
+        EntryCommand::CreateStream(command) => {
+            let stream_id = system
+                .create_stream(&session, command.stream_id, &command.name)
+                .await?
+                .stream_id;
+            // The stream is owned by the user who created it on the leader, which matters for the user quotas.
+            system.get_stream_by_id_mut(stream_id)?.owner_id = entry.user_id;
+        }
+        EntryCommand::UpdateStream(command) => {
+            system
+                .update_stream(&session, &command.stream_id, &command.name)
+                .await?;
+        }
+        EntryCommand::DeleteStream(command) => {
+            system.delete_stream(&session, &command.stream_id).await?;
+        }
+        EntryCommand::PurgeStream(command) => {
+            system.purge_stream(&session, &command.stream_id).await?;
+        }
+        EntryCommand::SetStreamQuota(command) => {
+            system
+                .set_stream_quota(&session, &command.stream_id, command.quota)
+                .await?;
+        }
+        EntryCommand::CreateTopic(command) => {
+            system
+                .create_topic(
+                    &session,
+                    &command.stream_id,
+                    command.topic_id,
+                    &command.name,
+                    command.partitions_count,
+                    command.message_expiry,
+                    command.compression_algorithm,
+                    command.compression_level,
+                    command.max_topic_size,
+                    command.replication_factor,
+                    command.cleanup_policy,
+                )
+                .await?;
+        }
+        EntryCommand::UpdateTopic(command) => {
+            system
+                .update_topic(
+                    &session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    &command.name,
+                    command.message_expiry,
+                    command.compression_algorithm,
+                    command.compression_level,
+                    command.max_topic_size,
+                    command.replication_factor,
+                    command.cleanup_policy,
+                )
+                .await?;
+        }
+        EntryCommand::DeleteTopic(command) => {
+            system
+                .delete_topic(&session, &command.stream_id, &command.topic_id)
+                .await?;
+        }
+        EntryCommand::PurgeTopic(command) => {
+            system
+                .purge_topic(&session, &command.stream_id, &command.topic_id)
+                .await?;
+        }
+        EntryCommand::SetTopicRetention(command) => {
+            system
+                .set_topic_retention(
+                    &session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    command.retention,
+                )
+                .await?;
+        }
+        EntryCommand::CreatePartitions(command) => {
+            system
+                .create_partitions(
+                    &session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    command.partitions_count,
+                )
+                .await?;
+        }
+        EntryCommand::DeletePartitions(command) => {
+            system
+                .delete_partitions(
+                    &session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    command.partitions_count,
+                )
+                .await?;
+        }
+        EntryCommand::CreateConsumerGroup(command) => {
+            system
+                .create_consumer_group(
+                    &session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    command.group_id,
+                    &command.name,
+                    command.assignment_strategy,
+                )
+                .await?;
+        }
+        EntryCommand::DeleteConsumerGroup(command) => {
+            system
+                .delete_consumer_group(
+                    &session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    &command.group_id,
+                )
+                .await?;
+        }
+        EntryCommand::CreateUser(command) => {
+            let user_id = system
+                .create_user(
+                    &session,
+                    &command.username,
+                    &command.password,
+                    command.status,
+                    command.permissions,
+                )
+                .await?
+                .id;
+            // The password has been already hashed by the leader.
+            system.get_user_mut(&user_id.try_into()?)?.password = command.password;
+        }
+        EntryCommand::UpdateUser(command) => {
+            system
+                .update_user(&session, &command.user_id, command.username, command.status)
+                .await?;
+        }
+        EntryCommand::DeleteUser(command) => {
+            system.delete_user(&session, &command.user_id).await?;
+        }
+        EntryCommand::ChangePassword(command) => {
+            // The password has been already hashed by the leader.
+            system.get_user_mut(&command.user_id)?.password = command.new_password;
+        }
+        EntryCommand::UpdatePermissions(command) => {
+            system
+                .update_permissions(&session, &command.user_id, command.permissions)
+                .await?;
+        }
+        EntryCommand::SetUserQuota(command) => {
+            system
+                .set_user_quota(&session, &command.user_id, command.quota)
+                .await?;
+        }
+        EntryCommand::CreateRole(command) => {
+            system
+                .create_role(&session, &command.name, command.permissions)
+                .await?;
+        }
+        EntryCommand::UpdateRole(command) => {
+            system
+                .update_role(
+                    &session,
+                    &command.role_id,
+                    command.name,
+                    command.permissions,
+                )
+                .await?;
+        }
+        EntryCommand::DeleteRole(command) => {
+            system.delete_role(&session, &command.role_id).await?;
+        }
+        EntryCommand::AssignRole(command) => {
+            system
+                .assign_role(&session, &command.role_id, &command.user_id)
+                .await?;
+        }
+        EntryCommand::UnassignRole(command) => {
+            system
+                .unassign_role(&session, &command.role_id, &command.user_id)
+                .await?;
+        }
+        EntryCommand::CreatePersonalAccessToken(command) => {
+            let expiry_at =
+                PersonalAccessToken::calculate_expiry_at(entry.timestamp, command.command.expiry);
+            let user = system.get_user_mut(&entry.user_id.try_into()?)?;
+            user.personal_access_tokens.insert(
+                command.hash.clone(),
+                PersonalAccessToken::raw(
+                    entry.user_id,
+                    &command.command.name,
+                    &command.hash,
+                    expiry_at,
+                ),
+            );
+        }
+        EntryCommand::DeletePersonalAccessToken(command) => {
+            let session = Session::stateless(entry.user_id, address);
+            system
+                .delete_personal_access_token(&session, &command.name)
+                .await?;
+        }
+        EntryCommand::Noop => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use crate::cluster::command::{ClusterCommand, Handshake};
+use crate::cluster::handlers::{handle_append_entries, handle_replicate_messages};
+use crate::cluster::node::ClusterNode;
+use crate::cluster::COMPONENT;
+use crate::configs::cluster::ClusterConfig;
+use crate::streaming::systems::system::SharedSystem;
+use crate::tcp::sender;
+use bytes::{BufMut, BytesMut};
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::error::IggyError;
+use std::net::SocketAddr;
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, error, info};
+
+const INITIAL_BYTES_LENGTH: usize = 4;
+
+/// Starts the cluster node, listening for the connections from the other nodes and running the election and replication loops.
+pub async fn start(config: ClusterConfig, system: SharedSystem) -> SocketAddr {
+    info!(
+        "Initializing cluster node: {}, peers: {}...",
+        config.node.id,
+        config.peers.len()
+    );
+    let node = system
+        .read()
+        .await
+        .cluster
+        .clone()
+        .expect("Cluster node must be initialized before starting the cluster server.");
+
+    let listener = TcpListener::bind(&config.node.address)
+        .await
+        .expect("Unable to start cluster server.");
+    let address = listener
+        .local_addr()
+        .expect("Failed to get local address for cluster server.");
+    let secret = config.secret;
+    let listener_node = node.clone();
+    let listener_system = system.clone();
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, address)) => {
+                    debug!("{COMPONENT} - accepted new connection: {address}");
+                    let node = listener_node.clone();
+                    let system = listener_system.clone();
+                    let secret = secret.clone();
+                    tokio::spawn(async move {
+                        if let Err(error) = handle_connection(stream, &node, &system, &secret).await
+                        {
+                            debug!("{COMPONENT} - connection: {address} has been closed. {error}");
+                        }
+                    });
+                }
+                Err(error) => error!("{COMPONENT} - unable to accept TCP socket. {error}"),
+            }
+        }
+    });
+
+    node.start(system);
+    info!("Cluster node: {} has started on: {address}", config.node.id);
+    address
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    node: &ClusterNode,
+    system: &SharedSystem,
+    secret: &str,
+) -> Result<(), IggyError> {
+    let mut peer_id = None;
+    let mut initial_buffer = [0u8; INITIAL_BYTES_LENGTH];
+    loop {
+        sender::read(&mut stream, &mut initial_buffer).await?;
+        let length = u32::from_le_bytes(initial_buffer) as usize;
+        let mut command_buffer = BytesMut::with_capacity(length);
+        command_buffer.put_bytes(0, length);
+        sender::read(&mut stream, &mut command_buffer).await?;
+        let command = match ClusterCommand::from_bytes(command_buffer.freeze()) {
+            Ok(command) => command,
+            Err(error) => {
+                sender::send_error_response(&mut stream, error).await?;
+                continue;
+            }
+        };
+
+        debug!("{COMPONENT} - received command: {command}");
+        if let ClusterCommand::Handshake(handshake) = &command {
+            if let Err(error) = authenticate(node, handshake, secret) {
+                sender::send_error_response(&mut stream, error).await?;
+                return Err(IggyError::Unauthenticated);
+            }
+
+            peer_id = Some(handshake.node_id);
+            sender::send_empty_ok_response(&mut stream).await?;
+            continue;
+        }
+
+        if peer_id.is_none() {
+            sender::send_error_response(&mut stream, IggyError::Unauthenticated).await?;
+            return Err(IggyError::Unauthenticated);
+        }
+
+        let response = match command {
+            ClusterCommand::RequestVote(command) => {
+                Ok(node.handle_request_vote(&command).await.to_bytes())
+            }
+            ClusterCommand::AppendEntries(command) => handle_append_entries(command, node, system)
+                .await
+                .map(|response| response.to_bytes()),
+            ClusterCommand::ReplicateMessages(command) => {
+                handle_replicate_messages(command, node, system)
+                    .await
+                    .map(|response| response.to_bytes())
+            }
+            ClusterCommand::Handshake(_) => Err(IggyError::InvalidCommand),
+        };
+        match response {
+            Ok(response) => sender::send_ok_response(&mut stream, &response).await?,
+            Err(error) => {
+                error!("{COMPONENT} - failed to handle command. {error}");
+                sender::send_error_response(&mut stream, error).await?
+            }
+        }
+    }
+}
+
+fn authenticate(node: &ClusterNode, handshake: &Handshake, secret: &str) -> Result<(), IggyError> {
+    if handshake.secret != secret {
+        error!(
+            "{COMPONENT} - invalid secret provided by node: {}",
+            handshake.node_id
+        );
+        return Err(IggyError::InvalidClusterSecret);
+    }
+
+    if !node.contains_peer(handshake.node_id) {
+        error!(
+            "{COMPONENT} - unknown node: {} tried to connect",
+            handshake.node_id
+        );
+        return Err(IggyError::ClusterNodeNotFound(handshake.node_id));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,495 @@
+use crate::state::entry::StateEntry;
+use crate::streaming::models::messages::RetainedMessage;
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::error::IggyError;
+use std::fmt::{Display, Formatter};
+use std::str::from_utf8;
+use std::sync::Arc;
+
+pub const HANDSHAKE_CODE: u32 = 1;
+pub const REQUEST_VOTE_CODE: u32 = 2;
+pub const APPEND_ENTRIES_CODE: u32 = 3;
+pub const REPLICATE_MESSAGES_CODE: u32 = 4;
+
+/// The commands exchanged between the cluster nodes, serialized as `[code][payload]`.
+#[derive(Debug)]
+pub enum ClusterCommand {
+    Handshake(Handshake),
+    RequestVote(RequestVote),
+    AppendEntries(AppendEntries),
+    ReplicateMessages(ReplicateMessages),
+}
+
+/// The first command sent on each connection, authenticating the node with the shared cluster secret.
+#[derive(Debug, PartialEq)]
+pub struct Handshake {
+    pub node_id: u32,
+    pub secret: String,
+}
+
+/// Sent by the candidate to request the vote from the other nodes.
+/// The candidate's log is described by the index of the next entry and the term of the last entry.
+#[derive(Debug, PartialEq)]
+pub struct RequestVote {
+    pub term: u64,
+    pub candidate_id: u32,
+    pub next_index: u64,
+    pub last_term: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VoteResponse {
+    pub term: u64,
+    pub granted: bool,
+}
+
+/// Sent by the leader to replicate the state entries, an empty list of entries is used as a heartbeat.
+/// The entries start at `prev_log_index`, and the entry preceding them must have `prev_log_term` in the follower's log.
+/// The `commit_index` is the number of the entries committed by the leader, which the follower can apply.
+#[derive(Debug)]
+pub struct AppendEntries {
+    pub term: u64,
+    pub leader_id: u32,
+    pub prev_log_index: u64,
+    pub prev_log_term: u64,
+    pub commit_index: u64,
+    pub entries: Vec<StateEntry>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AppendEntriesResponse {
+    pub term: u64,
+    pub success: bool,
+    pub next_index: u64,
+}
+
+/// Sent by the leader to replicate the messages of the partition, starting at the given offset.
+#[derive(Debug)]
+pub struct ReplicateMessages {
+    pub term: u64,
+    pub leader_id: u32,
+    pub stream_id: u32,
+    pub topic_id: u32,
+    pub partition_id: u32,
+    pub start_offset: u64,
+    pub messages: Vec<Arc<RetainedMessage>>,
+}
+
+/// Contains the offset of the next message expected by the follower.
+#[derive(Debug, PartialEq)]
+pub struct ReplicateMessagesResponse {
+    pub term: u64,
+    pub next_offset: u64,
+}
+
+impl ClusterCommand {
+    pub fn code(&self) -> u32 {
+        match self {
+            ClusterCommand::Handshake(_) => HANDSHAKE_CODE,
+            ClusterCommand::RequestVote(_) => REQUEST_VOTE_CODE,
+            ClusterCommand::AppendEntries(_) => APPEND_ENTRIES_CODE,
+            ClusterCommand::ReplicateMessages(_) => REPLICATE_MESSAGES_CODE,
+        }
+    }
+
+    fn payload(&self) -> Bytes {
+        match self {
+            ClusterCommand::Handshake(command) => command.to_bytes(),
+            ClusterCommand::RequestVote(command) => command.to_bytes(),
+            ClusterCommand::AppendEntries(command) => command.to_bytes(),
+            ClusterCommand::ReplicateMessages(command) => command.to_bytes(),
+        }
+    }
+}
+
+impl BytesSerializable for ClusterCommand {
+    fn to_bytes(&self) -> Bytes {
+        let payload = self.payload();
+        let mut bytes = BytesMut::with_capacity(4 + payload.len());
+        bytes.put_u32_le(self.code());
+        bytes.put_slice(&payload);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        let code = read_u32(&bytes, 0)?;
+        let payload = bytes.slice(4..);
+        match code {
+            HANDSHAKE_CODE => Ok(ClusterCommand::Handshake(Handshake::from_bytes(payload)?)),
+            REQUEST_VOTE_CODE => Ok(ClusterCommand::RequestVote(RequestVote::from_bytes(
+                payload,
+            )?)),
+            APPEND_ENTRIES_CODE => Ok(ClusterCommand::AppendEntries(AppendEntries::from_bytes(
+                payload,
+            )?)),
+            REPLICATE_MESSAGES_CODE => Ok(ClusterCommand::ReplicateMessages(
+                ReplicateMessages::from_bytes(payload)?,
+            )),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl BytesSerializable for Handshake {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(5 + self.secret.len());
+        bytes.put_u32_le(self.node_id);
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u8(self.secret.len() as u8);
+        bytes.put_slice(self.secret.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        let node_id = read_u32(&bytes, 0)?;
+        let secret_length = *bytes.get(4).ok_or(IggyError::InvalidCommand)? as usize;
+        let secret = bytes
+            .get(5..5 + secret_length)
+            .ok_or(IggyError::InvalidCommand)?;
+        let secret = from_utf8(secret)
+            .map_err(|_| IggyError::InvalidUtf8)?
+            .to_string();
+        Ok(Handshake { node_id, secret })
+    }
+}
+
+impl BytesSerializable for RequestVote {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(28);
+        bytes.put_u64_le(self.term);
+        bytes.put_u32_le(self.candidate_id);
+        bytes.put_u64_le(self.next_index);
+        bytes.put_u64_le(self.last_term);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        Ok(RequestVote {
+            term: read_u64(&bytes, 0)?,
+            candidate_id: read_u32(&bytes, 8)?,
+            next_index: read_u64(&bytes, 12)?,
+            last_term: read_u64(&bytes, 20)?,
+        })
+    }
+}
+
+impl BytesSerializable for VoteResponse {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(9);
+        bytes.put_u64_le(self.term);
+        bytes.put_u8(self.granted as u8);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        Ok(VoteResponse {
+            term: read_u64(&bytes, 0)?,
+            granted: read_bool(&bytes, 8)?,
+        })
+    }
+}
+
+impl BytesSerializable for AppendEntries {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(40);
+        bytes.put_u64_le(self.term);
+        bytes.put_u32_le(self.leader_id);
+        bytes.put_u64_le(self.prev_log_index);
+        bytes.put_u64_le(self.prev_log_term);
+        bytes.put_u64_le(self.commit_index);
+        bytes.put_u32_le(self.entries.len() as u32);
+        for entry in &self.entries {
+            let entry = entry.to_bytes();
+            bytes.put_u32_le(entry.len() as u32);
+            bytes.put_slice(&entry);
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        let term = read_u64(&bytes, 0)?;
+        let leader_id = read_u32(&bytes, 8)?;
+        let prev_log_index = read_u64(&bytes, 12)?;
+        let prev_log_term = read_u64(&bytes, 20)?;
+        let commit_index = read_u64(&bytes, 28)?;
+        let entries_count = read_u32(&bytes, 36)?;
+        let mut entries = Vec::with_capacity(entries_count as usize);
+        let mut position = 40;
+        for _ in 0..entries_count {
+            let length = read_u32(&bytes, position)? as usize;
+            position += 4;
+            if bytes.len() < position + length {
+                return Err(IggyError::InvalidCommand);
+            }
+            entries.push(StateEntry::from_bytes(
+                bytes.slice(position..position + length),
+            )?);
+            position += length;
+        }
+
+        Ok(AppendEntries {
+            term,
+            leader_id,
+            prev_log_index,
+            prev_log_term,
+            commit_index,
+            entries,
+        })
+    }
+}
+
+impl BytesSerializable for AppendEntriesResponse {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(17);
+        bytes.put_u64_le(self.term);
+        bytes.put_u8(self.success as u8);
+        bytes.put_u64_le(self.next_index);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        Ok(AppendEntriesResponse {
+            term: read_u64(&bytes, 0)?,
+            success: read_bool(&bytes, 8)?,
+            next_index: read_u64(&bytes, 9)?,
+        })
+    }
+}
+
+impl BytesSerializable for ReplicateMessages {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(36);
+        bytes.put_u64_le(self.term);
+        bytes.put_u32_le(self.leader_id);
+        bytes.put_u32_le(self.stream_id);
+        bytes.put_u32_le(self.topic_id);
+        bytes.put_u32_le(self.partition_id);
+        bytes.put_u64_le(self.start_offset);
+        bytes.put_u32_le(self.messages.len() as u32);
+        for message in &self.messages {
+            message.extend(&mut bytes);
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        let term = read_u64(&bytes, 0)?;
+        let leader_id = read_u32(&bytes, 8)?;
+        let stream_id = read_u32(&bytes, 12)?;
+        let topic_id = read_u32(&bytes, 16)?;
+        let partition_id = read_u32(&bytes, 20)?;
+        let start_offset = read_u64(&bytes, 24)?;
+        let messages_count = read_u32(&bytes, 32)?;
+        let mut messages = Vec::with_capacity(messages_count as usize);
+        let mut position = 36;
+        for _ in 0..messages_count {
+            let length = read_u32(&bytes, position)? as usize;
+            position += 4;
+            if bytes.len() < position + length {
+                return Err(IggyError::InvalidCommand);
+            }
+            messages.push(Arc::new(RetainedMessage::try_from_bytes(
+                bytes.slice(position..position + length),
+            )?));
+            position += length;
+        }
+
+        Ok(ReplicateMessages {
+            term,
+            leader_id,
+            stream_id,
+            topic_id,
+            partition_id,
+            start_offset,
+            messages,
+        })
+    }
+}
+
+impl BytesSerializable for ReplicateMessagesResponse {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(16);
+        bytes.put_u64_le(self.term);
+        bytes.put_u64_le(self.next_offset);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        Ok(ReplicateMessagesResponse {
+            term: read_u64(&bytes, 0)?,
+            next_offset: read_u64(&bytes, 8)?,
+        })
+    }
+}
+
+impl Display for ClusterCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClusterCommand::Handshake(command) => {
+                write!(f, "handshake from node: {}", command.node_id)
+            }
+            ClusterCommand::RequestVote(command) => write!(
+                f,
+                "request vote from node: {}, term: {}",
+                command.candidate_id, command.term
+            ),
+            ClusterCommand::AppendEntries(command) => write!(
+                f,
+                "append {} entries from leader: {}, term: {}",
+                command.entries.len(),
+                command.leader_id,
+                command.term
+            ),
+            ClusterCommand::ReplicateMessages(command) => write!(
+                f,
+                "replicate {} messages from leader: {}, term: {}, stream ID: {}, topic ID: {}, partition ID: {}, start offset: {}",
+                command.messages.len(),
+                command.leader_id,
+                command.term,
+                command.stream_id,
+                command.topic_id,
+                command.partition_id,
+                command.start_offset
+            ),
+        }
+    }
+}
+
+fn read_u32(bytes: &Bytes, position: usize) -> Result<u32, IggyError> {
+    let value = bytes
+        .get(position..position + 4)
+        .ok_or(IggyError::InvalidCommand)?;
+    Ok(u32::from_le_bytes(
+        value
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    ))
+}
+
+fn read_u64(bytes: &Bytes, position: usize) -> Result<u64, IggyError> {
+    let value = bytes
+        .get(position..position + 8)
+        .ok_or(IggyError::InvalidCommand)?;
+    Ok(u64::from_le_bytes(
+        value
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    ))
+}
+
+fn read_bool(bytes: &Bytes, position: usize) -> Result<bool, IggyError> {
+    let value = bytes.get(position).ok_or(IggyError::InvalidCommand)?;
+    Ok(*value == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iggy::messages::send_messages::Message;
+    use iggy::utils::timestamp::IggyTimestamp;
+
+    #[test]
+    fn handshake_should_be_serialized_and_deserialized() {
+        let command = ClusterCommand::Handshake(Handshake {
+            node_id: 2,
+            secret: "secret".to_string(),
+        });
+
+        let command = ClusterCommand::from_bytes(command.to_bytes()).unwrap();
+        let ClusterCommand::Handshake(handshake) = command else {
+            panic!("Invalid command");
+        };
+        assert_eq!(handshake.node_id, 2);
+        assert_eq!(handshake.secret, "secret");
+    }
+
+    #[test]
+    fn request_vote_should_be_serialized_and_deserialized() {
+        let command = RequestVote {
+            term: 3,
+            candidate_id: 2,
+            next_index: 10,
+            last_term: 2,
+        };
+
+        let deserialized = RequestVote::from_bytes(command.to_bytes()).unwrap();
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn append_entries_should_be_serialized_and_deserialized() {
+        let command = Bytes::from_static(&[1, 0, 0, 0, 3, 0, 0, 0, 1, 2, 3]);
+        let context = Bytes::new();
+        let timestamp = IggyTimestamp::now();
+        let checksum =
+            StateEntry::calculate_checksum(5, 2, 1, 1, 0, timestamp, 1, &context, &command);
+        let command = AppendEntries {
+            term: 2,
+            leader_id: 1,
+            prev_log_index: 5,
+            prev_log_term: 1,
+            commit_index: 4,
+            entries: vec![StateEntry::new(
+                5, 2, 1, 1, 0, timestamp, 1, checksum, context, command,
+            )],
+        };
+
+        let deserialized = AppendEntries::from_bytes(command.to_bytes()).unwrap();
+        assert_eq!(deserialized.term, command.term);
+        assert_eq!(deserialized.leader_id, command.leader_id);
+        assert_eq!(deserialized.prev_log_index, command.prev_log_index);
+        assert_eq!(deserialized.prev_log_term, command.prev_log_term);
+        assert_eq!(deserialized.commit_index, command.commit_index);
+        assert_eq!(deserialized.entries.len(), 1);
+        let entry = &deserialized.entries[0];
+        assert_eq!(entry.index, 5);
+        assert_eq!(entry.term, 2);
+        assert_eq!(entry.checksum, checksum);
+        assert_eq!(entry.command, command.entries[0].command);
+    }
+
+    #[test]
+    fn replicate_messages_should_be_serialized_and_deserialized() {
+        let messages = (0..3)
+            .map(|offset| {
+                Arc::new(RetainedMessage::new(
+                    offset,
+                    IggyTimestamp::now().as_micros(),
+                    Message::new(Some(offset as u128 + 1), Bytes::from("test"), None),
+                ))
+            })
+            .collect::<Vec<_>>();
+        let command = ReplicateMessages {
+            term: 1,
+            leader_id: 1,
+            stream_id: 2,
+            topic_id: 3,
+            partition_id: 4,
+            start_offset: 0,
+            messages,
+        };
+
+        let deserialized = ReplicateMessages::from_bytes(command.to_bytes()).unwrap();
+        assert_eq!(deserialized.stream_id, 2);
+        assert_eq!(deserialized.topic_id, 3);
+        assert_eq!(deserialized.partition_id, 4);
+        assert_eq!(deserialized.messages.len(), 3);
+        for (message, expected) in deserialized.messages.iter().zip(command.messages.iter()) {
+            assert_eq!(message.id, expected.id);
+            assert_eq!(message.offset, expected.offset);
+            assert_eq!(message.payload, expected.payload);
+        }
+    }
+
+    #[test]
+    fn truncated_command_should_not_be_deserialized() {
+        let bytes = AppendEntriesResponse {
+            term: 1,
+            success: true,
+            next_index: 2,
+        }
+        .to_bytes();
+        assert!(AppendEntriesResponse::from_bytes(bytes.slice(..10)).is_err());
+    }
+}
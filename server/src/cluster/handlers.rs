@@ -0,0 +1,268 @@
+use crate::cluster::command::{
+    AppendEntries, AppendEntriesResponse, ReplicateMessages, ReplicateMessagesResponse,
+};
+use crate::cluster::node::{ClusterNode, MAX_MESSAGES_PER_REQUEST};
+use crate::cluster::COMPONENT;
+use crate::streaming::systems::system::SharedSystem;
+use ahash::AHashMap;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use tracing::{debug, error, warn};
+
+/// Appends the state entries replicated by the leader once the preceding entry matches the leader's log,
+/// truncating the conflicting entries, and applies the entries committed by the leader.
+pub async fn handle_append_entries(
+    command: AppendEntries,
+    node: &ClusterNode,
+    system: &SharedSystem,
+) -> Result<AppendEntriesResponse, IggyError> {
+    let state = system.read().await.state.clone();
+    if !node.accept_leader(command.term, command.leader_id).await {
+        return Ok(AppendEntriesResponse {
+            term: node.get_term(),
+            success: false,
+            next_index: state.next_index(),
+        });
+    }
+
+    let prev_log_index = command.prev_log_index;
+    let next_index = state.next_index();
+    let is_matching = prev_log_index <= next_index
+        && (prev_log_index == 0
+            || state.term_at(prev_log_index - 1) == Some(command.prev_log_term));
+    if !is_matching {
+        // The committed entries always match the leader's log, so the leader can resume from there.
+        let next_index = next_index
+            .min(prev_log_index.saturating_sub(1))
+            .max(node.get_commit_index().min(next_index));
+        debug!(
+            "{COMPONENT} - state entry preceding index: {prev_log_index} doesn't match the leader's term: {}, expected index: {next_index}",
+            command.prev_log_term
+        );
+        return Ok(AppendEntriesResponse {
+            term: command.term,
+            success: false,
+            next_index,
+        });
+    }
+
+    let last_index = prev_log_index + command.entries.len() as u64;
+    for (index, entry) in (prev_log_index..).zip(command.entries) {
+        if entry.index != index {
+            return Err(IggyError::InvalidStateEntryIndex(entry.index, index));
+        }
+
+        match state.term_at(index) {
+            Some(term) if term == entry.term => continue,
+            Some(term) => {
+                if index < node.get_commit_index() {
+                    error!(
+                        "{COMPONENT} - committed state entry with index: {index} and term: {term} conflicts with the leader's term: {}",
+                        entry.term
+                    );
+                    return Err(IggyError::ClusterStateDiverged(index));
+                }
+
+                warn!(
+                    "{COMPONENT} - truncating state entries starting at index: {index}, term: {term}, leader's term: {}",
+                    entry.term
+                );
+                state.truncate(index).await.with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to truncate state entries starting at index: {index}"
+                    )
+                })?;
+                // The truncated entries might have been applied by the node while it was the leader.
+                if index < node.get_applied_index() {
+                    node.reload_committed_state(system)
+                        .await
+                        .with_error_context(|error| {
+                            format!(
+                                "{COMPONENT} (error: {error}) - failed to reload the committed state after truncating state entries starting at index: {index}"
+                            )
+                        })?;
+                }
+            }
+            None => {}
+        }
+
+        state.append(entry).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to append replicated state entry with index: {index}")
+        })?;
+    }
+
+    node.commit_entries(command.commit_index.min(last_index), system)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to commit state entries up to index: {}",
+                command.commit_index
+            )
+        })?;
+    Ok(AppendEntriesResponse {
+        term: command.term,
+        success: true,
+        next_index: last_index,
+    })
+}
+
+/// Appends the messages replicated by the leader, keeping their offsets and timestamps. The batch starts with the message
+/// preceding the ones missing on this node, so the partitions can be compared: the messages conflicting with the leader's
+/// ones (e.g. appended by the previous leader) are truncated before appending the batch. Once the partition is verified
+/// for the first time in the leader's term, the messages exceeding the batch are truncated too, as the leader doesn't have them.
+pub async fn handle_replicate_messages(
+    command: ReplicateMessages,
+    node: &ClusterNode,
+    system: &SharedSystem,
+) -> Result<ReplicateMessagesResponse, IggyError> {
+    if !node.accept_leader(command.term, command.leader_id).await {
+        return Ok(ReplicateMessagesResponse {
+            term: node.get_term(),
+            next_offset: 0,
+        });
+    }
+
+    let system = system.read().await;
+    let stream_id = Identifier::numeric(command.stream_id)?;
+    let topic_id = Identifier::numeric(command.topic_id)?;
+    let topic = system
+        .get_stream(&stream_id)
+        .and_then(|stream| stream.get_topic(&topic_id))
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - topic: {topic_id} not found in stream: {stream_id}"
+            )
+        })?;
+    let partition_id = command.partition_id;
+    let partition = topic.get_partition(partition_id)?;
+    let mut partition = partition.write().await;
+    let next_offset = partition.get_next_offset();
+    let (Some(first_message), Some(last_message)) =
+        (command.messages.first(), command.messages.last())
+    else {
+        return Ok(ReplicateMessagesResponse {
+            term: command.term,
+            next_offset,
+        });
+    };
+
+    if command.start_offset > next_offset {
+        debug!(
+            "{COMPONENT} - received messages starting at offset: {}, expected: {next_offset}, partition: {partition_id}, topic: {topic_id}, stream: {stream_id}",
+            command.start_offset
+        );
+        return Ok(ReplicateMessagesResponse {
+            term: command.term,
+            next_offset,
+        });
+    }
+
+    // The messages removed from this node (e.g. by the retention or the compaction) are considered matching.
+    let overlapping_messages = command
+        .messages
+        .iter()
+        .take_while(|message| message.offset < next_offset)
+        .collect::<Vec<_>>();
+    let mut local_messages = AHashMap::new();
+    if let Some(last_overlapping_message) = overlapping_messages.last() {
+        let count = (last_overlapping_message.offset - first_message.offset + 1) as u32;
+        for message in partition
+            .get_messages_by_offset(first_message.offset, count)
+            .await?
+        {
+            local_messages.insert(message.offset, (message.id, message.checksum));
+        }
+    }
+    let conflict_index = overlapping_messages.iter().position(|message| {
+        local_messages
+            .get(&message.offset)
+            .is_some_and(|(id, checksum)| *id != message.id || *checksum != message.checksum)
+    });
+
+    // The whole partition is verified, if the first message matches the local one or the batch starts the partition.
+    let is_verified =
+        command.start_offset == 0 || !overlapping_messages.is_empty() && conflict_index != Some(0);
+    let key = (command.stream_id, command.topic_id, partition_id);
+    let is_new_term = node.get_partition_term(key) < command.term;
+    if !is_verified && (is_new_term || conflict_index.is_some()) {
+        // The preceding messages are requested to find the offset at which the partitions diverged.
+        let next_offset = command
+            .start_offset
+            .saturating_sub(MAX_MESSAGES_PER_REQUEST as u64)
+            .min(next_offset);
+        debug!(
+            "{COMPONENT} - messages preceding offset: {} don't match the leader's ones, expected offset: {next_offset}, partition: {partition_id}, topic: {topic_id}, stream: {stream_id}",
+            command.start_offset
+        );
+        return Ok(ReplicateMessagesResponse {
+            term: command.term,
+            next_offset,
+        });
+    }
+
+    let mut removed_messages_count = 0;
+    let new_messages = match conflict_index {
+        Some(index) => {
+            let offset = command.messages[index].offset;
+            warn!(
+                "{COMPONENT} - message with offset: {offset} conflicts with the leader's one, truncating partition: {partition_id}, topic: {topic_id}, stream: {stream_id}"
+            );
+            removed_messages_count += partition.truncate_messages(offset).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to truncate partition: {partition_id} at offset: {offset}, topic: {topic_id}, stream: {stream_id}")
+            })?;
+            &command.messages[index..]
+        }
+        None => &command.messages[overlapping_messages.len()..],
+    };
+
+    if let Some(first_new_message) = new_messages.first() {
+        // The leader no longer has the messages missing on this node, so the partition continues at the leader's offset.
+        if first_new_message.offset > partition.get_next_offset()
+            && topic.cleanup_policy != CleanupPolicy::Compact
+        {
+            let offset = first_new_message.offset;
+            warn!(
+                "{COMPONENT} - messages preceding offset: {offset} are no longer available on the leader, skipping partition: {partition_id}, topic: {topic_id}, stream: {stream_id}"
+            );
+            removed_messages_count += partition.skip_messages_to(offset).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to skip partition: {partition_id} to offset: {offset}, topic: {topic_id}, stream: {stream_id}")
+            })?;
+        }
+
+        partition
+            .append_replicated_messages(new_messages.to_vec())
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to append replicated messages to partition: {partition_id}, topic: {topic_id}, stream: {stream_id}"
+                )
+            })?;
+        system.metrics.increment_messages(new_messages.len() as u64);
+        debug!(
+            "{COMPONENT} - appended {} replicated messages to partition: {partition_id}, topic: {topic_id}, stream: {stream_id}",
+            new_messages.len()
+        );
+    }
+
+    if is_new_term {
+        let offset = last_message.offset + 1;
+        if offset < partition.get_next_offset() {
+            warn!(
+                "{COMPONENT} - messages starting at offset: {offset} exceed the leader's ones, truncating partition: {partition_id}, topic: {topic_id}, stream: {stream_id}"
+            );
+            removed_messages_count += partition.truncate_messages(offset).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to truncate partition: {partition_id} at offset: {offset}, topic: {topic_id}, stream: {stream_id}")
+            })?;
+        }
+        node.set_partition_term(key, command.term);
+    }
+
+    system.metrics.decrement_messages(removed_messages_count);
+    Ok(ReplicateMessagesResponse {
+        term: command.term,
+        next_offset: partition.get_next_offset(),
+    })
+}
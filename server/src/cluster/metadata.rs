@@ -0,0 +1,120 @@
+use crate::cluster::COMPONENT;
+use bytes::{BufMut, BytesMut};
+use iggy::error::IggyError;
+use std::io::ErrorKind;
+use std::path::Path;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tracing::error;
+
+const CLUSTER_METADATA_SIZE: usize = 20;
+
+/// The election state and the commit index of the node, which must survive the restarts,
+/// so the node never votes twice in the same term and doesn't apply the uncommitted state entries.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ClusterMetadata {
+    pub term: u64,
+    pub voted_for: Option<u32>,
+    /// The number of the state entries committed by the majority of the nodes.
+    pub commit_index: u64,
+}
+
+impl ClusterMetadata {
+    /// Loads the metadata, returns `None` if it has never been persisted.
+    pub fn load(path: &str) -> Result<Option<Self>, IggyError> {
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                error!("{COMPONENT} - failed to read cluster metadata, path: {path}. {error}");
+                return Err(IggyError::CannotReadFile);
+            }
+        };
+
+        if bytes.len() != CLUSTER_METADATA_SIZE {
+            return Err(IggyError::InvalidClusterMetadata);
+        }
+
+        let term = u64::from_le_bytes(
+            bytes[..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let voted_for = u32::from_le_bytes(
+            bytes[8..12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let commit_index = u64::from_le_bytes(
+            bytes[12..20]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        Ok(Some(ClusterMetadata {
+            term,
+            // The node IDs start at 1, so 0 stands for no vote.
+            voted_for: (voted_for > 0).then_some(voted_for),
+            commit_index,
+        }))
+    }
+
+    /// Writes the metadata to the temporary file, syncs it to the disk and renames it, so it's never left partially written.
+    pub async fn persist(&self, path: &str) -> Result<(), IggyError> {
+        let mut bytes = BytesMut::with_capacity(CLUSTER_METADATA_SIZE);
+        bytes.put_u64_le(self.term);
+        bytes.put_u32_le(self.voted_for.unwrap_or(0));
+        bytes.put_u64_le(self.commit_index);
+
+        let temporary_path = format!("{path}.tmp");
+        let result = async {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&temporary_path)
+                .await?;
+            file.write_all(&bytes).await?;
+            file.sync_all().await?;
+            tokio::fs::rename(&temporary_path, path).await?;
+            if let Some(directory) = Path::new(path).parent() {
+                File::open(directory).await?.sync_all().await?;
+            }
+            Ok::<(), std::io::Error>(())
+        }
+        .await;
+        if let Err(error) = result {
+            error!("{COMPONENT} - failed to persist cluster metadata, path: {path}. {error}");
+            return Err(IggyError::CannotOverwriteFile);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn metadata_should_be_persisted_and_loaded() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("cluster");
+        let path = path.to_str().unwrap();
+        assert_eq!(ClusterMetadata::load(path).unwrap(), None);
+
+        let metadata = ClusterMetadata {
+            term: 3,
+            voted_for: Some(2),
+            commit_index: 10,
+        };
+        metadata.persist(path).await.unwrap();
+        assert_eq!(ClusterMetadata::load(path).unwrap(), Some(metadata));
+
+        let metadata = ClusterMetadata {
+            voted_for: None,
+            ..metadata
+        };
+        metadata.persist(path).await.unwrap();
+        assert_eq!(ClusterMetadata::load(path).unwrap(), Some(metadata));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod applier;
+pub mod cluster_server;
+pub mod command;
+pub mod handlers;
+pub mod metadata;
+pub mod node;
+pub mod peer;
+
+pub const COMPONENT: &str = "CLUSTER";
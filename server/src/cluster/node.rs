@@ -0,0 +1,1303 @@
+use crate::cluster::applier::apply_entry;
+use crate::cluster::command::{
+    AppendEntries, AppendEntriesResponse, ClusterCommand, ReplicateMessages,
+    ReplicateMessagesResponse, RequestVote, VoteResponse,
+};
+use crate::cluster::metadata::ClusterMetadata;
+use crate::cluster::peer::ClusterPeer;
+use crate::cluster::COMPONENT;
+use crate::configs::cluster::ClusterConfig;
+use crate::state::command::EntryCommand;
+use crate::state::StateKind;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::systems::system::SharedSystem;
+use crate::streaming::topics::topic::Topic;
+use dashmap::DashMap;
+use error_set::ErrContext;
+use futures::stream::{FuturesUnordered, StreamExt};
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::error::IggyError;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::timestamp::IggyTimestamp;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
+
+const MAX_ENTRIES_PER_REQUEST: usize = 1000;
+pub(crate) const MAX_MESSAGES_PER_REQUEST: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClusterNodeRole {
+    Follower,
+    Candidate,
+    Leader,
+}
+
+/// The election state of the node. The term, the vote and the commit index are persisted before they're acted upon,
+/// while the role is kept in memory only, so the node starts as a follower after restart.
+#[derive(Debug)]
+struct Election {
+    role: ClusterNodeRole,
+    term: u64,
+    voted_for: Option<u32>,
+    leader_id: Option<u32>,
+    deadline: Instant,
+    commit_index: u64,
+    /// The index of the no-op entry appended by the node once it has become the leader, the entries preceding it
+    /// are applied once they're committed, while the following ones are applied by the handlers.
+    term_start_index: u64,
+}
+
+impl Election {
+    fn metadata(&self) -> ClusterMetadata {
+        ClusterMetadata {
+            term: self.term,
+            voted_for: self.voted_for,
+            commit_index: self.commit_index,
+        }
+    }
+}
+
+/// The messages appended by the leader to the partition of the replicated topic, awaiting the acknowledgements of the peers.
+#[derive(Debug)]
+pub struct MessagesReplication {
+    node: Arc<ClusterNode>,
+    stream_id: u32,
+    topic_id: u32,
+    partition_id: u32,
+    replication_factor: u8,
+    required: u32,
+    partition: IggySharedMut<Partition>,
+    next_offset: u64,
+    term: u64,
+}
+
+impl MessagesReplication {
+    pub async fn wait(self) -> Result<(), IggyError> {
+        self.node.wait_for_replicas(&self).await
+    }
+}
+
+/// The node of the cluster, responsible for the leader election and the replication
+/// of the state entries and the partitions (of the topics with replication factor > 1) to its peers.
+#[derive(Debug)]
+pub struct ClusterNode {
+    pub id: u32,
+    peers: Vec<Arc<ClusterPeer>>,
+    state: Arc<StateKind>,
+    heartbeat_interval: IggyDuration,
+    election_timeout: IggyDuration,
+    replication_timeout: IggyDuration,
+    election: Mutex<Election>,
+    /// Held while the election state is changed and persisted, so the metadata is written in order,
+    /// without blocking the election state during the I/O.
+    persist_lock: tokio::sync::Mutex<()>,
+    applied_index: AtomicU64,
+    metadata_path: String,
+    replicated_offsets: DashMap<(u32, u32, u32, u32), u64>,
+    /// The term of the leader, which has verified the partition of this node (by stream, topic and partition ID).
+    partition_terms: DashMap<(u32, u32, u32), u64>,
+}
+
+impl ClusterNode {
+    pub fn new(
+        config: &ClusterConfig,
+        state: Arc<StateKind>,
+        metadata_path: &str,
+    ) -> Result<Self, IggyError> {
+        let metadata = ClusterMetadata::load(metadata_path).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to load cluster metadata, path: {metadata_path}")
+        })?;
+        let peers = config
+            .peers
+            .iter()
+            .map(|peer| {
+                Arc::new(ClusterPeer::new(
+                    config.node.id,
+                    &config.secret,
+                    peer,
+                    config.replication_timeout,
+                ))
+            })
+            .collect();
+        let node = Self {
+            id: config.node.id,
+            peers,
+            state,
+            heartbeat_interval: config.heartbeat_interval,
+            election_timeout: config.election_timeout,
+            replication_timeout: config.replication_timeout,
+            election: Mutex::new(Election {
+                role: ClusterNodeRole::Follower,
+                term: metadata.map(|metadata| metadata.term).unwrap_or_default(),
+                voted_for: metadata.and_then(|metadata| metadata.voted_for),
+                leader_id: None,
+                deadline: Instant::now(),
+                // The log of the node running without the metadata (e.g. created before it was introduced) is considered committed.
+                commit_index: metadata
+                    .map(|metadata| metadata.commit_index)
+                    .unwrap_or(u64::MAX),
+                term_start_index: 0,
+            }),
+            persist_lock: tokio::sync::Mutex::new(()),
+            applied_index: AtomicU64::new(0),
+            metadata_path: metadata_path.to_owned(),
+            replicated_offsets: DashMap::new(),
+            partition_terms: DashMap::new(),
+        };
+        node.election.lock().unwrap().deadline = node.next_election_deadline();
+        Ok(node)
+    }
+
+    /// Caps the persisted commit index at the number of the state entries loaded from the log,
+    /// and returns it as the number of the entries to be applied on startup.
+    pub fn init_commit_index(&self, next_index: u64) -> u64 {
+        let mut election = self.election.lock().unwrap();
+        election.commit_index = election.commit_index.min(next_index);
+        self.applied_index
+            .store(election.commit_index, Ordering::SeqCst);
+        election.commit_index
+    }
+
+    /// Commits the state entries appended on startup (e.g. the root user created on the empty log),
+    /// which are the same on each node, so they don't have to wait for the leader.
+    pub async fn commit_initial_entries(&self, next_index: u64) -> Result<(), IggyError> {
+        let _persist_lock = self.persist_lock.lock().await;
+        let metadata = {
+            let election = self.election.lock().unwrap();
+            if next_index <= election.commit_index {
+                return Ok(());
+            }
+
+            ClusterMetadata {
+                commit_index: next_index,
+                ..election.metadata()
+            }
+        };
+        metadata.persist(&self.metadata_path).await?;
+        self.election.lock().unwrap().commit_index = next_index;
+        self.applied_index.store(next_index, Ordering::SeqCst);
+        self.state.set_commit_index(next_index);
+        Ok(())
+    }
+
+    /// Makes the state entries applied by the leader wait until they're committed by the majority of the nodes.
+    pub fn enable_commit_timeout(&self) {
+        if !self.peers.is_empty() {
+            self.state.set_commit_timeout(self.replication_timeout);
+        }
+    }
+
+    /// Returns the number of the state entries committed by the majority of the nodes.
+    pub fn get_commit_index(&self) -> u64 {
+        self.election.lock().unwrap().commit_index
+    }
+
+    /// Returns the number of the state entries applied to the system.
+    pub fn get_applied_index(&self) -> u64 {
+        self.applied_index.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of nodes in the cluster, including the current one.
+    pub fn get_nodes_count(&self) -> u32 {
+        self.peers.len() as u32 + 1
+    }
+
+    pub fn contains_peer(&self, node_id: u32) -> bool {
+        self.peers.iter().any(|peer| peer.id == node_id)
+    }
+
+    pub fn get_role(&self) -> ClusterNodeRole {
+        self.election.lock().unwrap().role
+    }
+
+    pub fn get_term(&self) -> u64 {
+        self.election.lock().unwrap().term
+    }
+
+    pub fn get_leader_id(&self) -> Option<u32> {
+        self.election.lock().unwrap().leader_id
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.get_role() == ClusterNodeRole::Leader
+    }
+
+    /// Returns an error pointing to the current leader, if the node is not the leader.
+    /// The leader handles the commands once its no-op entry is applied, along with the entries of the previous terms.
+    pub fn ensure_leader(&self) -> Result<(), IggyError> {
+        let election = self.election.lock().unwrap();
+        if election.role == ClusterNodeRole::Leader {
+            if self.get_applied_index() > election.term_start_index {
+                return Ok(());
+            }
+
+            return Err(IggyError::LeaderNotElected);
+        }
+
+        match election.leader_id {
+            Some(leader_id) => Err(IggyError::NotLeader(leader_id)),
+            None => Err(IggyError::LeaderNotElected),
+        }
+    }
+
+    pub async fn handle_request_vote(&self, command: &RequestVote) -> VoteResponse {
+        let _persist_lock = self.persist_lock.lock().await;
+        let (metadata, granted) = {
+            let mut election = self.election.lock().unwrap();
+            if command.term < election.term {
+                return VoteResponse {
+                    term: election.term,
+                    granted: false,
+                };
+            }
+
+            let is_newer_term = command.term > election.term;
+            if is_newer_term {
+                self.mark_applied_if_leader(&election);
+                election.term = command.term;
+                election.role = ClusterNodeRole::Follower;
+                election.voted_for = None;
+                election.leader_id = None;
+            }
+
+            // The vote is granted only to the candidate whose log is at least as up-to-date as the local one.
+            let is_up_to_date = (command.last_term, command.next_index)
+                >= (self.state.last_term(), self.state.next_index());
+            let can_vote = election
+                .voted_for
+                .is_none_or(|voted_for| voted_for == command.candidate_id);
+            let granted = is_up_to_date && can_vote;
+            if granted {
+                election.voted_for = Some(command.candidate_id);
+            } else if !is_newer_term {
+                return VoteResponse {
+                    term: election.term,
+                    granted: false,
+                };
+            }
+
+            (election.metadata(), granted)
+        };
+
+        // The vote must be durable before it's sent, otherwise the node could vote twice in the same term after restart,
+        // while the newer term must be persisted even if the vote isn't granted.
+        let result = metadata.persist(&self.metadata_path).await;
+        let mut election = self.election.lock().unwrap();
+        let granted = match result {
+            Ok(()) => granted,
+            Err(error) if granted => {
+                error!(
+                    "{COMPONENT} (error: {error}) - failed to persist the vote for node: {} in term: {}",
+                    command.candidate_id, command.term
+                );
+                election.voted_for = None;
+                false
+            }
+            Err(error) => {
+                error!(
+                    "{COMPONENT} (error: {error}) - failed to persist the term: {}",
+                    command.term
+                );
+                false
+            }
+        };
+
+        if granted {
+            election.deadline = self.next_election_deadline();
+            debug!(
+                "{COMPONENT} - node: {} voted for node: {} in term: {}",
+                self.id, command.candidate_id, command.term
+            );
+        }
+
+        VoteResponse {
+            term: election.term,
+            granted,
+        }
+    }
+
+    /// Accepts the leader of the given term, returns `false` if the term is stale.
+    pub async fn accept_leader(&self, term: u64, leader_id: u32) -> bool {
+        let _persist_lock = self.persist_lock.lock().await;
+        let metadata = {
+            let mut election = self.election.lock().unwrap();
+            if term < election.term {
+                return false;
+            }
+
+            election.deadline = self.next_election_deadline();
+            if term == election.term
+                && election.role == ClusterNodeRole::Follower
+                && election.leader_id == Some(leader_id)
+            {
+                return true;
+            }
+
+            self.mark_applied_if_leader(&election);
+            election.term = term;
+            election.role = ClusterNodeRole::Follower;
+            election.voted_for = Some(leader_id);
+            election.leader_id = Some(leader_id);
+            election.metadata()
+        };
+
+        if let Err(error) = metadata.persist(&self.metadata_path).await {
+            error!("{COMPONENT} (error: {error}) - failed to persist the term: {term} of the leader: {leader_id}");
+        }
+        self.state.set_leader(term, leader_id);
+        info!(
+            "{COMPONENT} - node: {} accepted the leader: {leader_id} in term: {term}",
+            self.id
+        );
+        true
+    }
+
+    /// Returns the term of the leader, which has verified that the partition matches its own one,
+    /// the messages exceeding the leader's partition are truncated when it's verified in the newer term.
+    pub fn get_partition_term(&self, key: (u32, u32, u32)) -> u64 {
+        self.partition_terms
+            .get(&key)
+            .map(|term| *term)
+            .unwrap_or_default()
+    }
+
+    pub fn set_partition_term(&self, key: (u32, u32, u32), term: u64) {
+        self.partition_terms.insert(key, term);
+    }
+
+    pub fn start(self: Arc<Self>, system: SharedSystem) {
+        for peer in self.peers.iter() {
+            let node = self.clone();
+            let state_peer = peer.clone();
+            let state_system = system.clone();
+            tokio::spawn(async move {
+                node.run_state_loop(state_peer, state_system).await;
+            });
+
+            let node = self.clone();
+            let peer = peer.clone();
+            let system = system.clone();
+            tokio::spawn(async move {
+                node.run_partitions_loop(peer, system).await;
+            });
+        }
+
+        let node = self.clone();
+        tokio::spawn(async move {
+            node.run_election_loop(system).await;
+        });
+    }
+
+    /// Applies the committed state entries, which haven't been applied yet, and persists the commit index.
+    pub async fn commit_entries(
+        &self,
+        commit_index: u64,
+        system: &SharedSystem,
+    ) -> Result<(), IggyError> {
+        self.apply_entries(commit_index, system).await?;
+        let _persist_lock = self.persist_lock.lock().await;
+        let metadata = {
+            let election = self.election.lock().unwrap();
+            if commit_index <= election.commit_index {
+                return Ok(());
+            }
+
+            ClusterMetadata {
+                commit_index,
+                ..election.metadata()
+            }
+        };
+        metadata.persist(&self.metadata_path).await?;
+        self.election.lock().unwrap().commit_index = commit_index;
+        self.state.set_commit_index(commit_index);
+        Ok(())
+    }
+
+    /// Discards the effects of the uncommitted state entries applied by the node while it was the leader,
+    /// once they're truncated, by rebuilding the system from the committed entries.
+    pub async fn reload_committed_state(&self, system: &SharedSystem) -> Result<(), IggyError> {
+        let mut system = system.write().await;
+        let commit_index = self.get_commit_index();
+        system.reload_committed_state(commit_index).await?;
+        self.applied_index.store(commit_index, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Applies the state entries from the log to the system, up to the given (exclusive) index.
+    async fn apply_entries(&self, index: u64, system: &SharedSystem) -> Result<(), IggyError> {
+        if index <= self.get_applied_index() {
+            return Ok(());
+        }
+
+        let mut system = system.write().await;
+        let applied_index = self.get_applied_index();
+        if index <= applied_index {
+            return Ok(());
+        }
+
+        let entries = self
+            .state
+            .load_entries_from(applied_index, (index - applied_index) as usize)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to load state entries starting at index: {applied_index}"
+                )
+            })?;
+        for entry in entries {
+            apply_entry(&mut system, &entry)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to apply state entry with index: {}",
+                        entry.index
+                    )
+                })?;
+            self.applied_index.store(entry.index + 1, Ordering::SeqCst);
+        }
+
+        Ok(())
+    }
+
+    /// Prepares the replication of the messages appended to the partition, if the topic's replication factor requires any peers.
+    /// The returned replication holds its own handles, so it can be awaited once the system lock is released.
+    pub async fn replicate_messages(
+        self: &Arc<Self>,
+        topic: &Topic,
+        partition_id: u32,
+    ) -> Result<Option<MessagesReplication>, IggyError> {
+        let term = self.get_leader_term()?;
+        let required = (topic.replication_factor as u32).saturating_sub(1);
+        if required == 0 {
+            return Ok(None);
+        }
+
+        let partition = topic.get_partition(partition_id)?;
+        let next_offset = partition.read().await.get_next_offset();
+        Ok(Some(MessagesReplication {
+            node: self.clone(),
+            stream_id: topic.stream_id,
+            topic_id: topic.topic_id,
+            partition_id,
+            replication_factor: topic.replication_factor,
+            required,
+            partition,
+            next_offset,
+            term,
+        }))
+    }
+
+    /// Replicates the partition to the peers and waits for the acknowledgements required by the topic's replication factor.
+    /// Returns as soon as enough peers have acknowledged the messages, each peer is given the replication timeout.
+    async fn wait_for_replicas(&self, replication: &MessagesReplication) -> Result<(), IggyError> {
+        let timeout = self.replication_timeout.get_duration();
+        let mut replications = self
+            .peers
+            .iter()
+            .map(|peer| {
+                tokio::time::timeout(
+                    timeout,
+                    self.replicate_partition_with_retry(
+                        peer,
+                        replication.stream_id,
+                        replication.topic_id,
+                        replication.partition_id,
+                        &replication.partition,
+                        replication.term,
+                    ),
+                )
+            })
+            .collect::<FuturesUnordered<_>>();
+        let mut acknowledgements = 0;
+        while let Some(result) = replications.next().await {
+            if matches!(result, Ok(Ok(offset)) if offset >= replication.next_offset) {
+                acknowledgements += 1;
+                if acknowledgements >= replication.required {
+                    return Ok(());
+                }
+            }
+        }
+
+        error!(
+            "{COMPONENT} - not enough replicas: {} for partition: {}, topic: {}, stream: {}, required replication factor: {}",
+            acknowledgements + 1,
+            replication.partition_id,
+            replication.topic_id,
+            replication.stream_id,
+            replication.replication_factor
+        );
+        Err(IggyError::NotEnoughReplicas(
+            acknowledgements + 1,
+            replication.replication_factor,
+        ))
+    }
+
+    fn get_leader_term(&self) -> Result<u64, IggyError> {
+        let election = self.election.lock().unwrap();
+        match election.role {
+            ClusterNodeRole::Leader => Ok(election.term),
+            _ => Err(election
+                .leader_id
+                .map_or(IggyError::LeaderNotElected, IggyError::NotLeader)),
+        }
+    }
+
+    fn next_election_deadline(&self) -> Instant {
+        // The randomized timeout (between 1x and 2x of the configured one) avoids the split votes.
+        // The jitter must not be derived from the clock, as the nodes losing the leader at the same time would keep colliding.
+        let timeout = self.election_timeout.as_micros().max(1);
+        let mut seed = [0; 8];
+        if SystemRandom::new().fill(&mut seed).is_err() {
+            seed = (IggyTimestamp::now().as_micros() + self.id as u64 * 7919).to_le_bytes();
+        }
+        Instant::now() + Duration::from_micros(timeout + u64::from_le_bytes(seed) % timeout)
+    }
+
+    /// The handlers of the leader apply the commands before their entries are committed, so once it steps down,
+    /// the entries appended in its term are considered applied, unlike the uncommitted entries of the previous terms,
+    /// which can only precede the entries of the handlers, as the commands aren't handled until the no-op entry is applied.
+    /// If the new leader truncates any of them, the committed state is reloaded.
+    fn mark_applied_if_leader(&self, election: &Election) {
+        if election.role == ClusterNodeRole::Leader
+            && self.get_applied_index() > election.term_start_index
+        {
+            self.applied_index
+                .fetch_max(self.state.next_index(), Ordering::SeqCst);
+        }
+    }
+
+    async fn step_down(&self, term: u64) {
+        let _persist_lock = self.persist_lock.lock().await;
+        let metadata = {
+            let mut election = self.election.lock().unwrap();
+            if term <= election.term {
+                return;
+            }
+
+            info!(
+                "{COMPONENT} - node: {} discovered the higher term: {term}, current term: {}, stepping down",
+                self.id, election.term
+            );
+            self.mark_applied_if_leader(&election);
+            election.term = term;
+            election.role = ClusterNodeRole::Follower;
+            election.voted_for = None;
+            election.leader_id = None;
+            election.deadline = self.next_election_deadline();
+            election.metadata()
+        };
+
+        if let Err(error) = metadata.persist(&self.metadata_path).await {
+            error!("{COMPONENT} (error: {error}) - failed to persist the term: {term}");
+        }
+    }
+
+    async fn run_election_loop(&self, system: SharedSystem) {
+        let mut interval = tokio::time::interval(self.heartbeat_interval.get_duration());
+        loop {
+            interval.tick().await;
+            let should_start_election = {
+                let election = self.election.lock().unwrap();
+                election.role != ClusterNodeRole::Leader && Instant::now() >= election.deadline
+            };
+            if should_start_election {
+                self.start_election(&system).await;
+            }
+        }
+    }
+
+    async fn start_election(&self, system: &SharedSystem) {
+        let (term, deadline) = {
+            let _persist_lock = self.persist_lock.lock().await;
+            let (metadata, deadline) = {
+                let mut election = self.election.lock().unwrap();
+                election.term += 1;
+                election.role = ClusterNodeRole::Candidate;
+                election.voted_for = Some(self.id);
+                election.leader_id = None;
+                election.deadline = self.next_election_deadline();
+                (election.metadata(), election.deadline)
+            };
+            if let Err(error) = metadata.persist(&self.metadata_path).await {
+                error!(
+                    "{COMPONENT} (error: {error}) - failed to persist the term: {}, the election is cancelled",
+                    metadata.term
+                );
+                self.election.lock().unwrap().role = ClusterNodeRole::Follower;
+                return;
+            }
+            (metadata.term, deadline)
+        };
+
+        info!(
+            "{COMPONENT} - node: {} started the election in term: {term}",
+            self.id
+        );
+        let next_index = self.state.next_index();
+        let last_term = self.state.last_term();
+        let mut responses = self
+            .peers
+            .iter()
+            .map(|peer| {
+                peer.send(ClusterCommand::RequestVote(RequestVote {
+                    term,
+                    candidate_id: self.id,
+                    next_index,
+                    last_term,
+                }))
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        // The votes are counted as they arrive, and the unreachable peers are given up on at the randomized deadline,
+        // so the candidates don't keep starting the elections at the same time after the peers time out.
+        let deadline = tokio::time::Instant::from_std(deadline);
+        let mut votes = 1;
+        while let Ok(Some(response)) = tokio::time::timeout_at(deadline, responses.next()).await {
+            let Ok(response) = response.and_then(VoteResponse::from_bytes) else {
+                continue;
+            };
+            if response.term > term {
+                self.step_down(response.term).await;
+                return;
+            }
+            if response.granted {
+                votes += 1;
+                if votes > self.get_nodes_count() / 2 {
+                    drop(responses);
+                    self.become_leader(term, system).await;
+                    return;
+                }
+            }
+        }
+
+        debug!(
+            "{COMPONENT} - node: {} received {votes} vote(s) in term: {term}, the election has failed",
+            self.id
+        );
+    }
+
+    async fn become_leader(&self, term: u64, system: &SharedSystem) {
+        // Only the committed entries are applied, the entries of the previous terms can't be committed by the leader
+        // until the entry of its own term is stored by the majority of the nodes, so the no-op entry is appended.
+        if let Err(error) = self.apply_entries(self.get_commit_index(), system).await {
+            error!(
+                "{COMPONENT} (error: {error}) - node: {} failed to apply the committed state entries before becoming the leader in term: {term}",
+                self.id
+            );
+            return;
+        }
+
+        let next_index = self.state.next_index();
+        {
+            let mut election = self.election.lock().unwrap();
+            if election.term != term || election.role != ClusterNodeRole::Candidate {
+                return;
+            }
+
+            election.role = ClusterNodeRole::Leader;
+            election.leader_id = Some(self.id);
+            election.term_start_index = next_index;
+        }
+
+        self.state.set_leader(term, self.id);
+        for peer in self.peers.iter() {
+            peer.set_next_index(next_index);
+            peer.set_match_index(0);
+        }
+        self.replicated_offsets.clear();
+        info!(
+            "{COMPONENT} - node: {} has become the leader in term: {term}",
+            self.id
+        );
+
+        if let Err(error) = self.state.apply(0, EntryCommand::Noop).await {
+            warn!(
+                "{COMPONENT} (error: {error}) - the no-op entry of the leader: {} in term: {term} has not been committed yet",
+                self.id
+            );
+        }
+
+        // Without the peers, the leader is the majority on its own, so the no-op entry is committed right away.
+        if self.peers.is_empty() {
+            self.advance_commit_index(term).await;
+            if let Err(error) = self.apply_committed_entries(system).await {
+                error!("{COMPONENT} (error: {error}) - failed to apply the committed state entries of the previous terms");
+            }
+        }
+    }
+
+    /// Replicates the state entries to the peer, independently of the partitions,
+    /// as the handlers wait for the entries to be committed while holding the system lock.
+    async fn run_state_loop(&self, peer: Arc<ClusterPeer>, system: SharedSystem) {
+        let mut interval = tokio::time::interval(self.heartbeat_interval.get_duration());
+        loop {
+            interval.tick().await;
+            let Ok(term) = self.get_leader_term() else {
+                continue;
+            };
+
+            if let Err(error) = self.sync_state(&peer, term).await {
+                debug!(
+                    "{COMPONENT} - failed to sync the state with node: {}. {error}",
+                    peer.id
+                );
+            }
+
+            if let Err(error) = self.apply_committed_entries(&system).await {
+                error!("{COMPONENT} (error: {error}) - failed to apply the committed state entries of the previous terms");
+            }
+        }
+    }
+
+    async fn run_partitions_loop(&self, peer: Arc<ClusterPeer>, system: SharedSystem) {
+        let mut interval = tokio::time::interval(self.heartbeat_interval.get_duration());
+        loop {
+            interval.tick().await;
+            let Ok(term) = self.get_leader_term() else {
+                continue;
+            };
+
+            if let Err(error) = self.sync_partitions(&peer, term, &system).await {
+                debug!(
+                    "{COMPONENT} - failed to sync the partitions with node: {}. {error}",
+                    peer.id
+                );
+            }
+        }
+    }
+
+    /// Sends the missing state entries to the peer, or the empty heartbeat if the peer is up-to-date.
+    async fn sync_state(&self, peer: &ClusterPeer, term: u64) -> Result<(), IggyError> {
+        let next_index = peer.get_next_index();
+        let prev_log_term = match next_index {
+            0 => 0,
+            _ => self.state.term_at(next_index - 1).unwrap_or_default(),
+        };
+        let entries = if next_index < self.state.next_index() {
+            self.state
+                .load_entries_from(next_index, MAX_ENTRIES_PER_REQUEST)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to load state entries starting at index: {next_index}"
+                    )
+                })?
+        } else {
+            Vec::new()
+        };
+
+        let entries_count = entries.len();
+        let response = peer
+            .send(ClusterCommand::AppendEntries(AppendEntries {
+                term,
+                leader_id: self.id,
+                prev_log_index: next_index,
+                prev_log_term,
+                commit_index: self.get_commit_index(),
+                entries,
+            }))
+            .await?;
+        let response = AppendEntriesResponse::from_bytes(response)?;
+        if response.term > term {
+            self.step_down(response.term).await;
+            return Err(IggyError::StaleClusterTerm(term, response.term));
+        }
+
+        if !response.success {
+            // The logs don't match, so the preceding entries will be sent with the next request.
+            let next_index = response.next_index.min(next_index.saturating_sub(1));
+            debug!(
+                "{COMPONENT} - node: {} rejected the state entries, retrying from index: {next_index}",
+                peer.id
+            );
+            peer.set_next_index(next_index);
+            return Ok(());
+        }
+
+        if entries_count > 0 {
+            debug!(
+                "{COMPONENT} - replicated {entries_count} state entries to node: {}, next index: {}",
+                peer.id, response.next_index
+            );
+        }
+        peer.set_next_index(response.next_index);
+        peer.set_match_index(response.next_index);
+        self.advance_commit_index(term).await;
+        Ok(())
+    }
+
+    /// Commits the state entries stored by the majority of the nodes, as long as the last one belongs to the current term.
+    async fn advance_commit_index(&self, term: u64) {
+        let mut indexes = self
+            .peers
+            .iter()
+            .map(|peer| peer.get_match_index())
+            .collect::<Vec<_>>();
+        indexes.push(self.state.next_index());
+        indexes.sort_unstable_by(|a, b| b.cmp(a));
+        let commit_index = indexes[indexes.len() / 2];
+        if commit_index == 0 || self.state.term_at(commit_index - 1) != Some(term) {
+            return;
+        }
+
+        let _persist_lock = self.persist_lock.lock().await;
+        let (metadata, term_start_index) = {
+            let election = self.election.lock().unwrap();
+            if election.term != term || commit_index <= election.commit_index {
+                return;
+            }
+
+            let metadata = ClusterMetadata {
+                commit_index,
+                ..election.metadata()
+            };
+            (metadata, election.term_start_index)
+        };
+        if let Err(error) = metadata.persist(&self.metadata_path).await {
+            error!(
+                "{COMPONENT} (error: {error}) - failed to persist the commit index: {commit_index}"
+            );
+            return;
+        }
+
+        self.election.lock().unwrap().commit_index = commit_index;
+        // Once the no-op entry is applied, the following entries have already been applied by the handlers.
+        if self.get_applied_index() > term_start_index {
+            self.applied_index.fetch_max(commit_index, Ordering::SeqCst);
+            self.state.set_commit_index(commit_index);
+        }
+    }
+
+    /// Applies the entries of the previous terms, once they're committed along with the no-op entry of the leader.
+    async fn apply_committed_entries(&self, system: &SharedSystem) -> Result<(), IggyError> {
+        let (commit_index, term_start_index) = {
+            let election = self.election.lock().unwrap();
+            (election.commit_index, election.term_start_index)
+        };
+        if commit_index <= term_start_index || self.get_applied_index() > term_start_index {
+            return Ok(());
+        }
+
+        self.apply_entries(term_start_index + 1, system).await?;
+        self.applied_index.fetch_max(commit_index, Ordering::SeqCst);
+        self.state.set_commit_index(commit_index);
+        Ok(())
+    }
+
+    async fn sync_partitions(
+        &self,
+        peer: &ClusterPeer,
+        term: u64,
+        system: &SharedSystem,
+    ) -> Result<(), IggyError> {
+        let partitions = {
+            let system = system.read().await;
+            let mut partitions = Vec::new();
+            for stream in system.get_streams() {
+                for topic in stream.get_topics() {
+                    if topic.replication_factor <= 1 {
+                        continue;
+                    }
+
+                    for (partition_id, partition) in topic.partitions.iter() {
+                        partitions.push((
+                            topic.stream_id,
+                            topic.topic_id,
+                            *partition_id,
+                            partition.clone(),
+                        ));
+                    }
+                }
+            }
+            partitions
+        };
+
+        for (stream_id, topic_id, partition_id, partition) in partitions {
+            self.replicate_partition(peer, stream_id, topic_id, partition_id, &partition, term)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn replicate_partition_with_retry(
+        &self,
+        peer: &ClusterPeer,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+        partition: &IggySharedMut<Partition>,
+        term: u64,
+    ) -> Result<u64, IggyError> {
+        match self
+            .replicate_partition(peer, stream_id, topic_id, partition_id, partition, term)
+            .await
+        {
+            Ok(offset) => Ok(offset),
+            Err(error) => {
+                // The peer might not know the topic or partition yet, if the state hasn't been replicated.
+                debug!(
+                    "{COMPONENT} - failed to replicate partition: {partition_id} to node: {}, syncing the state. {error}",
+                    peer.id
+                );
+                self.sync_state(peer, term).await?;
+                self.replicate_partition(peer, stream_id, topic_id, partition_id, partition, term)
+                    .await
+            }
+        }
+    }
+
+    /// Sends the missing messages to the peer and returns the offset of the next message expected by the peer.
+    /// Each batch starts with the message preceding the peer's offset, so the peer can verify that its partition
+    /// matches the leader's one, otherwise the peer requests the preceding messages until the partitions match,
+    /// and truncates the conflicting messages. The partition lock is held only while reading the batch, never while waiting for the peer.
+    async fn replicate_partition(
+        &self,
+        peer: &ClusterPeer,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+        partition: &IggySharedMut<Partition>,
+        term: u64,
+    ) -> Result<u64, IggyError> {
+        let key = (peer.id, stream_id, topic_id, partition_id);
+        let next_offset = partition.read().await.get_next_offset();
+        // Only the offsets acknowledged after the verification are stored, the probed one must be verified first.
+        let mut peer_offset = self.replicated_offsets.get(&key).map(|offset| *offset);
+        let mut probed_offset = None;
+        loop {
+            if peer_offset.is_some_and(|offset| offset >= next_offset) {
+                break;
+            }
+
+            // The offset of the peer is unknown, so it's requested with the empty batch.
+            let (start_offset, messages) = match peer_offset.or(probed_offset) {
+                Some(offset) => {
+                    let start_offset = offset.saturating_sub(1);
+                    (
+                        start_offset,
+                        partition
+                            .read()
+                            .await
+                            .get_messages_by_offset(start_offset, MAX_MESSAGES_PER_REQUEST)
+                            .await?,
+                    )
+                }
+                None => (next_offset, Vec::new()),
+            };
+            let is_probe = messages.is_empty();
+            let response = peer
+                .send(ClusterCommand::ReplicateMessages(ReplicateMessages {
+                    term,
+                    leader_id: self.id,
+                    stream_id,
+                    topic_id,
+                    partition_id,
+                    start_offset,
+                    messages,
+                }))
+                .await?;
+            let response = ReplicateMessagesResponse::from_bytes(response)?;
+            if response.term > term {
+                self.step_down(response.term).await;
+                return Err(IggyError::StaleClusterTerm(term, response.term));
+            }
+
+            // The peer's messages exceeding the leader's partition will be truncated once it's verified.
+            let offset = response
+                .next_offset
+                .min(partition.read().await.get_next_offset());
+            if is_probe {
+                if probed_offset.is_some() {
+                    debug!(
+                        "{COMPONENT} - no messages to verify partition: {partition_id}, topic: {topic_id}, stream: {stream_id} of node: {} at offset: {offset}",
+                        peer.id
+                    );
+                    break;
+                }
+                probed_offset = Some(offset);
+                continue;
+            }
+
+            if offset <= start_offset && start_offset > 0 {
+                debug!(
+                    "{COMPONENT} - node: {} rejected the messages of partition: {partition_id}, topic: {topic_id}, stream: {stream_id} starting at offset: {start_offset}, retrying from offset: {offset}",
+                    peer.id
+                );
+                self.replicated_offsets.remove(&key);
+                peer_offset = None;
+                probed_offset = Some(offset);
+                continue;
+            }
+
+            self.replicated_offsets.insert(key, offset);
+            if peer_offset.is_some_and(|previous_offset| offset <= previous_offset) {
+                warn!(
+                    "{COMPONENT} - node: {} made no progress replicating partition: {partition_id}, topic: {topic_id}, stream: {stream_id} at offset: {offset}",
+                    peer.id
+                );
+                break;
+            }
+            peer_offset = Some(offset);
+        }
+
+        Ok(peer_offset.unwrap_or_default())
+    }
+}
+
+impl Display for ClusterNodeRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClusterNodeRole::Follower => write!(f, "follower"),
+            ClusterNodeRole::Candidate => write!(f, "candidate"),
+            ClusterNodeRole::Leader => write!(f, "leader"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::cluster::{ClusterNodeConfig, ClusterPeerConfig};
+    use crate::state::MockState;
+
+    fn node(next_index: u64, last_term: u64, metadata_path: &str) -> ClusterNode {
+        let mut state = MockState::new();
+        state.expect_next_index().return_const(next_index);
+        state.expect_last_term().return_const(last_term);
+        state.expect_set_leader().return_const(());
+        state
+            .expect_term_at()
+            .returning(move |index| (index < next_index).then_some(last_term));
+        state.expect_set_commit_index().return_const(());
+        let config = ClusterConfig {
+            enabled: true,
+            secret: "secret".to_string(),
+            node: ClusterNodeConfig {
+                id: 1,
+                address: "127.0.0.1:8070".to_string(),
+            },
+            peers: vec![
+                ClusterPeerConfig {
+                    id: 2,
+                    address: "127.0.0.1:8071".to_string(),
+                },
+                ClusterPeerConfig {
+                    id: 3,
+                    address: "127.0.0.1:8072".to_string(),
+                },
+            ],
+            heartbeat_interval: IggyDuration::from(100_000),
+            election_timeout: IggyDuration::from(1_000_000),
+            replication_timeout: IggyDuration::from(1_000_000),
+        };
+        ClusterNode::new(&config, Arc::new(StateKind::Mock(state)), metadata_path).unwrap()
+    }
+
+    fn metadata_path(directory: &tempfile::TempDir) -> String {
+        directory
+            .path()
+            .join("cluster")
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn request_vote(term: u64, candidate_id: u32, next_index: u64, last_term: u64) -> RequestVote {
+        RequestVote {
+            term,
+            candidate_id,
+            next_index,
+            last_term,
+        }
+    }
+
+    #[tokio::test]
+    async fn vote_should_be_granted_once_per_term() {
+        let directory = tempfile::tempdir().unwrap();
+        let node = node(5, 1, &metadata_path(&directory));
+
+        assert!(
+            node.handle_request_vote(&request_vote(2, 2, 5, 1))
+                .await
+                .granted
+        );
+        assert!(
+            node.handle_request_vote(&request_vote(2, 2, 5, 1))
+                .await
+                .granted
+        );
+        assert!(
+            !node
+                .handle_request_vote(&request_vote(2, 3, 5, 1))
+                .await
+                .granted
+        );
+        assert!(
+            node.handle_request_vote(&request_vote(3, 3, 5, 1))
+                .await
+                .granted
+        );
+    }
+
+    #[tokio::test]
+    async fn vote_should_not_be_granted_to_candidate_with_outdated_log() {
+        let directory = tempfile::tempdir().unwrap();
+        let node = node(5, 2, &metadata_path(&directory));
+
+        assert!(
+            !node
+                .handle_request_vote(&request_vote(3, 2, 10, 1))
+                .await
+                .granted
+        );
+        assert!(
+            !node
+                .handle_request_vote(&request_vote(3, 3, 4, 2))
+                .await
+                .granted
+        );
+        assert!(
+            node.handle_request_vote(&request_vote(3, 3, 1, 3))
+                .await
+                .granted
+        );
+    }
+
+    #[tokio::test]
+    async fn stale_leader_should_not_be_accepted() {
+        let directory = tempfile::tempdir().unwrap();
+        let node = node(0, 0, &metadata_path(&directory));
+
+        assert!(node.accept_leader(2, 2).await);
+        assert_eq!(node.get_leader_id(), Some(2));
+        assert!(matches!(node.ensure_leader(), Err(IggyError::NotLeader(2))));
+        assert!(!node.accept_leader(1, 3).await);
+        assert_eq!(node.get_leader_id(), Some(2));
+        assert_eq!(node.get_term(), 2);
+    }
+
+    #[tokio::test]
+    async fn leader_should_not_be_elected_initially() {
+        let directory = tempfile::tempdir().unwrap();
+        let node = node(0, 0, &metadata_path(&directory));
+
+        assert_eq!(node.get_role(), ClusterNodeRole::Follower);
+        assert!(matches!(
+            node.ensure_leader(),
+            Err(IggyError::LeaderNotElected)
+        ));
+    }
+
+    #[tokio::test]
+    async fn vote_should_be_persisted_across_restarts() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = metadata_path(&directory);
+        {
+            let node = node(5, 1, &path);
+            assert!(
+                node.handle_request_vote(&request_vote(2, 2, 5, 1))
+                    .await
+                    .granted
+            );
+        }
+
+        let node = node(5, 1, &path);
+        assert_eq!(node.get_term(), 2);
+        assert!(
+            !node
+                .handle_request_vote(&request_vote(2, 3, 5, 1))
+                .await
+                .granted
+        );
+        assert!(
+            node.handle_request_vote(&request_vote(2, 2, 5, 1))
+                .await
+                .granted
+        );
+    }
+
+    #[tokio::test]
+    async fn commit_index_should_advance_once_entries_of_current_term_are_stored_by_majority() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = metadata_path(&directory);
+        let node = node(10, 2, &path);
+        assert_eq!(node.init_commit_index(4), 4);
+        node.election.lock().unwrap().term = 2;
+
+        node.peers[0].set_match_index(3);
+        node.peers[1].set_match_index(3);
+        node.advance_commit_index(2).await;
+        assert_eq!(node.get_commit_index(), 4);
+
+        node.peers[1].set_match_index(8);
+        node.advance_commit_index(3).await;
+        assert_eq!(node.get_commit_index(), 4);
+
+        node.advance_commit_index(2).await;
+        assert_eq!(node.get_commit_index(), 8);
+        assert_eq!(node.get_applied_index(), 8);
+        assert_eq!(
+            ClusterMetadata::load(&path).unwrap().unwrap().commit_index,
+            8
+        );
+    }
+
+    #[tokio::test]
+    async fn leader_should_handle_commands_once_its_noop_entry_is_applied() {
+        let directory = tempfile::tempdir().unwrap();
+        let node = node(10, 2, &metadata_path(&directory));
+        assert_eq!(node.init_commit_index(4), 4);
+        {
+            let mut election = node.election.lock().unwrap();
+            election.role = ClusterNodeRole::Leader;
+            election.term_start_index = 9;
+        }
+
+        assert!(matches!(
+            node.ensure_leader(),
+            Err(IggyError::LeaderNotElected)
+        ));
+        node.applied_index.store(10, Ordering::SeqCst);
+        assert!(node.ensure_leader().is_ok());
+    }
+
+    #[tokio::test]
+    async fn leader_stepping_down_should_not_mark_uncommitted_entries_of_previous_terms_as_applied()
+    {
+        let directory = tempfile::tempdir().unwrap();
+        let node = node(10, 2, &metadata_path(&directory));
+        assert_eq!(node.init_commit_index(4), 4);
+        {
+            let mut election = node.election.lock().unwrap();
+            election.term = 2;
+            election.role = ClusterNodeRole::Leader;
+            election.term_start_index = 9;
+        }
+
+        node.step_down(3).await;
+        assert_eq!(node.get_role(), ClusterNodeRole::Follower);
+        assert_eq!(node.get_applied_index(), 4);
+    }
+
+    #[tokio::test]
+    async fn leader_stepping_down_should_mark_entries_applied_by_handlers_as_applied() {
+        let directory = tempfile::tempdir().unwrap();
+        let node = node(10, 2, &metadata_path(&directory));
+        assert_eq!(node.init_commit_index(4), 4);
+        {
+            let mut election = node.election.lock().unwrap();
+            election.term = 2;
+            election.role = ClusterNodeRole::Leader;
+            election.term_start_index = 3;
+        }
+
+        node.step_down(3).await;
+        assert_eq!(node.get_applied_index(), 10);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::cluster::command::{ClusterCommand, Handshake};
+use crate::cluster::COMPONENT;
+use crate::configs::cluster::ClusterPeerConfig;
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::error::IggyError;
+use iggy::utils::duration::IggyDuration;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+const REQUEST_INITIAL_BYTES_LENGTH: usize = 4;
+const RESPONSE_INITIAL_BYTES_LENGTH: usize = 8;
+
+/// The connection to the other node in the cluster, established lazily and re-established after any failure.
+#[derive(Debug)]
+pub struct ClusterPeer {
+    pub id: u32,
+    pub address: String,
+    node_id: u32,
+    secret: String,
+    timeout: IggyDuration,
+    stream: Mutex<Option<TcpStream>>,
+    next_index: AtomicU64,
+    match_index: AtomicU64,
+}
+
+impl ClusterPeer {
+    pub fn new(
+        node_id: u32,
+        secret: &str,
+        config: &ClusterPeerConfig,
+        timeout: IggyDuration,
+    ) -> Self {
+        Self {
+            id: config.id,
+            address: config.address.clone(),
+            node_id,
+            secret: secret.to_owned(),
+            timeout,
+            stream: Mutex::new(None),
+            next_index: AtomicU64::new(0),
+            match_index: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the index of the next state entry to be sent to the peer.
+    pub fn get_next_index(&self) -> u64 {
+        self.next_index.load(Ordering::SeqCst)
+    }
+
+    pub fn set_next_index(&self, next_index: u64) {
+        self.next_index.store(next_index, Ordering::SeqCst);
+    }
+
+    /// Returns the number of the state entries known to be stored by the peer.
+    pub fn get_match_index(&self) -> u64 {
+        self.match_index.load(Ordering::SeqCst)
+    }
+
+    pub fn set_match_index(&self, match_index: u64) {
+        self.match_index.store(match_index, Ordering::SeqCst);
+    }
+
+    /// Sends the command and returns the response payload, the connection is dropped on timeout or transport error.
+    /// The connection is taken out for the duration of the exchange and put back only once it completes,
+    /// so a cancelled call never leaves the half-read response on the connection reused by the next call.
+    pub async fn send(&self, command: ClusterCommand) -> Result<Bytes, IggyError> {
+        let mut stream = self.stream.lock().await;
+        let connection = stream.take();
+        let result = tokio::time::timeout(self.timeout.get_duration(), async {
+            let mut connection = match connection {
+                Some(connection) => connection,
+                None => self.connect().await?,
+            };
+            let response = Self::exchange(&mut connection, &command).await?;
+            Ok::<_, IggyError>((connection, response))
+        })
+        .await;
+
+        match result {
+            Ok(Ok((connection, (status, payload)))) => {
+                *stream = Some(connection);
+                if status != 0 {
+                    return Err(IggyError::from_code(status));
+                }
+
+                Ok(payload)
+            }
+            Ok(Err(error)) => {
+                debug!(
+                    "{COMPONENT} - failed to send command to node: {}, address: {}. {error}",
+                    self.id, self.address
+                );
+                Err(error)
+            }
+            Err(_) => {
+                warn!(
+                    "{COMPONENT} - timed out sending command to node: {}, address: {}",
+                    self.id, self.address
+                );
+                Err(IggyError::TcpError)
+            }
+        }
+    }
+
+    async fn connect(&self) -> Result<TcpStream, IggyError> {
+        let mut stream = TcpStream::connect(&self.address)
+            .await
+            .map_err(|_| IggyError::CannotEstablishConnection)?;
+        stream.set_nodelay(true).map_err(|_| IggyError::TcpError)?;
+        let handshake = ClusterCommand::Handshake(Handshake {
+            node_id: self.node_id,
+            secret: self.secret.clone(),
+        });
+        let (status, _) = Self::exchange(&mut stream, &handshake).await?;
+        if status != 0 {
+            return Err(IggyError::from_code(status));
+        }
+
+        debug!(
+            "{COMPONENT} - connected to node: {}, address: {}",
+            self.id, self.address
+        );
+        Ok(stream)
+    }
+
+    async fn exchange(
+        stream: &mut TcpStream,
+        command: &ClusterCommand,
+    ) -> Result<(u32, Bytes), IggyError> {
+        let payload = command.to_bytes();
+        let mut request = BytesMut::with_capacity(REQUEST_INITIAL_BYTES_LENGTH + payload.len());
+        request.put_u32_le(payload.len() as u32);
+        request.put_slice(&payload);
+        stream
+            .write_all(&request)
+            .await
+            .map_err(|_| IggyError::TcpError)?;
+
+        let mut response_buffer = [0u8; RESPONSE_INITIAL_BYTES_LENGTH];
+        stream
+            .read_exact(&mut response_buffer)
+            .await
+            .map_err(|_| IggyError::Disconnected)?;
+        let status = u32::from_le_bytes(
+            response_buffer[..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let length = u32::from_le_bytes(
+            response_buffer[4..]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        let mut response = BytesMut::with_capacity(length);
+        response.put_bytes(0, length);
+        stream
+            .read_exact(&mut response)
+            .await
+            .map_err(|_| IggyError::Disconnected)?;
+        Ok((status, response.freeze()))
+    }
+}
@@ -1,5 +1,6 @@
 use bytes::{BufMut, Bytes, BytesMut};
 use error_set::ErrContext;
+use iggy::archives::restore_archive::RestoreArchive;
 use iggy::command::*;
 use iggy::consumer_groups::create_consumer_group::CreateConsumerGroup;
 use iggy::consumer_groups::delete_consumer_group::DeleteConsumerGroup;
@@ -11,6 +12,8 @@ use iggy::consumer_offsets::delete_consumer_offset::DeleteConsumerOffset;
 use iggy::consumer_offsets::get_consumer_offset::GetConsumerOffset;
 use iggy::consumer_offsets::store_consumer_offset::StoreConsumerOffset;
 use iggy::error::IggyError;
+use iggy::messages::init_producer::InitProducer;
+use iggy::messages::nack_message::NackMessage;
 use iggy::messages::poll_messages::PollMessages;
 use iggy::messages::send_messages::SendMessages;
 use iggy::partitions::create_partitions::CreatePartitions;
@@ -19,11 +22,19 @@ use iggy::personal_access_tokens::create_personal_access_token::CreatePersonalAc
 use iggy::personal_access_tokens::delete_personal_access_token::DeletePersonalAccessToken;
 use iggy::personal_access_tokens::get_personal_access_tokens::GetPersonalAccessTokens;
 use iggy::personal_access_tokens::login_with_personal_access_token::LoginWithPersonalAccessToken;
+use iggy::roles::assign_role::AssignRole;
+use iggy::roles::create_role::CreateRole;
+use iggy::roles::delete_role::DeleteRole;
+use iggy::roles::get_role::GetRole;
+use iggy::roles::get_roles::GetRoles;
+use iggy::roles::unassign_role::UnassignRole;
+use iggy::roles::update_role::UpdateRole;
 use iggy::streams::create_stream::CreateStream;
 use iggy::streams::delete_stream::DeleteStream;
 use iggy::streams::get_stream::GetStream;
 use iggy::streams::get_streams::GetStreams;
 use iggy::streams::purge_stream::PurgeStream;
+use iggy::streams::set_stream_quota::SetStreamQuota;
 use iggy::streams::update_stream::UpdateStream;
 use iggy::system::get_client::GetClient;
 use iggy::system::get_clients::GetClients;
@@ -31,19 +42,26 @@ use iggy::system::get_me::GetMe;
 use iggy::system::get_snapshot::GetSnapshot;
 use iggy::system::get_stats::GetStats;
 use iggy::system::ping::Ping;
+use iggy::system::set_protocol_version::SetProtocolVersion;
 use iggy::topics::create_topic::CreateTopic;
 use iggy::topics::delete_topic::DeleteTopic;
 use iggy::topics::get_topic::GetTopic;
 use iggy::topics::get_topics::GetTopics;
 use iggy::topics::purge_topic::PurgeTopic;
+use iggy::topics::set_topic_retention::SetTopicRetention;
 use iggy::topics::update_topic::UpdateTopic;
+use iggy::transactions::abort_transaction::AbortTransaction;
+use iggy::transactions::begin_transaction::BeginTransaction;
+use iggy::transactions::commit_transaction::CommitTransaction;
 use iggy::users::change_password::ChangePassword;
 use iggy::users::create_user::CreateUser;
 use iggy::users::delete_user::DeleteUser;
 use iggy::users::get_user::GetUser;
 use iggy::users::get_users::GetUsers;
 use iggy::users::login_user::LoginUser;
+use iggy::users::login_with_token::LoginWithToken;
 use iggy::users::logout_user::LogoutUser;
+use iggy::users::set_user_quota::SetUserQuota;
 use iggy::users::update_permissions::UpdatePermissions;
 use iggy::users::update_user::UpdateUser;
 use iggy::validatable::Validatable;
@@ -57,6 +75,7 @@ use tracing::error;
 #[derive(Debug, PartialEq, EnumString)]
 pub enum ServerCommand {
     Ping(Ping),
+    SetProtocolVersion(SetProtocolVersion),
     GetStats(GetStats),
     GetMe(GetMe),
     GetClient(GetClient),
@@ -67,9 +86,18 @@ pub enum ServerCommand {
     DeleteUser(DeleteUser),
     UpdateUser(UpdateUser),
     UpdatePermissions(UpdatePermissions),
+    SetUserQuota(SetUserQuota),
     ChangePassword(ChangePassword),
     LoginUser(LoginUser),
+    LoginWithToken(LoginWithToken),
     LogoutUser(LogoutUser),
+    GetRole(GetRole),
+    GetRoles(GetRoles),
+    CreateRole(CreateRole),
+    DeleteRole(DeleteRole),
+    UpdateRole(UpdateRole),
+    AssignRole(AssignRole),
+    UnassignRole(UnassignRole),
     GetPersonalAccessTokens(GetPersonalAccessTokens),
     CreatePersonalAccessToken(CreatePersonalAccessToken),
     DeletePersonalAccessToken(DeletePersonalAccessToken),
@@ -77,6 +105,8 @@ pub enum ServerCommand {
     SendMessages(SendMessages),
     PollMessages(PollMessages),
     FlushUnsavedBuffer(FlushUnsavedBuffer),
+    NackMessage(NackMessage),
+    InitProducer(InitProducer),
     GetConsumerOffset(GetConsumerOffset),
     StoreConsumerOffset(StoreConsumerOffset),
     DeleteConsumerOffset(DeleteConsumerOffset),
@@ -86,12 +116,14 @@ pub enum ServerCommand {
     DeleteStream(DeleteStream),
     UpdateStream(UpdateStream),
     PurgeStream(PurgeStream),
+    SetStreamQuota(SetStreamQuota),
     GetTopic(GetTopic),
     GetTopics(GetTopics),
     CreateTopic(CreateTopic),
     DeleteTopic(DeleteTopic),
     UpdateTopic(UpdateTopic),
     PurgeTopic(PurgeTopic),
+    SetTopicRetention(SetTopicRetention),
     CreatePartitions(CreatePartitions),
     DeletePartitions(DeletePartitions),
     GetConsumerGroup(GetConsumerGroup),
@@ -100,6 +132,10 @@ pub enum ServerCommand {
     DeleteConsumerGroup(DeleteConsumerGroup),
     JoinConsumerGroup(JoinConsumerGroup),
     LeaveConsumerGroup(LeaveConsumerGroup),
+    BeginTransaction(BeginTransaction),
+    CommitTransaction(CommitTransaction),
+    AbortTransaction(AbortTransaction),
+    RestoreArchive(RestoreArchive),
     GetSnapshotFile(GetSnapshot),
 }
 
@@ -107,6 +143,7 @@ impl BytesSerializable for ServerCommand {
     fn to_bytes(&self) -> Bytes {
         match self {
             ServerCommand::Ping(payload) => as_bytes(payload),
+            ServerCommand::SetProtocolVersion(payload) => as_bytes(payload),
             ServerCommand::GetStats(payload) => as_bytes(payload),
             ServerCommand::GetMe(payload) => as_bytes(payload),
             ServerCommand::GetClient(payload) => as_bytes(payload),
@@ -117,9 +154,18 @@ impl BytesSerializable for ServerCommand {
             ServerCommand::DeleteUser(payload) => as_bytes(payload),
             ServerCommand::UpdateUser(payload) => as_bytes(payload),
             ServerCommand::UpdatePermissions(payload) => as_bytes(payload),
+            ServerCommand::SetUserQuota(payload) => as_bytes(payload),
             ServerCommand::ChangePassword(payload) => as_bytes(payload),
             ServerCommand::LoginUser(payload) => as_bytes(payload),
+            ServerCommand::LoginWithToken(payload) => as_bytes(payload),
             ServerCommand::LogoutUser(payload) => as_bytes(payload),
+            ServerCommand::GetRole(payload) => as_bytes(payload),
+            ServerCommand::GetRoles(payload) => as_bytes(payload),
+            ServerCommand::CreateRole(payload) => as_bytes(payload),
+            ServerCommand::DeleteRole(payload) => as_bytes(payload),
+            ServerCommand::UpdateRole(payload) => as_bytes(payload),
+            ServerCommand::AssignRole(payload) => as_bytes(payload),
+            ServerCommand::UnassignRole(payload) => as_bytes(payload),
             ServerCommand::GetPersonalAccessTokens(payload) => as_bytes(payload),
             ServerCommand::CreatePersonalAccessToken(payload) => as_bytes(payload),
             ServerCommand::DeletePersonalAccessToken(payload) => as_bytes(payload),
@@ -135,12 +181,14 @@ impl BytesSerializable for ServerCommand {
             ServerCommand::DeleteStream(payload) => as_bytes(payload),
             ServerCommand::UpdateStream(payload) => as_bytes(payload),
             ServerCommand::PurgeStream(payload) => as_bytes(payload),
+            ServerCommand::SetStreamQuota(payload) => as_bytes(payload),
             ServerCommand::GetTopic(payload) => as_bytes(payload),
             ServerCommand::GetTopics(payload) => as_bytes(payload),
             ServerCommand::CreateTopic(payload) => as_bytes(payload),
             ServerCommand::DeleteTopic(payload) => as_bytes(payload),
             ServerCommand::UpdateTopic(payload) => as_bytes(payload),
             ServerCommand::PurgeTopic(payload) => as_bytes(payload),
+            ServerCommand::SetTopicRetention(payload) => as_bytes(payload),
             ServerCommand::CreatePartitions(payload) => as_bytes(payload),
             ServerCommand::DeletePartitions(payload) => as_bytes(payload),
             ServerCommand::GetConsumerGroup(payload) => as_bytes(payload),
@@ -149,7 +197,13 @@ impl BytesSerializable for ServerCommand {
             ServerCommand::DeleteConsumerGroup(payload) => as_bytes(payload),
             ServerCommand::JoinConsumerGroup(payload) => as_bytes(payload),
             ServerCommand::LeaveConsumerGroup(payload) => as_bytes(payload),
+            ServerCommand::BeginTransaction(payload) => as_bytes(payload),
+            ServerCommand::CommitTransaction(payload) => as_bytes(payload),
+            ServerCommand::AbortTransaction(payload) => as_bytes(payload),
+            ServerCommand::RestoreArchive(payload) => as_bytes(payload),
             ServerCommand::FlushUnsavedBuffer(payload) => as_bytes(payload),
+            ServerCommand::NackMessage(payload) => as_bytes(payload),
+            ServerCommand::InitProducer(payload) => as_bytes(payload),
             ServerCommand::GetSnapshotFile(payload) => as_bytes(payload),
         }
     }
@@ -164,6 +218,9 @@ impl BytesSerializable for ServerCommand {
         let payload = bytes.slice(4..);
         match code {
             PING_CODE => Ok(ServerCommand::Ping(Ping::from_bytes(payload)?)),
+            SET_PROTOCOL_VERSION_CODE => Ok(ServerCommand::SetProtocolVersion(
+                SetProtocolVersion::from_bytes(payload)?,
+            )),
             GET_STATS_CODE => Ok(ServerCommand::GetStats(GetStats::from_bytes(payload)?)),
             GET_ME_CODE => Ok(ServerCommand::GetMe(GetMe::from_bytes(payload)?)),
             GET_CLIENT_CODE => Ok(ServerCommand::GetClient(GetClient::from_bytes(payload)?)),
@@ -176,11 +233,26 @@ impl BytesSerializable for ServerCommand {
             UPDATE_PERMISSIONS_CODE => Ok(ServerCommand::UpdatePermissions(
                 UpdatePermissions::from_bytes(payload)?,
             )),
+            SET_USER_QUOTA_CODE => Ok(ServerCommand::SetUserQuota(SetUserQuota::from_bytes(
+                payload,
+            )?)),
             CHANGE_PASSWORD_CODE => Ok(ServerCommand::ChangePassword(ChangePassword::from_bytes(
                 payload,
             )?)),
             LOGIN_USER_CODE => Ok(ServerCommand::LoginUser(LoginUser::from_bytes(payload)?)),
+            LOGIN_WITH_TOKEN_CODE => Ok(ServerCommand::LoginWithToken(LoginWithToken::from_bytes(
+                payload,
+            )?)),
             LOGOUT_USER_CODE => Ok(ServerCommand::LogoutUser(LogoutUser::from_bytes(payload)?)),
+            GET_ROLE_CODE => Ok(ServerCommand::GetRole(GetRole::from_bytes(payload)?)),
+            GET_ROLES_CODE => Ok(ServerCommand::GetRoles(GetRoles::from_bytes(payload)?)),
+            CREATE_ROLE_CODE => Ok(ServerCommand::CreateRole(CreateRole::from_bytes(payload)?)),
+            DELETE_ROLE_CODE => Ok(ServerCommand::DeleteRole(DeleteRole::from_bytes(payload)?)),
+            UPDATE_ROLE_CODE => Ok(ServerCommand::UpdateRole(UpdateRole::from_bytes(payload)?)),
+            ASSIGN_ROLE_CODE => Ok(ServerCommand::AssignRole(AssignRole::from_bytes(payload)?)),
+            UNASSIGN_ROLE_CODE => Ok(ServerCommand::UnassignRole(UnassignRole::from_bytes(
+                payload,
+            )?)),
             GET_PERSONAL_ACCESS_TOKENS_CODE => Ok(ServerCommand::GetPersonalAccessTokens(
                 GetPersonalAccessTokens::from_bytes(payload)?,
             )),
@@ -204,6 +276,12 @@ impl BytesSerializable for ServerCommand {
             FLUSH_UNSAVED_BUFFER_CODE => Ok(ServerCommand::FlushUnsavedBuffer(
                 FlushUnsavedBuffer::from_bytes(payload)?,
             )),
+            NACK_MESSAGE_CODE => Ok(ServerCommand::NackMessage(NackMessage::from_bytes(
+                payload,
+            )?)),
+            INIT_PRODUCER_CODE => Ok(ServerCommand::InitProducer(InitProducer::from_bytes(
+                payload,
+            )?)),
             STORE_CONSUMER_OFFSET_CODE => Ok(ServerCommand::StoreConsumerOffset(
                 StoreConsumerOffset::from_bytes(payload)?,
             )),
@@ -227,6 +305,9 @@ impl BytesSerializable for ServerCommand {
             PURGE_STREAM_CODE => Ok(ServerCommand::PurgeStream(PurgeStream::from_bytes(
                 payload,
             )?)),
+            SET_STREAM_QUOTA_CODE => Ok(ServerCommand::SetStreamQuota(SetStreamQuota::from_bytes(
+                payload,
+            )?)),
             GET_TOPIC_CODE => Ok(ServerCommand::GetTopic(GetTopic::from_bytes(payload)?)),
             GET_TOPICS_CODE => Ok(ServerCommand::GetTopics(GetTopics::from_bytes(payload)?)),
             CREATE_TOPIC_CODE => Ok(ServerCommand::CreateTopic(CreateTopic::from_bytes(
@@ -239,6 +320,9 @@ impl BytesSerializable for ServerCommand {
                 payload,
             )?)),
             PURGE_TOPIC_CODE => Ok(ServerCommand::PurgeTopic(PurgeTopic::from_bytes(payload)?)),
+            SET_TOPIC_RETENTION_CODE => Ok(ServerCommand::SetTopicRetention(
+                SetTopicRetention::from_bytes(payload)?,
+            )),
             CREATE_PARTITIONS_CODE => Ok(ServerCommand::CreatePartitions(
                 CreatePartitions::from_bytes(payload)?,
             )),
@@ -263,6 +347,18 @@ impl BytesSerializable for ServerCommand {
             LEAVE_CONSUMER_GROUP_CODE => Ok(ServerCommand::LeaveConsumerGroup(
                 LeaveConsumerGroup::from_bytes(payload)?,
             )),
+            BEGIN_TRANSACTION_CODE => Ok(ServerCommand::BeginTransaction(
+                BeginTransaction::from_bytes(payload)?,
+            )),
+            COMMIT_TRANSACTION_CODE => Ok(ServerCommand::CommitTransaction(
+                CommitTransaction::from_bytes(payload)?,
+            )),
+            ABORT_TRANSACTION_CODE => Ok(ServerCommand::AbortTransaction(
+                AbortTransaction::from_bytes(payload)?,
+            )),
+            RESTORE_ARCHIVE_CODE => Ok(ServerCommand::RestoreArchive(RestoreArchive::from_bytes(
+                payload,
+            )?)),
             GET_SNAPSHOT_FILE_CODE => Ok(ServerCommand::GetSnapshotFile(GetSnapshot::from_bytes(
                 payload,
             )?)),
@@ -282,10 +378,80 @@ fn as_bytes<T: Command>(command: &T) -> Bytes {
     bytes.freeze()
 }
 
+impl ServerCommand {
+    /// Returns `true` if the command modifies the replicated data, so it must be handled by the cluster leader.
+    pub fn requires_leader(&self) -> bool {
+        matches!(
+            self,
+            ServerCommand::CreateUser(_)
+                | ServerCommand::DeleteUser(_)
+                | ServerCommand::UpdateUser(_)
+                | ServerCommand::UpdatePermissions(_)
+                | ServerCommand::SetUserQuota(_)
+                | ServerCommand::ChangePassword(_)
+                | ServerCommand::CreateRole(_)
+                | ServerCommand::DeleteRole(_)
+                | ServerCommand::UpdateRole(_)
+                | ServerCommand::AssignRole(_)
+                | ServerCommand::UnassignRole(_)
+                | ServerCommand::CreatePersonalAccessToken(_)
+                | ServerCommand::DeletePersonalAccessToken(_)
+                | ServerCommand::SendMessages(_)
+                | ServerCommand::NackMessage(_)
+                | ServerCommand::InitProducer(_)
+                | ServerCommand::CreateStream(_)
+                | ServerCommand::DeleteStream(_)
+                | ServerCommand::UpdateStream(_)
+                | ServerCommand::PurgeStream(_)
+                | ServerCommand::SetStreamQuota(_)
+                | ServerCommand::CreateTopic(_)
+                | ServerCommand::DeleteTopic(_)
+                | ServerCommand::UpdateTopic(_)
+                | ServerCommand::PurgeTopic(_)
+                | ServerCommand::SetTopicRetention(_)
+                | ServerCommand::CreatePartitions(_)
+                | ServerCommand::DeletePartitions(_)
+                | ServerCommand::CreateConsumerGroup(_)
+                | ServerCommand::DeleteConsumerGroup(_)
+                | ServerCommand::RestoreArchive(_)
+        )
+    }
+
+    /// Returns `true` if the command only reads the data, so the pipelined connection can handle it concurrently
+    /// with the other commands, while the remaining ones are handled one at a time, in the order they were received.
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            ServerCommand::PollMessages(command) => !command.auto_commit,
+            _ => matches!(
+                self,
+                ServerCommand::Ping(_)
+                    | ServerCommand::GetStats(_)
+                    | ServerCommand::GetMe(_)
+                    | ServerCommand::GetClient(_)
+                    | ServerCommand::GetClients(_)
+                    | ServerCommand::GetUser(_)
+                    | ServerCommand::GetUsers(_)
+                    | ServerCommand::GetRole(_)
+                    | ServerCommand::GetRoles(_)
+                    | ServerCommand::GetPersonalAccessTokens(_)
+                    | ServerCommand::GetConsumerOffset(_)
+                    | ServerCommand::GetStream(_)
+                    | ServerCommand::GetStreams(_)
+                    | ServerCommand::GetTopic(_)
+                    | ServerCommand::GetTopics(_)
+                    | ServerCommand::GetConsumerGroup(_)
+                    | ServerCommand::GetConsumerGroups(_)
+                    | ServerCommand::GetSnapshotFile(_)
+            ),
+        }
+    }
+}
+
 impl Validatable<IggyError> for ServerCommand {
     fn validate(&self) -> Result<(), IggyError> {
         match self {
             ServerCommand::Ping(command) => command.validate(),
+            ServerCommand::SetProtocolVersion(command) => command.validate(),
             ServerCommand::GetStats(command) => command.validate(),
             ServerCommand::GetMe(command) => command.validate(),
             ServerCommand::GetClient(command) => command.validate(),
@@ -296,9 +462,18 @@ impl Validatable<IggyError> for ServerCommand {
             ServerCommand::DeleteUser(command) => command.validate(),
             ServerCommand::UpdateUser(command) => command.validate(),
             ServerCommand::UpdatePermissions(command) => command.validate(),
+            ServerCommand::SetUserQuota(command) => command.validate(),
             ServerCommand::ChangePassword(command) => command.validate(),
             ServerCommand::LoginUser(command) => command.validate(),
+            ServerCommand::LoginWithToken(command) => command.validate(),
             ServerCommand::LogoutUser(command) => command.validate(),
+            ServerCommand::GetRole(command) => command.validate(),
+            ServerCommand::GetRoles(command) => command.validate(),
+            ServerCommand::CreateRole(command) => command.validate(),
+            ServerCommand::DeleteRole(command) => command.validate(),
+            ServerCommand::UpdateRole(command) => command.validate(),
+            ServerCommand::AssignRole(command) => command.validate(),
+            ServerCommand::UnassignRole(command) => command.validate(),
             ServerCommand::GetPersonalAccessTokens(command) => command.validate(),
             ServerCommand::CreatePersonalAccessToken(command) => command.validate(),
             ServerCommand::DeletePersonalAccessToken(command) => command.validate(),
@@ -314,12 +489,14 @@ impl Validatable<IggyError> for ServerCommand {
             ServerCommand::DeleteStream(command) => command.validate(),
             ServerCommand::UpdateStream(command) => command.validate(),
             ServerCommand::PurgeStream(command) => command.validate(),
+            ServerCommand::SetStreamQuota(command) => command.validate(),
             ServerCommand::GetTopic(command) => command.validate(),
             ServerCommand::GetTopics(command) => command.validate(),
             ServerCommand::CreateTopic(command) => command.validate(),
             ServerCommand::DeleteTopic(command) => command.validate(),
             ServerCommand::UpdateTopic(command) => command.validate(),
             ServerCommand::PurgeTopic(command) => command.validate(),
+            ServerCommand::SetTopicRetention(command) => command.validate(),
             ServerCommand::CreatePartitions(command) => command.validate(),
             ServerCommand::DeletePartitions(command) => command.validate(),
             ServerCommand::GetConsumerGroup(command) => command.validate(),
@@ -328,7 +505,13 @@ impl Validatable<IggyError> for ServerCommand {
             ServerCommand::DeleteConsumerGroup(command) => command.validate(),
             ServerCommand::JoinConsumerGroup(command) => command.validate(),
             ServerCommand::LeaveConsumerGroup(command) => command.validate(),
+            ServerCommand::BeginTransaction(command) => command.validate(),
+            ServerCommand::CommitTransaction(command) => command.validate(),
+            ServerCommand::AbortTransaction(command) => command.validate(),
+            ServerCommand::RestoreArchive(command) => command.validate(),
             ServerCommand::FlushUnsavedBuffer(command) => command.validate(),
+            ServerCommand::NackMessage(command) => command.validate(),
+            ServerCommand::InitProducer(command) => command.validate(),
             ServerCommand::GetSnapshotFile(command) => command.validate(),
         }
     }
@@ -338,6 +521,9 @@ impl Display for ServerCommand {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             ServerCommand::Ping(_) => write!(formatter, "{PING}"),
+            ServerCommand::SetProtocolVersion(payload) => {
+                write!(formatter, "{SET_PROTOCOL_VERSION}|{payload}")
+            }
             ServerCommand::GetStats(_) => write!(formatter, "{GET_STATS}"),
             ServerCommand::GetMe(_) => write!(formatter, "{GET_ME}"),
             ServerCommand::GetClient(payload) => write!(formatter, "{GET_CLIENT}|{payload}"),
@@ -350,11 +536,24 @@ impl Display for ServerCommand {
             ServerCommand::UpdatePermissions(payload) => {
                 write!(formatter, "{UPDATE_PERMISSIONS}|{payload}")
             }
+            ServerCommand::SetUserQuota(payload) => {
+                write!(formatter, "{SET_USER_QUOTA}|{payload}")
+            }
             ServerCommand::ChangePassword(payload) => {
                 write!(formatter, "{CHANGE_PASSWORD}|{payload}")
             }
             ServerCommand::LoginUser(payload) => write!(formatter, "{LOGIN_USER}|{payload}"),
+            ServerCommand::LoginWithToken(payload) => {
+                write!(formatter, "{LOGIN_WITH_TOKEN}|{payload}")
+            }
             ServerCommand::LogoutUser(_) => write!(formatter, "{LOGOUT_USER}"),
+            ServerCommand::GetRole(payload) => write!(formatter, "{GET_ROLE}|{payload}"),
+            ServerCommand::GetRoles(_) => write!(formatter, "{GET_ROLES}"),
+            ServerCommand::CreateRole(payload) => write!(formatter, "{CREATE_ROLE}|{payload}"),
+            ServerCommand::DeleteRole(payload) => write!(formatter, "{DELETE_ROLE}|{payload}"),
+            ServerCommand::UpdateRole(payload) => write!(formatter, "{UPDATE_ROLE}|{payload}"),
+            ServerCommand::AssignRole(payload) => write!(formatter, "{ASSIGN_ROLE}|{payload}"),
+            ServerCommand::UnassignRole(payload) => write!(formatter, "{UNASSIGN_ROLE}|{payload}"),
             ServerCommand::GetPersonalAccessTokens(_) => {
                 write!(formatter, "{GET_PERSONAL_ACCESS_TOKENS}")
             }
@@ -373,12 +572,18 @@ impl Display for ServerCommand {
             ServerCommand::DeleteStream(payload) => write!(formatter, "{DELETE_STREAM}|{payload}"),
             ServerCommand::UpdateStream(payload) => write!(formatter, "{UPDATE_STREAM}|{payload}"),
             ServerCommand::PurgeStream(payload) => write!(formatter, "{PURGE_STREAM}|{payload}"),
+            ServerCommand::SetStreamQuota(payload) => {
+                write!(formatter, "{SET_STREAM_QUOTA}|{payload}")
+            }
             ServerCommand::GetTopic(payload) => write!(formatter, "{GET_TOPIC}|{payload}"),
             ServerCommand::GetTopics(payload) => write!(formatter, "{GET_TOPICS}|{payload}"),
             ServerCommand::CreateTopic(payload) => write!(formatter, "{CREATE_TOPIC}|{payload}"),
             ServerCommand::DeleteTopic(payload) => write!(formatter, "{DELETE_TOPIC}|{payload}"),
             ServerCommand::UpdateTopic(payload) => write!(formatter, "{UPDATE_TOPIC}|{payload}"),
             ServerCommand::PurgeTopic(payload) => write!(formatter, "{PURGE_TOPIC}|{payload}"),
+            ServerCommand::SetTopicRetention(payload) => {
+                write!(formatter, "{SET_TOPIC_RETENTION}|{payload}")
+            }
             ServerCommand::CreatePartitions(payload) => {
                 write!(formatter, "{CREATE_PARTITIONS}|{payload}")
             }
@@ -414,9 +619,27 @@ impl Display for ServerCommand {
             ServerCommand::LeaveConsumerGroup(payload) => {
                 write!(formatter, "{LEAVE_CONSUMER_GROUP}|{payload}")
             }
+            ServerCommand::BeginTransaction(payload) => {
+                write!(formatter, "{BEGIN_TRANSACTION}|{payload}")
+            }
+            ServerCommand::CommitTransaction(payload) => {
+                write!(formatter, "{COMMIT_TRANSACTION}|{payload}")
+            }
+            ServerCommand::AbortTransaction(payload) => {
+                write!(formatter, "{ABORT_TRANSACTION}|{payload}")
+            }
+            ServerCommand::RestoreArchive(payload) => {
+                write!(formatter, "{RESTORE_ARCHIVE}|{payload}")
+            }
             ServerCommand::FlushUnsavedBuffer(payload) => {
                 write!(formatter, "{FLUSH_UNSAVED_BUFFER}|{payload}")
             }
+            ServerCommand::NackMessage(payload) => {
+                write!(formatter, "{NACK_MESSAGE}|{payload}")
+            }
+            ServerCommand::InitProducer(payload) => {
+                write!(formatter, "{INIT_PRODUCER}|{payload}")
+            }
             ServerCommand::GetSnapshotFile(payload) => {
                 write!(formatter, "{GET_SNAPSHOT_FILE}|{payload}")
             }
@@ -435,6 +658,11 @@ mod tests {
             PING_CODE,
             &Ping::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::SetProtocolVersion(SetProtocolVersion::default()),
+            SET_PROTOCOL_VERSION_CODE,
+            &SetProtocolVersion::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::GetStats(GetStats::default()),
             GET_STATS_CODE,
@@ -485,6 +713,11 @@ mod tests {
             UPDATE_PERMISSIONS_CODE,
             &UpdatePermissions::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::SetUserQuota(SetUserQuota::default()),
+            SET_USER_QUOTA_CODE,
+            &SetUserQuota::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::ChangePassword(ChangePassword::default()),
             CHANGE_PASSWORD_CODE,
@@ -495,6 +728,46 @@ mod tests {
             LOGIN_USER_CODE,
             &LoginUser::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::GetRole(GetRole::default()),
+            GET_ROLE_CODE,
+            &GetRole::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::GetRoles(GetRoles::default()),
+            GET_ROLES_CODE,
+            &GetRoles::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::CreateRole(CreateRole::default()),
+            CREATE_ROLE_CODE,
+            &CreateRole::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::DeleteRole(DeleteRole::default()),
+            DELETE_ROLE_CODE,
+            &DeleteRole::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::UpdateRole(UpdateRole::default()),
+            UPDATE_ROLE_CODE,
+            &UpdateRole::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::AssignRole(AssignRole::default()),
+            ASSIGN_ROLE_CODE,
+            &AssignRole::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::UnassignRole(UnassignRole::default()),
+            UNASSIGN_ROLE_CODE,
+            &UnassignRole::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::LoginWithToken(LoginWithToken::default()),
+            LOGIN_WITH_TOKEN_CODE,
+            &LoginWithToken::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::LogoutUser(LogoutUser::default()),
             LOGOUT_USER_CODE,
@@ -570,6 +843,11 @@ mod tests {
             PURGE_STREAM_CODE,
             &PurgeStream::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::SetStreamQuota(SetStreamQuota::default()),
+            SET_STREAM_QUOTA_CODE,
+            &SetStreamQuota::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::GetTopic(GetTopic::default()),
             GET_TOPIC_CODE,
@@ -600,6 +878,11 @@ mod tests {
             PURGE_TOPIC_CODE,
             &PurgeTopic::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::SetTopicRetention(SetTopicRetention::default()),
+            SET_TOPIC_RETENTION_CODE,
+            &SetTopicRetention::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::CreatePartitions(CreatePartitions::default()),
             CREATE_PARTITIONS_CODE,
@@ -640,11 +923,41 @@ mod tests {
             LEAVE_CONSUMER_GROUP_CODE,
             &LeaveConsumerGroup::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::BeginTransaction(BeginTransaction::default()),
+            BEGIN_TRANSACTION_CODE,
+            &BeginTransaction::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::CommitTransaction(CommitTransaction::default()),
+            COMMIT_TRANSACTION_CODE,
+            &CommitTransaction::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::AbortTransaction(AbortTransaction::default()),
+            ABORT_TRANSACTION_CODE,
+            &AbortTransaction::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::RestoreArchive(RestoreArchive::default()),
+            RESTORE_ARCHIVE_CODE,
+            &RestoreArchive::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::FlushUnsavedBuffer(FlushUnsavedBuffer::default()),
             FLUSH_UNSAVED_BUFFER_CODE,
             &FlushUnsavedBuffer::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::NackMessage(NackMessage::default()),
+            NACK_MESSAGE_CODE,
+            &NackMessage::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::InitProducer(InitProducer::default()),
+            INIT_PRODUCER_CODE,
+            &InitProducer::default(),
+        );
     }
 
     fn assert_serialized_as_bytes_and_deserialized_from_bytes(
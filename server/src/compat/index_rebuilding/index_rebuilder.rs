@@ -5,6 +5,8 @@ use crate::{
 use std::io::SeekFrom;
 use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader, BufWriter};
 
+const INDEX_SIZE: u64 = 4 + 4 + 8;
+
 // Same struct as RetainedMessageBatch, but without payload
 pub struct BatchHeader {
     base_offset: u64,
@@ -68,6 +70,7 @@ impl IndexRebuilder {
         let mut writer = BufWriter::new(file::overwrite(&self.index_path).await?);
         let mut position = 0;
         let mut next_position;
+        let mut indexes_count = 0;
 
         loop {
             match Self::read_batch_header(&mut reader).await {
@@ -78,6 +81,7 @@ impl IndexRebuilder {
                     // Write index entry using current position
                     Self::write_index_entry(&mut writer, &header, position, self.start_offset)
                         .await?;
+                    indexes_count += 1;
 
                     // Skip batch messages
                     reader.seek(SeekFrom::Current(header.length as i64)).await?;
@@ -91,6 +95,8 @@ impl IndexRebuilder {
         }
 
         writer.flush().await?;
+        // The index might have been longer than the rebuilt one, so the stale entries are removed.
+        writer.get_ref().set_len(indexes_count * INDEX_SIZE).await?;
         Ok(())
     }
 }
@@ -0,0 +1,125 @@
+use iggy::error::IggyError;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ClientAuthConfig {
+    pub ca_file: String,
+    pub required: bool,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub users: Vec<ClientCertificateUserConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientCertificateUserConfig {
+    pub kind: ClientCertificateIdentityKind,
+    pub value: String,
+    pub username: String,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ClientCertificateIdentityKind {
+    CommonName,
+    DnsName,
+    Uri,
+    Email,
+}
+
+impl FromStr for ClientCertificateIdentityKind {
+    type Err = IggyError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "cn" => Ok(ClientCertificateIdentityKind::CommonName),
+            "dns" => Ok(ClientCertificateIdentityKind::DnsName),
+            "uri" => Ok(ClientCertificateIdentityKind::Uri),
+            "email" => Ok(ClientCertificateIdentityKind::Email),
+            _ => Err(IggyError::InvalidClientCertificateUser(value.to_owned())),
+        }
+    }
+}
+
+impl Display for ClientCertificateIdentityKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientCertificateIdentityKind::CommonName => write!(f, "cn"),
+            ClientCertificateIdentityKind::DnsName => write!(f, "dns"),
+            ClientCertificateIdentityKind::Uri => write!(f, "uri"),
+            ClientCertificateIdentityKind::Email => write!(f, "email"),
+        }
+    }
+}
+
+impl FromStr for ClientCertificateUserConfig {
+    type Err = IggyError;
+
+    /// Parses the mapping in the format `KIND:VALUE=USERNAME`, e.g. `cn:orders-service=orders`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let Some((identity, username)) = value.trim().rsplit_once('=') else {
+            return Err(IggyError::InvalidClientCertificateUser(value.to_owned()));
+        };
+        let Some((kind, identity)) = identity.split_once(':') else {
+            return Err(IggyError::InvalidClientCertificateUser(value.to_owned()));
+        };
+
+        let kind = kind
+            .parse::<ClientCertificateIdentityKind>()
+            .map_err(|_| IggyError::InvalidClientCertificateUser(value.to_owned()))?;
+        if identity.is_empty() || username.is_empty() {
+            return Err(IggyError::InvalidClientCertificateUser(value.to_owned()));
+        }
+
+        Ok(ClientCertificateUserConfig {
+            kind,
+            value: identity.to_owned(),
+            username: username.to_owned(),
+        })
+    }
+}
+
+impl Display for ClientCertificateUserConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}={}", self.kind, self.value, self.username)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_should_be_parsed_from_identity_and_username() {
+        let user = ClientCertificateUserConfig::from_str("cn:orders-service=orders").unwrap();
+        assert_eq!(user.kind, ClientCertificateIdentityKind::CommonName);
+        assert_eq!(user.value, "orders-service");
+        assert_eq!(user.username, "orders");
+        assert_eq!(user.to_string(), "cn:orders-service=orders");
+    }
+
+    #[test]
+    fn user_should_be_parsed_from_uri_containing_separators() {
+        let user =
+            ClientCertificateUserConfig::from_str("uri:spiffe://iggy/orders?env=prod=orders")
+                .unwrap();
+        assert_eq!(user.kind, ClientCertificateIdentityKind::Uri);
+        assert_eq!(user.value, "spiffe://iggy/orders?env=prod");
+        assert_eq!(user.username, "orders");
+    }
+
+    #[test]
+    fn user_should_not_be_parsed_given_invalid_format() {
+        for value in [
+            "orders",
+            "cn:orders-service",
+            "ou:orders-service=orders",
+            "cn:=orders",
+            "cn:orders-service=",
+        ] {
+            assert!(ClientCertificateUserConfig::from_str(value).is_err());
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use iggy::error::IggyError;
+use iggy::utils::duration::IggyDuration;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ClusterConfig {
+    pub enabled: bool,
+    pub secret: String,
+    pub node: ClusterNodeConfig,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub peers: Vec<ClusterPeerConfig>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub heartbeat_interval: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub election_timeout: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub replication_timeout: IggyDuration,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ClusterNodeConfig {
+    pub id: u32,
+    pub address: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterPeerConfig {
+    pub id: u32,
+    pub address: String,
+}
+
+impl FromStr for ClusterPeerConfig {
+    type Err = IggyError;
+
+    /// Parses the peer in the format `ID@ADDRESS`, e.g. `2@127.0.0.1:8071`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let Some((id, address)) = value.trim().split_once('@') else {
+            return Err(IggyError::InvalidClusterNode(value.to_owned()));
+        };
+
+        let id = id
+            .parse::<u32>()
+            .map_err(|_| IggyError::InvalidClusterNode(value.to_owned()))?;
+        if id == 0 || address.is_empty() {
+            return Err(IggyError::InvalidClusterNode(value.to_owned()));
+        }
+
+        Ok(ClusterPeerConfig {
+            id,
+            address: address.to_owned(),
+        })
+    }
+}
+
+impl Display for ClusterPeerConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.id, self.address)
+    }
+}
+
+impl ClusterConfig {
+    /// Returns the number of nodes in the cluster, including the current one.
+    pub fn get_nodes_count(&self) -> u32 {
+        self.peers.len() as u32 + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peer_should_be_parsed_from_id_and_address() {
+        let peer = ClusterPeerConfig::from_str("2@127.0.0.1:8071").unwrap();
+        assert_eq!(peer.id, 2);
+        assert_eq!(peer.address, "127.0.0.1:8071");
+        assert_eq!(peer.to_string(), "2@127.0.0.1:8071");
+    }
+
+    #[test]
+    fn peer_should_not_be_parsed_given_invalid_format() {
+        assert!(ClusterPeerConfig::from_str("127.0.0.1:8071").is_err());
+        assert!(ClusterPeerConfig::from_str("0@127.0.0.1:8071").is_err());
+        assert!(ClusterPeerConfig::from_str("node@127.0.0.1:8071").is_err());
+        assert!(ClusterPeerConfig::from_str("2@").is_err());
+    }
+}
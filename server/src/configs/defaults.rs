@@ -1,20 +1,25 @@
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::duration::IggyDuration;
 
+use crate::configs::client_auth::ClientAuthConfig;
+use crate::configs::cluster::{ClusterConfig, ClusterNodeConfig};
 use crate::configs::http::{
     HttpConfig, HttpCorsConfig, HttpJwtConfig, HttpMetricsConfig, HttpTlsConfig,
 };
+use crate::configs::oidc::OidcConfig;
 use crate::configs::quic::{QuicCertificateConfig, QuicConfig};
 use crate::configs::server::{
-    ArchiverConfig, DataMaintenanceConfig, HeartbeatConfig, MessageSaverConfig,
-    MessagesMaintenanceConfig, PersonalAccessTokenCleanerConfig, PersonalAccessTokenConfig,
-    ServerConfig, StateMaintenanceConfig, TelemetryConfig, TelemetryLogsConfig,
-    TelemetryTracesConfig,
+    ArchiverConfig, CompactionMaintenanceConfig, DataMaintenanceConfig, DiskArchiverConfig,
+    HeartbeatConfig, MessageSaverConfig, MessagesMaintenanceConfig,
+    PersonalAccessTokenCleanerConfig, PersonalAccessTokenConfig, ServerConfig,
+    SnapshotMaintenanceConfig, StateMaintenanceConfig, TelemetryConfig, TelemetryLogsConfig,
+    TelemetryTracesConfig, TransactionConfig,
 };
 use crate::configs::system::{
-    BackupConfig, CacheConfig, CompatibilityConfig, CompressionConfig, EncryptionConfig,
-    LoggingConfig, MessageDeduplicationConfig, PartitionConfig, RecoveryConfig, RuntimeConfig,
-    SegmentConfig, StateConfig, StreamConfig, SystemConfig, TopicConfig,
+    BackupConfig, CacheConfig, CompatibilityConfig, CompressionConfig, ConsumedRetentionConfig,
+    ConsumerGroupConfig, DeadLetterConfig, EncryptionConfig, LoggingConfig,
+    MessageDeduplicationConfig, PartitionConfig, RecoveryConfig, RuntimeConfig, SegmentConfig,
+    StateConfig, StreamConfig, SystemConfig, TieredStorageConfig, TopicConfig,
 };
 use crate::configs::tcp::{TcpConfig, TcpTlsConfig};
 use std::sync::Arc;
@@ -32,13 +37,31 @@ impl Default for ServerConfig {
         ServerConfig {
             data_maintenance: DataMaintenanceConfig::default(),
             heartbeat: HeartbeatConfig::default(),
+            transaction: TransactionConfig::default(),
             message_saver: MessageSaverConfig::default(),
             personal_access_token: PersonalAccessTokenConfig::default(),
             system: Arc::new(SystemConfig::default()),
             quic: QuicConfig::default(),
             tcp: TcpConfig::default(),
             http: HttpConfig::default(),
+            client_auth: ClientAuthConfig::default(),
+            oidc: OidcConfig::default(),
             telemetry: TelemetryConfig::default(),
+            cluster: ClusterConfig::default(),
+        }
+    }
+}
+
+impl Default for DiskArchiverConfig {
+    fn default() -> DiskArchiverConfig {
+        DiskArchiverConfig {
+            path: SERVER_CONFIG
+                .data_maintenance
+                .archiver
+                .disk
+                .path
+                .parse()
+                .unwrap(),
         }
     }
 }
@@ -53,7 +76,7 @@ impl Default for ArchiverConfig {
                 .kind
                 .parse()
                 .unwrap(),
-            disk: None,
+            disk: Some(DiskArchiverConfig::default()),
             s3: None,
         }
     }
@@ -89,6 +112,41 @@ impl Default for StateMaintenanceConfig {
     }
 }
 
+impl Default for CompactionMaintenanceConfig {
+    fn default() -> CompactionMaintenanceConfig {
+        CompactionMaintenanceConfig {
+            enabled: SERVER_CONFIG.data_maintenance.compaction.enabled,
+            interval: SERVER_CONFIG
+                .data_maintenance
+                .compaction
+                .interval
+                .parse()
+                .unwrap(),
+            tombstones_retention: SERVER_CONFIG
+                .data_maintenance
+                .compaction
+                .tombstones_retention
+                .parse()
+                .unwrap(),
+        }
+    }
+}
+
+impl Default for SnapshotMaintenanceConfig {
+    fn default() -> SnapshotMaintenanceConfig {
+        SnapshotMaintenanceConfig {
+            enabled: SERVER_CONFIG.data_maintenance.snapshot.enabled,
+            interval: SERVER_CONFIG
+                .data_maintenance
+                .snapshot
+                .interval
+                .parse()
+                .unwrap(),
+            min_entries: SERVER_CONFIG.data_maintenance.snapshot.min_entries as u64,
+        }
+    }
+}
+
 impl Default for QuicConfig {
     fn default() -> QuicConfig {
         QuicConfig {
@@ -116,6 +174,7 @@ impl Default for QuicCertificateConfig {
             self_signed: SERVER_CONFIG.quic.certificate.self_signed,
             cert_file: SERVER_CONFIG.quic.certificate.cert_file.parse().unwrap(),
             key_file: SERVER_CONFIG.quic.certificate.key_file.parse().unwrap(),
+            client_auth: SERVER_CONFIG.quic.certificate.client_auth,
         }
     }
 }
@@ -126,6 +185,7 @@ impl Default for TcpConfig {
             enabled: SERVER_CONFIG.tcp.enabled,
             address: SERVER_CONFIG.tcp.address.parse().unwrap(),
             ipv6: SERVER_CONFIG.tcp.ipv_6,
+            zero_copy: SERVER_CONFIG.tcp.zero_copy,
             tls: TcpTlsConfig::default(),
             socket: TcpSocketConfig::default(),
         }
@@ -138,6 +198,7 @@ impl Default for TcpTlsConfig {
             enabled: SERVER_CONFIG.tcp.tls.enabled,
             certificate: SERVER_CONFIG.tcp.tls.certificate.parse().unwrap(),
             password: SERVER_CONFIG.tcp.tls.password.parse().unwrap(),
+            client_auth: SERVER_CONFIG.tcp.tls.client_auth,
         }
     }
 }
@@ -252,6 +313,7 @@ impl Default for HttpTlsConfig {
             enabled: SERVER_CONFIG.http.tls.enabled,
             cert_file: SERVER_CONFIG.http.tls.cert_file.parse().unwrap(),
             key_file: SERVER_CONFIG.http.tls.key_file.parse().unwrap(),
+            client_auth: SERVER_CONFIG.http.tls.client_auth,
         }
     }
 }
@@ -289,6 +351,15 @@ impl Default for PersonalAccessTokenCleanerConfig {
     }
 }
 
+impl Default for TransactionConfig {
+    fn default() -> TransactionConfig {
+        TransactionConfig {
+            timeout: SERVER_CONFIG.transaction.timeout.parse().unwrap(),
+            cleaner_interval: SERVER_CONFIG.transaction.cleaner_interval.parse().unwrap(),
+        }
+    }
+}
+
 impl Default for SystemConfig {
     fn default() -> SystemConfig {
         SystemConfig {
@@ -305,6 +376,9 @@ impl Default for SystemConfig {
             state: StateConfig::default(),
             compression: CompressionConfig::default(),
             message_deduplication: MessageDeduplicationConfig::default(),
+            consumer_group: ConsumerGroupConfig::default(),
+            dead_letter: DeadLetterConfig::default(),
+            tiered_storage: TieredStorageConfig::default(),
             recovery: RecoveryConfig::default(),
         }
     }
@@ -342,6 +416,57 @@ impl Default for HeartbeatConfig {
     }
 }
 
+impl Default for ClientAuthConfig {
+    fn default() -> ClientAuthConfig {
+        ClientAuthConfig {
+            ca_file: SERVER_CONFIG.client_auth.ca_file.parse().unwrap(),
+            required: SERVER_CONFIG.client_auth.required,
+            users: Vec::new(),
+        }
+    }
+}
+
+impl Default for OidcConfig {
+    fn default() -> OidcConfig {
+        OidcConfig {
+            enabled: SERVER_CONFIG.oidc.enabled,
+            issuer: SERVER_CONFIG.oidc.issuer.parse().unwrap(),
+            audience: SERVER_CONFIG.oidc.audience.parse().unwrap(),
+            jwks: SERVER_CONFIG.oidc.jwks.parse().unwrap(),
+            jwks_refresh_interval: SERVER_CONFIG.oidc.jwks_refresh_interval.parse().unwrap(),
+            jwks_timeout: SERVER_CONFIG.oidc.jwks_timeout.parse().unwrap(),
+            clock_skew: SERVER_CONFIG.oidc.clock_skew.parse().unwrap(),
+            subjects: Vec::new(),
+            groups_claim: SERVER_CONFIG.oidc.groups_claim.parse().unwrap(),
+            groups: Vec::new(),
+            group_roles: Vec::new(),
+        }
+    }
+}
+
+impl Default for ClusterConfig {
+    fn default() -> ClusterConfig {
+        ClusterConfig {
+            enabled: SERVER_CONFIG.cluster.enabled,
+            secret: SERVER_CONFIG.cluster.secret.parse().unwrap(),
+            node: ClusterNodeConfig::default(),
+            peers: Vec::new(),
+            heartbeat_interval: SERVER_CONFIG.cluster.heartbeat_interval.parse().unwrap(),
+            election_timeout: SERVER_CONFIG.cluster.election_timeout.parse().unwrap(),
+            replication_timeout: SERVER_CONFIG.cluster.replication_timeout.parse().unwrap(),
+        }
+    }
+}
+
+impl Default for ClusterNodeConfig {
+    fn default() -> ClusterNodeConfig {
+        ClusterNodeConfig {
+            id: SERVER_CONFIG.cluster.node.id as u32,
+            address: SERVER_CONFIG.cluster.node.address.parse().unwrap(),
+        }
+    }
+}
+
 impl Default for RuntimeConfig {
     fn default() -> RuntimeConfig {
         RuntimeConfig {
@@ -413,6 +538,21 @@ impl Default for TopicConfig {
             path: SERVER_CONFIG.system.topic.path.parse().unwrap(),
             max_size: SERVER_CONFIG.system.topic.max_size.parse().unwrap(),
             delete_oldest_segments: SERVER_CONFIG.system.topic.delete_oldest_segments,
+            consumed_retention: ConsumedRetentionConfig::default(),
+        }
+    }
+}
+
+impl Default for ConsumedRetentionConfig {
+    fn default() -> ConsumedRetentionConfig {
+        ConsumedRetentionConfig {
+            max_size: SERVER_CONFIG
+                .system
+                .topic
+                .consumed_retention
+                .max_size
+                .parse()
+                .unwrap(),
         }
     }
 }
@@ -425,6 +565,7 @@ impl Default for PartitionConfig {
                 as u32,
             enforce_fsync: SERVER_CONFIG.system.partition.enforce_fsync,
             validate_checksum: SERVER_CONFIG.system.partition.validate_checksum,
+            io_backend: SERVER_CONFIG.system.partition.io_backend.parse().unwrap(),
         }
     }
 }
@@ -472,6 +613,50 @@ impl Default for MessageDeduplicationConfig {
     }
 }
 
+impl Default for ConsumerGroupConfig {
+    fn default() -> ConsumerGroupConfig {
+        ConsumerGroupConfig {
+            revocation_timeout: SERVER_CONFIG
+                .system
+                .consumer_group
+                .revocation_timeout
+                .parse()
+                .unwrap(),
+        }
+    }
+}
+
+impl Default for DeadLetterConfig {
+    fn default() -> DeadLetterConfig {
+        DeadLetterConfig {
+            enabled: SERVER_CONFIG.system.dead_letter.enabled,
+            max_delivery_attempts: SERVER_CONFIG.system.dead_letter.max_delivery_attempts as u32,
+            stream: SERVER_CONFIG.system.dead_letter.stream.parse().unwrap(),
+            topic: SERVER_CONFIG.system.dead_letter.topic.parse().unwrap(),
+        }
+    }
+}
+
+impl Default for TieredStorageConfig {
+    fn default() -> TieredStorageConfig {
+        TieredStorageConfig {
+            enabled: SERVER_CONFIG.system.tiered_storage.enabled,
+            cache_path: SERVER_CONFIG
+                .system
+                .tiered_storage
+                .cache_path
+                .parse()
+                .unwrap(),
+            cache_size: SERVER_CONFIG
+                .system
+                .tiered_storage
+                .cache_size
+                .parse()
+                .unwrap(),
+        }
+    }
+}
+
 impl Default for RecoveryConfig {
     fn default() -> RecoveryConfig {
         RecoveryConfig {
@@ -1,10 +1,17 @@
+use crate::configs::client_auth::ClientAuthConfig;
+use crate::configs::cluster::{ClusterConfig, ClusterNodeConfig};
+use crate::configs::oidc::OidcConfig;
 use crate::configs::quic::{QuicCertificateConfig, QuicConfig};
 use crate::configs::server::{
-    ArchiverConfig, DataMaintenanceConfig, DiskArchiverConfig, HeartbeatConfig,
-    MessagesMaintenanceConfig, S3ArchiverConfig, StateMaintenanceConfig, TelemetryConfig,
-    TelemetryLogsConfig, TelemetryTracesConfig,
+    ArchiverConfig, CompactionMaintenanceConfig, DataMaintenanceConfig, DiskArchiverConfig,
+    HeartbeatConfig, MessagesMaintenanceConfig, S3ArchiverConfig, SnapshotMaintenanceConfig,
+    StateMaintenanceConfig, TelemetryConfig, TelemetryLogsConfig, TelemetryTracesConfig,
+    TransactionConfig,
+};
+use crate::configs::system::{
+    ConsumedRetentionConfig, ConsumerGroupConfig, DeadLetterConfig, MessageDeduplicationConfig,
+    TieredStorageConfig,
 };
-use crate::configs::system::MessageDeduplicationConfig;
 use crate::configs::{
     http::{HttpConfig, HttpCorsConfig, HttpJwtConfig, HttpMetricsConfig, HttpTlsConfig},
     resource_quota::MemoryResourceQuota,
@@ -61,8 +68,8 @@ impl Display for HttpTlsConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ enabled: {}, cert_file: {}, key_file: {} }}",
-            self.enabled, self.cert_file, self.key_file
+            "{{ enabled: {}, cert_file: {}, key_file: {}, client_auth: {} }}",
+            self.enabled, self.cert_file, self.key_file, self.client_auth
         )
     }
 }
@@ -90,8 +97,8 @@ impl Display for QuicCertificateConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ self_signed: {}, cert_file: {}, key_file: {} }}",
-            self.self_signed, self.cert_file, self.key_file
+            "{{ self_signed: {}, cert_file: {}, key_file: {}, client_auth: {} }}",
+            self.self_signed, self.cert_file, self.key_file, self.client_auth
         )
     }
 }
@@ -119,8 +126,8 @@ impl Display for DataMaintenanceConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ archiver: {}, messages: {}, state: {} }}",
-            self.archiver, self.messages, self.state
+            "{{ archiver: {}, messages: {}, state: {}, compaction: {}, snapshot: {} }}",
+            self.archiver, self.messages, self.state, self.compaction, self.snapshot
         )
     }
 }
@@ -182,16 +189,109 @@ impl Display for StateMaintenanceConfig {
     }
 }
 
+impl Display for CompactionMaintenanceConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, interval: {}, tombstones_retention: {} }}",
+            self.enabled, self.interval, self.tombstones_retention
+        )
+    }
+}
+
+impl Display for SnapshotMaintenanceConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, interval: {}, min_entries: {} }}",
+            self.enabled, self.interval, self.min_entries
+        )
+    }
+}
+
 impl Display for ServerConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ data_maintenance: {}, message_saver: {}, heartbeat: {}, system: {}, quic: {}, tcp: {}, http: {}, telemetry: {} }}",
-            self.data_maintenance, self.message_saver, self.heartbeat, self.system, self.quic, self.tcp, self.http, self.telemetry
+            "{{ data_maintenance: {}, message_saver: {}, heartbeat: {}, transaction: {}, system: {}, quic: {}, tcp: {}, http: {}, client_auth: {}, oidc: {}, telemetry: {}, cluster: {} }}",
+            self.data_maintenance, self.message_saver, self.heartbeat, self.transaction, self.system, self.quic, self.tcp, self.http, self.client_auth, self.oidc, self.telemetry, self.cluster
         )
     }
 }
 
+impl Display for OidcConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, issuer: {}, audience: {}, jwks: {}, jwks_refresh_interval: {}, jwks_timeout: {}, clock_skew: {}, subjects: [{}], groups_claim: {}, groups: [{}], group_roles: [{}] }}",
+            self.enabled,
+            self.issuer,
+            self.audience,
+            self.jwks,
+            self.jwks_refresh_interval,
+            self.jwks_timeout,
+            self.clock_skew,
+            self.subjects
+                .iter()
+                .map(|subject| subject.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.groups_claim,
+            self.groups
+                .iter()
+                .map(|group| group.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.group_roles
+                .iter()
+                .map(|group_role| group_role.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl Display for ClientAuthConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ ca_file: {}, required: {}, users: [{}] }}",
+            self.ca_file,
+            self.required,
+            self.users
+                .iter()
+                .map(|user| user.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl Display for ClusterConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, node: {}, peers: [{}], heartbeat_interval: {}, election_timeout: {}, replication_timeout: {} }}",
+            self.enabled,
+            self.node,
+            self.peers
+                .iter()
+                .map(|peer| peer.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.heartbeat_interval,
+            self.election_timeout,
+            self.replication_timeout
+        )
+    }
+}
+
+impl Display for ClusterNodeConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{ id: {}, address: {} }}", self.id, self.address)
+    }
+}
+
 impl Display for MessageSaverConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -218,6 +318,16 @@ impl Display for HeartbeatConfig {
     }
 }
 
+impl Display for TransactionConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ timeout: {}, cleaner_interval: {} }}",
+            self.timeout, self.cleaner_interval
+        )
+    }
+}
+
 impl Display for EncryptionConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{{ enabled: {} }}", self.enabled)
@@ -234,21 +344,28 @@ impl Display for TopicConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ path: {}, max_size: {}, delete_oldest_segments: {} }}",
-            self.path, self.max_size, self.delete_oldest_segments
+            "{{ path: {}, max_size: {}, delete_oldest_segments: {}, consumed_retention: {} }}",
+            self.path, self.max_size, self.delete_oldest_segments, self.consumed_retention
         )
     }
 }
 
+impl Display for ConsumedRetentionConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{ max_size: {} }}", self.max_size)
+    }
+}
+
 impl Display for PartitionConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
           f,
-          "{{ path: {}, messages_required_to_save: {}, enforce_fsync: {}, validate_checksum: {} }}",
+          "{{ path: {}, messages_required_to_save: {}, enforce_fsync: {}, validate_checksum: {}, io_backend: {} }}",
           self.path,
           self.messages_required_to_save,
           self.enforce_fsync,
-          self.validate_checksum
+          self.validate_checksum,
+          self.io_backend
       )
     }
 }
@@ -263,6 +380,32 @@ impl Display for MessageDeduplicationConfig {
     }
 }
 
+impl Display for ConsumerGroupConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{ revocation_timeout: {} }}", self.revocation_timeout)
+    }
+}
+
+impl Display for DeadLetterConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, max_delivery_attempts: {}, stream: {}, topic: {} }}",
+            self.enabled, self.max_delivery_attempts, self.stream, self.topic
+        )
+    }
+}
+
+impl Display for TieredStorageConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, cache_path: {}, cache_size: {} }}",
+            self.enabled, self.cache_path, self.cache_size
+        )
+    }
+}
+
 impl Display for SegmentConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -290,8 +433,8 @@ impl Display for TcpConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ enabled: {}, address: {}, ipv6: {}, tls: {}, socket: {} }}",
-            self.enabled, self.address, self.ipv6, self.tls, self.socket,
+            "{{ enabled: {}, address: {}, ipv6: {}, zero_copy: {}, tls: {}, socket: {} }}",
+            self.enabled, self.address, self.ipv6, self.zero_copy, self.tls, self.socket,
         )
     }
 }
@@ -300,8 +443,8 @@ impl Display for TcpTlsConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ enabled: {}, certificate: {} }}",
-            self.enabled, self.certificate
+            "{{ enabled: {}, certificate: {}, client_auth: {} }}",
+            self.enabled, self.certificate, self.client_auth
         )
     }
 }
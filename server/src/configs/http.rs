@@ -65,6 +65,7 @@ pub struct HttpTlsConfig {
     pub enabled: bool,
     pub cert_file: String,
     pub key_file: String,
+    pub client_auth: bool,
 }
 
 impl HttpJwtConfig {
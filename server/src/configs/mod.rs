@@ -1,7 +1,10 @@
 pub mod server;
 pub mod system;
 
+pub mod client_auth;
+pub mod cluster;
 pub mod http;
+pub mod oidc;
 pub mod quic;
 pub mod tcp;
 
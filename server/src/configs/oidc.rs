@@ -0,0 +1,88 @@
+use iggy::error::IggyError;
+use iggy::utils::duration::IggyDuration;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct OidcConfig {
+    pub enabled: bool,
+    pub issuer: String,
+    pub audience: String,
+    pub jwks: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub jwks_refresh_interval: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub jwks_timeout: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub clock_skew: IggyDuration,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub subjects: Vec<OidcMappingConfig>,
+    pub groups_claim: String,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub groups: Vec<OidcMappingConfig>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub group_roles: Vec<OidcMappingConfig>,
+}
+
+/// The explicit mapping of the token subject or group to the name of the existing user (or role).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OidcMappingConfig {
+    pub value: String,
+    pub name: String,
+}
+
+impl OidcConfig {
+    /// Returns whether the keys are fetched from the URL, rather than read from the local file.
+    pub fn is_jwks_url(&self) -> bool {
+        self.jwks.starts_with("http://") || self.jwks.starts_with("https://")
+    }
+}
+
+impl FromStr for OidcMappingConfig {
+    type Err = IggyError;
+
+    /// Parses the mapping in the format `VALUE=NAME`, e.g. `iggy-admins=admin`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let Some((value, name)) = value.trim().rsplit_once('=') else {
+            return Err(IggyError::InvalidConfiguration);
+        };
+        if value.is_empty() || name.is_empty() {
+            return Err(IggyError::InvalidConfiguration);
+        }
+
+        Ok(OidcMappingConfig {
+            value: value.to_owned(),
+            name: name.to_owned(),
+        })
+    }
+}
+
+impl Display for OidcMappingConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.value, self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mapping_should_be_parsed_from_value_and_name() {
+        let mapping = OidcMappingConfig::from_str("iggy-admins=admin").unwrap();
+        assert_eq!(mapping.value, "iggy-admins");
+        assert_eq!(mapping.name, "admin");
+        assert_eq!(mapping.to_string(), "iggy-admins=admin");
+    }
+
+    #[test]
+    fn mapping_should_not_be_parsed_given_invalid_format() {
+        for value in ["iggy-admins", "=admin", "iggy-admins="] {
+            assert!(OidcMappingConfig::from_str(value).is_err());
+        }
+    }
+}
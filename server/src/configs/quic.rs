@@ -26,4 +26,5 @@ pub struct QuicCertificateConfig {
     pub self_signed: bool,
     pub cert_file: String,
     pub key_file: String,
+    pub client_auth: bool,
 }
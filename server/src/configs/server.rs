@@ -1,6 +1,9 @@
 use crate::archiver::ArchiverKindType;
+use crate::configs::client_auth::ClientAuthConfig;
+use crate::configs::cluster::ClusterConfig;
 use crate::configs::config_provider::ConfigProviderKind;
 use crate::configs::http::HttpConfig;
+use crate::configs::oidc::OidcConfig;
 use crate::configs::quic::QuicConfig;
 use crate::configs::system::SystemConfig;
 use crate::configs::tcp::TcpConfig;
@@ -22,11 +25,15 @@ pub struct ServerConfig {
     pub message_saver: MessageSaverConfig,
     pub personal_access_token: PersonalAccessTokenConfig,
     pub heartbeat: HeartbeatConfig,
+    pub transaction: TransactionConfig,
     pub system: Arc<SystemConfig>,
     pub quic: QuicConfig,
     pub tcp: TcpConfig,
     pub http: HttpConfig,
+    pub client_auth: ClientAuthConfig,
+    pub oidc: OidcConfig,
     pub telemetry: TelemetryConfig,
+    pub cluster: ClusterConfig,
 }
 
 #[serde_as]
@@ -35,6 +42,8 @@ pub struct DataMaintenanceConfig {
     pub archiver: ArchiverConfig,
     pub messages: MessagesMaintenanceConfig,
     pub state: StateMaintenanceConfig,
+    pub compaction: CompactionMaintenanceConfig,
+    pub snapshot: SnapshotMaintenanceConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -63,6 +72,25 @@ pub struct StateMaintenanceConfig {
     pub interval: IggyDuration,
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CompactionMaintenanceConfig {
+    pub enabled: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub interval: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub tombstones_retention: IggyDuration,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SnapshotMaintenanceConfig {
+    pub enabled: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub interval: IggyDuration,
+    pub min_entries: u64,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DiskArchiverConfig {
     pub path: String,
@@ -101,6 +129,15 @@ pub struct PersonalAccessTokenCleanerConfig {
     pub interval: IggyDuration,
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TransactionConfig {
+    #[serde_as(as = "DisplayFromStr")]
+    pub timeout: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub cleaner_interval: IggyDuration,
+}
+
 #[serde_as]
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct HeartbeatConfig {
@@ -162,4 +199,11 @@ impl ServerConfig {
         })?;
         Ok(server_config)
     }
+
+    /// Returns whether any of the enabled transports verifies the client certificates.
+    pub fn is_client_auth_enabled(&self) -> bool {
+        (self.tcp.enabled && self.tcp.tls.enabled && self.tcp.tls.client_auth)
+            || (self.quic.enabled && self.quic.certificate.client_auth)
+            || (self.http.enabled && self.http.tls.enabled && self.http.tls.client_auth)
+    }
 }
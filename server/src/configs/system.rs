@@ -1,4 +1,5 @@
 use crate::configs::resource_quota::MemoryResourceQuota;
+use derive_more::Display;
 use iggy::confirmation::Confirmation;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
@@ -9,6 +10,7 @@ use iggy::{
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use serde_with::DisplayFromStr;
+use std::str::FromStr;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SystemConfig {
@@ -25,6 +27,9 @@ pub struct SystemConfig {
     pub encryption: EncryptionConfig,
     pub compression: CompressionConfig,
     pub message_deduplication: MessageDeduplicationConfig,
+    pub consumer_group: ConsumerGroupConfig,
+    pub dead_letter: DeadLetterConfig,
+    pub tiered_storage: TieredStorageConfig,
     pub recovery: RecoveryConfig,
 }
 
@@ -91,6 +96,14 @@ pub struct TopicConfig {
     #[serde_as(as = "DisplayFromStr")]
     pub max_size: MaxTopicSize,
     pub delete_oldest_segments: bool,
+    pub consumed_retention: ConsumedRetentionConfig,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ConsumedRetentionConfig {
+    #[serde_as(as = "DisplayFromStr")]
+    pub max_size: MaxTopicSize,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -99,6 +112,28 @@ pub struct PartitionConfig {
     pub messages_required_to_save: u32,
     pub enforce_fsync: bool,
     pub validate_checksum: bool,
+    pub io_backend: IoBackend,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Display, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum IoBackend {
+    #[default]
+    #[display("tokio")]
+    Tokio,
+    #[display("io_uring")]
+    IoUring,
+}
+
+impl FromStr for IoBackend {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tokio" => Ok(IoBackend::Tokio),
+            "io_uring" => Ok(IoBackend::IoUring),
+            _ => Err(format!("Unknown I/O backend: {s}")),
+        }
+    }
 }
 
 #[serde_as]
@@ -110,6 +145,28 @@ pub struct MessageDeduplicationConfig {
     pub expiry: IggyDuration,
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ConsumerGroupConfig {
+    #[serde_as(as = "DisplayFromStr")]
+    pub revocation_timeout: IggyDuration,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeadLetterConfig {
+    pub enabled: bool,
+    pub max_delivery_attempts: u32,
+    pub stream: String,
+    pub topic: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TieredStorageConfig {
+    pub enabled: bool,
+    pub cache_path: String,
+    pub cache_size: IggyByteSize,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RecoveryConfig {
     pub recreate_missing_state: bool,
@@ -152,10 +209,26 @@ impl SystemConfig {
     pub fn get_state_info_path(&self) -> String {
         format!("{}/info", self.get_state_path())
     }
+
+    pub fn get_state_snapshot_path(&self) -> String {
+        format!("{}/snapshot", self.get_state_path())
+    }
     pub fn get_state_tokens_path(&self) -> String {
         format!("{}/tokens", self.get_state_path())
     }
 
+    pub fn get_state_cluster_path(&self) -> String {
+        format!("{}/cluster", self.get_state_path())
+    }
+
+    pub fn get_state_transactions_path(&self) -> String {
+        format!("{}/transactions", self.get_state_path())
+    }
+
+    pub fn get_state_transaction_ids_path(&self) -> String {
+        format!("{}/transaction_ids", self.get_state_path())
+    }
+
     pub fn get_backup_path(&self) -> String {
         format!("{}/{}", self.get_system_path(), self.backup.path)
     }
@@ -172,6 +245,27 @@ impl SystemConfig {
         format!("{}/{}", self.get_system_path(), self.runtime.path)
     }
 
+    pub fn get_archive_cache_path(&self) -> String {
+        format!(
+            "{}/{}",
+            self.get_system_path(),
+            self.tiered_storage.cache_path
+        )
+    }
+
+    pub fn get_archive_cache_segment_path(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+        start_offset: u64,
+    ) -> String {
+        format!(
+            "{}/{stream_id}/{topic_id}/{partition_id}/{start_offset:0>20}",
+            self.get_archive_cache_path()
+        )
+    }
+
     pub fn get_streams_path(&self) -> String {
         format!("{}/{}", self.get_system_path(), self.stream.path)
     }
@@ -211,6 +305,44 @@ impl SystemConfig {
         )
     }
 
+    pub fn get_producers_path(&self, stream_id: u32, topic_id: u32, partition_id: u32) -> String {
+        format!(
+            "{}/producers",
+            self.get_partition_path(stream_id, topic_id, partition_id)
+        )
+    }
+
+    pub fn get_transactions_path(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> String {
+        format!(
+            "{}/transactions",
+            self.get_partition_path(stream_id, topic_id, partition_id)
+        )
+    }
+
+    pub fn get_archived_segments_path(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> String {
+        format!(
+            "{}/archived_segments",
+            self.get_partition_path(stream_id, topic_id, partition_id)
+        )
+    }
+
+    pub fn get_time_index_path(&self, stream_id: u32, topic_id: u32, partition_id: u32) -> String {
+        format!(
+            "{}/time_index",
+            self.get_partition_path(stream_id, topic_id, partition_id)
+        )
+    }
+
     pub fn get_consumer_offsets_path(
         &self,
         stream_id: u32,
@@ -8,6 +8,7 @@ pub struct TcpConfig {
     pub enabled: bool,
     pub address: String,
     pub ipv6: bool,
+    pub zero_copy: bool,
     pub tls: TcpTlsConfig,
     pub socket: TcpSocketConfig,
 }
@@ -17,6 +18,7 @@ pub struct TcpTlsConfig {
     pub enabled: bool,
     pub certificate: String,
     pub password: String,
+    pub client_auth: bool,
 }
 
 #[serde_as]
@@ -1,16 +1,22 @@
 extern crate sysinfo;
 
 use super::server::{
-    ArchiverConfig, DataMaintenanceConfig, MessageSaverConfig, MessagesMaintenanceConfig,
-    StateMaintenanceConfig, TelemetryConfig,
+    ArchiverConfig, CompactionMaintenanceConfig, DataMaintenanceConfig, MessageSaverConfig,
+    MessagesMaintenanceConfig, SnapshotMaintenanceConfig, StateMaintenanceConfig, TelemetryConfig,
 };
 use super::system::CompressionConfig;
 use crate::archiver::ArchiverKindType;
-use crate::configs::server::{PersonalAccessTokenConfig, ServerConfig};
-use crate::configs::system::{CacheConfig, SegmentConfig};
+use crate::configs::client_auth::ClientAuthConfig;
+use crate::configs::cluster::ClusterConfig;
+use crate::configs::oidc::OidcConfig;
+use crate::configs::server::{PersonalAccessTokenConfig, ServerConfig, TransactionConfig};
+use crate::configs::system::{
+    CacheConfig, DeadLetterConfig, IoBackend, PartitionConfig, SegmentConfig, TieredStorageConfig,
+};
 use crate::configs::COMPONENT;
 use crate::server_error::ConfigError;
 use crate::streaming::segments::*;
+use ahash::AHashSet;
 use error_set::ErrContext;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::utils::byte_size::IggyByteSize;
@@ -31,6 +37,15 @@ impl Validatable<ConfigError> for ServerConfig {
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to validate personal access token config")
             })?;
+        self.transaction.validate().with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate transaction config")
+        })?;
+        self.system
+            .partition
+            .validate()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to validate partition config")
+            })?;
         self.system.segment.validate().with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to validate segment config")
         })?;
@@ -43,9 +58,32 @@ impl Validatable<ConfigError> for ServerConfig {
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to validate compression config")
             })?;
+        self.system
+            .dead_letter
+            .validate()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to validate dead letter config")
+            })?;
+        self.system
+            .tiered_storage
+            .validate()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to validate tiered storage config")
+            })?;
         self.telemetry.validate().with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to validate telemetry config")
         })?;
+        self.cluster.validate().with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate cluster config")
+        })?;
+        if self.is_client_auth_enabled() {
+            self.client_auth.validate().with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to validate client auth config")
+            })?;
+        }
+        self.oidc.validate().with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate OIDC config")
+        })?;
 
         let topic_size = match self.system.topic.max_size {
             MaxTopicSize::Custom(size) => Ok(size.as_bytes_u64()),
@@ -53,6 +91,10 @@ impl Validatable<ConfigError> for ServerConfig {
             MaxTopicSize::ServerDefault => Err(ConfigError::InvalidConfiguration),
         }?;
 
+        if let MaxTopicSize::ServerDefault = self.system.topic.consumed_retention.max_size {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
         if let IggyExpiry::ServerDefault = self.system.segment.message_expiry {
             return Err(ConfigError::InvalidConfiguration);
         }
@@ -73,12 +115,10 @@ impl Validatable<ConfigError> for ServerConfig {
 
 impl Validatable<ConfigError> for CompressionConfig {
     fn validate(&self) -> Result<(), ConfigError> {
-        let compression_alg = &self.default_algorithm;
-        if *compression_alg != CompressionAlgorithm::None {
-            // TODO(numinex): Change this message once server side compression is fully developed.
+        if !self.allow_override && self.default_algorithm != CompressionAlgorithm::None {
             println!(
-                "Server started with server-side compression enabled, using algorithm: {}, this feature is not implemented yet!",
-                compression_alg
+                "Server-side compression is enabled for all topics, using algorithm: {}.",
+                self.default_algorithm
             );
         }
 
@@ -86,6 +126,113 @@ impl Validatable<ConfigError> for CompressionConfig {
     }
 }
 
+impl Validatable<ConfigError> for DeadLetterConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.max_delivery_attempts == 0 {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        if self.stream.trim().is_empty() || self.topic.trim().is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for TieredStorageConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.cache_path.trim().is_empty() || self.cache_size.as_bytes_u64() == 0 {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for ClientAuthConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.ca_file.is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        let mut identities = AHashSet::with_capacity(self.users.len());
+        for user in &self.users {
+            if !identities.insert((user.kind, user.value.as_str())) {
+                return Err(ConfigError::InvalidConfiguration);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for OidcConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.issuer.is_empty() || self.audience.is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        if self.jwks.is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        if self.subjects.is_empty() && self.groups.is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        if self.jwks_timeout.is_zero() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        if !self.groups.is_empty() && self.groups_claim.is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for ClusterConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.node.id == 0 || self.node.address.is_empty() || self.secret.is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        let mut ids = AHashSet::with_capacity(self.peers.len() + 1);
+        ids.insert(self.node.id);
+        for peer in &self.peers {
+            if !ids.insert(peer.id) || peer.address == self.node.address {
+                return Err(ConfigError::InvalidConfiguration);
+            }
+        }
+
+        if self.heartbeat_interval.as_micros() == 0
+            || self.election_timeout.as_micros() <= self.heartbeat_interval.as_micros()
+        {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable<ConfigError> for TelemetryConfig {
     fn validate(&self) -> Result<(), ConfigError> {
         if !self.enabled {
@@ -160,6 +307,16 @@ impl Validatable<ConfigError> for SegmentConfig {
     }
 }
 
+impl Validatable<ConfigError> for PartitionConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.io_backend == IoBackend::IoUring && !cfg!(target_os = "linux") {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable<ConfigError> for MessageSaverConfig {
     fn validate(&self) -> Result<(), ConfigError> {
         if self.enabled && self.interval.is_zero() {
@@ -181,6 +338,14 @@ impl Validatable<ConfigError> for DataMaintenanceConfig {
         self.state.validate().with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to validate state maintenance config")
         })?;
+        self.compaction.validate().with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to validate compaction maintenance config"
+            )
+        })?;
+        self.snapshot.validate().with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate snapshot maintenance config")
+        })?;
         Ok(())
     }
 }
@@ -256,6 +421,36 @@ impl Validatable<ConfigError> for StateMaintenanceConfig {
     }
 }
 
+impl Validatable<ConfigError> for CompactionMaintenanceConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.enabled && self.interval.is_zero() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for SnapshotMaintenanceConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.enabled && (self.interval.is_zero() || self.min_entries == 0) {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for TransactionConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.timeout.is_zero() || self.cleaner_interval.is_zero() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable<ConfigError> for PersonalAccessTokenConfig {
     fn validate(&self) -> Result<(), ConfigError> {
         if self.max_tokens_per_user == 0 {
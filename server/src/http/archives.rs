@@ -0,0 +1,114 @@
+use crate::http::error::CustomError;
+use crate::http::jwt::json_web_token::Identity;
+use crate::http::shared::AppState;
+use crate::http::COMPONENT;
+use crate::streaming::session::Session;
+use axum::extract::{Path, State};
+use axum::routing::post;
+use axum::{Extension, Json, Router};
+use error_set::ErrContext;
+use iggy::archives::restore_archive::RestoreArchive;
+use iggy::identifier::Identifier;
+use iggy::models::archive::RestoredArchive;
+use iggy::validatable::Validatable;
+use std::sync::Arc;
+
+pub fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/streams/{stream_id}/archive/restore",
+            post(restore_stream_archive),
+        )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/archive/restore",
+            post(restore_topic_archive),
+        )
+        .with_state(state)
+}
+
+async fn restore_stream_archive(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(stream_id): Path<String>,
+    Json(mut command): Json<RestoreArchive>,
+) -> Result<Json<RestoredArchive>, CustomError> {
+    command.stream_id = Identifier::from_str_value(&stream_id)?;
+    command.topic_id = None;
+    restore_archive(&state, &identity, command).await
+}
+
+async fn restore_topic_archive(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id)): Path<(String, String)>,
+    Json(mut command): Json<RestoreArchive>,
+) -> Result<Json<RestoredArchive>, CustomError> {
+    command.stream_id = Identifier::from_str_value(&stream_id)?;
+    command.topic_id = Some(Identifier::from_str_value(&topic_id)?);
+    restore_archive(&state, &identity, command).await
+}
+
+async fn restore_archive(
+    state: &AppState,
+    identity: &Identity,
+    command: RestoreArchive,
+) -> Result<Json<RestoredArchive>, CustomError> {
+    command.validate()?;
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    let mut archive_restore = state
+        .system
+        .read()
+        .await
+        .prepare_archive_restore(&session, &command)
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to prepare archive restore for stream ID: {}, user ID: {}",
+                command.stream_id, identity.user_id
+            )
+        })?;
+    archive_restore
+        .fetch_archived_state(command.state_snapshot.as_deref())
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to fetch archived state for stream ID: {}, user ID: {}",
+                command.stream_id, identity.user_id
+            )
+        })?;
+
+    let mut system = state.system.write().await;
+    let entries = system
+        .recreate_archived_resources(&session, &command, &mut archive_restore)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to recreate archived resources for stream ID: {}, user ID: {}",
+                command.stream_id, identity.user_id
+            )
+        })?;
+    let system = system.downgrade();
+    for entry in entries {
+        system
+            .state
+            .apply(identity.user_id, entry)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to apply recreated archived resources for stream ID: {}, user ID: {}",
+                    command.stream_id, identity.user_id
+                )
+            })?;
+    }
+    drop(system);
+
+    let restored_archive = archive_restore
+        .restore_partitions(command.range)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to restore archive for stream ID: {}, user ID: {}",
+                command.stream_id, identity.user_id
+            )
+        })?;
+    Ok(Json(restored_archive))
+}
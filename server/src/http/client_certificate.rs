@@ -0,0 +1,59 @@
+use crate::tls::client_certificate::ClientCertificateAuthenticator;
+use axum_server::accept::{Accept, DefaultAcceptor};
+use axum_server::tls_rustls::RustlsAcceptor;
+use futures::future::BoxFuture;
+use std::io;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower_http::add_extension::AddExtension;
+
+/// The user mapped to the certificate presented by the client, available as the extension of every request
+/// sent over the same connection.
+#[derive(Debug, Clone)]
+pub struct ClientCertificateUser {
+    pub username: Option<String>,
+}
+
+/// Accepts the TLS connections, and maps the verified client certificate to the user.
+#[derive(Clone)]
+pub struct ClientCertificateAcceptor {
+    inner: RustlsAcceptor<DefaultAcceptor>,
+    authenticator: Arc<ClientCertificateAuthenticator>,
+}
+
+impl ClientCertificateAcceptor {
+    pub fn new(
+        inner: RustlsAcceptor<DefaultAcceptor>,
+        authenticator: Arc<ClientCertificateAuthenticator>,
+    ) -> Self {
+        Self {
+            inner,
+            authenticator,
+        }
+    }
+}
+
+impl<I, S> Accept<I, S> for ClientCertificateAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, ClientCertificateUser>;
+    type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let inner = self.inner.clone();
+        let authenticator = self.authenticator.clone();
+        Box::pin(async move {
+            let (stream, service) = inner.accept(stream, service).await?;
+            let username =
+                authenticator.get_username_from_chain(stream.get_ref().1.peer_certificates());
+            Ok((
+                stream,
+                AddExtension::new(service, ClientCertificateUser { username }),
+            ))
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use crate::http::error::CustomError;
+use crate::http::shared::AppState;
+use axum::body::Body;
+use axum::{
+    extract::State,
+    http::{Method, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+/// The endpoints which modify only the local data of the node, so they can be handled by the followers.
+const LOCAL_PATHS: &[&str] = &[
+    "/users/login",
+    "/users/login/token",
+    "/users/logout",
+    "/users/refresh-token",
+    "/personal-access-tokens/login",
+    "/snapshot",
+];
+
+pub async fn ensure_leader(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, CustomError> {
+    if request.method() != Method::GET && !is_local_path(request.uri().path()) {
+        state.system.read().await.ensure_leader()?;
+    }
+
+    Ok(next.run(request).await)
+}
+
+fn is_local_path(path: &str) -> bool {
+    LOCAL_PATHS.contains(&path)
+        || path.contains("/consumer-offsets")
+        || path.contains("/messages/flush/")
+}
@@ -92,6 +92,7 @@ async fn create_consumer_group(
                 &command.topic_id,
                 command.group_id,
                 &command.name,
+                command.assignment_strategy,
             )
             .await
             .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to create consumer group, stream ID: {}, topic ID: {}, group ID: {:?}", stream_id, topic_id, command.group_id))?;
@@ -80,6 +80,7 @@ async fn store_consumer_offset(
             &command.0.topic_id,
             command.0.partition_id,
             command.0.offset,
+            command.0.generation,
         )
         .await
         .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to store consumer offset, stream ID: {}, topic ID: {}, partition ID: {:?}", stream_id, topic_id, command.0.partition_id))?;
@@ -37,12 +37,31 @@ impl IntoResponse for CustomError {
                     IggyError::ConsumerGroupNameNotFound(_, _) => StatusCode::NOT_FOUND,
                     IggyError::ConsumerGroupMemberNotFound(_, _, _) => StatusCode::NOT_FOUND,
                     IggyError::ConsumerOffsetNotFound(_) => StatusCode::NOT_FOUND,
+                    IggyError::TransactionNotFound(_) => StatusCode::NOT_FOUND,
+                    IggyError::ArchivedStateNotFound => StatusCode::NOT_FOUND,
+                    IggyError::ArchivedStreamNotFound(_) => StatusCode::NOT_FOUND,
+                    IggyError::ArchivedTopicNotFound(_, _) => StatusCode::NOT_FOUND,
                     IggyError::ResourceNotFound(_) => StatusCode::NOT_FOUND,
                     IggyError::Unauthenticated => StatusCode::UNAUTHORIZED,
                     IggyError::AccessTokenMissing => StatusCode::UNAUTHORIZED,
                     IggyError::InvalidAccessToken => StatusCode::UNAUTHORIZED,
                     IggyError::InvalidPersonalAccessToken => StatusCode::UNAUTHORIZED,
                     IggyError::Unauthorized => StatusCode::FORBIDDEN,
+                    IggyError::NotLeader(_) => StatusCode::MISDIRECTED_REQUEST,
+                    IggyError::LeaderNotElected => StatusCode::SERVICE_UNAVAILABLE,
+                    IggyError::NotEnoughReplicas(_, _) => StatusCode::SERVICE_UNAVAILABLE,
+                    IggyError::UserProduceThroughputQuotaExceeded(_, _) => {
+                        StatusCode::TOO_MANY_REQUESTS
+                    }
+                    IggyError::UserConsumeThroughputQuotaExceeded(_, _) => {
+                        StatusCode::TOO_MANY_REQUESTS
+                    }
+                    IggyError::StreamProduceThroughputQuotaExceeded(_, _) => {
+                        StatusCode::TOO_MANY_REQUESTS
+                    }
+                    IggyError::StreamConsumeThroughputQuotaExceeded(_, _) => {
+                        StatusCode::TOO_MANY_REQUESTS
+                    }
                     _ => StatusCode::BAD_REQUEST,
                 };
                 (status_code, Json(ErrorResponse::from_error(error)))
@@ -88,6 +107,8 @@ impl ErrorResponse {
                 }
                 IggyError::ConsumerGroupNameAlreadyExists(_, _) => Some("name".to_string()),
                 IggyError::UserAlreadyExists => Some("username".to_string()),
+                IggyError::InvalidRoleName => Some("name".to_string()),
+                IggyError::RoleAlreadyExists(_) => Some("name".to_string()),
                 IggyError::PersonalAccessTokenAlreadyExists(_, _) => Some("name".to_string()),
                 _ => None,
             },
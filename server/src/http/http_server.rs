@@ -1,4 +1,6 @@
+use crate::configs::client_auth::ClientAuthConfig;
 use crate::configs::http::{HttpConfig, HttpCorsConfig};
+use crate::http::client_certificate::ClientCertificateAcceptor;
 use crate::http::diagnostics::request_diagnostics;
 use crate::http::jwt::cleaner::start_expired_tokens_cleaner;
 use crate::http::jwt::jwt_manager::JwtManager;
@@ -7,19 +9,24 @@ use crate::http::metrics::metrics;
 use crate::http::shared::AppState;
 use crate::http::*;
 use crate::streaming::systems::system::SharedSystem;
+use crate::tls::client_certificate::ClientCertificateAuthenticator;
+use crate::tls::server_config;
 use axum::extract::DefaultBodyLimit;
 use axum::http::Method;
 use axum::{middleware, Router};
-use axum_server::tls_rustls::RustlsConfig;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
 use std::net::SocketAddr;
-use std::path::PathBuf;
 use std::sync::Arc;
 use tower_http::cors::{AllowOrigin, CorsLayer};
 use tracing::{error, info};
 
 /// Starts the HTTP API server.
 /// Returns the address the server is listening on.
-pub async fn start(config: HttpConfig, system: SharedSystem) -> SocketAddr {
+pub async fn start(
+    config: HttpConfig,
+    client_auth: ClientAuthConfig,
+    system: SharedSystem,
+) -> SocketAddr {
     let api_name = if config.tls.enabled {
         "HTTP API (TLS)"
     } else {
@@ -31,15 +38,22 @@ pub async fn start(config: HttpConfig, system: SharedSystem) -> SocketAddr {
         .merge(system::router(app_state.clone(), &config.metrics))
         .merge(personal_access_tokens::router(app_state.clone()))
         .merge(users::router(app_state.clone()))
+        .merge(roles::router(app_state.clone()))
         .merge(streams::router(app_state.clone()))
         .merge(topics::router(app_state.clone()))
         .merge(consumer_groups::router(app_state.clone()))
         .merge(consumer_offsets::router(app_state.clone()))
         .merge(partitions::router(app_state.clone()))
         .merge(messages::router(app_state.clone()))
+        .merge(transactions::router(app_state.clone()))
+        .merge(archives::router(app_state.clone()))
         .layer(DefaultBodyLimit::max(
             config.max_request_size.as_bytes_u64() as usize,
         ))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            cluster::ensure_leader,
+        ))
         .layer(middleware::from_fn_with_state(app_state.clone(), jwt_auth));
 
     if config.cors.enabled {
@@ -74,12 +88,21 @@ pub async fn start(config: HttpConfig, system: SharedSystem) -> SocketAddr {
 
         address
     } else {
-        let tls_config = RustlsConfig::from_pem_file(
-            PathBuf::from(config.tls.cert_file),
-            PathBuf::from(config.tls.key_file),
+        let certificate_chain = server_config::load_pem_certificates(&config.tls.cert_file)
+            .unwrap_or_else(|error| panic!("Failed to load HTTP TLS certificate: {error}"));
+        let key = server_config::load_pem_key(&config.tls.key_file)
+            .unwrap_or_else(|error| panic!("Failed to load HTTP TLS key: {error}"));
+        let mut tls_config = server_config::create_server_config(
+            certificate_chain,
+            key,
+            config.tls.client_auth.then_some(&client_auth),
         )
-        .await
-        .unwrap();
+        .unwrap_or_else(|error| panic!("Failed to create HTTP TLS config: {error}"));
+        tls_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        let acceptor = ClientCertificateAcceptor::new(
+            RustlsAcceptor::new(RustlsConfig::from_config(Arc::new(tls_config))),
+            Arc::new(ClientCertificateAuthenticator::new(client_auth.users)),
+        );
 
         let listener = std::net::TcpListener::bind(config.address).unwrap();
         let address = listener
@@ -89,7 +112,8 @@ pub async fn start(config: HttpConfig, system: SharedSystem) -> SocketAddr {
         info!("Started {api_name} on: {address}");
 
         tokio::task::spawn(async move {
-            if let Err(error) = axum_server::from_tcp_rustls(listener, tls_config)
+            if let Err(error) = axum_server::from_tcp(listener)
+                .acceptor(acceptor)
                 .serve(app.into_make_service_with_connect_info::<SocketAddr>())
                 .await
             {
@@ -1,3 +1,4 @@
+use crate::http::client_certificate::ClientCertificateUser;
 use crate::http::jwt::json_web_token::Identity;
 use crate::http::shared::{AppState, RequestDetails};
 use axum::body::Body;
@@ -8,6 +9,7 @@ use axum::{
     response::Response,
 };
 use error_set::ErrContext;
+use iggy::identifier::Identifier;
 use std::sync::Arc;
 
 const COMPONENT: &str = "JWT_MIDDLEWARE";
@@ -21,6 +23,7 @@ const PUBLIC_PATHS: &[&str] = &[
     "/ping",
     "/stats",
     "/users/login",
+    "/users/login/token",
     "/users/refresh-token",
     "/personal-access-tokens/login",
 ];
@@ -34,19 +37,22 @@ pub async fn jwt_auth(
         return Ok(next.run(request).await);
     }
 
-    let bearer = request
-        .headers()
-        .get(AUTHORIZATION)
-        .ok_or(UNAUTHORIZED)
-        .with_error_context(|error| {
-            format!("{COMPONENT} (error: {error}) - missing or inaccessible Authorization header")
-        })?
+    let Some(authorization) = request.headers().get(AUTHORIZATION) else {
+        return client_certificate_auth(state, request, next).await;
+    };
+
+    let bearer = authorization
         .to_str()
         .with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - invalid authorization header format")
         })
         .map_err(|_| UNAUTHORIZED)?;
 
+    // The client without the access token sends the empty bearer, so it might use the client certificate instead.
+    if bearer.trim_end() == BEARER.trim_end() {
+        return client_certificate_auth(state, request, next).await;
+    }
+
     if !bearer.starts_with(BEARER) {
         return Err(StatusCode::UNAUTHORIZED);
     }
@@ -82,3 +88,42 @@ pub async fn jwt_auth(
     request.extensions_mut().insert(identity);
     Ok(next.run(request).await)
 }
+
+// The client presenting the certificate mapped to a user doesn't need the JWT.
+async fn client_certificate_auth(
+    state: Arc<AppState>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let username = request
+        .extensions()
+        .get::<ClientCertificateUser>()
+        .and_then(|user| user.username.as_deref())
+        .ok_or(UNAUTHORIZED)
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - missing Authorization header and client certificate user")
+        })?;
+    let user_id = {
+        let system = state.system.read().await;
+        let user = Identifier::named(username)
+            .and_then(|username| system.get_user(&username))
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get client certificate user: {username}")
+            })
+            .map_err(|_| UNAUTHORIZED)?;
+        if !user.is_active() {
+            return Err(UNAUTHORIZED);
+        }
+        user.id
+    };
+
+    let request_details = request.extensions().get::<RequestDetails>().unwrap();
+    let identity = Identity {
+        token_id: String::new(),
+        token_expiry: 0,
+        user_id,
+        ip_address: request_details.ip_address,
+    };
+    request.extensions_mut().insert(identity);
+    Ok(next.run(request).await)
+}
@@ -4,6 +4,7 @@ use crate::streaming::personal_access_tokens::personal_access_token::PersonalAcc
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::topics::consumer_group::ConsumerGroup;
 use crate::streaming::topics::topic::Topic;
+use crate::streaming::users::role::Role;
 use crate::streaming::users::user::User;
 use iggy::locking::IggySharedMut;
 use iggy::locking::IggySharedMutFn;
@@ -11,6 +12,7 @@ use iggy::models::client_info::ConsumerGroupInfo;
 use iggy::models::consumer_group::{ConsumerGroupDetails, ConsumerGroupMember};
 use iggy::models::identity_info::{IdentityInfo, TokenInfo};
 use iggy::models::personal_access_token::PersonalAccessTokenInfo;
+use iggy::models::role::{RoleInfo, RoleInfoDetails};
 use iggy::models::stream::StreamDetails;
 use iggy::models::topic::TopicDetails;
 use iggy::models::user_info::{UserInfo, UserInfoDetails};
@@ -62,8 +64,10 @@ pub fn map_topics(topics: &[&Topic]) -> Vec<iggy::models::topic::Topic> {
             messages_count: topic.get_messages_count(),
             message_expiry: topic.message_expiry,
             compression_algorithm: topic.compression_algorithm,
+            compression_level: topic.compression_level,
             max_topic_size: topic.max_topic_size,
             replication_factor: topic.replication_factor,
+            cleanup_policy: topic.cleanup_policy,
         };
         topics_data.push(topic);
     }
@@ -82,8 +86,10 @@ pub async fn map_topic(topic: &Topic) -> TopicDetails {
         partitions: Vec::new(),
         message_expiry: topic.message_expiry,
         compression_algorithm: topic.compression_algorithm,
+        compression_level: topic.compression_level,
         max_topic_size: topic.max_topic_size,
         replication_factor: topic.replication_factor,
+        cleanup_policy: topic.cleanup_policy,
     };
     for partition in topic.get_partitions() {
         let partition = partition.read().await;
@@ -127,6 +133,33 @@ pub fn map_users(users: &[&User]) -> Vec<UserInfo> {
     users_data
 }
 
+pub fn map_role(role: &Role) -> RoleInfoDetails {
+    let mut users = role.users.iter().copied().collect::<Vec<_>>();
+    users.sort();
+    RoleInfoDetails {
+        id: role.id,
+        created_at: role.created_at,
+        name: role.name.clone(),
+        permissions: role.permissions.clone(),
+        users,
+    }
+}
+
+pub fn map_roles(roles: &[&Role]) -> Vec<RoleInfo> {
+    let mut roles_data = Vec::with_capacity(roles.len());
+    for role in roles {
+        let role = RoleInfo {
+            id: role.id,
+            created_at: role.created_at,
+            name: role.name.clone(),
+            users_count: role.users.len() as u32,
+        };
+        roles_data.push(role);
+    }
+    roles_data.sort_by_key(|role| role.id);
+    roles_data
+}
+
 pub fn map_personal_access_tokens(
     personal_access_tokens: &[&PersonalAccessToken],
 ) -> Vec<PersonalAccessTokenInfo> {
@@ -193,6 +226,8 @@ pub async fn map_consumer_groups(
             name: consumer_group.name.clone(),
             partitions_count: consumer_group.partitions_count,
             members_count: consumer_group.get_members().len() as u32,
+            assignment_strategy: consumer_group.assignment_strategy,
+            generation: consumer_group.get_generation(),
         };
         groups.push(consumer_group);
     }
@@ -206,6 +241,8 @@ pub async fn map_consumer_group(consumer_group: &ConsumerGroup) -> ConsumerGroup
         name: consumer_group.name.clone(),
         partitions_count: consumer_group.partitions_count,
         members_count: consumer_group.get_members().len() as u32,
+        assignment_strategy: consumer_group.assignment_strategy,
+        generation: consumer_group.get_generation(),
         members: Vec::new(),
     };
     let members = consumer_group.get_members();
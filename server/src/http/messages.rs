@@ -7,11 +7,13 @@ use crate::streaming::systems::messages::PollingArgs;
 use crate::streaming::utils::random_id;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::{Extension, Json, Router};
 use error_set::ErrContext;
 use iggy::consumer::Consumer;
 use iggy::identifier::Identifier;
+use iggy::messages::init_producer::InitProducer;
+use iggy::messages::nack_message::NackMessage;
 use iggy::messages::poll_messages::PollMessages;
 use iggy::messages::send_messages::SendMessages;
 use iggy::models::messages::PolledMessages;
@@ -29,6 +31,14 @@ pub fn router(state: Arc<AppState>) -> Router {
             "/streams/{stream_id}/topics/{topic_id}/messages/flush/{partition_id}/{fsync}",
             get(flush_unsaved_buffer),
         )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/messages/nack",
+            post(nack_message),
+        )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/messages/producers",
+            post(init_producer),
+        )
         .with_state(state)
 }
 
@@ -51,7 +61,13 @@ async fn poll_messages(
             &query.0.stream_id,
             &query.0.topic_id,
             query.0.partition_id,
-            PollingArgs::new(query.0.strategy, query.0.count, query.0.auto_commit),
+            PollingArgs::new(
+                query.0.strategy,
+                query.0.count,
+                query.0.auto_commit,
+                query.0.isolation_level,
+                query.0.filter,
+            ),
         )
         .await
         .with_error_context(|error| {
@@ -83,9 +99,11 @@ async fn send_messages(
     let command_stream_id = command.stream_id;
     let command_topic_id = command.topic_id;
     let partitioning = command.partitioning;
-    let system = state.system.read().await;
     // TODO(haze): Add confirmation level after testing is complete
-    system
+    let replication = state
+        .system
+        .read()
+        .await
         .append_messages(
             &Session::stateless(identity.user_id, identity.ip_address),
             command_stream_id,
@@ -101,6 +119,15 @@ async fn send_messages(
                 stream_id, topic_id
             )
         })?;
+    // The replicas are awaited without holding the system lock.
+    if let Some(replication) = replication {
+        replication.wait().await.with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to replicate messages, stream ID: {}, topic ID: {}",
+                stream_id, topic_id
+            )
+        })?;
+    }
     Ok(StatusCode::CREATED)
 }
 
@@ -124,3 +151,62 @@ async fn flush_unsaved_buffer(
         .await?;
     Ok(StatusCode::OK)
 }
+
+#[instrument(skip_all, name = "trace_nack_message", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id, iggy_topic_id = topic_id))]
+async fn nack_message(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id)): Path<(String, String)>,
+    mut command: Json<NackMessage>,
+) -> Result<StatusCode, CustomError> {
+    command.stream_id = Identifier::from_str_value(&stream_id)?;
+    command.topic_id = Identifier::from_str_value(&topic_id)?;
+    command.validate()?;
+    let system = state.system.read().await;
+    system
+        .nack_message(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.0.consumer,
+            &command.0.stream_id,
+            &command.0.topic_id,
+            command.0.partition_id,
+            command.0.offset,
+            &command.0.reason,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to nack message, stream ID: {}, topic ID: {}, partition ID: {}, offset: {}",
+                stream_id, topic_id, command.0.partition_id, command.0.offset
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip_all, name = "trace_init_producer", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id, iggy_topic_id = topic_id))]
+async fn init_producer(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id)): Path<(String, String)>,
+    mut command: Json<InitProducer>,
+) -> Result<Json<u64>, CustomError> {
+    command.stream_id = Identifier::from_str_value(&stream_id)?;
+    command.topic_id = Identifier::from_str_value(&topic_id)?;
+    command.validate()?;
+    let system = state.system.read().await;
+    let epoch = system
+        .init_producer(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.0.stream_id,
+            &command.0.topic_id,
+            command.0.producer_id,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to init producer with ID: {}, stream ID: {}, topic ID: {}",
+                command.0.producer_id, stream_id, topic_id
+            )
+        })?;
+    Ok(Json(epoch))
+}
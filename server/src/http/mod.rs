@@ -1,3 +1,6 @@
+pub mod archives;
+pub mod client_certificate;
+pub mod cluster;
 pub mod consumer_groups;
 pub mod consumer_offsets;
 pub mod diagnostics;
@@ -9,10 +12,12 @@ pub mod messages;
 pub mod metrics;
 pub mod partitions;
 pub mod personal_access_tokens;
+pub mod roles;
 mod shared;
 pub mod streams;
 pub mod system;
 pub mod topics;
+pub mod transactions;
 pub mod users;
 
 pub const COMPONENT: &str = "HTTP";
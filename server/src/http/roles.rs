@@ -0,0 +1,259 @@
+use crate::http::error::CustomError;
+use crate::http::jwt::json_web_token::Identity;
+use crate::http::mapper;
+use crate::http::shared::AppState;
+use crate::http::COMPONENT;
+use crate::state::command::EntryCommand;
+use crate::streaming::session::Session;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use error_set::ErrContext;
+use iggy::identifier::Identifier;
+use iggy::models::role::{RoleInfo, RoleInfoDetails};
+use iggy::roles::assign_role::AssignRole;
+use iggy::roles::create_role::CreateRole;
+use iggy::roles::delete_role::DeleteRole;
+use iggy::roles::unassign_role::UnassignRole;
+use iggy::roles::update_role::UpdateRole;
+use iggy::validatable::Validatable;
+use std::sync::Arc;
+use tracing::instrument;
+
+pub fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/roles", get(get_roles).post(create_role))
+        .route(
+            "/roles/{role_id}",
+            get(get_role).put(update_role).delete(delete_role),
+        )
+        .route(
+            "/roles/{role_id}/users/{user_id}",
+            post(assign_role).delete(unassign_role),
+        )
+        .with_state(state)
+}
+
+async fn get_role(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(role_id): Path<String>,
+) -> Result<Json<RoleInfoDetails>, CustomError> {
+    let identifier_role_id = Identifier::from_str_value(&role_id)?;
+    let system = state.system.read().await;
+    let Ok(role) = system.find_role(
+        &Session::stateless(identity.user_id, identity.ip_address),
+        &identifier_role_id,
+    ) else {
+        return Err(CustomError::ResourceNotFound);
+    };
+    let Some(role) = role else {
+        return Err(CustomError::ResourceNotFound);
+    };
+
+    let role = mapper::map_role(role);
+    Ok(Json(role))
+}
+
+async fn get_roles(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<Vec<RoleInfo>>, CustomError> {
+    let system = state.system.read().await;
+    let roles = system
+        .get_roles(&Session::stateless(identity.user_id, identity.ip_address))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to get roles, user ID: {}",
+                identity.user_id
+            )
+        })?;
+    let roles = mapper::map_roles(&roles);
+    Ok(Json(roles))
+}
+
+#[instrument(skip_all, name = "trace_create_role", fields(iggy_user_id = identity.user_id))]
+async fn create_role(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Json(command): Json<CreateRole>,
+) -> Result<Json<RoleInfoDetails>, CustomError> {
+    command.validate()?;
+
+    let mut system = state.system.write().await;
+    let role = system
+        .create_role(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.name,
+            command.permissions.clone(),
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to create role, name: {}",
+                command.name
+            )
+        })?;
+    let response = Json(mapper::map_role(role));
+
+    let system = system.downgrade();
+    let name = command.name.clone();
+    system
+        .state
+        .apply(identity.user_id, EntryCommand::CreateRole(command))
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to apply create role, name: {name}")
+        })?;
+
+    Ok(response)
+}
+
+#[instrument(skip_all, name = "trace_update_role", fields(iggy_user_id = identity.user_id, iggy_role_id = role_id))]
+async fn update_role(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(role_id): Path<String>,
+    Json(mut command): Json<UpdateRole>,
+) -> Result<StatusCode, CustomError> {
+    command.role_id = Identifier::from_str_value(&role_id)?;
+    command.validate()?;
+
+    let mut system = state.system.write().await;
+    system
+        .update_role(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.role_id,
+            command.name.clone(),
+            command.permissions.clone(),
+        )
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to update role, role ID: {role_id}")
+        })?;
+
+    let system = system.downgrade();
+    system
+        .state
+        .apply(identity.user_id, EntryCommand::UpdateRole(command))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply update role, role ID: {role_id}"
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip_all, name = "trace_delete_role", fields(iggy_user_id = identity.user_id, iggy_role_id = role_id))]
+async fn delete_role(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(role_id): Path<String>,
+) -> Result<StatusCode, CustomError> {
+    let identifier_role_id = Identifier::from_str_value(&role_id)?;
+
+    let mut system = state.system.write().await;
+    system
+        .delete_role(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &identifier_role_id,
+        )
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to delete role with ID: {role_id}")
+        })?;
+
+    let system = system.downgrade();
+    system
+        .state
+        .apply(
+            identity.user_id,
+            EntryCommand::DeleteRole(DeleteRole {
+                role_id: identifier_role_id,
+            }),
+        )
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to apply delete role with ID: {role_id}")
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip_all, name = "trace_assign_role", fields(iggy_user_id = identity.user_id, iggy_role_id = role_id, iggy_assigned_user_id = user_id))]
+async fn assign_role(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((role_id, user_id)): Path<(String, String)>,
+) -> Result<StatusCode, CustomError> {
+    let command = AssignRole {
+        role_id: Identifier::from_str_value(&role_id)?,
+        user_id: Identifier::from_str_value(&user_id)?,
+    };
+
+    let mut system = state.system.write().await;
+    system
+        .assign_role(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.role_id,
+            &command.user_id,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to assign role with ID: {role_id} to user with ID: {user_id}"
+            )
+        })?;
+
+    let system = system.downgrade();
+    system
+        .state
+        .apply(identity.user_id, EntryCommand::AssignRole(command))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply assign role with ID: {role_id} to user with ID: {user_id}"
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip_all, name = "trace_unassign_role", fields(iggy_user_id = identity.user_id, iggy_role_id = role_id, iggy_unassigned_user_id = user_id))]
+async fn unassign_role(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((role_id, user_id)): Path<(String, String)>,
+) -> Result<StatusCode, CustomError> {
+    let command = UnassignRole {
+        role_id: Identifier::from_str_value(&role_id)?,
+        user_id: Identifier::from_str_value(&user_id)?,
+    };
+
+    let mut system = state.system.write().await;
+    system
+        .unassign_role(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.role_id,
+            &command.user_id,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to unassign role with ID: {role_id} from user with ID: {user_id}"
+            )
+        })?;
+
+    let system = system.downgrade();
+    system
+        .state
+        .apply(identity.user_id, EntryCommand::UnassignRole(command))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply unassign role with ID: {role_id} from user with ID: {user_id}"
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -6,7 +6,7 @@ use crate::http::COMPONENT;
 use crate::streaming::session::Session;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
-use axum::routing::{delete, get};
+use axum::routing::{delete, get, put};
 use axum::{Extension, Json, Router};
 use error_set::ErrContext;
 use iggy::identifier::Identifier;
@@ -14,6 +14,7 @@ use iggy::models::stream::{Stream, StreamDetails};
 use iggy::streams::create_stream::CreateStream;
 use iggy::streams::delete_stream::DeleteStream;
 use iggy::streams::purge_stream::PurgeStream;
+use iggy::streams::set_stream_quota::SetStreamQuota;
 use iggy::streams::update_stream::UpdateStream;
 use iggy::validatable::Validatable;
 
@@ -29,6 +30,7 @@ pub fn router(state: Arc<AppState>) -> Router {
             get(get_stream).put(update_stream).delete(delete_stream),
         )
         .route("/streams/{stream_id}/purge", delete(purge_stream))
+        .route("/streams/{stream_id}/quota", put(set_stream_quota))
         .with_state(state)
 }
 
@@ -222,3 +224,42 @@ async fn purge_stream(
         })?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[instrument(skip_all, name = "trace_set_stream_quota", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id))]
+async fn set_stream_quota(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(stream_id): Path<String>,
+    Json(mut command): Json<SetStreamQuota>,
+) -> Result<StatusCode, CustomError> {
+    command.stream_id = Identifier::from_str_value(&stream_id)?;
+    command.validate()?;
+
+    let mut system = state.system.write().await;
+    system
+        .set_stream_quota(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.stream_id,
+            command.quota,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to set stream quota, stream ID: {}",
+                stream_id
+            )
+        })?;
+
+    let system = system.downgrade();
+    system
+        .state
+        .apply(identity.user_id, EntryCommand::SetStreamQuota(command))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply set stream quota, stream ID: {}",
+                stream_id
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -7,7 +7,7 @@ use crate::state::command::EntryCommand;
 use crate::streaming::session::Session;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
-use axum::routing::{delete, get};
+use axum::routing::{delete, get, put};
 use axum::{Extension, Json, Router};
 use error_set::ErrContext;
 use iggy::identifier::Identifier;
@@ -15,6 +15,7 @@ use iggy::models::topic::{Topic, TopicDetails};
 use iggy::topics::create_topic::CreateTopic;
 use iggy::topics::delete_topic::DeleteTopic;
 use iggy::topics::purge_topic::PurgeTopic;
+use iggy::topics::set_topic_retention::SetTopicRetention;
 use iggy::topics::update_topic::UpdateTopic;
 use iggy::validatable::Validatable;
 use std::sync::Arc;
@@ -34,6 +35,10 @@ pub fn router(state: Arc<AppState>) -> Router {
             "/streams/{stream_id}/topics/{topic_id}/purge",
             delete(purge_topic),
         )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/retention",
+            put(set_topic_retention),
+        )
         .with_state(state)
 }
 
@@ -102,8 +107,10 @@ async fn create_topic(
             command.partitions_count,
             command.message_expiry,
             command.compression_algorithm,
+            command.compression_level,
             command.max_topic_size,
             command.replication_factor,
+            command.cleanup_policy,
         )
         .await
         .with_error_context(|error| {
@@ -150,8 +157,10 @@ async fn update_topic(
                 &command.name,
                 command.message_expiry,
                 command.compression_algorithm,
+                command.compression_level,
                 command.max_topic_size,
                 command.replication_factor,
+                command.cleanup_policy,
             )
             .await
             .with_error_context(|error| {
@@ -260,3 +269,44 @@ async fn purge_topic(
         })?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[instrument(skip_all, name = "trace_set_topic_retention", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id, iggy_topic_id = topic_id))]
+async fn set_topic_retention(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id)): Path<(String, String)>,
+    Json(mut command): Json<SetTopicRetention>,
+) -> Result<StatusCode, CustomError> {
+    command.stream_id = Identifier::from_str_value(&stream_id)?;
+    command.topic_id = Identifier::from_str_value(&topic_id)?;
+    command.validate()?;
+
+    let mut system = state.system.write().await;
+    system
+        .set_topic_retention(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.stream_id,
+            &command.topic_id,
+            command.retention.clone(),
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to set topic retention, stream ID: {}, topic ID: {}",
+                stream_id, topic_id
+            )
+        })?;
+
+    let system = system.downgrade();
+    system
+        .state
+        .apply(identity.user_id, EntryCommand::SetTopicRetention(command))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply set topic retention, stream ID: {}, topic ID: {}",
+                stream_id, topic_id
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,87 @@
+use crate::http::error::CustomError;
+use crate::http::jwt::json_web_token::Identity;
+use crate::http::shared::AppState;
+use crate::http::COMPONENT;
+use crate::streaming::session::Session;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, post};
+use axum::{Extension, Json, Router};
+use error_set::ErrContext;
+use iggy::transactions::commit_transaction::CommitTransaction;
+use iggy::validatable::Validatable;
+use std::sync::Arc;
+
+pub fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/transactions", post(begin_transaction))
+        .route(
+            "/transactions/{transaction_id}/commit",
+            post(commit_transaction),
+        )
+        .route("/transactions/{transaction_id}", delete(abort_transaction))
+        .with_state(state)
+}
+
+async fn begin_transaction(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<u64>, CustomError> {
+    let system = state.system.read().await;
+    let transaction_id = system
+        .begin_transaction(&Session::stateless(identity.user_id, identity.ip_address))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to begin transaction, user ID: {}",
+                identity.user_id
+            )
+        })?;
+    Ok(Json(transaction_id))
+}
+
+async fn commit_transaction(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(transaction_id): Path<u64>,
+    mut command: Json<CommitTransaction>,
+) -> Result<StatusCode, CustomError> {
+    command.transaction_id = transaction_id;
+    command.validate()?;
+    let system = state.system.read().await;
+    system
+        .commit_transaction(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            transaction_id,
+            command.0.offsets,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to commit transaction with ID: {transaction_id}, user ID: {}",
+                identity.user_id
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn abort_transaction(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(transaction_id): Path<u64>,
+) -> Result<StatusCode, CustomError> {
+    let system = state.system.read().await;
+    system
+        .abort_transaction(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            transaction_id,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to abort transaction with ID: {transaction_id}, user ID: {}",
+                identity.user_id
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
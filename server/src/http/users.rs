@@ -19,6 +19,8 @@ use iggy::users::change_password::ChangePassword;
 use iggy::users::create_user::CreateUser;
 use iggy::users::delete_user::DeleteUser;
 use iggy::users::login_user::LoginUser;
+use iggy::users::login_with_token::LoginWithToken;
+use iggy::users::set_user_quota::SetUserQuota;
 use iggy::users::update_permissions::UpdatePermissions;
 use iggy::users::update_user::UpdateUser;
 use iggy::validatable::Validatable;
@@ -35,7 +37,9 @@ pub fn router(state: Arc<AppState>) -> Router {
         )
         .route("/users/{user_id}/permissions", put(update_permissions))
         .route("/users/{user_id}/password", put(change_password))
+        .route("/users/{user_id}/quota", put(set_user_quota))
         .route("/users/login", post(login_user))
+        .route("/users/login/token", post(login_with_token))
         .route("/users/logout", delete(logout_user))
         .route("/users/refresh-token", post(refresh_token))
         .with_state(state)
@@ -209,6 +213,45 @@ async fn update_permissions(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[instrument(skip_all, name = "trace_set_user_quota", fields(iggy_user_id = identity.user_id, iggy_updated_user_id = user_id))]
+async fn set_user_quota(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(user_id): Path<String>,
+    Json(mut command): Json<SetUserQuota>,
+) -> Result<StatusCode, CustomError> {
+    command.user_id = Identifier::from_str_value(&user_id)?;
+    command.validate()?;
+
+    let mut system = state.system.write().await;
+    system
+        .set_user_quota(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.user_id,
+            command.quota,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to set user quota, user ID: {}",
+                user_id
+            )
+        })?;
+
+    let system = system.downgrade();
+    system
+        .state
+        .apply(identity.user_id, EntryCommand::SetUserQuota(command))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to apply set user quota, user ID: {}",
+                user_id
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[instrument(skip_all, name = "trace_change_password", fields(iggy_user_id = identity.user_id, iggy_updated_user_id = user_id))]
 async fn change_password(
     State(state): State<Arc<AppState>>,
@@ -312,6 +355,30 @@ async fn login_user(
     Ok(Json(map_generated_access_token_to_identity_info(tokens)))
 }
 
+#[instrument(skip_all, name = "trace_login_with_token")]
+async fn login_with_token(
+    State(state): State<Arc<AppState>>,
+    Json(command): Json<LoginWithToken>,
+) -> Result<Json<IdentityInfo>, CustomError> {
+    command.validate()?;
+    let authenticator = state.system.read().await.get_oidc_authenticator()?;
+    let identity = authenticator
+        .verify(&command.token)
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to verify token")
+        })?;
+    let mut system = state.system.write().await;
+    let user = system
+        .login_with_oidc_identity(&authenticator, &identity, None)
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to login with token")
+        })?;
+    let tokens = state.jwt_manager.generate(user.id)?;
+    Ok(Json(map_generated_access_token_to_identity_info(tokens)))
+}
+
 #[instrument(skip_all, name = "trace_logout_user", fields(iggy_user_id = identity.user_id))]
 async fn logout_user(
     State(state): State<Arc<AppState>>,
@@ -327,6 +394,11 @@ async fn logout_user(
                 identity.user_id
             )
         })?;
+    // The client authenticated using the certificate has no token to revoke.
+    if identity.token_id.is_empty() {
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
     state
         .jwt_manager
         .revoke_token(&identity.token_id, identity.token_expiry)
@@ -12,16 +12,19 @@ pub mod archiver;
 pub mod args;
 pub mod binary;
 pub mod channels;
+pub mod cluster;
 mod command;
-pub(crate) mod compat;
+pub mod compat;
 pub mod configs;
 pub mod http;
 pub mod log;
+pub mod oidc;
 pub mod quic;
 pub mod server_error;
 pub mod state;
 pub mod streaming;
 pub mod tcp;
+pub mod tls;
 pub mod versioning;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -3,13 +3,17 @@ use clap::Parser;
 use dotenvy::dotenv;
 use figlet_rs::FIGfont;
 use server::args::Args;
+use server::channels::commands::abort_expired_transactions::AbortExpiredTransactionsExecutor;
 use server::channels::commands::archive_state::ArchiveStateExecutor;
 use server::channels::commands::clean_personal_access_tokens::CleanPersonalAccessTokensExecutor;
+use server::channels::commands::compact_messages::CompactMessagesExecutor;
 use server::channels::commands::maintain_messages::MaintainMessagesExecutor;
 use server::channels::commands::print_sysinfo::SysInfoPrintExecutor;
 use server::channels::commands::save_messages::SaveMessagesExecutor;
+use server::channels::commands::snapshot_state::SnapshotStateExecutor;
 use server::channels::commands::verify_heartbeats::VerifyHeartbeatsExecutor;
 use server::channels::handler::ServerCommandHandler;
+use server::cluster::cluster_server;
 use server::configs::config_provider;
 use server::configs::server::ServerConfig;
 use server::http::http_server;
@@ -17,10 +21,12 @@ use server::http::http_server;
 use server::log::logger::Logging;
 #[cfg(feature = "tokio-console")]
 use server::log::tokio_console::Logging;
+use server::oidc::authenticator::OidcAuthenticator;
 use server::quic::quic_server;
 use server::server_error::ServerError;
 use server::streaming::systems::system::{SharedSystem, System};
 use server::tcp::tcp_server;
+use std::sync::Arc;
 use tokio::time::Instant;
 use tracing::{info, instrument};
 
@@ -82,13 +88,23 @@ async fn main() -> Result<(), ServerError> {
     // loads streams and starts accepting connections. This is necessary to
     // have the correct statistics when the server starts.
     system.write().await.get_stats().await?;
+    if config.cluster.enabled {
+        system.write().await.init_cluster(&config.cluster)?;
+    }
     system.write().await.init().await?;
+    if config.oidc.enabled {
+        let authenticator = OidcAuthenticator::load(config.oidc.clone()).await?;
+        system.write().await.oidc = Some(Arc::new(authenticator));
+    }
 
     let _command_handler = ServerCommandHandler::new(system.clone(), &config)
         .install_handler(SaveMessagesExecutor)
         .install_handler(MaintainMessagesExecutor)
+        .install_handler(CompactMessagesExecutor)
         .install_handler(ArchiveStateExecutor)
+        .install_handler(SnapshotStateExecutor)
         .install_handler(CleanPersonalAccessTokensExecutor)
+        .install_handler(AbortExpiredTransactionsExecutor)
         .install_handler(SysInfoPrintExecutor)
         .install_handler(VerifyHeartbeatsExecutor);
 
@@ -103,18 +119,24 @@ async fn main() -> Result<(), ServerError> {
 
     let mut current_config = config.clone();
 
+    if config.cluster.enabled {
+        let cluster_addr = cluster_server::start(config.cluster, system.clone()).await;
+        current_config.cluster.node.address = cluster_addr.to_string();
+    }
+
     if config.http.enabled {
-        let http_addr = http_server::start(config.http, system.clone()).await;
+        let http_addr =
+            http_server::start(config.http, config.client_auth.clone(), system.clone()).await;
         current_config.http.address = http_addr.to_string();
     }
 
     if config.quic.enabled {
-        let quic_addr = quic_server::start(config.quic, system.clone());
+        let quic_addr = quic_server::start(config.quic, config.client_auth.clone(), system.clone());
         current_config.quic.address = quic_addr.to_string();
     }
 
     if config.tcp.enabled {
-        let tcp_addr = tcp_server::start(config.tcp, system.clone()).await;
+        let tcp_addr = tcp_server::start(config.tcp, config.client_auth, system.clone()).await;
         current_config.tcp.address = tcp_addr.to_string();
     }
 
@@ -0,0 +1,415 @@
+use crate::configs::oidc::OidcConfig;
+use crate::oidc::COMPONENT;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::utils::timestamp::IggyTimestamp;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde_json::{Map, Value};
+use std::str::FromStr;
+use tracing::{error, info, warn};
+
+/// The algorithms of the asymmetric keys, which can be published by the identity provider.
+const ALLOWED_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// The identity of the token subject, read from the claims of the verified token.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OidcIdentity {
+    pub subject: Option<String>,
+    pub groups: Vec<String>,
+}
+
+#[derive(Debug)]
+struct Keys {
+    set: JwkSet,
+    loaded_at: IggyTimestamp,
+}
+
+/// Verifies the tokens issued by the external identity provider, using its public keys (JWKS).
+#[derive(Debug)]
+pub struct OidcAuthenticator {
+    config: OidcConfig,
+    keys: IggySharedMut<Keys>,
+}
+
+impl OidcAuthenticator {
+    pub async fn load(config: OidcConfig) -> Result<Self, IggyError> {
+        let set = Self::load_keys(&config).await?;
+        info!(
+            "Loaded {} OIDC key(s) of issuer: {} from: {}",
+            set.keys.len(),
+            config.issuer,
+            config.jwks
+        );
+        Ok(Self::new(config, set))
+    }
+
+    pub fn new(config: OidcConfig, set: JwkSet) -> Self {
+        Self {
+            config,
+            keys: IggySharedMut::new(Keys {
+                set,
+                loaded_at: IggyTimestamp::now(),
+            }),
+        }
+    }
+
+    /// Verifies the signature, issuer, audience and expiry of the token, and returns the identity of its subject.
+    pub async fn verify(&self, token: &str) -> Result<OidcIdentity, IggyError> {
+        let header = decode_header(token).map_err(|error| {
+            warn!("Invalid OIDC token header: {error}");
+            IggyError::InvalidAccessToken
+        })?;
+        if !ALLOWED_ALGORITHMS.contains(&header.alg) {
+            warn!("Unsupported OIDC token algorithm: {:?}", header.alg);
+            return Err(IggyError::InvalidAccessToken);
+        }
+
+        let jwk = self.get_key(header.kid.as_deref()).await?;
+        if let Some(key_algorithm) = jwk.common.key_algorithm {
+            if Algorithm::from_str(&key_algorithm.to_string()).ok() != Some(header.alg) {
+                warn!(
+                    "OIDC token algorithm: {:?} doesn't match the key algorithm: {key_algorithm}",
+                    header.alg
+                );
+                return Err(IggyError::InvalidAccessToken);
+            }
+        }
+
+        let key = DecodingKey::from_jwk(&jwk).map_err(|error| {
+            error!("Invalid OIDC key: {:?}, error: {error}", jwk.common.key_id);
+            IggyError::InvalidAccessToken
+        })?;
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.config.issuer]);
+        validation.set_audience(&[&self.config.audience]);
+        validation.leeway = self.config.clock_skew.as_secs() as u64;
+        let claims = decode::<Map<String, Value>>(token, &key, &validation)
+            .map_err(|error| {
+                warn!("Invalid OIDC token: {error}");
+                IggyError::InvalidAccessToken
+            })?
+            .claims;
+
+        Ok(OidcIdentity {
+            subject: get_string_claim(&claims, "sub"),
+            groups: get_string_claims(&claims, &self.config.groups_claim),
+        })
+    }
+
+    /// Returns the username explicitly mapped to the token subject, or to its first configured group, if any.
+    pub fn get_username(&self, identity: &OidcIdentity) -> Option<&str> {
+        self.config
+            .subjects
+            .iter()
+            .find(|subject| identity.subject.as_ref() == Some(&subject.value))
+            .or_else(|| {
+                self.config
+                    .groups
+                    .iter()
+                    .find(|group| identity.groups.contains(&group.value))
+            })
+            .map(|mapping| mapping.name.as_str())
+    }
+
+    /// Returns the names of the roles mapped to any of the groups of the token subject.
+    pub fn get_role_names(&self, identity: &OidcIdentity) -> Vec<&str> {
+        self.config
+            .group_roles
+            .iter()
+            .filter(|group_role| identity.groups.contains(&group_role.value))
+            .map(|group_role| group_role.name.as_str())
+            .collect()
+    }
+
+    /// Returns the key with the provided ID (or the only key, if the token has no ID),
+    /// reloading the keys from the URL once the interval has elapsed, as the identity provider might have rotated them.
+    async fn get_key(&self, kid: Option<&str>) -> Result<Jwk, IggyError> {
+        if let Some(jwk) = find_key(&self.keys.read().await.set, kid) {
+            return Ok(jwk);
+        }
+
+        if !self.config.is_jwks_url() {
+            warn!("OIDC key: {kid:?} was not found.");
+            return Err(IggyError::InvalidAccessToken);
+        }
+
+        let mut keys = self.keys.write().await;
+        let now = IggyTimestamp::now();
+        if now.as_micros() - keys.loaded_at.as_micros()
+            >= self.config.jwks_refresh_interval.as_micros()
+        {
+            info!("OIDC key: {kid:?} was not found, reloading the keys...");
+            keys.loaded_at = now;
+            keys.set = Self::load_keys(&self.config)
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to reload the keys")
+                })?;
+        }
+
+        find_key(&keys.set, kid).ok_or_else(|| {
+            warn!("OIDC key: {kid:?} was not found.");
+            IggyError::InvalidAccessToken
+        })
+    }
+
+    async fn load_keys(config: &OidcConfig) -> Result<JwkSet, IggyError> {
+        let jwks = if config.is_jwks_url() {
+            let client = reqwest::Client::builder()
+                .timeout(config.jwks_timeout.get_duration())
+                .build()
+                .map_err(|error| {
+                    error!("Cannot create OIDC keys client, error: {error}");
+                    IggyError::CannotLoadJwks(config.jwks.clone())
+                })?;
+            let response = client
+                .get(&config.jwks)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|error| {
+                    error!(
+                        "Cannot fetch OIDC keys from: {}, error: {error}",
+                        config.jwks
+                    );
+                    IggyError::CannotLoadJwks(config.jwks.clone())
+                })?;
+            response.text().await.map_err(|error| {
+                error!(
+                    "Cannot read OIDC keys from: {}, error: {error}",
+                    config.jwks
+                );
+                IggyError::CannotLoadJwks(config.jwks.clone())
+            })?
+        } else {
+            tokio::fs::read_to_string(&config.jwks)
+                .await
+                .map_err(|error| {
+                    error!(
+                        "Cannot read OIDC keys file: {}, error: {error}",
+                        config.jwks
+                    );
+                    IggyError::CannotLoadJwks(config.jwks.clone())
+                })?
+        };
+
+        serde_json::from_str::<JwkSet>(&jwks).map_err(|error| {
+            error!("Invalid OIDC keys from: {}, error: {error}", config.jwks);
+            IggyError::CannotLoadJwks(config.jwks.clone())
+        })
+    }
+}
+
+fn find_key(set: &JwkSet, kid: Option<&str>) -> Option<Jwk> {
+    match kid {
+        Some(kid) => set.find(kid).cloned(),
+        None if set.keys.len() == 1 => set.keys.first().cloned(),
+        None => None,
+    }
+}
+
+fn get_string_claim(claims: &Map<String, Value>, name: &str) -> Option<String> {
+    claims
+        .get(name)
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_owned())
+}
+
+/// Returns the values of the claim, which can be either the array of strings or the single string.
+fn get_string_claims(claims: &Map<String, Value>, name: &str) -> Vec<String> {
+    match claims.get(name) {
+        Some(Value::Array(values)) => values
+            .iter()
+            .filter_map(|value| value.as_str())
+            .map(|value| value.to_owned())
+            .collect(),
+        Some(Value::String(value)) => vec![value.to_owned()],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::oidc::OidcMappingConfig;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+    use serde_json::json;
+
+    const ISSUER: &str = "https://sso.iggy.local";
+    const AUDIENCE: &str = "iggy";
+    const KEY_ID: &str = "key-1";
+
+    struct TestKey {
+        encoding_key: EncodingKey,
+        jwk: Value,
+    }
+
+    fn create_key(kid: &str) -> TestKey {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let key_pair =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+                .unwrap();
+        let public_key = key_pair.public_key().as_ref();
+        TestKey {
+            encoding_key: EncodingKey::from_ec_der(pkcs8.as_ref()),
+            jwk: json!({
+                "kty": "EC",
+                "crv": "P-256",
+                "use": "sig",
+                "alg": "ES256",
+                "kid": kid,
+                "x": URL_SAFE_NO_PAD.encode(&public_key[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&public_key[33..65]),
+            }),
+        }
+    }
+
+    fn create_authenticator(key: &TestKey) -> OidcAuthenticator {
+        let config = OidcConfig {
+            enabled: true,
+            issuer: ISSUER.to_owned(),
+            audience: AUDIENCE.to_owned(),
+            subjects: vec![OidcMappingConfig::from_str("f1b2c3=orders").unwrap()],
+            groups: vec![OidcMappingConfig::from_str("iggy-admins=admin").unwrap()],
+            group_roles: vec![
+                OidcMappingConfig::from_str("analytics=readers").unwrap(),
+                OidcMappingConfig::from_str("iggy-admins=administrators").unwrap(),
+                OidcMappingConfig::from_str("analytics=auditors").unwrap(),
+            ],
+            ..OidcConfig::default()
+        };
+        let set = serde_json::from_value(json!({ "keys": [key.jwk] })).unwrap();
+        OidcAuthenticator::new(config, set)
+    }
+
+    fn create_token(key: &TestKey, kid: &str, claims: Value) -> String {
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(kid.to_owned());
+        encode(&header, &claims, &key.encoding_key).unwrap()
+    }
+
+    fn create_claims(issuer: &str, audience: &str) -> Value {
+        json!({
+            "iss": issuer,
+            "aud": audience,
+            "sub": "f1b2c3",
+            "exp": IggyTimestamp::now().to_secs() + 60,
+            "preferred_username": "orders",
+            "groups": ["analytics", "iggy-admins"],
+        })
+    }
+
+    #[tokio::test]
+    async fn identity_should_be_read_from_valid_token() {
+        let key = create_key(KEY_ID);
+        let authenticator = create_authenticator(&key);
+        let token = create_token(&key, KEY_ID, create_claims(ISSUER, AUDIENCE));
+
+        let identity = authenticator.verify(&token).await.unwrap();
+
+        assert_eq!(identity.subject.as_deref(), Some("f1b2c3"));
+        assert_eq!(identity.groups, vec!["analytics", "iggy-admins"]);
+        assert_eq!(authenticator.get_username(&identity), Some("orders"));
+    }
+
+    #[tokio::test]
+    async fn username_should_be_mapped_only_explicitly() {
+        let key = create_key(KEY_ID);
+        let authenticator = create_authenticator(&key);
+        let mut claims = create_claims(ISSUER, AUDIENCE);
+        claims["sub"] = json!("a4d5e6");
+        claims["preferred_username"] = json!("iggy");
+
+        let token = create_token(&key, KEY_ID, claims.clone());
+        let identity = authenticator.verify(&token).await.unwrap();
+        assert_eq!(authenticator.get_username(&identity), Some("admin"));
+
+        claims["groups"] = json!(["analytics"]);
+        let token = create_token(&key, KEY_ID, claims);
+        let identity = authenticator.verify(&token).await.unwrap();
+        assert_eq!(authenticator.get_username(&identity), None);
+    }
+
+    #[tokio::test]
+    async fn roles_should_be_mapped_from_token_groups() {
+        let key = create_key(KEY_ID);
+        let authenticator = create_authenticator(&key);
+        let mut claims = create_claims(ISSUER, AUDIENCE);
+
+        let token = create_token(&key, KEY_ID, claims.clone());
+        let identity = authenticator.verify(&token).await.unwrap();
+        assert_eq!(
+            authenticator.get_role_names(&identity),
+            vec!["readers", "administrators", "auditors"]
+        );
+
+        claims["groups"] = json!("analytics");
+        let token = create_token(&key, KEY_ID, claims.clone());
+        let identity = authenticator.verify(&token).await.unwrap();
+        assert_eq!(
+            authenticator.get_role_names(&identity),
+            vec!["readers", "auditors"]
+        );
+
+        claims["groups"] = json!(["other"]);
+        let token = create_token(&key, KEY_ID, claims);
+        let identity = authenticator.verify(&token).await.unwrap();
+        assert!(authenticator.get_role_names(&identity).is_empty());
+    }
+
+    #[tokio::test]
+    async fn token_of_other_issuer_or_audience_should_be_rejected() {
+        let key = create_key(KEY_ID);
+        let authenticator = create_authenticator(&key);
+
+        for claims in [
+            create_claims("https://other.iggy.local", AUDIENCE),
+            create_claims(ISSUER, "other"),
+        ] {
+            let token = create_token(&key, KEY_ID, claims);
+            assert!(authenticator.verify(&token).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn expired_token_should_be_rejected() {
+        let key = create_key(KEY_ID);
+        let authenticator = create_authenticator(&key);
+        let mut claims = create_claims(ISSUER, AUDIENCE);
+        claims["exp"] = json!(IggyTimestamp::now().to_secs() - 3600);
+        let token = create_token(&key, KEY_ID, claims);
+
+        assert!(authenticator.verify(&token).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn token_signed_by_unknown_key_should_be_rejected() {
+        let key = create_key(KEY_ID);
+        let authenticator = create_authenticator(&key);
+        let other_key = create_key(KEY_ID);
+
+        for token in [
+            create_token(&other_key, KEY_ID, create_claims(ISSUER, AUDIENCE)),
+            create_token(&key, "key-2", create_claims(ISSUER, AUDIENCE)),
+        ] {
+            assert!(authenticator.verify(&token).await.is_err());
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod authenticator;
+
+pub const COMPONENT: &str = "OIDC";
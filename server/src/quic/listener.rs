@@ -5,19 +5,27 @@ use crate::server_error::ConnectionError;
 use crate::streaming::clients::client_manager::Transport;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
+use crate::tls::client_certificate::ClientCertificateAuthenticator;
 use anyhow::{anyhow, Context};
 use bytes::Bytes;
 use iggy::validatable::Validatable;
 use iggy::{bytes_serializable::BytesSerializable, messages::MAX_PAYLOAD_SIZE};
 use quinn::{Connection, Endpoint, RecvStream, SendStream};
+use rustls::pki_types::CertificateDer;
+use std::sync::Arc;
 use tracing::{debug, error, info};
 
 const LISTENERS_COUNT: u32 = 10;
 const INITIAL_BYTES_LENGTH: usize = 4;
 
-pub fn start(endpoint: Endpoint, system: SharedSystem) {
+pub fn start(
+    endpoint: Endpoint,
+    authenticator: Arc<ClientCertificateAuthenticator>,
+    system: SharedSystem,
+) {
     for _ in 0..LISTENERS_COUNT {
         let endpoint = endpoint.clone();
+        let authenticator = authenticator.clone();
         let system = system.clone();
         tokio::spawn(async move {
             while let Some(incoming_connection) = endpoint.accept().await {
@@ -25,6 +33,7 @@ pub fn start(endpoint: Endpoint, system: SharedSystem) {
                     "Incoming connection from client: {}",
                     incoming_connection.remote_address()
                 );
+                let authenticator = authenticator.clone();
                 let system = system.clone();
                 let incoming_connection = incoming_connection.accept();
                 if incoming_connection.is_err() {
@@ -36,7 +45,9 @@ pub fn start(endpoint: Endpoint, system: SharedSystem) {
                 }
                 let incoming_connection = incoming_connection.unwrap();
                 tokio::spawn(async move {
-                    if let Err(error) = handle_connection(incoming_connection, system).await {
+                    if let Err(error) =
+                        handle_connection(incoming_connection, &authenticator, system).await
+                    {
                         error!("Connection has failed: {error}");
                     }
                 });
@@ -47,6 +58,7 @@ pub fn start(endpoint: Endpoint, system: SharedSystem) {
 
 async fn handle_connection(
     incoming_connection: quinn::Connecting,
+    authenticator: &ClientCertificateAuthenticator,
     system: SharedSystem,
 ) -> Result<(), ConnectionError> {
     let connection = incoming_connection.await?;
@@ -58,6 +70,17 @@ async fn handle_connection(
         .add_client(&address, Transport::Quic)
         .await;
 
+    let certificate_chain = connection
+        .peer_identity()
+        .and_then(|identity| identity.downcast::<Vec<CertificateDer<'static>>>().ok());
+    authenticator
+        .authenticate(
+            certificate_chain.as_deref().map(Vec::as_slice),
+            &system,
+            &session,
+        )
+        .await;
+
     let client_id = session.client_id;
     while let Some(stream) = accept_stream(&connection, &system, client_id).await? {
         let system = system.clone();
@@ -5,44 +5,59 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use error_set::ErrContext;
+use quinn::crypto::rustls::QuicServerConfig;
 use quinn::{Endpoint, IdleTimeout, VarInt};
 use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 use tracing::info;
 
+use crate::configs::client_auth::ClientAuthConfig;
 use crate::configs::quic::QuicConfig;
 use crate::quic::listener;
 use crate::quic::COMPONENT;
 use crate::server_error::QuicError;
 use crate::streaming::systems::system::SharedSystem;
+use crate::tls::client_certificate::ClientCertificateAuthenticator;
+use crate::tls::server_config;
 
 /// Starts the QUIC server.
 /// Returns the address the server is listening on.
-pub fn start(config: QuicConfig, system: SharedSystem) -> SocketAddr {
+pub fn start(
+    config: QuicConfig,
+    client_auth: ClientAuthConfig,
+    system: SharedSystem,
+) -> SocketAddr {
     info!("Initializing Iggy QUIC server...");
     let address = config.address.parse().unwrap();
-    let quic_config = configure_quic(config);
+    let quic_config = configure_quic(config, &client_auth);
     if let Err(error) = quic_config {
         panic!("Error when configuring QUIC: {:?}", error);
     }
 
     let endpoint = Endpoint::server(quic_config.unwrap(), address).unwrap();
     let addr = endpoint.local_addr().unwrap();
-    listener::start(endpoint, system);
+    let authenticator = Arc::new(ClientCertificateAuthenticator::new(client_auth.users));
+    listener::start(endpoint, authenticator, system);
     info!("Iggy QUIC server has started on: {:?}", addr);
     addr
 }
 
-fn configure_quic(config: QuicConfig) -> Result<quinn::ServerConfig, QuicError> {
+fn configure_quic(
+    config: QuicConfig,
+    client_auth: &ClientAuthConfig,
+) -> Result<quinn::ServerConfig, QuicError> {
     let (certificate, key) = match config.certificate.self_signed {
         true => generate_self_signed_cert()?,
         false => load_certificates(&config.certificate.cert_file, &config.certificate.key_file)?,
     };
 
-    let mut server_config = quinn::ServerConfig::with_single_cert(certificate, key)
-        .with_error_context(|error| {
-            format!("{COMPONENT} (error: {error}) - failed to create server config")
-        })
-        .map_err(|_| QuicError::ConfigCreationError)?;
+    let mut server_config = match config.certificate.client_auth {
+        true => configure_client_auth(certificate, key, client_auth)?,
+        false => quinn::ServerConfig::with_single_cert(certificate, key)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to create server config")
+            })
+            .map_err(|_| QuicError::ConfigCreationError)?,
+    };
     let mut transport = quinn::TransportConfig::default();
     transport.initial_mtu(config.initial_mtu.as_bytes_u64() as u16);
     transport.send_window(config.send_window.as_bytes_u64());
@@ -77,6 +92,24 @@ fn configure_quic(config: QuicConfig) -> Result<quinn::ServerConfig, QuicError>
     Ok(server_config)
 }
 
+fn configure_client_auth(
+    certificate: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+    client_auth: &ClientAuthConfig,
+) -> Result<quinn::ServerConfig, QuicError> {
+    let tls_config = server_config::create_server_config(certificate, key, Some(client_auth))
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to create TLS config with client auth")
+        })
+        .map_err(|_| QuicError::ConfigCreationError)?;
+    let crypto = QuicServerConfig::try_from(tls_config)
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to create QUIC crypto config")
+        })
+        .map_err(|_| QuicError::ConfigCreationError)?;
+    Ok(quinn::ServerConfig::with_crypto(Arc::new(crypto)))
+}
+
 fn generate_self_signed_cert<'a>() -> Result<(Vec<CertificateDer<'a>>, PrivateKeyDer<'a>), QuicError>
 {
     let certificate = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
@@ -43,6 +43,15 @@ error_set!(
 
         #[display("Cannot archive file: {}", file_path)]
         CannotArchiveFile { file_path: String },
+
+        #[display("Archived file not found: {}", file_path)]
+        ArchivedFileNotFound { file_path: String },
+
+        #[display("Cannot fetch archived file: {}", file_path)]
+        CannotFetchFile { file_path: String },
+
+        #[display("Cannot list archived files under path: {}", path)]
+        CannotListFiles { path: String },
     } || IoError;
 
     ConnectionError = {
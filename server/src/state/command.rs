@@ -2,11 +2,14 @@ use crate::state::models::CreatePersonalAccessTokenWithHash;
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::command::{
-    Command, CHANGE_PASSWORD_CODE, CREATE_CONSUMER_GROUP_CODE, CREATE_PARTITIONS_CODE,
-    CREATE_PERSONAL_ACCESS_TOKEN_CODE, CREATE_STREAM_CODE, CREATE_TOPIC_CODE, CREATE_USER_CODE,
-    DELETE_CONSUMER_GROUP_CODE, DELETE_PARTITIONS_CODE, DELETE_PERSONAL_ACCESS_TOKEN_CODE,
+    Command, ASSIGN_ROLE_CODE, CHANGE_PASSWORD_CODE, CREATE_CONSUMER_GROUP_CODE,
+    CREATE_PARTITIONS_CODE, CREATE_PERSONAL_ACCESS_TOKEN_CODE, CREATE_ROLE_CODE,
+    CREATE_STREAM_CODE, CREATE_TOPIC_CODE, CREATE_USER_CODE, DELETE_CONSUMER_GROUP_CODE,
+    DELETE_PARTITIONS_CODE, DELETE_PERSONAL_ACCESS_TOKEN_CODE, DELETE_ROLE_CODE,
     DELETE_STREAM_CODE, DELETE_TOPIC_CODE, DELETE_USER_CODE, PURGE_STREAM_CODE, PURGE_TOPIC_CODE,
-    UPDATE_PERMISSIONS_CODE, UPDATE_STREAM_CODE, UPDATE_TOPIC_CODE, UPDATE_USER_CODE,
+    SET_STREAM_QUOTA_CODE, SET_TOPIC_RETENTION_CODE, SET_USER_QUOTA_CODE, UNASSIGN_ROLE_CODE,
+    UPDATE_PERMISSIONS_CODE, UPDATE_ROLE_CODE, UPDATE_STREAM_CODE, UPDATE_TOPIC_CODE,
+    UPDATE_USER_CODE,
 };
 use iggy::consumer_groups::create_consumer_group::CreateConsumerGroup;
 use iggy::consumer_groups::delete_consumer_group::DeleteConsumerGroup;
@@ -14,31 +17,46 @@ use iggy::error::IggyError;
 use iggy::partitions::create_partitions::CreatePartitions;
 use iggy::partitions::delete_partitions::DeletePartitions;
 use iggy::personal_access_tokens::delete_personal_access_token::DeletePersonalAccessToken;
+use iggy::roles::assign_role::AssignRole;
+use iggy::roles::create_role::CreateRole;
+use iggy::roles::delete_role::DeleteRole;
+use iggy::roles::unassign_role::UnassignRole;
+use iggy::roles::update_role::UpdateRole;
 use iggy::streams::create_stream::CreateStream;
 use iggy::streams::delete_stream::DeleteStream;
 use iggy::streams::purge_stream::PurgeStream;
+use iggy::streams::set_stream_quota::SetStreamQuota;
 use iggy::streams::update_stream::UpdateStream;
 use iggy::topics::create_topic::CreateTopic;
 use iggy::topics::delete_topic::DeleteTopic;
 use iggy::topics::purge_topic::PurgeTopic;
+use iggy::topics::set_topic_retention::SetTopicRetention;
 use iggy::topics::update_topic::UpdateTopic;
 use iggy::users::change_password::ChangePassword;
 use iggy::users::create_user::CreateUser;
 use iggy::users::delete_user::DeleteUser;
+use iggy::users::set_user_quota::SetUserQuota;
 use iggy::users::update_permissions::UpdatePermissions;
 use iggy::users::update_user::UpdateUser;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, PartialEq)]
+/// The code of the entry without any command, it's not used by any of the client commands.
+pub const NOOP_CODE: u32 = 0;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "command", rename_all = "snake_case")]
 pub enum EntryCommand {
     CreateStream(CreateStream),
     UpdateStream(UpdateStream),
     DeleteStream(DeleteStream),
     PurgeStream(PurgeStream),
+    SetStreamQuota(SetStreamQuota),
     CreateTopic(CreateTopic),
     UpdateTopic(UpdateTopic),
     DeleteTopic(DeleteTopic),
     PurgeTopic(PurgeTopic),
+    SetTopicRetention(SetTopicRetention),
     CreatePartitions(CreatePartitions),
     DeletePartitions(DeletePartitions),
     CreateConsumerGroup(CreateConsumerGroup),
@@ -48,8 +66,16 @@ pub enum EntryCommand {
     DeleteUser(DeleteUser),
     ChangePassword(ChangePassword),
     UpdatePermissions(UpdatePermissions),
+    SetUserQuota(SetUserQuota),
+    CreateRole(CreateRole),
+    UpdateRole(UpdateRole),
+    DeleteRole(DeleteRole),
+    AssignRole(AssignRole),
+    UnassignRole(UnassignRole),
     CreatePersonalAccessToken(CreatePersonalAccessTokenWithHash),
     DeletePersonalAccessToken(DeletePersonalAccessToken),
+    /// Appended by the newly elected leader, so the entries of the previous terms are committed along with it.
+    Noop,
 }
 
 impl BytesSerializable for EntryCommand {
@@ -59,10 +85,12 @@ impl BytesSerializable for EntryCommand {
             EntryCommand::UpdateStream(command) => (command.code(), command.to_bytes()),
             EntryCommand::DeleteStream(command) => (command.code(), command.to_bytes()),
             EntryCommand::PurgeStream(command) => (command.code(), command.to_bytes()),
+            EntryCommand::SetStreamQuota(command) => (command.code(), command.to_bytes()),
             EntryCommand::CreateTopic(command) => (command.code(), command.to_bytes()),
             EntryCommand::UpdateTopic(command) => (command.code(), command.to_bytes()),
             EntryCommand::DeleteTopic(command) => (command.code(), command.to_bytes()),
             EntryCommand::PurgeTopic(command) => (command.code(), command.to_bytes()),
+            EntryCommand::SetTopicRetention(command) => (command.code(), command.to_bytes()),
             EntryCommand::CreatePartitions(command) => (command.code(), command.to_bytes()),
             EntryCommand::DeletePartitions(command) => (command.code(), command.to_bytes()),
             EntryCommand::CreateConsumerGroup(command) => (command.code(), command.to_bytes()),
@@ -72,12 +100,19 @@ impl BytesSerializable for EntryCommand {
             EntryCommand::DeleteUser(command) => (command.code(), command.to_bytes()),
             EntryCommand::ChangePassword(command) => (command.code(), command.to_bytes()),
             EntryCommand::UpdatePermissions(command) => (command.code(), command.to_bytes()),
+            EntryCommand::SetUserQuota(command) => (command.code(), command.to_bytes()),
+            EntryCommand::CreateRole(command) => (command.code(), command.to_bytes()),
+            EntryCommand::UpdateRole(command) => (command.code(), command.to_bytes()),
+            EntryCommand::DeleteRole(command) => (command.code(), command.to_bytes()),
+            EntryCommand::AssignRole(command) => (command.code(), command.to_bytes()),
+            EntryCommand::UnassignRole(command) => (command.code(), command.to_bytes()),
             EntryCommand::CreatePersonalAccessToken(command) => {
                 (command.code(), command.to_bytes())
             }
             EntryCommand::DeletePersonalAccessToken(command) => {
                 (command.code(), command.to_bytes())
             }
+            EntryCommand::Noop => (NOOP_CODE, Bytes::new()),
         };
 
         let mut bytes = BytesMut::with_capacity(4 + 4 + command.len());
@@ -105,10 +140,16 @@ impl BytesSerializable for EntryCommand {
                 payload,
             )?)),
             PURGE_STREAM_CODE => Ok(EntryCommand::PurgeStream(PurgeStream::from_bytes(payload)?)),
+            SET_STREAM_QUOTA_CODE => Ok(EntryCommand::SetStreamQuota(SetStreamQuota::from_bytes(
+                payload,
+            )?)),
             CREATE_TOPIC_CODE => Ok(EntryCommand::CreateTopic(CreateTopic::from_bytes(payload)?)),
             UPDATE_TOPIC_CODE => Ok(EntryCommand::UpdateTopic(UpdateTopic::from_bytes(payload)?)),
             DELETE_TOPIC_CODE => Ok(EntryCommand::DeleteTopic(DeleteTopic::from_bytes(payload)?)),
             PURGE_TOPIC_CODE => Ok(EntryCommand::PurgeTopic(PurgeTopic::from_bytes(payload)?)),
+            SET_TOPIC_RETENTION_CODE => Ok(EntryCommand::SetTopicRetention(
+                SetTopicRetention::from_bytes(payload)?,
+            )),
             CREATE_PARTITIONS_CODE => Ok(EntryCommand::CreatePartitions(
                 CreatePartitions::from_bytes(payload)?,
             )),
@@ -130,12 +171,23 @@ impl BytesSerializable for EntryCommand {
             UPDATE_PERMISSIONS_CODE => Ok(EntryCommand::UpdatePermissions(
                 UpdatePermissions::from_bytes(payload)?,
             )),
+            SET_USER_QUOTA_CODE => Ok(EntryCommand::SetUserQuota(SetUserQuota::from_bytes(
+                payload,
+            )?)),
+            CREATE_ROLE_CODE => Ok(EntryCommand::CreateRole(CreateRole::from_bytes(payload)?)),
+            UPDATE_ROLE_CODE => Ok(EntryCommand::UpdateRole(UpdateRole::from_bytes(payload)?)),
+            DELETE_ROLE_CODE => Ok(EntryCommand::DeleteRole(DeleteRole::from_bytes(payload)?)),
+            ASSIGN_ROLE_CODE => Ok(EntryCommand::AssignRole(AssignRole::from_bytes(payload)?)),
+            UNASSIGN_ROLE_CODE => Ok(EntryCommand::UnassignRole(UnassignRole::from_bytes(
+                payload,
+            )?)),
             CREATE_PERSONAL_ACCESS_TOKEN_CODE => Ok(EntryCommand::CreatePersonalAccessToken(
                 CreatePersonalAccessTokenWithHash::from_bytes(payload)?,
             )),
             DELETE_PERSONAL_ACCESS_TOKEN_CODE => Ok(EntryCommand::DeletePersonalAccessToken(
                 DeletePersonalAccessToken::from_bytes(payload)?,
             )),
+            NOOP_CODE => Ok(EntryCommand::Noop),
             _ => Err(IggyError::InvalidCommand),
         }
     }
@@ -148,10 +200,14 @@ impl Display for EntryCommand {
             EntryCommand::UpdateStream(command) => write!(f, "UpdateStream({})", command),
             EntryCommand::DeleteStream(command) => write!(f, "DeleteStream({})", command),
             EntryCommand::PurgeStream(command) => write!(f, "PurgeStream({})", command),
+            EntryCommand::SetStreamQuota(command) => write!(f, "SetStreamQuota({})", command),
             EntryCommand::CreateTopic(command) => write!(f, "CreateTopic({})", command),
             EntryCommand::UpdateTopic(command) => write!(f, "UpdateTopic({})", command),
             EntryCommand::DeleteTopic(command) => write!(f, "DeleteTopic({})", command),
             EntryCommand::PurgeTopic(command) => write!(f, "PurgeTopic({})", command),
+            EntryCommand::SetTopicRetention(command) => {
+                write!(f, "SetTopicRetention({})", command)
+            }
             EntryCommand::CreatePartitions(command) => write!(f, "CreatePartitions({})", command),
             EntryCommand::DeletePartitions(command) => write!(f, "DeletePartitions({})", command),
             EntryCommand::CreateConsumerGroup(command) => {
@@ -165,12 +221,19 @@ impl Display for EntryCommand {
             EntryCommand::DeleteUser(command) => write!(f, "DeleteUser({})", command),
             EntryCommand::ChangePassword(command) => write!(f, "ChangePassword({})", command),
             EntryCommand::UpdatePermissions(command) => write!(f, "UpdatePermissions({})", command),
+            EntryCommand::SetUserQuota(command) => write!(f, "SetUserQuota({})", command),
+            EntryCommand::CreateRole(command) => write!(f, "CreateRole({})", command),
+            EntryCommand::UpdateRole(command) => write!(f, "UpdateRole({})", command),
+            EntryCommand::DeleteRole(command) => write!(f, "DeleteRole({})", command),
+            EntryCommand::AssignRole(command) => write!(f, "AssignRole({})", command),
+            EntryCommand::UnassignRole(command) => write!(f, "UnassignRole({})", command),
             EntryCommand::CreatePersonalAccessToken(command) => {
                 write!(f, "CreatePersonalAccessToken({})", command)
             }
             EntryCommand::DeletePersonalAccessToken(command) => {
                 write!(f, "DeletePersonalAccessToken({})", command)
             }
+            EntryCommand::Noop => write!(f, "Noop"),
         }
     }
 }
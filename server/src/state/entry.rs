@@ -3,9 +3,15 @@ use bytes::{Buf, BufMut, Bytes, BytesMut};
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::error::IggyError;
 use iggy::utils::checksum;
+use iggy::utils::crypto::EncryptorKind;
 use iggy::utils::timestamp::IggyTimestamp;
 use std::fmt::{Display, Formatter};
 
+/// Size of the entry fields preceding the context: index, term, leader ID, version, flags, timestamp, user ID, checksum and context length.
+pub const ENTRY_HEADER_SIZE: usize = 8 + 8 + 4 + 4 + 8 + 8 + 4 + 4 + 4;
+/// Size of the command code and command length preceding the command payload.
+pub const COMMAND_HEADER_SIZE: usize = 4 + 4;
+
 /// State entry in the log
 /// - `index` - Index (operation number) of the entry in the log
 /// - `term` - Election term (view number) for replication
@@ -64,6 +70,51 @@ impl StateEntry {
         EntryCommand::from_bytes(self.command.clone())
     }
 
+    /// Returns the size of the serialized entry.
+    pub fn get_size_bytes(&self) -> usize {
+        ENTRY_HEADER_SIZE + self.context.len() + self.command.len()
+    }
+
+    /// Encrypts the payload of the command, keeping the command code and updating the command length.
+    pub fn encrypt_command(command: &Bytes, encryptor: &EncryptorKind) -> Result<Bytes, IggyError> {
+        let payload = Self::get_command_payload(command)?;
+        Ok(Self::map_command_payload(
+            command,
+            encryptor.encrypt(&payload)?,
+        ))
+    }
+
+    /// Decrypts the payload of the command, keeping the command code and updating the command length.
+    pub fn decrypt_command(command: &Bytes, encryptor: &EncryptorKind) -> Result<Bytes, IggyError> {
+        let payload = Self::get_command_payload(command)?;
+        Ok(Self::map_command_payload(
+            command,
+            encryptor.decrypt(&payload)?,
+        ))
+    }
+
+    fn get_command_payload(command: &Bytes) -> Result<Bytes, IggyError> {
+        if command.len() < COMMAND_HEADER_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let length = command.slice(4..8).get_u32_le() as usize;
+        if command.len() < COMMAND_HEADER_SIZE + length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(command.slice(COMMAND_HEADER_SIZE..COMMAND_HEADER_SIZE + length))
+    }
+
+    fn map_command_payload(command: &Bytes, payload: Vec<u8>) -> Bytes {
+        let code = command.slice(0..4).get_u32_le();
+        let mut bytes = BytesMut::with_capacity(COMMAND_HEADER_SIZE + payload.len());
+        bytes.put_u32_le(code);
+        bytes.put_u32_le(payload.len() as u32);
+        bytes.extend(payload);
+        bytes.freeze()
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn calculate_checksum(
         index: u64,
@@ -111,9 +162,7 @@ impl Display for StateEntry {
 
 impl BytesSerializable for StateEntry {
     fn to_bytes(&self) -> Bytes {
-        let mut bytes = BytesMut::with_capacity(
-            8 + 8 + 4 + 4 + 8 + 8 + 4 + 4 + 4 + self.context.len() + self.command.len(),
-        );
+        let mut bytes = BytesMut::with_capacity(self.get_size_bytes());
         bytes.put_u64_le(self.index);
         bytes.put_u64_le(self.term);
         bytes.put_u32_le(self.leader_id);
@@ -132,6 +181,10 @@ impl BytesSerializable for StateEntry {
     where
         Self: Sized,
     {
+        if bytes.len() < ENTRY_HEADER_SIZE {
+            return Err(IggyError::StateFileCorrupted);
+        }
+
         let index = bytes.slice(0..8).get_u64_le();
         let term = bytes.slice(8..16).get_u64_le();
         let leader_id = bytes.slice(16..20).get_u32_le();
@@ -141,8 +194,21 @@ impl BytesSerializable for StateEntry {
         let user_id = bytes.slice(40..44).get_u32_le();
         let checksum = bytes.slice(44..48).get_u32_le();
         let context_length = bytes.slice(48..52).get_u32_le() as usize;
-        let context = bytes.slice(52..52 + context_length);
-        let command = bytes.slice(52 + context_length..);
+        let command_position = ENTRY_HEADER_SIZE + context_length;
+        if bytes.len() < command_position + COMMAND_HEADER_SIZE {
+            return Err(IggyError::StateFileCorrupted);
+        }
+
+        let command_length = bytes
+            .slice(command_position + 4..command_position + COMMAND_HEADER_SIZE)
+            .get_u32_le() as usize;
+        let entry_size = command_position + COMMAND_HEADER_SIZE + command_length;
+        if bytes.len() < entry_size {
+            return Err(IggyError::StateFileCorrupted);
+        }
+
+        let context = bytes.slice(ENTRY_HEADER_SIZE..command_position);
+        let command = bytes.slice(command_position..entry_size);
 
         Ok(StateEntry {
             index,
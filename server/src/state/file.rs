@@ -1,20 +1,26 @@
 use crate::state::command::EntryCommand;
+use crate::state::snapshot::{StateSnapshot, StateSnapshotHeader, STATE_SNAPSHOT_HEADER_SIZE};
 use crate::state::{State, StateEntry, COMPONENT};
 use crate::streaming::persistence::persister::PersisterKind;
 use crate::streaming::utils::file;
 use crate::versioning::SemanticVersion;
-use bytes::{Buf, BufMut, Bytes, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
 use error_set::ErrContext;
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::error::IggyError;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::crypto::EncryptorKind;
+use iggy::utils::duration::IggyDuration;
 use iggy::utils::timestamp::IggyTimestamp;
 use std::fmt::Debug;
+use std::io::SeekFrom;
 use std::path::Path;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
-use std::sync::Arc;
-use tokio::io::{AsyncReadExt, BufReader};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::fs::OpenOptions;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, BufReader};
+use tokio::sync::Notify;
 use tracing::{debug, error, info};
 
 pub const BUF_READER_CAPACITY_BYTES: usize = 512 * 1000;
@@ -26,15 +32,33 @@ pub struct FileState {
     entries_count: AtomicU64,
     current_leader: AtomicU32,
     term: AtomicU64,
+    last_term: AtomicU64,
     version: u32,
     path: String,
+    snapshot_path: String,
     persister: Arc<PersisterKind>,
     encryptor: Option<Arc<EncryptorKind>>,
+    // The positions of the entries in the log file, so they can be read (or truncated) starting at any index.
+    positions: Mutex<Vec<EntryPosition>>,
+    size: AtomicU64,
+    // Serializes the writes to the log file, so the entries are always appended in the order of their indexes.
+    write_lock: tokio::sync::Mutex<()>,
+    commit_index: AtomicU64,
+    commit_timeout: AtomicU64,
+    committed: Notify,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EntryPosition {
+    index: u64,
+    term: u64,
+    position: u64,
 }
 
 impl FileState {
     pub fn new(
         path: &str,
+        snapshot_path: &str,
         version: &SemanticVersion,
         persister: Arc<PersisterKind>,
         encryptor: Option<Arc<EncryptorKind>>,
@@ -44,10 +68,18 @@ impl FileState {
             entries_count: AtomicU64::new(0),
             current_leader: AtomicU32::new(0),
             term: AtomicU64::new(0),
+            last_term: AtomicU64::new(0),
             path: path.into(),
+            snapshot_path: snapshot_path.into(),
             persister,
             encryptor,
             version: version.get_numeric_version().expect("Invalid version"),
+            positions: Mutex::new(Vec::new()),
+            size: AtomicU64::new(0),
+            write_lock: tokio::sync::Mutex::new(()),
+            commit_index: AtomicU64::new(0),
+            commit_timeout: AtomicU64::new(0),
+            committed: Notify::new(),
         }
     }
 
@@ -62,44 +94,116 @@ impl FileState {
     pub fn term(&self) -> u64 {
         self.term.load(Ordering::SeqCst)
     }
-}
 
-impl State for FileState {
-    async fn init(&self) -> Result<Vec<StateEntry>, IggyError> {
-        if !Path::new(&self.path).exists() {
-            info!("State file does not exist, creating a new one");
-            self.persister
-                .overwrite(&self.path, &[])
-                .await
-                .with_error_context(|error| {
-                    format!(
-                        "{COMPONENT} (error: {error}) - failed to overwrite state file, path: {}",
-                        self.path
-                    )
-                })?;
+    fn encrypt_command(&self, index: u64, command: Bytes) -> Result<Bytes, IggyError> {
+        let Some(encryptor) = &self.encryptor else {
+            return Ok(command);
+        };
+
+        debug!("Encrypting state entry command with index: {index}");
+        StateEntry::encrypt_command(&command, encryptor).with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to encrypt state entry command, index: {}",
+                index
+            )
+        })
+    }
+
+    async fn load_snapshot_header(&self) -> Result<Option<StateSnapshotHeader>, IggyError> {
+        if !Path::new(&self.snapshot_path).exists() {
+            return Ok(None);
         }
 
-        let entries = self.load_entries().await.with_error_context(|error| {
-            format!("{COMPONENT} (error: {error}) - failed to load entries")
-        })?;
-        let entries_count = entries.len() as u64;
-        self.entries_count.store(entries_count, Ordering::SeqCst);
-        if entries_count == 0 {
-            self.current_index.store(0, Ordering::SeqCst);
-        } else {
-            let last_index = entries[entries_count as usize - 1].index;
-            self.current_index.store(last_index, Ordering::SeqCst);
+        let mut file = file::open(&self.snapshot_path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to open state snapshot file, path: {}",
+                    self.snapshot_path
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        let mut header = [0; STATE_SNAPSHOT_HEADER_SIZE];
+        file.read_exact(&mut header)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read state snapshot header, path: {}",
+                    self.snapshot_path
+                )
+            })
+            .map_err(|_| IggyError::StateFileCorrupted)?;
+        Ok(Some(StateSnapshotHeader::from_bytes(&header)?))
+    }
+
+    fn get_position(&self, index: u64) -> Option<EntryPosition> {
+        let positions = self.positions.lock().unwrap();
+        let first_index = positions.first()?.index;
+        index
+            .checked_sub(first_index)
+            .and_then(|offset| positions.get(offset as usize))
+            .copied()
+    }
+
+    fn push_position(&self, index: u64, term: u64, length: u64) {
+        let position = self.size.fetch_add(length, Ordering::SeqCst);
+        self.positions.lock().unwrap().push(EntryPosition {
+            index,
+            term,
+            position,
+        });
+    }
+
+    async fn load_positions(&self) -> Result<Vec<StateEntry>, IggyError> {
+        let entries = self.read_entries(0, usize::MAX).await?;
+        let mut positions = Vec::with_capacity(entries.len());
+        let mut size = 0;
+        for (position, length, entry) in entries.iter() {
+            positions.push(EntryPosition {
+                index: entry.index,
+                term: entry.term,
+                position: *position,
+            });
+            size = position + length;
         }
+        *self.positions.lock().unwrap() = positions;
+        self.size.store(size, Ordering::SeqCst);
+        Ok(entries.into_iter().map(|(_, _, entry)| entry).collect())
+    }
 
-        Ok(entries)
+    async fn wait_for_commit(&self, index: u64) -> Result<(), IggyError> {
+        let timeout = self.commit_timeout.load(Ordering::SeqCst);
+        if timeout == 0 {
+            return Ok(());
+        }
+
+        let deadline = tokio::time::Instant::now() + Duration::from_micros(timeout);
+        loop {
+            // The notification is registered before checking the commit index, so it cannot be missed.
+            let committed = self.committed.notified();
+            if self.commit_index.load(Ordering::SeqCst) > index {
+                return Ok(());
+            }
+
+            if tokio::time::timeout_at(deadline, committed).await.is_err() {
+                error!("State entry with index: {index} has not been committed in time.");
+                return Err(IggyError::StateEntryNotCommitted(index));
+            }
+        }
     }
 
-    async fn load_entries(&self) -> Result<Vec<StateEntry>, IggyError> {
+    /// Reads at most `max_entries` entries starting at the given position of the log file,
+    /// returns them along with their positions and lengths in the file.
+    async fn read_entries(
+        &self,
+        start_position: u64,
+        max_entries: usize,
+    ) -> Result<Vec<(u64, u64, StateEntry)>, IggyError> {
         if !Path::new(&self.path).exists() {
             return Err(IggyError::StateFileNotFound);
         }
 
-        let file = file::open(&self.path)
+        let mut file = file::open(&self.path)
             .await
             .with_error_context(|error| {
                 format!(
@@ -119,21 +223,26 @@ impl State for FileState {
             })
             .map_err(|_| IggyError::CannotReadFileMetadata)?
             .len();
-        if file_size == 0 {
-            info!("State file is empty");
+        if file_size <= start_position || max_entries == 0 {
             return Ok(Vec::new());
         }
 
-        info!(
-            "Loading state, file size: {}",
-            IggyByteSize::from(file_size).as_human_string()
-        );
+        file.seek(SeekFrom::Start(start_position))
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to seek state file, path: {}, position: {start_position}",
+                    self.path
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
         let mut entries = Vec::new();
-        let mut total_size: u64 = 0;
+        let mut total_size: u64 = start_position;
         let mut reader = BufReader::with_capacity(BUF_READER_CAPACITY_BYTES, file);
         let mut current_index = 0;
         let mut entries_count = 0;
         loop {
+            let entry_position = total_size;
             let index = reader
                 .read_u64_le()
                 .await
@@ -277,18 +386,123 @@ impl State for FileState {
                 ));
             }
 
-            entries.push(entry);
-            if total_size == file_size {
+            entries.push((entry_position, total_size - entry_position, entry));
+            if total_size == file_size || entries.len() == max_entries {
                 break;
             }
         }
 
-        info!("Loaded {entries_count} state entries, current index: {current_index}");
+        debug!("Read {entries_count} state entries, current index: {current_index}");
         Ok(entries)
     }
+}
+
+impl State for FileState {
+    async fn init(&self) -> Result<Vec<StateEntry>, IggyError> {
+        if !Path::new(&self.path).exists() {
+            info!("State file does not exist, creating a new one");
+            self.persister
+                .overwrite(&self.path, &[])
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to overwrite state file, path: {}",
+                        self.path
+                    )
+                })?;
+        }
+
+        let snapshot_header = self
+            .load_snapshot_header()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load snapshot header")
+            })?;
+        let mut entries = self.load_positions().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to load entries")
+        })?;
+        info!("Loaded {} state entries", entries.len());
+        if let Some(snapshot_header) = &snapshot_header {
+            // The log might still contain the entries included in the snapshot, if it wasn't truncated yet.
+            entries.retain(|entry| entry.index > snapshot_header.index);
+            if let Some(first_entry) = entries.first() {
+                if first_entry.index != snapshot_header.index + 1 {
+                    error!(
+                        "State file is corrupted, expected index: {} after the snapshot, got: {}",
+                        snapshot_header.index + 1,
+                        first_entry.index
+                    );
+                    return Err(IggyError::StateFileCorrupted);
+                }
+            }
+            info!("Loaded state snapshot header: {snapshot_header}");
+        }
+
+        let last_entry = entries
+            .last()
+            .map(|entry| (entry.index, entry.term, entry.leader_id));
+        let last_snapshot_entry = snapshot_header.map(|snapshot_header| {
+            (
+                snapshot_header.index,
+                snapshot_header.term,
+                snapshot_header.leader_id,
+            )
+        });
+        match last_entry.or(last_snapshot_entry) {
+            Some((index, term, leader_id)) => {
+                self.entries_count.store(index + 1, Ordering::SeqCst);
+                self.current_index.store(index, Ordering::SeqCst);
+                self.term.store(term, Ordering::SeqCst);
+                self.last_term.store(term, Ordering::SeqCst);
+                self.current_leader.store(leader_id, Ordering::SeqCst);
+            }
+            None => {
+                self.entries_count.store(0, Ordering::SeqCst);
+                self.current_index.store(0, Ordering::SeqCst);
+            }
+        }
+
+        Ok(entries)
+    }
+
+    async fn load_entries(&self) -> Result<Vec<StateEntry>, IggyError> {
+        let entries = self.read_entries(0, usize::MAX).await?;
+        if entries.is_empty() {
+            info!("State file is empty");
+            return Ok(Vec::new());
+        }
+
+        let size = entries
+            .last()
+            .map(|(position, length, _)| position + length)
+            .unwrap_or_default();
+        info!(
+            "Loaded {} state entries, file size: {}",
+            entries.len(),
+            IggyByteSize::from(size).as_human_string()
+        );
+        Ok(entries.into_iter().map(|(_, _, entry)| entry).collect())
+    }
+
+    async fn load_entries_from(
+        &self,
+        index: u64,
+        count: usize,
+    ) -> Result<Vec<StateEntry>, IggyError> {
+        if index >= self.next_index() {
+            return Ok(Vec::new());
+        }
+
+        let Some(position) = self.get_position(index) else {
+            return Err(IggyError::InvalidStateEntryIndex(index, self.next_index()));
+        };
+        let entries = self.read_entries(position.position, count).await?;
+        Ok(entries.into_iter().map(|(_, _, entry)| entry).collect())
+    }
 
     async fn apply(&self, user_id: u32, command: EntryCommand) -> Result<(), IggyError> {
         debug!("Applying state entry with command: {command}, user ID: {user_id}");
+        let write_lock = self.write_lock.lock().await;
         let timestamp = IggyTimestamp::now();
         let index = if self.entries_count.load(Ordering::SeqCst) == 0 {
             0
@@ -300,7 +514,7 @@ impl State for FileState {
         let version = self.version;
         let flags = 0;
         let context = Bytes::new();
-        let mut command = command.to_bytes();
+        let command = command.to_bytes();
         let checksum = StateEntry::calculate_checksum(
             index,
             term,
@@ -313,27 +527,7 @@ impl State for FileState {
             &command,
         );
 
-        if let Some(encryptor) = &self.encryptor {
-            debug!("Encrypting state entry command with index: {index}");
-            let command_code = command.slice(0..4).get_u32_le();
-            let mut command_length = command.slice(4..8).get_u32_le() as usize;
-            let command_payload = command.slice(8..8 + command_length);
-            let encrypted_command_payload = encryptor
-                .encrypt(&command_payload)
-                .with_error_context(|error| {
-                    format!(
-                        "{COMPONENT} (error: {error}) - failed to encrypt state entry command, index: {}",
-                        index
-                    )
-                })?;
-            command_length = encrypted_command_payload.len();
-            let mut command_bytes = BytesMut::with_capacity(4 + 4 + command_length);
-            command_bytes.put_u32_le(command_code);
-            command_bytes.put_u32_le(command_length as u32);
-            command_bytes.extend(encrypted_command_payload);
-            command = command_bytes.freeze();
-        }
-
+        let command = self.encrypt_command(index, command)?;
         let entry = StateEntry::new(
             index,
             term,
@@ -348,6 +542,7 @@ impl State for FileState {
         );
         let bytes = entry.to_bytes();
         self.entries_count.fetch_add(1, Ordering::SeqCst);
+        self.last_term.store(term, Ordering::SeqCst);
         self.persister
             .append(&self.path, &bytes)
             .await
@@ -358,7 +553,245 @@ impl State for FileState {
                     bytes.len()
                 )
             })?;
+        self.push_position(index, term, bytes.len() as u64);
+        drop(write_lock);
         debug!("Applied state entry: {entry}");
+        self.wait_for_commit(index).await
+    }
+
+    async fn append(&self, entry: StateEntry) -> Result<(), IggyError> {
+        let _write_lock = self.write_lock.lock().await;
+        let next_index = self.next_index();
+        if entry.index != next_index {
+            return Err(IggyError::InvalidStateEntryIndex(entry.index, next_index));
+        }
+
+        let checksum = StateEntry::calculate_checksum(
+            entry.index,
+            entry.term,
+            entry.leader_id,
+            entry.version,
+            entry.flags,
+            entry.timestamp,
+            entry.user_id,
+            &entry.context,
+            &entry.command,
+        );
+        if checksum != entry.checksum {
+            return Err(IggyError::InvalidStateEntryChecksum(
+                checksum,
+                entry.checksum,
+                entry.index,
+            ));
+        }
+
+        let command = self.encrypt_command(entry.index, entry.command.clone())?;
+        let replicated_entry = StateEntry::new(
+            entry.index,
+            entry.term,
+            entry.leader_id,
+            entry.version,
+            entry.flags,
+            entry.timestamp,
+            entry.user_id,
+            entry.checksum,
+            entry.context.clone(),
+            command,
+        );
+        let bytes = replicated_entry.to_bytes();
+        self.persister
+            .append(&self.path, &bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to append replicated state entry to file, path: {}, index: {}",
+                    self.path, entry.index
+                )
+            })?;
+        self.push_position(entry.index, entry.term, bytes.len() as u64);
+        self.current_index.store(entry.index, Ordering::SeqCst);
+        self.entries_count.fetch_add(1, Ordering::SeqCst);
+        self.last_term.store(entry.term, Ordering::SeqCst);
+        if entry.term > self.term.load(Ordering::SeqCst) {
+            self.term.store(entry.term, Ordering::SeqCst);
+            self.current_leader.store(entry.leader_id, Ordering::SeqCst);
+        }
+        debug!("Appended replicated state entry: {entry}");
+        Ok(())
+    }
+
+    async fn truncate(&self, index: u64) -> Result<(), IggyError> {
+        let _write_lock = self.write_lock.lock().await;
+        let next_index = self.next_index();
+        if index >= next_index {
+            return Ok(());
+        }
+
+        let Some(position) = self.get_position(index) else {
+            return Err(IggyError::InvalidStateEntryIndex(index, next_index));
+        };
+        let file = OpenOptions::new()
+            .write(true)
+            .open(&self.path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to open state file, path: {}",
+                    self.path
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        file.set_len(position.position)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to truncate state file, path: {}, position: {}",
+                    self.path, position.position
+                )
+            })
+            .map_err(|_| IggyError::CannotOverwriteFile)?;
+        file.sync_all()
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to sync state file, path: {}",
+                    self.path
+                )
+            })
+            .map_err(|_| IggyError::CannotOverwriteFile)?;
+
+        let last_term = {
+            let mut positions = self.positions.lock().unwrap();
+            positions.retain(|entry| entry.index < index);
+            positions.last().map(|entry| entry.term).unwrap_or_default()
+        };
+        self.size.store(position.position, Ordering::SeqCst);
+        self.entries_count.store(index, Ordering::SeqCst);
+        self.current_index
+            .store(index.saturating_sub(1), Ordering::SeqCst);
+        self.last_term.store(last_term, Ordering::SeqCst);
+        info!(
+            "Truncated state entries starting at index: {index}, previous next index: {next_index}"
+        );
+        Ok(())
+    }
+
+    async fn load_snapshot(&self) -> Result<Option<StateSnapshot>, IggyError> {
+        if !Path::new(&self.snapshot_path).exists() {
+            return Ok(None);
+        }
+
+        let bytes = tokio::fs::read(&self.snapshot_path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read state snapshot file, path: {}",
+                    self.snapshot_path
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        info!(
+            "Loading state snapshot, file size: {}",
+            IggyByteSize::from(bytes.len() as u64).as_human_string()
+        );
+        let snapshot = StateSnapshot::from_bytes(Bytes::from(bytes), self.encryptor.as_deref())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to parse state snapshot, path: {}",
+                    self.snapshot_path
+                )
+            })?;
+        info!("Loaded state snapshot: {}", snapshot.header);
+        Ok(Some(snapshot))
+    }
+
+    async fn snapshot(&self, snapshot: StateSnapshot) -> Result<(), IggyError> {
+        let index = snapshot.header.index;
+        let next_index = self.next_index();
+        if index >= next_index {
+            return Err(IggyError::InvalidStateEntryIndex(index, next_index));
+        }
+
+        let bytes = snapshot.to_bytes(self.encryptor.as_deref())?;
+        self.persister
+            .overwrite_atomically(&self.snapshot_path, &bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to persist state snapshot, path: {}",
+                    self.snapshot_path
+                )
+            })?;
+
+        // Once the snapshot is persisted, the entries included in it can be removed from the log.
+        let mut remaining_entries = BytesMut::new();
+        let mut remaining_entries_count = 0;
+        for entry in self.load_entries().await? {
+            if entry.index <= index {
+                continue;
+            }
+
+            let command = self.encrypt_command(entry.index, entry.command)?;
+            let entry = StateEntry::new(
+                entry.index,
+                entry.term,
+                entry.leader_id,
+                entry.version,
+                entry.flags,
+                entry.timestamp,
+                entry.user_id,
+                entry.checksum,
+                entry.context,
+                command,
+            );
+            remaining_entries.extend(entry.to_bytes());
+            remaining_entries_count += 1;
+        }
+        self.persister
+            .overwrite_atomically(&self.path, &remaining_entries)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to truncate state file, path: {}",
+                    self.path
+                )
+            })?;
+        self.load_positions().await?;
+        info!(
+            "Persisted state snapshot: {}, remaining state entries: {remaining_entries_count}",
+            snapshot.header
+        );
         Ok(())
     }
+
+    fn next_index(&self) -> u64 {
+        self.entries_count.load(Ordering::SeqCst)
+    }
+
+    fn last_term(&self) -> u64 {
+        self.last_term.load(Ordering::SeqCst)
+    }
+
+    fn set_leader(&self, term: u64, leader_id: u32) {
+        self.term.store(term, Ordering::SeqCst);
+        self.current_leader.store(leader_id, Ordering::SeqCst);
+    }
+
+    fn term_at(&self, index: u64) -> Option<u64> {
+        self.get_position(index).map(|position| position.term)
+    }
+
+    fn commit_index(&self) -> u64 {
+        self.commit_index.load(Ordering::SeqCst)
+    }
+
+    fn set_commit_index(&self, commit_index: u64) {
+        self.commit_index.store(commit_index, Ordering::SeqCst);
+        self.committed.notify_waiters();
+    }
+
+    fn set_commit_timeout(&self, timeout: IggyDuration) {
+        self.commit_timeout
+            .store(timeout.as_micros(), Ordering::SeqCst);
+    }
 }
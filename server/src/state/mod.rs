@@ -1,6 +1,8 @@
 use crate::state::command::EntryCommand;
 use crate::state::entry::StateEntry;
+use crate::state::snapshot::StateSnapshot;
 use iggy::error::IggyError;
+use iggy::utils::duration::IggyDuration;
 #[cfg(test)]
 use mockall::automock;
 use std::fmt::Debug;
@@ -10,6 +12,7 @@ pub mod command;
 pub mod entry;
 pub mod file;
 pub mod models;
+pub mod snapshot;
 pub mod system;
 
 pub const COMPONENT: &str = "STATE";
@@ -25,11 +28,44 @@ pub enum StateKind {
 pub trait State: Send {
     fn init(&self) -> impl Future<Output = Result<Vec<StateEntry>, IggyError>> + Send;
     fn load_entries(&self) -> impl Future<Output = Result<Vec<StateEntry>, IggyError>> + Send;
+    /// Loads at most `count` entries, starting at the given index.
+    fn load_entries_from(
+        &self,
+        index: u64,
+        count: usize,
+    ) -> impl Future<Output = Result<Vec<StateEntry>, IggyError>> + Send;
     fn apply(
         &self,
         user_id: u32,
         command: EntryCommand,
     ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    /// Appends the entry replicated from the leader, preserving its index and term.
+    fn append(&self, entry: StateEntry) -> impl Future<Output = Result<(), IggyError>> + Send;
+    /// Removes the entries starting at the given index, which conflict with the ones replicated from the leader.
+    fn truncate(&self, index: u64) -> impl Future<Output = Result<(), IggyError>> + Send;
+    /// Loads the latest snapshot of the state, if any.
+    fn load_snapshot(
+        &self,
+    ) -> impl Future<Output = Result<Option<StateSnapshot>, IggyError>> + Send;
+    /// Persists the snapshot and removes the entries included in it from the log.
+    fn snapshot(
+        &self,
+        snapshot: StateSnapshot,
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    /// Returns the index of the next entry to be appended (the number of entries ever appended to the log).
+    fn next_index(&self) -> u64;
+    /// Returns the term of the last entry in the log.
+    fn last_term(&self) -> u64;
+    /// Sets the term and the leader used for the new entries.
+    fn set_leader(&self, term: u64, leader_id: u32);
+    /// Returns the term of the entry with the given index, if it's present in the log.
+    fn term_at(&self, index: u64) -> Option<u64>;
+    /// Returns the number of the entries committed by the majority of the cluster nodes.
+    fn commit_index(&self) -> u64;
+    /// Sets the commit index, completing the `apply` calls waiting for their entries to be committed.
+    fn set_commit_index(&self, commit_index: u64);
+    /// Makes `apply` wait (up to the given timeout) until the appended entry is committed, used in the cluster mode.
+    fn set_commit_timeout(&self, timeout: IggyDuration);
 }
 
 impl StateKind {
@@ -49,6 +85,18 @@ impl StateKind {
         }
     }
 
+    pub async fn load_entries_from(
+        &self,
+        index: u64,
+        count: usize,
+    ) -> Result<Vec<StateEntry>, IggyError> {
+        match self {
+            Self::File(s) => s.load_entries_from(index, count).await,
+            #[cfg(test)]
+            Self::Mock(s) => s.load_entries_from(index, count).await,
+        }
+    }
+
     pub async fn apply(&self, user_id: u32, command: EntryCommand) -> Result<(), IggyError> {
         match self {
             Self::File(s) => s.apply(user_id, command).await,
@@ -56,4 +104,92 @@ impl StateKind {
             Self::Mock(s) => s.apply(user_id, command).await,
         }
     }
+
+    pub async fn append(&self, entry: StateEntry) -> Result<(), IggyError> {
+        match self {
+            Self::File(s) => s.append(entry).await,
+            #[cfg(test)]
+            Self::Mock(s) => s.append(entry).await,
+        }
+    }
+
+    pub async fn truncate(&self, index: u64) -> Result<(), IggyError> {
+        match self {
+            Self::File(s) => s.truncate(index).await,
+            #[cfg(test)]
+            Self::Mock(s) => s.truncate(index).await,
+        }
+    }
+
+    pub async fn load_snapshot(&self) -> Result<Option<StateSnapshot>, IggyError> {
+        match self {
+            Self::File(s) => s.load_snapshot().await,
+            #[cfg(test)]
+            Self::Mock(s) => s.load_snapshot().await,
+        }
+    }
+
+    pub async fn snapshot(&self, snapshot: StateSnapshot) -> Result<(), IggyError> {
+        match self {
+            Self::File(s) => s.snapshot(snapshot).await,
+            #[cfg(test)]
+            Self::Mock(s) => s.snapshot(snapshot).await,
+        }
+    }
+
+    pub fn next_index(&self) -> u64 {
+        match self {
+            Self::File(s) => s.next_index(),
+            #[cfg(test)]
+            Self::Mock(s) => s.next_index(),
+        }
+    }
+
+    pub fn last_term(&self) -> u64 {
+        match self {
+            Self::File(s) => s.last_term(),
+            #[cfg(test)]
+            Self::Mock(s) => s.last_term(),
+        }
+    }
+
+    pub fn set_leader(&self, term: u64, leader_id: u32) {
+        match self {
+            Self::File(s) => s.set_leader(term, leader_id),
+            #[cfg(test)]
+            Self::Mock(s) => s.set_leader(term, leader_id),
+        }
+    }
+
+    pub fn term_at(&self, index: u64) -> Option<u64> {
+        match self {
+            Self::File(s) => s.term_at(index),
+            #[cfg(test)]
+            Self::Mock(s) => s.term_at(index),
+        }
+    }
+
+    pub fn commit_index(&self) -> u64 {
+        match self {
+            Self::File(s) => s.commit_index(),
+            #[cfg(test)]
+            Self::Mock(s) => s.commit_index(),
+        }
+    }
+
+    pub fn set_commit_index(&self, commit_index: u64) {
+        match self {
+            Self::File(s) => s.set_commit_index(commit_index),
+            #[cfg(test)]
+            Self::Mock(s) => s.set_commit_index(commit_index),
+        }
+    }
+
+    pub fn set_commit_timeout(&self, timeout: IggyDuration) {
+        match self {
+            Self::File(s) => s.set_commit_timeout(timeout),
+            #[cfg(test)]
+            Self::Mock(s) => s.set_commit_timeout(timeout),
+        }
+    }
 }
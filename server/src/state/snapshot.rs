@@ -0,0 +1,194 @@
+use crate::state::system::SystemState;
+use crate::state::COMPONENT;
+use bytes::{BufMut, Bytes, BytesMut};
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::utils::checksum;
+use iggy::utils::crypto::EncryptorKind;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::fmt::{Display, Formatter};
+use tracing::error;
+
+pub const STATE_SNAPSHOT_HEADER_SIZE: usize = 8 + 8 + 4 + 4 + 8 + 4 + 4;
+
+/// State snapshot holds the compacted system state including all the state log entries up to (and including) the index.
+/// - `header` - Describes the last entry included in the snapshot
+/// - `state` - System state built by applying all the entries included in the snapshot
+#[derive(Debug)]
+pub struct StateSnapshot {
+    pub header: StateSnapshotHeader,
+    pub state: SystemState,
+}
+
+/// State snapshot header, which can be read without loading the whole state.
+/// - `index` - Index of the last entry included in the snapshot
+/// - `term` - Election term of the last entry included in the snapshot
+/// - `leader_id` - Leader ID of the last entry included in the snapshot
+/// - `version` - Server version based on semver as number e.g. 1.234.567 -> 1234567
+/// - `created_at` - Timestamp when the snapshot was created
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateSnapshotHeader {
+    pub index: u64,
+    pub term: u64,
+    pub leader_id: u32,
+    pub version: u32,
+    pub created_at: IggyTimestamp,
+}
+
+impl StateSnapshot {
+    pub fn new(header: StateSnapshotHeader, state: SystemState) -> Self {
+        Self { header, state }
+    }
+
+    /// Serializes the snapshot, the state is encrypted if the encryptor is provided.
+    pub fn to_bytes(&self, encryptor: Option<&EncryptorKind>) -> Result<Bytes, IggyError> {
+        let state = serde_json::to_vec(&self.state)
+            .map_err(|error| {
+                error!(
+                    "{COMPONENT} - failed to serialize state snapshot with index: {}. {error}",
+                    self.header.index
+                );
+                IggyError::CannotSerializeResource
+            })
+            .map(Bytes::from)?;
+        let checksum = self.header.calculate_checksum(&state);
+        let state = match encryptor {
+            Some(encryptor) => Bytes::from(encryptor.encrypt(&state).with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to encrypt state snapshot with index: {}",
+                    self.header.index
+                )
+            })?),
+            None => state,
+        };
+
+        let mut bytes = BytesMut::with_capacity(STATE_SNAPSHOT_HEADER_SIZE + state.len());
+        bytes.put_u64_le(self.header.index);
+        bytes.put_u64_le(self.header.term);
+        bytes.put_u32_le(self.header.leader_id);
+        bytes.put_u32_le(self.header.version);
+        bytes.put_u64_le(self.header.created_at.into());
+        bytes.put_u32_le(checksum);
+        bytes.put_u32_le(state.len() as u32);
+        bytes.extend(state);
+        Ok(bytes.freeze())
+    }
+
+    /// Deserializes the snapshot, the state is decrypted if the encryptor is provided.
+    pub fn from_bytes(bytes: Bytes, encryptor: Option<&EncryptorKind>) -> Result<Self, IggyError> {
+        let header = StateSnapshotHeader::from_bytes(&bytes)?;
+        let checksum = u32::from_le_bytes(
+            bytes[32..36]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let state_length = u32::from_le_bytes(
+            bytes[36..40]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        if bytes.len() != STATE_SNAPSHOT_HEADER_SIZE + state_length {
+            error!(
+                "{COMPONENT} - state snapshot with index: {} is corrupted, expected state length: {state_length}, got: {}",
+                header.index,
+                bytes.len() - STATE_SNAPSHOT_HEADER_SIZE
+            );
+            return Err(IggyError::StateFileCorrupted);
+        }
+
+        let state = bytes.slice(STATE_SNAPSHOT_HEADER_SIZE..);
+        let state = match encryptor {
+            Some(encryptor) => Bytes::from(encryptor.decrypt(&state).with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to decrypt state snapshot with index: {}",
+                    header.index
+                )
+            })?),
+            None => state,
+        };
+        let calculated_checksum = header.calculate_checksum(&state);
+        if calculated_checksum != checksum {
+            return Err(IggyError::InvalidStateSnapshotChecksum(
+                calculated_checksum,
+                checksum,
+                header.index,
+            ));
+        }
+
+        let state = serde_json::from_slice(&state).map_err(|error| {
+            error!(
+                "{COMPONENT} - failed to deserialize state snapshot with index: {}. {error}",
+                header.index
+            );
+            IggyError::CannotDeserializeResource
+        })?;
+        Ok(Self { header, state })
+    }
+}
+
+impl StateSnapshotHeader {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IggyError> {
+        if bytes.len() < STATE_SNAPSHOT_HEADER_SIZE {
+            error!(
+                "{COMPONENT} - state snapshot is corrupted, expected at least {STATE_SNAPSHOT_HEADER_SIZE} bytes, got: {}",
+                bytes.len()
+            );
+            return Err(IggyError::StateFileCorrupted);
+        }
+
+        let index = u64::from_le_bytes(
+            bytes[0..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let term = u64::from_le_bytes(
+            bytes[8..16]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let leader_id = u32::from_le_bytes(
+            bytes[16..20]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let version = u32::from_le_bytes(
+            bytes[20..24]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let created_at = u64::from_le_bytes(
+            bytes[24..32]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        )
+        .into();
+        Ok(Self {
+            index,
+            term,
+            leader_id,
+            version,
+            created_at,
+        })
+    }
+
+    fn calculate_checksum(&self, state: &Bytes) -> u32 {
+        let mut bytes = BytesMut::with_capacity(8 + 8 + 4 + 4 + 8 + state.len());
+        bytes.put_u64_le(self.index);
+        bytes.put_u64_le(self.term);
+        bytes.put_u32_le(self.leader_id);
+        bytes.put_u32_le(self.version);
+        bytes.put_u64_le(self.created_at.into());
+        bytes.put_slice(state);
+        checksum::calculate(&bytes.freeze())
+    }
+}
+
+impl Display for StateSnapshotHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "StateSnapshot {{ index: {}, term: {}, leader ID: {}, version: {}, created at: {} }}",
+            self.index, self.term, self.leader_id, self.version, self.created_at
+        )
+    }
+}
@@ -1,61 +1,80 @@
 use crate::state::{EntryCommand, StateEntry, COMPONENT};
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use error_set::ErrContext;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
 use iggy::models::permissions::Permissions;
+use iggy::models::quota::Quota;
 use iggy::models::user_status::UserStatus;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::topics::consumed_retention::ConsumedRetention;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::timestamp::IggyTimestamp;
 use iggy::utils::topic_size::MaxTopicSize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use tracing::debug;
 
-#[derive(Debug)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SystemState {
     pub streams: AHashMap<u32, StreamState>,
     pub users: AHashMap<u32, UserState>,
+    pub current_stream_id: u32,
+    pub current_user_id: u32,
+    #[serde(default)]
+    pub roles: AHashMap<u32, RoleState>,
+    #[serde(default)]
+    pub current_role_id: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StreamState {
     pub id: u32,
     pub name: String,
     pub created_at: IggyTimestamp,
     pub topics: AHashMap<u32, TopicState>,
     pub current_topic_id: u32,
+    #[serde(default)]
+    pub owner_id: u32,
+    #[serde(default)]
+    pub quota: Option<Quota>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TopicState {
     pub id: u32,
     pub name: String,
     pub partitions: AHashMap<u32, PartitionState>,
     pub consumer_groups: AHashMap<u32, ConsumerGroupState>,
     pub compression_algorithm: CompressionAlgorithm,
+    pub compression_level: Option<u8>,
     pub message_expiry: IggyExpiry,
     pub max_topic_size: MaxTopicSize,
     pub replication_factor: Option<u8>,
+    pub cleanup_policy: CleanupPolicy,
+    #[serde(default)]
+    pub consumed_retention: ConsumedRetention,
     pub created_at: IggyTimestamp,
     pub current_consumer_group_id: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PartitionState {
     pub id: u32,
     pub created_at: IggyTimestamp,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PersonalAccessTokenState {
     pub name: String,
     pub token_hash: String,
     pub expiry_at: Option<IggyTimestamp>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct UserState {
     pub id: u32,
     pub username: String,
@@ -63,20 +82,44 @@ pub struct UserState {
     pub status: UserStatus,
     pub permissions: Option<Permissions>,
     pub personal_access_tokens: AHashMap<String, PersonalAccessTokenState>,
+    #[serde(default)]
+    pub quota: Option<Quota>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoleState {
+    pub id: u32,
+    pub name: String,
+    pub created_at: IggyTimestamp,
+    pub permissions: Option<Permissions>,
+    pub users: AHashSet<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ConsumerGroupState {
     pub id: u32,
     pub name: String,
+    pub assignment_strategy: PartitionAssignmentStrategy,
 }
 
 impl SystemState {
     pub async fn init(entries: Vec<StateEntry>) -> Result<Self, IggyError> {
-        let mut streams = AHashMap::new();
-        let mut users = AHashMap::new();
-        let mut current_stream_id = 0;
-        let mut current_user_id = 0;
+        Self::init_from_snapshot(None, entries).await
+    }
+
+    /// Initializes the system state from the snapshot (if any) by replaying the remaining entries on top of it.
+    pub async fn init_from_snapshot(
+        snapshot: Option<SystemState>,
+        entries: Vec<StateEntry>,
+    ) -> Result<Self, IggyError> {
+        let SystemState {
+            mut streams,
+            mut users,
+            mut current_stream_id,
+            mut current_user_id,
+            mut roles,
+            mut current_role_id,
+        } = snapshot.unwrap_or_default();
         for entry in entries {
             debug!("Processing state entry: {entry}",);
             match entry.command().with_error_context(|error| {
@@ -93,6 +136,8 @@ impl SystemState {
                         topics: AHashMap::new(),
                         current_topic_id: 0,
                         created_at: entry.timestamp,
+                        owner_id: entry.user_id,
+                        quota: None,
                     };
                     streams.insert(stream.id, stream);
                 }
@@ -114,6 +159,13 @@ impl SystemState {
                         .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
                     // It only affects the segments which are not part of the state
                 }
+                EntryCommand::SetStreamQuota(command) => {
+                    let stream_id = find_stream_id(&streams, &command.stream_id);
+                    let stream = streams
+                        .get_mut(&stream_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                    stream.quota = Some(command.quota).filter(|quota| !quota.is_unlimited());
+                }
                 EntryCommand::CreateTopic(command) => {
                     let stream_id = find_stream_id(&streams, &command.stream_id);
                     let stream = streams
@@ -129,9 +181,12 @@ impl SystemState {
                         consumer_groups: AHashMap::new(),
                         current_consumer_group_id: 0,
                         compression_algorithm: command.compression_algorithm,
+                        compression_level: command.compression_level,
                         message_expiry: command.message_expiry,
                         max_topic_size: command.max_topic_size,
                         replication_factor: command.replication_factor,
+                        cleanup_policy: command.cleanup_policy,
+                        consumed_retention: ConsumedRetention::default(),
                         created_at: entry.timestamp,
                         partitions: if command.partitions_count > 0 {
                             let mut partitions = AHashMap::new();
@@ -163,9 +218,11 @@ impl SystemState {
                         .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
                     topic.name = command.name;
                     topic.compression_algorithm = command.compression_algorithm;
+                    topic.compression_level = command.compression_level;
                     topic.message_expiry = command.message_expiry;
                     topic.max_topic_size = command.max_topic_size;
                     topic.replication_factor = command.replication_factor;
+                    topic.cleanup_policy = command.cleanup_policy;
                 }
                 EntryCommand::DeleteTopic(command) => {
                     let stream_id = find_stream_id(&streams, &command.stream_id);
@@ -187,6 +244,18 @@ impl SystemState {
                         .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
                     // It only affects the segments which are not part of the state
                 }
+                EntryCommand::SetTopicRetention(command) => {
+                    let stream_id = find_stream_id(&streams, &command.stream_id);
+                    let stream = streams
+                        .get_mut(&stream_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                    let topic = stream
+                        .topics
+                        .get_mut(&topic_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
+                    topic.consumed_retention = command.retention;
+                }
                 EntryCommand::CreatePartitions(command) => {
                     let stream_id = find_stream_id(&streams, &command.stream_id);
                     let stream = streams
@@ -258,6 +327,7 @@ impl SystemState {
                     let consumer_group = ConsumerGroupState {
                         id: consumer_group_id,
                         name: command.name,
+                        assignment_strategy: command.assignment_strategy,
                     };
                     topic
                         .consumer_groups
@@ -286,6 +356,7 @@ impl SystemState {
                         status: command.status,
                         permissions: command.permissions,
                         personal_access_tokens: AHashMap::new(),
+                        quota: None,
                     };
                     users.insert(user.id, user);
                 }
@@ -304,6 +375,9 @@ impl SystemState {
                 EntryCommand::DeleteUser(command) => {
                     let user_id = find_user_id(&users, &command.user_id);
                     users.remove(&user_id);
+                    for role in roles.values_mut() {
+                        role.users.remove(&user_id);
+                    }
                 }
                 EntryCommand::ChangePassword(command) => {
                     let user_id = find_user_id(&users, &command.user_id);
@@ -319,6 +393,56 @@ impl SystemState {
                         .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
                     user.permissions = command.permissions;
                 }
+                EntryCommand::SetUserQuota(command) => {
+                    let user_id = find_user_id(&users, &command.user_id);
+                    let user = users
+                        .get_mut(&user_id)
+                        .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
+                    user.quota = Some(command.quota).filter(|quota| !quota.is_unlimited());
+                }
+                EntryCommand::CreateRole(command) => {
+                    current_role_id += 1;
+                    let role = RoleState {
+                        id: current_role_id,
+                        name: command.name,
+                        created_at: entry.timestamp,
+                        permissions: command.permissions,
+                        users: AHashSet::new(),
+                    };
+                    roles.insert(role.id, role);
+                }
+                EntryCommand::UpdateRole(command) => {
+                    let role_id = find_role_id(&roles, &command.role_id);
+                    let role = roles
+                        .get_mut(&role_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Role: {role_id} not found")));
+                    if let Some(name) = command.name {
+                        role.name = name;
+                    }
+                    if let Some(permissions) = command.permissions {
+                        role.permissions = Some(permissions);
+                    }
+                }
+                EntryCommand::DeleteRole(command) => {
+                    let role_id = find_role_id(&roles, &command.role_id);
+                    roles.remove(&role_id);
+                }
+                EntryCommand::AssignRole(command) => {
+                    let role_id = find_role_id(&roles, &command.role_id);
+                    let user_id = find_user_id(&users, &command.user_id);
+                    let role = roles
+                        .get_mut(&role_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Role: {role_id} not found")));
+                    role.users.insert(user_id);
+                }
+                EntryCommand::UnassignRole(command) => {
+                    let role_id = find_role_id(&roles, &command.role_id);
+                    let user_id = find_user_id(&users, &command.user_id);
+                    let role = roles
+                        .get_mut(&role_id)
+                        .unwrap_or_else(|| panic!("{}", format!("Role: {role_id} not found")));
+                    role.users.remove(&user_id);
+                }
                 EntryCommand::CreatePersonalAccessToken(command) => {
                     let token_hash = command.hash;
                     let user_id = find_user_id(
@@ -368,10 +492,18 @@ impl SystemState {
                         .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
                     user.personal_access_tokens.remove(&command.name);
                 }
+                EntryCommand::Noop => {}
             }
         }
 
-        let state = SystemState { streams, users };
+        let state = SystemState {
+            streams,
+            users,
+            current_stream_id,
+            current_user_id,
+            roles,
+            current_role_id,
+        };
         debug!("+++ State +++");
         debug!("{state}");
         debug!("+++ State +++");
@@ -454,6 +586,24 @@ fn find_user_id(users: &AHashMap<u32, UserState>, user_id: &Identifier) -> u32 {
     }
 }
 
+fn find_role_id(roles: &AHashMap<u32, RoleState>, role_id: &Identifier) -> u32 {
+    match role_id.kind {
+        IdKind::Numeric => role_id
+            .get_u32_value()
+            .unwrap_or_else(|_| panic!("{}", format!("Invalid role ID: {role_id}"))),
+        IdKind::String => {
+            let name = role_id
+                .get_cow_str_value()
+                .unwrap_or_else(|_| panic!("{}", format!("Invalid role name: {role_id}")));
+            let role = roles
+                .values()
+                .find(|s| s.name == name)
+                .unwrap_or_else(|| panic!("{}", format!("Role: {name} not found")));
+            role.id
+        }
+    }
+}
+
 impl Display for SystemState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Streams:")?;
@@ -466,13 +616,22 @@ impl Display for SystemState {
             write!(f, "\n================\n")?;
             write!(f, "{}", user.1)?;
         }
+        write!(f, "Roles:")?;
+        for role in self.roles.iter() {
+            write!(f, "\n================\n")?;
+            write!(f, "{}", role.1)?;
+        }
         Ok(())
     }
 }
 
 impl Display for ConsumerGroupState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "ConsumerGroup -> ID: {}, Name: {}", self.id, self.name)
+        write!(
+            f,
+            "ConsumerGroup -> ID: {}, Name: {}, Assignment strategy: {}",
+            self.id, self.name, self.assignment_strategy
+        )
     }
 }
 
@@ -491,6 +650,21 @@ impl Display for UserState {
     }
 }
 
+impl Display for RoleState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let permissions = if let Some(permissions) = &self.permissions {
+            permissions.to_string()
+        } else {
+            "no_permissions".to_string()
+        };
+        write!(
+            f,
+            "Role -> ID: {}, Name: {}, Permissions: {}, Users: {:?}",
+            self.id, self.name, permissions, self.users
+        )
+    }
+}
+
 impl Display for StreamState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Stream -> ID: {}, Name: {}", self.id, self.name,)?;
@@ -1,11 +1,19 @@
 use crate::streaming::batching::batch_filter::BatchItemizer;
 use crate::streaming::batching::iterator::IntoMessagesIterator;
 use crate::streaming::models::messages::RetainedMessage;
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::error::IggyError;
 use iggy::utils::{byte_size::IggyByteSize, sizeable::Sizeable};
 
 pub const RETAINED_BATCH_HEADER_LEN: u64 = 8 + 8 + 4 + 4;
 
+// Compressed batch payload starts with a zeroed marker (which can never be the length
+// of the first message in a regular payload), followed by the algorithm code and the
+// length of the decompressed payload, which keeps the existing segments readable as is.
+const COMPRESSED_BATCH_MARKER: u32 = 0;
+const COMPRESSED_BATCH_PREFIX_LEN: usize = 4 + 1 + 4;
+
 #[derive(Debug)]
 pub struct RetainedMessageBatch {
     pub base_offset: u64,
@@ -42,6 +50,66 @@ impl RetainedMessageBatch {
             || (self.base_offset <= end_offset && self.get_last_offset() >= start_offset)
     }
 
+    pub fn is_compressed(&self) -> bool {
+        self.bytes.len() >= COMPRESSED_BATCH_PREFIX_LEN
+            && u32::from_le_bytes(self.bytes[0..4].try_into().unwrap()) == COMPRESSED_BATCH_MARKER
+    }
+
+    /// Compresses the batch payload using the optional level, the header (offsets and timestamp) remains unchanged.
+    /// If the compressed payload (along with its prefix) isn't smaller, the batch is kept uncompressed.
+    pub fn compress(
+        self,
+        algorithm: CompressionAlgorithm,
+        level: Option<u8>,
+    ) -> Result<Self, IggyError> {
+        if algorithm == CompressionAlgorithm::None || self.bytes.is_empty() {
+            return Ok(self);
+        }
+
+        let compressed = algorithm.compress(&self.bytes, level)?;
+        if COMPRESSED_BATCH_PREFIX_LEN + compressed.len() >= self.bytes.len() {
+            return Ok(self);
+        }
+
+        let mut bytes = BytesMut::with_capacity(COMPRESSED_BATCH_PREFIX_LEN + compressed.len());
+        bytes.put_u32_le(COMPRESSED_BATCH_MARKER);
+        bytes.put_u8(algorithm.as_code());
+        bytes.put_u32_le(self.bytes.len() as u32);
+        bytes.put_slice(&compressed);
+        Ok(RetainedMessageBatch::new(
+            self.base_offset,
+            self.last_offset_delta,
+            self.max_timestamp,
+            IggyByteSize::from(bytes.len() as u64),
+            bytes.freeze(),
+        ))
+    }
+
+    /// Decompresses the batch payload, if it's not compressed, the batch is returned as is.
+    /// The decompressed payload cannot exceed the length stored in the batch prefix.
+    pub fn decompress(self) -> Result<Self, IggyError> {
+        if !self.is_compressed() {
+            return Ok(self);
+        }
+
+        let algorithm = CompressionAlgorithm::from_code(self.bytes[4])
+            .map_err(|_| IggyError::CannotDecompressData)?;
+        let length = u32::from_le_bytes(self.bytes[5..9].try_into().unwrap()) as usize;
+        let decompressed =
+            algorithm.decompress(&self.bytes[COMPRESSED_BATCH_PREFIX_LEN..], length)?;
+        if decompressed.len() != length {
+            return Err(IggyError::CannotDecompressData);
+        }
+
+        Ok(RetainedMessageBatch::new(
+            self.base_offset,
+            self.last_offset_delta,
+            self.max_timestamp,
+            IggyByteSize::from(length as u64),
+            Bytes::from(decompressed),
+        ))
+    }
+
     pub fn get_last_offset(&self) -> u64 {
         self.base_offset + self.last_offset_delta as u64
     }
@@ -84,3 +152,109 @@ impl Sizeable for RetainedMessageBatch {
         self.length + RETAINED_BATCH_HEADER_LEN.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::batching::batch_accumulator::BatchAccumulator;
+    use crate::streaming::models::messages::RetainedMessage;
+    use iggy::messages::send_messages::Message;
+    use ring::rand::{SecureRandom, SystemRandom};
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    fn create_batch() -> RetainedMessageBatch {
+        let messages = (0..10)
+            .map(|offset| Message::from_str(&format!("{{\"offset\": {offset}}}")).unwrap())
+            .collect::<Vec<_>>();
+        create_batch_from_messages(messages)
+    }
+
+    fn create_batch_from_messages(messages: Vec<Message>) -> RetainedMessageBatch {
+        let messages = messages
+            .into_iter()
+            .enumerate()
+            .map(|(offset, message)| {
+                let offset = offset as u64;
+                Arc::new(RetainedMessage::new(offset, 1000 + offset, message))
+            })
+            .collect::<Vec<_>>();
+        let batch_size = messages
+            .iter()
+            .map(|message| message.get_size_bytes())
+            .sum::<IggyByteSize>();
+        let mut accumulator = BatchAccumulator::new(0, messages.len());
+        accumulator.append(batch_size, &messages);
+        accumulator.materialize_batch_and_update_state()
+    }
+
+    #[test]
+    fn compressed_batch_should_be_decompressed_to_the_same_messages() {
+        for algorithm in [
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Lz4,
+            CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Snappy,
+        ] {
+            let batch = create_batch();
+            let expected_bytes = batch.bytes.clone();
+            let compressed = create_batch().compress(algorithm, None).unwrap();
+            assert!(compressed.is_compressed());
+            assert_eq!(compressed.bytes[4], algorithm.as_code());
+            assert_eq!(compressed.base_offset, batch.base_offset);
+            assert_eq!(compressed.last_offset_delta, batch.last_offset_delta);
+            assert_eq!(compressed.max_timestamp, batch.max_timestamp);
+
+            let decompressed = compressed.decompress().unwrap();
+            assert!(!decompressed.is_compressed());
+            assert_eq!(decompressed.length, batch.length);
+            assert_eq!(decompressed.bytes, expected_bytes);
+            assert_eq!(decompressed.into_messages_iter().count(), 10);
+        }
+    }
+
+    #[test]
+    fn incompressible_batch_should_be_kept_uncompressed() {
+        let random = SystemRandom::new();
+        let mut id = [0u8; 16];
+        let mut payload = [0u8; 64];
+        random.fill(&mut id).unwrap();
+        random.fill(&mut payload).unwrap();
+        let message = Message::new(
+            Some(u128::from_le_bytes(id)),
+            Bytes::copy_from_slice(&payload),
+            None,
+        );
+        let batch = create_batch_from_messages(vec![message]);
+        let expected_bytes = batch.bytes.clone();
+        let expected_length = batch.length;
+        for algorithm in [
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Lz4,
+            CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Snappy,
+        ] {
+            let batch = RetainedMessageBatch::new(
+                batch.base_offset,
+                batch.last_offset_delta,
+                batch.max_timestamp,
+                batch.length,
+                batch.bytes.clone(),
+            );
+            let batch = batch.compress(algorithm, None).unwrap();
+            assert!(!batch.is_compressed());
+            assert_eq!(batch.length, expected_length);
+            assert_eq!(batch.bytes, expected_bytes);
+        }
+    }
+
+    #[test]
+    fn uncompressed_batch_should_not_be_changed() {
+        let batch = create_batch();
+        let expected_bytes = batch.bytes.clone();
+        let batch = batch.compress(CompressionAlgorithm::None, None).unwrap();
+        assert!(!batch.is_compressed());
+        let batch = batch.decompress().unwrap();
+        assert_eq!(batch.bytes, expected_bytes);
+    }
+}
@@ -1,5 +1,6 @@
 use prometheus_client::encoding::text::encode;
 use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
 use prometheus_client::metrics::gauge::Gauge;
 use prometheus_client::registry::Registry;
 use tracing::error;
@@ -15,6 +16,7 @@ pub(crate) struct Metrics {
     messages: Gauge,
     users: Gauge,
     clients: Gauge,
+    retention_blocked_segments: Family<Vec<(String, String)>, Gauge>,
 }
 
 impl Metrics {
@@ -29,6 +31,7 @@ impl Metrics {
             messages: Gauge::default(),
             users: Gauge::default(),
             clients: Gauge::default(),
+            retention_blocked_segments: Family::default(),
         };
 
         metrics.register_counter("http_requests", metrics.http_requests.clone());
@@ -39,6 +42,11 @@ impl Metrics {
         metrics.register_gauge("messages", metrics.messages.clone());
         metrics.register_gauge("users", metrics.users.clone());
         metrics.register_gauge("clients", metrics.clients.clone());
+        metrics.registry.register(
+            "retention_blocked_segments",
+            "count of segments which cannot be removed until the consumer group consumes them",
+            metrics.retention_blocked_segments.clone(),
+        );
 
         metrics
     }
@@ -120,4 +128,25 @@ impl Metrics {
     pub fn decrement_clients(&self, count: u32) {
         self.clients.dec_by(count as i64);
     }
+
+    pub fn set_retention_blocked_segments(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        consumer_group: &str,
+        count: u32,
+    ) {
+        let labels = vec![
+            ("stream_id".to_owned(), stream_id.to_string()),
+            ("topic_id".to_owned(), topic_id.to_string()),
+            ("consumer_group".to_owned(), consumer_group.to_owned()),
+        ];
+        self.retention_blocked_segments
+            .get_or_create(&labels)
+            .set(count as i64);
+    }
+
+    pub fn clear_retention_blocked_segments(&self) {
+        self.retention_blocked_segments.clear();
+    }
 }
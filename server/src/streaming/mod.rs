@@ -15,5 +15,6 @@ pub mod storage;
 pub mod streams;
 pub mod systems;
 pub mod topics;
+pub mod transactions;
 pub mod users;
 pub mod utils;
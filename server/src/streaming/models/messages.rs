@@ -5,6 +5,7 @@ use bytes::{BufMut, Bytes, BytesMut};
 use error_set::ErrContext;
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::error::IggyError;
+use iggy::messages::message_filter::MessageFilter;
 use iggy::models::messages::PolledMessage;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::checksum;
@@ -24,6 +25,40 @@ pub struct PolledMessages {
     pub messages: Vec<Arc<PolledMessage>>,
 }
 
+/// The polled messages, either loaded into memory, or referencing the batches stored in the segment log files.
+#[derive(Debug)]
+pub enum PolledMessagesKind {
+    Messages(iggy::models::messages::PolledMessages),
+    Batches(PolledBatches),
+}
+
+/// The polled messages referencing the batches stored in the segment log files, rather than being loaded into memory,
+/// so they can be sent directly from the files to the socket. The batches might contain the messages outside
+/// the polled range, which are skipped by the client.
+#[derive(Debug)]
+pub struct PolledBatches {
+    pub partition_id: u32,
+    pub current_offset: u64,
+    pub generation: Option<u32>,
+    pub first_offset: u64,
+    pub last_offset: u64,
+    pub regions: Vec<LogRegion>,
+}
+
+/// The range of bytes in the segment log file, consisting of the whole message batches.
+#[derive(Debug, Clone)]
+pub struct LogRegion {
+    pub file: Arc<std::fs::File>,
+    pub position: u64,
+    pub length: u64,
+}
+
+impl PolledBatches {
+    pub fn get_size_bytes(&self) -> u64 {
+        self.regions.iter().map(|region| region.length).sum()
+    }
+}
+
 #[derive(Debug)]
 pub struct RetainedMessage {
     pub id: u128,
@@ -50,6 +85,16 @@ impl RetainedMessage {
         };
         Ok(message)
     }
+
+    /// Returns `true` if the message headers match the filter.
+    pub fn matches(&self, filter: &MessageFilter) -> Result<bool, IggyError> {
+        if filter.is_empty() {
+            return Ok(true);
+        }
+
+        let headers = self.headers.clone().map(HashMap::from_bytes).transpose()?;
+        Ok(filter.matches(headers.as_ref()))
+    }
 }
 
 impl RetainedMessage {
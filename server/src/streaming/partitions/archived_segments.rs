@@ -0,0 +1,891 @@
+use crate::archiver::ArchiverKind;
+use crate::configs::system::SystemConfig;
+use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::COMPONENT;
+use crate::streaming::polling_consumer::PollingConsumer;
+use crate::streaming::segments::*;
+use crate::streaming::utils::{file, random_id};
+use dashmap::DashMap;
+use error_set::ErrContext;
+use iggy::archives::restore_archive::ArchiveRange;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::error::IggyError;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::models::archive::RestoredArchive;
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::expiry::IggyExpiry;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::fs;
+use tracing::{error, info, trace};
+
+/// The archived segment fetched into the runtime directory, which is yet to be moved into the partition.
+#[derive(Debug)]
+struct FetchedSegment {
+    start_offset: u64,
+    log_path: String,
+    index_path: String,
+}
+
+/// The segments of the archive cache, which are being fetched or read, thus must not be evicted.
+#[derive(Debug, Default)]
+pub struct ArchiveCachePins {
+    pins: DashMap<PathBuf, usize>,
+}
+
+/// Keeps the cached archived segment pinned until it's dropped.
+#[derive(Debug)]
+pub struct ArchiveCachePin {
+    pins: Arc<ArchiveCachePins>,
+    log_path: PathBuf,
+}
+
+impl ArchiveCachePins {
+    fn pin(self: &Arc<Self>, log_path: &str) -> ArchiveCachePin {
+        let log_path = PathBuf::from(log_path);
+        *self.pins.entry(log_path.clone()).or_insert(0) += 1;
+        ArchiveCachePin {
+            pins: self.clone(),
+            log_path,
+        }
+    }
+
+    fn is_pinned(&self, log_path: &Path) -> bool {
+        self.pins.contains_key(log_path)
+    }
+}
+
+impl Drop for ArchiveCachePin {
+    fn drop(&mut self) {
+        self.pins.pins.remove_if_mut(&self.log_path, |_, count| {
+            *count -= 1;
+            *count == 0
+        });
+    }
+}
+
+/// Fetches the archived segments of the partition into the archive cache, it doesn't borrow
+/// the partition, so that the segments can be fetched without holding the partition lock.
+#[derive(Debug, Clone)]
+struct ArchivedSegmentFetcher {
+    archiver: Arc<ArchiverKind>,
+    pins: Arc<ArchiveCachePins>,
+    config: Arc<SystemConfig>,
+    stream_id: u32,
+    topic_id: u32,
+    partition_id: u32,
+    message_expiry: IggyExpiry,
+    compression_algorithm: CompressionAlgorithm,
+    compression_level: Option<u8>,
+}
+
+/// The range of offsets of the archived segment, its messages are fetched from the archiver
+/// once the segment is no longer stored locally.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArchivedSegment {
+    pub start_offset: u64,
+    pub end_offset: u64,
+}
+
+impl Partition {
+    pub fn get_archived_segments(&self) -> &[ArchivedSegment] {
+        &self.archived_segments
+    }
+
+    fn get_archived_segment_fetcher(&self) -> Option<ArchivedSegmentFetcher> {
+        if !self.config.tiered_storage.enabled {
+            return None;
+        }
+
+        Some(ArchivedSegmentFetcher {
+            archiver: self.storage.archiver.clone()?,
+            pins: self.storage.archive_cache_pins.clone(),
+            config: self.config.clone(),
+            stream_id: self.stream_id,
+            topic_id: self.topic_id,
+            partition_id: self.partition_id,
+            message_expiry: self.message_expiry,
+            compression_algorithm: self.compression_algorithm,
+            compression_level: self.compression_level,
+        })
+    }
+
+    /// Fetches the archived segments, from which the messages are about to be polled, into the archive cache
+    /// without holding the partition lock. The returned pins keep the segments cached until the messages are read.
+    pub async fn prefetch_archived_segments(
+        partition: &IggySharedMut<Partition>,
+        consumer: PollingConsumer,
+        strategy: PollingStrategy,
+        count: u32,
+    ) -> Result<Vec<ArchiveCachePin>, IggyError> {
+        let (fetcher, archived_segments) = {
+            let partition = partition.read().await;
+            let Some(fetcher) = partition.get_archived_segment_fetcher() else {
+                return Ok(Vec::new());
+            };
+            let Some(start_offset) = partition.get_polling_start_offset(consumer, strategy, count)
+            else {
+                return Ok(Vec::new());
+            };
+            (
+                fetcher,
+                partition.get_archived_segments_by_offset(start_offset, count),
+            )
+        };
+
+        let mut pins = Vec::with_capacity(archived_segments.len());
+        for archived_segment in &archived_segments {
+            pins.push(fetcher.fetch(archived_segment).await?);
+        }
+        Ok(pins)
+    }
+
+    /// Returns the archived segments preceding the first local segment, which contain the messages within the offsets.
+    fn get_archived_segments_by_offset(
+        &self,
+        start_offset: u64,
+        count: u32,
+    ) -> Vec<ArchivedSegment> {
+        let Some(first_local_segment) = self.segments.first() else {
+            return Vec::new();
+        };
+
+        if count == 0 || start_offset >= first_local_segment.start_offset {
+            return Vec::new();
+        }
+
+        let end_offset = start_offset.saturating_add(count as u64 - 1);
+        self.archived_segments
+            .iter()
+            .filter(|segment| {
+                segment.start_offset < first_local_segment.start_offset
+                    && segment.start_offset <= end_offset
+                    && segment.end_offset >= start_offset
+            })
+            .copied()
+            .collect()
+    }
+
+    fn get_first_local_offset(&self) -> u64 {
+        if !self.should_increment_offset {
+            return u64::MAX;
+        }
+
+        self.segments
+            .first()
+            .map_or(u64::MAX, |segment| segment.start_offset)
+    }
+
+    /// Registers the segment which has been archived, so that its messages can be still read after deleting it locally.
+    pub async fn add_archived_segment(
+        &mut self,
+        start_offset: u64,
+        end_offset: u64,
+    ) -> Result<(), IggyError> {
+        let archived_segment = ArchivedSegment {
+            start_offset,
+            end_offset,
+        };
+        if self.archived_segments.contains(&archived_segment) {
+            return Ok(());
+        }
+
+        self.archived_segments
+            .retain(|segment| segment.start_offset != start_offset);
+        self.archived_segments.push(archived_segment);
+        self.archived_segments
+            .sort_by_key(|segment| segment.start_offset);
+        self.persist_archived_segments().await
+    }
+
+    /// Retrieves the messages of the archived segments preceding the first segment stored locally (up to a specified count).
+    /// The segments are fetched from the archiver into the local cache, unless they have been already fetched.
+    pub(crate) async fn get_archived_messages_by_offset(
+        &self,
+        start_offset: u64,
+        count: u32,
+    ) -> Result<Vec<Arc<RetainedMessage>>, IggyError> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let Some(fetcher) = self.get_archived_segment_fetcher() else {
+            return Ok(Vec::new());
+        };
+
+        let first_local_offset = self
+            .segments
+            .first()
+            .map_or(u64::MAX, |segment| segment.start_offset);
+        let mut messages = Vec::with_capacity(count as usize);
+        let mut offset = start_offset;
+        for archived_segment in &self.archived_segments {
+            if archived_segment.start_offset >= first_local_offset || messages.len() as u32 >= count
+            {
+                break;
+            }
+
+            if archived_segment.end_offset < offset {
+                continue;
+            }
+
+            let _pin = fetcher.fetch(archived_segment).await?;
+            let segment = fetcher.load(archived_segment).await?;
+            let remaining_count = count - messages.len() as u32;
+            let segment_messages = segment
+                .get_messages_by_offset(offset, remaining_count)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to get messages from archived segment: {segment}, offset: {offset}, count: {remaining_count}"
+                    )
+                })?;
+            messages.extend(
+                segment_messages
+                    .into_iter()
+                    .filter(|message| message.offset < first_local_offset),
+            );
+            offset = archived_segment.end_offset + 1;
+        }
+
+        trace!(
+            "Read {} messages from the archived segments starting at offset: {start_offset} for partition with ID: {}.",
+            messages.len(),
+            self.partition_id
+        );
+        Ok(messages)
+    }
+
+    /// Restores the archived segments within the range, which precede the messages stored locally.
+    /// The segments are fetched into the runtime directory without holding the partition lock,
+    /// which is only acquired to move them into the partition and register them.
+    pub async fn restore_archived_segments(
+        partition: &IggySharedMut<Partition>,
+        range: ArchiveRange,
+    ) -> Result<RestoredArchive, IggyError> {
+        let (archiver, config, ids, partition_path, first_local_offset) = {
+            let partition = partition.read().await;
+            let archiver = partition
+                .storage
+                .archiver
+                .clone()
+                .ok_or(IggyError::ArchiverDisabled)?;
+            (
+                archiver,
+                partition.config.clone(),
+                (
+                    partition.stream_id,
+                    partition.topic_id,
+                    partition.partition_id,
+                ),
+                partition.partition_path.clone(),
+                partition.get_first_local_offset(),
+            )
+        };
+        let archived_files = archiver.list(&partition_path, None).await.map_err(|error| {
+            error!("Failed to list archived segments for partition: {partition_path}. Error: {error}");
+            IggyError::CannotRestoreArchivedFile(partition_path.clone())
+        })?;
+        let mut start_offsets = archived_files
+            .iter()
+            .map(Path::new)
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == LOG_EXTENSION)
+            })
+            .filter_map(|path| path.file_stem()?.to_str()?.parse::<u64>().ok())
+            .collect::<Vec<_>>();
+        start_offsets.sort();
+        start_offsets.dedup();
+        let start_offsets = start_offsets
+            .iter()
+            .enumerate()
+            .filter(|(index, start_offset)| {
+                if **start_offset >= first_local_offset {
+                    return false;
+                }
+
+                let ArchiveRange::Offsets { from, to } = range else {
+                    return true;
+                };
+                let end_offset = start_offsets
+                    .get(index + 1)
+                    .map_or(u64::MAX, |next_start_offset| next_start_offset - 1);
+                **start_offset <= to && end_offset >= from
+            })
+            .map(|(_, start_offset)| *start_offset)
+            .collect::<Vec<_>>();
+        if start_offsets.is_empty() {
+            return Ok(RestoredArchive::default());
+        }
+
+        let (stream_id, topic_id, partition_id) = ids;
+        let runtime_path = config.get_runtime_path();
+        let mut fetched_segments = Vec::with_capacity(start_offsets.len());
+        for start_offset in start_offsets {
+            let segment_path =
+                config.get_segment_path(stream_id, topic_id, partition_id, start_offset);
+            let fetched_path = format!("{runtime_path}/restored_segment_{}", random_id::get_ulid());
+            let fetched_segment = FetchedSegment {
+                start_offset,
+                log_path: format!("{fetched_path}.{LOG_EXTENSION}"),
+                index_path: format!("{fetched_path}.{INDEX_EXTENSION}"),
+            };
+            let fetched = async {
+                archiver
+                    .fetch(
+                        &format!("{segment_path}.{INDEX_EXTENSION}"),
+                        &fetched_segment.index_path,
+                        None,
+                    )
+                    .await?;
+                archiver
+                    .fetch(
+                        &format!("{segment_path}.{LOG_EXTENSION}"),
+                        &fetched_segment.log_path,
+                        None,
+                    )
+                    .await
+            }
+            .await;
+            if let Err(error) = fetched {
+                error!("Failed to fetch archived segment: {segment_path}. Error: {error}");
+                fetched_segments.push(fetched_segment);
+                remove_fetched_segments(fetched_segments).await;
+                return Err(IggyError::CannotRestoreArchivedFile(format!(
+                    "{segment_path}.{LOG_EXTENSION}"
+                )));
+            }
+            fetched_segments.push(fetched_segment);
+        }
+
+        let mut partition = partition.write().await;
+        partition
+            .register_restored_segments(fetched_segments, range)
+            .await
+    }
+
+    /// Moves the fetched segments into the partition, skipping the ones, which no longer precede
+    /// the messages stored locally. If the partition has no messages yet, its empty segment is replaced
+    /// by the restored ones, and the new segment is created right after the last restored one.
+    async fn register_restored_segments(
+        &mut self,
+        fetched_segments: Vec<FetchedSegment>,
+        range: ArchiveRange,
+    ) -> Result<RestoredArchive, IggyError> {
+        let is_empty = !self.should_increment_offset;
+        let first_local_offset = self.get_first_local_offset();
+        let (fetched_segments, outdated_segments) = fetched_segments
+            .into_iter()
+            .partition::<Vec<_>, _>(|segment| segment.start_offset < first_local_offset);
+        remove_fetched_segments(outdated_segments).await;
+        let mut restored_archive = RestoredArchive::default();
+        if fetched_segments.is_empty() {
+            return Ok(restored_archive);
+        }
+
+        // The empty segment of the partition has to be removed first, as it might be overwritten by the restored one.
+        let empty_start_offset = self.segments.first().map(|segment| segment.start_offset);
+        if is_empty {
+            let empty_start_offsets = self
+                .segments
+                .iter()
+                .map(|segment| segment.start_offset)
+                .collect::<Vec<_>>();
+            for start_offset in empty_start_offsets {
+                self.delete_segment(start_offset).await?;
+            }
+        }
+
+        let mut restored_segments = Vec::new();
+        let mut fetched_segments = fetched_segments.into_iter();
+        while let Some(fetched_segment) = fetched_segments.next() {
+            let mut segment = Segment::create(
+                self.stream_id,
+                self.topic_id,
+                self.partition_id,
+                fetched_segment.start_offset,
+                self.config.clone(),
+                self.message_expiry,
+                self.compression_algorithm,
+                self.compression_level,
+                self.size_of_parent_stream.clone(),
+                self.size_of_parent_topic.clone(),
+                self.size_bytes.clone(),
+                self.messages_count_of_parent_stream.clone(),
+                self.messages_count_of_parent_topic.clone(),
+                self.messages_count.clone(),
+            );
+            let moved = async {
+                file::rename(&fetched_segment.index_path, &segment.index_path).await?;
+                file::rename(&fetched_segment.log_path, &segment.log_path).await
+            }
+            .await;
+            if let Err(error) = moved {
+                error!("Failed to move restored segment: {segment}. Error: {error}");
+                remove_fetched_segments(std::iter::once(fetched_segment).chain(fetched_segments))
+                    .await;
+                return Err(IggyError::CannotRestoreArchivedFile(
+                    segment.log_path.clone(),
+                ));
+            }
+
+            segment.load_from_disk().await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load restored segment: {segment}")
+            })?;
+            segment.is_closed = true;
+            segment.end_offset = segment.current_offset;
+            if let ArchiveRange::Timestamps { from, to } = range {
+                let first_message = segment
+                    .get_messages_by_offset(segment.start_offset, 1)
+                    .await?;
+                let last_message = segment
+                    .get_messages_by_offset(segment.end_offset, 1)
+                    .await?;
+                let is_within_range = match (first_message.first(), last_message.first()) {
+                    (Some(first_message), Some(last_message)) => {
+                        first_message.timestamp <= to.as_micros()
+                            && last_message.timestamp >= from.as_micros()
+                    }
+                    _ => false,
+                };
+                if !is_within_range {
+                    segment.delete().await.with_error_context(|error| {
+                        format!("{COMPONENT} (error: {error}) - failed to delete restored segment outside of the range: {segment}")
+                    })?;
+                    continue;
+                }
+            }
+
+            restored_archive.segments_count += 1;
+            restored_archive.messages_count += segment.get_messages_count();
+            restored_segments.push(segment);
+        }
+
+        let last_end_offset = restored_segments.last().map(|segment| segment.end_offset);
+        self.segments_count_of_parent_stream
+            .fetch_add(restored_segments.len() as u32, Ordering::SeqCst);
+        self.segments.extend(restored_segments);
+        self.segments.sort_by_key(|segment| segment.start_offset);
+        self.rebuild_time_index();
+        self.persist_time_index().await?;
+        if is_empty {
+            match last_end_offset {
+                Some(last_end_offset) => {
+                    self.current_offset = last_end_offset;
+                    self.should_increment_offset = true;
+                    self.add_persisted_segment(last_end_offset + 1).await?;
+                }
+                None => {
+                    self.add_persisted_segment(empty_start_offset.unwrap_or_default())
+                        .await?;
+                }
+            }
+        }
+
+        info!(
+            "Restored {} archived segments with {} messages for partition with ID: {}, stream with ID: {}, topic with ID: {}.",
+            restored_archive.segments_count,
+            restored_archive.messages_count,
+            self.partition_id,
+            self.stream_id,
+            self.topic_id
+        );
+        Ok(restored_archive)
+    }
+
+    pub async fn persist_archived_segments(&self) -> Result<(), IggyError> {
+        self.storage
+            .partition
+            .save_archived_segments(&self.archived_segments_path, &self.archived_segments)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to persist archived segments for partition: {self}"
+                )
+            })
+    }
+
+    pub async fn load_archived_segments(&mut self) -> Result<(), IggyError> {
+        self.archived_segments = self
+            .storage
+            .partition
+            .load_archived_segments(&self.archived_segments_path)
+            .await?;
+        Ok(())
+    }
+}
+
+impl ArchivedSegmentFetcher {
+    /// Fetches the archived segment into the archive cache (unless it's already cached) and pins it,
+    /// so that it's not evicted until the returned pin is dropped.
+    async fn fetch(
+        &self,
+        archived_segment: &ArchivedSegment,
+    ) -> Result<ArchiveCachePin, IggyError> {
+        let (cached_log_path, cached_index_path) = self.get_cached_paths(archived_segment);
+        let pin = self.pins.pin(&cached_log_path);
+        if Path::new(&cached_log_path).exists() && Path::new(&cached_index_path).exists() {
+            return Ok(pin);
+        }
+
+        info!(
+            "Fetching archived segment with start offset: {} for partition with ID: {}, stream with ID: {}, topic with ID: {}...",
+            archived_segment.start_offset, self.partition_id, self.stream_id, self.topic_id
+        );
+        let archived_path = self.config.get_segment_path(
+            self.stream_id,
+            self.topic_id,
+            self.partition_id,
+            archived_segment.start_offset,
+        );
+        self.fetch_archived_file(
+            &format!("{archived_path}.{INDEX_EXTENSION}"),
+            &cached_index_path,
+        )
+        .await?;
+        self.fetch_archived_file(
+            &format!("{archived_path}.{LOG_EXTENSION}"),
+            &cached_log_path,
+        )
+        .await?;
+        evict_cached_segments(
+            &self.config.get_archive_cache_path(),
+            self.config.tiered_storage.cache_size,
+            &self.pins,
+        )
+        .await;
+        Ok(pin)
+    }
+
+    /// Loads the fetched archived segment, which is only read, thus it must not affect the stats of the partition.
+    async fn load(&self, archived_segment: &ArchivedSegment) -> Result<Segment, IggyError> {
+        let mut segment = Segment::create(
+            self.stream_id,
+            self.topic_id,
+            self.partition_id,
+            archived_segment.start_offset,
+            self.config.clone(),
+            self.message_expiry,
+            self.compression_algorithm,
+            self.compression_level,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+        );
+        let (cached_log_path, cached_index_path) = self.get_cached_paths(archived_segment);
+        segment.log_path = cached_log_path;
+        segment.index_path = cached_index_path;
+        segment
+            .load_archived(archived_segment.end_offset)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load archived segment: {segment}")
+            })?;
+        Ok(segment)
+    }
+
+    fn get_cached_paths(&self, archived_segment: &ArchivedSegment) -> (String, String) {
+        let cached_path = self.config.get_archive_cache_segment_path(
+            self.stream_id,
+            self.topic_id,
+            self.partition_id,
+            archived_segment.start_offset,
+        );
+        (
+            format!("{cached_path}.{LOG_EXTENSION}"),
+            format!("{cached_path}.{INDEX_EXTENSION}"),
+        )
+    }
+
+    // The file is fetched under the temporary name first, so that the concurrent reads never observe the partial file.
+    async fn fetch_archived_file(
+        &self,
+        archived_file: &str,
+        destination: &str,
+    ) -> Result<(), IggyError> {
+        let fetched_path = format!("{destination}.{}", random_id::get_ulid());
+        if let Err(error) = self
+            .archiver
+            .fetch(archived_file, &fetched_path, None)
+            .await
+        {
+            error!("Failed to fetch archived file: {archived_file}. Error: {error}");
+            let _ = file::remove(&fetched_path).await;
+            return Err(IggyError::CannotReadFile);
+        }
+
+        file::rename(&fetched_path, destination)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to rename fetched file: {fetched_path} to: {destination}")
+            })
+            .map_err(|_| IggyError::CannotReadFile)
+    }
+}
+
+async fn remove_fetched_segments(fetched_segments: impl IntoIterator<Item = FetchedSegment>) {
+    for fetched_segment in fetched_segments {
+        let _ = file::remove(&fetched_segment.log_path).await;
+        let _ = file::remove(&fetched_segment.index_path).await;
+    }
+}
+
+/// Removes the least recently fetched segments from the archive cache until it no longer exceeds the maximum size,
+/// skipping the pinned ones, which are being fetched or read.
+async fn evict_cached_segments(cache_path: &str, max_size: IggyByteSize, pins: &ArchiveCachePins) {
+    let mut cached_segments = Vec::new();
+    let mut total_size = 0;
+    let mut directories = vec![PathBuf::from(cache_path)];
+    while let Some(directory) = directories.pop() {
+        let Ok(mut entries) = fs::read_dir(&directory).await else {
+            continue;
+        };
+
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let Ok(metadata) = entry.metadata().await else {
+                continue;
+            };
+
+            let path = entry.path();
+            if metadata.is_dir() {
+                directories.push(path);
+                continue;
+            }
+
+            total_size += metadata.len();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == LOG_EXTENSION)
+            {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                cached_segments.push((modified, path));
+            }
+        }
+    }
+
+    let max_size = max_size.as_bytes_u64();
+    if total_size <= max_size {
+        return;
+    }
+
+    cached_segments.sort_by_key(|(modified, _)| *modified);
+    for (_, log_path) in cached_segments {
+        if total_size <= max_size {
+            break;
+        }
+
+        if pins.is_pinned(&log_path) {
+            continue;
+        }
+
+        let index_path = log_path.with_extension(INDEX_EXTENSION);
+        for path in [&log_path, &index_path] {
+            let Ok(metadata) = fs::metadata(path).await else {
+                continue;
+            };
+
+            if fs::remove_file(path).await.is_ok() {
+                total_size = total_size.saturating_sub(metadata.len());
+            }
+        }
+        trace!(
+            "Removed cached archived segment: {}",
+            log_path.to_string_lossy()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::server::DiskArchiverConfig;
+    use crate::configs::system::{SegmentConfig, SystemConfig, TieredStorageConfig};
+    use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
+    use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
+    use crate::streaming::storage::SystemStorage;
+    use bytes::Bytes;
+    use iggy::compression::compression_algorithm::CompressionAlgorithm;
+    use iggy::messages::send_messages::Message;
+    use iggy::utils::expiry::IggyExpiry;
+    use iggy::utils::sizeable::Sizeable;
+    use iggy::utils::timestamp::IggyTimestamp;
+    use std::sync::atomic::AtomicU32;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn messages_of_deleted_archived_segments_should_be_read_from_archiver() {
+        let (mut partition, archiver, _tempdir) = create_partition().await;
+        archive_segments(&mut partition, &archiver).await;
+        assert_eq!(partition.segments[0].start_offset, 6);
+
+        let messages = partition.get_messages_by_offset(0, 10).await.unwrap();
+        assert_eq!(
+            messages
+                .iter()
+                .map(|message| message.offset)
+                .collect::<Vec<_>>(),
+            (0..9).collect::<Vec<_>>()
+        );
+        assert_eq!(messages[4].payload, Bytes::from("message 4"));
+        let cached_path = partition.config.get_archive_cache_segment_path(
+            partition.stream_id,
+            partition.topic_id,
+            partition.partition_id,
+            3,
+        );
+        assert!(Path::new(&format!("{cached_path}.{LOG_EXTENSION}")).exists());
+
+        let messages = partition.get_messages_by_offset(4, 3).await.unwrap();
+        assert_eq!(
+            messages
+                .iter()
+                .map(|message| message.offset)
+                .collect::<Vec<_>>(),
+            vec![4, 5, 6]
+        );
+
+        let archived_segments = partition
+            .storage
+            .partition
+            .load_archived_segments(&partition.archived_segments_path)
+            .await
+            .unwrap();
+        assert_eq!(archived_segments, partition.archived_segments);
+    }
+
+    #[tokio::test]
+    async fn prefetched_archived_segments_should_not_be_evicted_until_read() {
+        let (mut partition, archiver, _tempdir) = create_partition().await;
+        archive_segments(&mut partition, &archiver).await;
+        let partition = IggySharedMut::new(partition);
+
+        let pins = Partition::prefetch_archived_segments(
+            &partition,
+            PollingConsumer::Consumer(1, 1),
+            PollingStrategy::offset(4),
+            3,
+        )
+        .await
+        .unwrap();
+        assert_eq!(pins.len(), 1);
+
+        let partition = partition.read().await;
+        let cache_path = partition.config.get_archive_cache_path();
+        let cached_log_path = format!(
+            "{}.{LOG_EXTENSION}",
+            partition.config.get_archive_cache_segment_path(
+                partition.stream_id,
+                partition.topic_id,
+                partition.partition_id,
+                3,
+            )
+        );
+        let pins_of_cache = partition.storage.archive_cache_pins.clone();
+        evict_cached_segments(&cache_path, IggyByteSize::from(0), &pins_of_cache).await;
+        assert!(Path::new(&cached_log_path).exists());
+
+        drop(pins);
+        evict_cached_segments(&cache_path, IggyByteSize::from(0), &pins_of_cache).await;
+        assert!(!Path::new(&cached_log_path).exists());
+    }
+
+    /// Archives and deletes the first two segments with 3 messages each, the third segment remains local.
+    async fn archive_segments(partition: &mut Partition, archiver: &ArchiverKind) {
+        for batch in 0..3 {
+            append_messages(partition, batch, 3).await;
+        }
+        let archived_offsets = partition.segments[..2]
+            .iter()
+            .map(|segment| (segment.start_offset, segment.end_offset))
+            .collect::<Vec<_>>();
+        assert_eq!(archived_offsets, vec![(0, 2), (3, 5)]);
+
+        for (start_offset, end_offset) in archived_offsets {
+            let segment = partition.get_segment(start_offset).unwrap();
+            archiver
+                .archive(&[&segment.index_path, &segment.log_path], None)
+                .await
+                .unwrap();
+            partition
+                .add_archived_segment(start_offset, end_offset)
+                .await
+                .unwrap();
+            partition.delete_segment(start_offset).await.unwrap();
+        }
+    }
+
+    async fn append_messages(partition: &mut Partition, batch: u64, count: u64) {
+        let messages = (batch * count..(batch + 1) * count)
+            .map(|offset| Message::new(None, Bytes::from(format!("message {offset}")), None))
+            .collect::<Vec<_>>();
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|message| message.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await
+            .unwrap();
+        partition.flush_unsaved_buffer(true).await.unwrap();
+    }
+
+    async fn create_partition() -> (Partition, Arc<ArchiverKind>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_str().unwrap().to_string();
+        let config = Arc::new(SystemConfig {
+            path: path.clone(),
+            segment: SegmentConfig {
+                size: IggyByteSize::from(1),
+                ..Default::default()
+            },
+            tiered_storage: TieredStorageConfig {
+                enabled: true,
+                cache_path: "archive_cache".to_string(),
+                cache_size: IggyByteSize::from(1_000_000),
+            },
+            ..Default::default()
+        });
+        let archiver = Arc::new(ArchiverKind::get_disk_archiver(DiskArchiverConfig {
+            path: format!("{path}/archive"),
+        }));
+        archiver.init().await.unwrap();
+        let storage = Arc::new(
+            SystemStorage::new(
+                config.clone(),
+                Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {})),
+            )
+            .with_archiver(Some(archiver.clone())),
+        );
+        let mut partition = Partition::create(
+            1,
+            2,
+            3,
+            true,
+            config,
+            storage,
+            IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU32::new(0)),
+            IggyTimestamp::now(),
+        )
+        .await;
+        partition.persist().await.unwrap();
+        (partition, archiver, temp_dir)
+    }
+}
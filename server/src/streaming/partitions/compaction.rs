@@ -0,0 +1,386 @@
+use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::COMPONENT;
+use bytes::Bytes;
+use error_set::ErrContext;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::error::IggyError;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::messages::send_messages::{get_message_key, is_tombstone};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::trace;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct CompactedSegments {
+    pub segments_count: u32,
+    pub messages_count: u64,
+}
+
+#[derive(Debug)]
+struct LatestKeyedMessage {
+    offset: u64,
+    timestamp: u64,
+    tombstone: bool,
+    segment: usize,
+}
+
+impl Partition {
+    /// Retains only the latest message for each message key in the closed segments of the compacted partition.
+    /// The messages without the key are always retained, while the latest tombstone for a key is removed
+    /// once the tombstones retention has passed. Segments with the messages of the open transactions are skipped.
+    /// The closed segments are streamed and compacted without holding the partition lock, which is only acquired
+    /// to take their snapshot and to swap the files of the segments, which contained any messages to remove.
+    pub async fn compact(
+        partition: &IggySharedMut<Partition>,
+        tombstones_retention: IggyDuration,
+        now: IggyTimestamp,
+    ) -> Result<CompactedSegments, IggyError> {
+        let mut compacted_segments = CompactedSegments::default();
+        let (partition_id, closed_segments) = {
+            let partition = partition.read().await;
+            if partition.cleanup_policy != CleanupPolicy::Compact {
+                return Ok(compacted_segments);
+            }
+
+            let last_stable_offset = partition.get_last_stable_offset();
+            let closed_segments = partition
+                .segments
+                .iter()
+                .filter(|segment| {
+                    last_stable_offset.is_none_or(|offset| segment.end_offset < offset)
+                })
+                .filter_map(|segment| segment.as_closed())
+                .collect::<Vec<_>>();
+            (partition.partition_id, closed_segments)
+        };
+        if closed_segments.is_empty() {
+            return Ok(compacted_segments);
+        }
+
+        // The messages of the open segment are not taken into account, which only means that
+        // the messages superseded by them will be removed during one of the next compactions.
+        let mut latest_messages = HashMap::new();
+        let mut dirty_segments = vec![false; closed_segments.len()];
+        for (index, segment) in closed_segments.iter().enumerate() {
+            segment
+                .for_each_message(|message| {
+                    let Some((key, tombstone)) = Self::get_compaction_key(message)? else {
+                        return Ok(());
+                    };
+                    let latest = LatestKeyedMessage {
+                        offset: message.offset,
+                        timestamp: message.timestamp,
+                        tombstone,
+                        segment: index,
+                    };
+                    if let Some(previous) = latest_messages.insert(key, latest) {
+                        dirty_segments[previous.segment] = true;
+                    }
+                    Ok(())
+                })
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to load messages from segment with start offset: {} for partition with ID: {partition_id}",
+                        segment.start_offset
+                    )
+                })?;
+        }
+
+        let tombstones_expiry = now
+            .as_micros()
+            .saturating_sub(tombstones_retention.as_micros());
+        for latest in latest_messages.values() {
+            if latest.tombstone && latest.timestamp <= tombstones_expiry {
+                dirty_segments[latest.segment] = true;
+            }
+        }
+
+        let is_latest = |message: &RetainedMessage| {
+            let Ok(Some((key, _))) = Self::get_compaction_key(message) else {
+                return true;
+            };
+            let Some(latest) = latest_messages.get(&key) else {
+                return true;
+            };
+            if latest.offset != message.offset {
+                return false;
+            }
+
+            !latest.tombstone || latest.timestamp > tombstones_expiry
+        };
+
+        for (segment, _) in closed_segments
+            .iter()
+            .zip(dirty_segments)
+            .filter(|(_, dirty)| *dirty)
+        {
+            let Some(compacted_segment) = segment.compact(&is_latest).await.with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to compact segment with start offset: {} for partition with ID: {partition_id}",
+                    segment.start_offset
+                )
+            })?
+            else {
+                continue;
+            };
+
+            let mut partition = partition.write().await;
+            let Some(current_segment) = partition
+                .get_segment_mut(segment.start_offset)
+                .filter(|current_segment| current_segment.is_closed)
+            else {
+                trace!(
+                    "Segment with start offset: {} was deleted during compaction of partition with ID: {partition_id}.",
+                    segment.start_offset
+                );
+                compacted_segment.discard().await;
+                continue;
+            };
+
+            let removed_messages_count = compacted_segment.removed_messages_count;
+            current_segment
+                .replace_with_compacted(compacted_segment)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to replace segment with start offset: {} with compacted one for partition with ID: {partition_id}",
+                        segment.start_offset
+                    )
+                })?;
+            if let Some(cache) = partition.cache.as_mut() {
+                cache.purge();
+            }
+            compacted_segments.segments_count += 1;
+            compacted_segments.messages_count += removed_messages_count;
+        }
+
+        trace!(
+            "Compacted {} segments, removed {} messages for partition with ID: {partition_id}.",
+            compacted_segments.segments_count,
+            compacted_segments.messages_count,
+        );
+        Ok(compacted_segments)
+    }
+
+    /// Retrieves the messages skipping the gaps left by the compaction (up to a specified count).
+    pub(crate) async fn get_compacted_messages_by_offset(
+        &self,
+        start_offset: u64,
+        count: u32,
+    ) -> Result<Vec<Arc<RetainedMessage>>, IggyError> {
+        let mut messages = Vec::with_capacity(count as usize);
+        let mut offset = start_offset;
+        let mut window = count;
+        while (messages.len() as u32) < count && offset <= self.current_offset {
+            let remaining_count = count - messages.len() as u32;
+            let mut read_messages = self
+                .read_messages_by_offset(offset, window.max(remaining_count))
+                .await?;
+            read_messages.truncate(remaining_count as usize);
+            match read_messages.last() {
+                Some(message) => {
+                    offset = message.offset + 1;
+                    window = remaining_count;
+                }
+                None => {
+                    offset += window.max(remaining_count) as u64;
+                    window = window.saturating_mul(2);
+                }
+            }
+            messages.extend(read_messages);
+        }
+
+        Ok(messages)
+    }
+
+    fn get_compaction_key(message: &RetainedMessage) -> Result<Option<(Bytes, bool)>, IggyError> {
+        let Some(headers) = &message.headers else {
+            return Ok(None);
+        };
+
+        let headers = HashMap::from_bytes(headers.clone())?;
+        let Some(key) = get_message_key(&headers)? else {
+            return Ok(None);
+        };
+
+        Ok(Some((key, is_tombstone(&headers)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::system::{SegmentConfig, SystemConfig};
+    use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
+    use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
+    use crate::streaming::segments::Segment;
+    use crate::streaming::storage::SystemStorage;
+    use iggy::compression::compression_algorithm::CompressionAlgorithm;
+    use iggy::messages::send_messages::{Message, MESSAGE_KEY_HEADER, TOMBSTONE_HEADER};
+    use iggy::models::header::{HeaderKey, HeaderValue};
+    use iggy::utils::byte_size::IggyByteSize;
+    use iggy::utils::expiry::IggyExpiry;
+    use iggy::utils::sizeable::Sizeable;
+    use std::path::Path;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicU32, AtomicU64};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn compaction_should_retain_only_latest_messages_for_each_key() {
+        let (partition, _tempdir) = create_partition().await;
+        let messages = vec![
+            create_message(Some("key-1"), false, "value 1.1"),
+            create_message(Some("key-2"), false, "value 2.1"),
+            create_message(Some("key-1"), false, "value 1.2"),
+            create_message(None, false, "value"),
+            create_message(Some("key-2"), true, ""),
+        ];
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|message| message.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        let partition = IggySharedMut::new(partition);
+        {
+            let mut partition = partition.write().await;
+            partition
+                .append_messages(appendable_batch_info, messages, None)
+                .await
+                .unwrap();
+            partition.flush_unsaved_buffer(true).await.unwrap();
+            assert!(partition.segments[0].is_closed);
+            assert_eq!(partition.get_messages_count(), 5);
+        }
+
+        let compacted_segments =
+            Partition::compact(&partition, IggyDuration::from(0), IggyTimestamp::now())
+                .await
+                .unwrap();
+        assert_eq!(
+            compacted_segments,
+            CompactedSegments {
+                segments_count: 1,
+                messages_count: 3,
+            }
+        );
+
+        let compacted_partition = partition.read().await;
+        assert_eq!(compacted_partition.get_messages_count(), 2);
+        assert!(
+            Path::new(&compacted_partition.segments[0].get_compaction_manifest_path()).exists()
+        );
+        let messages = compacted_partition
+            .get_messages_by_offset(0, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            messages
+                .iter()
+                .map(|message| message.offset)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(messages[0].payload, Bytes::from("value 1.2"));
+        assert_eq!(compacted_partition.get_next_offset(), 5);
+
+        let messages = compacted_partition
+            .get_messages_by_offset(3, 1)
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].offset, 3);
+
+        let mut loaded_segment = Segment::create(
+            compacted_partition.stream_id,
+            compacted_partition.topic_id,
+            compacted_partition.partition_id,
+            0,
+            compacted_partition.config.clone(),
+            IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+        );
+        loaded_segment.load_from_disk().await.unwrap();
+        assert_eq!(loaded_segment.get_messages_count(), 2);
+        drop(compacted_partition);
+
+        let compacted_segments =
+            Partition::compact(&partition, IggyDuration::from(0), IggyTimestamp::now())
+                .await
+                .unwrap();
+        assert_eq!(compacted_segments, CompactedSegments::default());
+    }
+
+    fn create_message(key: Option<&str>, tombstone: bool, payload: &str) -> Message {
+        let mut headers = HashMap::new();
+        if let Some(key) = key {
+            headers.insert(
+                HeaderKey::new(MESSAGE_KEY_HEADER).unwrap(),
+                HeaderValue::from_str(key).unwrap(),
+            );
+        }
+        if tombstone {
+            headers.insert(
+                HeaderKey::new(TOMBSTONE_HEADER).unwrap(),
+                HeaderValue::from_bool(true).unwrap(),
+            );
+        }
+        let headers = if headers.is_empty() {
+            None
+        } else {
+            Some(headers)
+        };
+        Message::new(None, Bytes::from(payload.to_string()), headers)
+    }
+
+    async fn create_partition() -> (Partition, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(SystemConfig {
+            path: temp_dir.path().to_path_buf().to_str().unwrap().to_string(),
+            segment: SegmentConfig {
+                size: IggyByteSize::from(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let storage = Arc::new(SystemStorage::new(
+            config.clone(),
+            Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {})),
+        ));
+        let mut partition = Partition::create(
+            1,
+            2,
+            3,
+            true,
+            config,
+            storage,
+            IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU32::new(0)),
+            IggyTimestamp::now(),
+        )
+        .await;
+        partition.cleanup_policy = CleanupPolicy::Compact;
+        partition.persist().await.unwrap();
+        (partition, temp_dir)
+    }
+}
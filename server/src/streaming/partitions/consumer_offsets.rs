@@ -66,6 +66,7 @@ impl Partition {
             }
         };
 
+        self.prune_delivery_attempts(consumer, offset);
         Ok(())
     }
 
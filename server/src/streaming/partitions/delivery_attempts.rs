@@ -0,0 +1,80 @@
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::polling_consumer::PollingConsumer;
+use dashmap::DashMap;
+use iggy::error::IggyError;
+use tracing::trace;
+
+impl Partition {
+    /// Records the failed delivery attempt of the message for the consumer, returns the number of attempts so far.
+    pub fn record_delivery_attempt(
+        &self,
+        consumer: PollingConsumer,
+        offset: u64,
+    ) -> Result<u32, IggyError> {
+        if self.get_messages_count() == 0 || offset > self.current_offset {
+            return Err(IggyError::InvalidOffset(offset));
+        }
+
+        let (delivery_attempts, consumer_id) = self.get_delivery_attempts(consumer);
+        let mut attempts = delivery_attempts.entry((consumer_id, offset)).or_insert(0);
+        *attempts += 1;
+        trace!(
+            "Recorded delivery attempt: {} of message at offset: {} for {}, partition: {}",
+            *attempts,
+            offset,
+            consumer,
+            self.partition_id
+        );
+        Ok(*attempts)
+    }
+
+    pub fn clear_delivery_attempts(&self, consumer: PollingConsumer, offset: u64) {
+        let (delivery_attempts, consumer_id) = self.get_delivery_attempts(consumer);
+        delivery_attempts.remove(&(consumer_id, offset));
+    }
+
+    /// Removes the delivery attempts of the consumer for the messages up to the committed offset.
+    pub fn prune_delivery_attempts(&self, consumer: PollingConsumer, committed_offset: u64) {
+        let (delivery_attempts, consumer_id) = self.get_delivery_attempts(consumer);
+        if delivery_attempts.is_empty() {
+            return;
+        }
+
+        delivery_attempts
+            .retain(|(id, offset), _| *id != consumer_id || *offset > committed_offset);
+    }
+
+    /// Removes the delivery attempts and poisoned markers of the messages up to the given offset, once their segments are deleted.
+    pub fn prune_dead_letter_state(&self, end_offset: u64) {
+        self.consumer_delivery_attempts
+            .retain(|(_, offset), _| *offset > end_offset);
+        self.consumer_group_delivery_attempts
+            .retain(|(_, offset), _| *offset > end_offset);
+        self.poisoned_offsets.retain(|offset| *offset > end_offset);
+    }
+
+    /// Marks the message as poisoned, returns `false` if it has been already marked.
+    pub fn mark_message_as_poisoned(&self, offset: u64) -> bool {
+        self.poisoned_offsets.insert(offset)
+    }
+
+    pub fn unmark_message_as_poisoned(&self, offset: u64) {
+        self.poisoned_offsets.remove(&offset);
+    }
+
+    pub fn is_message_poisoned(&self, offset: u64) -> bool {
+        !self.poisoned_offsets.is_empty() && self.poisoned_offsets.contains(&offset)
+    }
+
+    fn get_delivery_attempts(&self, consumer: PollingConsumer) -> (&DashMap<(u32, u64), u32>, u32) {
+        match consumer {
+            PollingConsumer::Consumer(consumer_id, _) => {
+                (&self.consumer_delivery_attempts, consumer_id)
+            }
+            // The attempts are shared by all the members of the consumer group.
+            PollingConsumer::ConsumerGroup(consumer_group_id, _) => {
+                (&self.consumer_group_delivery_attempts, consumer_group_id)
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
 use crate::streaming::batching::iterator::IntoMessagesIterator;
-use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::models::messages::{LogRegion, RetainedMessage};
 use crate::streaming::partitions::partition::Partition;
 use crate::streaming::partitions::COMPONENT;
 use crate::streaming::polling_consumer::PollingConsumer;
@@ -8,9 +8,15 @@ use crate::streaming::segments::*;
 use error_set::ErrContext;
 use iggy::confirmation::Confirmation;
 use iggy::error::IggyError;
-use iggy::messages::send_messages::Message;
+use iggy::messages::message_filter::MessageFilter;
+use iggy::messages::poll_messages::{PollingKind, PollingStrategy};
+use iggy::messages::send_messages::{get_transaction_id, Message, ProducerSequence};
 use iggy::models::messages::POLLED_MESSAGE_METADATA;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::sizeable::Sizeable;
 use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashMap;
 use std::sync::{atomic::Ordering, Arc};
 use tracing::{trace, warn};
 
@@ -37,11 +43,11 @@ impl Partition {
         let mut messages = Vec::new();
         let mut remaining = count as usize;
 
-        for segment in &self.segments {
-            if segment.end_timestamp < query_ts {
-                continue;
-            }
+        let Some(position) = self.find_segment_position_by_timestamp(query_ts) else {
+            return Ok(messages);
+        };
 
+        for segment in &self.segments[position..] {
             let segment_messages = segment
                 .get_messages_by_timestamp(query_ts, remaining)
                 .await
@@ -70,6 +76,20 @@ impl Partition {
         &self,
         start_offset: u64,
         count: u32,
+    ) -> Result<Vec<Arc<RetainedMessage>>, IggyError> {
+        if self.cleanup_policy == CleanupPolicy::Compact {
+            return self
+                .get_compacted_messages_by_offset(start_offset, count)
+                .await;
+        }
+
+        self.read_messages_by_offset(start_offset, count).await
+    }
+
+    pub(crate) async fn read_messages_by_offset(
+        &self,
+        start_offset: u64,
+        count: u32,
     ) -> Result<Vec<Arc<RetainedMessage>>, IggyError> {
         trace!(
             "Getting messages for start offset: {start_offset} for partition: {}, current offset: {}...",
@@ -80,6 +100,32 @@ impl Partition {
             return Ok(Vec::new());
         }
 
+        // The messages preceding the first local segment might be still available in the archive.
+        let first_local_offset = self.segments[0].start_offset;
+        if start_offset >= first_local_offset || self.archived_segments.is_empty() {
+            return self
+                .read_local_messages_by_offset(start_offset, count)
+                .await;
+        }
+
+        let mut messages = self
+            .get_archived_messages_by_offset(start_offset, count)
+            .await?;
+        let remaining_count = count - messages.len() as u32;
+        if remaining_count > 0 {
+            messages.extend(
+                self.read_local_messages_by_offset(first_local_offset, remaining_count)
+                    .await?,
+            );
+        }
+        Ok(messages)
+    }
+
+    async fn read_local_messages_by_offset(
+        &self,
+        start_offset: u64,
+        count: u32,
+    ) -> Result<Vec<Arc<RetainedMessage>>, IggyError> {
         let end_offset = self.get_end_offset(start_offset, count);
         if let Some(cached) = self.try_get_messages_from_cache(start_offset, end_offset) {
             return Ok(cached);
@@ -97,6 +143,106 @@ impl Partition {
         }
     }
 
+    /// Retrieves up to `count` messages from the start offset matching the filter, reading the segments until enough
+    /// messages are found or the end offset is reached. Returns the offset of the last message read as well.
+    pub async fn get_filtered_messages_by_offset(
+        &self,
+        start_offset: u64,
+        count: u32,
+        end_offset: u64,
+        filter: &MessageFilter,
+    ) -> Result<(Vec<Arc<RetainedMessage>>, Option<u64>), IggyError> {
+        let end_offset = end_offset.min(self.current_offset);
+        let mut messages = Vec::with_capacity(count as usize);
+        let mut last_offset = None;
+        if self.segments.is_empty() || count == 0 || start_offset > end_offset {
+            return Ok((messages, last_offset));
+        }
+
+        // The compacted and archived messages can't be read from the single segment, so they're filtered once read.
+        let first_local_offset = self.segments[0].start_offset;
+        if self.cleanup_policy == CleanupPolicy::Compact {
+            self.read_filtered_messages_by_offset(
+                start_offset,
+                count,
+                end_offset,
+                filter,
+                &mut messages,
+                &mut last_offset,
+            )
+            .await?;
+            return Ok((messages, last_offset));
+        }
+
+        if start_offset < first_local_offset && !self.archived_segments.is_empty() {
+            self.read_filtered_messages_by_offset(
+                start_offset,
+                count,
+                end_offset.min(first_local_offset - 1),
+                filter,
+                &mut messages,
+                &mut last_offset,
+            )
+            .await?;
+        }
+
+        let start_offset = start_offset.max(first_local_offset);
+        for segment in self.filter_segments_by_offsets(start_offset, end_offset) {
+            let remaining_count = count - messages.len() as u32;
+            if remaining_count == 0 {
+                break;
+            }
+
+            let (segment_messages, segment_last_offset) = segment
+                .get_filtered_messages_by_offset(start_offset, remaining_count, end_offset, filter)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to get filtered messages from segment, segment: {}, \
+                         offset: {}, count: {}",
+                        segment, start_offset, remaining_count
+                    )
+                })?;
+            messages.extend(segment_messages);
+            if segment_last_offset.is_some() {
+                last_offset = segment_last_offset;
+            }
+        }
+        Ok((messages, last_offset))
+    }
+
+    async fn read_filtered_messages_by_offset(
+        &self,
+        mut offset: u64,
+        count: u32,
+        end_offset: u64,
+        filter: &MessageFilter,
+        messages: &mut Vec<Arc<RetainedMessage>>,
+        last_offset: &mut Option<u64>,
+    ) -> Result<(), IggyError> {
+        while (messages.len() as u32) < count && offset <= end_offset {
+            let read_messages = self
+                .get_messages_by_offset(offset, count - messages.len() as u32)
+                .await?;
+            let Some(last_message) = read_messages.last() else {
+                break;
+            };
+
+            offset = last_message.offset + 1;
+            for message in read_messages {
+                if message.offset > end_offset || messages.len() as u32 == count {
+                    return Ok(());
+                }
+
+                *last_offset = Some(message.offset);
+                if message.matches(filter)? {
+                    messages.push(message);
+                }
+            }
+        }
+        Ok(())
+    }
+
     // Retrieves the first messages (up to a specified count).
     pub async fn get_first_messages(
         &self,
@@ -162,6 +308,84 @@ impl Partition {
         self.get_messages_by_offset(offset, count).await
     }
 
+    /// Returns the offset of the first message to be polled with the given strategy, or `None` if it can't be
+    /// resolved without reading the messages, or there are no messages to be polled.
+    pub fn get_polling_start_offset(
+        &self,
+        consumer: PollingConsumer,
+        strategy: PollingStrategy,
+        count: u32,
+    ) -> Option<u64> {
+        match strategy.kind {
+            PollingKind::Offset => Some(strategy.value),
+            PollingKind::Timestamp => None,
+            PollingKind::First => Some(0),
+            PollingKind::Last => {
+                let count = (count as u64).min(self.current_offset + 1);
+                Some(1 + self.current_offset - count)
+            }
+            PollingKind::Next => {
+                let (consumer_offsets, consumer_id) = match consumer {
+                    PollingConsumer::Consumer(consumer_id, _) => {
+                        (&self.consumer_offsets, consumer_id)
+                    }
+                    PollingConsumer::ConsumerGroup(group_id, _) => {
+                        (&self.consumer_group_offsets, group_id)
+                    }
+                };
+                match consumer_offsets.get(&consumer_id) {
+                    None => Some(0),
+                    Some(consumer_offset) if consumer_offset.offset == self.current_offset => None,
+                    Some(consumer_offset) => Some(consumer_offset.offset + 1),
+                }
+            }
+        }
+    }
+
+    /// Returns the regions of the segment log files with the messages in the given range and the offset
+    /// of the last one, if all of them can be sent directly from the local segments as stored.
+    pub fn get_log_regions_by_offset(
+        &self,
+        start_offset: u64,
+        count: u32,
+    ) -> Option<(Vec<LogRegion>, u64, u64)> {
+        if self.cleanup_policy == CleanupPolicy::Compact
+            || self.segments.is_empty()
+            || start_offset > self.current_offset
+        {
+            return None;
+        }
+
+        let first_local_offset = self.segments[0].start_offset;
+        if start_offset < first_local_offset && !self.archived_segments.is_empty() {
+            return None;
+        }
+
+        let start_offset = start_offset.max(first_local_offset);
+        let end_offset = self.get_end_offset(start_offset, count);
+        // The poisoned messages are marked only when loaded, so they have to be polled as the regular messages.
+        if self
+            .poisoned_offsets
+            .iter()
+            .any(|offset| (start_offset..=end_offset).contains(&*offset))
+        {
+            return None;
+        }
+
+        let regions = self
+            .filter_segments_by_offsets(start_offset, end_offset)
+            .into_iter()
+            .map(|segment| {
+                segment.get_log_region(start_offset, end_offset.min(segment.current_offset))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if regions.is_empty() {
+            return None;
+        }
+
+        Some((regions, start_offset, end_offset))
+    }
+
     fn get_end_offset(&self, offset: u64, count: u32) -> u64 {
         let mut end_offset = offset + (count - 1) as u64;
         let segment = self.segments.last().unwrap();
@@ -353,28 +577,27 @@ impl Partition {
         messages: Vec<Message>,
         confirmation: Option<Confirmation>,
     ) -> Result<(), IggyError> {
-        {
-            let last_segment = self.segments.last_mut().ok_or(IggyError::SegmentNotFound)?;
-            if last_segment.is_closed {
-                let start_offset = last_segment.end_offset + 1;
-                trace!(
-                    "Current segment is closed, creating new segment with start offset: {} for partition with ID: {}...",
-                    start_offset, self.partition_id
-                );
-                self.add_persisted_segment(start_offset).await.with_error_context(|error| format!(
-                    "{COMPONENT} (error: {error}) - failed to add persisted segment, partition: {}, start offset: {}",
-                    self, start_offset,
-                ))?;
-            }
+        self.ensure_open_segment().await?;
+        let messages_count_before_deduplication = messages.len();
+        let (messages, producer_sequences) = self.discard_duplicated_sequences(messages)?;
+        if messages.is_empty() {
+            return Ok(());
         }
 
-        let batch_size = appendable_batch_info.batch_size
-            + ((POLLED_MESSAGE_METADATA * messages.len() as u32) as u64).into();
-        let base_offset = if !self.should_increment_offset {
-            0
-        } else {
-            self.current_offset + 1
+        // All the messages in a batch belong to the same transaction (if any), which is validated by the system.
+        let transaction_id = match &messages[0].headers {
+            Some(headers) => get_transaction_id(headers)?,
+            None => None,
         };
+        let batch_size = if messages.len() == messages_count_before_deduplication {
+            appendable_batch_info.batch_size
+        } else {
+            messages
+                .iter()
+                .map(|message| message.get_size_bytes())
+                .sum::<IggyByteSize>()
+        } + ((POLLED_MESSAGE_METADATA * messages.len() as u32) as u64).into();
+        let base_offset = self.get_next_offset();
 
         let mut messages_count = 0u32;
         let mut retained_messages = Vec::with_capacity(messages.len());
@@ -406,7 +629,48 @@ impl Partition {
             return Ok(());
         }
 
-        let last_offset = base_offset + (messages_count - 1) as u64;
+        self.append_retained_messages(
+            batch_size,
+            retained_messages,
+            producer_sequences,
+            transaction_id,
+            confirmation,
+        )
+        .await
+    }
+
+    /// Creates the new segment if the last one has been closed.
+    pub(crate) async fn ensure_open_segment(&mut self) -> Result<(), IggyError> {
+        let last_segment = self.segments.last_mut().ok_or(IggyError::SegmentNotFound)?;
+        if last_segment.is_closed {
+            let start_offset = last_segment.end_offset + 1;
+            trace!(
+                "Current segment is closed, creating new segment with start offset: {} for partition with ID: {}...",
+                start_offset, self.partition_id
+            );
+            self.add_persisted_segment(start_offset).await.with_error_context(|error| format!(
+                "{COMPONENT} (error: {error}) - failed to add persisted segment, partition: {}, start offset: {}",
+                self, start_offset,
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Appends the messages, which already have the increasing offsets starting at (or after) the next offset of the partition,
+    /// to the last segment and the cache, and persists them once enough messages have been buffered.
+    pub(crate) async fn append_retained_messages(
+        &mut self,
+        batch_size: IggyByteSize,
+        retained_messages: Vec<Arc<RetainedMessage>>,
+        producer_sequences: HashMap<u64, ProducerSequence>,
+        transaction_id: Option<u64>,
+        confirmation: Option<Confirmation>,
+    ) -> Result<(), IggyError> {
+        let base_offset = retained_messages[0].offset;
+        let last_offset = retained_messages[retained_messages.len() - 1].offset;
+        let messages_count = retained_messages.len() as u32;
+        let is_new_transaction = transaction_id
+            .is_some_and(|transaction_id| self.begin_transaction(transaction_id, base_offset));
         if self.should_increment_offset {
             self.current_offset = last_offset;
         } else {
@@ -425,11 +689,13 @@ impl Partition {
                     )
                 })?;
         }
+        self.update_last_segment_time_range();
 
         if let Some(cache) = &mut self.cache {
             cache.extend(retained_messages);
         }
 
+        self.producer_sequences.extend(producer_sequences);
         self.unsaved_messages_count += messages_count;
         {
             let last_segment = self.segments.last_mut().ok_or(IggyError::SegmentNotFound)?;
@@ -444,12 +710,32 @@ impl Partition {
 
                 last_segment.persist_messages(confirmation).await.unwrap();
                 self.unsaved_messages_count = 0;
+                self.persist_producer_sequences().await?;
+                if self
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.is_closed)
+                {
+                    self.persist_time_index().await?;
+                }
             }
         }
 
+        if is_new_transaction {
+            self.persist_transactions().await?;
+        }
         Ok(())
     }
 
+    /// Returns the offset that will be assigned to the next appended message.
+    pub fn get_next_offset(&self) -> u64 {
+        if self.should_increment_offset {
+            self.current_offset + 1
+        } else {
+            0
+        }
+    }
+
     pub fn get_messages_count(&self) -> u64 {
         self.messages_count.load(Ordering::SeqCst)
     }
@@ -473,20 +759,41 @@ impl Partition {
             last_segment.persist_messages(None).await.unwrap();
         }
         self.unsaved_messages_count = 0;
+        self.persist_producer_sequences().await?;
+        if self
+            .segments
+            .last()
+            .is_some_and(|segment| segment.is_closed)
+        {
+            self.persist_time_index().await?;
+        }
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use bytes::Bytes;
+    use iggy::compression::compression_algorithm::CompressionAlgorithm;
+    use iggy::identifier::Identifier;
+    use iggy::messages::message_filter::HeaderCondition;
+    use iggy::messages::send_messages::{
+        ProducerSequence, PRODUCER_EPOCH_HEADER, PRODUCER_ID_HEADER, PRODUCER_SEQUENCE_HEADER,
+        TRANSACTION_ID_HEADER,
+    };
+    use iggy::models::header::{HeaderKey, HeaderValue};
+    use iggy::models::messages::{MessageState, PolledMessage};
     use iggy::utils::byte_size::IggyByteSize;
     use iggy::utils::expiry::IggyExpiry;
     use iggy::utils::sizeable::Sizeable;
+    use std::collections::HashMap;
     use std::sync::atomic::{AtomicU32, AtomicU64};
     use tempfile::TempDir;
 
     use super::*;
-    use crate::configs::system::{MessageDeduplicationConfig, SystemConfig};
+    use crate::configs::system::{
+        CacheConfig, IoBackend, MessageDeduplicationConfig, PartitionConfig, SystemConfig,
+    };
     use crate::streaming::partitions::create_messages;
     use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
     use crate::streaming::storage::SystemStorage;
@@ -515,6 +822,53 @@ mod tests {
         assert_eq!(loaded_messages.len(), messages_count as usize);
     }
 
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn given_io_uring_backend_messages_should_be_persisted_and_loaded_from_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(SystemConfig {
+            path: temp_dir.path().to_path_buf().to_str().unwrap().to_string(),
+            cache: CacheConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            partition: PartitionConfig {
+                io_backend: IoBackend::IoUring,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let mut partition = create_partition_with_config(config).await;
+        partition.persist().await.unwrap();
+        let messages = create_messages();
+        let messages_count = messages.len() as u32;
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await
+            .unwrap();
+        partition.flush_unsaved_buffer(true).await.unwrap();
+
+        let loaded_messages = partition
+            .get_messages_by_offset(0, messages_count)
+            .await
+            .unwrap();
+        assert_eq!(loaded_messages.len(), messages_count as usize);
+        assert_eq!(
+            loaded_messages
+                .iter()
+                .map(|message| message.offset)
+                .collect::<Vec<_>>(),
+            (0..messages_count as u64).collect::<Vec<_>>()
+        );
+    }
+
     #[tokio::test]
     async fn given_enabled_message_deduplication_only_messages_with_unique_id_should_be_appended() {
         let (mut partition, _tempdir) = create_partition(true).await;
@@ -540,11 +894,384 @@ mod tests {
         assert_eq!(loaded_messages.len(), unique_messages_count);
     }
 
+    #[tokio::test]
+    async fn delivery_attempts_should_be_shared_by_consumer_group_members() {
+        let (mut partition, _tempdir) = create_partition(false).await;
+        let offset = 1;
+        let first_member = PollingConsumer::consumer_group(1, 1);
+        let second_member = PollingConsumer::consumer_group(1, 2);
+        let consumer = PollingConsumer::consumer(&Identifier::numeric(1).unwrap(), 3);
+        assert_eq!(
+            partition
+                .record_delivery_attempt(first_member, offset)
+                .unwrap_err()
+                .as_code(),
+            IggyError::InvalidOffset(offset).as_code()
+        );
+
+        let messages = create_messages();
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            partition
+                .record_delivery_attempt(first_member, offset)
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            partition
+                .record_delivery_attempt(second_member, offset)
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            partition.record_delivery_attempt(consumer, offset).unwrap(),
+            1
+        );
+
+        partition.clear_delivery_attempts(first_member, offset);
+        assert_eq!(
+            partition
+                .record_delivery_attempt(second_member, offset)
+                .unwrap(),
+            1
+        );
+
+        assert!(!partition.is_message_poisoned(offset));
+        partition.mark_message_as_poisoned(offset);
+        assert!(partition.is_message_poisoned(offset));
+        assert!(!partition.is_message_poisoned(offset + 1));
+    }
+
+    #[tokio::test]
+    async fn delivery_attempts_and_poisoned_messages_should_be_pruned() {
+        let (mut partition, _tempdir) = create_partition(false).await;
+        let messages = create_messages();
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await
+            .unwrap();
+        let group = PollingConsumer::consumer_group(1, 1);
+        let consumer = PollingConsumer::consumer(&Identifier::numeric(1).unwrap(), 2);
+        for offset in 1..=3 {
+            partition.record_delivery_attempt(group, offset).unwrap();
+            partition.record_delivery_attempt(consumer, offset).unwrap();
+            partition.mark_message_as_poisoned(offset);
+        }
+
+        partition.prune_delivery_attempts(group, 2);
+        assert_eq!(partition.consumer_group_delivery_attempts.len(), 1);
+        assert_eq!(partition.consumer_delivery_attempts.len(), 3);
+
+        partition.prune_dead_letter_state(1);
+        assert_eq!(partition.consumer_delivery_attempts.len(), 2);
+        assert!(!partition.is_message_poisoned(1));
+        assert!(partition.is_message_poisoned(2));
+    }
+
+    #[tokio::test]
+    async fn given_idempotent_producer_only_messages_with_new_sequences_should_be_appended() {
+        let (mut partition, _tempdir) = create_partition(false).await;
+        partition.persist().await.unwrap();
+        let producer_id = 7;
+        // The new epoch starts the sequences over.
+        for (epoch, sequences) in [(1, 1..=3), (1, 1..=3), (1, 2..=5), (2, 1..=2)] {
+            let messages = create_producer_messages(producer_id, epoch, sequences);
+            let appendable_batch_info = AppendableBatchInfo {
+                batch_size: messages
+                    .iter()
+                    .map(|m| m.get_size_bytes())
+                    .sum::<IggyByteSize>(),
+                partition_id: partition.partition_id,
+            };
+            partition
+                .append_messages(appendable_batch_info, messages, None)
+                .await
+                .unwrap();
+        }
+
+        let loaded_messages = partition.get_messages_by_offset(0, 100).await.unwrap();
+        assert_eq!(loaded_messages.len(), 7);
+        let last_sequence = Some(ProducerSequence {
+            producer_id,
+            epoch: 2,
+            sequence: 2,
+        });
+        assert_eq!(partition.get_producer_sequence(producer_id), last_sequence);
+
+        // The messages of the previous epoch are rejected.
+        let messages = create_producer_messages(producer_id, 1, 6..=6);
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        let result = partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await;
+        assert!(matches!(result, Err(IggyError::ProducerFenced(7, 1, 2))));
+
+        // The sequences leaving a gap after the last one are rejected, the same as the newer epoch not starting over.
+        for (epoch, sequence, expected_sequence) in [(2, 4, 3), (3, 2, 1)] {
+            let messages = create_producer_messages(producer_id, epoch, sequence..=sequence);
+            let appendable_batch_info = AppendableBatchInfo {
+                batch_size: messages
+                    .iter()
+                    .map(|m| m.get_size_bytes())
+                    .sum::<IggyByteSize>(),
+                partition_id: partition.partition_id,
+            };
+            let result = partition
+                .append_messages(appendable_batch_info, messages, None)
+                .await;
+            assert!(
+                matches!(result, Err(IggyError::OutOfOrderSequence(7, actual, expected)) if actual == sequence && expected == expected_sequence)
+            );
+        }
+        assert_eq!(partition.get_producer_sequence(producer_id), last_sequence);
+
+        partition.flush_unsaved_buffer(true).await.unwrap();
+        partition.producer_sequences.clear();
+        partition.load_producer_sequences().await.unwrap();
+        assert_eq!(partition.get_producer_sequence(producer_id), last_sequence);
+
+        // The sequences should be restored from the messages if they haven't been stored yet.
+        tokio::fs::remove_file(&partition.producers_path)
+            .await
+            .unwrap();
+        partition.producer_sequences.clear();
+        partition.load_producer_sequences().await.unwrap();
+        assert_eq!(partition.get_producer_sequence(producer_id), last_sequence);
+    }
+
+    #[tokio::test]
+    async fn started_producer_epoch_should_fence_previous_epochs_and_be_stored() {
+        let (mut partition, _tempdir) = create_partition(false).await;
+        partition.persist().await.unwrap();
+        let producer_id = 7;
+        partition
+            .start_producer_epoch(producer_id, 3)
+            .await
+            .unwrap();
+
+        let messages = create_producer_messages(producer_id, 2, 1..=1);
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        let result = partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await;
+        assert!(matches!(result, Err(IggyError::ProducerFenced(7, 2, 3))));
+
+        let messages = create_producer_messages(producer_id, 3, 1..=2);
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            partition.get_messages_by_offset(0, 10).await.unwrap().len(),
+            2
+        );
+
+        partition.producer_sequences.clear();
+        partition.load_producer_sequences().await.unwrap();
+        assert_eq!(
+            partition
+                .get_producer_sequence(producer_id)
+                .map(|s| s.epoch),
+            Some(3)
+        );
+    }
+
+    #[tokio::test]
+    async fn given_open_and_aborted_transactions_only_committed_messages_should_be_returned() {
+        let (mut partition, _tempdir) = create_partition(false).await;
+        partition.persist().await.unwrap();
+        let messages = || {
+            vec![
+                create_transaction_message(0, Some(1)),
+                create_transaction_message(1, None),
+                create_transaction_message(2, Some(2)),
+                create_transaction_message(3, None),
+            ]
+        };
+        assert!(partition.begin_transaction(1, 0));
+        assert!(!partition.begin_transaction(1, 1));
+        assert!(partition.begin_transaction(2, 2));
+        assert_eq!(partition.get_last_stable_offset(), Some(0));
+
+        let (committed, last_offset) = partition.filter_committed_messages(messages()).unwrap();
+        assert!(committed.is_empty());
+        assert_eq!(last_offset, None);
+
+        partition.complete_transaction(1, true).await.unwrap();
+        partition.complete_transaction(2, false).await.unwrap();
+        assert_eq!(partition.get_last_stable_offset(), None);
+        let (committed, last_offset) = partition.filter_committed_messages(messages()).unwrap();
+        assert_eq!(
+            committed
+                .iter()
+                .map(|message| message.offset)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 3]
+        );
+        assert_eq!(last_offset, Some(3));
+
+        // The transactions left open are kept open after loading them, until they're aborted.
+        assert!(partition.begin_transaction(3, 4));
+        partition.persist_transactions().await.unwrap();
+        partition.open_transactions.clear();
+        partition.aborted_transactions.clear();
+        partition.load_transactions().await.unwrap();
+        assert!(partition.open_transactions.contains_key(&3));
+        partition.abort_open_transactions().await.unwrap();
+        assert!(partition.open_transactions.is_empty());
+        assert!(partition.aborted_transactions.contains_key(&2));
+        assert!(partition.aborted_transactions.contains_key(&3));
+    }
+
+    #[tokio::test]
+    async fn filtered_messages_should_be_read_until_enough_messages_match() {
+        let (mut partition, _tempdir) = create_partition(false).await;
+        partition.persist().await.unwrap();
+        let header_key = HeaderKey::new("kind").unwrap();
+        let messages = (0..10)
+            .map(|offset: u32| {
+                let headers = HashMap::from([(
+                    header_key.clone(),
+                    HeaderValue::from_uint32(offset % 2).unwrap(),
+                )]);
+                Message::new(
+                    None,
+                    Bytes::from(format!("message {offset}")),
+                    Some(headers),
+                )
+            })
+            .collect::<Vec<_>>();
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await
+            .unwrap();
+        partition.flush_unsaved_buffer(true).await.unwrap();
+
+        let filter = MessageFilter::new(vec![HeaderCondition::Equals(
+            header_key,
+            HeaderValue::from_uint32(1).unwrap(),
+        )]);
+        let get_offsets = |messages: Vec<Arc<RetainedMessage>>| {
+            messages
+                .iter()
+                .map(|message| message.offset)
+                .collect::<Vec<_>>()
+        };
+        let (messages, last_offset) = partition
+            .get_filtered_messages_by_offset(0, 3, partition.current_offset, &filter)
+            .await
+            .unwrap();
+        assert_eq!(get_offsets(messages), vec![1, 3, 5]);
+        assert_eq!(last_offset, Some(5));
+
+        let (messages, last_offset) = partition
+            .get_filtered_messages_by_offset(6, 3, partition.current_offset, &filter)
+            .await
+            .unwrap();
+        assert_eq!(get_offsets(messages), vec![7, 9]);
+        assert_eq!(last_offset, Some(9));
+
+        let (messages, last_offset) = partition
+            .get_filtered_messages_by_offset(0, 3, 2, &filter)
+            .await
+            .unwrap();
+        assert_eq!(get_offsets(messages), vec![1]);
+        assert_eq!(last_offset, Some(2));
+    }
+
+    fn create_transaction_message(offset: u64, transaction_id: Option<u64>) -> PolledMessage {
+        let headers = transaction_id.map(|transaction_id| {
+            HashMap::from([(
+                HeaderKey::new(TRANSACTION_ID_HEADER).unwrap(),
+                HeaderValue::from_uint64(transaction_id).unwrap(),
+            )])
+        });
+        PolledMessage::create(
+            offset,
+            MessageState::Available,
+            IggyTimestamp::now(),
+            offset as u128,
+            Bytes::from(format!("message {offset}")),
+            0,
+            headers,
+        )
+    }
+
+    fn create_producer_messages(
+        producer_id: u64,
+        epoch: u64,
+        sequences: std::ops::RangeInclusive<u64>,
+    ) -> Vec<Message> {
+        sequences
+            .map(|sequence| {
+                let headers = HashMap::from([
+                    (
+                        HeaderKey::new(PRODUCER_ID_HEADER).unwrap(),
+                        HeaderValue::from_uint64(producer_id).unwrap(),
+                    ),
+                    (
+                        HeaderKey::new(PRODUCER_EPOCH_HEADER).unwrap(),
+                        HeaderValue::from_uint64(epoch).unwrap(),
+                    ),
+                    (
+                        HeaderKey::new(PRODUCER_SEQUENCE_HEADER).unwrap(),
+                        HeaderValue::from_uint64(sequence).unwrap(),
+                    ),
+                ]);
+                Message::new(
+                    None,
+                    Bytes::from(format!("message {sequence}")),
+                    Some(headers),
+                )
+            })
+            .collect()
+    }
+
     async fn create_partition(deduplication_enabled: bool) -> (Partition, TempDir) {
-        let stream_id = 1;
-        let topic_id = 2;
-        let partition_id = 3;
-        let with_segment = true;
         let temp_dir = TempDir::new().unwrap();
         let config = Arc::new(SystemConfig {
             path: temp_dir.path().to_path_buf().to_str().unwrap().to_string(),
@@ -554,29 +1281,36 @@ mod tests {
             },
             ..Default::default()
         });
+        (create_partition_with_config(config).await, temp_dir)
+    }
+
+    async fn create_partition_with_config(config: Arc<SystemConfig>) -> Partition {
+        let stream_id = 1;
+        let topic_id = 2;
+        let partition_id = 3;
+        let with_segment = true;
         let storage = Arc::new(SystemStorage::new(
             config.clone(),
             Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {})),
         ));
 
-        (
-            Partition::create(
-                stream_id,
-                topic_id,
-                partition_id,
-                with_segment,
-                config,
-                storage,
-                IggyExpiry::NeverExpire,
-                Arc::new(AtomicU64::new(0)),
-                Arc::new(AtomicU64::new(0)),
-                Arc::new(AtomicU64::new(0)),
-                Arc::new(AtomicU64::new(0)),
-                Arc::new(AtomicU32::new(0)),
-                IggyTimestamp::now(),
-            )
-            .await,
-            temp_dir,
+        Partition::create(
+            stream_id,
+            topic_id,
+            partition_id,
+            with_segment,
+            config,
+            storage,
+            IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU32::new(0)),
+            IggyTimestamp::now(),
         )
+        .await
     }
 }
@@ -1,12 +1,19 @@
 use bytes::Bytes;
 use iggy::messages::send_messages;
 
+pub mod archived_segments;
+pub mod compaction;
 pub mod consumer_offsets;
+pub mod delivery_attempts;
 pub mod messages;
 pub mod partition;
 pub mod persistence;
+pub mod producers;
+pub mod replication;
 pub mod segments;
 pub mod storage;
+pub mod time_index;
+pub mod transactions;
 
 pub const COMPONENT: &str = "STREAMING_PARTITIONS";
 
@@ -3,16 +3,22 @@ use crate::streaming::cache::buffer::SmartCache;
 use crate::streaming::cache::memory_tracker::CacheMemoryTracker;
 use crate::streaming::deduplication::message_deduplicator::MessageDeduplicator;
 use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::partitions::archived_segments::ArchivedSegment;
+use crate::streaming::partitions::time_index::SegmentTimeRange;
 use crate::streaming::segments::*;
 use crate::streaming::storage::SystemStorage;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::ConsumerKind;
+use iggy::messages::send_messages::ProducerSequence;
 use iggy::models::stats::CacheMetrics;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::duration::IggyDuration;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
 use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
@@ -26,6 +32,10 @@ pub struct Partition {
     pub offsets_path: String,
     pub consumer_offsets_path: String,
     pub consumer_group_offsets_path: String,
+    pub producers_path: String,
+    pub transactions_path: String,
+    pub archived_segments_path: String,
+    pub time_index_path: String,
     pub current_offset: u64,
     pub cache: Option<SmartCache<Arc<RetainedMessage>>>,
     pub cached_memory_tracker: Option<Arc<CacheMemoryTracker>>,
@@ -42,9 +52,20 @@ pub struct Partition {
     pub size_bytes: Arc<AtomicU64>,
     pub segments_count_of_parent_stream: Arc<AtomicU32>,
     pub(crate) message_expiry: IggyExpiry,
+    pub(crate) compression_algorithm: CompressionAlgorithm,
+    pub(crate) compression_level: Option<u8>,
+    pub(crate) cleanup_policy: CleanupPolicy,
     pub(crate) consumer_offsets: DashMap<u32, ConsumerOffset>,
     pub(crate) consumer_group_offsets: DashMap<u32, ConsumerOffset>,
+    pub(crate) consumer_delivery_attempts: DashMap<(u32, u64), u32>,
+    pub(crate) consumer_group_delivery_attempts: DashMap<(u32, u64), u32>,
+    pub(crate) poisoned_offsets: DashSet<u64>,
+    pub(crate) producer_sequences: HashMap<u64, ProducerSequence>,
+    pub(crate) open_transactions: HashMap<u64, u64>,
+    pub(crate) aborted_transactions: HashMap<u64, u64>,
     pub(crate) segments: Vec<Segment>,
+    pub(crate) archived_segments: Vec<ArchivedSegment>,
+    pub(crate) time_index: Vec<SegmentTimeRange>,
     pub(crate) config: Arc<SystemConfig>,
     pub(crate) storage: Arc<SystemStorage>,
 }
@@ -78,6 +99,8 @@ impl Partition {
         config: Arc<SystemConfig>,
         storage: Arc<SystemStorage>,
         message_expiry: IggyExpiry,
+        compression_algorithm: CompressionAlgorithm,
+        compression_level: Option<u8>,
         messages_count_of_parent_stream: Arc<AtomicU64>,
         messages_count_of_parent_topic: Arc<AtomicU64>,
         size_of_parent_stream: Arc<AtomicU64>,
@@ -91,6 +114,11 @@ impl Partition {
             config.get_consumer_offsets_path(stream_id, topic_id, partition_id);
         let consumer_group_offsets_path =
             config.get_consumer_group_offsets_path(stream_id, topic_id, partition_id);
+        let producers_path = config.get_producers_path(stream_id, topic_id, partition_id);
+        let transactions_path = config.get_transactions_path(stream_id, topic_id, partition_id);
+        let archived_segments_path =
+            config.get_archived_segments_path(stream_id, topic_id, partition_id);
+        let time_index_path = config.get_time_index_path(stream_id, topic_id, partition_id);
         let (cached_memory_tracker, messages) = match config.cache.enabled {
             false => (None, None),
             true => (
@@ -107,7 +135,14 @@ impl Partition {
             offsets_path,
             consumer_offsets_path,
             consumer_group_offsets_path,
+            producers_path,
+            transactions_path,
+            archived_segments_path,
+            time_index_path,
             message_expiry,
+            compression_algorithm,
+            compression_level,
+            cleanup_policy: CleanupPolicy::default(),
             cache: messages,
             cached_memory_tracker,
             message_deduplicator: match config.message_deduplication.enabled {
@@ -128,11 +163,19 @@ impl Partition {
                 false => None,
             },
             segments: vec![],
+            archived_segments: vec![],
+            time_index: vec![],
             current_offset: 0,
             unsaved_messages_count: 0,
             should_increment_offset: false,
             consumer_offsets: DashMap::new(),
             consumer_group_offsets: DashMap::new(),
+            consumer_delivery_attempts: DashMap::new(),
+            consumer_group_delivery_attempts: DashMap::new(),
+            poisoned_offsets: DashSet::new(),
+            producer_sequences: HashMap::new(),
+            open_transactions: HashMap::new(),
+            aborted_transactions: HashMap::new(),
             config,
             storage,
             created_at,
@@ -154,6 +197,8 @@ impl Partition {
                 0,
                 partition.config.clone(),
                 partition.message_expiry,
+                partition.compression_algorithm,
+                partition.compression_level,
                 partition.size_of_parent_stream.clone(),
                 partition.size_of_parent_topic.clone(),
                 partition.size_bytes.clone(),
@@ -165,6 +210,7 @@ impl Partition {
             partition
                 .segments_count_of_parent_stream
                 .fetch_add(1, Ordering::SeqCst);
+            partition.rebuild_time_index();
         }
 
         partition
@@ -214,6 +260,7 @@ mod tests {
     use crate::streaming::partitions::partition::Partition;
     use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
     use crate::streaming::storage::SystemStorage;
+    use iggy::compression::compression_algorithm::CompressionAlgorithm;
     use iggy::utils::duration::IggyDuration;
     use iggy::utils::expiry::IggyExpiry;
     use iggy::utils::timestamp::IggyTimestamp;
@@ -246,6 +293,8 @@ mod tests {
             config,
             storage,
             message_expiry,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -296,6 +345,8 @@ mod tests {
             }),
             storage,
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -328,6 +379,8 @@ mod tests {
             Arc::new(SystemConfig::default()),
             storage,
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -36,6 +36,9 @@ impl Partition {
         self.should_increment_offset = false;
         self.consumer_offsets.clear();
         self.consumer_group_offsets.clear();
+        self.consumer_delivery_attempts.clear();
+        self.consumer_group_delivery_attempts.clear();
+        self.poisoned_offsets.clear();
         if let Some(cache) = self.cache.as_mut() {
             cache.purge();
         }
@@ -47,6 +50,14 @@ impl Partition {
                 .fetch_sub(1, Ordering::SeqCst);
         }
         self.segments.clear();
+        if !self.archived_segments.is_empty() {
+            self.archived_segments.clear();
+            self.persist_archived_segments()
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to delete archived segments in partition: {self}")
+                })?;
+        }
         self.storage
             .partition
             .delete_consumer_offsets(&self.consumer_offsets_path)
@@ -0,0 +1,196 @@
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::COMPONENT;
+use error_set::ErrContext;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::{get_producer_sequence, Message, ProducerSequence};
+use std::collections::HashMap;
+use tracing::{error, info, trace, warn};
+
+const REPLAYED_MESSAGES_BATCH: u32 = 1000;
+
+/// The latest sequence numbers of the idempotent producers, stored along with the offset of the last persisted message.
+#[derive(Debug, Default, PartialEq)]
+pub struct StoredProducerSequences {
+    pub offset: u64,
+    pub sequences: HashMap<u64, ProducerSequence>,
+}
+
+impl Partition {
+    /// Discards the messages of the idempotent producers with the sequence numbers lower than or equal to the last
+    /// appended ones within the same epoch (e.g. sent again after a timeout). The newer epoch starts the sequences over,
+    /// while the messages of the older epoch are rejected, as the producer has been restarted or has given up on them.
+    /// The sequences following the last one must not leave a gap, otherwise the batch sent earlier has been lost or reordered.
+    /// Returns the remaining messages and the latest sequence of each producer, which should be stored once the messages are appended.
+    pub fn discard_duplicated_sequences(
+        &self,
+        messages: Vec<Message>,
+    ) -> Result<(Vec<Message>, HashMap<u64, ProducerSequence>), IggyError> {
+        let mut sequences = HashMap::new();
+        let mut unique_messages = Vec::with_capacity(messages.len());
+        for message in messages {
+            let Some(headers) = &message.headers else {
+                unique_messages.push(message);
+                continue;
+            };
+            let Some(sequence) = get_producer_sequence(headers)? else {
+                unique_messages.push(message);
+                continue;
+            };
+
+            let producer_id = sequence.producer_id;
+            let mut expected_sequence = 1;
+            if let Some(last) = sequences
+                .get(&producer_id)
+                .or_else(|| self.producer_sequences.get(&producer_id))
+            {
+                if sequence.epoch < last.epoch {
+                    error!(
+                        "Producer with ID: {producer_id} and epoch: {} has been fenced by the epoch: {} for partition with ID: {}.",
+                        sequence.epoch, last.epoch, self.partition_id
+                    );
+                    return Err(IggyError::ProducerFenced(
+                        producer_id,
+                        sequence.epoch,
+                        last.epoch,
+                    ));
+                }
+
+                if sequence.epoch == last.epoch && sequence.sequence <= last.sequence {
+                    warn!(
+                        "Ignored the duplicated sequence: {} (last: {}) of producer with ID: {producer_id} for partition with ID: {}.",
+                        sequence.sequence, last.sequence, self.partition_id
+                    );
+                    continue;
+                }
+
+                if sequence.epoch == last.epoch {
+                    expected_sequence = last.sequence + 1;
+                }
+            }
+
+            if sequence.sequence != expected_sequence {
+                error!(
+                    "Producer with ID: {producer_id} and epoch: {} sent the sequence: {} out of order, expected: {expected_sequence} for partition with ID: {}.",
+                    sequence.epoch, sequence.sequence, self.partition_id
+                );
+                return Err(IggyError::OutOfOrderSequence(
+                    producer_id,
+                    sequence.sequence,
+                    expected_sequence,
+                ));
+            }
+
+            sequences.insert(producer_id, sequence);
+            unique_messages.push(message);
+        }
+        Ok((unique_messages, sequences))
+    }
+
+    pub fn get_producer_sequence(&self, producer_id: u64) -> Option<ProducerSequence> {
+        self.producer_sequences.get(&producer_id).copied()
+    }
+
+    /// Starts the given epoch of the idempotent producer, so the messages of its older epochs get rejected,
+    /// while the sequences of the new epoch start over. The epoch is stored to be preserved across the restarts.
+    pub async fn start_producer_epoch(
+        &mut self,
+        producer_id: u64,
+        epoch: u64,
+    ) -> Result<(), IggyError> {
+        self.producer_sequences.insert(
+            producer_id,
+            ProducerSequence {
+                producer_id,
+                epoch,
+                sequence: 0,
+            },
+        );
+        self.persist_producer_sequences().await
+    }
+
+    /// Stores the latest sequence numbers along with the offset of the last persisted message.
+    pub async fn persist_producer_sequences(&self) -> Result<(), IggyError> {
+        if self.producer_sequences.is_empty() {
+            return Ok(());
+        }
+
+        self.storage
+            .partition
+            .save_producer_sequences(
+                &self.producers_path,
+                self.current_offset,
+                &self.producer_sequences,
+            )
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to persist producer sequences for partition: {self}"
+                )
+            })
+    }
+
+    /// Loads the stored sequence numbers and replays the messages of the last segment appended after they were stored.
+    pub async fn load_producer_sequences(&mut self) -> Result<(), IggyError> {
+        let mut start_offset = self
+            .segments
+            .last()
+            .map(|segment| segment.start_offset)
+            .unwrap_or_default();
+        if let Some(stored) = self
+            .storage
+            .partition
+            .load_producer_sequences(&self.producers_path)
+            .await?
+        {
+            self.producer_sequences = stored.sequences;
+            start_offset = start_offset.max(stored.offset + 1);
+        }
+
+        if self.get_messages_count() == 0 || start_offset > self.current_offset {
+            return Ok(());
+        }
+
+        trace!(
+            "Replaying producer sequences from offset: {start_offset} to: {} for partition with ID: {}...",
+            self.current_offset,
+            self.partition_id
+        );
+        while start_offset <= self.current_offset {
+            let count =
+                (self.current_offset - start_offset + 1).min(REPLAYED_MESSAGES_BATCH as u64);
+            let messages = self
+                .get_messages_by_offset(start_offset, count as u32)
+                .await?;
+            let Some(last_message) = messages.last() else {
+                break;
+            };
+
+            start_offset = last_message.offset + 1;
+            for message in messages {
+                let Some(headers) = &message.headers else {
+                    continue;
+                };
+                let headers = HashMap::from_bytes(headers.clone())?;
+                if let Some(sequence) = get_producer_sequence(&headers)? {
+                    let last = self
+                        .producer_sequences
+                        .entry(sequence.producer_id)
+                        .or_insert(sequence);
+                    if (sequence.epoch, sequence.sequence) > (last.epoch, last.sequence) {
+                        *last = sequence;
+                    }
+                }
+            }
+        }
+
+        if !self.producer_sequences.is_empty() {
+            info!(
+                "Loaded sequences of {} producer(s) for partition with ID: {}.",
+                self.producer_sequences.len(),
+                self.partition_id
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,439 @@
+use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::COMPONENT;
+use error_set::ErrContext;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::{get_producer_sequence, get_transaction_id};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::sizeable::Sizeable;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+const TRUNCATED_MESSAGES_BATCH: u32 = 1000;
+
+impl Partition {
+    /// Appends the messages replicated from the leader, keeping their offsets and timestamps.
+    /// The messages have been already deduplicated by the leader, so only their producer sequences are stored.
+    /// They must start at the next offset of the partition and be consecutive, unless the partition is compacted.
+    pub async fn append_replicated_messages(
+        &mut self,
+        messages: Vec<Arc<RetainedMessage>>,
+    ) -> Result<(), IggyError> {
+        let Some(first_message) = messages.first() else {
+            return Ok(());
+        };
+
+        let is_compacted = self.cleanup_policy == CleanupPolicy::Compact;
+        let next_offset = self.get_next_offset();
+        if first_message.offset < next_offset
+            || !is_compacted && first_message.offset != next_offset
+        {
+            warn!(
+                "{COMPONENT} - replicated message with offset: {} doesn't match the next offset: {next_offset} for partition with ID: {}",
+                first_message.offset, self.partition_id
+            );
+            return Err(IggyError::InvalidOffset(first_message.offset));
+        }
+
+        if let Some(message) = messages.windows(2).find_map(|pair| {
+            let is_valid = match is_compacted {
+                true => pair[1].offset > pair[0].offset,
+                false => pair[1].offset == pair[0].offset + 1,
+            };
+            (!is_valid).then_some(&pair[1])
+        }) {
+            warn!(
+                "{COMPONENT} - replicated message with offset: {} is out of order for partition with ID: {}",
+                message.offset, self.partition_id
+            );
+            return Err(IggyError::InvalidOffset(message.offset));
+        }
+
+        self.ensure_open_segment().await?;
+        let mut producer_sequences = HashMap::new();
+        let mut transaction_id = None;
+        for message in &messages {
+            let Some(headers) = &message.headers else {
+                continue;
+            };
+
+            let headers = HashMap::from_bytes(headers.clone())?;
+            if let Some(sequence) = get_producer_sequence(&headers)? {
+                producer_sequences.insert(sequence.producer_id, sequence);
+            }
+            if transaction_id.is_none() {
+                transaction_id = get_transaction_id(&headers)?;
+            }
+        }
+        if let Some(message_deduplicator) = &self.message_deduplicator {
+            for message in &messages {
+                message_deduplicator.try_insert(&message.id).await;
+            }
+        }
+
+        let batch_size = messages
+            .iter()
+            .map(|message| message.get_size_bytes())
+            .sum::<IggyByteSize>();
+        self.append_retained_messages(
+            batch_size,
+            messages,
+            producer_sequences,
+            transaction_id,
+            None,
+        )
+        .await
+    }
+
+    /// Removes the messages starting at the given offset, e.g. the ones appended by the previous leader,
+    /// which conflict with the partition of the current leader. The segments starting after the offset are deleted,
+    /// while the messages preceding the offset in the segment containing it are rewritten to the new segment.
+    /// The sequences of the producers, whose messages have been removed, are forgotten, so their retries
+    /// aren't discarded as duplicates. Returns the number of the removed messages.
+    pub async fn truncate_messages(&mut self, offset: u64) -> Result<u64, IggyError> {
+        let next_offset = self.get_next_offset();
+        if offset >= next_offset {
+            return Ok(0);
+        }
+
+        self.flush_unsaved_buffer(true).await?;
+        let segment_start_offset = self
+            .segments
+            .iter()
+            .rev()
+            .find(|segment| segment.start_offset <= offset)
+            .map(|segment| segment.start_offset)
+            .unwrap_or(offset);
+        let retained_messages = match offset - segment_start_offset {
+            0 => Vec::new(),
+            count => self
+                .read_messages_by_offset(segment_start_offset, count as u32)
+                .await?
+                .into_iter()
+                .filter(|message| message.offset < offset)
+                .collect::<Vec<_>>(),
+        };
+
+        let mut producer_ids = HashSet::new();
+        let mut read_offset = offset;
+        while read_offset < next_offset {
+            let messages = self
+                .read_messages_by_offset(read_offset, TRUNCATED_MESSAGES_BATCH)
+                .await?;
+            let Some(last_message) = messages.last() else {
+                break;
+            };
+
+            read_offset = last_message.offset + 1;
+            for message in &messages {
+                let Some(headers) = &message.headers else {
+                    continue;
+                };
+
+                let headers = HashMap::from_bytes(headers.clone())?;
+                if let Some(sequence) = get_producer_sequence(&headers)? {
+                    producer_ids.insert(sequence.producer_id);
+                }
+            }
+        }
+
+        self.producer_sequences
+            .retain(|producer_id, _| !producer_ids.contains(producer_id));
+        self.restart_segments_at(offset, segment_start_offset)
+            .await?;
+        self.append_replicated_messages(retained_messages).await?;
+        self.flush_unsaved_buffer(true).await?;
+        self.persist_producer_sequences().await?;
+        self.persist_transactions().await?;
+
+        let removed_messages_count = next_offset - offset;
+        info!(
+            "Truncated {removed_messages_count} messages starting at offset: {offset} for partition with ID: {}, stream with ID: {}, topic with ID: {}.",
+            self.partition_id, self.stream_id, self.topic_id
+        );
+        Ok(removed_messages_count)
+    }
+
+    /// Removes all the messages and continues the partition at the given offset, which is used when the messages
+    /// preceding it are no longer available on the leader (e.g. removed by the retention), so they can't be replicated.
+    /// Returns the number of the removed messages.
+    pub async fn skip_messages_to(&mut self, offset: u64) -> Result<u64, IggyError> {
+        let next_offset = self.get_next_offset();
+        if offset <= next_offset {
+            return Ok(0);
+        }
+
+        let removed_messages_count = self.get_messages_count();
+        self.restart_segments_at(0, offset).await?;
+        self.persist_transactions().await?;
+        info!(
+            "Skipped to offset: {offset} (from: {next_offset}) for partition with ID: {}, stream with ID: {}, topic with ID: {}.",
+            self.partition_id, self.stream_id, self.topic_id
+        );
+        Ok(removed_messages_count)
+    }
+
+    /// Deletes the segment containing the removed offset along with the following ones, and creates the new segment
+    /// with the given start offset in their place, discarding the state of the messages starting at the removed offset.
+    async fn restart_segments_at(
+        &mut self,
+        removed_offset: u64,
+        start_offset: u64,
+    ) -> Result<(), IggyError> {
+        let removed_segment_start_offset = self
+            .segments
+            .iter()
+            .rev()
+            .find(|segment| segment.start_offset <= removed_offset)
+            .map(|segment| segment.start_offset)
+            .unwrap_or(removed_offset);
+        for segment in self
+            .segments
+            .iter_mut()
+            .filter(|segment| segment.start_offset >= removed_segment_start_offset)
+        {
+            segment.delete().await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to delete segment: {segment}")
+            })?;
+            self.segments_count_of_parent_stream
+                .fetch_sub(1, Ordering::SeqCst);
+        }
+        self.segments
+            .retain(|segment| segment.start_offset < removed_segment_start_offset);
+        if let Some(cache) = self.cache.as_mut() {
+            cache.purge();
+        }
+
+        if start_offset == 0 {
+            self.current_offset = 0;
+            self.should_increment_offset = false;
+        } else {
+            self.current_offset = start_offset - 1;
+            self.should_increment_offset = true;
+        }
+        self.unsaved_messages_count = 0;
+        self.consumer_delivery_attempts
+            .retain(|(_, offset), _| *offset < removed_offset);
+        self.consumer_group_delivery_attempts
+            .retain(|(_, offset), _| *offset < removed_offset);
+        self.poisoned_offsets
+            .retain(|offset| *offset < removed_offset);
+        self.open_transactions
+            .retain(|_, first_offset| *first_offset < removed_offset);
+
+        self.add_persisted_segment(start_offset)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to add segment with start offset: {start_offset} for partition with ID: {}",
+                    self.partition_id
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::system::{SegmentConfig, SystemConfig};
+    use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
+    use crate::streaming::storage::SystemStorage;
+    use bytes::Bytes;
+    use iggy::compression::compression_algorithm::CompressionAlgorithm;
+    use iggy::messages::send_messages::Message;
+    use iggy::utils::expiry::IggyExpiry;
+    use iggy::utils::timestamp::IggyTimestamp;
+    use std::sync::atomic::{AtomicU32, AtomicU64};
+    use tempfile::TempDir;
+
+    const TIMESTAMP: u64 = 1_000_000;
+
+    #[tokio::test]
+    async fn replicated_messages_should_keep_offsets_and_timestamps() {
+        let (mut partition, _tempdir) = create_partition().await;
+
+        partition
+            .append_replicated_messages(create_messages(0..5, 1))
+            .await
+            .unwrap();
+
+        let messages = partition.get_messages_by_offset(0, 10).await.unwrap();
+        assert_eq!(messages.len(), 5);
+        for (offset, message) in messages.iter().enumerate() {
+            assert_eq!(message.offset, offset as u64);
+            assert_eq!(message.timestamp, TIMESTAMP + offset as u64);
+        }
+        assert_eq!(partition.get_next_offset(), 5);
+    }
+
+    #[tokio::test]
+    async fn replicated_messages_not_starting_at_next_offset_should_be_rejected() {
+        let (mut partition, _tempdir) = create_partition().await;
+        partition
+            .append_replicated_messages(create_messages(0..5, 1))
+            .await
+            .unwrap();
+
+        let result = partition
+            .append_replicated_messages(create_messages(6..8, 1))
+            .await;
+
+        assert!(matches!(result, Err(IggyError::InvalidOffset(6))));
+        assert_eq!(partition.get_next_offset(), 5);
+    }
+
+    #[tokio::test]
+    async fn truncated_messages_should_be_replaced_with_the_leader_ones() {
+        let (mut partition, _tempdir) = create_partition().await;
+        partition
+            .append_replicated_messages(create_messages(0..10, 1))
+            .await
+            .unwrap();
+
+        let removed_messages_count = partition.truncate_messages(6).await.unwrap();
+        assert_eq!(removed_messages_count, 4);
+        assert_eq!(partition.get_next_offset(), 6);
+        partition
+            .append_replicated_messages(create_messages(6..8, 2))
+            .await
+            .unwrap();
+
+        let messages = partition.get_messages_by_offset(0, 20).await.unwrap();
+        assert_eq!(messages.len(), 8);
+        for (offset, message) in messages.iter().enumerate() {
+            assert_eq!(message.offset, offset as u64);
+            let term = if offset < 6 { 1 } else { 2 };
+            assert_eq!(message.payload, create_payload(offset as u64, term));
+        }
+        assert_eq!(partition.get_messages_count(), 8);
+    }
+
+    #[tokio::test]
+    async fn truncated_messages_should_be_removed_from_following_segments() {
+        let (mut partition, _tempdir) = create_partition_with_segment_size(200).await;
+        for offset in 0..10 {
+            partition
+                .append_replicated_messages(create_messages(offset..offset + 1, 1))
+                .await
+                .unwrap();
+        }
+        assert!(partition.get_segments_count() > 2);
+
+        partition.truncate_messages(3).await.unwrap();
+        partition
+            .append_replicated_messages(create_messages(3..5, 2))
+            .await
+            .unwrap();
+
+        let messages = partition.get_messages_by_offset(0, 20).await.unwrap();
+        assert_eq!(messages.len(), 5);
+        for (offset, message) in messages.iter().enumerate() {
+            assert_eq!(message.offset, offset as u64);
+            let term = if offset < 3 { 1 } else { 2 };
+            assert_eq!(message.payload, create_payload(offset as u64, term));
+        }
+        assert_eq!(partition.get_messages_count(), 5);
+    }
+
+    #[tokio::test]
+    async fn partition_should_be_truncated_at_first_offset() {
+        let (mut partition, _tempdir) = create_partition().await;
+        partition
+            .append_replicated_messages(create_messages(0..3, 1))
+            .await
+            .unwrap();
+
+        partition.truncate_messages(0).await.unwrap();
+
+        assert_eq!(partition.get_next_offset(), 0);
+        assert!(partition
+            .get_messages_by_offset(0, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        partition
+            .append_replicated_messages(create_messages(0..1, 2))
+            .await
+            .unwrap();
+        assert_eq!(partition.get_next_offset(), 1);
+    }
+
+    #[tokio::test]
+    async fn partition_should_skip_to_offset_of_leader() {
+        let (mut partition, _tempdir) = create_partition().await;
+        partition
+            .append_replicated_messages(create_messages(0..3, 1))
+            .await
+            .unwrap();
+
+        partition.skip_messages_to(100).await.unwrap();
+        partition
+            .append_replicated_messages(create_messages(100..102, 1))
+            .await
+            .unwrap();
+
+        let messages = partition.get_messages_by_offset(100, 10).await.unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].offset, 100);
+        assert_eq!(partition.get_next_offset(), 102);
+        assert_eq!(partition.get_messages_count(), 2);
+    }
+
+    fn create_messages(offsets: std::ops::Range<u64>, term: u64) -> Vec<Arc<RetainedMessage>> {
+        offsets
+            .map(|offset| {
+                let message =
+                    Message::new(Some(offset as u128 + 1), create_payload(offset, term), None);
+                Arc::new(RetainedMessage::new(offset, TIMESTAMP + offset, message))
+            })
+            .collect()
+    }
+
+    fn create_payload(offset: u64, term: u64) -> Bytes {
+        Bytes::from(format!("message {offset} of term {term}"))
+    }
+
+    async fn create_partition() -> (Partition, TempDir) {
+        create_partition_with_segment_size(SegmentConfig::default().size.as_bytes_u64()).await
+    }
+
+    async fn create_partition_with_segment_size(segment_size: u64) -> (Partition, TempDir) {
+        let tempdir = TempDir::new().unwrap();
+        let config = Arc::new(SystemConfig {
+            path: tempdir.path().to_str().unwrap().to_string(),
+            segment: SegmentConfig {
+                size: IggyByteSize::from(segment_size),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let storage = Arc::new(SystemStorage::new(
+            config.clone(),
+            Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {})),
+        ));
+        let mut partition = Partition::create(
+            1,
+            2,
+            3,
+            true,
+            config,
+            storage,
+            IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU32::new(0)),
+            IggyTimestamp::now(),
+        )
+        .await;
+        partition.persist().await.unwrap();
+        (partition, tempdir)
+    }
+}
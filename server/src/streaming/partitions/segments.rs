@@ -62,6 +62,8 @@ impl Partition {
             start_offset,
             self.config.clone(),
             self.message_expiry,
+            self.compression_algorithm,
+            self.compression_level,
             self.size_of_parent_stream.clone(),
             self.size_of_parent_topic.clone(),
             self.size_bytes.clone(),
@@ -78,7 +80,8 @@ impl Partition {
             .fetch_add(1, Ordering::SeqCst);
         self.segments
             .sort_by(|a, b| a.start_offset.cmp(&b.start_offset));
-        Ok(())
+        self.rebuild_time_index();
+        self.persist_time_index().await
     }
 
     pub async fn delete_segment(&mut self, start_offset: u64) -> Result<DeletedSegment, IggyError> {
@@ -106,6 +109,9 @@ impl Partition {
         self.segments.retain(|s| s.start_offset != start_offset);
         self.segments
             .sort_by(|a, b| a.start_offset.cmp(&b.start_offset));
+        self.prune_dead_letter_state(deleted_segment.end_offset);
+        self.rebuild_time_index();
+        self.persist_time_index().await?;
         info!(
             "Segment with start offset: {} has been deleted from partition with ID: {}, stream with ID: {}, topic with ID: {}",
             start_offset, self.partition_id, self.stream_id, self.topic_id
@@ -1,15 +1,22 @@
 use crate::compat::index_rebuilding::index_rebuilder::IndexRebuilder;
 use crate::state::system::PartitionState;
 use crate::streaming::batching::batch_accumulator::BatchAccumulator;
+use crate::streaming::partitions::archived_segments::ArchivedSegment;
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
+use crate::streaming::partitions::producers::StoredProducerSequences;
+use crate::streaming::partitions::time_index::SegmentTimeRange;
+use crate::streaming::partitions::transactions::StoredTransactions;
 use crate::streaming::partitions::COMPONENT;
 use crate::streaming::persistence::persister::PersisterKind;
 use crate::streaming::segments::*;
 use crate::streaming::storage::PartitionStorage;
 use crate::streaming::utils::file;
+use bytes::{Buf, BufMut, BytesMut};
 use error_set::ErrContext;
 use iggy::consumer::ConsumerKind;
 use iggy::error::IggyError;
+use iggy::messages::send_messages::ProducerSequence;
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -18,6 +25,13 @@ use tokio::fs::create_dir_all;
 use tokio::io::AsyncReadExt;
 use tracing::{error, info, trace, warn};
 
+const PRODUCER_SEQUENCE_ENTRY_SIZE: usize = 24;
+const TRANSACTION_ENTRY_SIZE: usize = 17;
+const OPEN_TRANSACTION: u8 = 0;
+const ABORTED_TRANSACTION: u8 = 1;
+const ARCHIVED_SEGMENT_ENTRY_SIZE: usize = 16;
+const TIME_INDEX_ENTRY_SIZE: usize = 24;
+
 #[derive(Debug)]
 pub struct FilePartitionStorage {
     persister: Arc<PersisterKind>,
@@ -83,6 +97,8 @@ impl PartitionStorage for FilePartitionStorage {
                 start_offset,
                 partition.config.clone(),
                 partition.message_expiry,
+                partition.compression_algorithm,
+                partition.compression_level,
                 partition.size_of_parent_stream.clone(),
                 partition.size_of_parent_topic.clone(),
                 partition.size_bytes.clone(),
@@ -210,6 +226,30 @@ impl PartitionStorage for FilePartitionStorage {
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to load consumer offsets, partition: {partition}",)
             })?;
+        partition
+            .load_producer_sequences()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load producer sequences, partition: {partition}",)
+            })?;
+        partition
+            .load_transactions()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load transactions, partition: {partition}",)
+            })?;
+        partition
+            .load_archived_segments()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load archived segments, partition: {partition}",)
+            })?;
+        partition
+            .load_time_index()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load time index, partition: {partition}",)
+            })?;
         info!(
             "Loaded partition with ID: {} for stream with ID: {} and topic with ID: {}, current offset: {}.",
             partition.partition_id, partition.stream_id, partition.topic_id, partition.current_offset
@@ -337,7 +377,7 @@ impl PartitionStorage for FilePartitionStorage {
 
     async fn save_consumer_offset(&self, offset: u64, path: &str) -> Result<(), IggyError> {
         self.persister
-            .overwrite(path, &offset.to_le_bytes())
+            .overwrite_atomically(path, &offset.to_le_bytes())
             .await
             .with_error_context(|error| format!(
                 "{COMPONENT} (error: {error}) - failed to overwrite consumer offset with value: {}, path: {}",
@@ -371,6 +411,11 @@ impl PartitionStorage for FilePartitionStorage {
             }
 
             let name = dir_entry.file_name().into_string().unwrap();
+            if name.ends_with(".tmp") {
+                warn!("Skipping leftover temporary consumer offset file: '{name}'.");
+                continue;
+            }
+
             let consumer_id = name.parse::<u32>();
             if consumer_id.is_err() {
                 error!("Invalid consumer ID file with name: '{}'.", name);
@@ -441,4 +486,259 @@ impl PartitionStorage for FilePartitionStorage {
         }
         Ok(())
     }
+
+    async fn save_producer_sequences(
+        &self,
+        path: &str,
+        offset: u64,
+        sequences: &HashMap<u64, ProducerSequence>,
+    ) -> Result<(), IggyError> {
+        let mut bytes = BytesMut::with_capacity(8 + PRODUCER_SEQUENCE_ENTRY_SIZE * sequences.len());
+        bytes.put_u64_le(offset);
+        for sequence in sequences.values() {
+            bytes.put_u64_le(sequence.producer_id);
+            bytes.put_u64_le(sequence.epoch);
+            bytes.put_u64_le(sequence.sequence);
+        }
+        self.persister
+            .overwrite_atomically(path, &bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to overwrite producer sequences, path: {path}"
+                )
+            })?;
+        trace!(
+            "Stored {} producer sequences up to offset: {offset}, path: {path}",
+            sequences.len()
+        );
+        Ok(())
+    }
+
+    async fn load_producer_sequences(
+        &self,
+        path: &str,
+    ) -> Result<Option<StoredProducerSequences>, IggyError> {
+        if !Path::new(path).exists() {
+            trace!("Producer sequences file does not exist: {path}.");
+            return Ok(None);
+        }
+
+        let bytes = fs::read(path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read producer sequences, path: {path}"
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        if bytes.len() < 8 || (bytes.len() - 8) % PRODUCER_SEQUENCE_ENTRY_SIZE != 0 {
+            error!(
+                "Invalid producer sequences file: {path}, size: {}.",
+                bytes.len()
+            );
+            return Err(IggyError::CannotReadFile);
+        }
+
+        let mut bytes = bytes.as_slice();
+        let offset = bytes.get_u64_le();
+        let mut sequences = HashMap::with_capacity(bytes.len() / PRODUCER_SEQUENCE_ENTRY_SIZE);
+        while bytes.has_remaining() {
+            let sequence = ProducerSequence {
+                producer_id: bytes.get_u64_le(),
+                epoch: bytes.get_u64_le(),
+                sequence: bytes.get_u64_le(),
+            };
+            sequences.insert(sequence.producer_id, sequence);
+        }
+        Ok(Some(StoredProducerSequences { offset, sequences }))
+    }
+
+    async fn save_transactions(
+        &self,
+        path: &str,
+        open_transactions: &HashMap<u64, u64>,
+        aborted_transactions: &HashMap<u64, u64>,
+    ) -> Result<(), IggyError> {
+        let mut bytes = BytesMut::with_capacity(
+            TRANSACTION_ENTRY_SIZE * (open_transactions.len() + aborted_transactions.len()),
+        );
+        for (transaction_id, first_offset) in open_transactions {
+            bytes.put_u8(OPEN_TRANSACTION);
+            bytes.put_u64_le(*transaction_id);
+            bytes.put_u64_le(*first_offset);
+        }
+        for (transaction_id, last_offset) in aborted_transactions {
+            bytes.put_u8(ABORTED_TRANSACTION);
+            bytes.put_u64_le(*transaction_id);
+            bytes.put_u64_le(*last_offset);
+        }
+        self.persister
+            .overwrite_atomically(path, &bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to overwrite transactions, path: {path}"
+                )
+            })?;
+        trace!(
+            "Stored {} open and {} aborted transactions, path: {path}",
+            open_transactions.len(),
+            aborted_transactions.len()
+        );
+        Ok(())
+    }
+
+    async fn load_transactions(&self, path: &str) -> Result<Option<StoredTransactions>, IggyError> {
+        if !Path::new(path).exists() {
+            trace!("Transactions file does not exist: {path}.");
+            return Ok(None);
+        }
+
+        let bytes = fs::read(path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to read transactions, path: {path}")
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        if bytes.len() % TRANSACTION_ENTRY_SIZE != 0 {
+            error!("Invalid transactions file: {path}, size: {}.", bytes.len());
+            return Err(IggyError::CannotReadFile);
+        }
+
+        let mut bytes = bytes.as_slice();
+        let mut transactions = StoredTransactions::default();
+        while bytes.has_remaining() {
+            let kind = bytes.get_u8();
+            let transaction_id = bytes.get_u64_le();
+            let offset = bytes.get_u64_le();
+            match kind {
+                OPEN_TRANSACTION => transactions.open.insert(transaction_id, offset),
+                ABORTED_TRANSACTION => transactions.aborted.insert(transaction_id, offset),
+                _ => {
+                    error!("Invalid transaction kind: {kind} in file: {path}.");
+                    return Err(IggyError::CannotReadFile);
+                }
+            };
+        }
+        Ok(Some(transactions))
+    }
+    async fn save_archived_segments(
+        &self,
+        path: &str,
+        archived_segments: &[ArchivedSegment],
+    ) -> Result<(), IggyError> {
+        let mut bytes =
+            BytesMut::with_capacity(ARCHIVED_SEGMENT_ENTRY_SIZE * archived_segments.len());
+        for archived_segment in archived_segments {
+            bytes.put_u64_le(archived_segment.start_offset);
+            bytes.put_u64_le(archived_segment.end_offset);
+        }
+        self.persister
+            .overwrite_atomically(path, &bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to overwrite archived segments, path: {path}"
+                )
+            })?;
+        trace!(
+            "Stored {} archived segments, path: {path}",
+            archived_segments.len()
+        );
+        Ok(())
+    }
+
+    async fn load_archived_segments(&self, path: &str) -> Result<Vec<ArchivedSegment>, IggyError> {
+        if !Path::new(path).exists() {
+            trace!("Archived segments file does not exist: {path}.");
+            return Ok(Vec::new());
+        }
+
+        let bytes = fs::read(path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read archived segments, path: {path}"
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        if bytes.len() % ARCHIVED_SEGMENT_ENTRY_SIZE != 0 {
+            error!(
+                "Invalid archived segments file: {path}, size: {}.",
+                bytes.len()
+            );
+            return Err(IggyError::CannotReadFile);
+        }
+
+        let mut bytes = bytes.as_slice();
+        let mut archived_segments = Vec::with_capacity(bytes.len() / ARCHIVED_SEGMENT_ENTRY_SIZE);
+        while bytes.has_remaining() {
+            let start_offset = bytes.get_u64_le();
+            let end_offset = bytes.get_u64_le();
+            archived_segments.push(ArchivedSegment {
+                start_offset,
+                end_offset,
+            });
+        }
+        Ok(archived_segments)
+    }
+
+    async fn save_time_index(
+        &self,
+        path: &str,
+        time_index: &[SegmentTimeRange],
+    ) -> Result<(), IggyError> {
+        let mut bytes = BytesMut::with_capacity(TIME_INDEX_ENTRY_SIZE * time_index.len());
+        for time_range in time_index {
+            bytes.put_u64_le(time_range.start_offset);
+            bytes.put_u64_le(time_range.start_timestamp);
+            bytes.put_u64_le(time_range.end_timestamp);
+        }
+        self.persister
+            .overwrite_atomically(path, &bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to overwrite time index, path: {path}"
+                )
+            })?;
+        trace!(
+            "Stored {} time index entries, path: {path}",
+            time_index.len()
+        );
+        Ok(())
+    }
+
+    async fn load_time_index(&self, path: &str) -> Result<Vec<SegmentTimeRange>, IggyError> {
+        if !Path::new(path).exists() {
+            trace!("Time index file does not exist: {path}.");
+            return Ok(Vec::new());
+        }
+
+        let bytes = fs::read(path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to read time index, path: {path}")
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        if bytes.len() % TIME_INDEX_ENTRY_SIZE != 0 {
+            error!("Invalid time index file: {path}, size: {}.", bytes.len());
+            return Err(IggyError::CannotReadFile);
+        }
+
+        let mut bytes = bytes.as_slice();
+        let mut time_index = Vec::with_capacity(bytes.len() / TIME_INDEX_ENTRY_SIZE);
+        while bytes.has_remaining() {
+            let start_offset = bytes.get_u64_le();
+            let start_timestamp = bytes.get_u64_le();
+            let end_timestamp = bytes.get_u64_le();
+            time_index.push(SegmentTimeRange {
+                start_offset,
+                start_timestamp,
+                end_timestamp,
+            });
+        }
+        Ok(time_index)
+    }
 }
@@ -0,0 +1,244 @@
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::COMPONENT;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use tracing::trace;
+
+/// The range of the timestamps of the messages stored in the segment, used to find the segment
+/// which contains the messages appended at the given time without checking all the segments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentTimeRange {
+    pub start_offset: u64,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+}
+
+impl Partition {
+    pub fn get_time_index(&self) -> &[SegmentTimeRange] {
+        &self.time_index
+    }
+
+    /// Returns the position of the first segment containing the messages with the timestamp
+    /// greater than or equal to the given one, or `None` if all the messages are older.
+    pub(crate) fn find_segment_position_by_timestamp(&self, timestamp: u64) -> Option<usize> {
+        let position = self
+            .time_index
+            .partition_point(|time_range| time_range.end_timestamp < timestamp);
+        let start_offset = self.time_index.get(position)?.start_offset;
+        self.segments
+            .binary_search_by_key(&start_offset, |segment| segment.start_offset)
+            .ok()
+    }
+
+    /// Updates the time range of the last segment, after appending the messages to it.
+    pub(crate) fn update_last_segment_time_range(&mut self) {
+        let Some(segment) = self.segments.last() else {
+            return;
+        };
+
+        match self.time_index.last_mut() {
+            Some(time_range) if time_range.start_offset == segment.start_offset => {
+                time_range.start_timestamp = segment.start_timestamp;
+                time_range.end_timestamp = segment.end_timestamp;
+            }
+            _ => self.rebuild_time_index(),
+        }
+    }
+
+    /// Rebuilds the time index from the segments, after they have been added or removed.
+    pub(crate) fn rebuild_time_index(&mut self) {
+        self.time_index = self
+            .segments
+            .iter()
+            .map(|segment| SegmentTimeRange {
+                start_offset: segment.start_offset,
+                start_timestamp: segment.start_timestamp,
+                end_timestamp: segment.end_timestamp,
+            })
+            .collect();
+    }
+
+    pub async fn persist_time_index(&self) -> Result<(), IggyError> {
+        self.storage
+            .partition
+            .save_time_index(&self.time_index_path, &self.time_index)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to persist time index for partition: {self}"
+                )
+            })
+    }
+
+    /// Restores the start timestamps of the segments from the persisted time index, as the timestamps loaded
+    /// from the segment indexes are the max timestamps of the batches, which is exact only for the end timestamp.
+    /// The persisted range is used only if it has the same end timestamp, otherwise it's outdated, e.g. when
+    /// the server was stopped before persisting the time index, or the partition was stored by the previous version.
+    pub async fn load_time_index(&mut self) -> Result<(), IggyError> {
+        let persisted_time_index = self
+            .storage
+            .partition
+            .load_time_index(&self.time_index_path)
+            .await?;
+        for segment in &mut self.segments {
+            if let Ok(position) = persisted_time_index
+                .binary_search_by_key(&segment.start_offset, |time_range| time_range.start_offset)
+            {
+                let time_range = persisted_time_index[position];
+                if time_range.end_timestamp == segment.end_timestamp {
+                    segment.start_timestamp = time_range.start_timestamp;
+                }
+            }
+        }
+
+        self.rebuild_time_index();
+        trace!(
+            "Loaded time index with {} entries for partition with ID: {}.",
+            self.time_index.len(),
+            self.partition_id
+        );
+        if self.time_index != persisted_time_index {
+            self.persist_time_index().await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::system::{SegmentConfig, SystemConfig};
+    use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
+    use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
+    use crate::streaming::storage::SystemStorage;
+    use bytes::Bytes;
+    use iggy::compression::compression_algorithm::CompressionAlgorithm;
+    use iggy::messages::send_messages::Message;
+    use iggy::utils::byte_size::IggyByteSize;
+    use iggy::utils::expiry::IggyExpiry;
+    use iggy::utils::sizeable::Sizeable;
+    use iggy::utils::timestamp::IggyTimestamp;
+    use std::sync::atomic::{AtomicU32, AtomicU64};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn messages_should_be_polled_by_timestamp_from_the_segment_found_in_time_index() {
+        let (mut partition, _tempdir) = create_partition().await;
+        let mut timestamps = Vec::new();
+        for batch in 0..4 {
+            append_messages(&mut partition, batch, 3).await;
+            timestamps.push(partition.segments[batch as usize].start_timestamp);
+        }
+        assert_eq!(partition.time_index.len(), partition.segments.len());
+
+        let position = partition.find_segment_position_by_timestamp(timestamps[2]);
+        assert_eq!(position, Some(2));
+        assert_eq!(partition.find_segment_position_by_timestamp(u64::MAX), None);
+
+        let messages = partition
+            .get_messages_by_timestamp(IggyTimestamp::from(timestamps[2]), 4)
+            .await
+            .unwrap();
+        assert_eq!(
+            messages
+                .iter()
+                .map(|message| message.offset)
+                .collect::<Vec<_>>(),
+            vec![6, 7, 8, 9]
+        );
+    }
+
+    #[tokio::test]
+    async fn time_index_should_be_persisted_and_restored_after_deleting_segments() {
+        let (mut partition, _tempdir) = create_partition().await;
+        for batch in 0..3 {
+            append_messages(&mut partition, batch, 3).await;
+        }
+        partition.delete_segment(0).await.unwrap();
+        assert_eq!(partition.time_index[0].start_offset, 3);
+
+        let closed_segments_count = partition
+            .segments
+            .iter()
+            .filter(|segment| segment.is_closed)
+            .count();
+        assert!(closed_segments_count > 0);
+        let closed_time_ranges = partition.time_index[..closed_segments_count].to_vec();
+        let persisted_time_index = partition
+            .storage
+            .partition
+            .load_time_index(&partition.time_index_path)
+            .await
+            .unwrap();
+        assert_eq!(persisted_time_index.len(), partition.time_index.len());
+        assert_eq!(
+            persisted_time_index[..closed_segments_count],
+            closed_time_ranges
+        );
+
+        for segment in &mut partition.segments {
+            segment.start_timestamp = segment.end_timestamp;
+        }
+        partition.load_time_index().await.unwrap();
+        assert_eq!(
+            partition.time_index[..closed_segments_count],
+            closed_time_ranges
+        );
+    }
+
+    async fn append_messages(partition: &mut Partition, batch: u64, count: u64) {
+        let messages = (batch * count..(batch + 1) * count)
+            .map(|offset| Message::new(None, Bytes::from(format!("message {offset}")), None))
+            .collect::<Vec<_>>();
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|message| message.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await
+            .unwrap();
+        partition.flush_unsaved_buffer(true).await.unwrap();
+    }
+
+    async fn create_partition() -> (Partition, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(SystemConfig {
+            path: temp_dir.path().to_str().unwrap().to_string(),
+            segment: SegmentConfig {
+                size: IggyByteSize::from(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let storage = Arc::new(SystemStorage::new(
+            config.clone(),
+            Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {})),
+        ));
+        let mut partition = Partition::create(
+            1,
+            2,
+            3,
+            true,
+            config,
+            storage,
+            IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU32::new(0)),
+            IggyTimestamp::now(),
+        )
+        .await;
+        partition.persist().await.unwrap();
+        (partition, temp_dir)
+    }
+}
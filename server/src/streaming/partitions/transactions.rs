@@ -0,0 +1,145 @@
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::COMPONENT;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::get_transaction_id;
+use iggy::models::messages::PolledMessage;
+use std::collections::HashMap;
+use tracing::{info, trace};
+
+/// The open transactions along with the offsets of their first messages appended to the partition,
+/// and the aborted ones along with the current offset of the partition at the time of aborting.
+#[derive(Debug, Default, PartialEq)]
+pub struct StoredTransactions {
+    pub open: HashMap<u64, u64>,
+    pub aborted: HashMap<u64, u64>,
+}
+
+impl Partition {
+    /// Registers the transaction with its first message at the given offset.
+    /// Returns `true` if it's the first batch of the transaction appended to the partition.
+    pub fn begin_transaction(&mut self, transaction_id: u64, first_offset: u64) -> bool {
+        if self.open_transactions.contains_key(&transaction_id) {
+            return false;
+        }
+
+        self.open_transactions.insert(transaction_id, first_offset);
+        true
+    }
+
+    /// Commits or aborts the transaction, the messages of the aborted one will never be returned to the `read committed` consumers.
+    pub async fn complete_transaction(
+        &mut self,
+        transaction_id: u64,
+        commit: bool,
+    ) -> Result<(), IggyError> {
+        if self.open_transactions.remove(&transaction_id).is_none() {
+            return Ok(());
+        }
+
+        if !commit {
+            self.aborted_transactions
+                .insert(transaction_id, self.current_offset);
+        }
+        trace!(
+            "Transaction with ID: {transaction_id} has been {} for partition with ID: {}.",
+            if commit { "committed" } else { "aborted" },
+            self.partition_id
+        );
+        self.persist_transactions().await
+    }
+
+    /// Returns the offset of the first message of the oldest open transaction,
+    /// none of the messages starting from this offset can be returned to the `read committed` consumers.
+    pub fn get_last_stable_offset(&self) -> Option<u64> {
+        self.open_transactions.values().min().copied()
+    }
+
+    /// Discards the messages of the aborted transactions and the ones appended after the last stable offset.
+    /// Returns the remaining messages and the offset of the last message which has been processed (including the discarded
+    /// messages of the aborted transactions), which can be stored as the consumer offset.
+    pub fn filter_committed_messages(
+        &self,
+        messages: Vec<PolledMessage>,
+    ) -> Result<(Vec<PolledMessage>, Option<u64>), IggyError> {
+        let last_stable_offset = self.get_last_stable_offset();
+        let mut last_offset = None;
+        let mut committed_messages = Vec::with_capacity(messages.len());
+        for message in messages {
+            if last_stable_offset.is_some_and(|offset| message.offset >= offset) {
+                break;
+            }
+
+            last_offset = Some(message.offset);
+            if let Some(headers) = &message.headers {
+                if let Some(transaction_id) = get_transaction_id(headers)? {
+                    if self.aborted_transactions.contains_key(&transaction_id) {
+                        continue;
+                    }
+                }
+            }
+            committed_messages.push(message);
+        }
+        Ok((committed_messages, last_offset))
+    }
+
+    pub async fn persist_transactions(&self) -> Result<(), IggyError> {
+        self.storage
+            .partition
+            .save_transactions(
+                &self.transactions_path,
+                &self.open_transactions,
+                &self.aborted_transactions,
+            )
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to persist transactions for partition: {self}"
+                )
+            })
+    }
+
+    /// Loads the stored transactions, the ones which were still open are kept open,
+    /// until they're either committed (if the commit has been stored before stopping the server) or aborted.
+    pub async fn load_transactions(&mut self) -> Result<(), IggyError> {
+        let Some(stored) = self
+            .storage
+            .partition
+            .load_transactions(&self.transactions_path)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        self.open_transactions = stored.open;
+        self.aborted_transactions = stored.aborted;
+        // The aborted transactions whose messages have been already deleted are no longer needed.
+        let first_offset = self
+            .segments
+            .first()
+            .map(|segment| segment.start_offset)
+            .unwrap_or_default();
+        self.aborted_transactions
+            .retain(|_, last_offset| *last_offset >= first_offset);
+        self.persist_transactions().await
+    }
+
+    /// Aborts all the transactions left open, once the ones which have been committed are completed after the server restart.
+    pub async fn abort_open_transactions(&mut self) -> Result<(), IggyError> {
+        if self.open_transactions.is_empty() {
+            return Ok(());
+        }
+
+        let aborted_count = self.open_transactions.len();
+        for (transaction_id, _) in self.open_transactions.drain() {
+            self.aborted_transactions
+                .insert(transaction_id, self.current_offset);
+        }
+        self.persist_transactions().await?;
+        info!(
+            "Aborted {aborted_count} transaction(s) left open for partition with ID: {}.",
+            self.partition_id
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
 pub mod persister;
 pub mod task;
+#[cfg(target_os = "linux")]
+pub mod uring;
 
 pub const COMPONENT: &str = "STREAMING_PERSISTENCE";
@@ -1,3 +1,5 @@
+#[cfg(target_os = "linux")]
+use crate::streaming::persistence::uring::{UringDriver, CURRENT_POSITION};
 use crate::streaming::persistence::COMPONENT;
 use crate::streaming::utils::file;
 use error_set::ErrContext;
@@ -14,6 +16,8 @@ use mockall::automock;
 pub enum PersisterKind {
     File(FilePersister),
     FileWithSync(FileWithSyncPersister),
+    #[cfg(target_os = "linux")]
+    Uring(UringPersister),
     #[cfg(test)]
     Mock(MockPersister),
 }
@@ -23,6 +27,8 @@ impl PersisterKind {
         match self {
             PersisterKind::File(p) => p.append(path, bytes).await,
             PersisterKind::FileWithSync(p) => p.append(path, bytes).await,
+            #[cfg(target_os = "linux")]
+            PersisterKind::Uring(p) => p.append(path, bytes).await,
             #[cfg(test)]
             PersisterKind::Mock(p) => p.append(path, bytes).await,
         }
@@ -32,15 +38,40 @@ impl PersisterKind {
         match self {
             PersisterKind::File(p) => p.overwrite(path, bytes).await,
             PersisterKind::FileWithSync(p) => p.overwrite(path, bytes).await,
+            #[cfg(target_os = "linux")]
+            PersisterKind::Uring(p) => p.overwrite(path, bytes).await,
             #[cfg(test)]
             PersisterKind::Mock(p) => p.overwrite(path, bytes).await,
         }
     }
 
+    /// Overwrites the file with the data written to the temporary file first, so it's never left partially written.
+    pub async fn overwrite_atomically(&self, path: &str, bytes: &[u8]) -> Result<(), IggyError> {
+        let temporary_path = format!("{path}.tmp");
+        let _ = file::remove(&temporary_path).await;
+        self.overwrite(&temporary_path, bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to write temporary file, path: {temporary_path}"
+                )
+            })?;
+        file::rename(&temporary_path, path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to rename temporary file: {temporary_path} to: {path}"
+                )
+            })
+            .map_err(|_| IggyError::CannotOverwriteFile)
+    }
+
     pub async fn delete(&self, path: &str) -> Result<(), IggyError> {
         match self {
             PersisterKind::File(p) => p.delete(path).await,
             PersisterKind::FileWithSync(p) => p.delete(path).await,
+            #[cfg(target_os = "linux")]
+            PersisterKind::Uring(p) => p.delete(path).await,
             #[cfg(test)]
             PersisterKind::Mock(p) => p.delete(path).await,
         }
@@ -68,6 +99,53 @@ pub struct FilePersister;
 #[derive(Debug)]
 pub struct FileWithSyncPersister;
 
+/// Writes the files through io_uring, optionally syncing them after each write.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub struct UringPersister {
+    driver: &'static UringDriver,
+    fsync: bool,
+}
+
+#[cfg(target_os = "linux")]
+impl UringPersister {
+    pub fn new(fsync: bool) -> Result<Self, IggyError> {
+        let driver = UringDriver::get()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to start io_uring driver")
+            })
+            .map_err(|_| IggyError::IoUringUnavailable)?;
+        Ok(Self { driver, fsync })
+    }
+
+    async fn write(
+        &self,
+        path: &str,
+        file: tokio::fs::File,
+        offset: u64,
+        bytes: &[u8],
+    ) -> Result<(), IggyError> {
+        let file = std::sync::Arc::new(file.into_std().await);
+        self.driver
+            .write_all_at(&file, offset, vec![bytes::Bytes::copy_from_slice(bytes)])
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to write data to file: {path}")
+            })
+            .map_err(|_| IggyError::CannotWriteToFile)?;
+        if self.fsync {
+            self.driver
+                .sync_all(&file)
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to sync file: {path}")
+                })
+                .map_err(|_| IggyError::CannotSyncFile)?;
+        }
+        Ok(())
+    }
+}
+
 impl Persister for FilePersister {
     async fn append(&self, path: &str, bytes: &[u8]) -> Result<(), IggyError> {
         let mut file = file::append(path)
@@ -112,6 +190,39 @@ impl Persister for FilePersister {
     }
 }
 
+#[cfg(target_os = "linux")]
+impl Persister for UringPersister {
+    async fn append(&self, path: &str, bytes: &[u8]) -> Result<(), IggyError> {
+        let file = file::append(path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to append to file: {path}")
+            })
+            .map_err(|_| IggyError::CannotAppendToFile)?;
+        self.write(path, file, CURRENT_POSITION, bytes).await
+    }
+
+    async fn overwrite(&self, path: &str, bytes: &[u8]) -> Result<(), IggyError> {
+        let file = file::overwrite(path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to overwrite file: {path}")
+            })
+            .map_err(|_| IggyError::CannotOverwriteFile)?;
+        self.write(path, file, 0, bytes).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), IggyError> {
+        fs::remove_file(path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to delete file: {path}")
+            })
+            .map_err(|_| IggyError::CannotDeleteFile)?;
+        Ok(())
+    }
+}
+
 impl Persister for FileWithSyncPersister {
     async fn append(&self, path: &str, bytes: &[u8]) -> Result<(), IggyError> {
         let mut file = file::append(path)
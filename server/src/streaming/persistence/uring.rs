@@ -0,0 +1,460 @@
+use bytes::{Buf, Bytes};
+use io_uring::{opcode, types, IoUring};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, ErrorKind};
+use std::os::unix::io::AsRawFd;
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use tokio::sync::oneshot;
+use tracing::{error, info, warn};
+
+const RING_ENTRIES: u32 = 256;
+const REGISTERED_BUFFERS_COUNT: usize = 64;
+const REGISTERED_BUFFER_SIZE: usize = 64 * 1024;
+/// The offset of -1 makes io_uring use (and advance) the current file position, as done by `write(2)`,
+/// so that the files opened in the append mode are always written at their end.
+pub const CURRENT_POSITION: u64 = u64::MAX;
+
+static DRIVER: OnceLock<UringDriver> = OnceLock::new();
+
+enum UringOperation {
+    Write {
+        file: Arc<File>,
+        offset: u64,
+        buffers: Vec<Bytes>,
+        sender: oneshot::Sender<io::Result<usize>>,
+    },
+    Read {
+        file: Arc<File>,
+        offset: u64,
+        len: usize,
+        sender: oneshot::Sender<io::Result<Vec<u8>>>,
+    },
+    Fsync {
+        file: Arc<File>,
+        sender: oneshot::Sender<io::Result<()>>,
+    },
+}
+
+/// The operation submitted to the ring, which owns the file and the buffers until it's completed.
+struct InFlightOperation {
+    operation: UringOperation,
+    _iovecs: Vec<libc::iovec>,
+    buffer: Vec<u8>,
+    registered_buffer: Option<u16>,
+}
+
+/// The buffers registered in the ring, used by the positional reads to avoid mapping the pages on each read.
+struct RegisteredBuffers {
+    buffers: Vec<Box<[u8]>>,
+    available: Vec<u16>,
+}
+
+/// Submits the file operations to io_uring from a dedicated thread. The operations sent by the tasks
+/// are collected in batches, so that many appends and reads are submitted with a single system call.
+#[derive(Debug)]
+pub struct UringDriver {
+    sender: flume::Sender<UringOperation>,
+}
+
+impl UringDriver {
+    /// Returns the driver shared by all the files, starting its thread on the first use.
+    pub fn get() -> io::Result<&'static UringDriver> {
+        if let Some(driver) = DRIVER.get() {
+            return Ok(driver);
+        }
+
+        let driver = Self::start()?;
+        Ok(DRIVER.get_or_init(|| driver))
+    }
+
+    fn start() -> io::Result<UringDriver> {
+        let mut ring = IoUring::new(RING_ENTRIES)?;
+        let registered_buffers = Self::register_buffers(&mut ring);
+        let (sender, receiver) = flume::unbounded();
+        thread::Builder::new()
+            .name("iggy-io-uring".to_string())
+            .spawn(move || Self::run(ring, receiver, registered_buffers))?;
+        info!("Started io_uring driver with {RING_ENTRIES} entries.");
+        Ok(UringDriver { sender })
+    }
+
+    fn register_buffers(ring: &mut IoUring) -> Option<RegisteredBuffers> {
+        let mut buffers = (0..REGISTERED_BUFFERS_COUNT)
+            .map(|_| vec![0u8; REGISTERED_BUFFER_SIZE].into_boxed_slice())
+            .collect::<Vec<_>>();
+        let iovecs = buffers
+            .iter_mut()
+            .map(|buffer| libc::iovec {
+                iov_base: buffer.as_mut_ptr().cast(),
+                iov_len: buffer.len(),
+            })
+            .collect::<Vec<_>>();
+        // The registered buffers are owned by the driver thread for the whole lifetime of the ring.
+        if let Err(error) = unsafe { ring.submitter().register_buffers(&iovecs) } {
+            warn!("Failed to register io_uring buffers, reads will use regular buffers. {error}");
+            return None;
+        }
+
+        Some(RegisteredBuffers {
+            buffers,
+            available: (0..REGISTERED_BUFFERS_COUNT as u16).collect(),
+        })
+    }
+
+    /// Writes all the buffers at the given offset, or at the current position for `CURRENT_POSITION`.
+    pub async fn write_all_at(
+        &self,
+        file: &Arc<File>,
+        mut offset: u64,
+        mut buffers: Vec<Bytes>,
+    ) -> io::Result<()> {
+        buffers.retain(|buffer| !buffer.is_empty());
+        while !buffers.is_empty() {
+            let (sender, receiver) = oneshot::channel();
+            self.submit(UringOperation::Write {
+                file: file.clone(),
+                offset,
+                buffers: buffers.clone(),
+                sender,
+            })?;
+            let mut written = Self::receive(receiver).await??;
+            if written == 0 {
+                return Err(io::Error::from(ErrorKind::WriteZero));
+            }
+
+            if offset != CURRENT_POSITION {
+                offset += written as u64;
+            }
+
+            // Skip the written bytes and submit the remaining ones, if the write was short.
+            while written > 0 {
+                let buffer = &mut buffers[0];
+                let advance = written.min(buffer.len());
+                buffer.advance(advance);
+                written -= advance;
+                if buffer.is_empty() {
+                    buffers.remove(0);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads exactly `len` bytes at the given offset, returns `UnexpectedEof` if the file is shorter.
+    pub async fn read_exact_at(
+        &self,
+        file: &Arc<File>,
+        offset: u64,
+        len: usize,
+    ) -> io::Result<Vec<u8>> {
+        let mut data = Vec::with_capacity(len);
+        while data.len() < len {
+            let (sender, receiver) = oneshot::channel();
+            self.submit(UringOperation::Read {
+                file: file.clone(),
+                offset: offset + data.len() as u64,
+                len: len - data.len(),
+                sender,
+            })?;
+            let chunk = Self::receive(receiver).await??;
+            if chunk.is_empty() {
+                return Err(io::Error::from(ErrorKind::UnexpectedEof));
+            }
+
+            data.extend_from_slice(&chunk);
+        }
+
+        Ok(data)
+    }
+
+    pub async fn sync_all(&self, file: &Arc<File>) -> io::Result<()> {
+        let (sender, receiver) = oneshot::channel();
+        self.submit(UringOperation::Fsync {
+            file: file.clone(),
+            sender,
+        })?;
+        Self::receive(receiver).await?
+    }
+
+    fn submit(&self, operation: UringOperation) -> io::Result<()> {
+        self.sender
+            .send(operation)
+            .map_err(|_| io::Error::other("io_uring driver has stopped"))
+    }
+
+    async fn receive<T>(receiver: oneshot::Receiver<T>) -> io::Result<T> {
+        receiver
+            .await
+            .map_err(|_| io::Error::other("io_uring operation has been dropped"))
+    }
+
+    fn run(
+        mut ring: IoUring,
+        receiver: flume::Receiver<UringOperation>,
+        mut registered_buffers: Option<RegisteredBuffers>,
+    ) {
+        let mut in_flight = HashMap::new();
+        let mut next_id = 0u64;
+        loop {
+            if in_flight.is_empty() {
+                match receiver.recv() {
+                    Ok(operation) => {
+                        Self::push(
+                            &mut ring,
+                            &mut in_flight,
+                            &mut next_id,
+                            &mut registered_buffers,
+                            operation,
+                        );
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            // Collect all the pending operations to submit them at once.
+            while in_flight.len() < RING_ENTRIES as usize {
+                let Ok(operation) = receiver.try_recv() else {
+                    break;
+                };
+                Self::push(
+                    &mut ring,
+                    &mut in_flight,
+                    &mut next_id,
+                    &mut registered_buffers,
+                    operation,
+                );
+            }
+
+            if in_flight.is_empty() {
+                continue;
+            }
+
+            if let Err(error) = ring.submit_and_wait(1) {
+                if error.kind() == ErrorKind::Interrupted {
+                    continue;
+                }
+
+                error!("Failed to submit io_uring operations. {error}");
+                for (_, operation) in in_flight.drain() {
+                    Self::complete(
+                        operation,
+                        Err(io::Error::new(error.kind(), error.to_string())),
+                        &mut registered_buffers,
+                    );
+                }
+                continue;
+            }
+
+            let completions = ring
+                .completion()
+                .map(|entry| (entry.user_data(), entry.result()))
+                .collect::<Vec<_>>();
+            for (id, result) in completions {
+                let Some(operation) = in_flight.remove(&id) else {
+                    continue;
+                };
+                let result = match result {
+                    result if result < 0 => Err(io::Error::from_raw_os_error(-result)),
+                    result => Ok(result as usize),
+                };
+                Self::complete(operation, result, &mut registered_buffers);
+            }
+        }
+    }
+
+    fn push(
+        ring: &mut IoUring,
+        in_flight: &mut HashMap<u64, InFlightOperation>,
+        next_id: &mut u64,
+        registered_buffers: &mut Option<RegisteredBuffers>,
+        operation: UringOperation,
+    ) {
+        let id = *next_id;
+        *next_id = next_id.wrapping_add(1);
+        let mut operation = InFlightOperation {
+            operation,
+            _iovecs: Vec::new(),
+            buffer: Vec::new(),
+            registered_buffer: None,
+        };
+        let entry = match &operation.operation {
+            UringOperation::Write {
+                file,
+                offset,
+                buffers,
+                ..
+            } => {
+                let iovecs = buffers
+                    .iter()
+                    .map(|buffer| libc::iovec {
+                        iov_base: buffer.as_ptr() as *mut _,
+                        iov_len: buffer.len(),
+                    })
+                    .collect::<Vec<_>>();
+                let entry = opcode::Writev::new(
+                    types::Fd(file.as_raw_fd()),
+                    iovecs.as_ptr(),
+                    iovecs.len() as u32,
+                )
+                .offset(*offset)
+                .build();
+                operation._iovecs = iovecs;
+                entry
+            }
+            UringOperation::Read {
+                file, offset, len, ..
+            } => {
+                let registered_buffer = registered_buffers
+                    .as_mut()
+                    .filter(|_| *len <= REGISTERED_BUFFER_SIZE)
+                    .and_then(|buffers| {
+                        let index = buffers.available.pop()?;
+                        Some((index, buffers.buffers[index as usize].as_mut_ptr()))
+                    });
+                match registered_buffer {
+                    Some((index, pointer)) => {
+                        operation.registered_buffer = Some(index);
+                        opcode::ReadFixed::new(
+                            types::Fd(file.as_raw_fd()),
+                            pointer,
+                            *len as u32,
+                            index,
+                        )
+                        .offset(*offset)
+                        .build()
+                    }
+                    None => {
+                        operation.buffer = vec![0u8; *len];
+                        opcode::Read::new(
+                            types::Fd(file.as_raw_fd()),
+                            operation.buffer.as_mut_ptr(),
+                            *len as u32,
+                        )
+                        .offset(*offset)
+                        .build()
+                    }
+                }
+            }
+            UringOperation::Fsync { file, .. } => {
+                opcode::Fsync::new(types::Fd(file.as_raw_fd())).build()
+            }
+        };
+
+        // The buffers referenced by the entry are owned by the in-flight operation until it's completed.
+        let pushed = unsafe { ring.submission().push(&entry.user_data(id)) };
+        if pushed.is_err() {
+            Self::complete(
+                operation,
+                Err(io::Error::other("io_uring submission queue is full")),
+                registered_buffers,
+            );
+            return;
+        }
+
+        in_flight.insert(id, operation);
+    }
+
+    fn complete(
+        operation: InFlightOperation,
+        result: io::Result<usize>,
+        registered_buffers: &mut Option<RegisteredBuffers>,
+    ) {
+        match operation.operation {
+            UringOperation::Write { sender, .. } => {
+                let _ = sender.send(result);
+            }
+            UringOperation::Read { sender, .. } => {
+                let result = result.map(|read| match operation.registered_buffer {
+                    Some(index) => {
+                        let buffers = registered_buffers.as_mut().unwrap();
+                        let data = buffers.buffers[index as usize][..read].to_vec();
+                        buffers.available.push(index);
+                        data
+                    }
+                    None => {
+                        let mut buffer = operation.buffer;
+                        buffer.truncate(read);
+                        buffer
+                    }
+                });
+                if let (Err(_), Some(index)) = (&result, operation.registered_buffer) {
+                    registered_buffers.as_mut().unwrap().available.push(index);
+                }
+                let _ = sender.send(result);
+            }
+            UringOperation::Fsync { sender, .. } => {
+                let _ = sender.send(result.map(|_| ()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::OpenOptions;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn written_buffers_should_be_read_at_any_position() {
+        let Ok(driver) = UringDriver::get() else {
+            warn!("io_uring is not supported, skipping the test.");
+            return;
+        };
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("log");
+        let file = Arc::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .read(true)
+                .open(&path)
+                .unwrap(),
+        );
+
+        driver
+            .write_all_at(
+                &file,
+                CURRENT_POSITION,
+                vec![Bytes::from("header"), Bytes::from("payload")],
+            )
+            .await
+            .unwrap();
+        driver
+            .write_all_at(&file, CURRENT_POSITION, vec![Bytes::from("next")])
+            .await
+            .unwrap();
+        driver.sync_all(&file).await.unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"headerpayloadnext");
+        assert_eq!(driver.read_exact_at(&file, 6, 7).await.unwrap(), b"payload");
+        let large_payload = vec![7u8; REGISTERED_BUFFER_SIZE + 1];
+        driver
+            .write_all_at(
+                &file,
+                CURRENT_POSITION,
+                vec![Bytes::from(large_payload.clone())],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            driver
+                .read_exact_at(&file, 17, large_payload.len())
+                .await
+                .unwrap(),
+            large_payload
+        );
+        let end_position = 17 + large_payload.len() as u64;
+        assert_eq!(
+            driver
+                .read_exact_at(&file, end_position - 1, 10)
+                .await
+                .unwrap_err()
+                .kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+}
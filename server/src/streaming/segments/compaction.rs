@@ -0,0 +1,413 @@
+use super::indexes::*;
+use super::logs::*;
+use super::LOG_EXTENSION;
+use crate::configs::system::SystemConfig;
+use crate::streaming::batching::iterator::IntoMessagesIterator;
+use crate::streaming::batching::message_batch::RetainedMessageBatch;
+use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::segments::segment::Segment;
+use bytes::{Buf, BufMut, BytesMut};
+use error_set::ErrContext;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::confirmation::Confirmation;
+use iggy::error::IggyError;
+use iggy::utils::byte_size::IggyByteSize;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tracing::{info, trace, warn};
+
+const COMPACTED_FILE_SUFFIX: &str = "compacted";
+const COMPACTION_MANIFEST_EXTENSION: &str = "compaction";
+const COMPACTION_MANIFEST_SIZE: usize = 9;
+const COMPACTION_COMPLETED: u8 = 0;
+const COMPACTION_PENDING: u8 = 1;
+
+/// The snapshot of the closed segment, whose files are no longer appended to,
+/// thus its messages can be read and compacted without holding the partition lock.
+#[derive(Debug, Clone)]
+pub struct ClosedSegment {
+    pub start_offset: u64,
+    pub end_offset: u64,
+    log_path: String,
+    index_path: String,
+    compression_algorithm: CompressionAlgorithm,
+    compression_level: Option<u8>,
+    config: Arc<SystemConfig>,
+}
+
+/// The compacted files of the segment, which are yet to replace the current segment files.
+#[derive(Debug)]
+pub struct CompactedSegment {
+    pub start_offset: u64,
+    pub removed_messages_count: u64,
+    log_path: String,
+    index_path: String,
+    indexes: Vec<Index>,
+    last_index_position: u32,
+}
+
+impl Segment {
+    pub fn as_closed(&self) -> Option<ClosedSegment> {
+        if !self.is_closed {
+            return None;
+        }
+
+        Some(ClosedSegment {
+            start_offset: self.start_offset,
+            end_offset: self.end_offset,
+            log_path: self.log_path.clone(),
+            index_path: self.index_path.clone(),
+            compression_algorithm: self.compression_algorithm,
+            compression_level: self.compression_level,
+            config: self.config.clone(),
+        })
+    }
+
+    /// Replaces the segment files with the compacted ones. The manifest marks the swap as pending
+    /// before the files are renamed, so the swap interrupted by a crash is completed when the segment is loaded.
+    pub async fn replace_with_compacted(
+        &mut self,
+        compacted: CompactedSegment,
+    ) -> Result<(), IggyError> {
+        let removed_messages_count = self.removed_messages_count + compacted.removed_messages_count;
+        self.save_compaction_manifest(COMPACTION_PENDING, removed_messages_count)
+            .await?;
+        self.shutdown_reading().await;
+        let swap_result = async {
+            rename_file(&compacted.log_path, &self.log_path).await?;
+            rename_file(&compacted.index_path, &self.index_path).await
+        }
+        .await;
+        // The segment remains readable even if the swap has failed, it will be completed on the next load.
+        self.initialize_reading().await?;
+        swap_result?;
+        self.save_compaction_manifest(COMPACTION_COMPLETED, removed_messages_count)
+            .await?;
+
+        let compacted_size_bytes = self.log_size_bytes.load(Ordering::Acquire);
+        let removed_size_bytes = self
+            .size_bytes
+            .as_bytes_u64()
+            .saturating_sub(compacted_size_bytes);
+        self.size_bytes = IggyByteSize::from(compacted_size_bytes);
+        self.last_index_position = compacted.last_index_position;
+        self.removed_messages_count = removed_messages_count;
+        if self.indexes.is_some() {
+            self.indexes = Some(compacted.indexes);
+        }
+        self.size_of_parent_stream
+            .fetch_sub(removed_size_bytes, Ordering::AcqRel);
+        self.size_of_parent_topic
+            .fetch_sub(removed_size_bytes, Ordering::AcqRel);
+        self.size_of_parent_partition
+            .fetch_sub(removed_size_bytes, Ordering::AcqRel);
+        self.messages_count_of_parent_stream
+            .fetch_sub(compacted.removed_messages_count, Ordering::AcqRel);
+        self.messages_count_of_parent_topic
+            .fetch_sub(compacted.removed_messages_count, Ordering::AcqRel);
+        self.messages_count_of_parent_partition
+            .fetch_sub(compacted.removed_messages_count, Ordering::AcqRel);
+
+        info!(
+            "Compacted segment with start offset: {} for partition with ID: {}, removed {} messages ({}).",
+            self.start_offset,
+            self.partition_id,
+            compacted.removed_messages_count,
+            IggyByteSize::from(removed_size_bytes)
+        );
+        Ok(())
+    }
+
+    /// Completes the swap of the compacted files interrupted by a crash or removes the leftovers
+    /// of the unfinished compaction, and restores the number of messages removed by the previous compactions.
+    pub(super) async fn recover_compaction(&mut self) -> Result<(), IggyError> {
+        let compacted_log_path = get_compacted_path(&self.log_path);
+        let compacted_index_path = get_compacted_path(&self.index_path);
+        let Some((state, removed_messages_count)) = self.load_compaction_manifest().await? else {
+            remove_stale_file(&compacted_log_path).await?;
+            remove_stale_file(&compacted_index_path).await?;
+            return Ok(());
+        };
+
+        if state == COMPACTION_PENDING {
+            warn!("Completing the interrupted compaction of {self}.");
+            for (compacted_path, path) in [
+                (&compacted_log_path, &self.log_path),
+                (&compacted_index_path, &self.index_path),
+            ] {
+                if Path::new(compacted_path).exists() {
+                    rename_file(compacted_path, path).await?;
+                }
+            }
+            self.save_compaction_manifest(COMPACTION_COMPLETED, removed_messages_count)
+                .await?;
+        } else {
+            remove_stale_file(&compacted_log_path).await?;
+            remove_stale_file(&compacted_index_path).await?;
+        }
+
+        self.removed_messages_count = removed_messages_count;
+        Ok(())
+    }
+
+    pub fn get_compaction_manifest_path(&self) -> String {
+        let path = self
+            .log_path
+            .strip_suffix(&format!(".{LOG_EXTENSION}"))
+            .unwrap_or(&self.log_path);
+        format!("{path}.{COMPACTION_MANIFEST_EXTENSION}")
+    }
+
+    async fn save_compaction_manifest(
+        &self,
+        state: u8,
+        removed_messages_count: u64,
+    ) -> Result<(), IggyError> {
+        let path = self.get_compaction_manifest_path();
+        let temporary_path = format!("{path}.tmp");
+        let mut bytes = BytesMut::with_capacity(COMPACTION_MANIFEST_SIZE);
+        bytes.put_u8(state);
+        bytes.put_u64_le(removed_messages_count);
+        let mut file = fs::File::create(&temporary_path)
+            .await
+            .with_error_context(|error| {
+                format!("Failed to create compaction manifest: {temporary_path}. {error}")
+            })
+            .map_err(|_| IggyError::CannotWriteToFile)?;
+        file.write_all(&bytes)
+            .await
+            .with_error_context(|error| {
+                format!("Failed to write compaction manifest: {temporary_path}. {error}")
+            })
+            .map_err(|_| IggyError::CannotWriteToFile)?;
+        file.sync_all()
+            .await
+            .with_error_context(|error| {
+                format!("Failed to sync compaction manifest: {temporary_path}. {error}")
+            })
+            .map_err(|_| IggyError::CannotWriteToFile)?;
+        rename_file(&temporary_path, &path).await
+    }
+
+    async fn load_compaction_manifest(&self) -> Result<Option<(u8, u64)>, IggyError> {
+        let path = self.get_compaction_manifest_path();
+        if !Path::new(&path).exists() {
+            return Ok(None);
+        }
+
+        let bytes = fs::read(&path)
+            .await
+            .with_error_context(|error| {
+                format!("Failed to read compaction manifest: {path}. {error}")
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        if bytes.len() != COMPACTION_MANIFEST_SIZE {
+            return Err(IggyError::CannotReadFile);
+        }
+
+        let mut bytes = bytes.as_slice();
+        let state = bytes.get_u8();
+        if state != COMPACTION_COMPLETED && state != COMPACTION_PENDING {
+            return Err(IggyError::CannotReadFile);
+        }
+
+        Ok(Some((state, bytes.get_u64_le())))
+    }
+}
+
+impl ClosedSegment {
+    /// Streams the messages of the segment batch by batch, calling the provided callback for each message.
+    pub async fn for_each_message<F>(&self, mut f: F) -> Result<(), IggyError>
+    where
+        F: FnMut(&RetainedMessage) -> Result<(), IggyError>,
+    {
+        let log_reader = self.create_log_reader().await?;
+        let mut position = 0;
+        while let Some((batch, batch_size)) = log_reader.load_batch_at(position).await? {
+            position += batch_size;
+            let batch = batch.decompress()?;
+            for message in batch.into_messages_iter() {
+                f(&message)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the compacted files of the segment retaining only the messages matching the predicate,
+    /// or returns none if all the messages are retained. The batches keep their offsets, so the gaps
+    /// left by the removed messages are skipped while reading, and the last batch is always kept
+    /// (even if empty) to preserve the end offset of the segment.
+    pub async fn compact<F>(&self, predicate: F) -> Result<Option<CompactedSegment>, IggyError>
+    where
+        F: Fn(&RetainedMessage) -> bool,
+    {
+        let compacted_log_path = get_compacted_path(&self.log_path);
+        let compacted_index_path = get_compacted_path(&self.index_path);
+        remove_stale_file(&compacted_log_path).await?;
+        remove_stale_file(&compacted_index_path).await?;
+
+        let log_reader = self.create_log_reader().await?;
+        let mut log_writer = SegmentLogWriter::new(
+            &compacted_log_path,
+            Arc::new(AtomicU64::new(0)),
+            false,
+            Confirmation::Wait,
+            self.config.state.max_file_operation_retries,
+            self.config.state.retry_delay,
+            self.config.partition.io_backend,
+        )
+        .await?;
+        let mut index_writer =
+            SegmentIndexWriter::new(&compacted_index_path, Arc::new(AtomicU64::new(0)), false)
+                .await?;
+
+        let mut compacted_segment = CompactedSegment {
+            start_offset: self.start_offset,
+            removed_messages_count: 0,
+            log_path: compacted_log_path,
+            index_path: compacted_index_path,
+            indexes: Vec::new(),
+            last_index_position: 0,
+        };
+        let mut read_position = 0;
+        let mut pending_batch = None;
+        while let Some((batch, batch_size)) = log_reader.load_batch_at(read_position).await? {
+            read_position += batch_size;
+            let batch = batch.decompress()?;
+            let mut bytes = BytesMut::with_capacity(batch.length.as_bytes_u64() as usize);
+            let mut retained_messages_count = 0;
+            for message in batch.into_messages_iter() {
+                if predicate(&message) {
+                    message.extend(&mut bytes);
+                    retained_messages_count += 1;
+                } else {
+                    compacted_segment.removed_messages_count += 1;
+                }
+            }
+
+            let retained_batch = RetainedMessageBatch::new(
+                batch.base_offset,
+                batch.last_offset_delta,
+                batch.max_timestamp,
+                IggyByteSize::from(bytes.len() as u64),
+                bytes.freeze(),
+            );
+            if let Some((previous_batch, previous_messages_count)) =
+                pending_batch.replace((retained_batch, retained_messages_count))
+            {
+                if previous_messages_count > 0 {
+                    self.save_batch(
+                        previous_batch,
+                        &mut log_writer,
+                        &mut index_writer,
+                        &mut compacted_segment,
+                    )
+                    .await?;
+                }
+            }
+        }
+        if let Some((last_batch, _)) = pending_batch {
+            self.save_batch(
+                last_batch,
+                &mut log_writer,
+                &mut index_writer,
+                &mut compacted_segment,
+            )
+            .await?;
+        }
+        log_writer.fsync().await?;
+        index_writer.fsync().await?;
+        drop(log_writer);
+        drop(index_writer);
+
+        if compacted_segment.removed_messages_count == 0 {
+            trace!(
+                "No messages to remove during compaction of segment with start offset: {}.",
+                self.start_offset
+            );
+            compacted_segment.discard().await;
+            return Ok(None);
+        }
+
+        Ok(Some(compacted_segment))
+    }
+
+    async fn save_batch(
+        &self,
+        batch: RetainedMessageBatch,
+        log_writer: &mut SegmentLogWriter,
+        index_writer: &mut SegmentIndexWriter,
+        compacted_segment: &mut CompactedSegment,
+    ) -> Result<(), IggyError> {
+        let index = Index {
+            offset: (batch.get_last_offset() - self.start_offset) as u32,
+            position: compacted_segment.last_index_position,
+            timestamp: batch.max_timestamp,
+        };
+        let batch = batch
+            .compress(self.compression_algorithm, self.compression_level)
+            .with_error_context(|error| {
+                format!(
+                    "Failed to compress batch using {} algorithm for segment with start offset: {}. {error}",
+                    self.compression_algorithm, self.start_offset
+                )
+            })?;
+        let batch_size = log_writer
+            .save_batches(batch, Confirmation::Wait)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "Failed to save compacted batch for segment with start offset: {}. {error}",
+                    self.start_offset
+                )
+            })?;
+        index_writer.save_index(index).await?;
+        compacted_segment.indexes.push(index);
+        compacted_segment.last_index_position += batch_size.as_bytes_u64() as u32;
+        Ok(())
+    }
+
+    async fn create_log_reader(&self) -> Result<SegmentLogReader, IggyError> {
+        SegmentLogReader::new(
+            &self.log_path,
+            Arc::new(AtomicU64::new(0)),
+            self.config.partition.io_backend,
+        )
+        .await
+    }
+}
+
+impl CompactedSegment {
+    /// Removes the compacted files, which will not replace the segment files.
+    pub async fn discard(self) {
+        let _ = remove_stale_file(&self.log_path).await;
+        let _ = remove_stale_file(&self.index_path).await;
+    }
+}
+
+fn get_compacted_path(path: &str) -> String {
+    format!("{path}.{COMPACTED_FILE_SUFFIX}")
+}
+
+async fn rename_file(from: &str, to: &str) -> Result<(), IggyError> {
+    fs::rename(from, to)
+        .await
+        .with_error_context(|error| format!("Failed to rename file: {from} to: {to}. {error}"))
+        .map_err(|_| IggyError::CannotWriteToFile)
+}
+
+async fn remove_stale_file(path: &str) -> Result<(), IggyError> {
+    if !Path::new(path).exists() {
+        return Ok(());
+    }
+
+    fs::remove_file(path)
+        .await
+        .with_error_context(|error| {
+            format!("Failed to remove stale compacted file: {path}. {error}")
+        })
+        .map_err(|_| IggyError::CannotDeleteFile)
+}
@@ -81,6 +81,7 @@ impl IndexRange {
 mod tests {
     use super::*;
     use crate::configs::system::{SegmentConfig, SystemConfig};
+    use iggy::compression::compression_algorithm::CompressionAlgorithm;
     use iggy::utils::expiry::IggyExpiry;
     use std::sync::atomic::AtomicU64;
     use std::sync::Arc;
@@ -105,6 +106,8 @@ mod tests {
             start_offset,
             config,
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -162,26 +162,46 @@ impl SegmentIndexReader {
             return Ok(Some(Index::default()));
         }
 
-        let buf = match self.read_at(0, file_size).await {
+        // Indexes are appended in the timestamp order, so the first index with the timestamp
+        // greater than or equal to the requested one can be found with a binary search.
+        let mut low = 0;
+        let mut high = file_size / INDEX_SIZE;
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let current = self.read_index_at(middle).await?;
+            if current.timestamp < timestamp {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        if low == file_size / INDEX_SIZE {
+            return Ok(None);
+        }
+
+        if low == 0 {
+            return Ok(Some(Index::default()));
+        }
+
+        self.read_index_at(low - 1).await.map(Some)
+    }
+
+    async fn read_index_at(&self, index_number: u64) -> Result<Index, IggyError> {
+        let position = index_number * INDEX_SIZE;
+        let buf = match self.read_at(position, INDEX_SIZE).await {
             Ok(buf) => buf,
-            Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
             Err(error) => {
                 error!(
-                    "Error reading batch header at offset 0 in file {}: {error}",
+                    "Error reading index at position {position} in file {}: {error}",
                     self.file_path
                 );
                 return Err(IggyError::CannotReadFile);
             }
         };
-        let mut last_index: Option<Index> = None;
-        for chunk in buf.chunks_exact(INDEX_SIZE as usize) {
-            let current = parse_index(chunk)?;
-            if current.timestamp >= timestamp {
-                return Ok(Some(last_index.unwrap_or_default()));
-            }
-            last_index = Some(current);
-        }
-        Ok(None)
+        parse_index(&buf).with_error_context(|error| {
+            format!("Failed to parse index {}: {error}", self.file_path)
+        })
     }
 
     fn file_size(&self) -> u64 {
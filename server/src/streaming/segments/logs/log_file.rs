@@ -0,0 +1,74 @@
+use crate::configs::system::IoBackend;
+use crate::streaming::batching::message_batch::RetainedMessageBatch;
+#[cfg(target_os = "linux")]
+use crate::streaming::persistence::uring::{UringDriver, CURRENT_POSITION};
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use std::io::IoSlice;
+#[cfg(target_os = "linux")]
+use std::sync::Arc;
+use tokio::{fs::File, io::AsyncWriteExt};
+
+/// The log file opened for appending the message batches, written through the configured I/O backend.
+#[derive(Debug)]
+pub enum LogFile {
+    Tokio(File),
+    #[cfg(target_os = "linux")]
+    Uring {
+        file: Arc<std::fs::File>,
+        driver: &'static UringDriver,
+    },
+}
+
+impl LogFile {
+    pub async fn new(file: File, io_backend: IoBackend) -> Result<Self, IggyError> {
+        match io_backend {
+            IoBackend::Tokio => Ok(LogFile::Tokio(file)),
+            #[cfg(target_os = "linux")]
+            IoBackend::IoUring => {
+                let driver = UringDriver::get()
+                    .with_error_context(|error| format!("Failed to start io_uring driver. {error}"))
+                    .map_err(|_| IggyError::IoUringUnavailable)?;
+                Ok(LogFile::Uring {
+                    file: Arc::new(file.into_std().await),
+                    driver,
+                })
+            }
+            #[cfg(not(target_os = "linux"))]
+            IoBackend::IoUring => Err(IggyError::IoUringUnavailable),
+        }
+    }
+
+    /// Appends the header and the payload of the batch to the end of the file.
+    pub async fn write_batch(&mut self, batch: &RetainedMessageBatch) -> std::io::Result<()> {
+        let header = batch.header_as_bytes();
+        match self {
+            LogFile::Tokio(file) => {
+                let slices = [IoSlice::new(&header), IoSlice::new(&batch.bytes)];
+                let written = file.write_vectored(&slices).await?;
+                // Complete the short vectored write, if any, with the remaining part of the batch.
+                if written < header.len() {
+                    file.write_all(&header[written..]).await?;
+                    file.write_all(&batch.bytes).await?;
+                } else if written < header.len() + batch.bytes.len() {
+                    file.write_all(&batch.bytes[written - header.len()..])
+                        .await?;
+                }
+                Ok(())
+            }
+            #[cfg(target_os = "linux")]
+            LogFile::Uring { file, driver } => {
+                let buffers = vec![bytes::Bytes::copy_from_slice(&header), batch.bytes.clone()];
+                driver.write_all_at(file, CURRENT_POSITION, buffers).await
+            }
+        }
+    }
+
+    pub async fn sync_all(&self) -> std::io::Result<()> {
+        match self {
+            LogFile::Tokio(file) => file.sync_all().await,
+            #[cfg(target_os = "linux")]
+            LogFile::Uring { file, driver } => driver.sync_all(file).await,
+        }
+    }
+}
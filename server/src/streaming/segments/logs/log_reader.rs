@@ -1,3 +1,6 @@
+use crate::configs::system::IoBackend;
+#[cfg(target_os = "linux")]
+use crate::streaming::persistence::uring::UringDriver;
 use crate::streaming::{
     batching::{
         iterator::IntoMessagesIterator,
@@ -28,11 +31,18 @@ pub struct SegmentLogReader {
     file_path: String,
     file: Arc<File>,
     log_size_bytes: Arc<AtomicU64>,
+    /// When set, the reads are submitted to the io_uring driver instead of the blocking thread pool.
+    #[cfg(target_os = "linux")]
+    uring: Option<&'static UringDriver>,
 }
 
 impl SegmentLogReader {
     /// Opens the log file in read mode.
-    pub async fn new(file_path: &str, log_size_bytes: Arc<AtomicU64>) -> Result<Self, IggyError> {
+    pub async fn new(
+        file_path: &str,
+        log_size_bytes: Arc<AtomicU64>,
+        io_backend: IoBackend,
+    ) -> Result<Self, IggyError> {
         let file = OpenOptions::new()
             .read(true)
             .open(file_path)
@@ -65,13 +75,38 @@ impl SegmentLogReader {
 
         log_size_bytes.store(actual_log_size, Ordering::Release);
 
+        #[cfg(target_os = "linux")]
+        let uring = match io_backend {
+            IoBackend::Tokio => None,
+            IoBackend::IoUring => Some(
+                UringDriver::get()
+                    .with_error_context(|error| {
+                        format!(
+                            "Failed to start io_uring driver for log file: {file_path}. {error}"
+                        )
+                    })
+                    .map_err(|_| IggyError::IoUringUnavailable)?,
+            ),
+        };
+        #[cfg(not(target_os = "linux"))]
+        if io_backend == IoBackend::IoUring {
+            return Err(IggyError::IoUringUnavailable);
+        }
+
         Ok(Self {
             file_path: file_path.to_string(),
             file: Arc::new(file),
             log_size_bytes,
+            #[cfg(target_os = "linux")]
+            uring,
         })
     }
 
+    /// Returns the log file, which can be used to send its content directly to the socket.
+    pub fn file(&self) -> Arc<File> {
+        self.file.clone()
+    }
+
     /// Loads message batches given an index range.
     pub async fn load_batches_by_range_impl(
         &self,
@@ -126,6 +161,12 @@ impl SegmentLogReader {
             match self.read_next_batch(offset, file_size).await? {
                 Some((batch, bytes_read)) => {
                     offset += bytes_read;
+                    let batch = batch.decompress().with_error_context(|error| {
+                        format!(
+                            "Failed to decompress batch at offset {offset} in file {}: {error}",
+                            self.file_path
+                        )
+                    })?;
                     for msg in batch.into_messages_iter() {
                         message_ids.push(msg.id);
                     }
@@ -141,6 +182,15 @@ impl SegmentLogReader {
         Ok(message_ids)
     }
 
+    /// Loads the batch starting at the given position along with its size in bytes,
+    /// or none once the end of the log file has been reached.
+    pub async fn load_batch_at(
+        &self,
+        position: u64,
+    ) -> Result<Option<(RetainedMessageBatch, u64)>, IggyError> {
+        self.read_next_batch(position, self.file_size()).await
+    }
+
     /// Loads message batches given an index range and calls the provided callback for each batch.
     pub async fn load_batches_by_range_with_callback<F>(
         &self,
@@ -329,6 +379,11 @@ impl SegmentLogReader {
     }
 
     async fn read_at(&self, offset: u64, len: u64) -> Result<Vec<u8>, std::io::Error> {
+        #[cfg(target_os = "linux")]
+        if let Some(driver) = self.uring {
+            return driver.read_exact_at(&self.file, offset, len as usize).await;
+        }
+
         let file = self.file.clone();
         spawn_blocking(move || {
             let mut buf = vec![0u8; len as usize];
@@ -1,4 +1,5 @@
-use super::PersisterTask;
+use super::{LogFile, PersisterTask};
+use crate::configs::system::IoBackend;
 use crate::streaming::batching::message_batch::RetainedMessageBatch;
 use error_set::ErrContext;
 use iggy::{
@@ -6,17 +7,11 @@ use iggy::{
     error::IggyError,
     utils::{byte_size::IggyByteSize, duration::IggyDuration, sizeable::Sizeable},
 };
-use std::{
-    io::IoSlice,
-    sync::{
-        atomic::{AtomicU64, Ordering},
-        Arc,
-    },
-};
-use tokio::{
-    fs::{File, OpenOptions},
-    io::AsyncWriteExt,
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
 };
+use tokio::fs::OpenOptions;
 use tracing::{error, trace};
 
 /// A dedicated struct for writing to the log file.
@@ -24,7 +19,7 @@ use tracing::{error, trace};
 pub struct SegmentLogWriter {
     file_path: String,
     /// Holds the file for synchronous writes; when asynchronous persistence is enabled, this will be None.
-    file: Option<File>,
+    file: Option<LogFile>,
     /// When set, asynchronous writes are handled by this persister task.
     persister_task: Option<PersisterTask>,
     log_size_bytes: Arc<AtomicU64>,
@@ -44,6 +39,7 @@ impl SegmentLogWriter {
         server_confirmation: Confirmation,
         max_file_operation_retries: u32,
         retry_delay: IggyDuration,
+        io_backend: IoBackend,
     ) -> Result<Self, IggyError> {
         let file = OpenOptions::new()
             .write(true)
@@ -67,6 +63,8 @@ impl SegmentLogWriter {
 
         trace!("Opened log file for writing: {file_path}, size: {actual_log_size}");
 
+        let file = LogFile::new(file, io_backend).await?;
+
         let (file, persister_task) = match server_confirmation {
             Confirmation::NoWait => {
                 let persister = PersisterTask::new(
@@ -129,11 +127,7 @@ impl SegmentLogWriter {
     /// Write a batch of bytes to the log file and return the new file position.
     async fn write_batch(&mut self, batch_to_write: RetainedMessageBatch) -> Result<(), IggyError> {
         if let Some(ref mut file) = self.file {
-            let header = batch_to_write.header_as_bytes();
-            let batch_bytes = batch_to_write.bytes;
-            let slices = [IoSlice::new(&header), IoSlice::new(&batch_bytes)];
-
-            file.write_vectored(&slices)
+            file.write_batch(&batch_to_write)
                 .await
                 .with_error_context(|error| {
                     format!("Failed to log to file: {}. {error}", self.file_path)
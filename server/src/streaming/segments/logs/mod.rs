@@ -1,7 +1,9 @@
+mod log_file;
 mod log_reader;
 mod log_writer;
 mod persister_task;
 
+pub use log_file::LogFile;
 pub use log_reader::SegmentLogReader;
 pub use log_writer::SegmentLogWriter;
 pub use persister_task::PersisterTask;
@@ -1,15 +1,15 @@
+use super::LogFile;
 use crate::streaming::batching::message_batch::{RetainedMessageBatch, RETAINED_BATCH_HEADER_LEN};
 use flume::{unbounded, Receiver};
 use iggy::{error::IggyError, utils::duration::IggyDuration};
 use std::{
-    io::IoSlice,
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
     },
     time::Duration,
 };
-use tokio::{fs::File, io::AsyncWriteExt, select, time::sleep};
+use tokio::{select, time::sleep};
 use tracing::{error, trace, warn};
 
 #[derive(Debug)]
@@ -30,7 +30,7 @@ pub struct PersisterTask {
 impl PersisterTask {
     /// Creates a new persister task that takes ownership of `file`.
     pub fn new(
-        file: File,
+        file: LogFile,
         file_path: String,
         fsync: bool,
         log_file_size: Arc<AtomicU64>,
@@ -163,7 +163,7 @@ impl PersisterTask {
 
     /// The background task loop. Processes write requests until the channel is closed.
     async fn run(
-        mut file: File,
+        mut file: LogFile,
         file_path: String,
         receiver: Receiver<PersisterTaskCommand>,
         fsync: bool,
@@ -212,21 +212,18 @@ impl PersisterTask {
 
     /// Writes the provided data to the file using simple retry logic.
     async fn write_with_retries(
-        file: &mut File,
+        file: &mut LogFile,
         file_path: &str,
         batch_to_write: RetainedMessageBatch,
         fsync: bool,
         max_retries: u32,
         retry_delay: IggyDuration,
     ) -> Result<u64, IggyError> {
-        let header = batch_to_write.header_as_bytes();
-        let batch_bytes = batch_to_write.bytes;
-        let slices = [IoSlice::new(&header), IoSlice::new(&batch_bytes)];
-        let bytes_written = RETAINED_BATCH_HEADER_LEN + batch_bytes.len() as u64;
+        let bytes_written = RETAINED_BATCH_HEADER_LEN + batch_to_write.bytes.len() as u64;
 
         let mut attempts = 0;
         loop {
-            match file.write_vectored(&slices).await {
+            match file.write_batch(&batch_to_write).await {
                 Ok(_) => {
                     if fsync {
                         match file.sync_all().await {
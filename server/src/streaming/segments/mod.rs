@@ -1,3 +1,4 @@
+mod compaction;
 mod indexes;
 mod logs;
 mod reading_messages;
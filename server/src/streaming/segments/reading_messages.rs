@@ -1,13 +1,15 @@
 use super::indexes::*;
 use crate::streaming::batching::message_batch::RetainedMessageBatch;
 use crate::streaming::batching::{batch_filter::BatchItemizer, iterator::IntoMessagesIterator};
-use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::models::messages::{LogRegion, RetainedMessage};
 use crate::streaming::segments::segment::Segment;
 use error_set::ErrContext;
 use iggy::{
     error::IggyError,
+    messages::message_filter::MessageFilter,
     utils::{byte_size::IggyByteSize, checksum, sizeable::Sizeable},
 };
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tracing::{trace, warn};
 
@@ -20,7 +22,7 @@ impl Segment {
             return 0;
         }
 
-        self.current_offset - self.start_offset + 1
+        (self.current_offset - self.start_offset + 1).saturating_sub(self.removed_messages_count)
     }
 
     pub async fn get_messages_by_timestamp(
@@ -115,6 +117,40 @@ impl Segment {
         Ok(messages)
     }
 
+    /// Returns up to `count` messages from the offset matching the filter, which is evaluated while reading the segment
+    /// in chunks of `count` messages, and the offset of the last message read (matching or not) up to the end offset.
+    pub async fn get_filtered_messages_by_offset(
+        &self,
+        offset: u64,
+        count: u32,
+        end_offset: u64,
+        filter: &MessageFilter,
+    ) -> Result<(Vec<Arc<RetainedMessage>>, Option<u64>), IggyError> {
+        let end_offset = end_offset.min(self.current_offset);
+        let mut offset = offset.max(self.start_offset);
+        let mut messages = Vec::new();
+        let mut last_offset = None;
+        while (messages.len() as u32) < count && offset <= end_offset {
+            let chunk_count = (end_offset - offset + 1).min(count as u64) as u32;
+            let chunk = self.get_messages_by_offset(offset, chunk_count).await?;
+            // The messages might be missing if they have been removed by the compaction.
+            offset = chunk
+                .last()
+                .map_or(offset + chunk_count as u64, |message| message.offset + 1);
+            for message in chunk {
+                if message.offset > end_offset || messages.len() as u32 == count {
+                    break;
+                }
+
+                last_offset = Some(message.offset);
+                if message.matches(filter)? {
+                    messages.push(message);
+                }
+            }
+        }
+        Ok((messages, last_offset))
+    }
+
     pub async fn get_all_messages(&self) -> Result<Vec<Arc<RetainedMessage>>, IggyError> {
         self.get_messages_by_offset(self.start_offset, self.get_messages_count() as u32)
             .await
@@ -135,7 +171,7 @@ impl Segment {
             .unwrap()
             .load_batches_by_size_with_callback(size_bytes, |batch| {
                 total_size_bytes += batch.get_size_bytes();
-                batches.push(batch);
+                batches.push(batch.decompress()?);
                 Ok(())
             })
             .await
@@ -154,6 +190,41 @@ impl Segment {
         Ok(batches)
     }
 
+    /// Returns the region of the log file consisting of the batches with the messages in the given offset range,
+    /// or `None` if any of these messages hasn't been written to the file yet, or the indexes aren't cached.
+    pub fn get_log_region(&self, start_offset: u64, end_offset: u64) -> Option<LogRegion> {
+        let indexes = self.indexes.as_ref()?;
+        let log_reader = self.log_reader.as_ref()?;
+        let start_offset = start_offset.max(self.start_offset);
+        if start_offset > end_offset {
+            return None;
+        }
+
+        let relative_start_offset = (start_offset - self.start_offset) as u32;
+        let relative_end_offset = (end_offset - self.start_offset) as u32;
+        let first = indexes.partition_point(|index| index.offset < relative_start_offset);
+        let last = indexes.partition_point(|index| index.offset < relative_end_offset);
+        if last >= indexes.len() {
+            return None;
+        }
+
+        let position = indexes[first].position as u64;
+        let end_position = match indexes.get(last + 1) {
+            Some(index) => index.position as u64,
+            None => self.last_index_position as u64,
+        };
+        // The index is stored before the batch is written by the persister task, when the confirmation isn't awaited.
+        if end_position > self.log_size_bytes.load(Ordering::Acquire) {
+            return None;
+        }
+
+        Some(LogRegion {
+            file: log_reader.file(),
+            position,
+            length: end_position - position,
+        })
+    }
+
     fn load_messages_from_unsaved_buffer(
         &self,
         start_offset: u64,
@@ -181,6 +252,12 @@ impl Segment {
                     "Failed to load message batches by range {:?} from disk for {}. {error}",
                     index_range, self
                 )
+            })?
+            .into_iter()
+            .map(|batch| batch.decompress())
+            .collect::<Result<Vec<_>, _>>()
+            .with_error_context(|error| {
+                format!("Failed to decompress message batches for {self}. {error}")
             })?;
 
         trace!("Loaded {} message batches.", batches.len());
@@ -192,6 +269,19 @@ impl Segment {
         timestamp: u64,
     ) -> Result<Option<Index>, IggyError> {
         trace!("Loading index for timestamp: {}", timestamp);
+        if let Some(indexes) = &self.indexes {
+            if indexes.is_empty() {
+                return Ok(Some(Index::default()));
+            }
+
+            let position = indexes.partition_point(|index| index.timestamp < timestamp);
+            return Ok(match position {
+                0 => Some(Index::default()),
+                position if position == indexes.len() => None,
+                position => Some(indexes[position - 1]),
+            });
+        }
+
         let index = self
             .index_reader
             .as_ref()
@@ -253,6 +343,7 @@ impl Segment {
             .as_ref()
             .unwrap()
             .load_batches_by_range_with_callback(&IndexRange::max_range(), |batch| {
+                let batch = batch.decompress()?;
                 for message in batch.into_messages_iter() {
                     let calculated_checksum = checksum::calculate(&message.payload);
                     trace!(
@@ -4,10 +4,12 @@ use crate::configs::system::SystemConfig;
 use crate::streaming::batching::batch_accumulator::BatchAccumulator;
 use crate::streaming::segments::*;
 use error_set::ErrContext;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::error::IggyError;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::timestamp::IggyTimestamp;
+use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::fs::remove_file;
@@ -40,11 +42,14 @@ pub struct Segment {
     pub(super) index_writer: Option<SegmentIndexWriter>,
     pub(super) index_reader: Option<SegmentIndexReader>,
     pub message_expiry: IggyExpiry,
+    pub compression_algorithm: CompressionAlgorithm,
+    pub compression_level: Option<u8>,
     pub unsaved_messages: Option<BatchAccumulator>,
     pub config: Arc<SystemConfig>,
     pub indexes: Option<Vec<Index>>,
     pub(super) log_size_bytes: Arc<AtomicU64>,
     pub(super) index_size_bytes: Arc<AtomicU64>,
+    pub(super) removed_messages_count: u64,
 }
 
 impl Segment {
@@ -56,6 +61,8 @@ impl Segment {
         start_offset: u64,
         config: Arc<SystemConfig>,
         message_expiry: IggyExpiry,
+        compression_algorithm: CompressionAlgorithm,
+        compression_level: Option<u8>,
         size_of_parent_stream: Arc<AtomicU64>,
         size_of_parent_topic: Arc<AtomicU64>,
         size_of_parent_partition: Arc<AtomicU64>,
@@ -90,6 +97,8 @@ impl Segment {
             last_index_position: 0,
             max_size_bytes: config.segment.size,
             message_expiry,
+            compression_algorithm,
+            compression_level,
             indexes,
             unsaved_messages: None,
             is_closed: false,
@@ -106,6 +115,7 @@ impl Segment {
             config,
             log_size_bytes: Arc::new(AtomicU64::new(0)),
             index_size_bytes: Arc::new(AtomicU64::new(0)),
+            removed_messages_count: 0,
         }
     }
 
@@ -116,6 +126,7 @@ impl Segment {
             self.log_path, self.index_path
         );
 
+        self.recover_compaction().await?;
         if self.log_reader.is_none() || self.index_reader.is_none() {
             self.initialize_writing().await?;
             self.initialize_reading().await?;
@@ -146,6 +157,16 @@ impl Segment {
 
         self.current_offset = self.start_offset + last_index_offset;
 
+        // The index timestamps are the max timestamps of the persisted batches, the exact start timestamp
+        // of the closed segment is restored from the time index of the partition.
+        if let (Some(first_index), Some(last_index)) = (
+            self.indexes.as_ref().unwrap().first(),
+            self.indexes.as_ref().unwrap().last(),
+        ) {
+            self.start_timestamp = first_index.timestamp;
+            self.end_timestamp = last_index.timestamp;
+        }
+
         info!("Loaded {} indexes for segment with start offset: {} and partition with ID: {} for topic with ID: {} and stream with ID: {}.",
               self.indexes.as_ref().unwrap().len(),
               self.start_offset,
@@ -190,6 +211,33 @@ impl Segment {
         Ok(())
     }
 
+    /// Load the closed segment fetched from the archive, which is only used for reading its messages,
+    /// thus neither the writers are initialized nor the stats of the parents are updated.
+    pub async fn load_archived(&mut self, end_offset: u64) -> Result<(), IggyError> {
+        self.initialize_reading().await?;
+        let log_size_bytes = self.log_size_bytes.load(Ordering::Acquire);
+        self.size_bytes = IggyByteSize::from(log_size_bytes);
+        self.last_index_position = log_size_bytes as _;
+        self.indexes = match self.config.segment.cache_indexes {
+            true => Some(
+                self.index_reader
+                    .as_ref()
+                    .unwrap()
+                    .load_all_indexes_impl()
+                    .await
+                    .with_error_context(|error| {
+                        format!("Failed to load indexes for archived {self}. {error}")
+                    })
+                    .map_err(|_| IggyError::CannotReadFile)?,
+            ),
+            false => None,
+        };
+        self.current_offset = end_offset;
+        self.end_offset = end_offset;
+        self.is_closed = true;
+        Ok(())
+    }
+
     /// Save the segment state to disk.
     pub async fn persist(&mut self) -> Result<(), IggyError> {
         info!("Saving segment with start offset: {} for partition with ID: {} for topic with ID: {} and stream with ID: {}",
@@ -217,6 +265,7 @@ impl Segment {
             server_confirmation,
             max_file_operation_retries,
             retry_delay,
+            self.config.partition.io_backend,
         )
         .await?;
 
@@ -230,7 +279,12 @@ impl Segment {
     }
 
     pub async fn initialize_reading(&mut self) -> Result<(), IggyError> {
-        let log_reader = SegmentLogReader::new(&self.log_path, self.log_size_bytes.clone()).await?;
+        let log_reader = SegmentLogReader::new(
+            &self.log_path,
+            self.log_size_bytes.clone(),
+            self.config.partition.io_backend,
+        )
+        .await?;
         // TODO(hubcio): there is no need to store open fd for reader if we have index cache enabled
         let index_reader =
             SegmentIndexReader::new(&self.index_path, self.index_size_bytes.clone()).await?;
@@ -331,6 +385,17 @@ impl Segment {
                 format!("Failed to delete index file: {}. {error}", self.index_path)
             });
 
+        let compaction_manifest_path = self.get_compaction_manifest_path();
+        if Path::new(&compaction_manifest_path).exists() {
+            let _ = remove_file(&compaction_manifest_path)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "Failed to delete compaction manifest: {compaction_manifest_path}. {error}"
+                    )
+                });
+        }
+
         let segment_size_bytes = self.size_bytes.as_bytes_u64();
         self.size_of_parent_stream
             .fetch_sub(segment_size_bytes, Ordering::SeqCst);
@@ -412,6 +477,8 @@ mod tests {
             start_offset,
             config,
             message_expiry,
+            CompressionAlgorithm::None,
+            None,
             size_of_parent_stream,
             size_of_parent_topic,
             size_of_parent_partition,
@@ -464,6 +531,8 @@ mod tests {
             start_offset,
             config,
             message_expiry,
+            CompressionAlgorithm::None,
+            None,
             size_of_parent_stream,
             size_of_parent_topic,
             size_of_parent_partition,
@@ -474,4 +543,51 @@ mod tests {
 
         assert!(segment.indexes.is_none());
     }
+
+    #[tokio::test]
+    async fn should_complete_interrupted_compaction_when_loaded() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = Arc::new(SystemConfig {
+            path: temp_dir.path().to_str().unwrap().to_string(),
+            ..Default::default()
+        });
+        std::fs::create_dir_all(config.get_partition_path(1, 2, 3)).unwrap();
+        let messages_count_of_parent_partition = Arc::new(AtomicU64::new(0));
+        let mut segment = Segment::create(
+            1,
+            2,
+            3,
+            0,
+            config,
+            IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            messages_count_of_parent_partition.clone(),
+        );
+        let compacted_log_path = format!("{}.compacted", segment.log_path);
+        let compacted_index_path = format!("{}.compacted", segment.index_path);
+        std::fs::write(&segment.log_path, [0; 10]).unwrap();
+        std::fs::write(&segment.index_path, []).unwrap();
+        std::fs::write(&compacted_log_path, [0; 4]).unwrap();
+        std::fs::write(&compacted_index_path, []).unwrap();
+        let mut manifest = vec![1];
+        manifest.extend(5u64.to_le_bytes());
+        std::fs::write(segment.get_compaction_manifest_path(), manifest).unwrap();
+
+        segment.load_from_disk().await.unwrap();
+
+        assert_eq!(segment.size_bytes, 4);
+        assert_eq!(segment.removed_messages_count, 5);
+        assert!(!Path::new(&compacted_log_path).exists());
+        assert!(!Path::new(&compacted_index_path).exists());
+        assert_eq!(
+            std::fs::read(segment.get_compaction_manifest_path()).unwrap()[0],
+            0
+        );
+    }
 }
@@ -29,7 +29,7 @@ impl Segment {
             self.config.partition.messages_required_to_save as usize,
             batch.len(),
         );
-        if self.current_offset == 0 {
+        if self.size_bytes == 0 {
             self.start_timestamp = batch.first().unwrap().timestamp;
         }
         let batch_base_offset = batch.first().unwrap().offset;
@@ -106,10 +106,21 @@ impl Segment {
         );
 
         let batch = batch_accumulator.materialize_batch_and_update_state();
-        let batch_size = batch.get_size_bytes();
-        if batch_size > 0 {
+        if batch.get_size_bytes() > 0 {
             self.unsaved_messages = Some(batch_accumulator);
         }
+        let uncompressed_length = batch.length.as_bytes_u64();
+        let batch = batch
+            .compress(self.compression_algorithm, self.compression_level)
+            .with_error_context(|error| {
+                format!(
+                    "Failed to compress batch using {} algorithm for {self}. {error}",
+                    self.compression_algorithm
+                )
+            })?;
+        let batch_size = batch.get_size_bytes();
+        // The batch is stored compressed only if it's smaller, otherwise it's kept as is.
+        let saved_by_compression = uncompressed_length - batch.length.as_bytes_u64();
         let confirmation = match confirmation {
             Some(val) => val,
             None => self.config.segment.server_confirmation,
@@ -132,6 +143,19 @@ impl Segment {
             .with_error_context(|error| format!("Failed to save index for {self}. {error}"))?;
 
         self.last_index_position += batch_size.as_bytes_u64() as u32;
+        if saved_by_compression > 0 {
+            self.size_bytes = IggyByteSize::from(
+                self.size_bytes
+                    .as_bytes_u64()
+                    .saturating_sub(saved_by_compression),
+            );
+            self.size_of_parent_stream
+                .fetch_sub(saved_by_compression, Ordering::AcqRel);
+            self.size_of_parent_topic
+                .fetch_sub(saved_by_compression, Ordering::AcqRel);
+            self.size_of_parent_partition
+                .fetch_sub(saved_by_compression, Ordering::AcqRel);
+        }
         self.size_bytes += IggyByteSize::from(RETAINED_BATCH_HEADER_LEN);
         self.size_of_parent_stream
             .fetch_add(RETAINED_BATCH_HEADER_LEN, Ordering::AcqRel);
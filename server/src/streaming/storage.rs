@@ -1,8 +1,14 @@
 use super::persistence::persister::PersisterKind;
+use crate::archiver::ArchiverKind;
 use crate::configs::system::SystemConfig;
 use crate::state::system::{PartitionState, StreamState, TopicState};
+use crate::streaming::partitions::archived_segments::ArchiveCachePins;
+use crate::streaming::partitions::archived_segments::ArchivedSegment;
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
+use crate::streaming::partitions::producers::StoredProducerSequences;
 use crate::streaming::partitions::storage::FilePartitionStorage;
+use crate::streaming::partitions::time_index::SegmentTimeRange;
+use crate::streaming::partitions::transactions::StoredTransactions;
 use crate::streaming::streams::storage::FileStreamStorage;
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::systems::info::SystemInfo;
@@ -11,8 +17,10 @@ use crate::streaming::topics::storage::FileTopicStorage;
 use crate::streaming::topics::topic::Topic;
 use iggy::consumer::ConsumerKind;
 use iggy::error::IggyError;
+use iggy::messages::send_messages::ProducerSequence;
 #[cfg(test)]
 use mockall::automock;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::future::Future;
 use std::sync::Arc;
@@ -62,7 +70,7 @@ pub enum TopicStorageKind {
 pub enum PartitionStorageKind {
     File(FilePartitionStorage),
     #[cfg(test)]
-    Mock(MockPartitionStorage),
+    Mock(Box<MockPartitionStorage>),
 }
 
 #[cfg_attr(test, automock)]
@@ -121,6 +129,44 @@ pub trait PartitionStorage: Send {
         &self,
         path: &str,
     ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn save_producer_sequences(
+        &self,
+        path: &str,
+        offset: u64,
+        sequences: &HashMap<u64, ProducerSequence>,
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn load_producer_sequences(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<Option<StoredProducerSequences>, IggyError>> + Send;
+    fn save_transactions(
+        &self,
+        path: &str,
+        open_transactions: &HashMap<u64, u64>,
+        aborted_transactions: &HashMap<u64, u64>,
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn load_transactions(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<Option<StoredTransactions>, IggyError>> + Send;
+    fn save_archived_segments(
+        &self,
+        path: &str,
+        archived_segments: &[ArchivedSegment],
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn load_archived_segments(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<Vec<ArchivedSegment>, IggyError>> + Send;
+    fn save_time_index(
+        &self,
+        path: &str,
+        time_index: &[SegmentTimeRange],
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn load_time_index(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<Vec<SegmentTimeRange>, IggyError>> + Send;
 }
 
 #[derive(Debug)]
@@ -130,6 +176,8 @@ pub struct SystemStorage {
     pub topic: Arc<TopicStorageKind>,
     pub partition: Arc<PartitionStorageKind>,
     pub persister: Arc<PersisterKind>,
+    pub archiver: Option<Arc<ArchiverKind>>,
+    pub archive_cache_pins: Arc<ArchiveCachePins>,
 }
 
 impl SystemStorage {
@@ -145,8 +193,16 @@ impl SystemStorage {
                 persister.clone(),
             ))),
             persister,
+            archiver: None,
+            archive_cache_pins: Arc::new(ArchiveCachePins::default()),
         }
     }
+
+    /// Sets the archiver used for reading the segments which are no longer stored locally.
+    pub fn with_archiver(mut self, archiver: Option<Arc<ArchiverKind>>) -> Self {
+        self.archiver = archiver;
+        self
+    }
 }
 
 impl SystemInfoStorageKind {
@@ -186,5 +242,43 @@ impl PartitionStorageKind {
         ) -> Result<Vec<ConsumerOffset>, IggyError>;
         async fn delete_consumer_offsets(&self, path: &str) -> Result<(), IggyError>;
         async fn delete_consumer_offset(&self, path: &str) -> Result<(), IggyError>;
+        async fn save_producer_sequences(
+            &self,
+            path: &str,
+            offset: u64,
+            sequences: &HashMap<u64, ProducerSequence>
+        ) -> Result<(), IggyError>;
+        async fn load_producer_sequences(
+            &self,
+            path: &str
+        ) -> Result<Option<StoredProducerSequences>, IggyError>;
+        async fn save_transactions(
+            &self,
+            path: &str,
+            open_transactions: &HashMap<u64, u64>,
+            aborted_transactions: &HashMap<u64, u64>
+        ) -> Result<(), IggyError>;
+        async fn load_transactions(
+            &self,
+            path: &str
+        ) -> Result<Option<StoredTransactions>, IggyError>;
+        async fn save_archived_segments(
+            &self,
+            path: &str,
+            archived_segments: &[ArchivedSegment]
+        ) -> Result<(), IggyError>;
+        async fn load_archived_segments(
+            &self,
+            path: &str
+        ) -> Result<Vec<ArchivedSegment>, IggyError>;
+        async fn save_time_index(
+            &self,
+            path: &str,
+            time_index: &[SegmentTimeRange]
+        ) -> Result<(), IggyError>;
+        async fn load_time_index(
+            &self,
+            path: &str
+        ) -> Result<Vec<SegmentTimeRange>, IggyError>;
     }
 }
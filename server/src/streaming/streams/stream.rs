@@ -1,7 +1,9 @@
 use crate::configs::system::SystemConfig;
 use crate::streaming::storage::SystemStorage;
 use crate::streaming::topics::topic::Topic;
+use crate::streaming::utils::throughput::ThroughputWindow;
 use ahash::AHashMap;
+use iggy::models::quota::Quota;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::timestamp::IggyTimestamp;
 use std::fmt::Display;
@@ -19,6 +21,10 @@ pub struct Stream {
     pub size_bytes: Arc<AtomicU64>,
     pub messages_count: Arc<AtomicU64>,
     pub segments_count: Arc<AtomicU32>,
+    pub owner_id: u32,
+    pub quota: Option<Quota>,
+    pub produced_bytes: ThroughputWindow,
+    pub consumed_bytes: ThroughputWindow,
     pub(crate) topics: AHashMap<u32, Topic>,
     pub(crate) topics_ids: AHashMap<String, u32>,
     pub(crate) config: Arc<SystemConfig>,
@@ -54,6 +60,10 @@ impl Stream {
             size_bytes: Arc::new(AtomicU64::new(0)),
             messages_count: Arc::new(AtomicU64::new(0)),
             segments_count: Arc::new(AtomicU32::new(0)),
+            owner_id: 0,
+            quota: None,
+            produced_bytes: ThroughputWindow::default(),
+            consumed_bytes: ThroughputWindow::default(),
             topics: AHashMap::new(),
             topics_ids: AHashMap::new(),
             storage,
@@ -6,6 +6,7 @@ use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
 use iggy::locking::IggySharedMutFn;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use std::sync::atomic::Ordering;
@@ -24,8 +25,10 @@ impl Stream {
         partitions_count: u32,
         message_expiry: IggyExpiry,
         compression_algorithm: CompressionAlgorithm,
+        compression_level: Option<u8>,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<u32, IggyError> {
         let max_topic_size = Topic::get_max_topic_size(max_topic_size, &self.config)?;
         if self.topics_ids.contains_key(name) {
@@ -68,8 +71,10 @@ impl Stream {
             self.segments_count.clone(),
             message_expiry,
             compression_algorithm,
+            compression_level,
             max_topic_size,
             replication_factor,
+            cleanup_policy,
         )
         .await?;
         topic.persist().await.with_error_context(|error| {
@@ -81,14 +86,17 @@ impl Stream {
         Ok(id)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_topic(
         &mut self,
         id: &Identifier,
         name: &str,
         message_expiry: IggyExpiry,
         compression_algorithm: CompressionAlgorithm,
+        compression_level: Option<u8>,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<(), IggyError> {
         let message_expiry = Topic::get_message_expiry(message_expiry, &self.config);
         let max_topic_size = Topic::get_max_topic_size(max_topic_size, &self.config)?;
@@ -128,15 +136,24 @@ impl Stream {
             topic.name = name.to_owned();
             topic.message_expiry = message_expiry;
             topic.compression_algorithm = compression_algorithm;
+            topic.compression_level = compression_level;
+            let compression_algorithm = topic.get_compression_algorithm();
+            let compression_level = topic.get_compression_level();
             for partition in topic.partitions.values_mut() {
                 let mut partition = partition.write().await;
                 partition.message_expiry = message_expiry;
+                partition.compression_algorithm = compression_algorithm;
+                partition.compression_level = compression_level;
+                partition.cleanup_policy = cleanup_policy;
                 for segment in partition.segments.iter_mut() {
                     segment.message_expiry = message_expiry;
+                    segment.compression_algorithm = compression_algorithm;
+                    segment.compression_level = compression_level;
                 }
             }
             topic.max_topic_size = max_topic_size;
             topic.replication_factor = replication_factor;
+            topic.cleanup_policy = cleanup_policy;
             topic.persist().await.with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to persist topic: {topic}")
             })?;
@@ -292,8 +309,10 @@ mod tests {
                 1,
                 message_expiry,
                 compression_algorithm,
+                None,
                 max_topic_size,
                 1,
+                CleanupPolicy::default(),
             )
             .await
             .unwrap();
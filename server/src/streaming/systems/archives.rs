@@ -0,0 +1,347 @@
+use crate::archiver::ArchiverKind;
+use crate::configs::system::SystemConfig;
+use crate::server_error::ArchiverError;
+use crate::state::command::EntryCommand;
+use crate::state::file::FileState;
+use crate::state::system::{StreamState, SystemState, TopicState};
+use crate::state::State;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::persistence::persister::{FilePersister, PersisterKind};
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::System;
+use crate::streaming::systems::COMPONENT;
+use crate::streaming::utils::{file, random_id};
+use crate::versioning::SemanticVersion;
+use error_set::ErrContext;
+use iggy::archives::restore_archive::{ArchiveRange, RestoreArchive};
+use iggy::error::IggyError;
+use iggy::identifier::{IdKind, Identifier};
+use iggy::locking::IggySharedMut;
+use iggy::models::archive::RestoredArchive;
+use iggy::streams::create_stream::CreateStream;
+use iggy::topics::create_topic::CreateTopic;
+use iggy::utils::crypto::EncryptorKind;
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// The restore of the archive prepared under the system lock, the archived state and segments
+/// are fetched without holding it, while the lock is acquired only to recreate the stream and topics.
+#[derive(Debug)]
+pub struct ArchiveRestore {
+    archiver: Arc<ArchiverKind>,
+    config: Arc<SystemConfig>,
+    encryptor: Option<Arc<EncryptorKind>>,
+    requires_archived_state: bool,
+    archived_state: Option<SystemState>,
+    partitions: Vec<IggySharedMut<Partition>>,
+    restored_archive: RestoredArchive,
+}
+
+impl System {
+    /// Prepares the restore of the stream, topic or partition from the archived segments,
+    /// the archived state is required only if the stream or the topic doesn't exist.
+    pub fn prepare_archive_restore(
+        &self,
+        session: &Session,
+        command: &RestoreArchive,
+    ) -> Result<ArchiveRestore, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner.create_stream(session.get_user_id())?;
+        let archiver = self.archiver.clone().ok_or(IggyError::ArchiverDisabled)?;
+        let requires_archived_state = match self.get_stream(&command.stream_id) {
+            Ok(stream) => command
+                .topic_id
+                .as_ref()
+                .is_some_and(|topic_id| stream.get_topic(topic_id).is_err()),
+            Err(_) => true,
+        };
+        Ok(ArchiveRestore {
+            archiver,
+            config: self.config.clone(),
+            encryptor: self.encryptor.clone(),
+            requires_archived_state,
+            archived_state: None,
+            partitions: Vec::new(),
+            restored_archive: RestoredArchive::default(),
+        })
+    }
+
+    /// Recreates the stream and the topics, which don't exist, from the archived state and collects
+    /// the partitions to restore. Returns the state entries of the recreated stream and topics,
+    /// which have to be applied to the state, as if they were created by the user.
+    pub async fn recreate_archived_resources(
+        &mut self,
+        session: &Session,
+        command: &RestoreArchive,
+        archive_restore: &mut ArchiveRestore,
+    ) -> Result<Vec<EntryCommand>, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner.create_stream(session.get_user_id())?;
+        let mut entries = Vec::new();
+        let archived_state = archive_restore.archived_state.as_ref();
+        let (stream_id, is_recreated) = match self.get_stream(&command.stream_id) {
+            Ok(stream) => (stream.stream_id, false),
+            Err(_) => {
+                let stream_state = archived_state
+                    .and_then(|state| find_archived_stream(state, &command.stream_id))
+                    .ok_or(IggyError::ArchivedStreamNotFound(
+                        command.stream_id.to_string(),
+                    ))?;
+                let stream_id = stream_state.id;
+                let name = stream_state.name.clone();
+                self.create_stream(session, Some(stream_id), &name)
+                    .await
+                    .with_error_context(|error| {
+                        format!("{COMPONENT} (error: {error}) - failed to recreate archived stream with ID: {stream_id}")
+                    })?;
+                entries.push(EntryCommand::CreateStream(CreateStream {
+                    stream_id: Some(stream_id),
+                    name,
+                }));
+                archive_restore.restored_archive.created_streams_count += 1;
+                info!("Recreated archived stream with ID: {stream_id}.");
+                (stream_id, true)
+            }
+        };
+
+        let stream_identifier = Identifier::numeric(stream_id)?;
+        let topic_ids = match &command.topic_id {
+            Some(topic_id) => vec![topic_id.clone()],
+            None if is_recreated => archived_state
+                .and_then(|state| state.streams.get(&stream_id))
+                .map(|stream| {
+                    let mut topic_ids = stream.topics.keys().copied().collect::<Vec<_>>();
+                    topic_ids.sort();
+                    topic_ids
+                })
+                .unwrap_or_default()
+                .into_iter()
+                .map(Identifier::numeric)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => {
+                let mut topic_ids = self
+                    .get_stream(&stream_identifier)?
+                    .get_topics()
+                    .iter()
+                    .map(|topic| topic.topic_id)
+                    .collect::<Vec<_>>();
+                topic_ids.sort();
+                topic_ids
+                    .into_iter()
+                    .map(Identifier::numeric)
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+        };
+
+        for topic_id in topic_ids {
+            let topic_exists = self
+                .get_stream(&stream_identifier)?
+                .get_topic(&topic_id)
+                .is_ok();
+            if !topic_exists {
+                let topic_state = archived_state
+                    .and_then(|state| state.streams.get(&stream_id))
+                    .and_then(|stream| find_archived_topic(stream, &topic_id))
+                    .ok_or(IggyError::ArchivedTopicNotFound(
+                        topic_id.to_string(),
+                        stream_id.to_string(),
+                    ))?;
+                let entry = self
+                    .recreate_archived_topic(session, &stream_identifier, topic_state)
+                    .await?;
+                entries.push(entry);
+                archive_restore.restored_archive.created_topics_count += 1;
+            }
+
+            let topic = self.get_stream(&stream_identifier)?.get_topic(&topic_id)?;
+            let partition_ids = match command.partition_id {
+                Some(partition_id) => vec![partition_id],
+                None => {
+                    let mut partition_ids = topic.partitions.keys().copied().collect::<Vec<_>>();
+                    partition_ids.sort();
+                    partition_ids
+                }
+            };
+            for partition_id in partition_ids {
+                archive_restore
+                    .partitions
+                    .push(topic.get_partition(partition_id)?);
+            }
+        }
+
+        Ok(entries)
+    }
+
+    async fn recreate_archived_topic(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_state: &TopicState,
+    ) -> Result<EntryCommand, IggyError> {
+        let command = CreateTopic {
+            stream_id: stream_id.clone(),
+            topic_id: Some(topic_state.id),
+            partitions_count: topic_state.partitions.len() as u32,
+            compression_algorithm: topic_state.compression_algorithm,
+            compression_level: topic_state.compression_level,
+            message_expiry: topic_state.message_expiry,
+            max_topic_size: topic_state.max_topic_size,
+            replication_factor: topic_state.replication_factor,
+            cleanup_policy: topic_state.cleanup_policy,
+            name: topic_state.name.clone(),
+        };
+        self.create_topic(
+            session,
+            &command.stream_id,
+            command.topic_id,
+            &command.name,
+            command.partitions_count,
+            command.message_expiry,
+            command.compression_algorithm,
+            command.compression_level,
+            command.max_topic_size,
+            command.replication_factor,
+            command.cleanup_policy,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to recreate archived topic with ID: {}, stream ID: {stream_id}",
+                topic_state.id
+            )
+        })?;
+        info!(
+            "Recreated archived topic with ID: {} for stream with ID: {stream_id}.",
+            topic_state.id
+        );
+        Ok(EntryCommand::CreateTopic(command))
+    }
+}
+
+impl ArchiveRestore {
+    /// Fetches and loads the archived state (from the snapshot directory if specified), unless it's not required.
+    pub async fn fetch_archived_state(
+        &mut self,
+        state_snapshot: Option<&str>,
+    ) -> Result<(), IggyError> {
+        if !self.requires_archived_state {
+            return Ok(());
+        }
+
+        self.archived_state = Some(self.load_archived_state(state_snapshot).await?);
+        Ok(())
+    }
+
+    /// Restores the archived segments of the collected partitions, without holding the system lock.
+    pub async fn restore_partitions(
+        self,
+        range: ArchiveRange,
+    ) -> Result<RestoredArchive, IggyError> {
+        let mut restored_archive = self.restored_archive;
+        for partition in &self.partitions {
+            let restored_segments = Partition::restore_archived_segments(partition, range)
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to restore archived segments")
+                })?;
+            restored_archive.segments_count += restored_segments.segments_count;
+            restored_archive.messages_count += restored_segments.messages_count;
+        }
+
+        info!(
+            "Restored {} archived segments with {} messages for {} partitions.",
+            restored_archive.segments_count,
+            restored_archive.messages_count,
+            self.partitions.len()
+        );
+        Ok(restored_archive)
+    }
+
+    /// Fetches the archived state log and snapshot (from the snapshot directory if specified) into the runtime directory and loads them.
+    async fn load_archived_state(
+        &self,
+        state_snapshot: Option<&str>,
+    ) -> Result<SystemState, IggyError> {
+        let archiver = &self.archiver;
+        let state_log_path = self.config.get_state_log_path();
+        let state_snapshot_path = self.config.get_state_snapshot_path();
+        let fetched_id = random_id::get_ulid();
+        let runtime_path = self.config.get_runtime_path();
+        let fetched_log_path = format!("{runtime_path}/archived_state_{fetched_id}");
+        let fetched_snapshot_path = format!("{runtime_path}/archived_state_snapshot_{fetched_id}");
+        let base_directory = state_snapshot.map(|state_snapshot| state_snapshot.to_owned());
+        if let Err(error) = archiver
+            .fetch(&state_log_path, &fetched_log_path, base_directory.clone())
+            .await
+        {
+            error!(
+                "Failed to fetch archived state from snapshot: {}. Error: {error}",
+                state_snapshot.unwrap_or("latest")
+            );
+            return match error {
+                ArchiverError::ArchivedFileNotFound { .. } => Err(IggyError::ArchivedStateNotFound),
+                _ => Err(IggyError::CannotRestoreArchivedFile(state_log_path)),
+            };
+        }
+
+        // The state snapshot is archived only if it was created, otherwise the log contains all the entries.
+        match archiver
+            .fetch(&state_snapshot_path, &fetched_snapshot_path, base_directory)
+            .await
+        {
+            Ok(()) | Err(ArchiverError::ArchivedFileNotFound { .. }) => {}
+            Err(error) => {
+                error!("Failed to fetch archived state snapshot. Error: {error}");
+                let _ = file::remove(&fetched_log_path).await;
+                return Err(IggyError::CannotRestoreArchivedFile(state_snapshot_path));
+            }
+        }
+
+        let state = FileState::new(
+            &fetched_log_path,
+            &fetched_snapshot_path,
+            &SemanticVersion::current()?,
+            Arc::new(PersisterKind::File(FilePersister)),
+            self.encryptor.clone(),
+        );
+        let archived_state = match state.init().await {
+            Ok(entries) => state
+                .load_snapshot()
+                .await
+                .map(|snapshot| (snapshot, entries)),
+            Err(error) => Err(error),
+        };
+        let _ = file::remove(&fetched_log_path).await;
+        let _ = file::remove(&fetched_snapshot_path).await;
+        let (snapshot, entries) = archived_state.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to load archived state")
+        })?;
+        SystemState::init_from_snapshot(snapshot.map(|snapshot| snapshot.state), entries).await
+    }
+}
+
+fn find_archived_stream<'a>(
+    state: &'a SystemState,
+    stream_id: &Identifier,
+) -> Option<&'a StreamState> {
+    match stream_id.kind {
+        IdKind::Numeric => state.streams.get(&stream_id.get_u32_value().ok()?),
+        IdKind::String => {
+            let name = stream_id.get_cow_str_value().ok()?;
+            state.streams.values().find(|stream| stream.name == name)
+        }
+    }
+}
+
+fn find_archived_topic<'a>(
+    stream: &'a StreamState,
+    topic_id: &Identifier,
+) -> Option<&'a TopicState> {
+    match topic_id.kind {
+        IdKind::Numeric => stream.topics.get(&topic_id.get_u32_value().ok()?),
+        IdKind::String => {
+            let name = topic_id.get_cow_str_value().ok()?;
+            stream.topics.values().find(|topic| topic.name == name)
+        }
+    }
+}
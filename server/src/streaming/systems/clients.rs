@@ -46,6 +46,7 @@ impl System {
             );
         }
 
+        self.abort_client_transactions(client_id).await;
         for (stream_id, topic_id, consumer_group_id) in consumer_groups.into_iter() {
             _ = self
                 .leave_consumer_group_by_client(
@@ -3,9 +3,11 @@ use crate::streaming::systems::system::System;
 use crate::streaming::systems::COMPONENT;
 use crate::streaming::topics::consumer_group::ConsumerGroup;
 use error_set::ErrContext;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
 use iggy::locking::IggySharedMutFn;
+use iggy::models::consumer_group::ConsumerGroupMembership;
 use tokio::sync::RwLock;
 
 impl System {
@@ -62,6 +64,7 @@ impl System {
         topic_id: &Identifier,
         group_id: Option<u32>,
         name: &str,
+        assignment_strategy: PartitionAssignmentStrategy,
     ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
         self.ensure_authenticated(session)?;
         {
@@ -80,7 +83,7 @@ impl System {
             .with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream_id: {stream_id}, topic_id: {topic_id}"))?;
 
         topic
-            .create_consumer_group(group_id, name)
+            .create_consumer_group(group_id, name, assignment_strategy)
             .await
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to create consumer group with name: {name}")
@@ -149,7 +152,7 @@ impl System {
         stream_id: &Identifier,
         topic_id: &Identifier,
         consumer_group_id: &Identifier,
-    ) -> Result<(), IggyError> {
+    ) -> Result<ConsumerGroupMembership, IggyError> {
         self.ensure_authenticated(session)?;
         let stream_id_value;
         let topic_id_value;
@@ -173,6 +176,7 @@ impl System {
         }
 
         let group_id;
+        let membership;
         {
             let topic = self.find_topic(session, stream_id, topic_id)?;
 
@@ -190,7 +194,7 @@ impl System {
                 group_id = consumer_group.group_id;
             }
 
-            topic
+            membership = topic
                 .join_consumer_group(consumer_group_id, session.client_id)
                 .await
                 .with_error_context(|error| {
@@ -212,7 +216,7 @@ impl System {
                 )
             })?;
 
-        Ok(())
+        Ok(membership)
     }
 
     pub async fn leave_consumer_group(
@@ -8,6 +8,7 @@ use iggy::identifier::Identifier;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
 
 impl System {
+    #[allow(clippy::too_many_arguments)]
     pub async fn store_consumer_offset(
         &self,
         session: &Session,
@@ -16,6 +17,7 @@ impl System {
         topic_id: &Identifier,
         partition_id: Option<u32>,
         offset: u64,
+        generation: Option<u32>,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
         let topic = self.find_topic(session, stream_id, topic_id)
@@ -27,7 +29,13 @@ impl System {
         )?;
 
         topic
-            .store_consumer_offset(consumer, offset, partition_id, session.client_id)
+            .store_consumer_offset(
+                consumer,
+                offset,
+                partition_id,
+                generation,
+                session.client_id,
+            )
             .await
     }
 
@@ -1,19 +1,33 @@
+use crate::cluster::node::MessagesReplication;
 use crate::streaming::cache::memory_tracker::CacheMemoryTracker;
+use crate::streaming::models::messages::PolledMessagesKind;
+use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::session::Session;
+use crate::streaming::streams::stream::Stream;
 use crate::streaming::systems::system::System;
 use crate::streaming::systems::COMPONENT;
+use crate::streaming::topics::topic::Topic;
 use bytes::Bytes;
 use error_set::ErrContext;
 use iggy::confirmation::Confirmation;
-use iggy::consumer::Consumer;
-use iggy::messages::poll_messages::PollingStrategy;
+use iggy::consumer::{Consumer, ConsumerKind};
+use iggy::locking::IggySharedMutFn;
+use iggy::messages::message_filter::MessageFilter;
+use iggy::messages::poll_messages::{IsolationLevel, PollingStrategy};
 use iggy::messages::send_messages::Message;
 use iggy::messages::send_messages::Partitioning;
 use iggy::models::messages::{PolledMessage, PolledMessages};
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::sizeable::Sizeable;
 use iggy::{error::IggyError, identifier::Identifier};
-use tracing::{error, trace};
+use tracing::{error, info, trace, warn};
+
+struct PollingTarget<'a> {
+    topic: &'a Topic,
+    stream: &'a Stream,
+    consumer: Option<(PollingConsumer, u32)>,
+    generation: Option<u32>,
+}
 
 impl System {
     pub async fn poll_messages(
@@ -25,6 +39,72 @@ impl System {
         partition_id: Option<u32>,
         args: PollingArgs,
     ) -> Result<PolledMessages, IggyError> {
+        let target = self
+            .resolve_polling_target(session, consumer, stream_id, topic_id, partition_id, &args)
+            .await?;
+        self.poll_resolved_messages(session, consumer, stream_id, topic_id, target, args)
+            .await
+    }
+
+    /// Polls the messages as the batches stored in the segment log files, which can be sent without loading
+    /// them into memory, or as the regular messages, if the batches can't be sent as stored, e.g. when the
+    /// server-side encryption is enabled, the messages are filtered, or they haven't been persisted yet.
+    pub async fn poll_messages_or_batches(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        args: PollingArgs,
+    ) -> Result<PolledMessagesKind, IggyError> {
+        let target = self
+            .resolve_polling_target(session, consumer, stream_id, topic_id, partition_id, &args)
+            .await?;
+        let can_poll_batches = self.encryptor.is_none()
+            && args.isolation_level == IsolationLevel::ReadUncommitted
+            && args.filter.is_empty();
+        if let (true, Some((polling_consumer, partition_id))) = (can_poll_batches, target.consumer)
+        {
+            if let Some(mut batches) = target
+                .topic
+                .get_batches(polling_consumer, partition_id, args.strategy, args.count)
+                .await
+                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to get batches for consumer: {consumer}, stream_id: {stream_id}, topic_id: {topic_id}, partition ID: {partition_id}"))?
+            {
+                batches.generation = target.generation;
+                self.record_consumed_bytes(
+                    session.get_user_id(),
+                    target.stream,
+                    batches.get_size_bytes(),
+                );
+                if args.auto_commit {
+                    trace!("Last offset: {} will be automatically stored for {}, stream: {}, topic: {}, partition: {}", batches.last_offset, consumer, stream_id, topic_id, partition_id);
+                    target
+                        .topic
+                        .store_consumer_offset_internal(polling_consumer, batches.last_offset, partition_id)
+                        .await
+                        .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to store consumer offset internal, polling consumer: {}, offset: {}, partition ID: {}", polling_consumer, batches.last_offset, partition_id))?;
+                }
+                return Ok(PolledMessagesKind::Batches(batches));
+            }
+        }
+
+        let messages = self
+            .poll_resolved_messages(session, consumer, stream_id, topic_id, target, args)
+            .await?;
+        Ok(PolledMessagesKind::Messages(messages))
+    }
+
+    async fn resolve_polling_target(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        args: &PollingArgs,
+    ) -> Result<PollingTarget<'_>, IggyError> {
         self.ensure_authenticated(session)?;
         if args.count == 0 {
             return Err(IggyError::InvalidMessagesCount);
@@ -44,27 +124,115 @@ impl System {
             return Err(IggyError::NoPartitions(topic.topic_id, topic.stream_id));
         }
 
-        // There might be no partition assigned, if it's the consumer group member without any partitions.
-        let Some((polling_consumer, partition_id)) = topic
+        let stream = self.get_stream_by_id(topic.stream_id)?;
+        self.ensure_poll_quotas(session.get_user_id(), stream)?;
+
+        let resolved_consumer = topic
             .resolve_consumer_with_partition_id(consumer, session.client_id, partition_id, true)
             .await
-            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to resolve consumer with partition id, consumer: {consumer}, client ID: {}, partition ID: {:?}", session.client_id, partition_id))? else {
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to resolve consumer with partition id, consumer: {consumer}, client ID: {}, partition ID: {:?}", session.client_id, partition_id))?;
+
+        // The generation is resolved after the partition, as calculating the partition might complete the rebalance.
+        let generation = match consumer.kind {
+            ConsumerKind::Consumer => None,
+            ConsumerKind::ConsumerGroup => {
+                let consumer_group =
+                    topic
+                        .get_consumer_group(&consumer.id)
+                        .with_error_context(|error| {
+                            format!(
+                            "{COMPONENT} (error: {error}) - consumer group not found for ID: {}",
+                            consumer.id
+                        )
+                        })?;
+                let generation = consumer_group.read().await.get_generation();
+                Some(generation)
+            }
+        };
+
+        Ok(PollingTarget {
+            topic,
+            stream,
+            consumer: resolved_consumer,
+            generation,
+        })
+    }
+
+    async fn poll_resolved_messages(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        target: PollingTarget<'_>,
+        args: PollingArgs,
+    ) -> Result<PolledMessages, IggyError> {
+        let PollingTarget {
+            topic,
+            stream,
+            consumer: resolved_consumer,
+            generation,
+        } = target;
+
+        // There might be no partition assigned, if it's the consumer group member without any partitions.
+        let Some((polling_consumer, partition_id)) = resolved_consumer else {
             return Ok(PolledMessages {
                 messages: vec![],
                 partition_id: 0,
                 current_offset: 0,
-            })
+                generation,
+            });
         };
 
-        let mut polled_messages = topic
-            .get_messages(polling_consumer, partition_id, args.strategy, args.count)
-            .await?;
+        let read_committed = args.isolation_level == IsolationLevel::ReadCommitted;
+        let (mut polled_messages, mut last_offset) = if args.filter.is_empty() {
+            let polled_messages = topic
+                .get_messages(polling_consumer, partition_id, args.strategy, args.count)
+                .await?;
+            let last_offset = polled_messages
+                .messages
+                .last()
+                .map(|message| message.offset);
+            (polled_messages, last_offset)
+        } else {
+            // The last offset is the one of the last message read, so the auto commit skips the non-matching messages as well.
+            topic
+                .get_filtered_messages(
+                    polling_consumer,
+                    partition_id,
+                    args.strategy,
+                    args.count,
+                    &args.filter,
+                    read_committed,
+                )
+                .await
+                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to get filtered messages for consumer: {consumer}, stream_id: {stream_id}, topic_id: {topic_id}, partition ID: {partition_id}"))?
+        };
+        polled_messages.generation = generation;
+        let polled_size_bytes = polled_messages
+            .messages
+            .iter()
+            .map(|message| message.get_size_bytes().as_bytes_u64())
+            .sum::<u64>();
+        self.record_consumed_bytes(session.get_user_id(), stream, polled_size_bytes);
 
-        if polled_messages.messages.is_empty() {
-            return Ok(polled_messages);
+        if read_committed {
+            let partition = topic.get_partition(partition_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - partition with ID: {partition_id} was not found for stream_id: {stream_id}, topic_id: {topic_id}"))?;
+            let partition = partition.read().await;
+            let (messages, offset) =
+                partition.filter_committed_messages(polled_messages.messages)?;
+            polled_messages.messages = messages;
+            // The filtered messages have been read only up to the last stable offset already.
+            if args.filter.is_empty() {
+                last_offset = offset;
+            }
         }
 
-        let offset = polled_messages.messages.last().unwrap().offset;
+        // The offset might be available even without any messages, if they all belonged to the aborted transactions.
+        let Some(offset) = last_offset else {
+            return Ok(polled_messages);
+        };
+
         if args.auto_commit {
             trace!("Last offset: {} will be automatically stored for {}, stream: {}, topic: {}, partition: {}", offset, consumer, stream_id, topic_id, partition_id);
             topic
@@ -104,6 +272,8 @@ impl System {
         Ok(polled_messages)
     }
 
+    /// Appends the messages to the topic and returns the replication of the partition they were appended to,
+    /// if the topic is replicated in the cluster mode, which should be awaited once the system lock is released.
     pub async fn append_messages(
         &self,
         session: &Session,
@@ -112,7 +282,7 @@ impl System {
         partitioning: Partitioning,
         messages: Vec<Message>,
         confirmation: Option<Confirmation>,
-    ) -> Result<(), IggyError> {
+    ) -> Result<Option<MessagesReplication>, IggyError> {
         self.ensure_authenticated(session)?;
         let topic = self.find_topic(session, &stream_id, &topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream_id: {stream_id}, topic_id: {topic_id}"))?;
         self.permissioner.append_messages(
@@ -126,6 +296,13 @@ impl System {
             topic.topic_id
         ))?;
 
+        let transaction_id = Self::get_messages_transaction_id(&messages)?;
+        if let Some(transaction_id) = transaction_id {
+            self.transactions
+                .ensure_open(transaction_id, session.get_user_id())
+                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - cannot append messages for stream_id: {stream_id}, topic_id: {topic_id} within transaction with ID: {transaction_id}"))?;
+        }
+
         let mut batch_size_bytes = IggyByteSize::default();
         let mut messages = messages;
         if let Some(encryptor) = &self.encryptor {
@@ -150,16 +327,137 @@ impl System {
                 .sum::<IggyByteSize>();
         }
 
+        let stream = self.get_stream_by_id(topic.stream_id)?;
+        self.ensure_append_quotas(
+            session.get_user_id(),
+            stream,
+            batch_size_bytes.as_bytes_u64(),
+        )
+        .with_error_context(|error| format!("{COMPONENT} (error: {error}) - quota exceeded while appending messages for stream_id: {stream_id}, topic_id: {topic_id}"))?;
+
         if let Some(memory_tracker) = CacheMemoryTracker::get_instance() {
             if !memory_tracker.will_fit_into_cache(batch_size_bytes) {
                 self.clean_cache(batch_size_bytes).await;
             }
         }
         let messages_count = messages.len() as u64;
-        topic
+        let partition_id = topic
             .append_messages(batch_size_bytes, partitioning, messages, confirmation)
             .await?;
         self.metrics.increment_messages(messages_count);
+        self.record_produced_bytes(
+            session.get_user_id(),
+            stream,
+            batch_size_bytes.as_bytes_u64(),
+        );
+        if let Some(transaction_id) = transaction_id {
+            if let Err(error) = self.transactions.add_partition(
+                transaction_id,
+                topic.stream_id,
+                topic.topic_id,
+                partition_id,
+            ) {
+                // The transaction has been completed while appending the messages, so they have to be aborted.
+                let partition = topic.get_partition(partition_id)?;
+                partition
+                    .write()
+                    .await
+                    .complete_transaction(transaction_id, false)
+                    .await?;
+                return Err(error);
+            }
+        }
+        let Some(cluster) = &self.cluster else {
+            return Ok(None);
+        };
+        cluster
+            .replicate_messages(topic, partition_id)
+            .await
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to replicate messages for stream_id: {stream_id}, topic_id: {topic_id}, partition ID: {partition_id}"))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn nack_message(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+        reason: &str,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream_id: {stream_id}, topic_id: {topic_id}"))?;
+        self.permissioner
+            .poll_messages(session.get_user_id(), topic.stream_id, topic.topic_id)
+            .with_error_context(|error| format!(
+                "{COMPONENT} (error: {error}) - permission denied to nack message for user {} on stream_id: {}, topic_id: {}",
+                session.get_user_id(),
+                topic.stream_id,
+                topic.topic_id
+            ))?;
+
+        let config = &self.config.dead_letter;
+        let attempts = topic
+            .record_delivery_attempt(consumer, partition_id, offset, session.client_id)
+            .await
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to record delivery attempt for consumer: {consumer}, stream_id: {stream_id}, topic_id: {topic_id}, partition ID: {partition_id}, offset: {offset}"))?;
+
+        // The attempts keep growing past the limit, so the message is moved only by the nack which reaches it.
+        if !config.enabled || attempts != config.max_delivery_attempts {
+            return Ok(());
+        }
+
+        let dead_letter_stream_id = Identifier::from_str_value(&config.stream)?;
+        let dead_letter_topic_id = Identifier::from_str_value(&config.topic)?;
+        let dead_letter_topic = self
+            .get_stream(&dead_letter_stream_id)
+            .and_then(|stream| stream.get_topic(&dead_letter_topic_id))
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - dead letter topic: {dead_letter_topic_id} not found in stream: {dead_letter_stream_id}"))?;
+        if dead_letter_topic.stream_id == topic.stream_id
+            && dead_letter_topic.topic_id == topic.topic_id
+        {
+            warn!("Message at offset: {offset} in partition: {partition_id} has been nacked from the dead letter topic: {topic_id} in stream: {stream_id}, it will not be moved.");
+            return Ok(());
+        }
+
+        // The message is marked as poisoned before being moved, so it's not moved again by the other consumers.
+        if !topic.mark_message_as_poisoned(partition_id, offset).await? {
+            return Ok(());
+        }
+
+        let moved = async {
+            let message = topic
+                .get_dead_letter_message(partition_id, offset, reason)
+                .await
+                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to get message at offset: {offset}, partition ID: {partition_id}, stream_id: {stream_id}, topic_id: {topic_id}"))?;
+            let batch_size_bytes = message.get_size_bytes();
+            if let Some(memory_tracker) = CacheMemoryTracker::get_instance() {
+                if !memory_tracker.will_fit_into_cache(batch_size_bytes) {
+                    self.clean_cache(batch_size_bytes).await;
+                }
+            }
+            dead_letter_topic
+                .append_messages(
+                    batch_size_bytes,
+                    Partitioning::balanced(),
+                    vec![message],
+                    None,
+                )
+                .await
+                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to append message to the dead letter topic: {dead_letter_topic_id} in stream: {dead_letter_stream_id}"))
+        }
+        .await;
+        if let Err(error) = moved {
+            topic
+                .unmark_message_as_poisoned(partition_id, offset)
+                .await?;
+            return Err(error);
+        }
+
+        self.metrics.increment_messages(1);
+        info!("Message at offset: {offset} in partition: {partition_id} of topic: {topic_id} in stream: {stream_id} has been moved to the dead letter topic: {dead_letter_topic_id} in stream: {dead_letter_stream_id} after {attempts} delivery attempts.");
         Ok(())
     }
 
@@ -187,6 +485,28 @@ impl System {
         topic.flush_unsaved_buffer(partition_id, fsync).await?;
         Ok(())
     }
+
+    pub async fn init_producer(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        producer_id: u64,
+    ) -> Result<u64, IggyError> {
+        self.ensure_authenticated(session)?;
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream_id: {stream_id}, topic_id: {topic_id}"))?;
+        self.permissioner.append_messages(
+            session.get_user_id(),
+            topic.stream_id,
+            topic.topic_id,
+        ).with_error_context(|error| format!(
+            "{COMPONENT} (error: {error}) - permission denied to append messages for user {} on stream_id: {}, topic_id: {}",
+            session.get_user_id(),
+            topic.stream_id,
+            topic.topic_id
+        ))?;
+        topic.init_producer(producer_id).await
+    }
 }
 
 #[derive(Debug)]
@@ -194,14 +514,24 @@ pub struct PollingArgs {
     pub strategy: PollingStrategy,
     pub count: u32,
     pub auto_commit: bool,
+    pub isolation_level: IsolationLevel,
+    pub filter: MessageFilter,
 }
 
 impl PollingArgs {
-    pub fn new(strategy: PollingStrategy, count: u32, auto_commit: bool) -> Self {
+    pub fn new(
+        strategy: PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+        filter: MessageFilter,
+    ) -> Self {
         Self {
             strategy,
             count,
             auto_commit,
+            isolation_level,
+            filter,
         }
     }
 }
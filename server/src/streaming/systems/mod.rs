@@ -1,3 +1,4 @@
+pub mod archives;
 pub mod clients;
 pub mod consumer_groups;
 pub mod consumer_offsets;
@@ -5,12 +6,15 @@ pub mod info;
 pub mod messages;
 pub mod partitions;
 pub mod personal_access_tokens;
+pub mod quotas;
+pub mod roles;
 pub mod snapshot;
 pub mod stats;
 pub mod storage;
 pub mod streams;
 pub mod system;
 pub mod topics;
+pub mod transactions;
 pub mod users;
 
 pub const COMPONENT: &str = "STREAMING_SYSTEMS";
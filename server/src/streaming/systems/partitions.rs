@@ -28,6 +28,11 @@ impl System {
             ))?;
         }
 
+        self.ensure_topology_quotas(stream_id, 0, partitions_count)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - quota exceeded while creating partitions for stream_id: {stream_id}, topic_id: {topic_id}")
+            })?;
+
         let topic = self
             .get_stream_mut(stream_id)?
             .get_topic_mut(topic_id)
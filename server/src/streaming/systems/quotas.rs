@@ -0,0 +1,239 @@
+use crate::streaming::session::Session;
+use crate::streaming::streams::stream::Stream;
+use crate::streaming::systems::system::System;
+use crate::streaming::systems::COMPONENT;
+use crate::streaming::users::user::User;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::models::quota::Quota;
+use std::sync::atomic::Ordering;
+use tracing::info;
+
+impl System {
+    pub async fn set_stream_quota(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        quota: Quota,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .set_stream_quota(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to set quota for stream with ID: {stream_id} for user with ID: {}",
+                    session.get_user_id()
+                )
+            })?;
+
+        let stream = self.get_stream_mut(stream_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get mutable reference to stream with ID: {stream_id}")
+        })?;
+        stream.quota = Some(quota).filter(|quota| !quota.is_unlimited());
+        info!("Set quota for stream with ID: {stream_id} ({quota}).");
+        Ok(())
+    }
+
+    pub async fn set_user_quota(
+        &mut self,
+        session: &Session,
+        user_id: &Identifier,
+        quota: Quota,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .set_user_quota(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to set quota for user with ID: {user_id} for user with ID: {}",
+                    session.get_user_id()
+                )
+            })?;
+
+        let user = self.get_user_mut(user_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get mutable reference to the user with ID: {user_id}")
+        })?;
+        user.quota = Some(quota).filter(|quota| !quota.is_unlimited());
+        info!(
+            "Set quota for user: {} with ID: {user_id} ({quota}).",
+            user.username
+        );
+        Ok(())
+    }
+
+    /// Ensures that appending the batch of given size does not exceed the storage and produce throughput quotas
+    /// of the stream, its owner and the user sending the messages.
+    pub(crate) fn ensure_append_quotas(
+        &self,
+        user_id: u32,
+        stream: &Stream,
+        batch_size_bytes: u64,
+    ) -> Result<(), IggyError> {
+        if let Some(quota) = &stream.quota {
+            let size_bytes = stream.size_bytes.load(Ordering::SeqCst);
+            if quota.max_size > 0 && size_bytes + batch_size_bytes > quota.max_size {
+                return Err(IggyError::StreamSizeQuotaExceeded(
+                    stream.stream_id,
+                    quota.max_size,
+                ));
+            }
+
+            if stream
+                .produced_bytes
+                .is_exceeded(quota.max_produce_bytes_per_second)
+            {
+                return Err(IggyError::StreamProduceThroughputQuotaExceeded(
+                    stream.stream_id,
+                    quota.max_produce_bytes_per_second,
+                ));
+            }
+        }
+
+        if let Some((owner, quota)) = self.get_user_with_quota(stream.owner_id) {
+            if quota.max_size > 0 {
+                let size_bytes = self
+                    .get_owned_streams(owner.id)
+                    .map(|stream| stream.size_bytes.load(Ordering::SeqCst))
+                    .sum::<u64>();
+                if size_bytes + batch_size_bytes > quota.max_size {
+                    return Err(IggyError::UserSizeQuotaExceeded(owner.id, quota.max_size));
+                }
+            }
+        }
+
+        if let Some((user, quota)) = self.get_user_with_quota(user_id) {
+            if user
+                .produced_bytes
+                .is_exceeded(quota.max_produce_bytes_per_second)
+            {
+                return Err(IggyError::UserProduceThroughputQuotaExceeded(
+                    user.id,
+                    quota.max_produce_bytes_per_second,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ensures that the consume throughput quotas of the stream and the user polling the messages are not exceeded.
+    pub(crate) fn ensure_poll_quotas(
+        &self,
+        user_id: u32,
+        stream: &Stream,
+    ) -> Result<(), IggyError> {
+        if let Some(quota) = &stream.quota {
+            if stream
+                .consumed_bytes
+                .is_exceeded(quota.max_consume_bytes_per_second)
+            {
+                return Err(IggyError::StreamConsumeThroughputQuotaExceeded(
+                    stream.stream_id,
+                    quota.max_consume_bytes_per_second,
+                ));
+            }
+        }
+
+        if let Some((user, quota)) = self.get_user_with_quota(user_id) {
+            if user
+                .consumed_bytes
+                .is_exceeded(quota.max_consume_bytes_per_second)
+            {
+                return Err(IggyError::UserConsumeThroughputQuotaExceeded(
+                    user.id,
+                    quota.max_consume_bytes_per_second,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn record_produced_bytes(&self, user_id: u32, stream: &Stream, bytes: u64) {
+        stream.produced_bytes.record(bytes);
+        if let Some(user) = self.users.get(&user_id) {
+            user.produced_bytes.record(bytes);
+        }
+    }
+
+    pub(crate) fn record_consumed_bytes(&self, user_id: u32, stream: &Stream, bytes: u64) {
+        stream.consumed_bytes.record(bytes);
+        if let Some(user) = self.users.get(&user_id) {
+            user.consumed_bytes.record(bytes);
+        }
+    }
+
+    /// Ensures that creating the topics and partitions in the stream does not exceed the quotas of the stream and its owner.
+    pub(crate) fn ensure_topology_quotas(
+        &self,
+        stream_id: &Identifier,
+        topics_count: u32,
+        partitions_count: u32,
+    ) -> Result<(), IggyError> {
+        let stream = self.get_stream(stream_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get stream with ID: {stream_id}")
+        })?;
+        if let Some(quota) = &stream.quota {
+            if quota.max_topics > 0 && stream.get_topics_count() + topics_count > quota.max_topics {
+                return Err(IggyError::StreamTopicsQuotaExceeded(
+                    stream.stream_id,
+                    quota.max_topics,
+                ));
+            }
+
+            if quota.max_partitions > 0
+                && stream.get_partitions_count() + partitions_count > quota.max_partitions
+            {
+                return Err(IggyError::StreamPartitionsQuotaExceeded(
+                    stream.stream_id,
+                    quota.max_partitions,
+                ));
+            }
+        }
+
+        let Some((owner, quota)) = self.get_user_with_quota(stream.owner_id) else {
+            return Ok(());
+        };
+
+        if quota.max_topics > 0 {
+            let owned_topics_count = self
+                .get_owned_streams(owner.id)
+                .map(|stream| stream.get_topics_count())
+                .sum::<u32>();
+            if owned_topics_count + topics_count > quota.max_topics {
+                return Err(IggyError::UserTopicsQuotaExceeded(
+                    owner.id,
+                    quota.max_topics,
+                ));
+            }
+        }
+
+        if quota.max_partitions > 0 {
+            let owned_partitions_count = self
+                .get_owned_streams(owner.id)
+                .map(|stream| stream.get_partitions_count())
+                .sum::<u32>();
+            if owned_partitions_count + partitions_count > quota.max_partitions {
+                return Err(IggyError::UserPartitionsQuotaExceeded(
+                    owner.id,
+                    quota.max_partitions,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_user_with_quota(&self, user_id: u32) -> Option<(&User, &Quota)> {
+        let user = self.users.get(&user_id)?;
+        let quota = user.quota.as_ref()?;
+        Some((user, quota))
+    }
+
+    fn get_owned_streams(&self, user_id: u32) -> impl Iterator<Item = &Stream> {
+        self.streams
+            .values()
+            .filter(move |stream| stream.owner_id == user_id)
+    }
+}
@@ -0,0 +1,377 @@
+use crate::state::system::RoleState;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::System;
+use crate::streaming::systems::COMPONENT;
+use crate::streaming::users::permissioner::Permissioner;
+use crate::streaming::users::role::Role;
+use ahash::AHashSet;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::identifier::{IdKind, Identifier};
+use iggy::models::permissions::Permissions;
+use iggy::models::role::RoleId;
+use iggy::models::user_info::UserId;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tracing::{error, info, warn};
+
+static ROLE_ID: AtomicU32 = AtomicU32::new(1);
+const MAX_ROLES: usize = u32::MAX as usize;
+
+impl System {
+    pub(crate) fn load_roles(&mut self, roles: Vec<RoleState>, current_role_id: u32) {
+        info!("Loading roles...");
+        for role_state in roles.into_iter() {
+            let role = Role {
+                id: role_state.id,
+                name: role_state.name,
+                created_at: role_state.created_at,
+                permissions: role_state.permissions,
+                users: role_state.users,
+            };
+            self.roles.insert(role.id, role);
+        }
+
+        let roles_count = self.roles.len();
+        // The IDs of the deleted roles are never reused, the same as during the state replay.
+        ROLE_ID.store(current_role_id + 1, Ordering::SeqCst);
+        let users = self
+            .roles
+            .values()
+            .flat_map(|role| role.users.iter().copied())
+            .chain(self.oidc_roles.keys().copied())
+            .collect::<AHashSet<_>>();
+        for user_id in users {
+            self.refresh_permissions_for_user(user_id);
+        }
+        info!("Initialized {} role(s).", roles_count);
+    }
+
+    pub fn find_role(
+        &self,
+        session: &Session,
+        role_id: &Identifier,
+    ) -> Result<Option<&Role>, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .get_role(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to get role with ID: {role_id} for user with ID: {}",
+                    session.get_user_id()
+                )
+            })?;
+        self.try_get_role(role_id)
+    }
+
+    pub fn get_role(&self, role_id: &Identifier) -> Result<&Role, IggyError> {
+        self.try_get_role(role_id)?
+            .ok_or(IggyError::ResourceNotFound(role_id.to_string()))
+    }
+
+    pub fn try_get_role(&self, role_id: &Identifier) -> Result<Option<&Role>, IggyError> {
+        match role_id.kind {
+            IdKind::Numeric => Ok(self.roles.get(&role_id.get_u32_value()?)),
+            IdKind::String => {
+                let name = role_id.get_cow_str_value()?;
+                Ok(self.roles.values().find(|role| role.name == name))
+            }
+        }
+    }
+
+    fn get_role_mut(&mut self, role_id: &Identifier) -> Result<&mut Role, IggyError> {
+        match role_id.kind {
+            IdKind::Numeric => self
+                .roles
+                .get_mut(&role_id.get_u32_value()?)
+                .ok_or(IggyError::ResourceNotFound(role_id.to_string())),
+            IdKind::String => {
+                let name = role_id.get_cow_str_value()?;
+                self.roles
+                    .values_mut()
+                    .find(|role| role.name == name)
+                    .ok_or(IggyError::ResourceNotFound(role_id.to_string()))
+            }
+        }
+    }
+
+    pub async fn get_roles(&self, session: &Session) -> Result<Vec<&Role>, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .get_roles(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to get roles for user with ID: {}",
+                    session.get_user_id()
+                )
+            })?;
+        Ok(self.roles.values().collect())
+    }
+
+    pub async fn create_role(
+        &mut self,
+        session: &Session,
+        name: &str,
+        permissions: Option<Permissions>,
+    ) -> Result<&Role, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .create_role(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to create role for user with ID: {}",
+                    session.get_user_id()
+                )
+            })?;
+
+        if self.roles.values().any(|role| role.name == name) {
+            error!("Role: {name} already exists.");
+            return Err(IggyError::RoleAlreadyExists(name.to_owned()));
+        }
+
+        if self.roles.len() >= MAX_ROLES {
+            error!("Available roles limit reached.");
+            return Err(IggyError::RolesLimitReached);
+        }
+
+        let role_id = ROLE_ID.fetch_add(1, Ordering::SeqCst);
+        info!("Creating role: {name} with ID: {role_id}...");
+        let role = Role::new(role_id, name, permissions);
+        self.roles.insert(role.id, role);
+        info!("Created role: {name} with ID: {role_id}.");
+        self.get_role(&role_id.try_into()?)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get role with ID: {role_id}")
+            })
+    }
+
+    pub async fn update_role(
+        &mut self,
+        session: &Session,
+        role_id: &Identifier,
+        name: Option<String>,
+        permissions: Option<Permissions>,
+    ) -> Result<&Role, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .update_role(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to update role for user with ID: {}",
+                    session.get_user_id()
+                )
+            })?;
+
+        let existing_role_id = self
+            .get_role(role_id)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get role with ID: {role_id}")
+            })?
+            .id;
+        if let Some(name) = &name {
+            if self
+                .roles
+                .values()
+                .any(|role| role.name == *name && role.id != existing_role_id)
+            {
+                error!("Role: {name} already exists.");
+                return Err(IggyError::RoleAlreadyExists(name.to_owned()));
+            }
+        }
+
+        let role = self.get_role_mut(role_id)?;
+        if let Some(name) = name {
+            role.name = name;
+        }
+
+        let has_permissions = permissions.is_some();
+        if let Some(permissions) = permissions {
+            role.permissions = Some(permissions);
+        }
+        info!("Updated role: {} with ID: {}.", role.name, role.id);
+        if has_permissions {
+            for user_id in self.get_role_users(existing_role_id) {
+                self.refresh_permissions_for_user(user_id);
+            }
+        }
+
+        self.get_role(&existing_role_id.try_into()?)
+    }
+
+    pub async fn delete_role(
+        &mut self,
+        session: &Session,
+        role_id: &Identifier,
+    ) -> Result<Role, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .delete_role(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to delete role for user with ID: {}",
+                    session.get_user_id()
+                )
+            })?;
+
+        let existing_role_id = self
+            .get_role(role_id)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get role with ID: {role_id}")
+            })?
+            .id;
+        let users = self.get_role_users(existing_role_id);
+        let role = self
+            .roles
+            .remove(&existing_role_id)
+            .ok_or(IggyError::ResourceNotFound(role_id.to_string()))?;
+        for role_ids in self.oidc_roles.values_mut() {
+            role_ids.remove(&existing_role_id);
+        }
+        for user_id in users {
+            self.refresh_permissions_for_user(user_id);
+        }
+        info!("Deleted role: {} with ID: {}.", role.name, role.id);
+        Ok(role)
+    }
+
+    pub async fn assign_role(
+        &mut self,
+        session: &Session,
+        role_id: &Identifier,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .assign_role(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to assign role for user with ID: {}",
+                    session.get_user_id()
+                )
+            })?;
+
+        let user_id = self
+            .get_user(user_id)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get user with ID: {user_id}")
+            })?
+            .id;
+        let role = self.get_role_mut(role_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get role with ID: {role_id}")
+        })?;
+        if !role.users.insert(user_id) {
+            error!(
+                "Role: {} is already assigned to user with ID: {user_id}.",
+                role.name
+            );
+            return Err(IggyError::RoleAlreadyAssigned(role.name.clone(), user_id));
+        }
+
+        info!(
+            "Assigned role: {} with ID: {} to user with ID: {user_id}.",
+            role.name, role.id
+        );
+        self.refresh_permissions_for_user(user_id);
+        Ok(())
+    }
+
+    pub async fn unassign_role(
+        &mut self,
+        session: &Session,
+        role_id: &Identifier,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .unassign_role(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to unassign role for user with ID: {}",
+                    session.get_user_id()
+                )
+            })?;
+
+        let user_id = self
+            .get_user(user_id)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get user with ID: {user_id}")
+            })?
+            .id;
+        let role = self.get_role_mut(role_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get role with ID: {role_id}")
+        })?;
+        if !role.users.remove(&user_id) {
+            error!(
+                "Role: {} is not assigned to user with ID: {user_id}.",
+                role.name
+            );
+            return Err(IggyError::RoleNotAssigned(role.name.clone(), user_id));
+        }
+
+        info!(
+            "Unassigned role: {} with ID: {} from user with ID: {user_id}.",
+            role.name, role.id
+        );
+        self.refresh_permissions_for_user(user_id);
+        Ok(())
+    }
+
+    /// Replaces the roles granted to the user by the groups of its OIDC token, the roles which don't exist are ignored.
+    pub(crate) fn grant_oidc_roles(&mut self, user_id: UserId, role_names: Vec<&str>) {
+        let role_ids = role_names
+            .into_iter()
+            .filter_map(
+                |name| match self.roles.values().find(|role| role.name == name) {
+                    Some(role) => Some(role.id),
+                    None => {
+                        warn!("Role: {name} mapped to the OIDC group was not found.");
+                        None
+                    }
+                },
+            )
+            .collect::<AHashSet<_>>();
+        if role_ids.is_empty() {
+            self.oidc_roles.remove(&user_id);
+        } else {
+            info!("Granted OIDC roles with IDs: {role_ids:?} to user with ID: {user_id}.");
+            self.oidc_roles.insert(user_id, role_ids);
+        }
+        self.refresh_permissions_for_user(user_id);
+    }
+
+    /// Returns the users which the role is assigned to, or granted by the groups of their OIDC tokens.
+    fn get_role_users(&self, role_id: RoleId) -> AHashSet<UserId> {
+        let mut users = self
+            .roles
+            .get(&role_id)
+            .map(|role| role.users.clone())
+            .unwrap_or_default();
+        users.extend(
+            self.oidc_roles
+                .iter()
+                .filter(|(_, role_ids)| role_ids.contains(&role_id))
+                .map(|(user_id, _)| *user_id),
+        );
+        users
+    }
+
+    /// Recalculates the effective permissions of the user, granted both directly and by its roles.
+    pub(crate) fn refresh_permissions_for_user(&mut self, user_id: UserId) {
+        let Some(user) = self.users.get(&user_id) else {
+            return;
+        };
+
+        let oidc_roles = self.oidc_roles.get(&user_id);
+        let permissions = Permissioner::resolve_permissions(
+            user.permissions.as_ref(),
+            self.roles
+                .values()
+                .filter(|role| {
+                    role.users.contains(&user_id)
+                        || oidc_roles.is_some_and(|role_ids| role_ids.contains(&role.id))
+                })
+                .filter_map(|role| role.permissions.as_ref()),
+        );
+        self.permissioner
+            .update_permissions_for_user(user_id, permissions);
+    }
+}
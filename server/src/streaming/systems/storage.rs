@@ -63,7 +63,7 @@ impl SystemInfoStorage for FileSystemInfoStorage {
             .with_context(|| "Failed to serialize system info")
             .map_err(|_| IggyError::CannotSerializeResource)?;
         self.persister
-            .overwrite(&self.path, &data)
+            .overwrite_atomically(&self.path, &data)
             .await
             .with_error_context(|error| {
                 format!(
@@ -9,7 +9,6 @@ use futures::future::try_join_all;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
 use iggy::locking::IggySharedMutFn;
-use std::cell::RefCell;
 use std::sync::atomic::{AtomicU32, Ordering};
 use tokio::fs;
 use tokio::fs::read_dir;
@@ -56,6 +55,8 @@ impl System {
                 self.storage.clone(),
             );
             stream.created_at = stream_state.created_at;
+            stream.owner_id = stream_state.owner_id;
+            stream.quota = stream_state.quota;
             unloaded_streams.push(stream);
         }
 
@@ -83,12 +84,14 @@ impl System {
             info!("Recreating missing state in recovery config is enabled, missing streams will be created.");
             for stream_id in missing_ids {
                 let stream_state = streams.iter().find(|s| s.id == stream_id).unwrap();
-                let stream = Stream::create(
+                let mut stream = Stream::create(
                     stream_id,
                     &stream_state.name,
                     self.config.clone(),
                     self.storage.clone(),
                 );
+                stream.owner_id = stream_state.owner_id;
+                stream.quota = stream_state.quota;
                 stream.persist().await?;
                 unloaded_streams.push(stream);
                 info!(
@@ -102,19 +105,16 @@ impl System {
             .into_iter()
             .map(|s| (s.id, s))
             .collect::<AHashMap<_, _>>();
-        let loaded_streams = RefCell::new(Vec::new());
         let load_stream_tasks = unloaded_streams.into_iter().map(|mut stream| {
             let state = streams_states.remove(&stream.stream_id).unwrap();
-            let load_stream_task = async {
+            async move {
                 stream.load(state).await?;
-                loaded_streams.borrow_mut().push(stream);
-                Result::<(), IggyError>::Ok(())
-            };
-            load_stream_task
+                Result::<Stream, IggyError>::Ok(stream)
+            }
         });
-        try_join_all(load_stream_tasks).await?;
+        let loaded_streams = try_join_all(load_stream_tasks).await?;
 
-        for stream in loaded_streams.take() {
+        for stream in loaded_streams {
             if self.streams.contains_key(&stream.stream_id) {
                 error!("Stream with ID: '{}' already exists.", &stream.stream_id);
                 continue;
@@ -237,7 +237,7 @@ impl System {
         self.get_stream_by_id(*stream_id.unwrap())
     }
 
-    fn get_stream_by_id(&self, stream_id: u32) -> Result<&Stream, IggyError> {
+    pub(crate) fn get_stream_by_id(&self, stream_id: u32) -> Result<&Stream, IggyError> {
         let stream = self.streams.get(&stream_id);
         if stream.is_none() {
             return Err(IggyError::StreamIdNotFound(stream_id));
@@ -260,7 +260,10 @@ impl System {
         self.get_stream_by_id_mut(stream_id)
     }
 
-    fn get_stream_by_id_mut(&mut self, stream_id: u32) -> Result<&mut Stream, IggyError> {
+    pub(crate) fn get_stream_by_id_mut(
+        &mut self,
+        stream_id: u32,
+    ) -> Result<&mut Stream, IggyError> {
         let stream = self.streams.get_mut(&stream_id);
         if stream.is_none() {
             return Err(IggyError::StreamIdNotFound(stream_id));
@@ -302,7 +305,8 @@ impl System {
             return Err(IggyError::StreamIdAlreadyExists(id));
         }
 
-        let stream = Stream::create(id, name, self.config.clone(), self.storage.clone());
+        let mut stream = Stream::create(id, name, self.config.clone(), self.storage.clone());
+        stream.owner_id = session.get_user_id();
         stream.persist().await?;
         info!("Created stream with ID: {id}, name: '{name}'.");
         self.streams_ids.insert(name.to_owned(), stream.stream_id);
@@ -1,8 +1,12 @@
 use crate::archiver::{ArchiverKind, ArchiverKindType};
+use crate::cluster::node::ClusterNode;
+use crate::configs::cluster::ClusterConfig;
 use crate::configs::server::{DataMaintenanceConfig, PersonalAccessTokenConfig};
-use crate::configs::system::SystemConfig;
+use crate::configs::system::{IoBackend, SystemConfig};
 use crate::map_toggle_str;
+use crate::oidc::authenticator::OidcAuthenticator;
 use crate::state::file::FileState;
+use crate::state::snapshot::{StateSnapshot, StateSnapshotHeader};
 use crate::state::system::SystemState;
 use crate::state::StateKind;
 use crate::streaming::cache::memory_tracker::CacheMemoryTracker;
@@ -13,23 +17,27 @@ use crate::streaming::session::Session;
 use crate::streaming::storage::SystemStorage;
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::systems::COMPONENT;
+use crate::streaming::transactions::transaction_coordinator::TransactionCoordinator;
 use crate::streaming::users::permissioner::Permissioner;
+use crate::streaming::users::role::Role;
 use crate::streaming::users::user::User;
 use crate::versioning::SemanticVersion;
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use error_set::ErrContext;
 use iggy::error::IggyError;
 use iggy::locking::IggySharedMut;
 use iggy::locking::IggySharedMutFn;
+use iggy::models::role::RoleId;
 use iggy::models::user_info::UserId;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::crypto::{Aes256GcmEncryptor, EncryptorKind};
+use iggy::utils::timestamp::IggyTimestamp;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::fs::{create_dir_all, remove_dir_all};
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tokio::time::Instant;
-use tracing::{error, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace};
 
 #[derive(Debug)]
 pub struct SharedSystem {
@@ -67,12 +75,18 @@ pub struct System {
     pub(crate) streams: AHashMap<u32, Stream>,
     pub(crate) streams_ids: AHashMap<String, u32>,
     pub(crate) users: AHashMap<UserId, User>,
+    pub(crate) roles: AHashMap<RoleId, Role>,
+    /// The roles granted to the users by the groups of their latest OIDC tokens, which are not stored in the state.
+    pub(crate) oidc_roles: AHashMap<UserId, AHashSet<RoleId>>,
     pub(crate) config: Arc<SystemConfig>,
     pub(crate) client_manager: IggySharedMut<ClientManager>,
     pub(crate) encryptor: Option<Arc<EncryptorKind>>,
     pub(crate) metrics: Metrics,
     pub(crate) state: Arc<StateKind>,
     pub(crate) archiver: Option<Arc<ArchiverKind>>,
+    pub(crate) cluster: Option<Arc<ClusterNode>>,
+    pub oidc: Option<Arc<OidcAuthenticator>>,
+    pub(crate) transactions: TransactionCoordinator,
     pub personal_access_token: PersonalAccessTokenConfig,
 }
 
@@ -100,10 +114,14 @@ impl System {
         };
 
         let state_persister = Self::resolve_persister(config.state.enforce_fsync);
-        let partition_persister = Self::resolve_persister(config.partition.enforce_fsync);
+        let partition_persister = Self::resolve_partition_persister(
+            config.partition.io_backend,
+            config.partition.enforce_fsync,
+        );
 
         let state = Arc::new(StateKind::File(FileState::new(
             &config.get_state_log_path(),
+            &config.get_state_snapshot_path(),
             &version,
             state_persister,
             encryptor.clone(),
@@ -125,6 +143,21 @@ impl System {
         }
     }
 
+    fn resolve_partition_persister(
+        io_backend: IoBackend,
+        enforce_fsync: bool,
+    ) -> Arc<PersisterKind> {
+        match io_backend {
+            IoBackend::Tokio => Self::resolve_persister(enforce_fsync),
+            #[cfg(target_os = "linux")]
+            IoBackend::IoUring => Arc::new(PersisterKind::Uring(
+                UringPersister::new(enforce_fsync).expect("Failed to start io_uring persister"),
+            )),
+            #[cfg(not(target_os = "linux"))]
+            IoBackend::IoUring => panic!("io_uring I/O backend is available only on Linux"),
+        }
+    }
+
     pub fn create(
         system_config: Arc<SystemConfig>,
         storage: SystemStorage,
@@ -158,22 +191,47 @@ impl System {
             None
         };
 
+        let transactions = TransactionCoordinator::new(
+            system_config.get_state_transactions_path(),
+            system_config.get_state_transaction_ids_path(),
+            storage.persister.clone(),
+        );
         System {
             config: system_config,
             streams: AHashMap::new(),
             streams_ids: AHashMap::new(),
-            storage: Arc::new(storage),
+            storage: Arc::new(storage.with_archiver(archiver.clone())),
             encryptor,
             client_manager: IggySharedMut::new(ClientManager::default()),
             permissioner: Permissioner::default(),
             metrics: Metrics::init(),
             users: AHashMap::new(),
+            roles: AHashMap::new(),
+            oidc_roles: AHashMap::new(),
             state,
             personal_access_token: pat_config,
             archiver,
+            cluster: None,
+            oidc: None,
+            transactions,
         }
     }
 
+    /// Creates the cluster node, which must be done before the system is initialized,
+    /// so that only the state entries committed by the cluster are applied on startup.
+    pub fn init_cluster(&mut self, config: &ClusterConfig) -> Result<(), IggyError> {
+        let node = ClusterNode::new(
+            config,
+            self.state.clone(),
+            &self.config.get_state_cluster_path(),
+        )
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to initialize cluster node")
+        })?;
+        self.cluster = Some(Arc::new(node));
+        Ok(())
+    }
+
     #[instrument(skip_all, name = "trace_system_init")]
     pub async fn init(&mut self) -> Result<(), IggyError> {
         let system_path = self.config.get_system_path();
@@ -205,14 +263,37 @@ impl System {
             self.config.get_system_path()
         );
 
-        let state_entries = self.state.init().await.with_error_context(|error| {
+        let mut state_entries = self.state.init().await.with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to initialize state entries")
         })?;
-        let system_state = SystemState::init(state_entries)
+        let mut has_uncommitted_entries = false;
+        if let Some(cluster) = self.cluster.as_ref() {
+            let next_index = self.state.next_index();
+            let commit_index = cluster.init_commit_index(next_index);
+            has_uncommitted_entries = commit_index < next_index;
+            state_entries.retain(|entry| entry.index < commit_index);
+            self.state.set_commit_index(commit_index);
+            info!(
+                "Applying {} committed state entries, uncommitted: {}.",
+                state_entries.len(),
+                next_index - commit_index
+            );
+        }
+        let state_snapshot = self
+            .state
+            .load_snapshot()
             .await
             .with_error_context(|error| {
-                format!("{COMPONENT} (error: {error}) - failed to initialize system state")
+                format!("{COMPONENT} (error: {error}) - failed to load state snapshot")
             })?;
+        let system_state = SystemState::init_from_snapshot(
+            state_snapshot.map(|snapshot| snapshot.state),
+            state_entries,
+        )
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to initialize system state")
+        })?;
         let now = Instant::now();
         self.load_version().await.with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to load version")
@@ -222,11 +303,33 @@ impl System {
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to load users")
             })?;
+        self.load_roles(
+            system_state.roles.into_values().collect(),
+            system_state.current_role_id,
+        );
         self.load_streams(system_state.streams.into_values().collect())
             .await
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to load streams")
             })?;
+        self.recover_transactions()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to recover transactions")
+            })?;
+        if let Some(cluster) = self.cluster.as_ref() {
+            if !has_uncommitted_entries {
+                cluster
+                    .commit_initial_entries(self.state.next_index())
+                    .await
+                    .with_error_context(|error| {
+                        format!(
+                            "{COMPONENT} (error: {error}) - failed to commit initial state entries"
+                        )
+                    })?;
+            }
+            cluster.enable_commit_timeout();
+        }
         if let Some(archiver) = self.archiver.as_ref() {
             archiver
                 .init()
@@ -237,6 +340,161 @@ impl System {
         Ok(())
     }
 
+    /// Creates the snapshot of the state, if at least `min_entries` entries were appended to the log since the last one.
+    /// The caller must ensure that no entries are appended to the state while the snapshot is being created.
+    /// In the cluster mode, only the committed entries are included, as the other ones could be truncated.
+    pub async fn create_state_snapshot(
+        &self,
+        min_entries: u64,
+    ) -> Result<Option<StateSnapshotHeader>, IggyError> {
+        let state_snapshot = self
+            .state
+            .load_snapshot()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load state snapshot")
+            })?;
+        let last_snapshot_index = state_snapshot
+            .as_ref()
+            .map(|snapshot| snapshot.header.index);
+        let commit_index = self
+            .cluster
+            .as_ref()
+            .map_or(u64::MAX, |cluster| cluster.get_commit_index());
+        let state_entries = self
+            .state
+            .load_entries()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load state entries")
+            })?
+            .into_iter()
+            .filter(|entry| last_snapshot_index.is_none_or(|index| entry.index > index))
+            .filter(|entry| entry.index < commit_index)
+            .collect::<Vec<_>>();
+        let Some(last_entry) = state_entries.last() else {
+            debug!("No state entries were appended since the last snapshot.");
+            return Ok(None);
+        };
+
+        if (state_entries.len() as u64) < min_entries {
+            debug!(
+                "Only {} state entries were appended since the last snapshot, required: {min_entries}.",
+                state_entries.len()
+            );
+            return Ok(None);
+        }
+
+        let header = StateSnapshotHeader {
+            index: last_entry.index,
+            term: last_entry.term,
+            leader_id: last_entry.leader_id,
+            version: SemanticVersion::current()?.get_numeric_version()?,
+            created_at: IggyTimestamp::now(),
+        };
+        let system_state = SystemState::init_from_snapshot(
+            state_snapshot.map(|snapshot| snapshot.state),
+            state_entries,
+        )
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to compact system state")
+        })?;
+        self.state
+            .snapshot(StateSnapshot::new(header, system_state))
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to persist state snapshot: {header}")
+            })?;
+        Ok(Some(header))
+    }
+
+    /// Rebuilds the users, roles and streams from the snapshot and the state entries preceding the commit index,
+    /// discarding the uncommitted entries applied by the node while it was the leader, which have been truncated since.
+    /// The streams are loaded from the disk the same way as on startup, so the buffered messages are persisted first.
+    pub(crate) async fn reload_committed_state(
+        &mut self,
+        commit_index: u64,
+    ) -> Result<(), IggyError> {
+        self.persist_messages().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to persist messages before reloading the state")
+        })?;
+        let state_snapshot = self
+            .state
+            .load_snapshot()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load state snapshot")
+            })?;
+        let last_snapshot_index = state_snapshot
+            .as_ref()
+            .map(|snapshot| snapshot.header.index);
+        let state_entries = self
+            .state
+            .load_entries()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load state entries")
+            })?
+            .into_iter()
+            .filter(|entry| last_snapshot_index.is_none_or(|index| entry.index > index))
+            .filter(|entry| entry.index < commit_index)
+            .collect::<Vec<_>>();
+        let system_state = SystemState::init_from_snapshot(
+            state_snapshot.map(|snapshot| snapshot.state),
+            state_entries,
+        )
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to initialize system state")
+        })?;
+
+        info!(
+            "Reloading the state committed up to index: {commit_index}, streams: {}, users: {}, roles: {}.",
+            system_state.streams.len(),
+            system_state.users.len(),
+            system_state.roles.len()
+        );
+        let client_manager = self.client_manager.read().await;
+        for stream in self.streams.values() {
+            if !system_state.streams.contains_key(&stream.stream_id) {
+                client_manager
+                    .delete_consumer_groups_for_stream(stream.stream_id)
+                    .await;
+            }
+            self.metrics.decrement_streams(1);
+            self.metrics.decrement_topics(stream.get_topics_count());
+            self.metrics
+                .decrement_partitions(stream.get_partitions_count());
+            self.metrics.decrement_messages(stream.get_messages_count());
+            self.metrics.decrement_segments(stream.get_segments_count());
+        }
+        drop(client_manager);
+        self.metrics.decrement_users(self.users.len() as u32);
+        self.streams.clear();
+        self.streams_ids.clear();
+        self.users.clear();
+        self.roles.clear();
+        self.permissioner = Permissioner::default();
+
+        self.load_users(system_state.users.into_values().collect())
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load users")
+            })?;
+        self.load_roles(
+            system_state.roles.into_values().collect(),
+            system_state.current_role_id,
+        );
+        self.load_streams(system_state.streams.into_values().collect())
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load streams")
+            })?;
+        info!("Reloaded the state committed up to index: {commit_index}.");
+        Ok(())
+    }
+
     #[instrument(skip_all, name = "trace_shutdown")]
     pub async fn shutdown(&mut self) -> Result<(), IggyError> {
         self.persist_messages().await?;
@@ -268,6 +526,14 @@ impl System {
         }
     }
 
+    /// Returns an error if the node is running in the cluster mode and it's not the leader.
+    pub fn ensure_leader(&self) -> Result<(), IggyError> {
+        match &self.cluster {
+            Some(cluster) => cluster.ensure_leader(),
+            None => Ok(()),
+        }
+    }
+
     pub async fn clean_cache(&self, size_to_clean: IggyByteSize) {
         for stream in self.streams.values() {
             for topic in stream.get_topics() {
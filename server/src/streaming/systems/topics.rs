@@ -7,8 +7,11 @@ use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
 use iggy::locking::IggySharedMutFn;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::topics::consumed_retention::ConsumedRetention;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
+use tracing::{error, info};
 
 impl System {
     pub fn find_topic(
@@ -90,6 +93,23 @@ impl System {
         Ok(Some(topic))
     }
 
+    /// In the cluster mode, the replication factor cannot exceed the number of nodes.
+    fn validate_replication_factor(&self, replication_factor: Option<u8>) -> Result<(), IggyError> {
+        let (Some(cluster), Some(replication_factor)) = (&self.cluster, replication_factor) else {
+            return Ok(());
+        };
+
+        if replication_factor as u32 > cluster.get_nodes_count() {
+            error!(
+                "Replication factor: {replication_factor} exceeds the number of cluster nodes: {}",
+                cluster.get_nodes_count()
+            );
+            return Err(IggyError::InvalidReplicationFactor);
+        }
+
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create_topic(
         &mut self,
@@ -100,10 +120,13 @@ impl System {
         partitions_count: u32,
         message_expiry: IggyExpiry,
         compression_algorithm: CompressionAlgorithm,
+        compression_level: Option<u8>,
         max_topic_size: MaxTopicSize,
         replication_factor: Option<u8>,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<&Topic, IggyError> {
         self.ensure_authenticated(session)?;
+        self.validate_replication_factor(replication_factor)?;
         {
             let stream = self.get_stream(stream_id).with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to get stream with ID: {stream_id}")
@@ -118,6 +141,11 @@ impl System {
                 })?;
         }
 
+        self.ensure_topology_quotas(stream_id, 1, partitions_count)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - quota exceeded while creating topic with name: {name} in stream with ID: {stream_id}")
+            })?;
+
         let created_topic_id = self
             .get_stream_mut(stream_id)?
             .create_topic(
@@ -126,8 +154,10 @@ impl System {
                 partitions_count,
                 message_expiry,
                 compression_algorithm,
+                compression_level,
                 max_topic_size,
                 replication_factor.unwrap_or(1),
+                cleanup_policy,
             )
             .await
             .with_error_context(|error| {
@@ -159,10 +189,13 @@ impl System {
         name: &str,
         message_expiry: IggyExpiry,
         compression_algorithm: CompressionAlgorithm,
+        compression_level: Option<u8>,
         max_topic_size: MaxTopicSize,
         replication_factor: Option<u8>,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<&Topic, IggyError> {
         self.ensure_authenticated(session)?;
+        self.validate_replication_factor(replication_factor)?;
         {
             let topic = self
                 .find_topic(session, stream_id, topic_id)
@@ -191,8 +224,10 @@ impl System {
                 name,
                 message_expiry,
                 compression_algorithm,
+                compression_level,
                 max_topic_size,
                 replication_factor.unwrap_or(1),
+                cleanup_policy,
             )
             .await
             .with_error_context(|error| {
@@ -203,7 +238,7 @@ impl System {
 
         // TODO: if message_expiry is changed, we need to check if we need to purge messages based on the new expiry
         // TODO: if max_size_bytes is changed, we need to check if we need to purge messages based on the new size
-        // TODO: if replication_factor is changed, we need to do `something`
+        // If replication_factor is increased, the partitions will be caught up by the cluster node (if enabled).
         self.get_stream(stream_id)
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to get stream with ID: {stream_id}")
@@ -283,4 +318,39 @@ impl System {
             format!("{COMPONENT} (error: {error}) - failed to purge topic with ID: {topic_id} in stream with ID: {stream_id}")
         })
     }
+
+    pub async fn set_topic_retention(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        retention: ConsumedRetention,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        {
+            let topic = self
+                .find_topic(session, stream_id, topic_id)
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to find topic with ID: {topic_id} in stream with ID: {stream_id}")
+                })?;
+            self.permissioner
+                .update_topic(session.get_user_id(), topic.stream_id, topic.topic_id)
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - permission denied to set retention for topic with ID: {topic_id} in stream with ID: {stream_id} for user with ID: {}",
+                        session.get_user_id(),
+                    )
+                })?;
+        }
+
+        let topic = self
+            .get_stream_mut(stream_id)?
+            .get_topic_mut(topic_id)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get mutable reference to topic with ID: {topic_id} in stream with ID: {stream_id}")
+            })?;
+        info!("Set retention for topic with ID: {topic_id} in stream with ID: {stream_id} ({retention}).");
+        topic.consumed_retention = retention;
+        Ok(())
+    }
 }
@@ -0,0 +1,353 @@
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::System;
+use crate::streaming::systems::COMPONENT;
+use crate::streaming::transactions::committed_transactions::{
+    CommittedOffset, CommittedTransaction,
+};
+use crate::streaming::transactions::transaction_coordinator::Transaction;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::messages::send_messages::{get_transaction_id, Message};
+use iggy::transactions::commit_transaction::TransactionOffset;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::timestamp::IggyTimestamp;
+use tracing::{error, info};
+
+impl System {
+    pub async fn begin_transaction(&self, session: &Session) -> Result<u64, IggyError> {
+        self.ensure_authenticated(session)?;
+        // The transaction state is not replicated, so the transactions are available only in the standalone mode.
+        if self.cluster.is_some() {
+            return Err(IggyError::TransactionsNotSupported);
+        }
+
+        let transaction_id = self
+            .transactions
+            .begin(session.get_user_id(), session.client_id)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to begin transaction for user with ID: {}",
+                    session.get_user_id()
+                )
+            })?;
+        info!(
+            "Transaction with ID: {transaction_id} has begun for user with ID: {}.",
+            session.get_user_id()
+        );
+        Ok(transaction_id)
+    }
+
+    /// Commits the transaction by storing the decision first, so the transaction can be completed again on the server restart,
+    /// then storing the consumer offsets and committing the transaction in all of its partitions.
+    pub async fn commit_transaction(
+        &self,
+        session: &Session,
+        transaction_id: u64,
+        offsets: Vec<TransactionOffset>,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        self.transactions
+            .ensure_open(transaction_id, session.get_user_id())
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to commit transaction with ID: {transaction_id}")
+            })?;
+        let offsets = self
+            .resolve_transaction_offsets(session, offsets)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to resolve consumer offsets for transaction with ID: {transaction_id}")
+            })?;
+        let transaction = self
+            .transactions
+            .complete(transaction_id, session.get_user_id())
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to commit transaction with ID: {transaction_id}")
+            })?;
+        let committed_transaction = CommittedTransaction {
+            transaction_id,
+            partitions: transaction.partitions.iter().copied().collect(),
+            offsets,
+        };
+        if let Err(error) = self
+            .transactions
+            .store_commit(committed_transaction.clone())
+            .await
+        {
+            // Without the stored decision, the transaction would be aborted on the server restart anyway.
+            self.complete_transaction(&transaction, false).await?;
+            return Err(error);
+        }
+
+        self.complete_committed_transaction(&committed_transaction)
+            .await?;
+        info!("Transaction with ID: {transaction_id} has been committed.");
+        Ok(())
+    }
+
+    pub async fn abort_transaction(
+        &self,
+        session: &Session,
+        transaction_id: u64,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        let transaction = self
+            .transactions
+            .complete(transaction_id, session.get_user_id())
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to abort transaction with ID: {transaction_id}")
+            })?;
+        self.complete_transaction(&transaction, false).await?;
+        info!("Transaction with ID: {transaction_id} has been aborted.");
+        Ok(())
+    }
+
+    /// Aborts the transactions left open by the disconnected client.
+    pub async fn abort_client_transactions(&self, client_id: u32) {
+        for transaction in self.transactions.complete_client_transactions(client_id) {
+            if let Err(error) = self.complete_transaction(&transaction, false).await {
+                error!(
+                    "Failed to abort transaction with ID: {} of client with ID: {client_id}. {error}",
+                    transaction.transaction_id
+                );
+                continue;
+            }
+
+            info!(
+                "Transaction with ID: {} of disconnected client with ID: {client_id} has been aborted.",
+                transaction.transaction_id
+            );
+        }
+    }
+
+    /// Aborts the transactions which have been open for longer than the given timeout.
+    pub async fn abort_expired_transactions(&self, timeout: IggyDuration) {
+        let expired_transactions = self
+            .transactions
+            .complete_expired_transactions(IggyTimestamp::now(), timeout);
+        for transaction in expired_transactions {
+            if let Err(error) = self.complete_transaction(&transaction, false).await {
+                error!(
+                    "Failed to abort expired transaction with ID: {}. {error}",
+                    transaction.transaction_id
+                );
+                continue;
+            }
+
+            info!(
+                "Transaction with ID: {} has been aborted, as it was open for longer than: {timeout}.",
+                transaction.transaction_id
+            );
+        }
+    }
+
+    /// Completes the transactions which have been committed before the server has stopped, and aborts the ones left open,
+    /// as the open transactions are not stored and can no longer be committed.
+    pub(crate) async fn recover_transactions(&self) -> Result<(), IggyError> {
+        self.transactions.load_transaction_ids().await?;
+        let committed_transactions = self.transactions.load_commits().await?;
+        for transaction in committed_transactions {
+            self.complete_committed_transaction(&transaction).await?;
+            info!(
+                "Transaction with ID: {} committed before the restart has been completed.",
+                transaction.transaction_id
+            );
+        }
+
+        for stream in self.streams.values() {
+            for topic in stream.get_topics() {
+                for partition in topic.get_partitions() {
+                    partition
+                        .write()
+                        .await
+                        .abort_open_transactions()
+                        .await
+                        .with_error_context(|error| {
+                            format!(
+                                "{COMPONENT} (error: {error}) - failed to abort open transactions for stream ID: {}, topic ID: {}",
+                                stream.stream_id, topic.topic_id
+                            )
+                        })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the ID of the transaction which the messages belong to, all of them must belong to the same one (or none).
+    pub fn get_messages_transaction_id(messages: &[Message]) -> Result<Option<u64>, IggyError> {
+        let mut transaction_id = None;
+        for (index, message) in messages.iter().enumerate() {
+            let message_transaction_id = match &message.headers {
+                Some(headers) => get_transaction_id(headers)?,
+                None => None,
+            };
+            if index == 0 {
+                transaction_id = message_transaction_id;
+            } else if transaction_id != message_transaction_id {
+                return Err(IggyError::InvalidTransactionMessages);
+            }
+        }
+        Ok(transaction_id)
+    }
+
+    /// Resolves the consumer offsets to be stored along with the transaction, so they're validated before it's committed.
+    async fn resolve_transaction_offsets(
+        &self,
+        session: &Session,
+        offsets: Vec<TransactionOffset>,
+    ) -> Result<Vec<CommittedOffset>, IggyError> {
+        let mut committed_offsets = Vec::with_capacity(offsets.len());
+        for offset in offsets {
+            let topic = self.find_topic(session, &offset.stream_id, &offset.topic_id)
+                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic with ID: {} was not found in stream with ID: {}", offset.topic_id, offset.stream_id))?;
+            self.permissioner.store_consumer_offset(
+                session.get_user_id(),
+                topic.stream_id,
+                topic.topic_id,
+            )?;
+            let (consumer, partition_id) = topic
+                .resolve_consumer_offset(
+                    &offset.consumer,
+                    offset.offset,
+                    offset.partition_id,
+                    offset.generation,
+                    session.client_id,
+                )
+                .await?;
+            committed_offsets.push(CommittedOffset {
+                stream_id: topic.stream_id,
+                topic_id: topic.topic_id,
+                partition_id,
+                consumer,
+                offset: offset.offset,
+            });
+        }
+        Ok(committed_offsets)
+    }
+
+    /// Stores the consumer offsets and commits the transaction in all of its partitions, then removes the stored decision.
+    /// Both steps can be safely repeated, if the server has stopped before the decision was removed.
+    async fn complete_committed_transaction(
+        &self,
+        transaction: &CommittedTransaction,
+    ) -> Result<(), IggyError> {
+        let transaction_id = transaction.transaction_id;
+        for offset in &transaction.offsets {
+            let Some(partition) =
+                self.find_partition(offset.stream_id, offset.topic_id, offset.partition_id)?
+            else {
+                continue;
+            };
+
+            partition
+                .read()
+                .await
+                .store_consumer_offset(offset.consumer, offset.offset)
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to store consumer offset for committed transaction with ID: {transaction_id}")
+                })?;
+        }
+        self.complete_transaction_partitions(transaction_id, &transaction.partitions, true)
+            .await?;
+        self.transactions.remove_commit(transaction_id).await
+    }
+
+    /// Commits or aborts the transaction in all the partitions to which its messages have been appended.
+    pub(crate) async fn complete_transaction(
+        &self,
+        transaction: &Transaction,
+        commit: bool,
+    ) -> Result<(), IggyError> {
+        self.complete_transaction_partitions(
+            transaction.transaction_id,
+            transaction.partitions.iter(),
+            commit,
+        )
+        .await
+    }
+
+    async fn complete_transaction_partitions<'a>(
+        &self,
+        transaction_id: u64,
+        partitions: impl IntoIterator<Item = &'a (u32, u32, u32)>,
+        commit: bool,
+    ) -> Result<(), IggyError> {
+        for (stream_id, topic_id, partition_id) in partitions {
+            let Some(partition) = self.find_partition(*stream_id, *topic_id, *partition_id)? else {
+                continue;
+            };
+
+            partition
+                .write()
+                .await
+                .complete_transaction(transaction_id, commit)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to complete transaction with ID: {transaction_id} for stream ID: {stream_id}, topic ID: {topic_id}, partition ID: {partition_id}"
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
+    /// The partitions (or the whole topics and streams) could have been deleted in the meantime, which is not an error.
+    fn find_partition(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> Result<Option<IggySharedMut<Partition>>, IggyError> {
+        let Ok(stream) = self.get_stream(&Identifier::numeric(stream_id)?) else {
+            return Ok(None);
+        };
+        let Ok(topic) = stream.get_topic(&Identifier::numeric(topic_id)?) else {
+            return Ok(None);
+        };
+        Ok(topic.get_partition(partition_id).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iggy::messages::send_messages::TRANSACTION_ID_HEADER;
+    use iggy::models::header::{HeaderKey, HeaderValue};
+    use std::collections::HashMap;
+
+    #[test]
+    fn messages_should_belong_to_the_same_transaction() {
+        let create_message = |transaction_id: Option<u64>| {
+            let headers = transaction_id.map(|transaction_id| {
+                HashMap::from([(
+                    HeaderKey::new(TRANSACTION_ID_HEADER).unwrap(),
+                    HeaderValue::from_uint64(transaction_id).unwrap(),
+                )])
+            });
+            Message::new(None, "test".into(), headers)
+        };
+
+        let messages = vec![create_message(None), create_message(None)];
+        assert_eq!(
+            System::get_messages_transaction_id(&messages).unwrap(),
+            None
+        );
+
+        let messages = vec![create_message(Some(1)), create_message(Some(1))];
+        assert_eq!(
+            System::get_messages_transaction_id(&messages).unwrap(),
+            Some(1)
+        );
+
+        let messages = vec![create_message(Some(1)), create_message(None)];
+        assert!(System::get_messages_transaction_id(&messages).is_err());
+
+        let messages = vec![create_message(Some(1)), create_message(Some(2))];
+        assert!(System::get_messages_transaction_id(&messages).is_err());
+    }
+}
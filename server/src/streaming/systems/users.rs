@@ -1,3 +1,4 @@
+use crate::oidc::authenticator::{OidcAuthenticator, OidcIdentity};
 use crate::state::command::EntryCommand;
 use crate::state::system::UserState;
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
@@ -17,6 +18,7 @@ use iggy::users::create_user::CreateUser;
 use iggy::users::defaults::*;
 use std::env;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 use tracing::{error, info, warn};
 
 static USER_ID: AtomicU32 = AtomicU32::new(1);
@@ -72,6 +74,7 @@ impl System {
                     )
                 })
                 .collect();
+            user.quota = user_state.quota;
             self.users.insert(user_state.id, user);
         }
 
@@ -267,6 +270,10 @@ impl System {
             .ok_or(IggyError::ResourceNotFound(user_id.to_string()))?;
         self.permissioner
             .delete_permissions_for_user(existing_user_id);
+        for role in self.roles.values_mut() {
+            role.users.remove(&existing_user_id);
+        }
+        self.oidc_roles.remove(&existing_user_id);
         let mut client_manager = self.client_manager.write().await;
         client_manager
             .delete_clients_for_user(existing_user_id)
@@ -345,12 +352,9 @@ impl System {
                 error!("Cannot change the root user permissions.");
                 return Err(IggyError::CannotChangePermissions(user.id));
             }
-
-            self.permissioner
-                .update_permissions_for_user(user.id, permissions.clone());
         }
 
-        {
+        let existing_user_id = {
             let user = self.get_user_mut(user_id).with_error_context(|error| {
                 format!(
                     "{COMPONENT} (error: {error}) - failed to get mutable reference to the user with id: {user_id}"
@@ -361,7 +365,10 @@ impl System {
                 "Updated permissions for user: {} with ID: {user_id}.",
                 user.username
             );
-        }
+            user.id
+        };
+
+        self.refresh_permissions_for_user(existing_user_id);
 
         Ok(())
     }
@@ -414,6 +421,43 @@ impl System {
             .await
     }
 
+    /// Returns the authenticator verifying the tokens issued by the external identity provider (OpenID Connect).
+    /// The token should be verified without holding the system lock, as the keys might have to be fetched first.
+    pub fn get_oidc_authenticator(&self) -> Result<Arc<OidcAuthenticator>, IggyError> {
+        self.oidc.clone().ok_or_else(|| {
+            warn!("Cannot login with the token, OIDC authentication is disabled.");
+            IggyError::FeatureUnavailable
+        })
+    }
+
+    /// Logs in the user explicitly mapped to the subject (or its group) of the already verified token,
+    /// and grants it the roles mapped to the groups of the token subject.
+    pub async fn login_with_oidc_identity(
+        &mut self,
+        authenticator: &OidcAuthenticator,
+        identity: &OidcIdentity,
+        session: Option<&Session>,
+    ) -> Result<&User, IggyError> {
+        let Some(username) = authenticator.get_username(identity) else {
+            warn!(
+                "Token subject: {:?} with groups: {:?} isn't mapped to any user.",
+                identity.subject, identity.groups
+            );
+            return Err(IggyError::InvalidCredentials);
+        };
+
+        info!(
+            "Logging in token subject: {:?} as user: {username}...",
+            identity.subject
+        );
+        let user_id = self
+            .login_user_with_credentials(username, None, session)
+            .await?
+            .id;
+        self.grant_oidc_roles(user_id, authenticator.get_role_names(identity));
+        self.get_user(&Identifier::numeric(user_id)?)
+    }
+
     pub async fn login_user_with_credentials(
         &self,
         username: &str,
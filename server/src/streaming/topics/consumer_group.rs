@@ -1,5 +1,8 @@
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::error::IggyError;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::trace;
 
@@ -9,6 +12,12 @@ pub struct ConsumerGroup {
     pub group_id: u32,
     pub name: String,
     pub partitions_count: u32,
+    pub assignment_strategy: PartitionAssignmentStrategy,
+    // Incremented on every rebalance, so the members can detect that their partitions have changed.
+    generation: AtomicU32,
+    // The revoked partitions are handed over to the new owner without the acknowledgement after this timeout,
+    // so they don't stay unowned when the previous owner stops polling (cooperative strategy).
+    revocation_timeout: Duration,
     members: AHashMap<u32, RwLock<ConsumerGroupMember>>,
 }
 
@@ -18,19 +27,66 @@ pub struct ConsumerGroupMember {
     partitions: AHashMap<u32, u32>,
     current_partition_index: Option<u32>,
     current_partition_id: Option<u32>,
+    // Partitions revoked from this member (cooperative strategy), waiting to be handed over
+    // to the new owner (partition ID, member ID) once this member acknowledges the revocation.
+    revoked_partitions: Vec<(u32, u32)>,
+    revoked_at: Option<Instant>,
+    // The member is notified about the revocation by the new generation returned on the first poll,
+    // and acknowledges it with the next one, after invoking its revocation callbacks.
+    revocation_notified: bool,
 }
 
 impl ConsumerGroup {
-    pub fn new(topic_id: u32, group_id: u32, name: &str, partitions_count: u32) -> ConsumerGroup {
+    pub fn new(
+        topic_id: u32,
+        group_id: u32,
+        name: &str,
+        partitions_count: u32,
+        assignment_strategy: PartitionAssignmentStrategy,
+        revocation_timeout: Duration,
+    ) -> ConsumerGroup {
         ConsumerGroup {
             topic_id,
             group_id,
             name: name.to_string(),
             partitions_count,
+            assignment_strategy,
+            generation: AtomicU32::new(0),
+            revocation_timeout,
             members: AHashMap::new(),
         }
     }
 
+    pub fn get_generation(&self) -> u32 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    pub fn validate_generation(&self, generation: u32) -> Result<(), IggyError> {
+        let current_generation = self.get_generation();
+        if generation != current_generation {
+            return Err(IggyError::StaleConsumerGroupGeneration(
+                generation,
+                self.group_id,
+                current_generation,
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_member_partitions(&self, member_id: u32) -> Result<Vec<u32>, IggyError> {
+        let member = self.members.get(&member_id);
+        if let Some(member) = member {
+            let mut partitions = member.read().await.get_partitions();
+            partitions.sort_unstable();
+            return Ok(partitions);
+        }
+        Err(IggyError::ConsumerGroupMemberNotFound(
+            member_id,
+            self.group_id,
+            self.topic_id,
+        ))
+    }
+
     pub fn get_members(&self) -> Vec<&RwLock<ConsumerGroupMember>> {
         self.members.values().collect()
     }
@@ -43,6 +99,8 @@ impl ConsumerGroup {
     pub async fn calculate_partition_id(&self, member_id: u32) -> Result<Option<u32>, IggyError> {
         let member = self.members.get(&member_id);
         if let Some(member) = member {
+            self.acknowledge_revocation(member).await;
+            self.hand_over_expired_revocations().await;
             return Ok(member.write().await.calculate_partition_id());
         }
         Err(IggyError::ConsumerGroupMemberNotFound(
@@ -72,6 +130,9 @@ impl ConsumerGroup {
                 partitions: AHashMap::new(),
                 current_partition_index: None,
                 current_partition_id: None,
+                revoked_partitions: Vec::new(),
+                revoked_at: None,
+                revocation_notified: false,
             }),
         );
         trace!(
@@ -95,37 +156,203 @@ impl ConsumerGroup {
         }
     }
 
+    async fn acknowledge_revocation(&self, member: &RwLock<ConsumerGroupMember>) {
+        {
+            let mut member = member.write().await;
+            if member.revoked_partitions.is_empty() {
+                return;
+            }
+            if !member.revocation_notified {
+                member.revocation_notified = true;
+                return;
+            }
+        }
+        self.hand_over_revoked_partitions(member).await;
+    }
+
+    async fn hand_over_revoked_partitions(&self, member: &RwLock<ConsumerGroupMember>) {
+        let (member_id, revoked_partitions) = {
+            let mut member = member.write().await;
+            if member.revoked_partitions.is_empty() {
+                return;
+            }
+            member.revoked_at = None;
+            member.revocation_notified = false;
+            (member.id, std::mem::take(&mut member.revoked_partitions))
+        };
+
+        for (partition_id, new_member_id) in revoked_partitions {
+            let Some(new_member) = self.members.get(&new_member_id) else {
+                continue;
+            };
+            new_member.write().await.add_partition(partition_id);
+            self.generation.fetch_add(1, Ordering::SeqCst);
+            trace!("Handed over partition ID: {} from member with ID: {} to member with ID: {} for topic with ID: {} in consumer group: {}",
+                partition_id, member_id, new_member_id, self.topic_id, self.group_id);
+        }
+    }
+
+    async fn hand_over_expired_revocations(&self) {
+        if self.assignment_strategy != PartitionAssignmentStrategy::Cooperative {
+            return;
+        }
+
+        for member in self.members.values() {
+            let expired = member
+                .read()
+                .await
+                .revoked_at
+                .is_some_and(|revoked_at| revoked_at.elapsed() >= self.revocation_timeout);
+            if expired {
+                trace!("Revocation timeout expired for member with ID: {} for topic with ID: {} in consumer group: {}",
+                    member.read().await.id, self.topic_id, self.group_id);
+                self.hand_over_revoked_partitions(member).await;
+            }
+        }
+    }
+
     async fn assign_partitions(&mut self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
         let mut members = self.members.values_mut().collect::<Vec<_>>();
         if members.is_empty() {
             return;
         }
 
-        let members_count = members.len() as u32;
+        let mut current_assignments = Vec::with_capacity(members.len());
         for member in members.iter_mut() {
-            let mut member = member.write().await;
-            member.current_partition_index = None;
-            member.current_partition_id = None;
-            member.partitions.clear();
+            let member = member.get_mut();
+            // Not yet acknowledged revocations are discarded, so the partitions are still considered as owned by the current member.
+            let revoked_partitions = std::mem::take(&mut member.revoked_partitions);
+            let mut partitions = member.get_partitions();
+            partitions.extend(
+                revoked_partitions
+                    .into_iter()
+                    .map(|(partition_id, _)| partition_id),
+            );
+            partitions.retain(|partition_id| *partition_id <= self.partitions_count);
+            partitions.sort_unstable();
+            current_assignments.push((member.id, partitions));
         }
+        current_assignments.sort_by_key(|(member_id, _)| *member_id);
 
-        for partition_index in 0..self.partitions_count {
-            let partition_id = partition_index + 1;
-            let member_index = partition_index % members_count;
-            let member = members.get(member_index as usize).unwrap();
-            let mut member = member.write().await;
-            let member_partition_index = member.partitions.len() as u32;
-            member
-                .partitions
-                .insert(member_partition_index, partition_id);
-            if member.current_partition_id.is_none() {
-                member.current_partition_id = Some(partition_id);
-                member.current_partition_index = Some(member_partition_index);
+        let assignments = match self.assignment_strategy {
+            PartitionAssignmentStrategy::RoundRobin => {
+                assign_round_robin(current_assignments.len(), self.partitions_count)
+            }
+            PartitionAssignmentStrategy::Range => {
+                assign_range(current_assignments.len(), self.partitions_count)
+            }
+            PartitionAssignmentStrategy::Sticky | PartitionAssignmentStrategy::Cooperative => {
+                assign_sticky(&current_assignments, self.partitions_count)
+            }
+        };
+
+        let mut owners = AHashMap::new();
+        if self.assignment_strategy == PartitionAssignmentStrategy::Cooperative {
+            for (member_id, partitions) in current_assignments.iter() {
+                for partition_id in partitions {
+                    owners.insert(*partition_id, *member_id);
+                }
             }
-            trace!("Assigned partition ID: {} to member with ID: {} for topic with ID: {} in consumer group: {}",
-                partition_id, member.id, self.topic_id, self.group_id)
         }
+
+        let mut revocations = AHashMap::new();
+        let mut new_assignments = AHashMap::new();
+        for ((member_id, _), partitions) in current_assignments.iter().zip(assignments) {
+            let mut member_partitions = Vec::with_capacity(partitions.len());
+            for partition_id in partitions {
+                match owners.get(&partition_id) {
+                    Some(owner_id) if owner_id != member_id => {
+                        revocations
+                            .entry(*owner_id)
+                            .or_insert_with(Vec::new)
+                            .push((partition_id, *member_id));
+                    }
+                    _ => member_partitions.push(partition_id),
+                }
+            }
+            new_assignments.insert(*member_id, member_partitions);
+        }
+
+        for member in members.iter_mut() {
+            let member = member.get_mut();
+            let partitions = new_assignments.remove(&member.id).unwrap_or_default();
+            member.set_partitions(partitions);
+            member.revoked_partitions = revocations.remove(&member.id).unwrap_or_default();
+            member.revocation_notified = false;
+            member.revoked_at = if member.revoked_partitions.is_empty() {
+                None
+            } else {
+                Some(Instant::now())
+            };
+            trace!("Assigned partition IDs: {:?} to member with ID: {}, revoked partitions: {:?} for topic with ID: {} in consumer group: {}",
+                member.get_partitions(), member.id, member.revoked_partitions, self.topic_id, self.group_id)
+        }
+    }
+}
+
+fn assign_round_robin(members_count: usize, partitions_count: u32) -> Vec<Vec<u32>> {
+    let mut assignments = vec![Vec::new(); members_count];
+    for partition_index in 0..partitions_count {
+        let member_index = partition_index as usize % members_count;
+        assignments[member_index].push(partition_index + 1);
     }
+    assignments
+}
+
+fn assign_range(members_count: usize, partitions_count: u32) -> Vec<Vec<u32>> {
+    let members_count = members_count as u32;
+    let base = partitions_count / members_count;
+    let extra = partitions_count % members_count;
+    (0..members_count)
+        .map(|member_index| {
+            let start = member_index * base + member_index.min(extra);
+            let length = base + u32::from(member_index < extra);
+            (start + 1..=start + length).collect()
+        })
+        .collect()
+}
+
+fn assign_sticky(current_assignments: &[(u32, Vec<u32>)], partitions_count: u32) -> Vec<Vec<u32>> {
+    let members_count = current_assignments.len() as u32;
+    let base = partitions_count / members_count;
+    let extra = partitions_count % members_count;
+
+    // The members already owning the most partitions get the larger quotas, so fewer partitions are moved.
+    let mut order = (0..current_assignments.len()).collect::<Vec<_>>();
+    order.sort_by_key(|index| std::cmp::Reverse(current_assignments[*index].1.len()));
+    let mut quotas = vec![base as usize; current_assignments.len()];
+    for index in order.into_iter().take(extra as usize) {
+        quotas[index] += 1;
+    }
+
+    let mut taken = AHashSet::new();
+    // Each member keeps its current partitions up to the quota, the remaining ones are redistributed.
+    let mut assignments = current_assignments
+        .iter()
+        .zip(quotas.iter())
+        .map(|((_, partitions), quota)| {
+            partitions
+                .iter()
+                .copied()
+                .filter(|partition_id| taken.insert(*partition_id))
+                .take(*quota)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut unassigned =
+        (1..=partitions_count).filter(|partition_id| !taken.contains(partition_id));
+    for (partitions, quota) in assignments.iter_mut().zip(quotas) {
+        while partitions.len() < quota {
+            let Some(partition_id) = unassigned.next() else {
+                break;
+            };
+            partitions.push(partition_id);
+        }
+        partitions.sort_unstable();
+    }
+    assignments
 }
 
 impl ConsumerGroupMember {
@@ -133,6 +360,33 @@ impl ConsumerGroupMember {
         self.partitions.values().copied().collect()
     }
 
+    fn set_partitions(&mut self, partitions: Vec<u32>) {
+        let current_partition_id = self.current_partition_id;
+        self.partitions = partitions
+            .into_iter()
+            .enumerate()
+            .map(|(index, partition_id)| (index as u32, partition_id))
+            .collect();
+        let current_partition = current_partition_id
+            .and_then(|current_partition_id| {
+                self.partitions
+                    .iter()
+                    .find(|(_, partition_id)| **partition_id == current_partition_id)
+            })
+            .or_else(|| self.partitions.get_key_value(&0));
+        self.current_partition_index = current_partition.map(|(index, _)| *index);
+        self.current_partition_id = current_partition.map(|(_, partition_id)| *partition_id);
+    }
+
+    fn add_partition(&mut self, partition_id: u32) {
+        let partition_index = self.partitions.len() as u32;
+        self.partitions.insert(partition_index, partition_id);
+        if self.current_partition_id.is_none() {
+            self.current_partition_index = Some(partition_index);
+            self.current_partition_id = Some(partition_id);
+        }
+    }
+
     pub fn calculate_partition_id(&mut self) -> Option<u32> {
         let partition_index = self.current_partition_index?;
         let Some(partition_id) = self.partitions.get(&partition_index) else {
@@ -164,16 +418,19 @@ impl ConsumerGroupMember {
 mod tests {
     use super::*;
 
+    const REVOCATION_TIMEOUT: Duration = Duration::from_secs(30);
+
     #[tokio::test]
     async fn should_calculate_partition_id_using_round_robin() {
         let member_id = 123;
-        let mut consumer_group = ConsumerGroup {
-            topic_id: 1,
-            group_id: 1,
-            name: "test".to_string(),
-            partitions_count: 3,
-            members: AHashMap::new(),
-        };
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            3,
+            PartitionAssignmentStrategy::RoundRobin,
+            REVOCATION_TIMEOUT,
+        );
 
         consumer_group.add_member(member_id).await;
         for i in 0..1000 {
@@ -189,13 +446,14 @@ mod tests {
     #[tokio::test]
     async fn should_assign_all_partitions_to_the_only_single_member() {
         let member_id = 123;
-        let mut consumer_group = ConsumerGroup {
-            topic_id: 1,
-            group_id: 1,
-            name: "test".to_string(),
-            partitions_count: 3,
-            members: AHashMap::new(),
-        };
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            3,
+            PartitionAssignmentStrategy::RoundRobin,
+            REVOCATION_TIMEOUT,
+        );
 
         consumer_group.add_member(member_id).await;
         let member = consumer_group.members.get(&member_id).unwrap();
@@ -214,13 +472,14 @@ mod tests {
     async fn should_assign_partitions_to_the_multiple_members() {
         let member1_id = 123;
         let member2_id = 456;
-        let mut consumer_group = ConsumerGroup {
-            topic_id: 1,
-            group_id: 1,
-            name: "test".to_string(),
-            partitions_count: 3,
-            members: AHashMap::new(),
-        };
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            3,
+            PartitionAssignmentStrategy::RoundRobin,
+            REVOCATION_TIMEOUT,
+        );
 
         consumer_group.add_member(member1_id).await;
         consumer_group.add_member(member2_id).await;
@@ -251,13 +510,14 @@ mod tests {
     async fn should_assign_only_single_partition_to_the_only_single_member() {
         let member1_id = 123;
         let member2_id = 456;
-        let mut consumer_group = ConsumerGroup {
-            topic_id: 1,
-            group_id: 1,
-            name: "test".to_string(),
-            partitions_count: 1,
-            members: AHashMap::new(),
-        };
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            1,
+            PartitionAssignmentStrategy::RoundRobin,
+            REVOCATION_TIMEOUT,
+        );
 
         consumer_group.add_member(member1_id).await;
         consumer_group.add_member(member2_id).await;
@@ -272,4 +532,225 @@ mod tests {
             assert_eq!(member2.partitions.len(), 1);
         }
     }
+
+    #[tokio::test]
+    async fn should_assign_contiguous_partitions_using_range_strategy() {
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            5,
+            PartitionAssignmentStrategy::Range,
+            REVOCATION_TIMEOUT,
+        );
+
+        consumer_group.add_member(1).await;
+        consumer_group.add_member(2).await;
+        assert_eq!(
+            get_member_partitions(&consumer_group, 1).await,
+            vec![1, 2, 3]
+        );
+        assert_eq!(get_member_partitions(&consumer_group, 2).await, vec![4, 5]);
+    }
+
+    #[tokio::test]
+    async fn should_move_only_required_partitions_using_sticky_strategy() {
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            6,
+            PartitionAssignmentStrategy::Sticky,
+            REVOCATION_TIMEOUT,
+        );
+
+        consumer_group.add_member(1).await;
+        consumer_group.add_member(2).await;
+        let member1_partitions = get_member_partitions(&consumer_group, 1).await;
+        let member2_partitions = get_member_partitions(&consumer_group, 2).await;
+        assert_eq!(member1_partitions, vec![1, 2, 3]);
+        assert_eq!(member2_partitions, vec![4, 5, 6]);
+
+        consumer_group.add_member(3).await;
+        let member1 = get_member_partitions(&consumer_group, 1).await;
+        let member2 = get_member_partitions(&consumer_group, 2).await;
+        let member3 = get_member_partitions(&consumer_group, 3).await;
+        assert_eq!(member1.len(), 2);
+        assert_eq!(member2.len(), 2);
+        assert_eq!(member3.len(), 2);
+        assert!(member1.iter().all(|id| member1_partitions.contains(id)));
+        assert!(member2.iter().all(|id| member2_partitions.contains(id)));
+
+        consumer_group.delete_member(1).await;
+        let member2_after = get_member_partitions(&consumer_group, 2).await;
+        let member3_after = get_member_partitions(&consumer_group, 3).await;
+        assert_eq!(member2_after.len() + member3_after.len(), 6);
+        assert!(member2.iter().all(|id| member2_after.contains(id)));
+        assert!(member3.iter().all(|id| member3_after.contains(id)));
+    }
+
+    #[tokio::test]
+    async fn should_hand_over_partitions_after_revocation_is_acknowledged_using_cooperative_strategy(
+    ) {
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            4,
+            PartitionAssignmentStrategy::Cooperative,
+            REVOCATION_TIMEOUT,
+        );
+
+        consumer_group.add_member(1).await;
+        assert_eq!(
+            get_member_partitions(&consumer_group, 1).await,
+            vec![1, 2, 3, 4]
+        );
+
+        consumer_group.add_member(2).await;
+        let member1_partitions = get_member_partitions(&consumer_group, 1).await;
+        assert_eq!(member1_partitions.len(), 2);
+        assert!(get_member_partitions(&consumer_group, 2).await.is_empty());
+        assert_eq!(
+            consumer_group.calculate_partition_id(2).await.unwrap(),
+            None
+        );
+
+        consumer_group.calculate_partition_id(1).await.unwrap();
+        assert!(get_member_partitions(&consumer_group, 2).await.is_empty());
+
+        consumer_group.calculate_partition_id(1).await.unwrap();
+        let member2_partitions = get_member_partitions(&consumer_group, 2).await;
+        assert_eq!(member2_partitions.len(), 2);
+        assert!(member2_partitions
+            .iter()
+            .all(|id| !member1_partitions.contains(id)));
+        assert!(consumer_group
+            .calculate_partition_id(2)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn should_hand_over_partitions_after_revocation_timeout_using_cooperative_strategy() {
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            4,
+            PartitionAssignmentStrategy::Cooperative,
+            REVOCATION_TIMEOUT,
+        );
+
+        consumer_group.add_member(1).await;
+        consumer_group.add_member(2).await;
+        consumer_group.calculate_partition_id(2).await.unwrap();
+        assert!(get_member_partitions(&consumer_group, 2).await.is_empty());
+
+        consumer_group.revocation_timeout = Duration::ZERO;
+        assert!(consumer_group
+            .calculate_partition_id(2)
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(get_member_partitions(&consumer_group, 2).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_assign_partitions_of_deleted_member_immediately_using_cooperative_strategy() {
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            4,
+            PartitionAssignmentStrategy::Cooperative,
+            REVOCATION_TIMEOUT,
+        );
+
+        consumer_group.add_member(1).await;
+        consumer_group.add_member(2).await;
+        consumer_group.delete_member(1).await;
+        assert_eq!(
+            get_member_partitions(&consumer_group, 2).await,
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_increment_generation_on_every_rebalance() {
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            3,
+            PartitionAssignmentStrategy::RoundRobin,
+            REVOCATION_TIMEOUT,
+        );
+        assert_eq!(consumer_group.get_generation(), 0);
+
+        consumer_group.add_member(1).await;
+        assert_eq!(consumer_group.get_generation(), 1);
+        consumer_group.add_member(2).await;
+        assert_eq!(consumer_group.get_generation(), 2);
+        consumer_group.reassign_partitions(4).await;
+        assert_eq!(consumer_group.get_generation(), 3);
+        consumer_group.delete_member(2).await;
+        assert_eq!(consumer_group.get_generation(), 4);
+        consumer_group.delete_member(2).await;
+        assert_eq!(consumer_group.get_generation(), 4);
+    }
+
+    #[tokio::test]
+    async fn should_increment_generation_once_revoked_partitions_are_handed_over() {
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            4,
+            PartitionAssignmentStrategy::Cooperative,
+            REVOCATION_TIMEOUT,
+        );
+
+        consumer_group.add_member(1).await;
+        consumer_group.add_member(2).await;
+        let generation = consumer_group.get_generation();
+        consumer_group.calculate_partition_id(1).await.unwrap();
+        assert_eq!(consumer_group.get_generation(), generation);
+        consumer_group.calculate_partition_id(1).await.unwrap();
+        assert!(consumer_group.get_generation() > generation);
+        let generation = consumer_group.get_generation();
+        consumer_group.calculate_partition_id(1).await.unwrap();
+        assert_eq!(consumer_group.get_generation(), generation);
+    }
+
+    #[tokio::test]
+    async fn should_reject_stale_generation() {
+        let mut consumer_group = ConsumerGroup::new(
+            1,
+            1,
+            "test",
+            3,
+            PartitionAssignmentStrategy::RoundRobin,
+            REVOCATION_TIMEOUT,
+        );
+
+        consumer_group.add_member(1).await;
+        let generation = consumer_group.get_generation();
+        assert!(consumer_group.validate_generation(generation).is_ok());
+
+        consumer_group.add_member(2).await;
+        let error = consumer_group.validate_generation(generation).unwrap_err();
+        assert_eq!(
+            error.as_code(),
+            IggyError::StaleConsumerGroupGeneration(0, 0, 0).as_code()
+        );
+    }
+
+    async fn get_member_partitions(consumer_group: &ConsumerGroup, member_id: u32) -> Vec<u32> {
+        consumer_group
+            .get_member_partitions(member_id)
+            .await
+            .unwrap()
+    }
 }
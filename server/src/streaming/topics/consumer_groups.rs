@@ -2,9 +2,11 @@ use crate::streaming::topics::consumer_group::ConsumerGroup;
 use crate::streaming::topics::topic::Topic;
 use crate::streaming::topics::COMPONENT;
 use error_set::ErrContext;
+use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
 use iggy::locking::IggySharedMutFn;
+use iggy::models::consumer_group::ConsumerGroupMembership;
 use std::sync::atomic::Ordering;
 use tokio::sync::RwLock;
 use tracing::info;
@@ -86,6 +88,7 @@ impl Topic {
         &mut self,
         group_id: Option<u32>,
         name: &str,
+        assignment_strategy: PartitionAssignmentStrategy,
     ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
         if self.consumer_groups_ids.contains_key(name) {
             return Err(IggyError::ConsumerGroupNameAlreadyExists(
@@ -119,13 +122,19 @@ impl Topic {
             return Err(IggyError::ConsumerGroupIdAlreadyExists(id, self.topic_id));
         }
 
-        let consumer_group =
-            ConsumerGroup::new(self.topic_id, id, name, self.partitions.len() as u32);
+        let consumer_group = ConsumerGroup::new(
+            self.topic_id,
+            id,
+            name,
+            self.partitions.len() as u32,
+            assignment_strategy,
+            self.config.consumer_group.revocation_timeout.get_duration(),
+        );
         self.consumer_groups.insert(id, RwLock::new(consumer_group));
         self.consumer_groups_ids.insert(name.to_owned(), id);
         info!(
-            "Created consumer group with ID: {} for topic with ID: {} and stream with ID: {}.",
-            id, self.topic_id, self.stream_id
+            "Created consumer group with ID: {} and assignment strategy: {} for topic with ID: {} and stream with ID: {}.",
+            id, assignment_strategy, self.topic_id, self.stream_id
         );
         self.get_consumer_group_by_id(id)
     }
@@ -181,17 +190,25 @@ impl Topic {
         &self,
         group_id: &Identifier,
         member_id: u32,
-    ) -> Result<(), IggyError> {
+    ) -> Result<ConsumerGroupMembership, IggyError> {
         let consumer_group = self.get_consumer_group(group_id).with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to get consumer group with id: {group_id}")
         })?;
         let mut consumer_group = consumer_group.write().await;
         consumer_group.add_member(member_id).await;
+        let generation = consumer_group.get_generation();
+        let partitions = consumer_group.get_member_partitions(member_id).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get partitions for member with ID: {member_id} in consumer group with id: {group_id}")
+        })?;
         info!(
-            "Member with ID: {} has joined consumer group with ID: {} for topic with ID: {} and stream with ID: {}.",
-            member_id, group_id, self.topic_id, self.stream_id
+            "Member with ID: {} has joined consumer group with ID: {} for topic with ID: {} and stream with ID: {}, generation: {}, partitions: {:?}.",
+            member_id, group_id, self.topic_id, self.stream_id, generation, partitions
         );
-        Ok(())
+        Ok(ConsumerGroupMembership {
+            member_id,
+            generation,
+            partitions,
+        })
     }
 
     pub async fn leave_consumer_group(
@@ -219,6 +236,7 @@ mod tests {
     use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
     use crate::streaming::storage::SystemStorage;
     use iggy::compression::compression_algorithm::CompressionAlgorithm;
+    use iggy::topics::cleanup_policy::CleanupPolicy;
     use iggy::utils::expiry::IggyExpiry;
     use iggy::utils::topic_size::MaxTopicSize;
     use std::sync::atomic::{AtomicU32, AtomicU64};
@@ -230,7 +248,9 @@ mod tests {
         let name = "test";
         let mut topic = get_topic().await;
         let topic_id = topic.topic_id;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, PartitionAssignmentStrategy::default())
+            .await;
         assert!(result.is_ok());
         {
             let created_consumer_group = result.unwrap().read().await;
@@ -258,10 +278,18 @@ mod tests {
         let group_id = 1;
         let name = "test";
         let mut topic = get_topic().await;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, PartitionAssignmentStrategy::default())
+            .await;
         assert!(result.is_ok());
         assert_eq!(topic.consumer_groups.len(), 1);
-        let result = topic.create_consumer_group(Some(group_id), "test2").await;
+        let result = topic
+            .create_consumer_group(
+                Some(group_id),
+                "test2",
+                PartitionAssignmentStrategy::default(),
+            )
+            .await;
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(matches!(err, IggyError::ConsumerGroupIdAlreadyExists(_, _)));
@@ -273,11 +301,15 @@ mod tests {
         let group_id = 1;
         let name = "test";
         let mut topic = get_topic().await;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, PartitionAssignmentStrategy::default())
+            .await;
         assert!(result.is_ok());
         assert_eq!(topic.consumer_groups.len(), 1);
         let group_id = group_id + 1;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, PartitionAssignmentStrategy::default())
+            .await;
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(matches!(
@@ -292,7 +324,9 @@ mod tests {
         let group_id = 1;
         let name = "test";
         let mut topic = get_topic().await;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, PartitionAssignmentStrategy::default())
+            .await;
         assert!(result.is_ok());
         assert_eq!(topic.consumer_groups.len(), 1);
         let result = topic
@@ -307,7 +341,9 @@ mod tests {
         let group_id = 1;
         let name = "test";
         let mut topic = get_topic().await;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, PartitionAssignmentStrategy::default())
+            .await;
         assert!(result.is_ok());
         assert_eq!(topic.consumer_groups.len(), 1);
         let group_id = group_id + 1;
@@ -325,13 +361,17 @@ mod tests {
         let member_id = 1;
         let mut topic = get_topic().await;
         topic
-            .create_consumer_group(Some(group_id), name)
+            .create_consumer_group(Some(group_id), name, PartitionAssignmentStrategy::default())
             .await
             .unwrap();
         let result = topic
             .join_consumer_group(&Identifier::numeric(group_id).unwrap(), member_id)
             .await;
         assert!(result.is_ok());
+        let membership = result.unwrap();
+        assert_eq!(membership.member_id, member_id);
+        assert_eq!(membership.generation, 1);
+        assert_eq!(membership.partitions.len(), topic.partitions.len());
         let consumer_group = topic
             .get_consumer_group(&Identifier::numeric(group_id).unwrap())
             .unwrap()
@@ -348,7 +388,7 @@ mod tests {
         let member_id = 1;
         let mut topic = get_topic().await;
         topic
-            .create_consumer_group(Some(group_id), name)
+            .create_consumer_group(Some(group_id), name, PartitionAssignmentStrategy::default())
             .await
             .unwrap();
         topic
@@ -399,8 +439,10 @@ mod tests {
             segments_count_of_parent_stream,
             IggyExpiry::NeverExpire,
             compression_algorithm,
+            None,
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap()
@@ -2,7 +2,7 @@ use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::topics::topic::Topic;
 use crate::streaming::topics::COMPONENT;
 use error_set::ErrContext;
-use iggy::consumer::Consumer;
+use iggy::consumer::{Consumer, ConsumerKind};
 use iggy::error::IggyError;
 use iggy::locking::IggySharedMutFn;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
@@ -13,15 +13,12 @@ impl Topic {
         consumer: Consumer,
         offset: u64,
         partition_id: Option<u32>,
+        generation: Option<u32>,
         client_id: u32,
     ) -> Result<(), IggyError> {
-        let Some((polling_consumer, partition_id)) = self
-            .resolve_consumer_with_partition_id(&consumer, client_id, partition_id, false)
-            .await
-            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to resolve consumer with partition id, consumer ID: {}, client ID: {}, partition ID: {:?}", consumer.id, client_id, partition_id))? else {
-            return Err(IggyError::ConsumerOffsetNotFound(client_id));
-        };
-
+        let (polling_consumer, partition_id) = self
+            .resolve_consumer_offset(&consumer, offset, partition_id, generation, client_id)
+            .await?;
         let partition = self.get_partition(partition_id).with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to get partition with id: {partition_id}")
         })?;
@@ -32,6 +29,39 @@ impl Topic {
             .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to store consumer offset, consumer: {polling_consumer}, offset: {offset}"))
     }
 
+    /// Validates the generation of the consumer group (if provided) and resolves the consumer along with the partition ID,
+    /// for which the consumer offset can be stored.
+    pub async fn resolve_consumer_offset(
+        &self,
+        consumer: &Consumer,
+        offset: u64,
+        partition_id: Option<u32>,
+        generation: Option<u32>,
+        client_id: u32,
+    ) -> Result<(PollingConsumer, u32), IggyError> {
+        if let (ConsumerKind::ConsumerGroup, Some(generation)) = (consumer.kind, generation) {
+            let consumer_group =
+                self.get_consumer_group(&consumer.id)
+                    .with_error_context(|error| {
+                        format!(
+                        "{COMPONENT} (error: {error}) - failed to get consumer group with ID: {}",
+                        consumer.id
+                    )
+                    })?;
+            consumer_group.read().await.validate_generation(generation).with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - rejected consumer offset: {offset} from stale generation: {generation}, consumer group ID: {}, client ID: {client_id}", consumer.id)
+            })?;
+        }
+
+        let Some((polling_consumer, partition_id)) = self
+            .resolve_consumer_with_partition_id(consumer, client_id, partition_id, false)
+            .await
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to resolve consumer with partition id, consumer ID: {}, client ID: {}, partition ID: {:?}", consumer.id, client_id, partition_id))? else {
+            return Err(IggyError::ConsumerOffsetNotFound(client_id));
+        };
+        Ok((polling_consumer, partition_id))
+    }
+
     pub async fn store_consumer_offset_internal(
         &self,
         consumer: PollingConsumer,
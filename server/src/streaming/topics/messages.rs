@@ -1,22 +1,33 @@
 use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
-use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::models::messages::{PolledBatches, RetainedMessage};
+use crate::streaming::partitions::partition::Partition;
 use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::topics::topic::Topic;
 use crate::streaming::topics::COMPONENT;
 use crate::streaming::utils::file::folder_size;
-use crate::streaming::utils::hash;
 use ahash::AHashMap;
 use error_set::ErrContext;
+use iggy::bytes_serializable::BytesSerializable;
 use iggy::confirmation::Confirmation;
+use iggy::consumer::Consumer;
 use iggy::error::IggyError;
 use iggy::locking::IggySharedMutFn;
+use iggy::messages::message_filter::MessageFilter;
+use iggy::messages::nack_message::{
+    DEAD_LETTER_OFFSET_HEADER, DEAD_LETTER_PARTITION_ID_HEADER, DEAD_LETTER_REASON_HEADER,
+    DEAD_LETTER_STREAM_ID_HEADER, DEAD_LETTER_TOPIC_ID_HEADER,
+};
 use iggy::messages::poll_messages::{PollingKind, PollingStrategy};
 use iggy::messages::send_messages::{Message, Partitioning, PartitioningKind};
-use iggy::models::messages::PolledMessages;
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::models::messages::{MessageState, PolledMessages};
+use iggy::partitioner::calculate_partition_id_by_messages_key;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
 use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tracing::{info, trace, warn};
@@ -47,6 +58,8 @@ impl Topic {
         }
 
         let partition = partition.unwrap();
+        let _archive_cache_pins =
+            Partition::prefetch_archived_segments(partition, consumer, strategy, count).await?;
         let partition = partition.read().await;
         let value = strategy.value;
         let messages = match strategy.kind {
@@ -64,22 +77,234 @@ impl Topic {
 
         let messages = messages
             .into_iter()
-            .map(|msg| msg.to_polled_message())
+            .map(|msg| {
+                let mut message = msg.to_polled_message()?;
+                if partition.is_message_poisoned(message.offset) {
+                    message.state = MessageState::Poisoned;
+                }
+                Ok(message)
+            })
             .collect::<Result<Vec<_>, IggyError>>()?;
         Ok(PolledMessages {
             partition_id,
             current_offset: partition.current_offset,
             messages,
+            generation: None,
         })
     }
 
+    /// Returns the messages matching the filter and the offset of the last message read, which doesn't have to match it.
+    /// With the read committed isolation level, the messages are read only up to the last stable offset.
+    pub async fn get_filtered_messages(
+        &self,
+        consumer: PollingConsumer,
+        partition_id: u32,
+        strategy: PollingStrategy,
+        count: u32,
+        filter: &MessageFilter,
+        read_committed: bool,
+    ) -> Result<(PolledMessages, Option<u64>), IggyError> {
+        let partition = self.get_partition(partition_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get partition with id: {partition_id}")
+        })?;
+        let _archive_cache_pins =
+            Partition::prefetch_archived_segments(&partition, consumer, strategy, count).await?;
+        let partition = partition.read().await;
+        let start_offset = match strategy.kind {
+            PollingKind::Timestamp => partition
+                .get_messages_by_timestamp(strategy.value.into(), 1)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to get messages by timestamp: {}",
+                        strategy.value
+                    )
+                })?
+                .first()
+                .map(|message| message.offset),
+            _ => partition.get_polling_start_offset(consumer, strategy, count),
+        };
+        let end_offset = match partition.get_last_stable_offset() {
+            Some(last_stable_offset) if read_committed => last_stable_offset.checked_sub(1),
+            _ => Some(partition.current_offset),
+        };
+
+        let (messages, last_offset) = match (start_offset, end_offset) {
+            (Some(start_offset), Some(end_offset)) => {
+                partition
+                    .get_filtered_messages_by_offset(start_offset, count, end_offset, filter)
+                    .await?
+            }
+            _ => (Vec::new(), None),
+        };
+        let messages = messages
+            .into_iter()
+            .map(|msg| {
+                let mut message = msg.to_polled_message()?;
+                if partition.is_message_poisoned(message.offset) {
+                    message.state = MessageState::Poisoned;
+                }
+                Ok(message)
+            })
+            .collect::<Result<Vec<_>, IggyError>>()?;
+        Ok((
+            PolledMessages {
+                partition_id,
+                current_offset: partition.current_offset,
+                messages,
+                generation: None,
+            },
+            last_offset,
+        ))
+    }
+
+    /// Returns the batches stored in the segment log files containing the messages to be polled,
+    /// or `None` if they have to be polled as the regular messages.
+    pub async fn get_batches(
+        &self,
+        consumer: PollingConsumer,
+        partition_id: u32,
+        strategy: PollingStrategy,
+        count: u32,
+    ) -> Result<Option<PolledBatches>, IggyError> {
+        let partition = self.get_partition(partition_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get partition with id: {partition_id}")
+        })?;
+        let partition = partition.read().await;
+        let Some(start_offset) = partition.get_polling_start_offset(consumer, strategy, count)
+        else {
+            return Ok(None);
+        };
+
+        let Some((regions, first_offset, last_offset)) =
+            partition.get_log_regions_by_offset(start_offset, count)
+        else {
+            return Ok(None);
+        };
+
+        trace!("Polling {} log regions with messages from offset: {first_offset} to: {last_offset} for partition with ID: {partition_id}.", regions.len());
+        Ok(Some(PolledBatches {
+            partition_id,
+            current_offset: partition.current_offset,
+            generation: None,
+            first_offset,
+            last_offset,
+            regions,
+        }))
+    }
+
+    pub async fn record_delivery_attempt(
+        &self,
+        consumer: &Consumer,
+        partition_id: u32,
+        offset: u64,
+        client_id: u32,
+    ) -> Result<u32, IggyError> {
+        let Some((polling_consumer, partition_id)) = self
+            .resolve_consumer_with_partition_id(consumer, client_id, Some(partition_id), false)
+            .await
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to resolve consumer with partition id, consumer: {consumer}, client ID: {client_id}, partition ID: {partition_id}"))? else {
+            return Err(IggyError::ConsumerOffsetNotFound(client_id));
+        };
+
+        let partition = self.get_partition(partition_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get partition with id: {partition_id}")
+        })?;
+        let partition = partition.read().await;
+        partition
+            .record_delivery_attempt(polling_consumer, offset)
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to record delivery attempt, consumer: {polling_consumer}, offset: {offset}"))
+    }
+
+    /// Returns the copy of the message to be appended to the dead letter topic, with the headers pointing to the original message.
+    /// The payload is copied as stored, so it remains encrypted if the server-side encryption is enabled.
+    pub async fn get_dead_letter_message(
+        &self,
+        partition_id: u32,
+        offset: u64,
+        reason: &str,
+    ) -> Result<Message, IggyError> {
+        let partition = self.get_partition(partition_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get partition with id: {partition_id}")
+        })?;
+        let partition = partition.read().await;
+        let messages = partition.get_messages_by_offset(offset, 1).await?;
+        let Some(message) = messages
+            .into_iter()
+            .find(|message| message.offset == offset)
+        else {
+            return Err(IggyError::InvalidOffset(offset));
+        };
+
+        let mut headers = message
+            .headers
+            .clone()
+            .map(HashMap::from_bytes)
+            .transpose()?
+            .unwrap_or_default();
+        headers.insert(
+            HeaderKey::new(DEAD_LETTER_STREAM_ID_HEADER)?,
+            HeaderValue::from_uint32(self.stream_id)?,
+        );
+        headers.insert(
+            HeaderKey::new(DEAD_LETTER_TOPIC_ID_HEADER)?,
+            HeaderValue::from_uint32(self.topic_id)?,
+        );
+        headers.insert(
+            HeaderKey::new(DEAD_LETTER_PARTITION_ID_HEADER)?,
+            HeaderValue::from_uint32(partition_id)?,
+        );
+        headers.insert(
+            HeaderKey::new(DEAD_LETTER_OFFSET_HEADER)?,
+            HeaderValue::from_uint64(offset)?,
+        );
+        if !reason.is_empty() {
+            headers.insert(
+                HeaderKey::new(DEAD_LETTER_REASON_HEADER)?,
+                HeaderValue::from_str(reason)?,
+            );
+        }
+        Ok(Message::new(
+            Some(message.id),
+            message.payload.clone(),
+            Some(headers),
+        ))
+    }
+
+    /// Marks the message as poisoned, returns `false` if it has been already marked.
+    pub async fn mark_message_as_poisoned(
+        &self,
+        partition_id: u32,
+        offset: u64,
+    ) -> Result<bool, IggyError> {
+        let partition = self.get_partition(partition_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get partition with id: {partition_id}")
+        })?;
+        let partition = partition.read().await;
+        Ok(partition.mark_message_as_poisoned(offset))
+    }
+
+    pub async fn unmark_message_as_poisoned(
+        &self,
+        partition_id: u32,
+        offset: u64,
+    ) -> Result<(), IggyError> {
+        let partition = self.get_partition(partition_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get partition with id: {partition_id}")
+        })?;
+        let partition = partition.read().await;
+        partition.unmark_message_as_poisoned(offset);
+        Ok(())
+    }
+
+    /// Appends the messages to the partition resolved by the partitioning and returns its ID.
     pub async fn append_messages(
         &self,
         batch_size: IggyByteSize,
         partitioning: Partitioning,
         messages: Vec<Message>,
         confirmation: Option<Confirmation>,
-    ) -> Result<(), IggyError> {
+    ) -> Result<u32, IggyError> {
         if !self.has_partitions() {
             return Err(IggyError::NoPartitions(self.topic_id, self.stream_id));
         }
@@ -90,10 +315,6 @@ impl Topic {
             return Err(IggyError::TopicFull(self.topic_id, self.stream_id));
         }
 
-        if messages.is_empty() {
-            return Ok(());
-        }
-
         let partition_id = match partitioning.kind {
             PartitioningKind::Balanced => self.get_next_partition_id(),
             PartitioningKind::PartitionId => u32::from_le_bytes(
@@ -106,9 +327,14 @@ impl Topic {
             }
         };
 
+        if messages.is_empty() {
+            return Ok(partition_id);
+        }
+
         let appendable_batch_info = AppendableBatchInfo::new(batch_size, partition_id);
         self.append_messages_to_partition(appendable_batch_info, messages, confirmation)
-            .await
+            .await?;
+        Ok(partition_id)
     }
 
     pub async fn flush_unsaved_buffer(
@@ -129,6 +355,41 @@ impl Topic {
             .await
     }
 
+    /// Allocates the new epoch of the idempotent producer, higher than any of its epochs known to the topic partitions,
+    /// and starts it in all of them. The partitions are locked for the whole time, so the concurrent calls get distinct epochs.
+    pub async fn init_producer(&self, producer_id: u64) -> Result<u64, IggyError> {
+        let mut partition_locks = self.partitions.iter().collect::<Vec<_>>();
+        partition_locks.sort_by_key(|(partition_id, _)| **partition_id);
+        let mut partitions = Vec::with_capacity(partition_locks.len());
+        for (_, partition_lock) in partition_locks {
+            partitions.push(partition_lock.write().await);
+        }
+
+        let epoch = partitions
+            .iter()
+            .filter_map(|partition| partition.get_producer_sequence(producer_id))
+            .map(|sequence| sequence.epoch)
+            .max()
+            .unwrap_or_default()
+            + 1;
+        for partition in partitions.iter_mut() {
+            partition
+                .start_producer_epoch(producer_id, epoch)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to start epoch: {epoch} of producer with ID: {producer_id} for partition with ID: {}",
+                        partition.partition_id
+                    )
+                })?;
+        }
+        info!(
+            "Started epoch: {epoch} of producer with ID: {producer_id} for topic with ID: {} in stream with ID: {}.",
+            self.topic_id, self.stream_id
+        );
+        Ok(epoch)
+    }
+
     async fn append_messages_to_partition(
         &self,
         appendable_batch_info: AppendableBatchInfo,
@@ -168,17 +429,13 @@ impl Topic {
     }
 
     fn calculate_partition_id_by_messages_key_hash(&self, messages_key: &[u8]) -> u32 {
-        let messages_key_hash = hash::calculate_32(messages_key);
-        let partitions_count = self.get_partitions_count();
-        let mut partition_id = messages_key_hash % partitions_count;
-        if partition_id == 0 {
-            partition_id = partitions_count;
-        }
+        // The idempotent producers resolve the partition the same way, to assign the sequences per partition.
+        let partition_id =
+            calculate_partition_id_by_messages_key(messages_key, self.get_partitions_count());
         trace!(
-            "Calculated partition ID: {} for messages key: {:?}, hash: {}",
+            "Calculated partition ID: {} for messages key: {:?}",
             partition_id,
-            messages_key,
-            messages_key_hash
+            messages_key
         );
         partition_id
     }
@@ -317,8 +574,10 @@ mod tests {
     use crate::streaming::persistence::persister::FileWithSyncPersister;
     use crate::streaming::persistence::persister::PersisterKind;
     use crate::streaming::storage::SystemStorage;
+    use crate::streaming::utils::hash;
     use bytes::Bytes;
     use iggy::compression::compression_algorithm::CompressionAlgorithm;
+    use iggy::topics::cleanup_policy::CleanupPolicy;
     use iggy::utils::topic_size::MaxTopicSize;
     use std::sync::atomic::AtomicU32;
     use std::sync::atomic::AtomicU64;
@@ -458,8 +717,10 @@ mod tests {
             segments_count_of_parent_stream,
             IggyExpiry::NeverExpire,
             compression_algorithm,
+            None,
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
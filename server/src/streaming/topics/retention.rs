@@ -0,0 +1,257 @@
+use crate::streaming::topics::topic::Topic;
+use ahash::{AHashMap, AHashSet};
+use iggy::locking::IggySharedMutFn;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::topic_size::MaxTopicSize;
+use std::sync::atomic::Ordering;
+use tracing::debug;
+
+/// Segments which cannot be removed yet, identified by the partition ID and start offset, per consumer group name.
+#[derive(Debug, Default)]
+pub struct BlockedSegments {
+    groups: AHashMap<String, AHashSet<(u32, u64)>>,
+}
+
+impl BlockedSegments {
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Returns the number of blocked segments for each consumer group.
+    pub fn counts(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.groups
+            .iter()
+            .map(|(group, segments)| (group.as_str(), segments.len() as u32))
+    }
+
+    fn block(&mut self, consumer_group: &str, partition_id: u32, start_offset: u64) {
+        self.groups
+            .entry(consumer_group.to_owned())
+            .or_default()
+            .insert((partition_id, start_offset));
+    }
+}
+
+impl Topic {
+    /// Returns true if the segments of this topic can be removed only after being consumed by the consumer groups.
+    pub fn retains_unconsumed_segments(&self) -> bool {
+        self.cleanup_policy == CleanupPolicy::Consumed && !self.exceeds_retention_max_size()
+    }
+
+    /// Returns true if the topic with the consumed cleanup policy exceeds the hard max size,
+    /// in which case its oldest segments are removed even if they were not consumed yet.
+    pub fn exceeds_retention_max_size(&self) -> bool {
+        if self.cleanup_policy != CleanupPolicy::Consumed {
+            return false;
+        }
+
+        let max_size = match self.consumed_retention.max_size {
+            MaxTopicSize::ServerDefault => self.config.topic.consumed_retention.max_size,
+            max_size => max_size,
+        };
+        match max_size {
+            MaxTopicSize::Custom(size) => {
+                self.size_bytes.load(Ordering::SeqCst) > size.as_bytes_u64()
+            }
+            MaxTopicSize::Unlimited | MaxTopicSize::ServerDefault => false,
+        }
+    }
+
+    /// Keeps the segments consumed by all the retained consumer groups (stored offset is at or past the segment end offset),
+    /// the other ones are recorded in `blocked` along with the consumer groups that did not consume them yet.
+    pub async fn retain_consumed_segments(
+        &self,
+        partition_id: u32,
+        start_offsets: Vec<u64>,
+        blocked: &mut BlockedSegments,
+    ) -> Vec<u64> {
+        let consumer_groups = self.get_retained_consumer_groups().await;
+        if consumer_groups.is_empty() {
+            return start_offsets;
+        }
+
+        let Some(partition) = self.partitions.get(&partition_id) else {
+            return Vec::new();
+        };
+
+        let partition = partition.read().await;
+        let mut consumed_start_offsets = Vec::with_capacity(start_offsets.len());
+        for start_offset in start_offsets {
+            let Some(segment) = partition.get_segment(start_offset) else {
+                continue;
+            };
+
+            let mut is_consumed = true;
+            for (group_id, group_name) in &consumer_groups {
+                let stored_offset = partition
+                    .consumer_group_offsets
+                    .get(group_id)
+                    .map(|consumer_offset| consumer_offset.offset);
+                if stored_offset.is_some_and(|offset| offset >= segment.end_offset) {
+                    continue;
+                }
+
+                debug!(
+                    "Segment with start offset: {start_offset} and end offset: {} was not consumed by consumer group: {group_name} (stored offset: {stored_offset:?}) for stream ID: {}, topic ID: {}, partition ID: {partition_id}",
+                    segment.end_offset, self.stream_id, self.topic_id
+                );
+                blocked.block(group_name, partition_id, start_offset);
+                is_consumed = false;
+            }
+
+            if is_consumed {
+                consumed_start_offsets.push(start_offset);
+            }
+        }
+
+        consumed_start_offsets
+    }
+
+    async fn get_retained_consumer_groups(&self) -> Vec<(u32, String)> {
+        let retained_groups = &self.consumed_retention.consumer_groups;
+        let mut consumer_groups = Vec::new();
+        for consumer_group in self.consumer_groups.values() {
+            let consumer_group = consumer_group.read().await;
+            if retained_groups.is_empty() || retained_groups.contains(&consumer_group.name) {
+                consumer_groups.push((consumer_group.group_id, consumer_group.name.clone()));
+            }
+        }
+
+        consumer_groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::system::SystemConfig;
+    use crate::streaming::partitions::partition::ConsumerOffset;
+    use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
+    use crate::streaming::storage::SystemStorage;
+    use iggy::compression::compression_algorithm::CompressionAlgorithm;
+    use iggy::consumer::ConsumerKind;
+    use iggy::consumer_groups::partition_assignment_strategy::PartitionAssignmentStrategy;
+    use iggy::topics::consumed_retention::ConsumedRetention;
+    use iggy::utils::expiry::IggyExpiry;
+    use std::sync::atomic::{AtomicU32, AtomicU64};
+    use std::sync::Arc;
+
+    const PARTITION_ID: u32 = 1;
+    const SEGMENT_END_OFFSET: u64 = 9;
+
+    #[tokio::test]
+    async fn should_retain_only_segments_consumed_by_all_consumer_groups() {
+        let topic = get_topic(Vec::new()).await;
+        store_offset(&topic, 1, SEGMENT_END_OFFSET).await;
+        store_offset(&topic, 2, SEGMENT_END_OFFSET - 1).await;
+        let mut blocked = BlockedSegments::default();
+
+        let start_offsets = topic
+            .retain_consumed_segments(PARTITION_ID, vec![0], &mut blocked)
+            .await;
+
+        assert!(start_offsets.is_empty());
+        let counts = blocked.counts().collect::<Vec<_>>();
+        assert_eq!(counts, vec![("group-2", 1)]);
+
+        store_offset(&topic, 2, SEGMENT_END_OFFSET).await;
+        let mut blocked = BlockedSegments::default();
+        let start_offsets = topic
+            .retain_consumed_segments(PARTITION_ID, vec![0], &mut blocked)
+            .await;
+
+        assert_eq!(start_offsets, vec![0]);
+        assert!(blocked.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_block_segments_given_consumer_group_without_stored_offset() {
+        let topic = get_topic(Vec::new()).await;
+        store_offset(&topic, 1, SEGMENT_END_OFFSET).await;
+        let mut blocked = BlockedSegments::default();
+
+        let start_offsets = topic
+            .retain_consumed_segments(PARTITION_ID, vec![0], &mut blocked)
+            .await;
+
+        assert!(start_offsets.is_empty());
+        let counts = blocked.counts().collect::<Vec<_>>();
+        assert_eq!(counts, vec![("group-2", 1)]);
+    }
+
+    #[tokio::test]
+    async fn should_take_into_account_only_selected_consumer_groups() {
+        let topic = get_topic(vec!["group-1".to_owned()]).await;
+        store_offset(&topic, 1, SEGMENT_END_OFFSET).await;
+        let mut blocked = BlockedSegments::default();
+
+        let start_offsets = topic
+            .retain_consumed_segments(PARTITION_ID, vec![0], &mut blocked)
+            .await;
+
+        assert_eq!(start_offsets, vec![0]);
+        assert!(blocked.is_empty());
+    }
+
+    async fn store_offset(topic: &Topic, group_id: u32, offset: u64) {
+        let partition = topic.get_partition(PARTITION_ID).unwrap();
+        let partition = partition.read().await;
+        partition.consumer_group_offsets.insert(
+            group_id,
+            ConsumerOffset::new(ConsumerKind::ConsumerGroup, group_id, offset, "offsets"),
+        );
+    }
+
+    async fn get_topic(consumer_groups: Vec<String>) -> Topic {
+        let tempdir = tempfile::TempDir::new().unwrap();
+        let config = Arc::new(SystemConfig {
+            path: tempdir.path().to_str().unwrap().to_string(),
+            ..Default::default()
+        });
+        let storage = Arc::new(SystemStorage::new(
+            config.clone(),
+            Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {})),
+        ));
+        let mut topic = Topic::create(
+            1,
+            2,
+            "test",
+            1,
+            config,
+            storage,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU32::new(0)),
+            IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
+            None,
+            MaxTopicSize::ServerDefault,
+            1,
+            CleanupPolicy::Consumed,
+        )
+        .await
+        .unwrap();
+        topic.consumed_retention = ConsumedRetention {
+            consumer_groups,
+            ..Default::default()
+        };
+        for group_id in 1..=2 {
+            topic
+                .create_consumer_group(
+                    Some(group_id),
+                    &format!("group-{group_id}"),
+                    PartitionAssignmentStrategy::default(),
+                )
+                .await
+                .unwrap();
+        }
+
+        {
+            let partition = topic.get_partition(PARTITION_ID).unwrap();
+            let mut partition = partition.write().await;
+            let segment = partition.get_segment_mut(0).unwrap();
+            segment.end_offset = SEGMENT_END_OFFSET;
+        }
+        topic
+    }
+}
@@ -41,7 +41,12 @@ impl TopicStorage for FileTopicStorage {
         topic.message_expiry = message_expiry;
         topic.max_topic_size = max_topic_size;
         topic.compression_algorithm = state.compression_algorithm;
+        topic.compression_level = state.compression_level;
+        let compression_algorithm = topic.get_compression_algorithm();
+        let compression_level = topic.get_compression_level();
         topic.replication_factor = state.replication_factor.unwrap_or(1);
+        topic.cleanup_policy = state.cleanup_policy;
+        topic.consumed_retention = state.consumed_retention.clone();
 
         let mut dir_entries = fs::read_dir(&topic.partitions_path).await
             .with_context(|| format!("Failed to read partition with ID: {} for stream with ID: {} for topic with ID: {} and path: {}",
@@ -77,7 +82,7 @@ impl TopicStorage for FileTopicStorage {
             }
 
             let partition_state = partition_state.unwrap();
-            let partition = Partition::create(
+            let mut partition = Partition::create(
                 topic.stream_id,
                 topic.topic_id,
                 partition_id,
@@ -85,6 +90,8 @@ impl TopicStorage for FileTopicStorage {
                 topic.config.clone(),
                 topic.storage.clone(),
                 message_expiry,
+                compression_algorithm,
+                compression_level,
                 topic.messages_count_of_parent_stream.clone(),
                 topic.messages_count.clone(),
                 topic.size_of_parent_stream.clone(),
@@ -93,6 +100,7 @@ impl TopicStorage for FileTopicStorage {
                 partition_state.created_at,
             )
             .await;
+            partition.cleanup_policy = topic.cleanup_policy;
             unloaded_partitions.push(partition);
         }
 
@@ -139,6 +147,8 @@ impl TopicStorage for FileTopicStorage {
                     topic.config.clone(),
                     topic.storage.clone(),
                     message_expiry,
+                    compression_algorithm,
+                    compression_level,
                     topic.messages_count_of_parent_stream.clone(),
                     topic.messages_count.clone(),
                     topic.size_of_parent_stream.clone(),
@@ -147,6 +157,7 @@ impl TopicStorage for FileTopicStorage {
                     partition_state.created_at,
                 )
                 .await;
+                partition.cleanup_policy = topic.cleanup_policy;
                 partition.persist().await.with_error_context(|error| {
                     format!(
                         "{COMPONENT} (error: {error}) - failed to persist partition: {partition}"
@@ -196,6 +207,12 @@ impl TopicStorage for FileTopicStorage {
                 consumer_group.id,
                 &consumer_group.name,
                 topic.get_partitions_count(),
+                consumer_group.assignment_strategy,
+                topic
+                    .config
+                    .consumer_group
+                    .revocation_timeout
+                    .get_duration(),
             );
             topic
                 .consumer_groups_ids
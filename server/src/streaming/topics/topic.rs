@@ -9,6 +9,8 @@ use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::{Consumer, ConsumerKind};
 use iggy::error::IggyError;
 use iggy::locking::IggySharedMut;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::topics::consumed_retention::ConsumedRetention;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
@@ -42,8 +44,11 @@ pub struct Topic {
     pub(crate) current_partition_id: AtomicU32,
     pub message_expiry: IggyExpiry,
     pub compression_algorithm: CompressionAlgorithm,
+    pub compression_level: Option<u8>,
     pub max_topic_size: MaxTopicSize,
     pub replication_factor: u8,
+    pub cleanup_policy: CleanupPolicy,
+    pub consumed_retention: ConsumedRetention,
     pub created_at: IggyTimestamp,
 }
 
@@ -71,8 +76,10 @@ impl Topic {
             segments_count_of_parent_stream,
             IggyExpiry::NeverExpire,
             Default::default(),
+            None,
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap()
@@ -91,8 +98,10 @@ impl Topic {
         segments_count_of_parent_stream: Arc<AtomicU32>,
         message_expiry: IggyExpiry,
         compression_algorithm: CompressionAlgorithm,
+        compression_level: Option<u8>,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<Topic, IggyError> {
         let path = config.get_topic_path(stream_id, topic_id);
         let partitions_path = config.get_partitions_path(stream_id, topic_id);
@@ -116,7 +125,10 @@ impl Topic {
             message_expiry: Topic::get_message_expiry(message_expiry, &config),
             max_topic_size: Topic::get_max_topic_size(max_topic_size, &config)?,
             compression_algorithm,
+            compression_level,
             replication_factor,
+            cleanup_policy,
+            consumed_retention: ConsumedRetention::default(),
             config,
             created_at: IggyTimestamp::now(),
         };
@@ -230,6 +242,26 @@ impl Topic {
         }
     }
 
+    /// Returns the compression algorithm applied to the stored messages, the topic one is used
+    /// only if overriding the server default algorithm is allowed.
+    pub fn get_compression_algorithm(&self) -> CompressionAlgorithm {
+        if self.config.compression.allow_override {
+            self.compression_algorithm
+        } else {
+            self.config.compression.default_algorithm
+        }
+    }
+
+    /// Returns the compression level applied to the stored messages, the topic one is used
+    /// only if overriding the server default algorithm is allowed, otherwise the algorithm's default level.
+    pub fn get_compression_level(&self) -> Option<u8> {
+        if self.config.compression.allow_override {
+            self.compression_level
+        } else {
+            None
+        }
+    }
+
     pub fn get_message_expiry(message_expiry: IggyExpiry, config: &SystemConfig) -> IggyExpiry {
         match message_expiry {
             IggyExpiry::ServerDefault => config.segment.message_expiry,
@@ -248,15 +280,18 @@ impl fmt::Display for Topic {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Topic {{ id: {}, stream_id: {}, name: {}, path: {}, partitions: {}, message_expiry: {}, max_topic_size: {}, replication_factor: {} }}",
+            "Topic {{ id: {}, stream_id: {}, name: {}, path: {}, partitions: {}, message_expiry: {}, compression_algorithm: {}, compression_level: {:?}, max_topic_size: {}, replication_factor: {}, cleanup_policy: {} }}",
             self.topic_id,
             self.stream_id,
             self.name,
             self.path,
             self.partitions.len(),
             self.message_expiry,
+            self.compression_algorithm,
+            self.compression_level,
             self.max_topic_size,
             self.replication_factor,
+            self.cleanup_policy,
         )
     }
 }
@@ -305,8 +340,10 @@ mod tests {
             segments_count_of_parent_stream,
             message_expiry,
             compression_algorithm,
+            None,
             max_topic_size,
             replication_factor,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
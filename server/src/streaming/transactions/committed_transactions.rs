@@ -0,0 +1,210 @@
+use crate::streaming::persistence::persister::PersisterKind;
+use crate::streaming::polling_consumer::PollingConsumer;
+use crate::streaming::transactions::COMPONENT;
+use bytes::{Buf, BufMut, BytesMut};
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::fs;
+use tracing::{error, trace};
+
+const PARTITION_ENTRY_SIZE: usize = 12;
+const OFFSET_ENTRY_SIZE: usize = 29;
+const CONSUMER_KIND: u8 = 1;
+const CONSUMER_GROUP_KIND: u8 = 2;
+
+/// The decision to commit the transaction, which is stored before completing the transaction in any of its partitions,
+/// so that the commit can be completed again, if the server has stopped before the transaction was completed everywhere.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommittedTransaction {
+    pub transaction_id: u64,
+    pub partitions: Vec<(u32, u32, u32)>,
+    pub offsets: Vec<CommittedOffset>,
+}
+
+/// The consumer offset stored along with the committed transaction, already resolved to the partition it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommittedOffset {
+    pub stream_id: u32,
+    pub topic_id: u32,
+    pub partition_id: u32,
+    pub consumer: PollingConsumer,
+    pub offset: u64,
+}
+
+#[derive(Debug)]
+pub struct CommittedTransactionsStorage {
+    path: String,
+    persister: Arc<PersisterKind>,
+}
+
+impl CommittedTransactionsStorage {
+    pub fn new(path: String, persister: Arc<PersisterKind>) -> Self {
+        Self { path, persister }
+    }
+
+    pub async fn save(&self, transactions: &[CommittedTransaction]) -> Result<(), IggyError> {
+        let bytes = to_bytes(transactions);
+        self.persister
+            .overwrite_atomically(&self.path, &bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to overwrite committed transactions, path: {}",
+                    self.path
+                )
+            })?;
+        trace!(
+            "Stored {} committed transactions, path: {}",
+            transactions.len(),
+            self.path
+        );
+        Ok(())
+    }
+
+    pub async fn load(&self) -> Result<Vec<CommittedTransaction>, IggyError> {
+        if !Path::new(&self.path).exists() {
+            trace!("Committed transactions file does not exist: {}.", self.path);
+            return Ok(Vec::new());
+        }
+
+        let bytes = fs::read(&self.path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read committed transactions, path: {}",
+                    self.path
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        from_bytes(&bytes).inspect_err(|_| {
+            error!(
+                "Invalid committed transactions file: {}, size: {}.",
+                self.path,
+                bytes.len()
+            );
+        })
+    }
+}
+
+fn to_bytes(transactions: &[CommittedTransaction]) -> BytesMut {
+    let mut bytes = BytesMut::new();
+    for transaction in transactions {
+        bytes.put_u64_le(transaction.transaction_id);
+        bytes.put_u32_le(transaction.partitions.len() as u32);
+        for (stream_id, topic_id, partition_id) in &transaction.partitions {
+            bytes.put_u32_le(*stream_id);
+            bytes.put_u32_le(*topic_id);
+            bytes.put_u32_le(*partition_id);
+        }
+        bytes.put_u32_le(transaction.offsets.len() as u32);
+        for offset in &transaction.offsets {
+            bytes.put_u32_le(offset.stream_id);
+            bytes.put_u32_le(offset.topic_id);
+            bytes.put_u32_le(offset.partition_id);
+            let (kind, first_id, second_id) = match offset.consumer {
+                PollingConsumer::Consumer(consumer_id, partition_id) => {
+                    (CONSUMER_KIND, consumer_id, partition_id)
+                }
+                PollingConsumer::ConsumerGroup(consumer_group_id, member_id) => {
+                    (CONSUMER_GROUP_KIND, consumer_group_id, member_id)
+                }
+            };
+            bytes.put_u8(kind);
+            bytes.put_u32_le(first_id);
+            bytes.put_u32_le(second_id);
+            bytes.put_u64_le(offset.offset);
+        }
+    }
+    bytes
+}
+
+fn from_bytes(mut bytes: &[u8]) -> Result<Vec<CommittedTransaction>, IggyError> {
+    let mut transactions = Vec::new();
+    while bytes.has_remaining() {
+        if bytes.remaining() < 12 {
+            return Err(IggyError::CannotReadFile);
+        }
+        let transaction_id = bytes.get_u64_le();
+        let partitions_count = bytes.get_u32_le() as usize;
+        if bytes.remaining() < partitions_count * PARTITION_ENTRY_SIZE + 4 {
+            return Err(IggyError::CannotReadFile);
+        }
+        let mut partitions = Vec::with_capacity(partitions_count);
+        for _ in 0..partitions_count {
+            partitions.push((bytes.get_u32_le(), bytes.get_u32_le(), bytes.get_u32_le()));
+        }
+        let offsets_count = bytes.get_u32_le() as usize;
+        if bytes.remaining() < offsets_count * OFFSET_ENTRY_SIZE {
+            return Err(IggyError::CannotReadFile);
+        }
+        let mut offsets = Vec::with_capacity(offsets_count);
+        for _ in 0..offsets_count {
+            let stream_id = bytes.get_u32_le();
+            let topic_id = bytes.get_u32_le();
+            let partition_id = bytes.get_u32_le();
+            let kind = bytes.get_u8();
+            let first_id = bytes.get_u32_le();
+            let second_id = bytes.get_u32_le();
+            let consumer = match kind {
+                CONSUMER_KIND => PollingConsumer::Consumer(first_id, second_id),
+                CONSUMER_GROUP_KIND => PollingConsumer::ConsumerGroup(first_id, second_id),
+                _ => return Err(IggyError::CannotReadFile),
+            };
+            offsets.push(CommittedOffset {
+                stream_id,
+                topic_id,
+                partition_id,
+                consumer,
+                offset: bytes.get_u64_le(),
+            });
+        }
+        transactions.push(CommittedTransaction {
+            transaction_id,
+            partitions,
+            offsets,
+        });
+    }
+    Ok(transactions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn committed_transactions_should_be_deserialized_from_bytes() {
+        let transactions = vec![
+            CommittedTransaction {
+                transaction_id: 1,
+                partitions: vec![(1, 2, 3), (1, 2, 4)],
+                offsets: vec![
+                    CommittedOffset {
+                        stream_id: 1,
+                        topic_id: 2,
+                        partition_id: 3,
+                        consumer: PollingConsumer::Consumer(10, 3),
+                        offset: 100,
+                    },
+                    CommittedOffset {
+                        stream_id: 1,
+                        topic_id: 2,
+                        partition_id: 4,
+                        consumer: PollingConsumer::ConsumerGroup(20, 5),
+                        offset: 200,
+                    },
+                ],
+            },
+            CommittedTransaction {
+                transaction_id: 2,
+                partitions: vec![(5, 6, 7)],
+                offsets: Vec::new(),
+            },
+        ];
+
+        let bytes = to_bytes(&transactions);
+        assert_eq!(from_bytes(&bytes).unwrap(), transactions);
+        assert!(from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod committed_transactions;
+pub mod transaction_coordinator;
+pub mod transaction_ids;
+
+pub const COMPONENT: &str = "STREAMING_TRANSACTIONS";
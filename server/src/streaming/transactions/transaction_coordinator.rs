@@ -0,0 +1,334 @@
+use crate::streaming::persistence::persister::PersisterKind;
+use crate::streaming::transactions::committed_transactions::{
+    CommittedTransaction, CommittedTransactionsStorage,
+};
+use crate::streaming::transactions::transaction_ids::TransactionIdsStorage;
+use crate::streaming::transactions::COMPONENT;
+use dashmap::DashMap;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::models::user_info::UserId;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Keeps track of the open transactions and the partitions to which their messages have been appended,
+/// so they can be committed or aborted at once.
+/// The decisions to commit the transactions are stored until the transactions are completed in all of their partitions.
+#[derive(Debug)]
+pub struct TransactionCoordinator {
+    transaction_ids: Mutex<TransactionIds>,
+    transactions: DashMap<u64, Transaction>,
+    committed_transactions: Mutex<Vec<CommittedTransaction>>,
+    storage: CommittedTransactionsStorage,
+    ids_storage: TransactionIdsStorage,
+}
+
+/// The IDs are reserved in blocks, so the reserved bound doesn't have to be stored for each transaction.
+/// The IDs remaining in the block are skipped on the server restart, as the aborted transactions are still
+/// remembered by the partitions, and their IDs must not be reused.
+#[derive(Debug)]
+struct TransactionIds {
+    next: u64,
+    reserved: u64,
+}
+
+const TRANSACTION_IDS_BLOCK_SIZE: u64 = 1000;
+
+#[derive(Debug)]
+pub struct Transaction {
+    pub transaction_id: u64,
+    pub user_id: UserId,
+    pub client_id: u32,
+    pub partitions: HashSet<(u32, u32, u32)>,
+    pub started_at: IggyTimestamp,
+}
+
+impl TransactionCoordinator {
+    pub fn new(path: String, ids_path: String, persister: Arc<PersisterKind>) -> Self {
+        Self {
+            transaction_ids: Mutex::new(TransactionIds {
+                next: 1,
+                reserved: 1,
+            }),
+            transactions: DashMap::new(),
+            committed_transactions: Mutex::new(Vec::new()),
+            storage: CommittedTransactionsStorage::new(path, persister.clone()),
+            ids_storage: TransactionIdsStorage::new(ids_path, persister),
+        }
+    }
+
+    /// Allocates the ID of the new transaction, storing the next reserved block of IDs first, once the current one is used up.
+    pub async fn begin(&self, user_id: UserId, client_id: u32) -> Result<u64, IggyError> {
+        let mut transaction_ids = self.transaction_ids.lock().await;
+        if transaction_ids.next >= transaction_ids.reserved {
+            let reserved = transaction_ids.next + TRANSACTION_IDS_BLOCK_SIZE;
+            self.ids_storage.save(reserved).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to reserve transaction IDs up to: {reserved}")
+            })?;
+            transaction_ids.reserved = reserved;
+        }
+        let transaction_id = transaction_ids.next;
+        transaction_ids.next += 1;
+        drop(transaction_ids);
+        self.transactions.insert(
+            transaction_id,
+            Transaction {
+                transaction_id,
+                user_id,
+                client_id,
+                partitions: HashSet::new(),
+                started_at: IggyTimestamp::now(),
+            },
+        );
+        Ok(transaction_id)
+    }
+
+    /// Ensures that the transaction is open and belongs to the given user.
+    pub fn ensure_open(&self, transaction_id: u64, user_id: UserId) -> Result<(), IggyError> {
+        match self.transactions.get(&transaction_id) {
+            Some(transaction) if transaction.user_id == user_id => Ok(()),
+            _ => Err(IggyError::TransactionNotFound(transaction_id)),
+        }
+    }
+
+    pub fn add_partition(
+        &self,
+        transaction_id: u64,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> Result<(), IggyError> {
+        let mut transaction = self
+            .transactions
+            .get_mut(&transaction_id)
+            .ok_or(IggyError::TransactionNotFound(transaction_id))?;
+        transaction
+            .partitions
+            .insert((stream_id, topic_id, partition_id));
+        Ok(())
+    }
+
+    /// Removes the transaction, which is about to be committed or aborted.
+    pub fn complete(&self, transaction_id: u64, user_id: UserId) -> Result<Transaction, IggyError> {
+        self.ensure_open(transaction_id, user_id)?;
+        self.transactions
+            .remove(&transaction_id)
+            .map(|(_, transaction)| transaction)
+            .ok_or(IggyError::TransactionNotFound(transaction_id))
+    }
+
+    /// Removes all the transactions begun by the given client, e.g. once it has disconnected.
+    pub fn complete_client_transactions(&self, client_id: u32) -> Vec<Transaction> {
+        let transaction_ids = self
+            .transactions
+            .iter()
+            .filter(|transaction| transaction.client_id == client_id)
+            .map(|transaction| transaction.transaction_id)
+            .collect::<Vec<_>>();
+        transaction_ids
+            .into_iter()
+            .filter_map(|transaction_id| self.transactions.remove(&transaction_id))
+            .map(|(_, transaction)| transaction)
+            .collect()
+    }
+
+    /// Removes all the transactions which have been open for longer than the given timeout.
+    pub fn complete_expired_transactions(
+        &self,
+        now: IggyTimestamp,
+        timeout: IggyDuration,
+    ) -> Vec<Transaction> {
+        let transaction_ids = self
+            .transactions
+            .iter()
+            .filter(|transaction| {
+                transaction.started_at.as_micros() + timeout.as_micros() <= now.as_micros()
+            })
+            .map(|transaction| transaction.transaction_id)
+            .collect::<Vec<_>>();
+        transaction_ids
+            .into_iter()
+            .filter_map(|transaction_id| self.transactions.remove(&transaction_id))
+            .map(|(_, transaction)| transaction)
+            .collect()
+    }
+
+    /// Stores the decision to commit the transaction, it must be done before completing the transaction in any partition.
+    pub async fn store_commit(&self, transaction: CommittedTransaction) -> Result<(), IggyError> {
+        let transaction_id = transaction.transaction_id;
+        let mut committed_transactions = self.committed_transactions.lock().await;
+        committed_transactions.push(transaction);
+        if let Err(error) = self.storage.save(&committed_transactions).await {
+            committed_transactions.pop();
+            return Err(error).with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to store commit of transaction with ID: {transaction_id}")
+            });
+        }
+        Ok(())
+    }
+
+    /// Removes the decision to commit the transaction, once it has been completed in all of its partitions.
+    pub async fn remove_commit(&self, transaction_id: u64) -> Result<(), IggyError> {
+        let mut committed_transactions = self.committed_transactions.lock().await;
+        committed_transactions.retain(|transaction| transaction.transaction_id != transaction_id);
+        self.storage
+            .save(&committed_transactions)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to remove commit of transaction with ID: {transaction_id}")
+            })
+    }
+
+    /// Loads the bound of the reserved transaction IDs, so the next transactions get the IDs above it.
+    pub async fn load_transaction_ids(&self) -> Result<(), IggyError> {
+        let Some(reserved) = self.ids_storage.load().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to load reserved transaction IDs")
+        })?
+        else {
+            return Ok(());
+        };
+
+        *self.transaction_ids.lock().await = TransactionIds {
+            next: reserved,
+            reserved,
+        };
+        Ok(())
+    }
+
+    /// Loads the transactions which have been committed, but might not have been completed in all of their partitions yet.
+    pub async fn load_commits(&self) -> Result<Vec<CommittedTransaction>, IggyError> {
+        let transactions = self.storage.load().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to load committed transactions")
+        })?;
+        *self.committed_transactions.lock().await = transactions.clone();
+        Ok(transactions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::persistence::persister::FilePersister;
+    use crate::streaming::polling_consumer::PollingConsumer;
+    use crate::streaming::transactions::committed_transactions::CommittedOffset;
+    use std::path::Path;
+    use std::time::Duration;
+
+    fn create_coordinator(directory: &Path) -> TransactionCoordinator {
+        TransactionCoordinator::new(
+            directory.join("transactions").to_str().unwrap().to_owned(),
+            directory
+                .join("transaction_ids")
+                .to_str()
+                .unwrap()
+                .to_owned(),
+            Arc::new(PersisterKind::File(FilePersister)),
+        )
+    }
+
+    #[tokio::test]
+    async fn transaction_should_be_completed_only_by_its_owner() {
+        let directory = tempfile::tempdir().unwrap();
+        let coordinator = create_coordinator(directory.path());
+        let transaction_id = coordinator.begin(1, 10).await.unwrap();
+        coordinator.add_partition(transaction_id, 1, 2, 3).unwrap();
+
+        assert!(coordinator.ensure_open(transaction_id, 1).is_ok());
+        assert!(coordinator.complete(transaction_id, 2).is_err());
+
+        let transaction = coordinator.complete(transaction_id, 1).unwrap();
+        assert_eq!(transaction.partitions, HashSet::from([(1, 2, 3)]));
+        assert!(coordinator.ensure_open(transaction_id, 1).is_err());
+        assert!(coordinator.add_partition(transaction_id, 1, 2, 3).is_err());
+    }
+
+    #[tokio::test]
+    async fn client_transactions_should_be_completed() {
+        let directory = tempfile::tempdir().unwrap();
+        let coordinator = create_coordinator(directory.path());
+        let first_transaction_id = coordinator.begin(1, 10).await.unwrap();
+        let second_transaction_id = coordinator.begin(1, 20).await.unwrap();
+        assert_ne!(first_transaction_id, second_transaction_id);
+
+        let transactions = coordinator.complete_client_transactions(10);
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].transaction_id, first_transaction_id);
+        assert!(coordinator.ensure_open(second_transaction_id, 1).is_ok());
+    }
+
+    #[tokio::test]
+    async fn expired_transactions_should_be_completed() {
+        let directory = tempfile::tempdir().unwrap();
+        let coordinator = create_coordinator(directory.path());
+        let transaction_id = coordinator.begin(1, 10).await.unwrap();
+        let timeout = IggyDuration::new(Duration::from_secs(60));
+
+        assert!(coordinator
+            .complete_expired_transactions(IggyTimestamp::now(), timeout)
+            .is_empty());
+
+        let now = IggyTimestamp::from(IggyTimestamp::now().as_micros() + timeout.as_micros());
+        let transactions = coordinator.complete_expired_transactions(now, timeout);
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].transaction_id, transaction_id);
+        assert!(coordinator.ensure_open(transaction_id, 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn commits_should_be_stored_until_removed() {
+        let directory = tempfile::tempdir().unwrap();
+        let coordinator = create_coordinator(directory.path());
+        let transaction = CommittedTransaction {
+            transaction_id: 1,
+            partitions: vec![(1, 2, 3)],
+            offsets: vec![CommittedOffset {
+                stream_id: 1,
+                topic_id: 2,
+                partition_id: 3,
+                consumer: PollingConsumer::Consumer(10, 3),
+                offset: 100,
+            }],
+        };
+        coordinator.store_commit(transaction.clone()).await.unwrap();
+
+        let restarted_coordinator = create_coordinator(directory.path());
+        assert_eq!(
+            restarted_coordinator.load_commits().await.unwrap(),
+            vec![transaction]
+        );
+
+        restarted_coordinator.remove_commit(1).await.unwrap();
+        assert!(create_coordinator(directory.path())
+            .load_commits()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn transaction_ids_should_not_be_reused_after_restart() {
+        let directory = tempfile::tempdir().unwrap();
+        let coordinator = create_coordinator(directory.path());
+        coordinator.load_transaction_ids().await.unwrap();
+        let first_transaction_id = coordinator.begin(1, 10).await.unwrap();
+        let second_transaction_id = coordinator.begin(1, 10).await.unwrap();
+        assert_eq!(first_transaction_id, 1);
+        assert_eq!(second_transaction_id, 2);
+
+        let restarted_coordinator = create_coordinator(directory.path());
+        restarted_coordinator.load_transaction_ids().await.unwrap();
+        let transaction_id = restarted_coordinator.begin(1, 10).await.unwrap();
+        assert_eq!(transaction_id, 1 + TRANSACTION_IDS_BLOCK_SIZE);
+
+        for _ in 0..TRANSACTION_IDS_BLOCK_SIZE {
+            restarted_coordinator.begin(1, 10).await.unwrap();
+        }
+        let restarted_coordinator = create_coordinator(directory.path());
+        restarted_coordinator.load_transaction_ids().await.unwrap();
+        let transaction_id = restarted_coordinator.begin(1, 10).await.unwrap();
+        assert_eq!(transaction_id, 1 + 3 * TRANSACTION_IDS_BLOCK_SIZE);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::streaming::persistence::persister::PersisterKind;
+use crate::streaming::transactions::COMPONENT;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::fs;
+use tracing::{error, trace};
+
+/// Stores the upper bound (exclusive) of the transaction IDs reserved so far,
+/// so the IDs allocated after the server restart start above it and are never reused.
+#[derive(Debug)]
+pub struct TransactionIdsStorage {
+    path: String,
+    persister: Arc<PersisterKind>,
+}
+
+impl TransactionIdsStorage {
+    pub fn new(path: String, persister: Arc<PersisterKind>) -> Self {
+        Self { path, persister }
+    }
+
+    pub async fn save(&self, reserved_transaction_id: u64) -> Result<(), IggyError> {
+        self.persister
+            .overwrite_atomically(&self.path, &reserved_transaction_id.to_le_bytes())
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to overwrite reserved transaction ID, path: {}",
+                    self.path
+                )
+            })?;
+        trace!(
+            "Stored reserved transaction ID: {reserved_transaction_id}, path: {}",
+            self.path
+        );
+        Ok(())
+    }
+
+    pub async fn load(&self) -> Result<Option<u64>, IggyError> {
+        if !Path::new(&self.path).exists() {
+            trace!("Transaction IDs file does not exist: {}.", self.path);
+            return Ok(None);
+        }
+
+        let bytes = fs::read(&self.path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read reserved transaction ID, path: {}",
+                    self.path
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        let bytes: [u8; 8] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            error!(
+                "Invalid transaction IDs file: {}, size: {}.",
+                self.path,
+                bytes.len()
+            );
+            IggyError::CannotReadFile
+        })?;
+        Ok(Some(u64::from_le_bytes(bytes)))
+    }
+}
@@ -1,3 +1,4 @@
 pub mod permissioner;
 pub mod permissioner_rules;
+pub mod role;
 pub mod user;
@@ -58,6 +58,22 @@ impl Permissioner {
         }
     }
 
+    /// Resolves the effective permissions of the user, which are its own permissions
+    /// extended with the permissions of all the roles assigned to the user.
+    pub fn resolve_permissions<'a>(
+        permissions: Option<&Permissions>,
+        roles_permissions: impl IntoIterator<Item = &'a Permissions>,
+    ) -> Option<Permissions> {
+        let mut effective_permissions = permissions.cloned();
+        for role_permissions in roles_permissions {
+            match effective_permissions.as_mut() {
+                Some(effective_permissions) => effective_permissions.merge(role_permissions),
+                None => effective_permissions = Some(role_permissions.clone()),
+            }
+        }
+        effective_permissions
+    }
+
     pub fn update_permissions_for_user(
         &mut self,
         user_id: UserId,
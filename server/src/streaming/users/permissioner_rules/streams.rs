@@ -51,6 +51,11 @@ impl Permissioner {
         self.manage_stream(user_id, stream_id)
     }
 
+    /// Setting the quota requires the global permission, as the stream permissions could be used to lift it.
+    pub fn set_stream_quota(&self, user_id: u32) -> Result<(), IggyError> {
+        self.create_stream(user_id)
+    }
+
     fn manage_stream(&self, user_id: u32, stream_id: u32) -> Result<(), IggyError> {
         if let Some(global_permissions) = self.users_permissions.get(&user_id) {
             if global_permissions.manage_streams {
@@ -30,6 +30,38 @@ impl Permissioner {
         self.manager_users(user_id)
     }
 
+    pub fn set_user_quota(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+
+    pub fn get_role(&self, user_id: u32) -> Result<(), IggyError> {
+        self.read_users(user_id)
+    }
+
+    pub fn get_roles(&self, user_id: u32) -> Result<(), IggyError> {
+        self.read_users(user_id)
+    }
+
+    pub fn create_role(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+
+    pub fn update_role(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+
+    pub fn delete_role(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+
+    pub fn assign_role(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+
+    pub fn unassign_role(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+
     fn manager_users(&self, user_id: u32) -> Result<(), IggyError> {
         if let Some(global_permissions) = self.users_permissions.get(&user_id) {
             if global_permissions.manage_users {
@@ -0,0 +1,27 @@
+use ahash::AHashSet;
+use iggy::models::permissions::Permissions;
+use iggy::models::role::RoleId;
+use iggy::models::user_info::UserId;
+use iggy::utils::timestamp::IggyTimestamp;
+
+/// The reusable set of permissions, which are granted to all the users the role is assigned to.
+#[derive(Debug)]
+pub struct Role {
+    pub id: RoleId,
+    pub name: String,
+    pub created_at: IggyTimestamp,
+    pub permissions: Option<Permissions>,
+    pub users: AHashSet<UserId>,
+}
+
+impl Role {
+    pub fn new(id: RoleId, name: &str, permissions: Option<Permissions>) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            created_at: IggyTimestamp::now(),
+            permissions,
+            users: AHashSet::new(),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
 use crate::streaming::utils::crypto;
+use crate::streaming::utils::throughput::ThroughputWindow;
 use ahash::AHashMap;
+use iggy::models::quota::Quota;
 use iggy::models::user_status::UserStatus;
 use iggy::models::{permissions::Permissions, user_info::UserId};
 use iggy::users::defaults::*;
@@ -15,6 +17,9 @@ pub struct User {
     pub created_at: IggyTimestamp,
     pub permissions: Option<Permissions>,
     pub personal_access_tokens: AHashMap<String, PersonalAccessToken>,
+    pub quota: Option<Quota>,
+    pub produced_bytes: ThroughputWindow,
+    pub consumed_bytes: ThroughputWindow,
 }
 
 impl Default for User {
@@ -27,6 +32,9 @@ impl Default for User {
             created_at: IggyTimestamp::now(),
             permissions: None,
             personal_access_tokens: AHashMap::new(),
+            quota: None,
+            produced_bytes: ThroughputWindow::default(),
+            consumed_bytes: ThroughputWindow::default(),
         }
     }
 }
@@ -70,6 +78,9 @@ impl User {
             status,
             permissions,
             personal_access_tokens: AHashMap::new(),
+            quota: None,
+            produced_bytes: ThroughputWindow::default(),
+            consumed_bytes: ThroughputWindow::default(),
         }
     }
 
@@ -15,7 +15,7 @@ pub async fn overwrite(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new()
         .create(true)
         .write(true)
-        .truncate(false)
+        .truncate(true)
         .open(path)
         .await
 }
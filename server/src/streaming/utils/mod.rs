@@ -3,3 +3,4 @@ pub mod file;
 pub mod hash;
 pub mod head_tail_buf;
 pub mod random_id;
+pub mod throughput;
@@ -0,0 +1,75 @@
+use iggy::utils::timestamp::IggyTimestamp;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const MICROS_PER_SECOND: u64 = 1_000_000;
+
+/// Counts the bytes transferred within the current one-second window, used to enforce the throughput quotas.
+#[derive(Debug, Default)]
+pub struct ThroughputWindow {
+    second: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl ThroughputWindow {
+    /// Returns true if the bytes transferred within the current second reached the limit (0 means unlimited).
+    pub fn is_exceeded(&self, limit: u64) -> bool {
+        self.is_exceeded_at(limit, Self::current_second())
+    }
+
+    /// Adds the bytes to the current second, starting a new window if the previous one has passed.
+    pub fn record(&self, bytes: u64) {
+        self.record_at(bytes, Self::current_second())
+    }
+
+    fn is_exceeded_at(&self, limit: u64, second: u64) -> bool {
+        limit > 0
+            && self.second.load(Ordering::Acquire) == second
+            && self.bytes.load(Ordering::Acquire) >= limit
+    }
+
+    fn record_at(&self, bytes: u64, second: u64) {
+        let current_second = self.second.load(Ordering::Acquire);
+        if current_second != second
+            && self
+                .second
+                .compare_exchange(current_second, second, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+        {
+            self.bytes.store(bytes, Ordering::Release);
+            return;
+        }
+
+        self.bytes.fetch_add(bytes, Ordering::AcqRel);
+    }
+
+    fn current_second() -> u64 {
+        IggyTimestamp::now().as_micros() / MICROS_PER_SECOND
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_exceeded_only_after_reaching_the_limit_within_the_same_second() {
+        let window = ThroughputWindow::default();
+        window.record_at(600, 10);
+        assert!(!window.is_exceeded_at(1000, 10));
+
+        window.record_at(400, 10);
+        assert!(window.is_exceeded_at(1000, 10));
+        assert!(!window.is_exceeded_at(0, 10));
+    }
+
+    #[test]
+    fn should_start_new_window_in_the_next_second() {
+        let window = ThroughputWindow::default();
+        window.record_at(1000, 10);
+        assert!(window.is_exceeded_at(1000, 10));
+        assert!(!window.is_exceeded_at(1000, 11));
+
+        window.record_at(100, 11);
+        assert!(!window.is_exceeded_at(1000, 11));
+    }
+}
@@ -3,15 +3,22 @@ use crate::command::ServerCommand;
 use crate::server_error::ConnectionError;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
+use crate::tcp::sender;
+use crate::tcp::tcp_pipelined_sender::{response_frame, PipelinedResponse};
 use bytes::{BufMut, BytesMut};
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::error::IggyError;
+use iggy::system::set_protocol_version::ProtocolVersion;
 use iggy::validatable::Validatable;
 use std::io::ErrorKind;
 use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, watch, Semaphore};
 use tracing::{debug, error, info};
 
 const INITIAL_BYTES_LENGTH: usize = 4;
+const PIPELINED_INITIAL_BYTES_LENGTH: usize = 8;
+const MAX_IN_FLIGHT_REQUESTS: usize = 1024;
 
 pub(crate) async fn handle_connection(
     session: Arc<Session>,
@@ -58,11 +65,173 @@ pub(crate) async fn handle_connection(
             continue;
         }
 
+        if let ServerCommand::SetProtocolVersion(command) = command {
+            debug!("Received a TCP command to set the protocol version: {command}");
+            sender.send_empty_ok_response().await?;
+            if command.version == ProtocolVersion::V2 {
+                info!("Session: {session} has switched to the pipelined protocol.");
+                return handle_pipelined_connection(session, sender, system).await;
+            }
+            continue;
+        }
+
         debug!("Received a TCP command: {command}, payload size: {length}");
         command::handle(command, sender, &session, system.clone()).await?;
     }
 }
 
+async fn handle_pipelined_connection(
+    session: Arc<Session>,
+    sender: &mut SenderKind,
+    system: SharedSystem,
+) -> Result<(), ConnectionError> {
+    match sender {
+        SenderKind::Tcp(sender) => {
+            handle_pipelined_stream(&mut sender.stream, session, system).await
+        }
+        SenderKind::TcpTls(sender) => {
+            handle_pipelined_stream(&mut sender.stream, session, system).await
+        }
+        _ => Err(ConnectionError::from(IggyError::FeatureUnavailable)),
+    }
+}
+
+// The requests are read one after another. The commands changing the state are handled one at a time,
+// in the order they were received (e.g. the sent messages are appended in order), while the read-only ones
+// are handled concurrently, once the preceding state changes have completed. The responses are written
+// by a single writer in the order of completion, so a slow read doesn't block the other ones.
+async fn handle_pipelined_stream<T>(
+    stream: &mut T,
+    session: Arc<Session>,
+    system: SharedSystem,
+) -> Result<(), ConnectionError>
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+{
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    let (responses, pending_responses) = mpsc::channel(MAX_IN_FLIGHT_REQUESTS);
+    tokio::try_join!(
+        read_pipelined_requests(&mut reader, responses, session, system),
+        write_pipelined_responses(&mut writer, pending_responses)
+    )?;
+    Ok(())
+}
+
+async fn read_pipelined_requests<R>(
+    reader: &mut R,
+    responses: mpsc::Sender<PipelinedResponse>,
+    session: Arc<Session>,
+    system: SharedSystem,
+) -> Result<(), ConnectionError>
+where
+    R: AsyncRead + Unpin,
+{
+    let in_flight_requests = Arc::new(Semaphore::new(MAX_IN_FLIGHT_REQUESTS));
+    // The number of the received state changing commands, and of the completed ones, as each of them waits for
+    // the preceding one, so they're handled in order, and the read-only commands wait for all the preceding ones.
+    let mut received_changes = 0u64;
+    let completed_changes = Arc::new(watch::Sender::new(0u64));
+    let mut initial_buffer = [0u8; PIPELINED_INITIAL_BYTES_LENGTH];
+    loop {
+        // Any failure to read the frame breaks the framing, so the connection is closed.
+        sender::read(reader, &mut initial_buffer).await?;
+        let request_id = u32::from_le_bytes(
+            initial_buffer[..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let length = u32::from_le_bytes(
+            initial_buffer[4..]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        debug!("Received a pipelined TCP request: {request_id}, length: {length}");
+        let mut command_buffer = BytesMut::with_capacity(length as usize);
+        command_buffer.put_bytes(0, length as usize);
+        sender::read(reader, &mut command_buffer).await?;
+        let command = match ServerCommand::from_bytes(command_buffer.freeze()) {
+            Ok(command) => command,
+            Err(_) => {
+                send_pipelined_error(&responses, request_id, IggyError::InvalidCommand).await?;
+                continue;
+            }
+        };
+        if let Err(error) = command.validate() {
+            error!("Command validation failed: {error}");
+            send_pipelined_error(&responses, request_id, error).await?;
+            continue;
+        }
+        if let ServerCommand::SetProtocolVersion(_) = command {
+            send_pipelined_error(&responses, request_id, IggyError::InvalidCommand).await?;
+            continue;
+        }
+
+        debug!("Received a pipelined TCP command: {command}, request: {request_id}, payload size: {length}");
+        let permit = in_flight_requests
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("In-flight requests semaphore should never be closed");
+        let (preceding_changes, change) = if command.is_read_only() {
+            (received_changes, None)
+        } else {
+            received_changes += 1;
+            (received_changes - 1, Some(received_changes))
+        };
+        let mut sender = SenderKind::get_tcp_pipelined_sender(request_id, responses.clone());
+        let responses = responses.clone();
+        let session = session.clone();
+        let system = system.clone();
+        let completed_changes = completed_changes.clone();
+        tokio::spawn(async move {
+            let mut completed = completed_changes.subscribe();
+            // The sender is owned by the waiting tasks as well, so it's never dropped while they wait.
+            let _ = completed
+                .wait_for(|completed| *completed >= preceding_changes)
+                .await;
+            if let Err(error) = command::handle(command, &mut sender, &session, system).await {
+                // The error response has already been sent, the error closes the connection.
+                let _ = responses.send(Err(error)).await;
+            }
+            // The failed command completes as well, otherwise the following ones would never be handled.
+            if let Some(change) = change {
+                completed_changes.send_replace(change);
+            }
+            drop(permit);
+        });
+    }
+}
+
+async fn write_pipelined_responses<W>(
+    writer: &mut W,
+    mut pending_responses: mpsc::Receiver<PipelinedResponse>,
+) -> Result<(), ConnectionError>
+where
+    W: AsyncWrite + Unpin,
+{
+    while let Some(response) = pending_responses.recv().await {
+        let frame = response?;
+        writer
+            .write_all(&frame)
+            .await
+            .map_err(|_| IggyError::TcpError)?;
+    }
+    Ok(())
+}
+
+async fn send_pipelined_error(
+    responses: &mpsc::Sender<PipelinedResponse>,
+    request_id: u32,
+    error: IggyError,
+) -> Result<(), ConnectionError> {
+    let frame = response_frame(request_id, &error.as_code().to_le_bytes(), &[]);
+    responses
+        .send(Ok(frame))
+        .await
+        .map_err(|_| IggyError::ConnectionClosed)?;
+    Ok(())
+}
+
 pub(crate) fn handle_error(error: ConnectionError) {
     match error {
         ConnectionError::IoError(error) => match error.kind() {
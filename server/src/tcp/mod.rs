@@ -1,6 +1,7 @@
 pub mod connection_handler;
 pub mod sender;
 pub mod tcp_listener;
+pub mod tcp_pipelined_sender;
 pub mod tcp_sender;
 pub mod tcp_server;
 mod tcp_socket;
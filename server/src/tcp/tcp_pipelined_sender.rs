@@ -0,0 +1,66 @@
+use crate::binary::sender::Sender;
+use crate::server_error::ServerError;
+use crate::tcp::sender;
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy::error::IggyError;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+/// The response frame of the pipelined protocol, or the error which requires closing the connection.
+pub(crate) type PipelinedResponse = Result<Bytes, IggyError>;
+
+/// Sends the responses of the pipelined protocol for a single request, which are written to the connection
+/// by the writer of the connection handler, as the requests can be completed in any order.
+#[derive(Debug)]
+pub struct TcpPipelinedSender {
+    pub(crate) request_id: u32,
+    pub(crate) responses: mpsc::Sender<PipelinedResponse>,
+}
+
+impl TcpPipelinedSender {
+    async fn send_response(&mut self, status: &[u8], payload: &[u8]) -> Result<(), IggyError> {
+        debug!(
+            "Sending pipelined response for request: {} with status: {:?}...",
+            self.request_id, status
+        );
+        let frame = response_frame(self.request_id, status, payload);
+        self.responses
+            .send(Ok(frame))
+            .await
+            .map_err(|_| IggyError::ConnectionClosed)
+    }
+}
+
+/// Builds the response frame of the pipelined protocol: `[request_id][status][length][payload]`.
+pub(crate) fn response_frame(request_id: u32, status: &[u8], payload: &[u8]) -> Bytes {
+    let mut frame = BytesMut::with_capacity(12 + payload.len());
+    frame.put_u32_le(request_id);
+    frame.put_slice(status);
+    frame.put_u32_le(payload.len() as u32);
+    frame.put_slice(payload);
+    frame.freeze()
+}
+
+impl Sender for TcpPipelinedSender {
+    async fn read(&mut self, _buffer: &mut [u8]) -> Result<usize, IggyError> {
+        // The requests are read by the connection handler, which owns the reading half of the connection.
+        Err(IggyError::FeatureUnavailable)
+    }
+
+    async fn send_empty_ok_response(&mut self) -> Result<(), IggyError> {
+        self.send_response(sender::STATUS_OK, &[]).await
+    }
+
+    async fn send_ok_response(&mut self, payload: &[u8]) -> Result<(), IggyError> {
+        self.send_response(sender::STATUS_OK, payload).await
+    }
+
+    async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError> {
+        self.send_response(&error.as_code().to_le_bytes(), &[])
+            .await
+    }
+
+    async fn shutdown(&mut self) -> Result<(), ServerError> {
+        Ok(())
+    }
+}
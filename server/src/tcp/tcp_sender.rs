@@ -1,13 +1,96 @@
 use crate::binary::sender::Sender;
+use crate::streaming::models::messages::LogRegion;
 use crate::tcp::COMPONENT;
 use crate::{server_error::ServerError, tcp::sender};
 use error_set::ErrContext;
 use iggy::error::IggyError;
 use tokio::{io::AsyncWriteExt, net::TcpStream};
+use tracing::debug;
 
 #[derive(Debug)]
 pub struct TcpSender {
     pub(crate) stream: TcpStream,
+    /// Whether the polled batches can be sent directly from the segment log files.
+    pub(crate) zero_copy: bool,
+}
+
+impl TcpSender {
+    /// Sends the OK response consisting of the header built in userspace, followed by the regions
+    /// of the segment log files, which are sent directly from the files to the socket.
+    pub async fn send_ok_response_with_regions(
+        &mut self,
+        header: &[u8],
+        regions: &[LogRegion],
+    ) -> Result<(), IggyError> {
+        let length = header.len() as u64 + regions.iter().map(|region| region.length).sum::<u64>();
+        let length = u32::try_from(length).map_err(|_| IggyError::TcpError)?;
+        debug!("Sending response with {} file regions...", regions.len());
+        self.stream
+            .write_all(&[sender::STATUS_OK, &length.to_le_bytes(), header].concat())
+            .await
+            .map_err(|_| IggyError::TcpError)?;
+        for region in regions {
+            send_region(&mut self.stream, region)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to send file region at position: {}, length: {}",
+                        region.position, region.length
+                    )
+                })
+                .map_err(|_| IggyError::TcpError)?;
+        }
+        debug!("Sent response with {} file regions", regions.len());
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn send_region(stream: &mut TcpStream, region: &LogRegion) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd;
+    use tokio::io::Interest;
+
+    let socket_fd = stream.as_raw_fd();
+    let file_fd = region.file.as_raw_fd();
+    let end = region.position + region.length;
+    let mut offset = region.position as libc::off_t;
+    while (offset as u64) < end {
+        let remaining = (end - offset as u64) as usize;
+        stream.writable().await?;
+        let result = stream.try_io(Interest::WRITABLE, || {
+            // SAFETY: both descriptors remain open for the duration of the call, as the stream
+            // and the file are borrowed, and the kernel only updates the offset on success.
+            let sent = unsafe { libc::sendfile(socket_fd, file_fd, &mut offset, remaining) };
+            if sent < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(sent as usize)
+        });
+        match result {
+            Ok(0) => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            Ok(_) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(())
+}
+
+// Without sendfile, the region is read into memory, which is still cheaper than deserializing the messages.
+#[cfg(not(target_os = "linux"))]
+async fn send_region(stream: &mut TcpStream, region: &LogRegion) -> std::io::Result<()> {
+    use std::os::unix::fs::FileExt;
+
+    let file = region.file.clone();
+    let position = region.position;
+    let length = region.length as usize;
+    let buffer = tokio::task::spawn_blocking(move || {
+        let mut buffer = vec![0u8; length];
+        file.read_exact_at(&mut buffer, position)?;
+        Ok::<_, std::io::Error>(buffer)
+    })
+    .await??;
+    stream.write_all(&buffer).await
 }
 
 impl Sender for TcpSender {
@@ -1,39 +1,45 @@
 use crate::binary::sender::SenderKind;
+use crate::configs::client_auth::ClientAuthConfig;
 use crate::configs::tcp::TcpTlsConfig;
 use crate::streaming::clients::client_manager::Transport;
 use crate::streaming::systems::system::SharedSystem;
 use crate::tcp::connection_handler::{handle_connection, handle_error};
+use crate::tls::client_certificate::ClientCertificateAuthenticator;
+use crate::tls::server_config;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tokio::net::TcpSocket;
 use tokio::sync::oneshot;
-use tokio_native_tls::native_tls;
-use tokio_native_tls::native_tls::Identity;
+use tokio_rustls::TlsAcceptor;
 use tracing::{error, info};
 
 pub(crate) async fn start(
     address: &str,
     config: TcpTlsConfig,
+    client_auth: ClientAuthConfig,
     socket: TcpSocket,
     system: SharedSystem,
 ) -> SocketAddr {
     let address = address.to_string();
     let (tx, rx) = oneshot::channel();
     tokio::spawn(async move {
-        let certificate = std::fs::read(config.certificate.clone());
-        if certificate.is_err() {
-            panic!("Unable to read certificate file.");
-        }
+        let (certificate_chain, key) =
+            match server_config::load_pkcs12(&config.certificate, &config.password) {
+                Ok(identity) => identity,
+                Err(error) => panic!("Unable to create identity from certificate. {error}"),
+            };
 
-        let identity = Identity::from_pkcs12(&certificate.unwrap(), &config.password);
-        if identity.is_err() {
-            panic!("Unable to create identity from certificate.");
+        let tls_config = server_config::create_server_config(
+            certificate_chain,
+            key,
+            config.client_auth.then_some(&client_auth),
+        );
+        if let Err(error) = tls_config {
+            panic!("Unable to create TLS config. {error}");
         }
 
-        let acceptor = tokio_native_tls::TlsAcceptor::from(
-            native_tls::TlsAcceptor::builder(identity.unwrap())
-                .build()
-                .unwrap(),
-        );
+        let acceptor = TlsAcceptor::from(Arc::new(tls_config.unwrap()));
+        let authenticator = Arc::new(ClientCertificateAuthenticator::new(client_auth.users));
 
         let addr = address.parse();
         if addr.is_err() {
@@ -71,10 +77,22 @@ pub(crate) async fn start(
 
                     let client_id = session.client_id;
                     let acceptor = acceptor.clone();
-                    let stream = acceptor.accept(stream).await.unwrap();
+                    let authenticator = authenticator.clone();
                     let system = system.clone();
-                    let mut sender = SenderKind::get_tcp_tls_sender(stream);
                     tokio::spawn(async move {
+                        let stream = match acceptor.accept(stream).await {
+                            Ok(stream) => stream,
+                            Err(error) => {
+                                error!("Failed to establish TLS connection for client: {client_id}, address: {address}. {error}");
+                                system.read().await.delete_client(client_id).await;
+                                return;
+                            }
+                        };
+
+                        authenticator
+                            .authenticate(stream.get_ref().1.peer_certificates(), &system, &session)
+                            .await;
+                        let mut sender = SenderKind::get_tcp_tls_sender(stream);
                         if let Err(error) =
                             handle_connection(session, &mut sender, system.clone()).await
                         {
@@ -0,0 +1,189 @@
+use crate::configs::client_auth::{ClientCertificateIdentityKind, ClientCertificateUserConfig};
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use crate::tls::COMPONENT;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use rustls::pki_types::CertificateDer;
+use tracing::{info, warn};
+use x509_parser::certificate::X509Certificate;
+use x509_parser::extensions::GeneralName;
+use x509_parser::prelude::FromDer;
+
+/// The identities of the verified client certificate, which can be mapped to the user.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ClientCertificate {
+    pub common_names: Vec<String>,
+    pub dns_names: Vec<String>,
+    pub uris: Vec<String>,
+    pub emails: Vec<String>,
+}
+
+impl ClientCertificate {
+    pub fn from_der(certificate: &[u8]) -> Result<Self, IggyError> {
+        let (_, certificate) = X509Certificate::from_der(certificate)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to parse client certificate")
+            })
+            .map_err(|_| IggyError::InvalidTlsCertificate)?;
+
+        let mut client_certificate = ClientCertificate {
+            common_names: certificate
+                .subject()
+                .iter_common_name()
+                .filter_map(|common_name| common_name.as_str().ok())
+                .map(|common_name| common_name.to_owned())
+                .collect(),
+            ..Default::default()
+        };
+        let alternative_names = certificate
+            .subject_alternative_name()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to parse client certificate subject alternative names")
+            })
+            .map_err(|_| IggyError::InvalidTlsCertificate)?;
+        let Some(alternative_names) = alternative_names else {
+            return Ok(client_certificate);
+        };
+
+        for name in &alternative_names.value.general_names {
+            match name {
+                GeneralName::DNSName(name) => client_certificate.dns_names.push(name.to_string()),
+                GeneralName::URI(uri) => client_certificate.uris.push(uri.to_string()),
+                GeneralName::RFC822Name(email) => client_certificate.emails.push(email.to_string()),
+                _ => {}
+            }
+        }
+        Ok(client_certificate)
+    }
+
+    fn has_identity(&self, kind: ClientCertificateIdentityKind, value: &str) -> bool {
+        let identities = match kind {
+            ClientCertificateIdentityKind::CommonName => &self.common_names,
+            ClientCertificateIdentityKind::DnsName => &self.dns_names,
+            ClientCertificateIdentityKind::Uri => &self.uris,
+            ClientCertificateIdentityKind::Email => &self.emails,
+        };
+        identities.iter().any(|identity| identity == value)
+    }
+}
+
+/// Maps the verified client certificates to the users, so the clients can be authenticated without the credentials.
+#[derive(Debug, Default)]
+pub struct ClientCertificateAuthenticator {
+    users: Vec<ClientCertificateUserConfig>,
+}
+
+impl ClientCertificateAuthenticator {
+    pub fn new(users: Vec<ClientCertificateUserConfig>) -> Self {
+        Self { users }
+    }
+
+    /// Returns the username mapped to the first identity of the certificate matching the configured users.
+    pub fn get_username(&self, certificate: &ClientCertificate) -> Option<&str> {
+        self.users
+            .iter()
+            .find(|user| certificate.has_identity(user.kind, &user.value))
+            .map(|user| user.username.as_str())
+    }
+
+    /// Returns the username mapped to the end-entity certificate presented by the client, if any.
+    pub fn get_username_from_chain(&self, chain: Option<&[CertificateDer]>) -> Option<String> {
+        let certificate = chain?.first()?;
+        let certificate = match ClientCertificate::from_der(certificate) {
+            Ok(certificate) => certificate,
+            Err(error) => {
+                warn!("Cannot read the client certificate: {error}");
+                return None;
+            }
+        };
+
+        let username = self.get_username(&certificate);
+        if username.is_none() {
+            warn!("Client certificate: {certificate:?} isn't mapped to any user.");
+        }
+        username.map(|username| username.to_owned())
+    }
+
+    /// Authenticates the session as the user mapped to the certificate presented by the client.
+    /// The client without the mapped certificate stays unauthenticated, so it has to log in as usual.
+    pub async fn authenticate(
+        &self,
+        chain: Option<&[CertificateDer<'_>]>,
+        system: &SharedSystem,
+        session: &Session,
+    ) {
+        let Some(username) = self.get_username_from_chain(chain) else {
+            return;
+        };
+
+        let system = system.read().await;
+        match system
+            .login_user_with_credentials(&username, None, Some(session))
+            .await
+        {
+            Ok(user) => info!(
+                "Authenticated client: {} as user: {username} with ID: {} using the client certificate.",
+                session.client_id, user.id
+            ),
+            Err(error) => warn!(
+                "Cannot authenticate client: {} as user: {username} using the client certificate: {error}",
+                session.client_id
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{CertificateParams, DnType, KeyPair, SanType};
+    use std::str::FromStr;
+
+    fn create_certificate() -> ClientCertificate {
+        let mut params = CertificateParams::new(vec!["orders.iggy.local".to_owned()]).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "orders-service");
+        params.subject_alt_names.push(SanType::URI(
+            "spiffe://iggy/orders".to_string().try_into().unwrap(),
+        ));
+        params.subject_alt_names.push(SanType::Rfc822Name(
+            "orders@iggy.local".to_string().try_into().unwrap(),
+        ));
+        let certificate = params.self_signed(&KeyPair::generate().unwrap()).unwrap();
+        ClientCertificate::from_der(certificate.der()).unwrap()
+    }
+
+    #[test]
+    fn identities_should_be_read_from_certificate() {
+        let certificate = create_certificate();
+        assert_eq!(certificate.common_names, vec!["orders-service"]);
+        assert_eq!(certificate.dns_names, vec!["orders.iggy.local"]);
+        assert_eq!(certificate.uris, vec!["spiffe://iggy/orders"]);
+        assert_eq!(certificate.emails, vec!["orders@iggy.local"]);
+    }
+
+    #[test]
+    fn username_should_be_mapped_from_matching_identity() {
+        let certificate = create_certificate();
+        let authenticator = ClientCertificateAuthenticator::new(vec![
+            ClientCertificateUserConfig::from_str("cn:analytics-service=analytics").unwrap(),
+            ClientCertificateUserConfig::from_str("uri:spiffe://iggy/orders=orders").unwrap(),
+        ]);
+
+        assert_eq!(authenticator.get_username(&certificate), Some("orders"));
+    }
+
+    #[test]
+    fn username_should_not_be_mapped_given_identity_of_other_kind() {
+        let certificate = create_certificate();
+        let authenticator =
+            ClientCertificateAuthenticator::new(vec![ClientCertificateUserConfig::from_str(
+                "dns:orders-service=orders",
+            )
+            .unwrap()]);
+
+        assert_eq!(authenticator.get_username(&certificate), None);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod client_certificate;
+pub mod server_config;
+
+pub const COMPONENT: &str = "TLS";
@@ -0,0 +1,142 @@
+use crate::configs::client_auth::ClientAuthConfig;
+use crate::tls::COMPONENT;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use openssl::pkcs12::Pkcs12;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::server::danger::ClientCertVerifier;
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use std::sync::Arc;
+
+pub type CertificateChain = Vec<CertificateDer<'static>>;
+
+/// Returns the crypto provider used by all the TLS servers, regardless of the process-wide default.
+pub fn crypto_provider() -> Arc<CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+/// Creates the TLS server config, which verifies the client certificates if the client auth config is provided.
+pub fn create_server_config(
+    certificate_chain: CertificateChain,
+    key: PrivateKeyDer<'static>,
+    client_auth: Option<&ClientAuthConfig>,
+) -> Result<ServerConfig, IggyError> {
+    let builder = ServerConfig::builder_with_provider(crypto_provider())
+        .with_safe_default_protocol_versions()
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to set TLS protocol versions")
+        })
+        .map_err(|_| IggyError::InvalidConfiguration)?;
+    let builder = match client_auth {
+        Some(config) => builder.with_client_cert_verifier(create_client_verifier(config)?),
+        None => builder.with_no_client_auth(),
+    };
+    builder
+        .with_single_cert(certificate_chain, key)
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to use TLS certificate")
+        })
+        .map_err(|_| IggyError::InvalidTlsCertificate)
+}
+
+/// Creates the verifier of the client certificates issued by the configured CA.
+pub fn create_client_verifier(
+    config: &ClientAuthConfig,
+) -> Result<Arc<dyn ClientCertVerifier>, IggyError> {
+    let mut roots = RootCertStore::empty();
+    for certificate in load_pem_certificates(&config.ca_file)? {
+        roots
+            .add(certificate)
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to add CA certificate from: {}",
+                    config.ca_file
+                )
+            })
+            .map_err(|_| IggyError::FailedToAddCertificate)?;
+    }
+
+    let mut builder =
+        WebPkiClientVerifier::builder_with_provider(Arc::new(roots), crypto_provider());
+    if !config.required {
+        builder = builder.allow_unauthenticated();
+    }
+    builder
+        .build()
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to create client certificate verifier")
+        })
+        .map_err(|_| IggyError::InvalidTlsCertificate)
+}
+
+pub fn load_pem_certificates(cert_file: &str) -> Result<CertificateChain, IggyError> {
+    let certificates = CertificateDer::pem_file_iter(cert_file)
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to open cert file: {cert_file}")
+        })
+        .map_err(|_| IggyError::InvalidTlsCertificatePath)?
+        .collect::<Result<Vec<_>, _>>()
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to read cert file: {cert_file}")
+        })
+        .map_err(|_| IggyError::InvalidTlsCertificate)?;
+    if certificates.is_empty() {
+        return Err(IggyError::InvalidTlsCertificate);
+    }
+
+    Ok(certificates)
+}
+
+pub fn load_pem_key(key_file: &str) -> Result<PrivateKeyDer<'static>, IggyError> {
+    PrivateKeyDer::from_pem_file(key_file)
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to read key file: {key_file}")
+        })
+        .map_err(|_| IggyError::InvalidTlsPrivateKey)
+}
+
+/// Loads the certificate chain and the private key from the password-protected PKCS #12 (PFX) file.
+pub fn load_pkcs12(
+    file: &str,
+    password: &str,
+) -> Result<(CertificateChain, PrivateKeyDer<'static>), IggyError> {
+    let pkcs12 = std::fs::read(file)
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to read certificate file: {file}")
+        })
+        .map_err(|_| IggyError::InvalidTlsCertificatePath)?;
+    let pkcs12 = Pkcs12::from_der(&pkcs12)
+        .and_then(|pkcs12| pkcs12.parse2(password))
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to parse certificate file: {file}")
+        })
+        .map_err(|_| IggyError::InvalidTlsCertificate)?;
+
+    let (Some(certificate), Some(key)) = (pkcs12.cert, pkcs12.pkey) else {
+        return Err(IggyError::InvalidTlsCertificate);
+    };
+
+    let mut certificate_chain = vec![certificate];
+    certificate_chain.extend(pkcs12.ca.into_iter().flatten());
+    let certificate_chain = certificate_chain
+        .iter()
+        .map(|certificate| certificate.to_der().map(CertificateDer::from))
+        .collect::<Result<Vec<_>, _>>()
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to encode certificate from: {file}")
+        })
+        .map_err(|_| IggyError::InvalidTlsCertificate)?;
+    let key = key
+        .private_key_to_pkcs8()
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to encode private key from: {file}")
+        })
+        .map_err(|_| IggyError::InvalidTlsPrivateKey)?;
+    Ok((
+        certificate_chain,
+        PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key)),
+    ))
+}
@@ -3,6 +3,7 @@ use iggy::clients::client::IggyClient;
 use iggy::error::IggyError;
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use rand::Rng;
@@ -39,8 +40,10 @@ async fn create_topics(client: &IggyClient) -> Result<(), IggyError> {
                 Default::default(),
                 None,
                 None,
+                None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
 
@@ -52,8 +55,10 @@ async fn create_topics(client: &IggyClient) -> Result<(), IggyError> {
                 Default::default(),
                 None,
                 None,
+                None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
 
@@ -65,8 +70,10 @@ async fn create_topics(client: &IggyClient) -> Result<(), IggyError> {
                 Default::default(),
                 None,
                 None,
+                None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
 
@@ -78,8 +85,10 @@ async fn create_topics(client: &IggyClient) -> Result<(), IggyError> {
                 Default::default(),
                 None,
                 None,
+                None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
 
@@ -91,8 +100,10 @@ async fn create_topics(client: &IggyClient) -> Result<(), IggyError> {
                 Default::default(),
                 None,
                 None,
+                None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
     }
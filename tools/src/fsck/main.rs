@@ -0,0 +1,237 @@
+mod segment;
+
+use crate::segment::{check_segment, repair_segment, SegmentReport, TailRepair};
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use server::streaming::segments::{INDEX_EXTENSION, LOG_EXTENSION};
+use std::path::{Path, PathBuf};
+use tracing::{error, info, warn};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Registry};
+
+/// Maximum number of the offset gaps printed for a single segment.
+const MAX_PRINTED_GAPS: usize = 10;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Verifies the partition segments and indexes of the stopped server", long_about = None)]
+pub struct FsckArgs {
+    /// Path to the server system directory
+    #[arg(long, default_value = "local_data")]
+    pub path: String,
+
+    /// Truncate the partial writes at the end of the last segments, quarantine any other invalid data
+    /// and regenerate the invalid indexes
+    #[arg(long, default_value_t = false)]
+    pub repair: bool,
+}
+
+#[derive(Debug, Default)]
+struct Summary {
+    partitions_count: u64,
+    segments_count: u64,
+    messages_count: u64,
+    gaps_count: u64,
+    invalid_segments_count: u64,
+    repaired_segments_count: u64,
+    quarantined_segments_count: u64,
+    lost_bytes: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = FsckArgs::parse();
+    Registry::default()
+        .with(tracing_subscriber::fmt::layer())
+        .with(EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new("INFO")))
+        .init();
+
+    let streams_path = Path::new(&args.path).join("streams");
+    if !streams_path.exists() {
+        bail!(
+            "Streams directory: {} does not exist.",
+            streams_path.display()
+        );
+    }
+
+    let mut summary = Summary::default();
+    for stream_path in read_numeric_dirs(&streams_path).await? {
+        for topic_path in read_numeric_dirs(&stream_path.join("topics")).await? {
+            for partition_path in read_numeric_dirs(&topic_path.join("partitions")).await? {
+                check_partition(&partition_path, args.repair, &mut summary).await?;
+            }
+        }
+    }
+
+    info!(
+        "Checked {} partitions, {} segments and {} messages, found {} offset gaps.",
+        summary.partitions_count,
+        summary.segments_count,
+        summary.messages_count,
+        summary.gaps_count
+    );
+    if summary.invalid_segments_count == 0 {
+        info!("All the segments are valid.");
+        return Ok(());
+    }
+
+    if summary.repaired_segments_count == summary.invalid_segments_count {
+        info!(
+            "Repaired {} invalid segments, removed {} bytes of the torn writes, quarantined the invalid data of {} segments.",
+            summary.repaired_segments_count, summary.lost_bytes, summary.quarantined_segments_count
+        );
+        return Ok(());
+    }
+
+    bail!(
+        "Found {} invalid segments with {} bytes of the torn writes, use --repair to remove them and regenerate the indexes.",
+        summary.invalid_segments_count,
+        summary.lost_bytes
+    )
+}
+
+async fn check_partition(partition_path: &Path, repair: bool, summary: &mut Summary) -> Result<()> {
+    let mut start_offsets = Vec::new();
+    let mut dir_entries = tokio::fs::read_dir(partition_path)
+        .await
+        .with_context(|| format!("Failed to read partition: {}", partition_path.display()))?;
+    while let Some(dir_entry) = dir_entries.next_entry().await? {
+        let path = dir_entry.path();
+        if path
+            .extension()
+            .is_none_or(|extension| extension != LOG_EXTENSION)
+        {
+            continue;
+        }
+
+        let Some(start_offset) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<u64>().ok())
+        else {
+            warn!("Skipping unexpected log file: {}", path.display());
+            continue;
+        };
+        start_offsets.push(start_offset);
+    }
+
+    start_offsets.sort_unstable();
+    summary.partitions_count += 1;
+    info!(
+        "Checking partition: {} with {} segments...",
+        partition_path.display(),
+        start_offsets.len()
+    );
+    let mut previous_offset = None;
+    let segments_count = start_offsets.len();
+    for (position, start_offset) in start_offsets.into_iter().enumerate() {
+        let segment_path = partition_path.join(format!("{start_offset:0>20}"));
+        let log_path = format!("{}.{LOG_EXTENSION}", segment_path.display());
+        let index_path = format!("{}.{INDEX_EXTENSION}", segment_path.display());
+        let report = check_segment(&log_path, &index_path, start_offset, previous_offset)
+            .await
+            .with_context(|| format!("Failed to check segment: {log_path}"))?;
+        print_report(&report);
+        summary.segments_count += 1;
+        summary.messages_count += report.messages_count;
+        summary.gaps_count += report.gaps.len() as u64;
+        previous_offset = report.last_offset;
+        if report.is_valid() {
+            continue;
+        }
+
+        summary.invalid_segments_count += 1;
+        summary.lost_bytes += report.lost_bytes();
+        if !repair {
+            continue;
+        }
+
+        match repair_segment(&report, position + 1 == segments_count).await {
+            Ok(TailRepair::Quarantined(quarantine_path)) => {
+                summary.repaired_segments_count += 1;
+                summary.quarantined_segments_count += 1;
+                warn!("Repaired segment: {log_path}, the invalid data was moved to: {quarantine_path}");
+            }
+            Ok(_) => {
+                summary.repaired_segments_count += 1;
+                info!("Repaired segment: {log_path}");
+            }
+            Err(error) => error!("Failed to repair segment: {log_path}, {error}"),
+        }
+    }
+
+    Ok(())
+}
+
+fn print_report(report: &SegmentReport) {
+    let offsets = match (report.first_offset, report.messages_count) {
+        (Some(first_offset), count) if count > 0 => format!(
+            "offsets: {first_offset}..={}",
+            report.last_offset.unwrap_or(first_offset)
+        ),
+        _ => "no messages".to_owned(),
+    };
+    info!(
+        "Segment: {}, size: {} bytes, batches: {}, messages: {}, {offsets}",
+        report.log_path, report.log_size, report.batches_count, report.messages_count
+    );
+    if !report.gaps.is_empty() {
+        let gaps = report
+            .gaps
+            .iter()
+            .take(MAX_PRINTED_GAPS)
+            .map(|gap| gap.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        warn!(
+            "Segment: {} has {} offset gaps (expected for the compacted topics): {gaps}{}",
+            report.log_path,
+            report.gaps.len(),
+            if report.gaps.len() > MAX_PRINTED_GAPS {
+                ", ..."
+            } else {
+                ""
+            }
+        );
+    }
+
+    if let Some(torn_tail) = &report.torn_tail {
+        error!(
+            "Segment: {} has {} bytes of the torn write, {torn_tail}",
+            report.log_path,
+            report.lost_bytes()
+        );
+    }
+
+    if let Some(index_problem) = &report.index_problem {
+        error!("Index: {} is invalid, {index_problem}", report.index_path);
+    }
+}
+
+/// Returns the subdirectories named by the numeric IDs (streams, topics or partitions), sorted by ID.
+async fn read_numeric_dirs(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut dirs = Vec::new();
+    let mut dir_entries = tokio::fs::read_dir(path)
+        .await
+        .with_context(|| format!("Failed to read directory: {}", path.display()))?;
+    while let Some(dir_entry) = dir_entries.next_entry().await? {
+        if !dir_entry.file_type().await?.is_dir() {
+            continue;
+        }
+
+        if let Some(id) = dir_entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        {
+            dirs.push((id, dir_entry.path()));
+        }
+    }
+
+    dirs.sort_unstable_by_key(|(id, _)| *id);
+    Ok(dirs.into_iter().map(|(_, path)| path).collect())
+}
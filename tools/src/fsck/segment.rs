@@ -0,0 +1,604 @@
+use bytes::Bytes;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::checksum;
+use server::compat::index_rebuilding::index_rebuilder::IndexRebuilder;
+use server::streaming::batching::message_batch::{RetainedMessageBatch, RETAINED_BATCH_HEADER_LEN};
+use server::streaming::models::messages::RetainedMessage;
+use server::streaming::segments::Index;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::{ErrorKind, SeekFrom};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader};
+
+/// Size of the index entry: relative offset, position and timestamp.
+const INDEX_SIZE: u64 = 4 + 4 + 8;
+/// Extension appended to the log path for the file holding the invalid data removed from the log.
+const QUARANTINE_EXTENSION: &str = "quarantine";
+/// Size of the message fields preceding the headers: offset, state, timestamp, ID, checksum and headers length.
+const MESSAGE_HEADER_SIZE: usize = 8 + 1 + 8 + 16 + 4 + 4;
+
+/// Result of checking a single segment, everything from the `valid_size` position of the log onwards is invalid.
+#[derive(Debug, Default)]
+pub struct SegmentReport {
+    pub log_path: String,
+    pub index_path: String,
+    pub start_offset: u64,
+    pub log_size: u64,
+    pub valid_size: u64,
+    pub batches_count: u64,
+    pub messages_count: u64,
+    pub first_offset: Option<u64>,
+    pub last_offset: Option<u64>,
+    pub gaps: Vec<OffsetGap>,
+    pub torn_tail: Option<TornTail>,
+    pub index_problem: Option<String>,
+}
+
+/// Offsets missing between two consecutive messages, expected for the compacted topics.
+#[derive(Debug, Clone, Copy)]
+pub struct OffsetGap {
+    pub from: u64,
+    pub to: u64,
+}
+
+/// Invalid data found at the end of the log, typically left by a write interrupted by an unclean shutdown.
+/// The partial write is the incomplete batch ending with the log, any other invalid data means a corruption.
+#[derive(Debug)]
+pub struct TornTail {
+    pub position: u64,
+    pub reason: String,
+    pub offsets: Option<(u64, u64)>,
+    pub partial_write: bool,
+}
+
+/// The action taken to repair the torn tail of the log.
+#[derive(Debug, PartialEq)]
+pub enum TailRepair {
+    None,
+    Truncated,
+    Quarantined(String),
+}
+
+/// Batch read from the log with the position at which it starts.
+struct ValidatedBatch {
+    position: u64,
+    size: u64,
+    last_offset: u64,
+    max_timestamp: u64,
+}
+
+impl SegmentReport {
+    pub fn is_valid(&self) -> bool {
+        self.torn_tail.is_none() && self.index_problem.is_none()
+    }
+
+    pub fn lost_bytes(&self) -> u64 {
+        self.log_size - self.valid_size
+    }
+}
+
+impl Display for OffsetGap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.from == self.to {
+            write!(f, "{}", self.from)
+        } else {
+            write!(f, "{}..={}", self.from, self.to)
+        }
+    }
+}
+
+impl Display for TornTail {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "position: {}, reason: {}, partial write: {}",
+            self.position, self.reason, self.partial_write
+        )?;
+        if let Some((from, to)) = self.offsets {
+            write!(f, ", lost offsets: {from}..={to}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates every batch and message checksum of the segment log and compares the index against it.
+/// The `previous_offset` is the last message offset of the preceding segment, used to detect the gaps between segments.
+pub async fn check_segment(
+    log_path: &str,
+    index_path: &str,
+    start_offset: u64,
+    previous_offset: Option<u64>,
+) -> Result<SegmentReport, std::io::Error> {
+    let file = File::open(log_path).await?;
+    let log_size = file.metadata().await?.len();
+    let mut report = SegmentReport {
+        log_path: log_path.to_owned(),
+        index_path: index_path.to_owned(),
+        start_offset,
+        log_size,
+        last_offset: previous_offset,
+        ..Default::default()
+    };
+    let mut reader = BufReader::new(file);
+    let mut batches = Vec::new();
+    let mut position = 0;
+    while position < log_size {
+        match read_batch(&mut reader, position, log_size, &batches, &mut report).await? {
+            Ok(batch) => {
+                position += batch.size;
+                batches.push(batch);
+            }
+            Err(torn_tail) => {
+                report.torn_tail = Some(torn_tail);
+                break;
+            }
+        }
+    }
+
+    report.valid_size = position;
+    report.batches_count = batches.len() as u64;
+    report.index_problem = check_index(index_path, start_offset, &batches).await?;
+    Ok(report)
+}
+
+/// Removes the torn tail of the log (if any) and regenerates the index based on the remaining batches.
+/// Only the partial write at the end of the last segment (the only one being appended to) is truncated,
+/// any other invalid data is moved to the quarantine file next to the log, so that it can be inspected or restored.
+pub async fn repair_segment(
+    report: &SegmentReport,
+    is_last_segment: bool,
+) -> Result<TailRepair, String> {
+    let tail_repair = match &report.torn_tail {
+        None => TailRepair::None,
+        Some(torn_tail) if torn_tail.partial_write && is_last_segment => {
+            truncate_log(report).await?;
+            TailRepair::Truncated
+        }
+        Some(_) => {
+            let quarantine_path = get_quarantine_path(&report.log_path);
+            quarantine_tail(report, &quarantine_path).await?;
+            truncate_log(report).await?;
+            TailRepair::Quarantined(quarantine_path)
+        }
+    };
+
+    IndexRebuilder::new(
+        report.log_path.clone(),
+        report.index_path.clone(),
+        report.start_offset,
+    )
+    .rebuild()
+    .await
+    .map_err(|error| format!("failed to rebuild index: {}, {error}", report.index_path))?;
+    Ok(tail_repair)
+}
+
+pub fn get_quarantine_path(log_path: &str) -> String {
+    format!("{log_path}.{QUARANTINE_EXTENSION}")
+}
+
+/// Copies the invalid data to the new quarantine file, the existing one is never overwritten.
+async fn quarantine_tail(report: &SegmentReport, quarantine_path: &str) -> Result<(), String> {
+    let mut log = File::open(&report.log_path)
+        .await
+        .map_err(|error| format!("failed to open log: {}, {error}", report.log_path))?;
+    log.seek(SeekFrom::Start(report.valid_size))
+        .await
+        .map_err(|error| format!("failed to seek log: {}, {error}", report.log_path))?;
+    let mut tail = Vec::with_capacity(report.lost_bytes() as usize);
+    log.read_to_end(&mut tail)
+        .await
+        .map_err(|error| format!("failed to read log: {}, {error}", report.log_path))?;
+    let mut quarantine = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(quarantine_path)
+        .await
+        .map_err(|error| format!("failed to create quarantine file: {quarantine_path}, {error}"))?;
+    quarantine
+        .write_all(&tail)
+        .await
+        .map_err(|error| format!("failed to write quarantine file: {quarantine_path}, {error}"))?;
+    quarantine
+        .sync_all()
+        .await
+        .map_err(|error| format!("failed to sync quarantine file: {quarantine_path}, {error}"))
+}
+
+async fn truncate_log(report: &SegmentReport) -> Result<(), String> {
+    let file = OpenOptions::new()
+        .write(true)
+        .open(&report.log_path)
+        .await
+        .map_err(|error| format!("failed to open log: {}, {error}", report.log_path))?;
+    file.set_len(report.valid_size)
+        .await
+        .map_err(|error| format!("failed to truncate log: {}, {error}", report.log_path))?;
+    file.sync_all()
+        .await
+        .map_err(|error| format!("failed to sync log: {}, {error}", report.log_path))
+}
+
+async fn read_batch(
+    reader: &mut BufReader<File>,
+    position: u64,
+    log_size: u64,
+    batches: &[ValidatedBatch],
+    report: &mut SegmentReport,
+) -> Result<Result<ValidatedBatch, TornTail>, std::io::Error> {
+    let torn_tail = |reason: String, offsets: Option<(u64, u64)>, partial_write: bool| {
+        Ok(Err(TornTail {
+            position,
+            reason,
+            offsets,
+            partial_write,
+        }))
+    };
+    if log_size - position < RETAINED_BATCH_HEADER_LEN {
+        return torn_tail(
+            format!(
+                "incomplete batch header, expected {RETAINED_BATCH_HEADER_LEN} bytes, got: {}",
+                log_size - position
+            ),
+            None,
+            true,
+        );
+    }
+
+    // The base offset isn't reliable (it's reset once the accumulated batch is persisted),
+    // so the batch boundaries are based on the last offsets, just like the indexes are.
+    let base_offset = reader.read_u64_le().await?;
+    let length = reader.read_u32_le().await? as u64;
+    let last_offset_delta = reader.read_u32_le().await?;
+    let max_timestamp = reader.read_u64_le().await?;
+    let first_offset = batches
+        .last()
+        .map_or(report.start_offset, |batch| batch.last_offset + 1);
+    let last_offset = base_offset.saturating_add(last_offset_delta as u64);
+    let offsets = Some((first_offset, last_offset));
+    if log_size - position - RETAINED_BATCH_HEADER_LEN < length {
+        return torn_tail(
+            format!(
+                "incomplete batch payload, expected {length} bytes, got: {}",
+                log_size - position - RETAINED_BATCH_HEADER_LEN
+            ),
+            offsets,
+            true,
+        );
+    }
+
+    let mut payload = vec![0; length as usize];
+    match reader.read_exact(&mut payload).await {
+        Ok(_) => {}
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => {
+            return torn_tail("unexpected end of the log".to_owned(), offsets, true);
+        }
+        Err(error) => return Err(error),
+    }
+
+    if last_offset < first_offset {
+        return torn_tail(
+            format!("batch last offset: {last_offset} is lower than the expected first offset: {first_offset}"),
+            None,
+            false,
+        );
+    }
+
+    let batch = match RetainedMessageBatch::new(
+        base_offset,
+        last_offset_delta,
+        max_timestamp,
+        IggyByteSize::from(length),
+        Bytes::from(payload),
+    )
+    .decompress()
+    {
+        Ok(batch) => batch,
+        Err(error) => {
+            return torn_tail(format!("cannot decompress batch, {error}"), offsets, false)
+        }
+    };
+
+    let messages = match read_messages(&batch.bytes, first_offset, last_offset) {
+        Ok(messages) => messages,
+        Err(reason) => return torn_tail(reason, offsets, false),
+    };
+
+    let mut last_message_offset = report.last_offset;
+    let mut gaps = Vec::new();
+    for message in &messages {
+        if let Some(last_message_offset) = last_message_offset {
+            if message.offset > last_message_offset + 1 {
+                gaps.push(OffsetGap {
+                    from: last_message_offset + 1,
+                    to: message.offset - 1,
+                });
+            }
+        }
+        last_message_offset = Some(message.offset);
+    }
+
+    report.gaps.extend(gaps);
+    report.messages_count += messages.len() as u64;
+    if report.first_offset.is_none() {
+        report.first_offset = messages.first().map(|message| message.offset);
+    }
+    report.last_offset = last_message_offset;
+    Ok(Ok(ValidatedBatch {
+        position,
+        size: RETAINED_BATCH_HEADER_LEN + length,
+        last_offset,
+        max_timestamp,
+    }))
+}
+
+fn read_messages(
+    bytes: &Bytes,
+    first_offset: u64,
+    last_offset: u64,
+) -> Result<Vec<RetainedMessage>, String> {
+    let mut messages: Vec<RetainedMessage> = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        if bytes.len() - position < 4 {
+            return Err(format!(
+                "incomplete message length at batch position: {position}"
+            ));
+        }
+
+        let length = u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap()) as usize;
+        let start = position + 4;
+        if bytes.len() - start < length || length < MESSAGE_HEADER_SIZE {
+            return Err(format!(
+                "invalid message length: {length} at batch position: {position}"
+            ));
+        }
+
+        let message_bytes = bytes.slice(start..start + length);
+        let headers_length = u32::from_le_bytes(message_bytes[37..41].try_into().unwrap()) as usize;
+        if length - MESSAGE_HEADER_SIZE < headers_length {
+            return Err(format!(
+                "invalid message headers length: {headers_length} at batch position: {position}"
+            ));
+        }
+
+        let message = RetainedMessage::try_from_bytes(message_bytes).map_err(|error| {
+            format!("cannot parse message at batch position: {position}, {error}")
+        })?;
+        if message.offset < first_offset || message.offset > last_offset {
+            return Err(format!(
+                "message offset: {} is outside of the batch offsets: {first_offset}..={last_offset}",
+                message.offset
+            ));
+        }
+
+        if let Some(previous_message) = messages.last() {
+            if message.offset <= previous_message.offset {
+                return Err(format!(
+                    "message offset: {} is not greater than the previous one: {}",
+                    message.offset, previous_message.offset
+                ));
+            }
+        }
+
+        if let Some(headers) = &message.headers {
+            HashMap::<HeaderKey, HeaderValue>::from_bytes(headers.clone()).map_err(|error| {
+                format!(
+                    "cannot parse headers of message with offset: {}, {error}",
+                    message.offset
+                )
+            })?;
+        }
+
+        let calculated_checksum = checksum::calculate(&message.payload);
+        if calculated_checksum != message.checksum {
+            return Err(format!(
+                "invalid checksum of message with offset: {}, expected: {}, calculated: {calculated_checksum}",
+                message.offset, message.checksum
+            ));
+        }
+
+        messages.push(message);
+        position = start + length;
+    }
+
+    Ok(messages)
+}
+
+/// Compares the index with the entries expected for the valid batches, returns the first found problem.
+async fn check_index(
+    index_path: &str,
+    start_offset: u64,
+    batches: &[ValidatedBatch],
+) -> Result<Option<String>, std::io::Error> {
+    let bytes = match tokio::fs::read(index_path).await {
+        Ok(bytes) => bytes,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            return Ok(Some("index does not exist".to_owned()));
+        }
+        Err(error) => return Err(error),
+    };
+
+    let index_size = bytes.len() as u64;
+    let indexes_count = index_size / INDEX_SIZE;
+    for (position, batch) in batches.iter().enumerate() {
+        let expected = Index {
+            offset: (batch.last_offset - start_offset) as u32,
+            position: batch.position as u32,
+            timestamp: batch.max_timestamp,
+        };
+        if position as u64 >= indexes_count {
+            return Ok(Some(format!(
+                "index is missing {} entries, starting from offset: {}",
+                batches.len() as u64 - indexes_count,
+                batch.last_offset
+            )));
+        }
+
+        let entry = &bytes[position * INDEX_SIZE as usize..(position + 1) * INDEX_SIZE as usize];
+        let actual = Index {
+            offset: u32::from_le_bytes(entry[0..4].try_into().unwrap()),
+            position: u32::from_le_bytes(entry[4..8].try_into().unwrap()),
+            timestamp: u64::from_le_bytes(entry[8..16].try_into().unwrap()),
+        };
+        if actual != expected {
+            return Ok(Some(format!(
+                "index entry: {position} is {actual:?}, expected: {expected:?}"
+            )));
+        }
+    }
+
+    if indexes_count > batches.len() as u64 {
+        return Ok(Some(format!(
+            "index has {} entries more than the valid batches in the log",
+            indexes_count - batches.len() as u64
+        )));
+    }
+
+    if !index_size.is_multiple_of(INDEX_SIZE) {
+        return Ok(Some(format!(
+            "index has {} bytes of incomplete entry at the end",
+            index_size % INDEX_SIZE
+        )));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+    use iggy::messages::send_messages::Message;
+    use server::streaming::segments::{INDEX_EXTENSION, LOG_EXTENSION};
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn partial_write_of_last_segment_should_be_truncated() {
+        let temp_dir = TempDir::new().unwrap();
+        let (log_path, index_path) = create_segment(&temp_dir, &[0, 1]);
+        let valid_size = std::fs::metadata(&log_path).unwrap().len();
+        let mut log = std::fs::read(&log_path).unwrap();
+        log.extend(&create_batch(2, 3)[..30]);
+        std::fs::write(&log_path, &log).unwrap();
+
+        let report = check_segment(&log_path, &index_path, 0, None)
+            .await
+            .unwrap();
+        assert!(report.torn_tail.as_ref().unwrap().partial_write);
+        assert_eq!(report.valid_size, valid_size);
+
+        let tail_repair = repair_segment(&report, true).await.unwrap();
+        assert_eq!(tail_repair, TailRepair::Truncated);
+        assert!(!Path::new(&get_quarantine_path(&log_path)).exists());
+        let report = check_segment(&log_path, &index_path, 0, None)
+            .await
+            .unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.log_size, valid_size);
+        assert_eq!(report.messages_count, 2);
+    }
+
+    #[tokio::test]
+    async fn corrupted_data_should_be_quarantined() {
+        let temp_dir = TempDir::new().unwrap();
+        let (log_path, index_path) = create_segment(&temp_dir, &[0, 2]);
+        let first_batch_size = create_batch(0, 1).len();
+        let mut log = std::fs::read(&log_path).unwrap();
+        let last_byte = log.len() - 1;
+        log[last_byte] ^= 0xFF;
+        std::fs::write(&log_path, &log).unwrap();
+
+        let report = check_segment(&log_path, &index_path, 0, None)
+            .await
+            .unwrap();
+        assert!(!report.torn_tail.as_ref().unwrap().partial_write);
+        assert_eq!(report.valid_size, first_batch_size as u64);
+
+        let tail_repair = repair_segment(&report, true).await.unwrap();
+        let quarantine_path = get_quarantine_path(&log_path);
+        assert_eq!(
+            tail_repair,
+            TailRepair::Quarantined(quarantine_path.clone())
+        );
+        assert_eq!(
+            std::fs::read(&quarantine_path).unwrap(),
+            log[first_batch_size..]
+        );
+        let report = check_segment(&log_path, &index_path, 0, None)
+            .await
+            .unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.messages_count, 2);
+    }
+
+    #[tokio::test]
+    async fn partial_write_of_closed_segment_should_be_quarantined() {
+        let temp_dir = TempDir::new().unwrap();
+        let (log_path, index_path) = create_segment(&temp_dir, &[0]);
+        let mut log = std::fs::read(&log_path).unwrap();
+        log.extend(&create_batch(1, 2)[..10]);
+        std::fs::write(&log_path, &log).unwrap();
+
+        let report = check_segment(&log_path, &index_path, 0, None)
+            .await
+            .unwrap();
+        assert!(report.torn_tail.as_ref().unwrap().partial_write);
+
+        let tail_repair = repair_segment(&report, false).await.unwrap();
+        assert_eq!(
+            tail_repair,
+            TailRepair::Quarantined(get_quarantine_path(&log_path))
+        );
+        assert!(repair_segment(&report, false).await.is_err());
+    }
+
+    /// Creates the segment with the batches starting at the given offsets, each batch contains
+    /// the messages up to the next base offset, while the last one contains the single message.
+    fn create_segment(temp_dir: &TempDir, base_offsets: &[u64]) -> (String, String) {
+        let segment_path = temp_dir.path().join(format!("{:0>20}", 0));
+        let log_path = format!("{}.{LOG_EXTENSION}", segment_path.display());
+        let index_path = format!("{}.{INDEX_EXTENSION}", segment_path.display());
+        let mut log = Vec::new();
+        let mut index = Vec::new();
+        for (position, base_offset) in base_offsets.iter().enumerate() {
+            let last_offset = match base_offsets.get(position + 1) {
+                Some(next_base_offset) => next_base_offset - 1,
+                None => *base_offset,
+            };
+            index.extend((last_offset as u32).to_le_bytes());
+            index.extend((log.len() as u32).to_le_bytes());
+            index.extend(last_offset.to_le_bytes());
+            log.extend(create_batch(*base_offset, last_offset));
+        }
+        std::fs::write(&log_path, log).unwrap();
+        std::fs::write(&index_path, index).unwrap();
+        (log_path, index_path)
+    }
+
+    fn create_batch(base_offset: u64, last_offset: u64) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        for offset in base_offset..=last_offset {
+            RetainedMessage::new(
+                offset,
+                offset,
+                Message::new(None, Bytes::from(format!("message-{offset}")), None),
+            )
+            .extend(&mut bytes);
+        }
+        let batch = RetainedMessageBatch::new(
+            base_offset,
+            (last_offset - base_offset) as u32,
+            last_offset,
+            IggyByteSize::from(bytes.len() as u64),
+            bytes.freeze(),
+        );
+        let mut batch_bytes = batch.header_as_bytes().to_vec();
+        batch_bytes.extend(&batch.bytes);
+        batch_bytes
+    }
+}
@@ -0,0 +1,356 @@
+use bytes::Bytes;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::utils::crypto::EncryptorKind;
+use iggy::utils::timestamp::IggyTimestamp;
+use serde::{Deserialize, Serialize};
+use serde_with::base64::Base64;
+use serde_with::serde_as;
+use server::state::command::EntryCommand;
+use server::state::entry::StateEntry;
+use std::fmt::{Display, Formatter};
+
+/// State entry decoded from the log, with the command decrypted (if needed) and parsed.
+#[derive(Debug)]
+pub struct LogEntry {
+    pub size: u64,
+    pub entry: StateEntry,
+    pub command: EntryCommand,
+}
+
+/// Describes the first invalid entry found in the log, everything from the offset onwards is considered corrupted.
+#[derive(Debug)]
+pub struct Corruption {
+    pub offset: u64,
+    pub reason: String,
+}
+
+/// Result of reading the log, all the entries preceding the corruption (if any) are valid.
+#[derive(Debug)]
+pub struct StateLog {
+    pub entries: Vec<LogEntry>,
+    pub size: u64,
+    pub corruption: Option<Corruption>,
+}
+
+/// JSON representation of the state entry used for printing, exporting and importing the log.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntryRecord {
+    pub index: u64,
+    pub term: u64,
+    pub leader_id: u32,
+    pub version: u32,
+    pub flags: u64,
+    pub timestamp: IggyTimestamp,
+    pub user_id: u32,
+    pub checksum: u32,
+    #[serde_as(as = "Base64")]
+    pub context: Vec<u8>,
+    pub command: EntryCommand,
+}
+
+impl Display for Corruption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "offset: {}, reason: {}", self.offset, self.reason)
+    }
+}
+
+impl StateLog {
+    /// Decodes the entries until the end of the log or the first invalid entry.
+    pub fn read(bytes: Bytes, encryptor: Option<&EncryptorKind>) -> Self {
+        let size = bytes.len() as u64;
+        let mut entries: Vec<LogEntry> = Vec::new();
+        let mut offset = 0;
+        let mut corruption = None;
+        while offset < bytes.len() {
+            let expected_index = entries.last().map(|entry| entry.entry.index + 1);
+            match read_entry(&bytes, offset, expected_index, encryptor) {
+                Ok(entry) => {
+                    offset += entry.size as usize;
+                    entries.push(entry);
+                }
+                Err(reason) => {
+                    corruption = Some(Corruption {
+                        offset: offset as u64,
+                        reason,
+                    });
+                    break;
+                }
+            }
+        }
+
+        Self {
+            entries,
+            size,
+            corruption,
+        }
+    }
+
+    /// Returns the size of the log without the corrupted tail.
+    pub fn valid_size(&self) -> u64 {
+        self.corruption
+            .as_ref()
+            .map_or(self.size, |corruption| corruption.offset)
+    }
+}
+
+impl From<LogEntry> for EntryRecord {
+    fn from(log_entry: LogEntry) -> Self {
+        let entry = log_entry.entry;
+        Self {
+            index: entry.index,
+            term: entry.term,
+            leader_id: entry.leader_id,
+            version: entry.version,
+            flags: entry.flags,
+            timestamp: entry.timestamp,
+            user_id: entry.user_id,
+            checksum: entry.checksum,
+            context: entry.context.to_vec(),
+            command: log_entry.command,
+        }
+    }
+}
+
+impl EntryRecord {
+    /// Builds the state entry with the checksum calculated for the current content of the record.
+    pub fn to_entry(&self) -> StateEntry {
+        let context = Bytes::from(self.context.clone());
+        let command = self.command.to_bytes();
+        let checksum = StateEntry::calculate_checksum(
+            self.index,
+            self.term,
+            self.leader_id,
+            self.version,
+            self.flags,
+            self.timestamp,
+            self.user_id,
+            &context,
+            &command,
+        );
+        StateEntry::new(
+            self.index,
+            self.term,
+            self.leader_id,
+            self.version,
+            self.flags,
+            self.timestamp,
+            self.user_id,
+            checksum,
+            context,
+            command,
+        )
+    }
+}
+
+/// Serializes the entry in the same format as the server does, the command payload is encrypted if the encryptor is provided.
+pub fn write_entry(entry: &StateEntry, encryptor: Option<&EncryptorKind>) -> Result<Bytes, String> {
+    let Some(encryptor) = encryptor else {
+        return Ok(entry.to_bytes());
+    };
+
+    let command = StateEntry::encrypt_command(&entry.command, encryptor)
+        .map_err(|error| format!("failed to encrypt command: {error}"))?;
+    let encrypted_entry = StateEntry::new(
+        entry.index,
+        entry.term,
+        entry.leader_id,
+        entry.version,
+        entry.flags,
+        entry.timestamp,
+        entry.user_id,
+        entry.checksum,
+        entry.context.clone(),
+        command,
+    );
+    Ok(encrypted_entry.to_bytes())
+}
+
+fn read_entry(
+    bytes: &Bytes,
+    offset: usize,
+    expected_index: Option<u64>,
+    encryptor: Option<&EncryptorKind>,
+) -> Result<LogEntry, String> {
+    let entry = StateEntry::from_bytes(bytes.slice(offset..))
+        .map_err(|error| format!("failed to read entry, {error}"))?;
+    let index = entry.index;
+    if let Some(expected_index) = expected_index {
+        if index != expected_index {
+            return Err(format!("expected index: {expected_index}, got: {index}"));
+        }
+    }
+
+    let size = entry.get_size_bytes() as u64;
+    let command = match encryptor {
+        Some(encryptor) => {
+            StateEntry::decrypt_command(&entry.command, encryptor).map_err(|error| {
+                format!("failed to decrypt command of entry with index: {index}, {error}")
+            })?
+        }
+        None => entry.command.clone(),
+    };
+    let calculated_checksum = StateEntry::calculate_checksum(
+        index,
+        entry.term,
+        entry.leader_id,
+        entry.version,
+        entry.flags,
+        entry.timestamp,
+        entry.user_id,
+        &entry.context,
+        &command,
+    );
+    if calculated_checksum != entry.checksum {
+        return Err(format!(
+            "invalid checksum of entry with index: {index}, expected: {}, calculated: {calculated_checksum}",
+            entry.checksum
+        ));
+    }
+
+    let entry_command = EntryCommand::from_bytes(command.clone()).map_err(|error| {
+        format!("failed to parse command of entry with index: {index}, {error}")
+    })?;
+    Ok(LogEntry {
+        size,
+        entry: StateEntry::new(
+            index,
+            entry.term,
+            entry.leader_id,
+            entry.version,
+            entry.flags,
+            entry.timestamp,
+            entry.user_id,
+            entry.checksum,
+            entry.context,
+            command,
+        ),
+        command: entry_command,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+    use iggy::streams::create_stream::CreateStream;
+    use iggy::users::create_user::CreateUser;
+    use iggy::utils::crypto::Aes256GcmEncryptor;
+    use server::state::file::FileState;
+    use server::state::State;
+    use server::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
+    use server::versioning::SemanticVersion;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn state_log_should_be_read_and_written_back_unchanged() {
+        let temp_dir = TempDir::new().unwrap();
+        let (log, commands) = create_state_log(&temp_dir, None).await;
+
+        let state_log = StateLog::read(log.clone(), None);
+        assert!(state_log.corruption.is_none());
+        assert_eq!(state_log.valid_size(), log.len() as u64);
+        assert_commands(&state_log, &commands);
+
+        let mut bytes = BytesMut::new();
+        for entry in state_log.entries {
+            let record = EntryRecord::from(entry);
+            let json = serde_json::to_string(&record).unwrap();
+            let record: EntryRecord = serde_json::from_str(&json).unwrap();
+            bytes.extend(write_entry(&record.to_entry(), None).unwrap());
+        }
+        assert_eq!(bytes.freeze(), log);
+    }
+
+    #[tokio::test]
+    async fn encrypted_state_log_should_be_read_and_written_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = [1; 32];
+        let encryptor = EncryptorKind::Aes256Gcm(Aes256GcmEncryptor::new(&key).unwrap());
+        let (log, commands) = create_state_log(&temp_dir, Some(&key)).await;
+
+        let state_log = StateLog::read(log.clone(), Some(&encryptor));
+        assert!(state_log.corruption.is_none());
+        assert_commands(&state_log, &commands);
+
+        let mut bytes = BytesMut::new();
+        for entry in &state_log.entries {
+            bytes.extend(write_entry(&entry.entry, Some(&encryptor)).unwrap());
+        }
+        let written_log = StateLog::read(bytes.freeze(), Some(&encryptor));
+        assert!(written_log.corruption.is_none());
+        assert_commands(&written_log, &commands);
+        let invalid_log = StateLog::read(log, None);
+        assert_eq!(invalid_log.corruption.unwrap().offset, 0);
+    }
+
+    #[tokio::test]
+    async fn truncated_entry_should_be_reported_as_corruption() {
+        let temp_dir = TempDir::new().unwrap();
+        let (log, commands) = create_state_log(&temp_dir, None).await;
+        let last_entry_size = StateLog::read(log.clone(), None)
+            .entries
+            .last()
+            .unwrap()
+            .size as usize;
+        let valid_size = log.len() - last_entry_size;
+
+        for size in [valid_size + 10, log.len() - 1] {
+            let state_log = StateLog::read(log.slice(..size), None);
+            assert_eq!(state_log.entries.len(), commands.len() - 1);
+            assert_eq!(state_log.valid_size(), valid_size as u64);
+        }
+    }
+
+    async fn create_state_log(
+        temp_dir: &TempDir,
+        encryption_key: Option<&[u8]>,
+    ) -> (Bytes, Vec<EntryCommand>) {
+        let log_path = temp_dir.path().join("log");
+        let snapshot_path = temp_dir.path().join("snapshot");
+        let encryptor = encryption_key.map(|key| {
+            Arc::new(EncryptorKind::Aes256Gcm(
+                Aes256GcmEncryptor::new(key).unwrap(),
+            ))
+        });
+        let state = FileState::new(
+            log_path.to_str().unwrap(),
+            snapshot_path.to_str().unwrap(),
+            &SemanticVersion::from_str("1.2.3").unwrap(),
+            Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {})),
+            encryptor,
+        );
+        state.init().await.unwrap();
+        let commands = vec![
+            EntryCommand::CreateStream(CreateStream {
+                stream_id: Some(1),
+                name: "stream".to_string(),
+            }),
+            EntryCommand::CreateUser(CreateUser {
+                username: "user".to_string(),
+                password: "secret".to_string(),
+                status: Default::default(),
+                permissions: None,
+            }),
+        ];
+        for command in &commands {
+            state
+                .apply(1, EntryCommand::from_bytes(command.to_bytes()).unwrap())
+                .await
+                .unwrap();
+        }
+
+        let log = Bytes::from(std::fs::read(log_path).unwrap());
+        (log, commands)
+    }
+
+    fn assert_commands(state_log: &StateLog, commands: &[EntryCommand]) {
+        assert_eq!(state_log.entries.len(), commands.len());
+        for (index, (entry, command)) in state_log.entries.iter().zip(commands).enumerate() {
+            assert_eq!(entry.entry.index, index as u64);
+            assert_eq!(entry.command.to_bytes(), command.to_bytes());
+        }
+    }
+}
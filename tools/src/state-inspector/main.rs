@@ -0,0 +1,261 @@
+mod log;
+
+use crate::log::{write_entry, EntryRecord, StateLog};
+use anyhow::{bail, Context, Result};
+use bytes::{Bytes, BytesMut};
+use clap::{Args, Parser, Subcommand};
+use iggy::utils::crypto::{Aes256GcmEncryptor, EncryptorKind};
+use std::path::Path;
+use tracing::{info, warn};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Registry};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Inspects and repairs the server state log offline", long_about = None)]
+pub struct StateInspectorArgs {
+    #[command(subcommand)]
+    pub command: StateInspectorCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum StateInspectorCommand {
+    /// Print the state log entries as JSON, one entry per line
+    Inspect(InspectArgs),
+    /// Verify the checksums and indexes of the state log entries
+    Verify(LogArgs),
+    /// Truncate the corrupted tail of the state log, keeping all the valid entries
+    Truncate(TruncateArgs),
+    /// Export the valid state log entries to the JSON file
+    Export(ExportArgs),
+    /// Import the state log entries from the JSON file created by the export command
+    Import(ImportArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct LogArgs {
+    /// Path to the state log file
+    #[arg(long, default_value = "local_data/state/log")]
+    pub path: String,
+
+    /// Base64 encoded key used by the server to encrypt the state log (if any)
+    #[arg(long, default_value = "")]
+    pub encryption_key: String,
+}
+
+#[derive(Args, Debug)]
+pub struct InspectArgs {
+    #[clap(flatten)]
+    pub log: LogArgs,
+
+    /// Print each entry as the pretty formatted JSON
+    #[arg(long, default_value_t = false)]
+    pub pretty: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct TruncateArgs {
+    #[clap(flatten)]
+    pub log: LogArgs,
+
+    /// Skip copying the original state log to the `<path>.bak` file before truncating it
+    #[arg(long, default_value_t = false)]
+    pub no_backup: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    #[clap(flatten)]
+    pub log: LogArgs,
+
+    /// Path to the JSON file to which the entries will be exported
+    #[arg(long)]
+    pub output: String,
+}
+
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    #[clap(flatten)]
+    pub log: LogArgs,
+
+    /// Path to the JSON file from which the entries will be imported
+    #[arg(long)]
+    pub input: String,
+
+    /// Overwrite the state log if it already exists
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = StateInspectorArgs::parse();
+
+    // The entries are printed to stdout, so the logs go to stderr to keep the output parsable.
+    Registry::default()
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new("INFO")))
+        .init();
+
+    match args.command {
+        StateInspectorCommand::Inspect(args) => inspect(args).await,
+        StateInspectorCommand::Verify(args) => verify(args).await,
+        StateInspectorCommand::Truncate(args) => truncate(args).await,
+        StateInspectorCommand::Export(args) => export(args).await,
+        StateInspectorCommand::Import(args) => import(args).await,
+    }
+}
+
+async fn inspect(args: InspectArgs) -> Result<()> {
+    let log = read_log(&args.log).await?;
+    let corruption = log.corruption;
+    for entry in log.entries {
+        let record = EntryRecord::from(entry);
+        let json = match args.pretty {
+            true => serde_json::to_string_pretty(&record)?,
+            false => serde_json::to_string(&record)?,
+        };
+        println!("{json}");
+    }
+
+    if let Some(corruption) = corruption {
+        warn!("State log is corrupted, {corruption}");
+    }
+    Ok(())
+}
+
+async fn verify(args: LogArgs) -> Result<()> {
+    let log = read_log(&args).await?;
+    match &log.corruption {
+        Some(corruption) => bail!(
+            "State log is corrupted after {} valid entries, {corruption}. Use the truncate command to remove the {} corrupted bytes.",
+            log.entries.len(),
+            log.size - corruption.offset
+        ),
+        None => {
+            info!("State log is valid.");
+            Ok(())
+        }
+    }
+}
+
+async fn truncate(args: TruncateArgs) -> Result<()> {
+    let log = read_log(&args.log).await?;
+    let Some(corruption) = &log.corruption else {
+        info!("State log is valid, there is nothing to truncate.");
+        return Ok(());
+    };
+
+    let path = &args.log.path;
+    if !args.no_backup {
+        let backup_path = format!("{path}.bak");
+        tokio::fs::copy(path, &backup_path)
+            .await
+            .with_context(|| format!("Failed to back up the state log to: {backup_path}"))?;
+        info!("Backed up the state log to: {backup_path}");
+    }
+
+    let file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .await
+        .with_context(|| format!("Failed to open the state log: {path}"))?;
+    file.set_len(corruption.offset).await?;
+    file.sync_all().await?;
+    info!(
+        "Truncated the state log to {} bytes, removed {} corrupted bytes, {} valid entries remain.",
+        corruption.offset,
+        log.size - corruption.offset,
+        log.entries.len()
+    );
+    Ok(())
+}
+
+async fn export(args: ExportArgs) -> Result<()> {
+    let log = read_log(&args.log).await?;
+    if let Some(corruption) = &log.corruption {
+        warn!("State log is corrupted, only the valid entries will be exported, {corruption}");
+    }
+
+    let records = log
+        .entries
+        .into_iter()
+        .map(EntryRecord::from)
+        .collect::<Vec<_>>();
+    let json = serde_json::to_vec_pretty(&records)?;
+    tokio::fs::write(&args.output, json)
+        .await
+        .with_context(|| format!("Failed to write the exported entries to: {}", args.output))?;
+    info!("Exported {} entries to: {}", records.len(), args.output);
+    Ok(())
+}
+
+async fn import(args: ImportArgs) -> Result<()> {
+    let path = &args.log.path;
+    if Path::new(path).exists() && !args.force {
+        bail!("State log: {path} already exists, use --force to overwrite it.");
+    }
+
+    let json = tokio::fs::read(&args.input)
+        .await
+        .with_context(|| format!("Failed to read the entries from: {}", args.input))?;
+    let records: Vec<EntryRecord> = serde_json::from_slice(&json)
+        .with_context(|| format!("Failed to parse the entries from: {}", args.input))?;
+    let encryptor = get_encryptor(&args.log)?;
+    let mut bytes = BytesMut::new();
+    let mut previous_index = None;
+    for record in &records {
+        if let Some(previous_index) = previous_index {
+            if record.index != previous_index + 1 {
+                bail!(
+                    "Invalid entry index: {}, expected: {}",
+                    record.index,
+                    previous_index + 1
+                );
+            }
+        }
+
+        let entry = record.to_entry();
+        if entry.checksum != record.checksum {
+            warn!(
+                "Entry with index: {} was modified, checksum changed from: {} to: {}",
+                entry.index, record.checksum, entry.checksum
+            );
+        }
+
+        bytes.extend(write_entry(&entry, encryptor.as_ref()).map_err(anyhow::Error::msg)?);
+        previous_index = Some(record.index);
+    }
+
+    tokio::fs::write(path, bytes.freeze())
+        .await
+        .with_context(|| format!("Failed to write the state log: {path}"))?;
+    info!("Imported {} entries to: {path}", records.len());
+    Ok(())
+}
+
+async fn read_log(args: &LogArgs) -> Result<StateLog> {
+    let encryptor = get_encryptor(args)?;
+    let bytes = tokio::fs::read(&args.path)
+        .await
+        .with_context(|| format!("Failed to read the state log: {}", args.path))?;
+    let log = StateLog::read(Bytes::from(bytes), encryptor.as_ref());
+    info!(
+        "Read {} valid entries ({} of {} bytes) from the state log: {}",
+        log.entries.len(),
+        log.valid_size(),
+        log.size,
+        args.path
+    );
+    Ok(log)
+}
+
+fn get_encryptor(args: &LogArgs) -> Result<Option<EncryptorKind>> {
+    if args.encryption_key.is_empty() {
+        return Ok(None);
+    }
+
+    let encryptor = Aes256GcmEncryptor::from_base64_key(&args.encryption_key)
+        .context("Invalid encryption key")?;
+    Ok(Some(EncryptorKind::Aes256Gcm(encryptor)))
+}